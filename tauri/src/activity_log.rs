@@ -0,0 +1,84 @@
+//! Recent Actions Log
+//!
+//! Keeps a small in-memory log of recent actions (provider applied,
+//! backup finished, update available). Entries are shown in the tray
+//! "最近操作" submenu and optionally as desktop notifications.
+
+use std::collections::VecDeque;
+use std::sync::{Mutex, OnceLock};
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Runtime};
+
+use crate::notifications::NotificationCategory;
+
+/// Maximum number of entries kept in memory
+const MAX_ENTRIES: usize = 20;
+
+/// A single recent action
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityEntry {
+    /// Action kind: "provider", "backup", "update", "tool"
+    pub kind: String,
+    pub message: String,
+    /// RFC 3339 timestamp
+    pub timestamp: String,
+}
+
+static ACTIVITY_LOG: OnceLock<Mutex<VecDeque<ActivityEntry>>> = OnceLock::new();
+
+fn store() -> &'static Mutex<VecDeque<ActivityEntry>> {
+    ACTIVITY_LOG.get_or_init(|| Mutex::new(VecDeque::new()))
+}
+
+/// Snapshot of the log, newest first
+pub fn entries() -> Vec<ActivityEntry> {
+    store()
+        .lock()
+        .map(|entries| entries.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Record an action, refresh the tray and show a notification if its category is enabled
+pub fn record<R: Runtime>(app: &AppHandle<R>, kind: &str, message: impl Into<String>) {
+    let entry = ActivityEntry {
+        kind: kind.to_string(),
+        message: message.into(),
+        timestamp: Local::now().to_rfc3339(),
+    };
+
+    if let Ok(mut entries) = store().lock() {
+        entries.push_front(entry.clone());
+        entries.truncate(MAX_ENTRIES);
+    }
+    let _ = app.emit("activity-log-changed", &entry);
+
+    crate::notifications::notify(
+        app,
+        NotificationCategory::for_activity(&entry.kind),
+        "AI Toolbox",
+        entry.message,
+    );
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let _ = crate::tray::refresh_tray_menus(&app).await;
+    });
+}
+
+/// Get recent actions, newest first
+#[tauri::command]
+pub fn get_activity_log() -> Vec<ActivityEntry> {
+    entries()
+}
+
+/// Clear recent actions
+#[tauri::command]
+pub async fn clear_activity_log<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    if let Ok(mut entries) = store().lock() {
+        entries.clear();
+    }
+    let _ = app.emit("activity-log-changed", ());
+    crate::tray::refresh_tray_menus(&app).await
+}
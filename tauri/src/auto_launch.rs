@@ -7,6 +7,9 @@
 
 use thiserror::Error;
 
+/// Command line argument passed when the app is started by the OS at login
+pub const AUTOSTART_ARG: &str = "--autostart";
+
 #[derive(Error, Debug)]
 pub enum AutoLaunchError {
     #[error("Failed to get executable path: {0}")]
@@ -55,6 +58,7 @@ fn get_auto_launch() -> Result<auto_launch::AutoLaunch, AutoLaunchError> {
     AutoLaunchBuilder::new()
         .set_app_name(app_name)
         .set_app_path(&app_path.to_string_lossy())
+        .set_args(&[AUTOSTART_ARG])
         .build()
         .map_err(|e| AutoLaunchError::Build(e.to_string()))
 }
@@ -77,6 +81,11 @@ pub fn disable_auto_launch() -> Result<(), AutoLaunchError> {
     Ok(())
 }
 
+/// Whether this process was started by the OS at login
+pub fn is_launched_at_login() -> bool {
+    std::env::args().any(|arg| arg == AUTOSTART_ARG)
+}
+
 /// Check if auto launch is enabled
 pub fn is_auto_launch_enabled() -> Result<bool, AutoLaunchError> {
     let auto_launch = get_auto_launch()?;
@@ -0,0 +1,366 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde_json::Value;
+
+use crate::coding::change_history;
+use crate::coding::cli_detect;
+use crate::coding::provider_store::{self, ProviderStore};
+use crate::db::DbState;
+use crate::error::AppResult;
+use super::types::*;
+
+/// Amp providers and common config
+pub const AMP: ProviderStore = ProviderStore {
+    display_name: "Amp",
+    provider_table: "amp_provider",
+    common_table: Some("amp_common_config"),
+};
+
+// ============================================================================
+// Amp Config Path Commands
+// ============================================================================
+
+/// Get Amp config directory ($XDG_CONFIG_HOME/amp or ~/.config/amp on every platform)
+fn get_amp_config_dir() -> Result<PathBuf, String> {
+    if let Ok(dir) = std::env::var("XDG_CONFIG_HOME") {
+        if !dir.trim().is_empty() {
+            return Ok(PathBuf::from(dir).join("amp"));
+        }
+    }
+
+    let home_dir = std::env::var("USERPROFILE")
+        .or_else(|_| std::env::var("HOME"))
+        .map_err(|_| "Failed to get home directory".to_string())?;
+
+    Ok(Path::new(&home_dir).join(".config").join("amp"))
+}
+
+/// Get Amp settings.json path (AMP_SETTINGS_FILE overrides the default location)
+fn get_amp_settings_path() -> Result<PathBuf, String> {
+    if let Ok(file) = std::env::var("AMP_SETTINGS_FILE") {
+        if !file.trim().is_empty() {
+            return Ok(PathBuf::from(file));
+        }
+    }
+    Ok(get_amp_config_dir()?.join("settings.json"))
+}
+
+/// Get Amp settings.json file path
+#[tauri::command]
+pub fn get_amp_config_path() -> Result<String, String> {
+    Ok(get_amp_settings_path()?.to_string_lossy().to_string())
+}
+
+/// Detect Amp installation
+#[tauri::command]
+pub fn get_amp_status() -> Result<AmpStatus, String> {
+    let settings_path = get_amp_settings_path()?;
+    let binary = cli_detect::detect_cli(&["amp"]);
+    let installed = binary.is_some() || settings_path.exists();
+
+    Ok(AmpStatus {
+        binary,
+        installed,
+        settings_exists: settings_path.exists(),
+        settings_path: settings_path.to_string_lossy().to_string(),
+    })
+}
+
+// ============================================================================
+// Amp Provider Commands
+// ============================================================================
+
+/// List all Amp providers ordered by sort_index
+#[tauri::command]
+pub async fn list_amp_providers(
+    state: tauri::State<'_, DbState>,
+) -> AppResult<Vec<AmpProvider>> {
+    let db = state.0.lock().await;
+    provider_store::list_providers(&db, &AMP).await
+}
+
+/// Create a new Amp provider
+#[tauri::command]
+pub async fn create_amp_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    provider: AmpProviderInput,
+) -> AppResult<AmpProvider> {
+    let db = state.0.lock().await;
+    provider_store::create_provider(&db, &app, &AMP, provider).await
+}
+
+/// Update an existing Amp provider
+#[tauri::command]
+pub async fn update_amp_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    provider: AmpProvider,
+) -> AppResult<AmpProvider> {
+    let db = state.0.lock().await;
+    let provider = provider_store::update_provider(&db, &app, &AMP, provider).await?;
+
+    // Re-apply to config files if this provider is currently applied
+    if provider.is_applied {
+        if let Err(e) = apply_config_to_file(&db, &provider.id).await {
+            log::warn!("Failed to auto-apply updated Amp config: {}", e);
+        }
+    }
+    Ok(provider)
+}
+
+/// Delete an Amp provider
+#[tauri::command]
+pub async fn delete_amp_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+    provider_store::delete_provider(&db, &app, &AMP, &id).await
+}
+
+/// Reorder Amp providers
+#[tauri::command]
+pub async fn reorder_amp_providers(
+    state: tauri::State<'_, DbState>,
+    ids: Vec<String>,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+    provider_store::reorder_providers(&db, &AMP, &ids).await
+}
+
+/// Select an Amp provider (mark as applied in database, but not write to file)
+#[tauri::command]
+pub async fn select_amp_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+    provider_store::select_provider(&db, &app, &AMP, &id).await
+}
+
+/// Toggle is_disabled status for a provider
+#[tauri::command]
+pub async fn toggle_amp_provider_disabled(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    provider_id: String,
+    is_disabled: bool,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+    provider_store::toggle_provider_disabled(&db, &app, &AMP, &provider_id, is_disabled).await
+}
+
+// ============================================================================
+// Amp Config File Commands
+// ============================================================================
+
+/// Read Amp settings.json (JSONC accepted)
+fn read_settings_value() -> Result<Value, String> {
+    let settings_path = get_amp_settings_path()?;
+    if !settings_path.exists() {
+        return Ok(serde_json::json!({}));
+    }
+    let content = fs::read_to_string(&settings_path)
+        .map_err(|e| format!("Failed to read settings.json: {}", e))?;
+    if content.trim().is_empty() {
+        return Ok(serde_json::json!({}));
+    }
+    json5::from_str(&content).map_err(|e| format!("Failed to parse settings.json: {}", e))
+}
+
+/// Read current Amp settings.json
+#[tauri::command]
+pub async fn read_amp_settings() -> Result<Value, String> {
+    read_settings_value()
+}
+
+/// Get the amp.mcpServers section of settings.json
+#[tauri::command]
+pub async fn get_amp_mcp_servers() -> Result<Value, String> {
+    Ok(read_settings_value()?
+        .get("amp.mcpServers")
+        .cloned()
+        .unwrap_or_else(|| serde_json::json!({})))
+}
+
+/// Detect which provider matches the current settings.json and sync is_applied
+///
+/// A provider matches when every key in its `settings` section has the same value
+/// in settings.json. Returns the matched provider id.
+#[tauri::command]
+pub async fn detect_amp_applied_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+) -> Result<Option<String>, String> {
+    let db = state.0.lock().await;
+
+    let providers = provider_store::list_providers(&db, &AMP).await?;
+
+    let settings = read_settings_value()?;
+    let matched = providers.iter().find(|provider| {
+        settings_section(&provider.settings_config)
+            .map(|section| {
+                !section.is_empty()
+                    && section
+                        .iter()
+                        .all(|(key, value)| settings.get(key) == Some(value))
+            })
+            .unwrap_or(false)
+    });
+
+    let matched_id = matched.map(|p| p.id.clone());
+    let current_id = providers.iter().find(|p| p.is_applied).map(|p| p.id.clone());
+    if matched_id != current_id {
+        match &matched_id {
+            Some(id) => provider_store::select_provider(&db, &app, &AMP, id).await?,
+            None => provider_store::unselect_provider(&db, &app, &AMP).await?,
+        }
+    }
+
+    Ok(matched_id)
+}
+
+/// Extract the `settings` object from a provider/common settings_config string
+fn settings_section(settings_config: &str) -> Result<serde_json::Map<String, Value>, String> {
+    if settings_config.trim().is_empty() {
+        return Ok(serde_json::Map::new());
+    }
+    let value: Value = serde_json::from_str(settings_config)
+        .map_err(|e| format!("Failed to parse settings config: {}", e))?;
+    Ok(value
+        .get("settings")
+        .and_then(|v| v.as_object())
+        .cloned()
+        .unwrap_or_default())
+}
+
+/// Build the settings.json content for a provider without writing it
+///
+/// Keys set by any Amp provider are treated as managed: they are removed
+/// first, then the common config and the selected provider's keys are written.
+async fn build_settings_content(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    provider_id: &str,
+) -> Result<String, String> {
+    let provider = provider_store::get_enabled_provider(db, &AMP, provider_id).await?;
+    let providers = provider_store::list_providers(db, &AMP).await?;
+    let common_config = provider_store::common_config_text(db, &AMP).await?;
+    let common_settings = settings_section(&common_config)?;
+
+    let mut settings = read_settings_value()?;
+    let obj = settings
+        .as_object_mut()
+        .ok_or_else(|| "Invalid settings.json format".to_string())?;
+
+    for other in &providers {
+        if let Ok(section) = settings_section(&other.settings_config) {
+            for key in section.keys() {
+                obj.remove(key);
+            }
+        }
+    }
+    for (key, value) in common_settings {
+        obj.insert(key, value);
+    }
+    for (key, value) in settings_section(&provider.settings_config)? {
+        obj.insert(key, value);
+    }
+
+    serde_json::to_string_pretty(&settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))
+}
+
+/// Internal function: apply provider settings to Amp settings.json
+async fn apply_config_to_file(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    provider_id: &str,
+) -> Result<(), String> {
+    let content = build_settings_content(db, provider_id).await?;
+    let settings_path = get_amp_settings_path()?;
+
+    change_history::write_config("amp", &settings_path, content)
+        .map_err(|e| format!("Failed to write settings.json: {}", e))
+}
+
+/// Preview the changes applying a provider would make (diff source for the frontend)
+#[tauri::command]
+pub async fn preview_amp_config(
+    state: tauri::State<'_, DbState>,
+    provider_id: String,
+) -> Result<AmpApplyPreview, String> {
+    let db = state.0.lock().await;
+    let new_settings = build_settings_content(&db, &provider_id).await?;
+
+    let settings_path = get_amp_settings_path()?;
+    let current_settings = fs::read_to_string(&settings_path).unwrap_or_default();
+
+    Ok(AmpApplyPreview {
+        settings_path: settings_path.to_string_lossy().to_string(),
+        has_changes: current_settings.trim() != new_settings.trim(),
+        current_settings,
+        new_settings,
+    })
+}
+
+/// Apply Amp provider settings
+/// `dry_run` returns the diff of the files instead of writing them.
+#[tauri::command]
+pub async fn apply_amp_config(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    provider_id: String,
+    dry_run: Option<bool>,
+) -> Result<Vec<change_history::ConfigDiff>, String> {
+    let db = state.0.lock().await;
+    if dry_run.unwrap_or(false) {
+        return change_history::preview(true, apply_config_to_file(&db, &provider_id)).await;
+    }
+    apply_config_internal(&db, &app, &provider_id, false).await?;
+    Ok(Vec::new())
+}
+
+/// Internal function to apply config: writes files and updates database
+pub async fn apply_config_internal<R: tauri::Runtime>(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    app: &tauri::AppHandle<R>,
+    provider_id: &str,
+    from_tray: bool,
+) -> Result<(), String> {
+    apply_config_to_file(db, provider_id).await?;
+    Ok(provider_store::mark_applied(db, app, &AMP, provider_id, from_tray).await?)
+}
+
+// ============================================================================
+// Amp Common Config Commands
+// ============================================================================
+
+/// Get Amp common config
+#[tauri::command]
+pub async fn get_amp_common_config(
+    state: tauri::State<'_, DbState>,
+) -> AppResult<Option<AmpCommonConfig>> {
+    let db = state.0.lock().await;
+    provider_store::get_common_config(&db, &AMP).await
+}
+
+/// Save Amp common config
+#[tauri::command]
+pub async fn save_amp_common_config(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    config: String,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+    provider_store::save_common_config(&db, &app, &AMP, &config).await?;
+
+    // Re-apply current provider so the merged config reaches the files
+    if let Ok(Some(provider)) = provider_store::applied_provider(&db, &AMP).await {
+        if let Err(e) = apply_config_to_file(&db, &provider.id).await {
+            log::warn!("Failed to re-apply Amp config: {}", e);
+        }
+    }
+    Ok(())
+}
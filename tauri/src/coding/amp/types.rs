@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+use crate::coding::provider_store::{StoredCommonConfig, StoredProvider, StoredProviderInput};
+
+// ============================================================================
+// Amp Provider Types
+// ============================================================================
+
+/// AmpProvider - API response
+///
+/// `settings_config` is a JSON string: `{ "settings": { "amp.url": "...", ... } }`
+/// where `settings` holds keys merged into ~/.config/amp/settings.json
+pub type AmpProvider = StoredProvider;
+
+/// AmpProvider - Input from frontend (for create operation)
+pub type AmpProviderInput = StoredProviderInput;
+
+// ============================================================================
+// Amp Common Config Types
+// ============================================================================
+
+/// AmpCommonConfig - API response
+pub type AmpCommonConfig = StoredCommonConfig;
+
+/// Amp detection result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AmpStatus {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub binary: Option<crate::coding::cli_detect::CliBinaryInfo>,
+    pub installed: bool,
+    /// ~/.config/amp/settings.json
+    pub settings_path: String,
+    pub settings_exists: bool,
+}
+
+/// Preview of what applying a provider would write (current vs new)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AmpApplyPreview {
+    pub settings_path: String,
+    pub current_settings: String,
+    pub new_settings: String,
+    pub has_changes: bool,
+}
@@ -0,0 +1,196 @@
+//! Checker building blocks: built-in templates, URL/body templating and
+//! reading the balance out of a JSON response.
+
+use serde_json::Value;
+
+use super::types::{BalanceAuthStyle, BalanceTemplate};
+
+/// (id, name, endpoint, auth style, auth name, headers, balance path, divisor, unit)
+type TemplateRow = (
+    &'static str,
+    &'static str,
+    &'static str,
+    BalanceAuthStyle,
+    Option<&'static str>,
+    &'static [(&'static str, &'static str)],
+    &'static str,
+    Option<f64>,
+    Option<&'static str>,
+);
+
+/// Built-in templates
+const TEMPLATES: &[TemplateRow] = &[
+    (
+        "new_api",
+        "new-api / one-api (token usage)",
+        "{origin}/api/usage/token",
+        BalanceAuthStyle::Bearer,
+        None,
+        &[],
+        "data.total_available",
+        Some(500000.0),
+        Some("USD"),
+    ),
+    (
+        "one_api_user",
+        "new-api / one-api (account, /api/user/self)",
+        "{origin}/api/user/self",
+        BalanceAuthStyle::None,
+        None,
+        &[("Authorization", "Bearer <access token>"), ("New-Api-User", "<user id>")],
+        "data.quota",
+        Some(500000.0),
+        Some("USD"),
+    ),
+    (
+        "openai_billing",
+        "OpenAI-compatible billing (subscription)",
+        "{origin}/v1/dashboard/billing/subscription",
+        BalanceAuthStyle::Bearer,
+        None,
+        &[],
+        "hard_limit_usd",
+        None,
+        Some("USD"),
+    ),
+    (
+        "deepseek",
+        "DeepSeek",
+        "https://api.deepseek.com/user/balance",
+        BalanceAuthStyle::Bearer,
+        None,
+        &[],
+        "balance_infos[0].total_balance",
+        None,
+        Some("CNY"),
+    ),
+    (
+        "moonshot",
+        "Moonshot (Kimi)",
+        "https://api.moonshot.cn/v1/users/me/balance",
+        BalanceAuthStyle::Bearer,
+        None,
+        &[],
+        "data.available_balance",
+        None,
+        Some("CNY"),
+    ),
+    (
+        "siliconflow",
+        "SiliconFlow",
+        "https://api.siliconflow.cn/v1/user/info",
+        BalanceAuthStyle::Bearer,
+        None,
+        &[],
+        "data.totalBalance",
+        None,
+        Some("CNY"),
+    ),
+    (
+        "openrouter",
+        "OpenRouter (key limit)",
+        "https://openrouter.ai/api/v1/key",
+        BalanceAuthStyle::Bearer,
+        None,
+        &[],
+        "data.limit_remaining",
+        None,
+        Some("USD"),
+    ),
+];
+
+pub fn templates() -> Vec<BalanceTemplate> {
+    TEMPLATES
+        .iter()
+        .map(
+            |(id, name, endpoint, auth_style, auth_name, headers, balance_path, divisor, unit)| BalanceTemplate {
+                id: id.to_string(),
+                name: name.to_string(),
+                endpoint: endpoint.to_string(),
+                method: "GET".to_string(),
+                auth_style: *auth_style,
+                auth_name: auth_name.map(String::from),
+                headers: headers
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.to_string()))
+                    .collect(),
+                balance_path: balance_path.to_string(),
+                divisor: *divisor,
+                unit: unit.map(String::from),
+            },
+        )
+        .collect()
+}
+
+/// Scheme and host of a URL, e.g. "https://relay.example.com"
+pub fn origin_of(base_url: &str) -> String {
+    match reqwest::Url::parse(base_url) {
+        Ok(url) => url.origin().ascii_serialization(),
+        Err(_) => base_url.trim_end_matches('/').to_string(),
+    }
+}
+
+/// Fill `{base_url}`, `{origin}` and `{api_key}` into a template
+pub fn render(template: &str, base_url: &str, api_key: &str) -> String {
+    let base_url = base_url.trim_end_matches('/');
+    template
+        .replace("{base_url}", base_url)
+        .replace("{origin}", &origin_of(base_url))
+        .replace("{api_key}", api_key)
+}
+
+/// Value at a path like "data.items[0].balance" (a leading "$." is allowed)
+pub fn value_at<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    let path = path.trim();
+    let path = path.strip_prefix("$.").or_else(|| path.strip_prefix('$')).unwrap_or(path);
+
+    let mut current = value;
+    for segment in path.split('.').filter(|s| !s.is_empty()) {
+        let (key, indices) = match segment.find('[') {
+            Some(pos) => (&segment[..pos], &segment[pos..]),
+            None => (segment, ""),
+        };
+        if !key.is_empty() {
+            current = current.get(key)?;
+        }
+        for index in indices.split('[').filter(|s| !s.is_empty()) {
+            let index: usize = index.trim_end_matches(']').trim().parse().ok()?;
+            current = current.get(index)?;
+        }
+    }
+    Some(current)
+}
+
+/// Balance as a number; relays often return it as a string
+pub fn as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_fills_placeholders() {
+        assert_eq!(
+            render("{origin}/api/usage/token?key={api_key}", "https://relay.example.com/v1/", "sk-1"),
+            "https://relay.example.com/api/usage/token?key=sk-1"
+        );
+        assert_eq!(render("{base_url}/balance", "https://a.com/v1/", ""), "https://a.com/v1/balance");
+    }
+
+    #[test]
+    fn value_at_follows_keys_and_indices() {
+        let value = serde_json::json!({
+            "data": { "total_available": 250000 },
+            "balance_infos": [{ "total_balance": "12.50" }]
+        });
+        assert_eq!(as_number(value_at(&value, "data.total_available").unwrap()), Some(250000.0));
+        assert_eq!(as_number(value_at(&value, "$.balance_infos[0].total_balance").unwrap()), Some(12.5));
+        assert!(value_at(&value, "balance_infos[1]").is_none());
+    }
+}
@@ -0,0 +1,184 @@
+use chrono::Local;
+use serde_json::Value;
+
+use crate::coding::{db_extract_id, provider_import, vault};
+use crate::db::DbState;
+use crate::error::{AppError, AppResult};
+use super::checker;
+use super::types::*;
+
+const SUPPORTED_TOOLS: [&str; 3] = ["claude", "codex", "opencode"];
+
+fn from_db_value(mut value: Value) -> Option<BalanceChecker> {
+    let provider_id = db_extract_id(&value);
+    let map = value.as_object_mut()?;
+    map.remove("id");
+    map.insert("provider_id".to_string(), Value::String(provider_id));
+    serde_json::from_value(value).ok()
+}
+
+pub async fn get_checker(state: &DbState, provider_id: &str) -> AppResult<BalanceChecker> {
+    let db = state.0.lock().await;
+    let records: Vec<Value> = db
+        .query("SELECT *, type::string(id) as id FROM type::thing('balance_checker', $id)")
+        .bind(("id", provider_id.to_string()))
+        .await?
+        .take(0)?;
+    records
+        .into_iter()
+        .next()
+        .and_then(from_db_value)
+        .ok_or_else(|| AppError::not_found("BALANCE_CHECKER_NOT_FOUND", &[("id", provider_id)]))
+}
+
+/// Send the checker's request and read the raw balance from the response
+async fn run_checker(state: &DbState, checker: &BalanceChecker) -> AppResult<f64> {
+    let provider = {
+        let db = state.0.lock().await;
+        provider_import::share::export_provider(&db, &checker.tool, &checker.provider_id).await?
+    };
+    let mut api_key = Value::String(provider.api_key);
+    vault::resolve_references(&mut api_key)?;
+    let api_key = api_key.as_str().unwrap_or_default().to_string();
+
+    let url = checker::render(&checker.endpoint, &provider.base_url, &api_key);
+    let method = reqwest::Method::from_bytes(checker.method.trim().to_uppercase().as_bytes())
+        .map_err(AppError::invalid)?;
+    let client = crate::http_client::client(state).await.map_err(AppError::http)?;
+    let mut request = client.request(method, &url);
+    request = match checker.auth_style {
+        BalanceAuthStyle::Bearer => request.bearer_auth(&api_key),
+        BalanceAuthStyle::Header => {
+            request.header(checker.auth_name.as_deref().unwrap_or("x-api-key"), &api_key)
+        }
+        BalanceAuthStyle::Query => {
+            request.query(&[(checker.auth_name.as_deref().unwrap_or("key"), api_key.as_str())])
+        }
+        BalanceAuthStyle::None => request,
+    };
+    if !checker.headers.is_empty() {
+        let mut headers = serde_json::to_value(&checker.headers).map_err(AppError::invalid)?;
+        vault::resolve_references(&mut headers)?;
+        for (name, value) in headers.as_object().into_iter().flatten() {
+            let value = value.as_str().unwrap_or_default();
+            request = request.header(name.as_str(), checker::render(value, &provider.base_url, &api_key));
+        }
+    }
+    if let Some(body) = checker.body.as_deref().filter(|b| !b.trim().is_empty()) {
+        request = request
+            .header("Content-Type", "application/json")
+            .body(checker::render(body, &provider.base_url, &api_key));
+    }
+
+    let response = request.send().await?;
+    let status = response.status();
+    let text = response.text().await?;
+    if !status.is_success() {
+        let snippet: String = text.chars().take(200).collect();
+        return Err(AppError::http(format!("HTTP {}: {}", status, snippet)));
+    }
+    let payload: Value = serde_json::from_str(&text).map_err(AppError::invalid)?;
+
+    checker::value_at(&payload, &checker.balance_path)
+        .and_then(checker::as_number)
+        .ok_or_else(|| AppError::not_found("BALANCE_VALUE_NOT_FOUND", &[("path", checker.balance_path.as_str())]))
+}
+
+// ============================================================================
+// Balance Commands
+// ============================================================================
+
+/// Built-in checker templates for common relays and vendors
+#[tauri::command]
+pub fn list_balance_templates() -> Vec<BalanceTemplate> {
+    checker::templates()
+}
+
+#[tauri::command]
+pub async fn list_balance_checkers(state: tauri::State<'_, DbState>) -> AppResult<Vec<BalanceChecker>> {
+    let db = state.0.lock().await;
+    let records: Vec<Value> = db
+        .query("SELECT *, type::string(id) as id FROM balance_checker")
+        .await?
+        .take(0)?;
+    Ok(records.into_iter().filter_map(from_db_value).collect())
+}
+
+/// Create or replace the checker of a provider
+#[tauri::command]
+pub async fn save_balance_checker(
+    state: tauri::State<'_, DbState>,
+    checker: BalanceChecker,
+) -> AppResult<BalanceChecker> {
+    let mut checker = checker;
+    checker.provider_id = checker.provider_id.trim().to_string();
+    checker.tool = checker.tool.trim().to_lowercase();
+    checker.endpoint = checker.endpoint.trim().to_string();
+    checker.balance_path = checker.balance_path.trim().to_string();
+    checker.divisor = checker.divisor.filter(|d| *d != 0.0);
+    checker.headers = checker
+        .headers
+        .into_iter()
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .filter(|(name, _)| !name.is_empty())
+        .collect();
+
+    if !SUPPORTED_TOOLS.contains(&checker.tool.as_str()) {
+        return Err(AppError::validation("UNSUPPORTED_TOOL", &[("tool", checker.tool.as_str())]));
+    }
+    if checker.provider_id.is_empty() || checker.endpoint.is_empty() || checker.balance_path.is_empty() {
+        return Err(AppError::validation("BALANCE_CHECKER_INCOMPLETE", &[]));
+    }
+
+    let mut data = serde_json::to_value(&checker).map_err(AppError::invalid)?;
+    if let Some(map) = data.as_object_mut() {
+        map.remove("provider_id");
+    }
+    let db = state.0.lock().await;
+    db.query("UPSERT type::thing('balance_checker', $id) CONTENT $data")
+        .bind(("id", checker.provider_id.clone()))
+        .bind(("data", data))
+        .await?;
+    Ok(checker)
+}
+
+#[tauri::command]
+pub async fn delete_balance_checker(state: tauri::State<'_, DbState>, provider_id: String) -> AppResult<()> {
+    let db = state.0.lock().await;
+    db.query("DELETE type::thing('balance_checker', $id)")
+        .bind(("id", provider_id))
+        .await?;
+    Ok(())
+}
+
+/// Run `checker` and remember the result on it
+pub async fn refresh(state: &DbState, checker: BalanceChecker) -> AppResult<BalanceResult> {
+    let provider_id = checker.provider_id.clone();
+    let raw = run_checker(state, &checker).await?;
+    let balance = match checker.divisor {
+        Some(divisor) => raw / divisor,
+        None => raw,
+    };
+    let checked_at = Local::now().to_rfc3339();
+
+    let db = state.0.lock().await;
+    db.query("UPDATE type::thing('balance_checker', $id) SET last_balance = $balance, last_checked_at = $checked_at")
+        .bind(("id", provider_id.clone()))
+        .bind(("balance", balance))
+        .bind(("checked_at", checked_at.clone()))
+        .await?;
+
+    Ok(BalanceResult {
+        provider_id,
+        balance,
+        unit: checker.unit,
+        checked_at,
+    })
+}
+
+/// Query a provider's balance with its checker and remember the result
+#[tauri::command]
+pub async fn query_balance(state: tauri::State<'_, DbState>, provider_id: String) -> AppResult<BalanceResult> {
+    let checker = get_checker(&state, &provider_id).await?;
+    refresh(&state, checker).await
+}
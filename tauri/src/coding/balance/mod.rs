@@ -0,0 +1,13 @@
+//! Relay Balance Checks
+//!
+//! Every relay exposes its balance differently, so each provider gets a
+//! user-defined checker: an endpoint template, how to send the key and where
+//! the balance sits in the JSON response. `query_balance` runs it.
+//! Built-in templates cover common relays and can be copied into a checker.
+
+pub mod checker;
+pub mod commands;
+pub mod types;
+
+pub use commands::*;
+pub use types::*;
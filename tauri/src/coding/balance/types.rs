@@ -0,0 +1,91 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// How the provider's API key is sent to the balance endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BalanceAuthStyle {
+    /// `Authorization: Bearer <key>`
+    #[default]
+    Bearer,
+    /// `<auth_name>: <key>`, e.g. `x-api-key`
+    Header,
+    /// `?<auth_name>=<key>`
+    Query,
+    /// Key only used in the endpoint template (`{api_key}`) or not at all
+    None,
+}
+
+/// Balance checker of one provider
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BalanceChecker {
+    /// Provider id in its tool (claude/codex provider id, OpenCode provider_id)
+    pub provider_id: String,
+    /// "claude", "codex" or "opencode"
+    pub tool: String,
+    /// URL template; `{base_url}`, `{origin}` and `{api_key}` are filled in
+    pub endpoint: String,
+    /// "GET" or "POST"
+    #[serde(default = "default_method")]
+    pub method: String,
+    #[serde(default)]
+    pub auth_style: BalanceAuthStyle,
+    /// Header or query parameter name for `header` / `query` auth
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth_name: Option<String>,
+    /// Extra request headers; values are templates like `endpoint` and may be
+    /// vault references, e.g. an account access token for `/api/user/self`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    /// JSON body template for POST requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    /// Path of the balance in the response, e.g. "data.total_available" or "balance_infos[0].total_balance"
+    pub balance_path: String,
+    /// Raw value is divided by this (e.g. 500000 quota units per dollar)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub divisor: Option<f64>,
+    /// Display unit, e.g. "USD", "CNY"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_balance: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_checked_at: Option<String>,
+}
+
+fn default_method() -> String {
+    "GET".to_string()
+}
+
+/// Built-in checker definition for a known relay / vendor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BalanceTemplate {
+    pub id: String,
+    pub name: String,
+    pub endpoint: String,
+    pub method: String,
+    pub auth_style: BalanceAuthStyle,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth_name: Option<String>,
+    /// Headers to fill in, with placeholder values
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    pub balance_path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub divisor: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+}
+
+/// Result of `query_balance`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BalanceResult {
+    pub provider_id: String,
+    /// Balance after applying the divisor
+    pub balance: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    pub checked_at: String,
+}
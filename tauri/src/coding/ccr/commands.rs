@@ -0,0 +1,345 @@
+use std::fs;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+use serde_json::{Map, Value};
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+use crate::coding::change_history;
+use crate::coding::model_rules;
+use crate::coding::cli_detect;
+use crate::coding::open_code::adapter as opencode_adapter;
+use crate::coding::open_code::types::OpenCodeFavoriteProvider;
+use crate::coding::vault;
+use crate::db::DbState;
+use super::types::*;
+
+/// Windows CREATE_NO_WINDOW flag to prevent console window from appearing
+#[cfg(target_os = "windows")]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+const DEFAULT_HOST: &str = "127.0.0.1";
+const DEFAULT_PORT: u16 = 3456;
+
+// ============================================================================
+// CCR Path Helpers
+// ============================================================================
+
+/// Get claude-code-router config directory (~/.claude-code-router/)
+fn get_ccr_config_dir() -> Result<PathBuf, String> {
+    let home_dir = std::env::var("USERPROFILE")
+        .or_else(|_| std::env::var("HOME"))
+        .map_err(|_| "Failed to get home directory".to_string())?;
+
+    Ok(Path::new(&home_dir).join(".claude-code-router"))
+}
+
+fn get_ccr_config_file() -> Result<PathBuf, String> {
+    Ok(get_ccr_config_dir()?.join("config.json"))
+}
+
+fn read_config_value() -> Result<Value, String> {
+    let config_path = get_ccr_config_file()?;
+    if !config_path.exists() {
+        return Ok(serde_json::json!({}));
+    }
+    let content = fs::read_to_string(&config_path)
+        .map_err(|e| format!("Failed to read CCR config: {}", e))?;
+    if content.trim().is_empty() {
+        return Ok(serde_json::json!({}));
+    }
+    json5::from_str(&content).map_err(|e| format!("Failed to parse CCR config: {}", e))
+}
+
+fn write_config_value(config: &Value) -> Result<(), String> {
+    let config_dir = get_ccr_config_dir()?;
+    if !config_dir.exists() {
+        fs::create_dir_all(&config_dir)
+            .map_err(|e| format!("Failed to create .claude-code-router directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(config)
+        .map_err(|e| format!("Failed to serialize CCR config: {}", e))?;
+    change_history::write_config("ccr", get_ccr_config_file()?, content)
+        .map_err(|e| format!("Failed to write CCR config: {}", e))
+}
+
+fn host_and_port(config: &Value) -> (String, u16) {
+    let host = config
+        .get("HOST")
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .unwrap_or(DEFAULT_HOST)
+        .to_string();
+    let port = config
+        .get("PORT")
+        .and_then(|v| v.as_u64())
+        .and_then(|p| u16::try_from(p).ok())
+        .unwrap_or(DEFAULT_PORT);
+    (host, port)
+}
+
+fn is_listening(host: &str, port: u16) -> bool {
+    // Wildcard bind addresses are reachable on loopback
+    let host = if host == "0.0.0.0" { DEFAULT_HOST } else { host };
+    let Ok(mut addrs) = (host, port).to_socket_addrs() else {
+        return false;
+    };
+    addrs.any(|addr| TcpStream::connect_timeout(&addr, Duration::from_millis(500)).is_ok())
+}
+
+fn ccr_command(args: &[&str]) -> Result<Command, String> {
+    let binary = cli_detect::find_binary(&["ccr"])
+        .ok_or_else(|| "claude-code-router (ccr) is not installed".to_string())?;
+    let mut cmd = Command::new(binary);
+    cmd.args(args).stdin(Stdio::null());
+    #[cfg(target_os = "windows")]
+    cmd.creation_flags(CREATE_NO_WINDOW);
+    Ok(cmd)
+}
+
+/// CCR transformer and endpoint for an OpenCode SDK package
+fn ccr_endpoint(npm: &str, base_url: &str) -> (Option<&'static str>, String) {
+    let base = base_url.trim_end_matches('/');
+    match npm {
+        "@ai-sdk/anthropic" => (
+            Some("Anthropic"),
+            format!("{}/v1/messages", base.trim_end_matches("/v1")),
+        ),
+        "@ai-sdk/google" => (Some("gemini"), format!("{}/models/", base)),
+        _ => (None, format!("{}/chat/completions", base)),
+    }
+}
+
+/// Convert a toolbox provider into a CCR `Providers` entry
+/// Existing transformer settings for the same provider name are kept
+fn to_ccr_provider(provider: &OpenCodeFavoriteProvider, existing: Option<&Value>) -> Value {
+    let config = &provider.provider_config;
+    let options = config.options.as_ref();
+
+    let mut models: Vec<String> = config.models.keys().cloned().collect();
+    models.sort();
+
+    let (transformer, api_base_url) = ccr_endpoint(&provider.npm, &provider.base_url);
+
+    let mut entry = Map::new();
+    entry.insert("name".to_string(), Value::String(provider.provider_id.clone()));
+    entry.insert("api_base_url".to_string(), Value::String(api_base_url));
+    entry.insert(
+        "api_key".to_string(),
+        Value::String(options.and_then(|o| o.api_key.clone()).unwrap_or_default()),
+    );
+    entry.insert(
+        "models".to_string(),
+        Value::Array(models.into_iter().map(Value::String).collect()),
+    );
+
+    match existing.and_then(|e| e.get("transformer")) {
+        Some(kept) => {
+            entry.insert("transformer".to_string(), kept.clone());
+        }
+        None => {
+            if let Some(name) = transformer {
+                entry.insert("transformer".to_string(), serde_json::json!({ "use": [name] }));
+            }
+        }
+    }
+    Value::Object(entry)
+}
+
+// ============================================================================
+// CCR Config Commands
+// ============================================================================
+
+/// Detect claude-code-router and whether its service is running
+#[tauri::command]
+pub fn get_ccr_status() -> Result<CcrStatus, String> {
+    let config_path = get_ccr_config_file()?;
+    let config = read_config_value().unwrap_or_else(|_| serde_json::json!({}));
+    let (host, port) = host_and_port(&config);
+
+    Ok(CcrStatus {
+        binary: cli_detect::detect_cli(&["ccr"]),
+        config_exists: config_path.exists(),
+        config_path: config_path.to_string_lossy().to_string(),
+        running: is_listening(&host, port),
+        host,
+        port,
+    })
+}
+
+/// Read claude-code-router config.json
+#[tauri::command]
+pub fn read_ccr_config() -> Result<Value, String> {
+    read_config_value()
+}
+
+/// Save claude-code-router config.json (`dry_run` returns the diff instead)
+#[tauri::command]
+pub fn save_ccr_config(config: Value, dry_run: Option<bool>) -> Result<Vec<change_history::ConfigDiff>, String> {
+    if !config.is_object() {
+        return Err("Invalid CCR config format".to_string());
+    }
+    change_history::preview_sync(dry_run.unwrap_or(false), || write_config_value(&config))
+}
+
+/// Generate CCR `Providers` from toolbox providers
+/// If `provider_ids` is None, all saved providers are synced. Providers not
+/// managed by the toolbox are left untouched. With `dry_run` nothing is written
+/// and the result carries the diff.
+#[tauri::command]
+pub async fn sync_ccr_providers(
+    state: tauri::State<'_, DbState>,
+    provider_ids: Option<Vec<String>>,
+    dry_run: Option<bool>,
+) -> Result<CcrSyncResult, String> {
+    let providers: Vec<OpenCodeFavoriteProvider> = {
+        let db = state.0.lock().await;
+        let records_result: Result<Vec<Value>, _> = db
+            .query("SELECT *, type::string(id) as id FROM opencode_favorite_provider ORDER BY created_at ASC")
+            .await
+            .map_err(|e| format!("Failed to query favorite providers: {}", e))?
+            .take(0);
+        records_result
+            .map_err(|e| format!("Failed to deserialize favorite providers: {}", e))?
+            .into_iter()
+            .filter_map(opencode_adapter::from_db_value_favorite_provider)
+            .filter(|p| !p.base_url.is_empty())
+            .filter(|p| {
+                provider_ids
+                    .as_ref()
+                    .map(|ids| ids.contains(&p.provider_id))
+                    .unwrap_or(true)
+            })
+            .collect()
+    };
+
+    let mut config = read_config_value()?;
+    let root = config
+        .as_object_mut()
+        .ok_or_else(|| "Invalid CCR config format".to_string())?;
+    let mut entries: Vec<Value> = root
+        .get("Providers")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+
+    let mut result = CcrSyncResult {
+        providers: 0,
+        models: 0,
+        changes: Vec::new(),
+    };
+    for provider in &providers {
+        let position = entries
+            .iter()
+            .position(|e| e.get("name").and_then(|n| n.as_str()) == Some(provider.provider_id.as_str()));
+        let mut entry = to_ccr_provider(provider, position.map(|i| &entries[i]));
+        vault::resolve_references(&mut entry)?;
+        match position {
+            Some(i) => entries[i] = entry,
+            None => entries.push(entry),
+        }
+        result.providers += 1;
+        result.models += provider.provider_config.models.len();
+    }
+
+    root.insert("Providers".to_string(), Value::Array(entries));
+
+    // Re-route existing Router targets through the model rules
+    let rules = {
+        let db = state.0.lock().await;
+        model_rules::load_rules(&db).await
+    };
+    model_rules::apply_to_ccr_router(&rules, &mut config);
+    result.changes = change_history::preview_sync(dry_run.unwrap_or(false), || write_config_value(&config))?;
+    Ok(result)
+}
+
+/// Set a CCR router rule (`default`, `background`, `think`, `longContext`, `webSearch`)
+/// `target` is "provider,model"; an empty target removes the rule.
+/// The target is routed through the model rules before it is written;
+/// `dry_run` returns the diff instead.
+#[tauri::command]
+pub async fn set_ccr_router_rule(
+    state: tauri::State<'_, DbState>,
+    rule: String,
+    target: String,
+    dry_run: Option<bool>,
+) -> Result<Vec<change_history::ConfigDiff>, String> {
+    let target = target.trim();
+    if !target.is_empty() && !target.contains(',') {
+        return Err("Router target must be in the form \"provider,model\"".to_string());
+    }
+    let rules = {
+        let db = state.0.lock().await;
+        model_rules::load_rules(&db).await
+    };
+
+    let mut config = read_config_value()?;
+    let root = config
+        .as_object_mut()
+        .ok_or_else(|| "Invalid CCR config format".to_string())?;
+    let router = root
+        .entry("Router".to_string())
+        .or_insert_with(|| serde_json::json!({}));
+    if !router.is_object() {
+        *router = serde_json::json!({});
+    }
+    let router = router.as_object_mut().unwrap();
+    if target.is_empty() {
+        router.remove(&rule);
+    } else {
+        router.insert(rule, Value::String(model_rules::rewrite_qualified(&rules, target, ',')));
+    }
+
+    change_history::preview_sync(dry_run.unwrap_or(false), || write_config_value(&config))
+}
+
+// ============================================================================
+// CCR Service Commands
+// ============================================================================
+
+/// Start the CCR service in the background
+#[tauri::command]
+pub fn start_ccr_service() -> Result<(), String> {
+    let config = read_config_value().unwrap_or_else(|_| serde_json::json!({}));
+    let (host, port) = host_and_port(&config);
+    if is_listening(&host, port) {
+        return Ok(());
+    }
+
+    ccr_command(&["start"])?
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to start CCR service: {}", e))?;
+    Ok(())
+}
+
+/// Stop the CCR service
+#[tauri::command]
+pub fn stop_ccr_service() -> Result<(), String> {
+    let output = ccr_command(&["stop"])?
+        .output()
+        .map_err(|e| format!("Failed to stop CCR service: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to stop CCR service: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+/// Restart the CCR service so config changes take effect
+#[tauri::command]
+pub fn restart_ccr_service() -> Result<(), String> {
+    let config = read_config_value().unwrap_or_else(|_| serde_json::json!({}));
+    let (host, port) = host_and_port(&config);
+    if is_listening(&host, port) {
+        stop_ccr_service()?;
+    }
+    start_ccr_service()
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+use crate::coding::cli_detect::CliBinaryInfo;
+
+// ============================================================================
+// claude-code-router Types
+// ============================================================================
+
+/// claude-code-router detection result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CcrStatus {
+    /// ccr binary (None if not installed)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub binary: Option<CliBinaryInfo>,
+    /// ~/.claude-code-router/config.json path
+    pub config_path: String,
+    pub config_exists: bool,
+    pub host: String,
+    pub port: u16,
+    /// Service is accepting connections on host:port
+    pub running: bool,
+}
+
+/// Result of generating CCR providers from the toolbox
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CcrSyncResult {
+    pub providers: usize,
+    pub models: usize,
+    /// Diff of config.json on a dry run
+    #[serde(default)]
+    pub changes: Vec<crate::coding::change_history::ConfigDiff>,
+}
@@ -0,0 +1,174 @@
+use std::fs;
+
+use tauri::AppHandle;
+
+use super::store::with_history;
+use super::types::{ChangeSummary, FileChange, UndoResult};
+use crate::error::{AppError, AppResult};
+use crate::events::{emit_change, ChangeAction};
+
+const DEFAULT_LIST_LIMIT: usize = 50;
+
+/// Group ids, newest first, keeping only the first occurrence
+fn groups_newest_first(entries: &[FileChange]) -> Vec<String> {
+    let mut groups: Vec<String> = Vec::new();
+    for change in entries.iter().rev() {
+        if !groups.contains(&change.group_id) {
+            groups.push(change.group_id.clone());
+        }
+    }
+    groups
+}
+
+/// Whether the file still holds what the change wrote
+fn is_unmodified(change: &FileChange) -> bool {
+    fs::read_to_string(&change.path)
+        .map(|current| current == change.after)
+        .unwrap_or(false)
+}
+
+fn restore(change: &FileChange) -> std::io::Result<()> {
+    match &change.before {
+        Some(before) => fs::write(&change.path, before),
+        None => match fs::remove_file(&change.path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        },
+    }
+}
+
+/// Undo the latest change not undone yet (of `scope`, or of any module)
+fn undo_latest(entries: &mut [FileChange], scope: Option<&str>) -> AppResult<UndoResult> {
+    let group_id = entries
+        .iter()
+        .rev()
+        .find(|c| !c.undone && scope.map(|s| c.scope == s).unwrap_or(true))
+        .map(|c| c.group_id.clone())
+        .ok_or_else(|| AppError::not_found("NO_CHANGE_TO_UNDO", &[]))?;
+
+    let indices: Vec<usize> = entries
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| c.group_id == group_id && !c.undone)
+        .map(|(i, _)| i)
+        .collect();
+
+    // When a file was written several times in the group only its last write
+    // must match; check everything before touching any file
+    for &i in &indices {
+        let change = &entries[i];
+        let is_last_write = !indices.iter().any(|&j| j > i && entries[j].path == change.path);
+        if is_last_write && !is_unmodified(change) {
+            return Err(AppError::conflict("CHANGE_MODIFIED_SINCE", &[("path", &change.path)]));
+        }
+    }
+
+    let mut files: Vec<String> = Vec::new();
+    for &i in indices.iter().rev() {
+        restore(&entries[i])?;
+        entries[i].undone = true;
+        if !files.contains(&entries[i].path) {
+            files.push(entries[i].path.clone());
+        }
+    }
+
+    let first = &entries[indices[0]];
+    Ok(UndoResult {
+        scope: first.scope.clone(),
+        files,
+        timestamp: first.timestamp.clone(),
+    })
+}
+
+// ============================================================================
+// Change History Commands
+// ============================================================================
+
+/// Restore the files of the most recent config change
+/// `scope` limits it to one module (e.g. "claude_code"); None means any module.
+#[tauri::command]
+pub fn undo_last_change(app: AppHandle, scope: Option<String>) -> AppResult<UndoResult> {
+    let result = with_history(|entries| undo_latest(entries, scope.as_deref())).map_err(AppError::io)??;
+    log::info!("Undid {} change to {:?}", result.scope, result.files);
+    emit_change(&app, "change_history", ChangeAction::Updated, None, "window");
+    Ok(result)
+}
+
+/// List recorded config changes, newest first
+#[tauri::command]
+pub fn list_change_history(scope: Option<String>, limit: Option<usize>) -> AppResult<Vec<ChangeSummary>> {
+    let entries = with_history(|entries| entries.clone()).map_err(AppError::io)?;
+    let entries: Vec<FileChange> = entries
+        .into_iter()
+        .filter(|c| scope.as_deref().map(|s| c.scope == s).unwrap_or(true))
+        .collect();
+
+    Ok(groups_newest_first(&entries)
+        .into_iter()
+        .take(limit.unwrap_or(DEFAULT_LIST_LIMIT))
+        .filter_map(|group_id| {
+            let changes: Vec<&FileChange> = entries.iter().filter(|c| c.group_id == group_id).collect();
+            let first = changes.first()?;
+            let mut files: Vec<String> = Vec::new();
+            for change in &changes {
+                if !files.contains(&change.path) {
+                    files.push(change.path.clone());
+                }
+            }
+            Some(ChangeSummary {
+                group_id: group_id.clone(),
+                scope: first.scope.clone(),
+                files,
+                timestamp: first.timestamp.clone(),
+                undone: changes.iter().all(|c| c.undone),
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(group_id: &str, path: &std::path::Path, before: Option<&str>, after: &str) -> FileChange {
+        FileChange {
+            id: uuid::Uuid::new_v4().to_string(),
+            scope: "test".to_string(),
+            group_id: group_id.to_string(),
+            path: path.to_string_lossy().to_string(),
+            before: before.map(String::from),
+            after: after.to_string(),
+            timestamp: "2026-01-01T00:00:00+00:00".to_string(),
+            undone: false,
+        }
+    }
+
+    #[test]
+    fn undo_restores_group_and_refuses_modified_files() {
+        let dir = std::env::temp_dir().join(format!("ai-toolbox-history-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let settings = dir.join("settings.json");
+        let env = dir.join(".env");
+
+        fs::write(&settings, "v2").unwrap();
+        fs::write(&env, "KEY=1").unwrap();
+        let mut entries = vec![
+            change("a", &settings, Some("v1"), "v2"),
+            change("b", &settings, Some("v2"), "v3"),
+            change("b", &env, None, "KEY=1"),
+        ];
+        fs::write(&settings, "v3").unwrap();
+
+        let result = undo_latest(&mut entries, Some("test")).unwrap();
+        assert_eq!(result.files.len(), 2);
+        assert_eq!(fs::read_to_string(&settings).unwrap(), "v2");
+        assert!(!env.exists());
+
+        fs::write(&settings, "edited by hand").unwrap();
+        let err = undo_latest(&mut entries, None).unwrap_err();
+        assert!(matches!(err, AppError::Conflict(_)));
+        assert!(!entries[0].undone);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
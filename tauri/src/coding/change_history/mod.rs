@@ -0,0 +1,17 @@
+//! Change History
+//!
+//! Config files written by any tool module go through `write_config`, which
+//! keeps a before/after snapshot of the file. `undo_last_change` restores the
+//! most recent change of a scope (module), as long as the file has not been
+//! modified by something else since. Apply commands with `dry_run` run
+//! their writes through `preview` to return diffs instead.
+
+pub mod commands;
+pub mod preview;
+pub mod store;
+pub mod types;
+
+pub use commands::*;
+pub use preview::{config_exists, preview, preview_sync, read_config};
+pub use store::write_config;
+pub use types::*;
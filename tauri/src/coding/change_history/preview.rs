@@ -0,0 +1,144 @@
+//! Dry runs of config writes
+//!
+//! Inside `preview` / `preview_sync` with `dry_run` set, `write_config`
+//! records a unified diff of each write instead of touching the file, so an
+//! apply command can show the user what it would change.
+
+use std::cell::RefCell;
+use std::fs;
+use std::future::Future;
+use std::io;
+use std::path::Path;
+
+use similar::TextDiff;
+
+use super::types::ConfigDiff;
+
+tokio::task_local! {
+    static CAPTURED: RefCell<Vec<ConfigDiff>>;
+}
+
+/// Unified diff of a file from `before` (None = new file) to `after`
+pub fn unified_diff(path: &str, before: Option<&str>, after: &str) -> String {
+    let old_header = if before.is_some() { path } else { "/dev/null" };
+    TextDiff::from_lines(before.unwrap_or(""), after)
+        .unified_diff()
+        .context_radius(3)
+        .header(old_header, path)
+        .to_string()
+}
+
+/// Record a write when a dry run is in progress; returns false outside of one
+pub(super) fn capture_write(scope: &str, path: &Path, contents: &[u8]) -> bool {
+    CAPTURED
+        .try_with(|captured| {
+            let path = path.to_string_lossy().to_string();
+            let after = String::from_utf8_lossy(contents).to_string();
+            let mut captured = captured.borrow_mut();
+            // A file written twice keeps its original content as the base
+            let before = match captured.iter().position(|c| c.path == path) {
+                Some(index) => captured.remove(index).before,
+                None => fs::read_to_string(&path).ok(),
+            };
+            if before.as_deref() != Some(after.as_str()) {
+                captured.push(ConfigDiff {
+                    scope: scope.to_string(),
+                    diff: unified_diff(&path, before.as_deref(), &after),
+                    created: before.is_none(),
+                    path,
+                    before,
+                    after,
+                });
+            }
+        })
+        .is_ok()
+}
+
+/// Content written to `path` earlier in the current dry run
+fn captured_content(path: &Path) -> Option<String> {
+    let path = path.to_string_lossy();
+    CAPTURED
+        .try_with(|captured| {
+            captured
+                .borrow()
+                .iter()
+                .find(|c| c.path == path)
+                .map(|c| c.after.clone())
+        })
+        .ok()
+        .flatten()
+}
+
+/// Read a config file as a dry run sees it, so a second edit of the same file
+/// builds on the first one
+pub fn read_config(path: &Path) -> io::Result<String> {
+    match captured_content(path) {
+        Some(content) => Ok(content),
+        None => fs::read_to_string(path),
+    }
+}
+
+/// `Path::exists` counting files created earlier in the current dry run
+pub fn config_exists(path: &Path) -> bool {
+    captured_content(path).is_some() || path.exists()
+}
+
+/// Run `apply`; with `dry_run` its config writes are returned as diffs instead of written
+pub async fn preview<T, E>(
+    dry_run: bool,
+    apply: impl Future<Output = Result<T, E>>,
+) -> Result<Vec<ConfigDiff>, E> {
+    if !dry_run {
+        apply.await?;
+        return Ok(Vec::new());
+    }
+    CAPTURED
+        .scope(RefCell::new(Vec::new()), async move {
+            apply.await?;
+            Ok(CAPTURED.with(|captured| captured.take()))
+        })
+        .await
+}
+
+/// `preview` for synchronous commands
+pub fn preview_sync<T, E>(dry_run: bool, apply: impl FnOnce() -> Result<T, E>) -> Result<Vec<ConfigDiff>, E> {
+    if !dry_run {
+        apply()?;
+        return Ok(Vec::new());
+    }
+    CAPTURED.sync_scope(RefCell::new(Vec::new()), || {
+        apply()?;
+        Ok(CAPTURED.with(|captured| captured.take()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::change_history::write_config;
+
+    #[test]
+    fn dry_run_records_diff_without_writing() {
+        let dir = std::env::temp_dir().join(format!("aitb-preview-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("settings.json");
+        std::fs::write(&path, "{\n  \"model\": \"a\"\n}\n").unwrap();
+
+        let diffs = preview_sync(true, || {
+            write_config("test", &path, "{\n  \"model\": \"b\"\n}\n")
+        })
+        .unwrap();
+        assert_eq!(diffs.len(), 1);
+        assert!(!diffs[0].created);
+        assert!(diffs[0].diff.contains("-  \"model\": \"a\""));
+        assert!(diffs[0].diff.contains("+  \"model\": \"b\""));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "{\n  \"model\": \"a\"\n}\n");
+
+        let new_file = dir.join("new.env");
+        let diffs = preview_sync(true, || write_config("test", &new_file, "A=1\n")).unwrap();
+        assert!(diffs[0].created);
+        assert!(!new_file.exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,130 @@
+//! History file (`change_history.json` in the app data folder) and the
+//! tracked write used by tool modules.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use chrono::Local;
+
+use super::types::FileChange;
+
+/// Entries kept in the history file (oldest dropped first)
+const MAX_ENTRIES: usize = 200;
+
+/// Files larger than this are written but not snapshotted
+const MAX_SNAPSHOT_BYTES: usize = 2 * 1024 * 1024;
+
+/// Writes of one scope closer together than this form one change
+const GROUP_WINDOW: Duration = Duration::from_secs(2);
+
+/// Serializes access to the history file
+static HISTORY_LOCK: Mutex<()> = Mutex::new(());
+
+/// Last write per scope: (time, group id)
+static LAST_WRITE: OnceLock<Mutex<HashMap<String, (Instant, String)>>> = OnceLock::new();
+
+fn history_path() -> Option<PathBuf> {
+    dirs::data_dir()
+        .map(|p| p.join("com.ai-toolbox"))
+        .or_else(|| dirs::home_dir().map(|p| p.join(".ai-toolbox")))
+        .map(|dir| dir.join("change_history.json"))
+}
+
+fn load(path: &Path) -> Vec<FileChange> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save(path: &Path, entries: &[FileChange]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create app data dir: {}", e))?;
+    }
+    let content =
+        serde_json::to_string(entries).map_err(|e| format!("Failed to serialize change history: {}", e))?;
+    fs::write(path, content).map_err(|e| format!("Failed to write change history: {}", e))
+}
+
+/// Run `f` on the history with the file locked, saving it afterwards
+pub fn with_history<T>(f: impl FnOnce(&mut Vec<FileChange>) -> T) -> Result<T, String> {
+    let path = history_path().ok_or_else(|| "Failed to get app data dir".to_string())?;
+    let _guard = HISTORY_LOCK.lock().map_err(|e| e.to_string())?;
+    let mut entries = load(&path);
+    let result = f(&mut entries);
+    if entries.len() > MAX_ENTRIES {
+        let excess = entries.len() - MAX_ENTRIES;
+        entries.drain(..excess);
+    }
+    save(&path, &entries)?;
+    Ok(result)
+}
+
+/// Group id for a write of `scope` now
+fn group_for(scope: &str) -> String {
+    let mut last = LAST_WRITE
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let now = Instant::now();
+    let group_id = match last.get(scope) {
+        Some((at, group_id)) if now.duration_since(*at) < GROUP_WINDOW => group_id.clone(),
+        _ => uuid::Uuid::new_v4().to_string(),
+    };
+    last.insert(scope.to_string(), (now, group_id.clone()));
+    group_id
+}
+
+/// Content before a write: Some(None) when the file does not exist,
+/// None when it cannot be snapshotted (unreadable, binary or too large)
+fn read_before(path: &Path) -> Option<Option<String>> {
+    match fs::read(path) {
+        Ok(bytes) if bytes.len() <= MAX_SNAPSHOT_BYTES => String::from_utf8(bytes).ok().map(Some),
+        Ok(_) => None,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Some(None),
+        Err(_) => None,
+    }
+}
+
+/// Atomic write (see `config_format::atomic_write`) that records the change in the history of `scope`
+/// (during a dry run the write is only recorded, see `preview`)
+pub fn write_config(scope: &str, path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    let path = path.as_ref();
+    let contents = contents.as_ref();
+    if super::preview::capture_write(scope, path, contents) {
+        return Ok(());
+    }
+    let before = read_before(path);
+
+    crate::coding::config_format::atomic_write(path, contents)?;
+
+    let after = match std::str::from_utf8(contents) {
+        Ok(after) if contents.len() <= MAX_SNAPSHOT_BYTES => after,
+        _ => return Ok(()),
+    };
+    let Some(before) = before else {
+        return Ok(());
+    };
+    if before.as_deref() == Some(after) {
+        return Ok(());
+    }
+
+    let change = FileChange {
+        id: uuid::Uuid::new_v4().to_string(),
+        scope: scope.to_string(),
+        group_id: group_for(scope),
+        path: path.to_string_lossy().to_string(),
+        before,
+        after: after.to_string(),
+        timestamp: Local::now().to_rfc3339(),
+        undone: false,
+    };
+    if let Err(e) = with_history(|entries| entries.push(change)) {
+        log::warn!("Failed to record config change: {}", e);
+    }
+    Ok(())
+}
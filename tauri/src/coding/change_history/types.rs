@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+/// One config file write
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileChange {
+    pub id: String,
+    /// Module that wrote the file, e.g. "claude_code", "mcp"
+    pub scope: String,
+    /// Writes of one operation (e.g. settings + .env) share the same group id
+    pub group_id: String,
+    pub path: String,
+    /// Content before the write; None when the file did not exist
+    pub before: Option<String>,
+    pub after: String,
+    /// RFC 3339 timestamp
+    pub timestamp: String,
+    #[serde(default)]
+    pub undone: bool,
+}
+
+/// History entry as listed in the UI (without file contents)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeSummary {
+    pub group_id: String,
+    pub scope: String,
+    pub files: Vec<String>,
+    pub timestamp: String,
+    pub undone: bool,
+}
+
+/// Result of `undo_last_change`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UndoResult {
+    pub scope: String,
+    /// Restored files
+    pub files: Vec<String>,
+    /// When the undone change was made
+    pub timestamp: String,
+}
+
+/// A config write of a dry run, as a unified diff
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigDiff {
+    /// Module that would write the file
+    pub scope: String,
+    pub path: String,
+    /// The file does not exist yet
+    pub created: bool,
+    pub before: Option<String>,
+    pub after: String,
+    /// `diff -u` style text
+    pub diff: String,
+}
@@ -64,6 +64,7 @@ pub fn from_db_value_provider(value: Value) -> ClaudeCodeProvider {
         sort_index: get_i64_compat(&value, "sort_index", "sortIndex"),
         is_applied: get_bool_compat(&value, "is_applied", "isApplied", false),
         is_disabled: get_bool_compat(&value, "is_disabled", "isDisabled", false),
+        is_archived: get_bool_compat(&value, "is_archived", "isArchived", false),
         created_at: get_str_compat(&value, "created_at", "createdAt", ""),
         updated_at: get_str_compat(&value, "updated_at", "updatedAt", ""),
     }
@@ -72,7 +73,7 @@ pub fn from_db_value_provider(value: Value) -> ClaudeCodeProvider {
 /// Convert ClaudeCodeProviderContent to database Value
 pub fn to_db_value_provider(content: &ClaudeCodeProviderContent) -> Value {
     serde_json::to_value(content).unwrap_or_else(|e| {
-        eprintln!("Failed to serialize provider content: {}", e);
+        log::warn!("Failed to serialize provider content: {}", e);
         json!({})
     })
 }
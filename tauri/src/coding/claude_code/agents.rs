@@ -0,0 +1,331 @@
+//! Claude sub-agents
+//!
+//! Agent definition files under `~/.claude/agents/<name>.md` (front matter
+//! with name / description / tools / model, then the system prompt).
+//! Reusable presets live in `claude_agent_preset`; enabled presets are
+//! written to the agents directory whenever a Claude provider is applied.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::coding::{change_history, db_extract_id};
+use crate::db::DbState;
+use crate::events::{emit_change, ChangeAction};
+use super::commands::get_claude_config_path;
+use super::front_matter;
+use super::types::{ClaudeAgent, ClaudeAgentInput, ClaudeAgentPreset};
+
+type Db = surrealdb::Surreal<surrealdb::engine::local::Db>;
+
+/// Front matter of an agent file
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct AgentMeta {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    description: String,
+    /// Comma-separated tool names; all tools when missing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tools: Option<serde_yaml::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    model: Option<String>,
+}
+
+fn agents_dir() -> Result<PathBuf, String> {
+    let settings = PathBuf::from(get_claude_config_path()?);
+    Ok(settings.parent().unwrap_or(Path::new(".")).join("agents"))
+}
+
+/// Agent names are file names: letters, digits, '-' and '_'
+fn validate(input: &ClaudeAgentInput) -> Result<(), String> {
+    let name_ok = !input.name.is_empty()
+        && input
+            .name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'));
+    if !name_ok {
+        return Err(format!("Invalid agent name: {}", input.name));
+    }
+    if input.description.trim().is_empty() {
+        return Err(format!("Agent '{}' needs a description", input.name));
+    }
+    Ok(())
+}
+
+/// `tools` as written by hand: "Read, Grep" or a YAML list
+fn parse_tools(value: Option<&serde_yaml::Value>) -> Vec<String> {
+    match value {
+        Some(serde_yaml::Value::String(tools)) => tools
+            .split(',')
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(String::from)
+            .collect(),
+        Some(serde_yaml::Value::Sequence(tools)) => tools
+            .iter()
+            .filter_map(|t| t.as_str())
+            .map(String::from)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn render(input: &ClaudeAgentInput) -> Result<String, String> {
+    let tools: Vec<&str> = input.tools.iter().map(|t| t.trim()).filter(|t| !t.is_empty()).collect();
+    let meta = AgentMeta {
+        name: input.name.clone(),
+        description: input.description.trim().to_string(),
+        tools: (!tools.is_empty()).then(|| serde_yaml::Value::String(tools.join(", "))),
+        model: input.model.clone().filter(|m| !m.trim().is_empty()),
+    };
+    front_matter::render(&meta, &input.prompt)
+}
+
+fn read_agent(path: &Path) -> Option<ClaudeAgent> {
+    let content = fs::read_to_string(path).ok()?;
+    let (meta, prompt) = front_matter::parse::<AgentMeta>(&content).ok()?;
+    let file_name = path.file_stem()?.to_string_lossy().to_string();
+    let updated_at = fs::metadata(path)
+        .and_then(|m| m.modified())
+        .map(|time| DateTime::<Local>::from(time).to_rfc3339())
+        .unwrap_or_default();
+    Some(ClaudeAgent {
+        name: if meta.name.is_empty() { file_name.clone() } else { meta.name.clone() },
+        file_name,
+        description: meta.description,
+        tools: parse_tools(meta.tools.as_ref()),
+        model: meta.model,
+        prompt,
+        path: path.to_string_lossy().to_string(),
+        updated_at,
+    })
+}
+
+/// Agent files in `dir`, by name
+fn list_in(dir: &Path) -> Vec<ClaudeAgent> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut agents: Vec<ClaudeAgent> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|e| e.to_str()) == Some("md"))
+        .filter_map(|path| read_agent(&path))
+        .collect();
+    agents.sort_by(|a, b| a.name.cmp(&b.name));
+    agents
+}
+
+/// Write an agent file; returns false when it already had this content
+fn write_agent(dir: &Path, input: &ClaudeAgentInput) -> Result<bool, String> {
+    validate(input)?;
+    let path = dir.join(format!("{}.md", input.name));
+    let content = render(input)?;
+    if change_history::read_config(&path).ok().as_deref() == Some(content.as_str()) {
+        return Ok(false);
+    }
+    change_history::write_config("claude_code", &path, content)
+        .map_err(|e| format!("Failed to write agent file: {}", e))?;
+    Ok(true)
+}
+
+fn preset_from_db_value(mut value: Value) -> Option<ClaudeAgentPreset> {
+    let id = db_extract_id(&value);
+    value.as_object_mut()?.insert("id".to_string(), Value::String(id));
+    serde_json::from_value(value).ok()
+}
+
+async fn load_presets(db: &Db) -> Result<Vec<ClaudeAgentPreset>, String> {
+    let records: Vec<Value> = db
+        .query("SELECT *, type::string(id) as id FROM claude_agent_preset")
+        .await
+        .map_err(|e| format!("Failed to query agent presets: {}", e))?
+        .take(0)
+        .map_err(|e| format!("Failed to parse agent presets: {}", e))?;
+    let mut presets: Vec<ClaudeAgentPreset> = records.into_iter().filter_map(preset_from_db_value).collect();
+    presets.sort_by(|a, b| a.agent.name.cmp(&b.agent.name));
+    Ok(presets)
+}
+
+/// Write every enabled preset to ~/.claude/agents; returns the names of changed files
+pub async fn sync_presets(db: &Db) -> Result<Vec<String>, String> {
+    let presets = load_presets(db).await?;
+    let dir = agents_dir()?;
+    let mut written = Vec::new();
+    for preset in presets.iter().filter(|p| p.enabled) {
+        if write_agent(&dir, &preset.agent)? {
+            written.push(preset.agent.name.clone());
+        }
+    }
+    Ok(written)
+}
+
+// ============================================================================
+// Claude Agent Commands
+// ============================================================================
+
+/// Agent files under ~/.claude/agents, by name
+#[tauri::command]
+pub fn list_claude_agents() -> Result<Vec<ClaudeAgent>, String> {
+    Ok(list_in(&agents_dir()?))
+}
+
+/// Create or edit an agent file (`<name>.md`); `original_file_name` is the
+/// file of the edited agent, removed when the name changed
+#[tauri::command]
+pub fn save_claude_agent(
+    app: tauri::AppHandle,
+    input: ClaudeAgentInput,
+    original_file_name: Option<String>,
+) -> Result<ClaudeAgent, String> {
+    let dir = agents_dir()?;
+    write_agent(&dir, &input)?;
+    if let Some(original) = original_file_name.filter(|n| *n != input.name) {
+        delete_agent_file(&dir, &original)?;
+    }
+    let agent = read_agent(&dir.join(format!("{}.md", input.name)))
+        .ok_or_else(|| format!("Failed to read agent file: {}", input.name))?;
+    emit_change(&app, "claude_agent", ChangeAction::Updated, Some(&agent.name), "window");
+    Ok(agent)
+}
+
+fn delete_agent_file(dir: &Path, name: &str) -> Result<(), String> {
+    if name.is_empty() || name.contains(['/', '\\']) || name.contains("..") {
+        return Err(format!("Invalid agent name: {}", name));
+    }
+    let path = dir.join(format!("{}.md", name));
+    fs::remove_file(&path).map_err(|e| format!("Failed to delete {}: {}", path.display(), e))
+}
+
+/// Delete `<file_name>.md` from ~/.claude/agents
+#[tauri::command]
+pub fn delete_claude_agent(app: tauri::AppHandle, file_name: String) -> Result<(), String> {
+    delete_agent_file(&agents_dir()?, &file_name)?;
+    emit_change(&app, "claude_agent", ChangeAction::Deleted, Some(&file_name), "window");
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn list_claude_agent_presets(state: tauri::State<'_, DbState>) -> Result<Vec<ClaudeAgentPreset>, String> {
+    let db = state.0.lock().await;
+    load_presets(&db).await
+}
+
+/// Create (without `id`) or update an agent preset; enabled presets are synced to disk on apply
+#[tauri::command]
+pub async fn save_claude_agent_preset(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: Option<String>,
+    agent: ClaudeAgentInput,
+    enabled: bool,
+) -> Result<ClaudeAgentPreset, String> {
+    validate(&agent)?;
+    let id = id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let db = state.0.lock().await;
+    db.query("UPSERT type::thing('claude_agent_preset', $id) SET agent = $agent, enabled = $enabled, updated_at = $now, created_at = created_at ?? $now")
+        .bind(("id", id.clone()))
+        .bind(("agent", serde_json::to_value(&agent).map_err(|e| format!("Failed to serialize agent: {}", e))?))
+        .bind(("enabled", enabled))
+        .bind(("now", Local::now().to_rfc3339()))
+        .await
+        .map_err(|e| format!("Failed to save agent preset: {}", e))?;
+    let preset = load_presets(&db)
+        .await?
+        .into_iter()
+        .find(|p| p.id == id)
+        .ok_or_else(|| format!("Agent preset not found: {}", id))?;
+    emit_change(&app, "claude_agent_preset", ChangeAction::Updated, Some(&id), "window");
+    Ok(preset)
+}
+
+#[tauri::command]
+pub async fn delete_claude_agent_preset(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+) -> Result<(), String> {
+    let db = state.0.lock().await;
+    db.query("DELETE type::thing('claude_agent_preset', $id)")
+        .bind(("id", id.clone()))
+        .await
+        .map_err(|e| format!("Failed to delete agent preset: {}", e))?;
+    emit_change(&app, "claude_agent_preset", ChangeAction::Deleted, Some(&id), "window");
+    Ok(())
+}
+
+/// Write enabled presets to ~/.claude/agents now; returns the names of changed files
+#[tauri::command]
+pub async fn sync_claude_agent_presets(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+) -> Result<Vec<String>, String> {
+    let db = state.0.lock().await;
+    let written = sync_presets(&db).await?;
+    emit_change(&app, "claude_agent", ChangeAction::Synced, None, "window");
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_agent_front_matter() {
+        let dir = std::env::temp_dir().join(format!("aitb-agents-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("reviewer.md");
+        fs::write(
+            &path,
+            "---\nname: code-reviewer\ndescription: Reviews diffs\ntools: Read, Grep,Glob\ncolor: blue\n---\n\nYou review code.\n",
+        )
+        .unwrap();
+
+        let agent = read_agent(&path).unwrap();
+        assert_eq!(agent.name, "code-reviewer");
+        assert_eq!(agent.file_name, "reviewer");
+        assert_eq!(agent.tools, vec!["Read", "Grep", "Glob"]);
+        assert_eq!(agent.model, None);
+        assert_eq!(agent.prompt, "You review code.\n");
+        assert_eq!(list_in(&dir).len(), 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn renders_tools_as_comma_list() {
+        let input = ClaudeAgentInput {
+            name: "tester".to_string(),
+            description: "Runs tests".to_string(),
+            tools: vec!["Bash".to_string(), "Read".to_string()],
+            model: Some("sonnet".to_string()),
+            prompt: "Run the tests.".to_string(),
+        };
+        let content = render(&input).unwrap();
+        assert!(content.contains("tools: Bash, Read\n"));
+        assert!(content.contains("model: sonnet\n"));
+        assert!(content.ends_with("---\n\nRun the tests.\n"));
+        assert!(validate(&ClaudeAgentInput { name: "../x".to_string(), ..input }).is_err());
+    }
+
+    #[test]
+    fn dry_run_does_not_create_agents_dir() {
+        let dir = std::env::temp_dir().join(format!("aitb-agents-{}", uuid::Uuid::new_v4()));
+        let input = ClaudeAgentInput {
+            name: "tester".to_string(),
+            description: "Runs tests".to_string(),
+            tools: Vec::new(),
+            model: None,
+            prompt: "Run the tests.".to_string(),
+        };
+
+        let diffs = change_history::preview_sync(true, || write_agent(&dir, &input)).unwrap();
+        assert_eq!(diffs.len(), 1);
+        assert!(diffs[0].created);
+        assert!(!dir.exists());
+    }
+}
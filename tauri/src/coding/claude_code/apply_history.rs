@@ -0,0 +1,235 @@
+//! Apply history
+//!
+//! Every provider apply (window, tray, failover) is logged in `apply_history`
+//! with the file it wrote, a copy of it and its SHA-256, so "what changed my
+//! settings yesterday?" can be answered and an earlier state written back with
+//! `revert_claude_apply`. Only the newest `MAX_ENTRIES` are kept.
+//!
+//! Keys the apply resolved from the vault are stored as their `vault://`
+//! references again and resolved anew on revert, so the history (and every
+//! database backup) holds no more secrets than the providers themselves.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::Local;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::coding::{change_history, vault};
+use crate::db::DbState;
+use crate::error::{AppError, AppResult};
+use crate::events::{emit_change, ChangeAction};
+use super::commands::{get_claude_config_path, set_applied_flag};
+use super::local_settings::{load_apply_target, local_settings_path};
+use super::types::ClaudeApplyHistoryEntry;
+
+type Db = surrealdb::Surreal<surrealdb::engine::local::Db>;
+
+const MAX_ENTRIES: usize = 200;
+const DEFAULT_LIMIT: usize = 50;
+
+pub fn content_hash(content: &str) -> String {
+    hex::encode(Sha256::digest(content.as_bytes()))
+}
+
+/// Log the file an apply of `provider_id` just wrote
+/// Failures are logged; history must never block an apply.
+pub async fn record(db: &Db, provider_id: &str, source: &str) {
+    if let Err(e) = try_record(db, provider_id, source).await {
+        log::warn!("Failed to record apply history: {}", e);
+    }
+}
+
+/// Vault keys the provider and the common config reference
+async fn applied_secrets(db: &Db, provider_id: &str) -> AppResult<Vec<(String, String)>> {
+    let provider_configs: Vec<String> = db
+        .query("SELECT VALUE settings_config FROM claude_provider WHERE id = type::thing('claude_provider', $id)")
+        .bind(("id", provider_id.to_string()))
+        .await
+        .and_then(|mut result| result.take(0))
+        .unwrap_or_default();
+    let common_configs: Vec<String> = db
+        .query("SELECT VALUE config FROM claude_common_config:`common`")
+        .await
+        .and_then(|mut result| result.take(0))
+        .unwrap_or_default();
+
+    let mut secrets = Vec::new();
+    for config in provider_configs.iter().chain(common_configs.iter()) {
+        if let Ok(config) = serde_json::from_str::<Value>(config) {
+            secrets.extend(vault::referenced_secrets(&config)?);
+        }
+    }
+    Ok(secrets)
+}
+
+/// `content` with the resolved keys replaced by their references
+fn with_references(content: &str, secrets: &[(String, String)]) -> AppResult<String> {
+    let mut settings: Value = serde_json::from_str(content).map_err(AppError::invalid)?;
+    vault::restore_references(&mut settings, secrets);
+    serde_json::to_string_pretty(&settings).map_err(AppError::invalid)
+}
+
+async fn try_record(db: &Db, provider_id: &str, source: &str) -> AppResult<()> {
+    let target = load_apply_target(db).await;
+    let path = if target == "local" {
+        local_settings_path()?
+    } else {
+        PathBuf::from(get_claude_config_path().map_err(AppError::io)?)
+    };
+    let content = fs::read_to_string(&path)?;
+    let content = with_references(&content, &applied_secrets(db, provider_id).await?)?;
+    insert_entry(db, provider_id, &target, &path.to_string_lossy(), &content, source).await
+}
+
+async fn insert_entry(
+    db: &Db,
+    provider_id: &str,
+    target: &str,
+    path: &str,
+    content: &str,
+    source: &str,
+) -> AppResult<()> {
+    let provider_name: Option<String> = db
+        .query("SELECT VALUE name FROM claude_provider WHERE id = type::thing('claude_provider', $id) LIMIT 1")
+        .bind(("id", provider_id.to_string()))
+        .await
+        .ok()
+        .and_then(|mut result| result.take::<Vec<String>>(0).ok())
+        .and_then(|names| names.into_iter().next());
+
+    db.query("CREATE apply_history CONTENT $data")
+        .bind((
+            "data",
+            serde_json::json!({
+                "provider_id": provider_id,
+                "provider_name": provider_name.unwrap_or_default(),
+                "target": target,
+                "path": path,
+                "settings_hash": content_hash(content),
+                "settings": content,
+                "source": source,
+                "created_at": Local::now().to_rfc3339(),
+            }),
+        ))
+        .await?;
+
+    // Everything older than the MAX_ENTRIES-th newest entry goes
+    let cutoff: Vec<String> = db
+        .query("SELECT VALUE created_at FROM apply_history ORDER BY created_at DESC LIMIT 1 START $skip")
+        .bind(("skip", MAX_ENTRIES as i64 - 1))
+        .await
+        .and_then(|mut result| result.take(0))?;
+    if let Some(cutoff) = cutoff.into_iter().next() {
+        db.query("DELETE apply_history WHERE created_at < $cutoff")
+            .bind(("cutoff", cutoff))
+            .await?;
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Claude Apply History Commands
+// ============================================================================
+
+/// Applies, newest first
+#[tauri::command]
+pub async fn list_claude_apply_history(
+    state: tauri::State<'_, DbState>,
+    limit: Option<usize>,
+) -> AppResult<Vec<ClaudeApplyHistoryEntry>> {
+    let db = state.0.lock().await;
+    let records: Vec<Value> = db
+        .query("SELECT *, type::string(id) AS id OMIT settings FROM apply_history ORDER BY created_at DESC LIMIT $limit")
+        .bind(("limit", limit.unwrap_or(DEFAULT_LIMIT) as i64))
+        .await?
+        .take(0)?;
+    Ok(records
+        .into_iter()
+        .filter_map(|mut record| {
+            let id = crate::coding::db_extract_id(&record);
+            record.as_object_mut()?.insert("id".to_string(), Value::String(id));
+            serde_json::from_value(record).ok()
+        })
+        .collect())
+}
+
+/// Write the settings file of an earlier apply back as it was and mark its
+/// provider applied again (when it still exists). The revert is logged too.
+#[tauri::command]
+pub async fn revert_claude_apply(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+    let records: Vec<Value> = db
+        .query("SELECT * FROM type::thing('apply_history', $id)")
+        .bind(("id", id.clone()))
+        .await?
+        .take(0)?;
+    let entry = records
+        .into_iter()
+        .next()
+        .ok_or_else(|| AppError::not_found("HISTORY_ENTRY_NOT_FOUND", &[("id", id.as_str())]))?;
+    let field = |key: &str| entry.get(key).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+    let (provider_id, path, content) = (field("provider_id"), field("path"), field("settings"));
+    if path.is_empty() {
+        return Err(AppError::invalid(format!("apply history entry '{}' has no file path", id)));
+    }
+    if content_hash(&content) != field("settings_hash") {
+        return Err(AppError::invalid(format!("apply history entry '{}' is corrupted", id)));
+    }
+    let mut settings: Value = serde_json::from_str(&content).map_err(AppError::invalid)?;
+    vault::resolve_references(&mut settings)?;
+    let resolved = serde_json::to_string_pretty(&settings).map_err(AppError::invalid)?;
+
+    // Back up the file that is about to be overwritten
+    if Path::new(&path) == local_settings_path()? {
+        super::backup::backup_local_settings().map_err(AppError::io)?;
+    } else {
+        super::backup::backup_settings().map_err(AppError::io)?;
+    }
+    change_history::write_config("claude_code", &path, resolved)?;
+
+    // The applied flag follows the file: cleared when its provider is gone or disabled
+    let provider_exists: Vec<String> = db
+        .query("SELECT VALUE type::string(id) FROM type::thing('claude_provider', $id) WHERE is_archived != true AND is_disabled != true")
+        .bind(("id", provider_id.clone()))
+        .await
+        .and_then(|mut result| result.take(0))
+        .unwrap_or_default();
+    let applied = (!provider_exists.is_empty()).then_some(provider_id.as_str());
+    set_applied_flag(&db, applied).await?;
+    if let Err(e) = insert_entry(&db, &provider_id, &field("target"), &path, &content, "revert").await {
+        log::warn!("Failed to record apply history: {}", e);
+    }
+
+    crate::activity_log::record(
+        &app,
+        "provider",
+        format!("Claude Code 配置已恢复到 {} 的状态", field("created_at")),
+    );
+    emit_change(&app, "claude_provider", ChangeAction::Applied, None, "window");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stores_vault_references_instead_of_keys() {
+        let secrets = vec![("sk-live-123".to_string(), "vault://k1".to_string())];
+        let content = with_references(r#"{"env":{"ANTHROPIC_AUTH_TOKEN":"sk-live-123"}}"#, &secrets).unwrap();
+        assert!(!content.contains("sk-live-123"));
+        assert!(content.contains("vault://k1"));
+    }
+
+    #[test]
+    fn hashes_content_as_sha256_hex() {
+        assert_eq!(content_hash("{}"), "44136fa355b3678a1146ad16f7e8649e94fb4fc21fe77e8310c060f61caaff8a");
+        assert_ne!(content_hash("{}"), content_hash("{ }"));
+    }
+}
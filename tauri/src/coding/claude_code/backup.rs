@@ -0,0 +1,191 @@
+//! settings.json backups
+//!
+//! Before a provider is applied, the current `~/.claude/settings.json` is
+//! copied to `~/.claude/backups/settings-<timestamp>.json`, so hand edits can
+//! be restored with `rollback_claude_settings`. Only the newest
+//! `MAX_BACKUPS` copies are kept. settings.local.json is backed up the same
+//! way (as `settings.local-<timestamp>.json`) before it is overwritten.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local};
+
+use crate::coding::change_history;
+use crate::events::{emit_change, ChangeAction};
+use super::commands::get_claude_config_path;
+use super::local_settings::local_settings_path;
+use super::types::ClaudeSettingsBackup;
+
+const MAX_BACKUPS: usize = 20;
+const BACKUP_PREFIX: &str = "settings-";
+const LOCAL_BACKUP_PREFIX: &str = "settings.local-";
+
+fn settings_path() -> Result<PathBuf, String> {
+    get_claude_config_path().map(PathBuf::from)
+}
+
+fn backup_dir(settings: &Path) -> PathBuf {
+    settings
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join("backups")
+}
+
+/// Backup ids are file stems created by `backup_into`; anything else (e.g. a path) is rejected
+fn is_backup_id(id: &str) -> bool {
+    has_prefix_and_stamp(id, BACKUP_PREFIX)
+}
+
+fn has_prefix_and_stamp(id: &str, prefix: &str) -> bool {
+    id.strip_prefix(prefix)
+        .is_some_and(|stamp| !stamp.is_empty() && stamp.chars().all(|c| c.is_ascii_digit() || c == '-'))
+}
+
+/// Backups with `prefix` in `dir`, newest first
+fn list_in(dir: &Path, prefix: &str) -> Vec<ClaudeSettingsBackup> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut backups: Vec<ClaudeSettingsBackup> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                return None;
+            }
+            let id = path.file_stem()?.to_str()?.to_string();
+            if !has_prefix_and_stamp(&id, prefix) {
+                return None;
+            }
+            let metadata = entry.metadata().ok()?;
+            let created_at = metadata
+                .modified()
+                .map(|time| DateTime::<Local>::from(time).to_rfc3339())
+                .unwrap_or_default();
+            Some(ClaudeSettingsBackup {
+                id,
+                path: path.to_string_lossy().to_string(),
+                created_at,
+                size: metadata.len(),
+            })
+        })
+        .collect();
+    // Ids embed the timestamp, so they sort chronologically
+    backups.sort_by(|a, b| b.id.cmp(&a.id));
+    backups
+}
+
+/// Copy `settings` into `dir` unless it is missing or equal to the newest backup,
+/// then prune old copies. Returns the new backup id.
+fn backup_into(settings: &Path, dir: &Path, prefix: &str) -> io::Result<Option<String>> {
+    let Ok(content) = fs::read(settings) else {
+        return Ok(None);
+    };
+    let existing = list_in(dir, prefix);
+    if let Some(newest) = existing.first() {
+        if fs::read(&newest.path).ok().as_deref() == Some(content.as_slice()) {
+            return Ok(None);
+        }
+    }
+
+    fs::create_dir_all(dir)?;
+    let id = format!("{}{}", prefix, Local::now().format("%Y%m%d-%H%M%S-%3f"));
+    fs::write(dir.join(format!("{}.json", id)), &content)?;
+
+    for old in list_in(dir, prefix).iter().skip(MAX_BACKUPS) {
+        let _ = fs::remove_file(&old.path);
+    }
+    Ok(Some(id))
+}
+
+/// Back up settings.json before it is overwritten by a provider apply
+pub fn backup_settings() -> Result<Option<String>, String> {
+    let settings = settings_path()?;
+    backup_into(&settings, &backup_dir(&settings), BACKUP_PREFIX)
+        .map_err(|e| format!("Failed to back up settings.json: {}", e))
+}
+
+/// Back up settings.local.json before it is overwritten
+pub fn backup_local_settings() -> Result<Option<String>, String> {
+    let local = local_settings_path()?;
+    backup_into(&local, &backup_dir(&local), LOCAL_BACKUP_PREFIX)
+        .map_err(|e| format!("Failed to back up settings.local.json: {}", e))
+}
+
+// ============================================================================
+// Claude Settings Backup Commands
+// ============================================================================
+
+/// Backups of ~/.claude/settings.json, newest first
+#[tauri::command]
+pub fn list_claude_settings_backups() -> Result<Vec<ClaudeSettingsBackup>, String> {
+    let settings = settings_path()?;
+    Ok(list_in(&backup_dir(&settings), BACKUP_PREFIX))
+}
+
+/// Restore settings.json from a backup; the current file is backed up first
+#[tauri::command]
+pub fn rollback_claude_settings(app: tauri::AppHandle, backup_id: String) -> Result<(), String> {
+    if !is_backup_id(&backup_id) {
+        return Err(format!("Invalid backup id: {}", backup_id));
+    }
+    let settings = settings_path()?;
+    let backup = backup_dir(&settings).join(format!("{}.json", backup_id));
+    let content = fs::read_to_string(&backup)
+        .map_err(|e| format!("Failed to read backup {}: {}", backup_id, e))?;
+    serde_json::from_str::<serde_json::Value>(&content)
+        .map_err(|e| format!("Backup {} is not valid JSON: {}", backup_id, e))?;
+
+    backup_settings()?;
+    change_history::write_config("claude_code", &settings, content)
+        .map_err(|e| format!("Failed to write settings file: {}", e))?;
+
+    emit_change(&app, "claude_settings", ChangeAction::Updated, Some(&backup_id), "window");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backup_skips_unchanged_settings_and_prunes() {
+        let dir = std::env::temp_dir().join(format!("aitb-claude-backup-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let settings = dir.join("settings.json");
+        let backups = backup_dir(&settings);
+
+        assert_eq!(backup_into(&settings, &backups, BACKUP_PREFIX).unwrap(), None);
+
+        fs::write(&settings, "{\"env\":{}}").unwrap();
+        let id = backup_into(&settings, &backups, BACKUP_PREFIX).unwrap().unwrap();
+        assert!(is_backup_id(&id));
+        assert_eq!(backup_into(&settings, &backups, BACKUP_PREFIX).unwrap(), None);
+
+        // settings.local.json copies never show up as settings.json backups
+        let local = dir.join("settings.local.json");
+        fs::write(&local, "{\"env\":{\"A\":\"1\"}}").unwrap();
+        let local_id = backup_into(&local, &backups, LOCAL_BACKUP_PREFIX).unwrap().unwrap();
+        assert!(!is_backup_id(&local_id));
+        assert_eq!(list_in(&backups, BACKUP_PREFIX).len(), 1);
+
+        for i in 0..MAX_BACKUPS + 2 {
+            fs::write(&settings, format!("{{\"n\":{}}}", i)).unwrap();
+            backup_into(&settings, &backups, BACKUP_PREFIX).unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+        assert_eq!(list_in(&backups, BACKUP_PREFIX).len(), MAX_BACKUPS);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn backup_id_rejects_paths() {
+        assert!(is_backup_id("settings-20260301-101500-123"));
+        assert!(!is_backup_id("../settings"));
+        assert!(!is_backup_id("settings-../../x"));
+        assert!(!is_backup_id("settings-"));
+    }
+}
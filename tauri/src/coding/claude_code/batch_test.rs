@@ -0,0 +1,180 @@
+//! Batch test of all Claude providers
+//!
+//! Sends the `test_claude_provider` request to every non-archived provider at
+//! once (at most `CONCURRENCY` in flight), emits a progress event as each one
+//! finishes and returns the results ranked: reachable providers by latency,
+//! then the failed ones. The latest result per provider is kept in
+//! `claude_provider_test` so the ranking survives a restart.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use chrono::Local;
+use futures_util::stream::{self, StreamExt};
+use serde_json::Value;
+use tauri::Emitter;
+
+use crate::db::DbState;
+use crate::error::{AppError, AppResult};
+use super::adapter;
+use super::commands::test_provider;
+use super::types::{ClaudeProviderTestEntry, ClaudeProviderTestProgress, ClaudeProviderTestResult};
+
+type Db = surrealdb::Surreal<surrealdb::engine::local::Db>;
+
+pub const TEST_PROGRESS_EVENT: &str = "claude-provider-test-progress";
+const CONCURRENCY: usize = 8;
+
+/// Successful first by latency, then failed by name
+pub fn rank(entries: &mut [ClaudeProviderTestEntry]) {
+    entries.sort_by(|a, b| {
+        b.result
+            .success
+            .cmp(&a.result.success)
+            .then_with(|| match a.result.success {
+                true => a.result.latency_ms.cmp(&b.result.latency_ms),
+                false => a.provider_name.cmp(&b.provider_name),
+            })
+    });
+}
+
+/// Drop the cached result of a deleted provider
+pub async fn delete_result(db: &Db, provider_id: &str) {
+    if let Err(e) = db
+        .query("DELETE type::thing('claude_provider_test', $id)")
+        .bind(("id", provider_id.to_string()))
+        .await
+    {
+        log::warn!("Failed to delete provider test result: {}", e);
+    }
+}
+
+async fn save_result(db: &Db, entry: &ClaudeProviderTestEntry) -> AppResult<()> {
+    let data = serde_json::to_value(entry).map_err(AppError::invalid)?;
+    db.query("UPSERT type::thing('claude_provider_test', $id) CONTENT $data")
+        .bind(("id", entry.provider_id.clone()))
+        .bind(("data", data))
+        .await?;
+    Ok(())
+}
+
+// ============================================================================
+// Claude Provider Batch Test Commands
+// ============================================================================
+
+/// Test every non-archived provider concurrently and return the ranked results
+#[tauri::command]
+pub async fn test_all_claude_providers(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    model: Option<String>,
+) -> AppResult<Vec<ClaudeProviderTestEntry>> {
+    let providers: Vec<_> = {
+        let db = state.0.lock().await;
+        let records: Vec<Value> = db
+            .query("SELECT *, type::string(id) as id FROM claude_provider WHERE is_archived != true ORDER BY sort_index ASC")
+            .await?
+            .take(0)?;
+        records.into_iter().map(adapter::from_db_value_provider).collect()
+    };
+
+    let total = providers.len();
+    let done = AtomicUsize::new(0);
+    let state = &*state;
+    let mut entries: Vec<ClaudeProviderTestEntry> = stream::iter(providers)
+        .map(|provider| {
+            let (app, model, done) = (&app, model.clone(), &done);
+            async move {
+                // Providers that cannot be tested (no key, Bedrock / Vertex) count as failed
+                let result = test_provider(state, &provider, model)
+                    .await
+                    .unwrap_or_else(|error| ClaudeProviderTestResult {
+                        success: false,
+                        status: None,
+                        latency_ms: 0,
+                        model: None,
+                        error: Some(error.to_string()),
+                    });
+                let entry = ClaudeProviderTestEntry {
+                    provider_id: provider.id,
+                    provider_name: provider.name,
+                    result,
+                    tested_at: Local::now().to_rfc3339(),
+                };
+                let _ = app.emit(
+                    TEST_PROGRESS_EVENT,
+                    ClaudeProviderTestProgress {
+                        done: done.fetch_add(1, Ordering::SeqCst) + 1,
+                        total,
+                        entry: entry.clone(),
+                    },
+                );
+                entry
+            }
+        })
+        .buffer_unordered(CONCURRENCY)
+        .collect()
+        .await;
+
+    let db = state.0.lock().await;
+    for entry in &entries {
+        save_result(&db, entry).await?;
+    }
+    rank(&mut entries);
+    Ok(entries)
+}
+
+/// Cached results of the last batch tests, ranked
+#[tauri::command]
+pub async fn get_claude_provider_test_results(
+    state: tauri::State<'_, DbState>,
+) -> AppResult<Vec<ClaudeProviderTestEntry>> {
+    let db = state.0.lock().await;
+    let records: Vec<Value> = db
+        .query("SELECT * OMIT id FROM claude_provider_test")
+        .await?
+        .take(0)?;
+    let mut entries: Vec<ClaudeProviderTestEntry> = records
+        .into_iter()
+        .filter_map(|record| serde_json::from_value(record).ok())
+        .collect();
+    rank(&mut entries);
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, success: bool, latency_ms: u64) -> ClaudeProviderTestEntry {
+        ClaudeProviderTestEntry {
+            provider_id: name.to_lowercase(),
+            provider_name: name.to_string(),
+            result: ClaudeProviderTestResult {
+                success,
+                status: success.then_some(200),
+                latency_ms,
+                model: None,
+                error: (!success).then(|| "timeout".to_string()),
+            },
+            tested_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn ranks_reachable_providers_by_latency() {
+        let mut entries = vec![
+            entry("Slow", true, 900),
+            entry("Zeta", false, 30),
+            entry("Fast", true, 120),
+            entry("Alpha", false, 5000),
+        ];
+        rank(&mut entries);
+        let names: Vec<&str> = entries.iter().map(|e| e.provider_name.as_str()).collect();
+        assert_eq!(names, vec!["Fast", "Slow", "Alpha", "Zeta"]);
+
+        // Stored with the result fields inline
+        let stored = serde_json::to_value(&entries[0]).unwrap();
+        assert_eq!(stored["latencyMs"], 120);
+        assert_eq!(serde_json::from_value::<ClaudeProviderTestEntry>(stored).unwrap().provider_id, "fast");
+    }
+}
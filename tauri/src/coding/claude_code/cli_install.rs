@@ -0,0 +1,248 @@
+//! Claude Code CLI installation
+//!
+//! Detects the `claude` binary and how it was installed (npm or bun global
+//! package, or the native installer), compares its version with the latest
+//! npm release, and installs or upgrades it with the matching tool. Installer
+//! output is streamed line by line as `claude-cli-install-progress` events.
+
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+use serde_json::Value;
+use tauri::{AppHandle, Emitter};
+
+use crate::coding::cli_detect;
+use crate::db::DbState;
+use crate::error::{AppError, AppResult};
+use crate::http_client;
+use super::types::*;
+
+/// Windows CREATE_NO_WINDOW flag to prevent console window from appearing
+#[cfg(target_os = "windows")]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+const PACKAGE: &str = "@anthropic-ai/claude-code";
+const LATEST_URL: &str = "https://registry.npmjs.org/@anthropic-ai/claude-code/latest";
+const REGISTRY_TIMEOUT_SECS: u64 = 10;
+
+/// Event emitted for every line of installer output
+pub const INSTALL_PROGRESS_EVENT: &str = "claude-cli-install-progress";
+
+/// Package managers the CLI can be installed with, in order of preference
+const PACKAGE_MANAGERS: [&str; 2] = ["npm", "bun"];
+
+/// "native", "bun", "npm" or "unknown", judged from the binary path and its symlink target
+fn install_method(path: &Path) -> &'static str {
+    let resolved = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let text = format!("{}|{}", path.display(), resolved.display())
+        .replace('\\', "/")
+        .to_lowercase();
+    // The native installer links ~/.local/bin/claude to ~/.local/share/claude/versions/<v>;
+    // ~/.claude/local is the older self-managed install. Both update with `claude update`.
+    if text.contains("/.local/share/claude/") || text.contains("/.claude/local/") || text.contains("/.local/bin/claude") {
+        "native"
+    } else if text.contains("/.bun/") {
+        "bun"
+    } else if text.contains("/node_modules/") || text.contains("/npm/") || text.contains("/.npm-global/") {
+        "npm"
+    } else {
+        "unknown"
+    }
+}
+
+/// Whether `latest` is a higher dotted version than `current`
+fn is_newer(latest: &str, current: &str) -> bool {
+    fn parts(version: &str) -> Vec<u64> {
+        version
+            .trim_start_matches('v')
+            .split(['-', '+'])
+            .next()
+            .unwrap_or("")
+            .split('.')
+            .map(|part| part.parse().unwrap_or(0))
+            .collect()
+    }
+    let (latest, current) = (parts(latest), parts(current));
+    for i in 0..latest.len().max(current.len()) {
+        let (l, c) = (latest.get(i).copied().unwrap_or(0), current.get(i).copied().unwrap_or(0));
+        if l != c {
+            return l > c;
+        }
+    }
+    false
+}
+
+async fn latest_version(state: &DbState) -> Option<String> {
+    let client = http_client::client_with_timeout(state, REGISTRY_TIMEOUT_SECS).await.ok()?;
+    let url = http_client::resolve_url(state, LATEST_URL).await;
+    let info: Value = client.get(&url).send().await.ok()?.json().await.ok()?;
+    info.get("version").and_then(|v| v.as_str()).map(|v| v.to_string())
+}
+
+async fn detect(state: &DbState) -> ClaudeCliStatus {
+    let binary = cli_detect::detect_cli(&["claude"]);
+    let latest = latest_version(state).await;
+    let update_available = match (&binary, &latest) {
+        (Some(info), Some(latest)) => info.version.as_deref().is_some_and(|v| is_newer(latest, v)),
+        _ => false,
+    };
+    ClaudeCliStatus {
+        installed: binary.is_some(),
+        install_method: binary
+            .as_ref()
+            .map(|info| install_method(Path::new(&info.path)).to_string()),
+        path: binary.as_ref().map(|info| info.path.clone()),
+        version: binary.and_then(|info| info.version),
+        latest_version: latest,
+        update_available,
+        package_managers: PACKAGE_MANAGERS
+            .iter()
+            .filter(|pm| cli_detect::find_binary(&[*pm]).is_some())
+            .map(|pm| pm.to_string())
+            .collect(),
+    }
+}
+
+/// Program and arguments that install or upgrade the CLI with `method`
+fn install_command(method: &str, status: &ClaudeCliStatus) -> AppResult<(PathBuf, Vec<String>)> {
+    let package = format!("{}@latest", PACKAGE);
+    match method {
+        "npm" => {
+            let npm = cli_detect::find_binary(&["npm"])
+                .ok_or_else(|| AppError::not_found("TOOL_NOT_INSTALLED", &[("tool", "npm"), ("binary", "npm")]))?;
+            Ok((npm, vec!["install".into(), "-g".into(), package]))
+        }
+        "bun" => {
+            let bun = cli_detect::find_binary(&["bun"])
+                .ok_or_else(|| AppError::not_found("TOOL_NOT_INSTALLED", &[("tool", "bun"), ("binary", "bun")]))?;
+            Ok((bun, vec!["add".into(), "-g".into(), package]))
+        }
+        "native" => match (&status.path, status.install_method.as_deref()) {
+            (Some(path), Some("native")) => Ok((PathBuf::from(path), vec!["update".into()])),
+            _ if cfg!(target_os = "windows") => Ok((
+                PathBuf::from("powershell"),
+                vec![
+                    "-NoProfile".into(),
+                    "-ExecutionPolicy".into(),
+                    "Bypass".into(),
+                    "-Command".into(),
+                    "irm https://claude.ai/install.ps1 | iex".into(),
+                ],
+            )),
+            _ => Ok((
+                PathBuf::from("bash"),
+                vec!["-c".into(), "curl -fsSL https://claude.ai/install.sh | bash".into()],
+            )),
+        },
+        other => Err(AppError::invalid(format!("unsupported install method '{}'", other))),
+    }
+}
+
+/// Emit every line of `reader` as a progress event; returns the last non-empty line
+fn stream_lines<R: tauri::Runtime>(app: &AppHandle<R>, reader: impl Read, stream: &str) -> Option<String> {
+    let mut last = None;
+    for line in BufReader::new(reader).lines().map_while(Result::ok) {
+        let _ = app.emit(
+            INSTALL_PROGRESS_EVENT,
+            ClaudeCliInstallProgress {
+                stream: stream.to_string(),
+                line: line.clone(),
+            },
+        );
+        if !line.trim().is_empty() {
+            last = Some(line);
+        }
+    }
+    last
+}
+
+fn run_installer(app: AppHandle, program: PathBuf, args: Vec<String>) -> AppResult<()> {
+    let mut cmd = Command::new(&program);
+    cmd.args(&args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(target_os = "windows")]
+    cmd.creation_flags(CREATE_NO_WINDOW);
+
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| AppError::io(format!("{}: {}", program.display(), e)))?;
+    let stderr = child.stderr.take();
+    let stderr_app = app.clone();
+    let stderr_thread = std::thread::spawn(move || stderr.and_then(|s| stream_lines(&stderr_app, s, "stderr")));
+    if let Some(stdout) = child.stdout.take() {
+        stream_lines(&app, stdout, "stdout");
+    }
+    let last_error = stderr_thread.join().ok().flatten();
+
+    let status = child.wait()?;
+    if status.success() {
+        return Ok(());
+    }
+    let code = status.code().map(|c| c.to_string()).unwrap_or_else(|| "signal".to_string());
+    Err(AppError::io(match last_error {
+        Some(line) => format!("installer exited with {}: {}", code, line),
+        None => format!("installer exited with {}", code),
+    }))
+}
+
+// ============================================================================
+// Claude CLI Commands
+// ============================================================================
+
+/// Whether `claude` is installed, how, and whether a newer release exists
+#[tauri::command]
+pub async fn get_claude_cli_status(state: tauri::State<'_, DbState>) -> AppResult<ClaudeCliStatus> {
+    Ok(detect(&state).await)
+}
+
+/// Install or upgrade the CLI, streaming output as `claude-cli-install-progress` events
+/// `method` ("npm", "bun" or "native") defaults to how it is installed now, else
+/// the first available package manager, else the native installer.
+#[tauri::command]
+pub async fn install_claude_cli(
+    state: tauri::State<'_, DbState>,
+    app: AppHandle,
+    method: Option<String>,
+) -> AppResult<ClaudeCliStatus> {
+    let status = detect(&state).await;
+    let method = method
+        .filter(|m| !m.trim().is_empty())
+        .or_else(|| status.install_method.clone().filter(|m| m != "unknown"))
+        .or_else(|| status.package_managers.first().cloned())
+        .unwrap_or_else(|| "native".to_string());
+    let (program, args) = install_command(&method, &status)?;
+
+    tauri::async_runtime::spawn_blocking(move || run_installer(app, program, args))
+        .await
+        .map_err(AppError::io)??;
+    Ok(detect(&state).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn install_method_from_path() {
+        assert_eq!(install_method(Path::new("/home/u/.local/bin/claude")), "native");
+        assert_eq!(install_method(Path::new("/home/u/.claude/local/claude")), "native");
+        assert_eq!(install_method(Path::new("/home/u/.bun/bin/claude")), "bun");
+        assert_eq!(install_method(Path::new("C:\\Users\\u\\AppData\\Roaming\\npm\\claude.cmd")), "npm");
+        assert_eq!(install_method(Path::new("/opt/tools/claude")), "unknown");
+    }
+
+    #[test]
+    fn compares_versions() {
+        assert!(is_newer("2.0.14", "2.0.9"));
+        assert!(is_newer("1.1", "1.0.99"));
+        assert!(!is_newer("2.0.14", "2.0.14"));
+        assert!(!is_newer("2.0.14-beta", "2.0.14"));
+        assert!(!is_newer("1.9.0", "v2.0.0"));
+    }
+}
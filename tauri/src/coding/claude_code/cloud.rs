@@ -0,0 +1,175 @@
+//! AWS Bedrock and Google Vertex AI providers
+//!
+//! Providers of category "bedrock" / "vertex" keep typed fields in a
+//! `bedrock` / `vertex` object of their settings_config instead of a base URL
+//! and token:
+//!
+//! ```json
+//! { "bedrock": { "region": "us-east-1", "profile": "work" }, "model": "us.anthropic.claude-sonnet-4-20250514-v1:0" }
+//! { "vertex": { "region": "us-east5", "projectId": "my-gcp-project" } }
+//! ```
+//!
+//! On apply they become `CLAUDE_CODE_USE_BEDROCK` / `CLAUDE_CODE_USE_VERTEX`
+//! and the matching AWS / Google env vars; `ANTHROPIC_BASE_URL` and
+//! `ANTHROPIC_AUTH_TOKEN` are not written. Credentials themselves stay with the
+//! AWS / gcloud CLI, except an optional Bedrock API key.
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+pub const BEDROCK_KEY: &str = "bedrock";
+pub const VERTEX_KEY: &str = "vertex";
+
+/// Every env var `cloud_env` can write
+pub const CLOUD_ENV_KEYS: [&str; 8] = [
+    "CLAUDE_CODE_USE_BEDROCK",
+    "AWS_REGION",
+    "AWS_PROFILE",
+    "AWS_BEARER_TOKEN_BEDROCK",
+    "ANTHROPIC_SMALL_FAST_MODEL_AWS_REGION",
+    "CLAUDE_CODE_USE_VERTEX",
+    "CLOUD_ML_REGION",
+    "ANTHROPIC_VERTEX_PROJECT_ID",
+];
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BedrockConfig {
+    #[serde(default)]
+    region: String,
+    /// Named profile from ~/.aws/credentials
+    #[serde(default)]
+    profile: Option<String>,
+    /// Bedrock API key (AWS_BEARER_TOKEN_BEDROCK) instead of AWS credentials
+    #[serde(default)]
+    bearer_token: Option<String>,
+    /// Region of the small / fast model when it differs
+    #[serde(default)]
+    small_fast_model_region: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VertexConfig {
+    /// e.g. "us-east5" or "global"
+    #[serde(default)]
+    region: String,
+    #[serde(default)]
+    project_id: String,
+}
+
+/// Whether the provider runs on Bedrock or Vertex rather than an Anthropic-style endpoint
+pub fn is_cloud(provider_config: &Value) -> bool {
+    provider_config.get(BEDROCK_KEY).is_some_and(Value::is_object)
+        || provider_config.get(VERTEX_KEY).is_some_and(Value::is_object)
+}
+
+fn section<T: DeserializeOwned + Default>(provider_config: &Value, key: &str) -> Option<T> {
+    provider_config
+        .get(key)
+        .filter(|v| v.is_object())
+        .map(|v| serde_json::from_value(v.clone()).unwrap_or_default())
+}
+
+fn insert(env: &mut Map<String, Value>, key: &str, value: Option<&str>) {
+    if let Some(value) = value.map(str::trim).filter(|v| !v.is_empty()) {
+        env.insert(key.to_string(), Value::String(value.to_string()));
+    }
+}
+
+/// Env vars selecting Bedrock / Vertex; None for other providers
+pub fn cloud_env(provider_config: &Value) -> Option<Map<String, Value>> {
+    let mut env = Map::new();
+    if let Some(bedrock) = section::<BedrockConfig>(provider_config, BEDROCK_KEY) {
+        insert(&mut env, "CLAUDE_CODE_USE_BEDROCK", Some("1"));
+        insert(&mut env, "AWS_REGION", Some(bedrock.region.as_str()));
+        insert(&mut env, "AWS_PROFILE", bedrock.profile.as_deref());
+        insert(&mut env, "AWS_BEARER_TOKEN_BEDROCK", bedrock.bearer_token.as_deref());
+        insert(
+            &mut env,
+            "ANTHROPIC_SMALL_FAST_MODEL_AWS_REGION",
+            bedrock.small_fast_model_region.as_deref(),
+        );
+        return Some(env);
+    }
+    if let Some(vertex) = section::<VertexConfig>(provider_config, VERTEX_KEY) {
+        insert(&mut env, "CLAUDE_CODE_USE_VERTEX", Some("1"));
+        insert(&mut env, "CLOUD_ML_REGION", Some(vertex.region.as_str()));
+        insert(&mut env, "ANTHROPIC_VERTEX_PROJECT_ID", Some(vertex.project_id.as_str()));
+        return Some(env);
+    }
+    None
+}
+
+/// Regional API endpoint, used for health checks
+pub fn endpoint(provider_config: &Value) -> Option<String> {
+    if let Some(bedrock) = section::<BedrockConfig>(provider_config, BEDROCK_KEY) {
+        let region = bedrock.region.trim();
+        return (!region.is_empty()).then(|| format!("https://bedrock-runtime.{}.amazonaws.com", region));
+    }
+    section::<VertexConfig>(provider_config, VERTEX_KEY).map(|vertex| match vertex.region.trim() {
+        "" | "global" => "https://aiplatform.googleapis.com".to_string(),
+        region => format!("https://{}-aiplatform.googleapis.com", region),
+    })
+}
+
+/// (field, rule code) of missing or malformed cloud fields, see `settings_schema`
+pub fn field_errors(provider_config: &Value) -> Vec<(String, &'static str)> {
+    let required: [(&str, &[&str]); 2] = [(BEDROCK_KEY, &["region"]), (VERTEX_KEY, &["region", "projectId"])];
+    let mut errors = Vec::new();
+    for (key, fields) in required {
+        match provider_config.get(key) {
+            None | Some(Value::Null) => {}
+            Some(Value::Object(section)) => {
+                for (name, value) in section {
+                    if !value.is_string() && !value.is_null() {
+                        errors.push((format!("{}.{}", key, name), "string"));
+                    }
+                }
+                for field in fields {
+                    let name = format!("{}.{}", key, field);
+                    let present = section
+                        .get(*field)
+                        .and_then(|v| v.as_str())
+                        .is_some_and(|v| !v.trim().is_empty());
+                    if !present && !errors.iter().any(|(f, _)| *f == name) {
+                        errors.push((name, "required"));
+                    }
+                }
+            }
+            Some(_) => errors.push((key.to_string(), "object")),
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_bedrock_and_vertex_env() {
+        let bedrock = serde_json::json!({ "bedrock": { "region": "us-west-2", "profile": "work", "bearerToken": "" } });
+        let env = cloud_env(&bedrock).unwrap();
+        assert_eq!(
+            Value::Object(env),
+            serde_json::json!({ "CLAUDE_CODE_USE_BEDROCK": "1", "AWS_REGION": "us-west-2", "AWS_PROFILE": "work" })
+        );
+        assert_eq!(endpoint(&bedrock).as_deref(), Some("https://bedrock-runtime.us-west-2.amazonaws.com"));
+
+        let vertex = serde_json::json!({ "vertex": { "region": "global", "projectId": "proj-1" } });
+        assert_eq!(cloud_env(&vertex).unwrap()["ANTHROPIC_VERTEX_PROJECT_ID"], "proj-1");
+        assert_eq!(endpoint(&vertex).as_deref(), Some("https://aiplatform.googleapis.com"));
+
+        assert!(cloud_env(&serde_json::json!({ "env": {} })).is_none());
+    }
+
+    #[test]
+    fn reports_missing_cloud_fields() {
+        let config = serde_json::json!({ "vertex": { "region": "us-east5", "projectId": 42 } });
+        assert_eq!(field_errors(&config), vec![("vertex.projectId".to_string(), "string")]);
+        let config = serde_json::json!({ "bedrock": {} });
+        assert_eq!(field_errors(&config), vec![("bedrock.region".to_string(), "required")]);
+    }
+}
@@ -1,12 +1,17 @@
 use chrono::Local;
+use std::collections::BTreeSet;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, RwLock};
 use serde_json::Value;
 
+use crate::coding::{change_history, model_rules};
 use crate::db::DbState;
+use crate::error::{AppError, AppResult};
 use super::adapter;
 use super::types::*;
 use tauri::Emitter;
+use crate::events::{emit_change, ChangeAction};
 
 const KNOWN_ENV_FIELDS: [&str; 7] = [
     "ANTHROPIC_AUTH_TOKEN",
@@ -22,38 +27,87 @@ const KNOWN_ENV_FIELDS: [&str; 7] = [
 // Claude Code Provider Commands
 // ============================================================================
 
-/// List all Claude Code providers ordered by sort_index
+/// Query with blank filters dropped and the search text lowercased
+fn normalize_query(query: Option<ClaudeProviderQuery>) -> ClaudeProviderQuery {
+    let mut query = query.unwrap_or_default();
+    query.category = query.category.map(|c| c.trim().to_string()).filter(|c| !c.is_empty());
+    query.search = query.search.map(|s| s.trim().to_lowercase()).filter(|s| !s.is_empty());
+    query
+}
+
+/// WHERE clause of the provider list filters (bound as $archived, $category, $search)
+fn provider_conditions(include_archived: bool, query: &ClaudeProviderQuery) -> String {
+    let mut conditions = Vec::new();
+    match query.archived {
+        Some(_) => conditions.push("(is_archived ?? false) = $archived"),
+        None if !include_archived => conditions.push("is_archived != true"),
+        None => {}
+    }
+    if query.category.is_some() {
+        conditions.push("category = $category");
+    }
+    if query.search.is_some() {
+        conditions.push(
+            "(string::contains(string::lowercase(name), $search) \
+             OR string::contains(string::lowercase(notes ?? ''), $search))",
+        );
+    }
+    if conditions.is_empty() {
+        String::new()
+    } else {
+        format!(" WHERE {}", conditions.join(" AND "))
+    }
+}
+
+/// List Claude Code providers ordered by sort_index
+/// Archived providers are left out unless `include_archived` is set; `query`
+/// narrows the list further (`query.archived` overrides `include_archived`)
+/// and pages it when `limit` is given.
 #[tauri::command]
 pub async fn list_claude_providers(
     state: tauri::State<'_, DbState>,
-) -> Result<Vec<ClaudeCodeProvider>, String> {
-    let db = state.0.lock().await;
+    include_archived: Option<bool>,
+    query: Option<ClaudeProviderQuery>,
+) -> AppResult<Vec<ClaudeCodeProvider>> {
+    let query = normalize_query(query);
+    let mut sql = format!(
+        "SELECT *, type::string(id) as id FROM claude_provider{} ORDER BY sort_index ASC",
+        provider_conditions(include_archived.unwrap_or(false), &query)
+    );
+    if let Some(limit) = query.limit.filter(|limit| *limit > 0) {
+        let start = query.page.unwrap_or(1).saturating_sub(1).saturating_mul(limit);
+        sql.push_str(&format!(" LIMIT {} START {}", limit, start));
+    }
 
+    let db = state.0.lock().await;
     let records_result: Result<Vec<Value>, _> = db
-        .query("SELECT *, type::string(id) as id FROM claude_provider")
-        .await
-        .map_err(|e| format!("Failed to query providers: {}", e))?
+        .query(sql)
+        .bind(("archived", query.archived.unwrap_or(false)))
+        .bind(("category", query.category.clone()))
+        .bind(("search", query.search.clone()))
+        .await?
         .take(0);
 
-match records_result {
-        Ok(records) => {
-            if records.is_empty() {
-                // Database is empty, try to load from local file as temporary provider
+    match records_result {
+        Ok(records) if !records.is_empty() => {
+            Ok(records.into_iter().map(adapter::from_db_value_provider).collect())
+        }
+        Ok(_) => {
+            // Database is empty, try to load from local file as temporary provider
+            let stored: Vec<Value> = db
+                .query("SELECT id FROM claude_provider LIMIT 1")
+                .await
+                .and_then(|mut result| result.take(0))
+                .unwrap_or_default();
+            if stored.is_empty() {
                 if let Ok(temp_provider) = load_temp_provider_from_file().await {
                     return Ok(vec![temp_provider]);
                 }
-                Ok(Vec::new())
-            } else {
-                let mut result: Vec<ClaudeCodeProvider> = records
-                    .into_iter()
-                    .map(adapter::from_db_value_provider)
-                    .collect();
-                result.sort_by_key(|p| p.sort_index.unwrap_or(0));
-                Ok(result)
             }
+            Ok(Vec::new())
         }
         Err(e) => {
-            eprintln!("❌ Failed to deserialize providers: {}", e);
+            log::warn!("Failed to deserialize providers: {}", e);
             // Try to load from local file as fallback
             if let Ok(temp_provider) = load_temp_provider_from_file().await {
                 return Ok(vec![temp_provider]);
@@ -63,6 +117,33 @@ match records_result {
     }
 }
 
+/// Number of providers matching the filters of `list_claude_providers` (paging ignored)
+#[tauri::command]
+pub async fn count_claude_providers(
+    state: tauri::State<'_, DbState>,
+    include_archived: Option<bool>,
+    query: Option<ClaudeProviderQuery>,
+) -> AppResult<usize> {
+    let query = normalize_query(query);
+    let sql = format!(
+        "SELECT count() AS total FROM claude_provider{} GROUP ALL",
+        provider_conditions(include_archived.unwrap_or(false), &query)
+    );
+    let db = state.0.lock().await;
+    let records: Vec<Value> = db
+        .query(sql)
+        .bind(("archived", query.archived.unwrap_or(false)))
+        .bind(("category", query.category))
+        .bind(("search", query.search))
+        .await?
+        .take(0)?;
+    Ok(records
+        .first()
+        .and_then(|record| record.get("total"))
+        .and_then(|total| total.as_u64())
+        .unwrap_or(0) as usize)
+}
+
 /// Load a temporary provider from settings.json without writing to database
 /// This is used when the database is empty and we want to show the local config
 async fn load_temp_provider_from_file() -> Result<ClaudeCodeProvider, String> {
@@ -133,6 +214,7 @@ async fn load_temp_provider_from_file() -> Result<ClaudeCodeProvider, String> {
         sort_index: Some(0),
         is_applied: true,
         is_disabled: false,
+        is_archived: false,
         created_at: now.clone(),
         updated_at: now,
     })
@@ -144,7 +226,8 @@ pub async fn create_claude_provider(
     state: tauri::State<'_, DbState>,
     app: tauri::AppHandle,
     provider: ClaudeCodeProviderInput,
-) -> Result<ClaudeCodeProvider, String> {
+) -> AppResult<ClaudeCodeProvider> {
+    super::settings_schema::validate(&provider.settings_config)?;
     let db = state.0.lock().await;
 
     let now = Local::now().to_rfc3339();
@@ -160,6 +243,7 @@ pub async fn create_claude_provider(
         sort_index: provider.sort_index,
         is_applied: false,
         is_disabled: false,
+        is_archived: false,
         created_at: now.clone(),
         updated_at: now,
     };
@@ -169,38 +253,120 @@ pub async fn create_claude_provider(
     // Create new provider - SurrealDB auto-generates record ID
     db.query("CREATE claude_provider CONTENT $data")
         .bind(("data", json_data))
-        .await
-        .map_err(|e| format!("Failed to create provider: {}", e))?;
+        .await?;
 
     // Fetch the created record to get the auto-generated ID
     let result: Result<Vec<Value>, _> = db
         .query("SELECT *, type::string(id) as id FROM claude_provider ORDER BY created_at DESC LIMIT 1")
-        .await
-        .map_err(|e| format!("Failed to fetch created provider: {}", e))?
+        .await?
         .take(0);
 
     // Notify to refresh tray menu
-    let _ = app.emit("config-changed", "window");
+    emit_change(&app, "claude_provider", ChangeAction::Created, None, "window");
 
     match result {
         Ok(records) => {
             if let Some(record) = records.first() {
                 Ok(adapter::from_db_value_provider(record.clone()))
             } else {
-                Err("Failed to retrieve created provider".to_string())
+                Err(AppError::db("Failed to retrieve created provider"))
             }
         }
-        Err(e) => Err(format!("Failed to retrieve created provider: {}", e)),
+        Err(e) => Err(e.into()),
     }
 }
 
+/// Copy a Claude Code provider as "<name> (copy)" at the end of the list
+/// `new_id` sets the record id of the copy; a random one is used otherwise
+#[tauri::command]
+pub async fn clone_claude_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+    new_id: Option<String>,
+) -> AppResult<ClaudeCodeProvider> {
+    let db = state.0.lock().await;
+
+    let records: Vec<Value> = db
+        .query("SELECT *, type::string(id) as id FROM type::thing('claude_provider', $id)")
+        .bind(("id", id.clone()))
+        .await?
+        .take(0)?;
+    let source = records
+        .into_iter()
+        .next()
+        .map(adapter::from_db_value_provider)
+        .ok_or_else(|| AppError::not_found("PROVIDER_NOT_FOUND", &[("id", id.as_str())]))?;
+
+    let new_id = match new_id.map(|id| id.trim().to_string()).filter(|id| !id.is_empty()) {
+        Some(new_id) => {
+            if !new_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+                return Err(AppError::validation("INVALID_PROVIDER_ID", &[("id", new_id.as_str())]));
+            }
+            let taken: Vec<Value> = db
+                .query("SELECT id FROM type::thing('claude_provider', $id)")
+                .bind(("id", new_id.clone()))
+                .await?
+                .take(0)?;
+            if !taken.is_empty() {
+                return Err(AppError::conflict("PROVIDER_ALREADY_EXISTS", &[("id", new_id.as_str())]));
+            }
+            new_id
+        }
+        None => uuid::Uuid::new_v4().simple().to_string(),
+    };
+
+    let now = Local::now().to_rfc3339();
+    let content = ClaudeCodeProviderContent {
+        name: format!("{} (copy)", source.name),
+        category: source.category,
+        settings_config: source.settings_config,
+        source_provider_id: source.source_provider_id,
+        website_url: source.website_url,
+        notes: source.notes,
+        icon: source.icon,
+        icon_color: source.icon_color,
+        sort_index: Some(super::provider_io::next_sort_index(&db).await?),
+        is_applied: false,
+        is_disabled: source.is_disabled,
+        is_archived: false,
+        created_at: now.clone(),
+        updated_at: now,
+    };
+    db.query("CREATE type::thing('claude_provider', $id) CONTENT $data")
+        .bind(("id", new_id.clone()))
+        .bind(("data", adapter::to_db_value_provider(&content)))
+        .await?;
+
+    emit_change(&app, "claude_provider", ChangeAction::Created, Some(&new_id), "window");
+
+    Ok(ClaudeCodeProvider {
+        id: new_id,
+        name: content.name,
+        category: content.category,
+        settings_config: content.settings_config,
+        source_provider_id: content.source_provider_id,
+        website_url: content.website_url,
+        notes: content.notes,
+        icon: content.icon,
+        icon_color: content.icon_color,
+        sort_index: content.sort_index,
+        is_applied: content.is_applied,
+        is_disabled: content.is_disabled,
+        is_archived: content.is_archived,
+        created_at: content.created_at,
+        updated_at: content.updated_at,
+    })
+}
+
 /// Update an existing Claude Code provider
 #[tauri::command]
 pub async fn update_claude_provider(
     state: tauri::State<'_, DbState>,
     app: tauri::AppHandle,
     provider: ClaudeCodeProvider,
-) -> Result<ClaudeCodeProvider, String> {
+) -> AppResult<ClaudeCodeProvider> {
+    super::settings_schema::validate(&provider.settings_config)?;
     let db = state.0.lock().await;
 
     // Use the id from frontend (pure string id without table prefix)
@@ -212,17 +378,13 @@ pub async fn update_claude_provider(
     let existing_result: Result<Vec<Value>, _> = db
         .query("SELECT * OMIT id FROM claude_provider WHERE id = type::thing('claude_provider', $id) LIMIT 1")
         .bind(("id", id.clone()))
-        .await
-        .map_err(|e| format!("Failed to query existing provider: {}", e))?
+        .await?
         .take(0);
 
     // Check if provider exists
     if let Ok(records) = &existing_result {
         if records.is_empty() {
-            return Err(format!(
-                "Claude Code provider with ID '{}' not found",
-                id
-            ));
+            return Err(AppError::not_found("PROVIDER_NOT_FOUND", &[("id", id.as_str())]));
         }
     }
 
@@ -249,6 +411,15 @@ pub async fn update_claude_provider(
         (now.clone(), false)
     };
 
+    // Archiving goes through archive_claude_provider; keep the stored flag
+    let existing_is_archived = existing_result
+        .as_ref()
+        .ok()
+        .and_then(|records| records.first())
+        .and_then(|record| record.get("is_archived"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
     let content = ClaudeCodeProviderContent {
         name: provider.name,
         category: provider.category,
@@ -261,28 +432,32 @@ pub async fn update_claude_provider(
         sort_index: provider.sort_index,
         is_applied: provider.is_applied,
         is_disabled: existing_is_disabled,
+        is_archived: existing_is_archived,
         created_at,
         updated_at: now,
     };
 
     let json_data = adapter::to_db_value_provider(&content);
 
+    // Keep the state before this edit restorable
+    super::provider_history::record_version_by_id(&db, &id, "update").await;
+
     // Use database id for update
     db.query(format!("UPDATE claude_provider:`{}` CONTENT $data", id))
         .bind(("data", json_data))
-        .await
-        .map_err(|e| format!("Failed to update provider: {}", e))?;
+        .await?;
+    super::provider_history::record_version_by_id(&db, &id, "update").await;
 
     // 如果该配置当前是应用状态，立即重新写入到配置文件
     if content.is_applied {
         if let Err(e) = apply_config_to_file(&db, &id).await {
-            eprintln!("Failed to auto-apply updated config: {}", e);
+            log::warn!("Failed to auto-apply updated config: {}", e);
             // 不中断更新流程，只记录错误
         }
     }
 
     // Notify frontend and tray to refresh
-    let _ = app.emit("config-changed", "window");
+    emit_change(&app, "claude_provider", ChangeAction::Updated, None, "window");
 
     Ok(ClaudeCodeProvider {
         id,
@@ -297,6 +472,7 @@ pub async fn update_claude_provider(
         sort_index: content.sort_index,
         is_applied: content.is_applied,
         is_disabled: content.is_disabled,
+        is_archived: content.is_archived,
         created_at: content.created_at,
         updated_at: content.updated_at,
     })
@@ -308,15 +484,15 @@ pub async fn delete_claude_provider(
     state: tauri::State<'_, DbState>,
     app: tauri::AppHandle,
     id: String,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let db = state.0.lock().await;
 
-    db.query(format!("DELETE claude_provider:`{}`", id))
-        .await
-        .map_err(|e| format!("Failed to delete claude provider: {}", e))?;
+    db.query(format!("DELETE claude_provider:`{}`", id)).await?;
+    super::provider_history::delete_history(&db, &id).await;
+    super::batch_test::delete_result(&db, &id).await;
 
     // Notify to refresh tray menu
-    let _ = app.emit("config-changed", "window");
+    emit_change(&app, "claude_provider", ChangeAction::Deleted, None, "window");
 
     Ok(())
 }
@@ -326,7 +502,7 @@ pub async fn delete_claude_provider(
 pub async fn reorder_claude_providers(
     state: tauri::State<'_, DbState>,
     ids: Vec<String>,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let db = state.0.lock().await;
     let now = Local::now().to_rfc3339();
 
@@ -335,8 +511,7 @@ pub async fn reorder_claude_providers(
             .bind(("index", index as i32))
             .bind(("now", now.clone()))
             .bind(("id", id.clone()))
-            .await
-            .map_err(|e| format!("Failed to update provider {}: {}", id, e))?;
+            .await?;
     }
 
     Ok(())
@@ -349,7 +524,7 @@ pub async fn select_claude_provider(
     state: tauri::State<'_, DbState>,
     app: tauri::AppHandle,
     id: String,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let db = state.0.lock().await;
 
     let now = Local::now().to_rfc3339();
@@ -357,45 +532,280 @@ pub async fn select_claude_provider(
     // Mark all providers as not applied (only update the currently applied one)
     db.query("UPDATE claude_provider SET is_applied = false, updated_at = $now WHERE is_applied = true")
         .bind(("now", now.clone()))
-        .await
-        .map_err(|e| format!("Failed to reset applied status: {}", e))?;
+        .await?;
 
     // Mark target provider as applied
     db.query("UPDATE claude_provider SET is_applied = true, updated_at = $now WHERE id = type::thing('claude_provider', $id)")
         .bind(("id", id))
         .bind(("now", now))
-        .await
-        .map_err(|e| format!("Failed to set applied status: {}", e))?;
+        .await?;
 
     // Notify frontend to refresh
-    let _ = app.emit("config-changed", "window");
+    emit_change(&app, "claude_provider", ChangeAction::Applied, None, "window");
 
     Ok(())
 }
 
 // ============================================================================
-// Claude Config File Commands
+// Claude Provider Test Commands
 // ============================================================================
 
-/// Get Claude config file path (~/.claude/settings.json)
+/// Model used for the test when the provider sets none
+const TEST_FALLBACK_MODEL: &str = "claude-sonnet-4-20250514";
+const TEST_TIMEOUT_SECS: u64 = 30;
+
+/// Send a minimal messages request to a provider's ANTHROPIC_BASE_URL with its key,
+/// without applying it. HTTP and network failures are reported in the result.
 #[tauri::command]
-pub fn get_claude_config_path() -> Result<String, String> {
-    let home_dir = std::env::var("USERPROFILE")
+pub async fn test_claude_provider(
+    state: tauri::State<'_, DbState>,
+    provider_id: String,
+    model: Option<String>,
+) -> AppResult<ClaudeProviderTestResult> {
+    let provider = {
+        let db = state.0.lock().await;
+        let records: Vec<Value> = db
+            .query("SELECT *, type::string(id) as id FROM claude_provider WHERE id = type::thing('claude_provider', $id) LIMIT 1")
+            .bind(("id", provider_id.clone()))
+            .await?
+            .take(0)?;
+        records
+            .into_iter()
+            .next()
+            .map(adapter::from_db_value_provider)
+            .ok_or_else(|| AppError::not_found("PROVIDER_NOT_FOUND", &[("id", provider_id.as_str())]))?
+    };
+    test_provider(&state, &provider, model).await
+}
+
+/// The request behind `test_claude_provider`, also used by the batch test
+pub async fn test_provider(
+    state: &DbState,
+    provider: &ClaudeCodeProvider,
+    model: Option<String>,
+) -> AppResult<ClaudeProviderTestResult> {
+    let mut provider_config: Value =
+        serde_json::from_str(&provider.settings_config).map_err(AppError::invalid)?;
+    crate::coding::vault::resolve_references(&mut provider_config)?;
+    let env = provider_env(&provider_config);
+    let env_str = |key: &str| {
+        env.get(key)
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(String::from)
+    };
+
+    if super::cloud::is_cloud(&provider_config) {
+        return Err(AppError::validation("CLOUD_PROVIDER_NOT_TESTABLE", &[("name", provider.name.as_str())]));
+    }
+    let api_key = env_str("ANTHROPIC_AUTH_TOKEN")
+        .ok_or_else(|| AppError::validation("PROVIDER_API_KEY_MISSING", &[("name", provider.name.as_str())]))?;
+    let base_url = env_str("ANTHROPIC_BASE_URL").unwrap_or_else(|| "https://api.anthropic.com".to_string());
+    let model = model
+        .filter(|m| !m.trim().is_empty())
+        .or_else(|| env_str("ANTHROPIC_MODEL"))
+        .or_else(|| env_str("ANTHROPIC_DEFAULT_SONNET_MODEL"))
+        .unwrap_or_else(|| TEST_FALLBACK_MODEL.to_string());
+
+    let client = crate::http_client::client_with_proxy(
+        state,
+        provider_proxy(&provider_config).as_deref(),
+        TEST_TIMEOUT_SECS,
+    )
+    .await
+    .map_err(AppError::invalid)?;
+    let url = crate::coding::speed_test::stream::endpoint_url(
+        crate::coding::speed_test::stream::Protocol::Anthropic,
+        &base_url,
+    );
+    let body = serde_json::json!({
+        "model": model,
+        "max_tokens": 1,
+        "messages": [{ "role": "user", "content": "ping" }],
+    });
+
+    let start = std::time::Instant::now();
+    let response = client
+        .post(&url)
+        .header("x-api-key", &api_key)
+        .header("anthropic-version", "2023-06-01")
+        .bearer_auth(&api_key)
+        .json(&body)
+        .send()
+        .await;
+    let latency_ms = start.elapsed().as_millis() as u64;
+
+    let response = match response {
+        Ok(response) => response,
+        Err(e) => {
+            return Ok(ClaudeProviderTestResult {
+                success: false,
+                status: None,
+                latency_ms,
+                model: None,
+                error: Some(e.to_string()),
+            })
+        }
+    };
+    let status = response.status();
+    let text = response.text().await.unwrap_or_default();
+    let json: Option<Value> = serde_json::from_str(&text).ok();
+    if !status.is_success() {
+        let message = json
+            .as_ref()
+            .and_then(|j| j.pointer("/error/message"))
+            .and_then(|m| m.as_str())
+            .map(String::from)
+            .unwrap_or_else(|| text.chars().take(200).collect());
+        return Ok(ClaudeProviderTestResult {
+            success: false,
+            status: Some(status.as_u16()),
+            latency_ms,
+            model: None,
+            error: Some(message),
+        });
+    }
+
+    Ok(ClaudeProviderTestResult {
+        success: true,
+        status: Some(status.as_u16()),
+        latency_ms,
+        model: json
+            .as_ref()
+            .and_then(|j| j.get("model"))
+            .and_then(|m| m.as_str())
+            .map(String::from)
+            .or(Some(model)),
+        error: None,
+    })
+}
+
+// ============================================================================
+// Claude Provider Balance Commands
+// ============================================================================
+
+/// How long a queried balance is reused before the endpoint is asked again
+const BALANCE_CACHE_SECS: i64 = 300;
+
+/// Remaining quota of a relay provider, from its balance checker (see
+/// `coding::balance`; save one with tool "claude" and the provider id).
+/// A result younger than `BALANCE_CACHE_SECS` is returned as is unless `force`.
+#[tauri::command]
+pub async fn query_claude_provider_balance(
+    state: tauri::State<'_, DbState>,
+    provider_id: String,
+    force: Option<bool>,
+) -> AppResult<crate::coding::balance::BalanceResult> {
+    let checker = crate::coding::balance::get_checker(&state, &provider_id).await?;
+    if checker.tool != "claude" {
+        return Err(AppError::not_found("BALANCE_CHECKER_NOT_FOUND", &[("id", provider_id.as_str())]));
+    }
+    if !force.unwrap_or(false) {
+        let cached = checker.last_balance.zip(checker.last_checked_at.as_deref()).filter(|(_, checked_at)| {
+            chrono::DateTime::parse_from_rfc3339(checked_at)
+                .is_ok_and(|t| (Local::now().fixed_offset() - t).num_seconds() < BALANCE_CACHE_SECS)
+        });
+        if let Some((balance, checked_at)) = cached {
+            return Ok(crate::coding::balance::BalanceResult {
+                provider_id,
+                balance,
+                unit: checker.unit.clone(),
+                checked_at: checked_at.to_string(),
+            });
+        }
+    }
+    crate::coding::balance::refresh(&state, checker).await
+}
+
+// ============================================================================
+// Claude Config File Commands
+// ============================================================================
+
+/// Claude config directory from the app settings (`claude_config_dir`); None when unset
+static CONFIG_DIR_OVERRIDE: OnceLock<RwLock<Option<PathBuf>>> = OnceLock::new();
+
+/// Update the config directory override (on startup and when settings are saved)
+pub fn set_config_dir_override(dir: &str) {
+    let storage = CONFIG_DIR_OVERRIDE.get_or_init(|| RwLock::new(None));
+    if let Ok(mut guard) = storage.write() {
+        *guard = Some(dir.trim()).filter(|d| !d.is_empty()).map(expand_home);
+    }
+}
+
+fn config_dir_override() -> Option<PathBuf> {
+    CONFIG_DIR_OVERRIDE
+        .get()
+        .and_then(|storage| storage.read().ok())
+        .and_then(|guard| guard.clone())
+}
+
+fn home_dir() -> Result<PathBuf, String> {
+    std::env::var("USERPROFILE")
         .or_else(|_| std::env::var("HOME"))
-        .map_err(|_| "Failed to get home directory".to_string())?;
+        .map(PathBuf::from)
+        .map_err(|_| "Failed to get home directory".to_string())
+}
 
-    let config_path = Path::new(&home_dir).join(".claude").join("settings.json");
+/// `~` and `~/...` relative to the home directory
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix('~'), home_dir()) {
+        (Some(rest), Ok(home)) if rest.is_empty() || rest.starts_with(['/', '\\']) => {
+            home.join(rest.trim_start_matches(['/', '\\']))
+        }
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Claude config directory and where it came from: "custom" (app settings),
+/// "env" (CLAUDE_CONFIG_DIR, as the CLI reads it) or "default" (~/.claude)
+fn resolve_config_dir() -> Result<(PathBuf, &'static str), String> {
+    if let Some(dir) = config_dir_override() {
+        return Ok((dir, "custom"));
+    }
+    if let Ok(dir) = std::env::var("CLAUDE_CONFIG_DIR") {
+        if !dir.trim().is_empty() {
+            return Ok((expand_home(dir.trim()), "env"));
+        }
+    }
+    Ok((home_dir()?.join(".claude"), "default"))
+}
+
+/// Claude config directory (~/.claude unless overridden)
+pub fn get_claude_config_dir() -> Result<PathBuf, String> {
+    resolve_config_dir().map(|(dir, _)| dir)
+}
+
+/// Where the CLI keeps its global state (~/.claude.json): inside the config
+/// directory when one is set, otherwise in the home directory
+pub fn get_claude_state_path() -> Result<PathBuf, String> {
+    match resolve_config_dir()? {
+        (dir, "custom" | "env") => Ok(dir.join(".claude.json")),
+        _ => Ok(home_dir()?.join(".claude.json")),
+    }
+}
+
+/// Get Claude config file path (settings.json in the config directory)
+#[tauri::command]
+pub fn get_claude_config_path() -> Result<String, String> {
+    let config_path = get_claude_config_dir()?.join("settings.json");
     Ok(config_path.to_string_lossy().to_string())
 }
 
+/// Claude config directory with its source ("custom", "env" or "default")
+#[tauri::command]
+pub fn get_claude_config_dir_info() -> AppResult<ClaudeConfigDirInfo> {
+    let (dir, source) = resolve_config_dir().map_err(AppError::io)?;
+    Ok(ClaudeConfigDirInfo {
+        path: dir.to_string_lossy().to_string(),
+        source: source.to_string(),
+    })
+}
+
 /// Reveal Claude config folder in file explorer
 #[tauri::command]
 pub fn reveal_claude_config_folder() -> Result<(), String> {
-    let home_dir = std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .map_err(|_| "Failed to get home directory".to_string())?;
-
-    let config_dir = Path::new(&home_dir).join(".claude");
+    let config_dir = get_claude_config_dir()?;
 
     // Ensure directory exists
     if !config_dir.exists() {
@@ -458,73 +868,69 @@ pub async fn read_claude_settings() -> Result<ClaudeSettings, String> {
 async fn apply_config_to_file(
     db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
     provider_id: &str,
-) -> Result<(), String> {
+) -> AppResult<()> {
     apply_config_to_file_public(db, provider_id).await
 }
 
-/// Public version of apply_config_to_file for tray module
-pub async fn apply_config_to_file_public(
-    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
-    provider_id: &str,
-) -> Result<(), String> {
-
+/// Proxy the provider is reached through (`proxy` in settings_config), with a
+/// scheme (http://, https://, socks5://, socks5h://; http:// when omitted)
+pub fn provider_proxy(provider_config: &serde_json::Value) -> Option<String> {
+    provider_config
+        .get("proxy")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(crate::http_client::normalize_proxy_url)
+}
 
-    // Get the provider
-    // Use type::thing(table, id) to create a Thing from table name and id
-    let provider_result: Result<Vec<Value>, _> = db
-        .query("SELECT *, type::string(id) as id FROM claude_provider WHERE id = type::thing('claude_provider', $id) LIMIT 1")
-        .bind(("id", provider_id.to_string()))
-        .await
-        .map_err(|e| format!("Failed to query provider: {}", e))?
-        .take(0);
+/// Env vars `provider_env` writes itself, besides cloud and limit vars
+const PROVIDER_ENV_KEYS: [&str; 9] = [
+    "ANTHROPIC_AUTH_TOKEN",
+    "ANTHROPIC_BASE_URL",
+    "ANTHROPIC_MODEL",
+    "ANTHROPIC_DEFAULT_HAIKU_MODEL",
+    "ANTHROPIC_DEFAULT_SONNET_MODEL",
+    "ANTHROPIC_DEFAULT_OPUS_MODEL",
+    "HTTPS_PROXY",
+    "HTTP_PROXY",
+    "NO_PROXY",
+];
 
-    let provider = match provider_result {
-        Ok(records) => {
-            if let Some(record) = records.first() {
-                adapter::from_db_value_provider(record.clone())
-            } else {
-                return Err("Provider not found".to_string());
-            }
+/// Every env var `provider_env` can write for any of `provider_configs`,
+/// i.e. the keys a previous provider may have left in a merged settings file
+pub fn managed_env_keys<'a>(provider_configs: impl IntoIterator<Item = &'a Value>) -> BTreeSet<String> {
+    let mut keys: BTreeSet<String> = PROVIDER_ENV_KEYS
+        .iter()
+        .chain(super::cloud::CLOUD_ENV_KEYS.iter())
+        .map(|key| key.to_string())
+        .chain(super::limits::env_vars().map(str::to_string))
+        .collect();
+    for config in provider_configs {
+        if let Some(extra_env) = config.get("extraEnv").and_then(|v| v.as_object()) {
+            keys.extend(extra_env.keys().cloned());
         }
-        Err(e) => {
-            return Err(format!("Failed to deserialize provider: {}", e));
-        }
-    };
-
-    // Check if provider is disabled
-    if provider.is_disabled {
-        return Err(format!("Provider '{}' is disabled and cannot be applied", provider_id));
     }
+    keys
+}
 
-    // Parse provider settings_config
-    let provider_config: serde_json::Value = serde_json::from_str(&provider.settings_config)
-        .map_err(|e| format!("Failed to parse provider config: {}", e))?;
-
-    // Get common config
-    let common_config_result: Result<Vec<Value>, _> = db
-        .query("SELECT * OMIT id FROM claude_common_config:`common` LIMIT 1")
-        .await
-        .map_err(|e| format!("Failed to query common config: {}", e))?
-        .take(0);
+/// Claude env vars (base URL, token, models, limits, proxy) from a provider's settings_config
+/// Also used for project-level settings (see `projects`).
+/// Free-form `extraEnv` entries come first; the fields below override them.
+pub fn provider_env(provider_config: &serde_json::Value) -> serde_json::Map<String, serde_json::Value> {
+    let mut env = serde_json::Map::new();
 
-    let common_config: serde_json::Value = match common_config_result {
-        Ok(records) => {
-            if let Some(record) = records.first() {
-                let config = adapter::from_db_value_common(record.clone());
-                serde_json::from_str(&config.config)
-                    .map_err(|e| format!("Failed to parse common config: {}", e))?
-            } else {
-                serde_json::json!({})
+    if let Some(extra_env) = provider_config.get("extraEnv").and_then(|v| v.as_object()) {
+        for (key, value) in extra_env {
+            if value.is_string() {
+                env.insert(key.clone(), value.clone());
             }
         }
-        Err(_) => serde_json::json!({}),
-    };
-
-    // Build env section from provider config
-    let mut env = serde_json::Map::new();
+    }
 
-    // Get env section from provider config
-    if let Some(env_config) = provider_config.get("env").and_then(|v| v.as_object()) {
+    // Bedrock / Vertex select the cloud and its region instead of a base URL and token
+    if let Some(cloud_env) = super::cloud::cloud_env(provider_config) {
+        env.extend(cloud_env);
+    } else if let Some(env_config) = provider_config.get("env").and_then(|v| v.as_object()) {
         // 兼容旧版本：优先使用 ANTHROPIC_AUTH_TOKEN，如果没有则使用 ANTHROPIC_API_KEY
         let api_key = env_config
             .get("ANTHROPIC_AUTH_TOKEN")
@@ -570,46 +976,158 @@ pub async fn apply_config_to_file_public(
         );
     }
 
-    // Merge common config and provider env
+    env.extend(super::limits::limits_env(&super::limits::provider_limits(provider_config)));
+
+    // Only this provider goes through the proxy: the next provider without one
+    // drops the vars, from settings.json by the rewrite and from
+    // settings.local.json via `managed_env_keys`
+    if let Some(proxy) = provider_proxy(provider_config) {
+        env.insert("HTTPS_PROXY".to_string(), serde_json::json!(proxy));
+        env.insert("HTTP_PROXY".to_string(), serde_json::json!(proxy));
+        let no_proxy = provider_config
+            .get("noProxy")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|v| !v.is_empty());
+        if let Some(no_proxy) = no_proxy {
+            env.insert("NO_PROXY".to_string(), serde_json::json!(no_proxy));
+        }
+    }
+
+    env
+}
+
+/// settings.json content for a provider: the common config merged with the
+/// provider's settings, model rules, hook sets and permission presets
+pub async fn build_settings(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    provider_id: &str,
+) -> AppResult<serde_json::Map<String, Value>> {
+    // Get the provider
+    // Use type::thing(table, id) to create a Thing from table name and id
+    let records: Vec<Value> = db
+        .query("SELECT *, type::string(id) as id FROM claude_provider WHERE id = type::thing('claude_provider', $id) LIMIT 1")
+        .bind(("id", provider_id.to_string()))
+        .await?
+        .take(0)?;
+    let provider = records
+        .into_iter()
+        .next()
+        .map(adapter::from_db_value_provider)
+        .ok_or_else(|| AppError::not_found("PROVIDER_NOT_FOUND", &[("id", provider_id)]))?;
+
+    // Check if provider is disabled
+    if provider.is_disabled {
+        return Err(AppError::conflict("PROVIDER_DISABLED", &[("id", provider_id)]));
+    }
+    if provider.is_archived {
+        return Err(AppError::conflict("PROVIDER_ARCHIVED", &[("id", provider_id)]));
+    }
+
+    // Parse provider settings_config, resolving vault:// key references
+    let mut provider_config: serde_json::Value =
+        serde_json::from_str(&provider.settings_config).map_err(AppError::invalid)?;
+    crate::coding::vault::resolve_references(&mut provider_config)?;
+
+    // Get common config
+    let common_config_result: Result<Vec<Value>, _> = db
+        .query("SELECT * OMIT id FROM claude_common_config:`common` LIMIT 1")
+        .await?
+        .take(0);
+
+    let common_config: serde_json::Value = match common_config_result {
+        Ok(records) => {
+            if let Some(record) = records.first() {
+                let config = adapter::from_db_value_common(record.clone());
+                serde_json::from_str(&config.config).map_err(AppError::invalid)?
+            } else {
+                serde_json::json!({})
+            }
+        }
+        Err(_) => serde_json::json!({}),
+    };
+
+    // Overlay the provider's env and settings onto the common config, per its `$merge` rules
     let mut final_settings = if let serde_json::Value::Object(map) = common_config {
         map
     } else {
         serde_json::Map::new()
     };
+    let merge_rules = super::merge::take_rules(&mut final_settings)?;
+    super::merge::merge(&mut final_settings, super::merge::provider_overlay(&provider_config), &merge_rules);
 
-    // Get or create env from common config
-    let mut merged_env = final_settings
-        .get("env")
-        .and_then(|v| v.as_object())
-        .cloned()
-        .unwrap_or_default();
+    let mut merged_env = match final_settings.remove("env") {
+        Some(serde_json::Value::Object(env)) => env,
+        _ => serde_json::Map::new(),
+    };
 
-    // Merge provider env into common env (provider takes precedence)
-    for (key, value) in env {
-        merged_env.insert(key, value);
-    }
+    // Route requested models through the model rules
+    let rules = model_rules::load_rules(db).await;
+    model_rules::apply_to_claude_env(&rules, &mut merged_env);
+
+    // Global token limits, unless the provider sets its own
+    let token_limits = super::limits::load_global(db).await;
+    super::limits::apply_global(&token_limits, &provider_config, &mut merged_env);
+
+    // Hooks of the enabled hook sets go after the common config's own hooks
+    let hook_sets = super::hooks::load_hook_sets(db).await.map_err(AppError::db)?;
+    super::hooks::merge_into_settings(&hook_sets, &mut final_settings);
 
-    // Remove old env and insert merged env at the end (env should be at the bottom)
-    final_settings.remove("env");
+    // Rules of the enabled permission presets go after the common config's own rules
+    let permission_presets = super::permissions::load_presets(db).await?;
+    super::permissions::merge_into_settings(&permission_presets, &mut final_settings);
+
+    // Insert merged env at the end (env should be at the bottom)
     final_settings.insert("env".to_string(), serde_json::json!(merged_env));
+    Ok(final_settings)
+}
+
+/// Public version of apply_config_to_file for tray module
+pub async fn apply_config_to_file_public(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    provider_id: &str,
+) -> AppResult<()> {
+    let final_settings = build_settings(db, provider_id).await?;
+
+    // With the "local" target the settings go into settings.local.json, merged
+    // with what is already there (often keys the user keeps out of settings.json);
+    // env vars of the previous provider are dropped first
+    if super::local_settings::load_apply_target(db).await == "local" {
+        let provider_configs: Vec<String> = db
+            .query("SELECT VALUE settings_config FROM claude_provider")
+            .await
+            .and_then(|mut result| result.take(0))?;
+        let provider_configs: Vec<Value> = provider_configs
+            .iter()
+            .filter_map(|config| serde_json::from_str(config).ok())
+            .collect();
+        super::local_settings::apply_into_file(
+            &super::local_settings::local_settings_path()?,
+            final_settings,
+            &managed_env_keys(&provider_configs),
+        )?;
+        super::agents::sync_presets(db).await.map_err(AppError::io)?;
+        return Ok(());
+    }
 
     // Write to settings.json
-    let config_path_str = get_claude_config_path()?;
+    let config_path_str = get_claude_config_path().map_err(AppError::io)?;
     let config_path = Path::new(&config_path_str);
 
     // Ensure directory exists
     if let Some(parent) = config_path.parent() {
         if !parent.exists() {
-            fs::create_dir_all(parent)
-                .map_err(|e| format!("Failed to create .claude directory: {}", e))?;
+            fs::create_dir_all(parent)?;
         }
     }
 
-    let json_content = serde_json::to_string_pretty(&final_settings)
-        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    let json_content = serde_json::to_string_pretty(&final_settings).map_err(AppError::invalid)?;
 
-    fs::write(config_path, json_content)
-        .map_err(|e| format!("Failed to write settings file: {}", e))?;
+    // Temp file + rename: a crash or full disk leaves the previous settings.json intact
+    change_history::write_config("claude_code", config_path, json_content)?;
+
+    // Enabled agent presets go to ~/.claude/agents with every apply
+    super::agents::sync_presets(db).await.map_err(AppError::io)?;
 
     Ok(())
 }
@@ -620,7 +1138,7 @@ pub async fn toggle_claude_code_provider_disabled(
     app: tauri::AppHandle,
     provider_id: String,
     is_disabled: bool,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let db = state.0.lock().await;
 
     // Update is_disabled field in database
@@ -631,17 +1149,14 @@ pub async fn toggle_claude_code_provider_disabled(
     ))
     .bind(("is_disabled", is_disabled))
     .bind(("now", now))
-    .await
-    .map_err(|e| format!("Failed to toggle provider disabled status: {}", e))?;
+    .await?;
 
     // If this provider is applied and now disabled, re-apply config to update files
     let provider: Option<Value> = db
         .query("SELECT *, type::string(id) as id FROM claude_provider WHERE id = type::thing('claude_provider', $id)")
         .bind(("id", provider_id.clone()))
-        .await
-        .map_err(|e| format!("Failed to query provider: {}", e))?
-        .take(0)
-        .map_err(|e| format!("Failed to parse provider: {}", e))?;
+        .await?
+        .take(0)?;
 
     if let Some(provider_value) = provider {
         let is_applied = provider_value
@@ -659,47 +1174,147 @@ pub async fn toggle_claude_code_provider_disabled(
     Ok(())
 }
 
+/// Archive a provider: it is kept (and can be restored) but hidden from the active list
+#[tauri::command]
+pub async fn archive_claude_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+) -> AppResult<()> {
+    set_archived(&state, &app, &id, true).await
+}
+
+#[tauri::command]
+pub async fn unarchive_claude_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+) -> AppResult<()> {
+    set_archived(&state, &app, &id, false).await
+}
+
+async fn set_archived(state: &DbState, app: &tauri::AppHandle, id: &str, is_archived: bool) -> AppResult<()> {
+    let db = state.0.lock().await;
+    let records: Vec<Value> = db
+        .query("SELECT *, type::string(id) as id FROM type::thing('claude_provider', $id)")
+        .bind(("id", id.to_string()))
+        .await?
+        .take(0)?;
+    let provider = records
+        .into_iter()
+        .next()
+        .map(adapter::from_db_value_provider)
+        .ok_or_else(|| AppError::not_found("PROVIDER_NOT_FOUND", &[("id", id)]))?;
+    if is_archived && provider.is_applied {
+        return Err(AppError::conflict("PROVIDER_APPLIED", &[("name", provider.name.as_str())]));
+    }
+
+    db.query("UPDATE type::thing('claude_provider', $id) SET is_archived = $is_archived, updated_at = $now")
+        .bind(("id", id.to_string()))
+        .bind(("is_archived", is_archived))
+        .bind(("now", Local::now().to_rfc3339()))
+        .await?;
+
+    emit_change(app, "claude_provider", ChangeAction::Updated, Some(id), "window");
+    Ok(())
+}
+
 /// Apply Claude Code provider configuration to settings.json
+/// With `dry_run` nothing is written and the settings.json diff is returned.
 #[tauri::command]
 pub async fn apply_claude_config(
     state: tauri::State<'_, DbState>,
     app: tauri::AppHandle,
     provider_id: String,
-) -> Result<(), String> {
+    dry_run: Option<bool>,
+) -> AppResult<Vec<change_history::ConfigDiff>> {
     let db = state.0.lock().await;
-    apply_config_internal(&db, &app, &provider_id, false).await
+    if dry_run.unwrap_or(false) {
+        return change_history::preview(true, apply_config_to_file(&db, &provider_id)).await;
+    }
+    apply_config_internal(&db, &app, &provider_id, false).await?;
+    Ok(Vec::new())
 }
 
-/// Internal function to apply config: writes to file and updates database
+/// Move the applied flag to `id` (or clear it), both updates in one transaction
+pub(super) async fn set_applied_flag(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    id: Option<&str>,
+) -> AppResult<()> {
+    db.query(
+        "BEGIN TRANSACTION;
+         UPDATE claude_provider SET is_applied = false, updated_at = $now WHERE is_applied = true;
+         IF $id != NONE {
+             UPDATE claude_provider SET is_applied = true, updated_at = $now WHERE id = type::thing('claude_provider', $id);
+         };
+         COMMIT TRANSACTION;",
+    )
+    .bind(("id", id.map(|id| id.to_string())))
+    .bind(("now", Local::now().to_rfc3339()))
+    .await
+    .and_then(|response| response.check())?;
+    Ok(())
+}
+
+/// Select and apply a provider in one step: the applied flag moves to it and
+/// settings.json is written, or on failure the flag stays where it was
+#[tauri::command]
+pub async fn activate_claude_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+    apply_config_internal(&db, &app, &id, false).await
+}
+
+/// Internal function to apply config: updates database and writes to file
 /// This is the single source of truth for applying a Claude Code provider config
 pub async fn apply_config_internal<R: tauri::Runtime>(
     db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
     app: &tauri::AppHandle<R>,
     provider_id: &str,
     from_tray: bool,
-) -> Result<(), String> {
-    // 应用配置到文件
-    apply_config_to_file(db, provider_id).await?;
+) -> AppResult<()> {
+    // Keep a copy of the current settings.json, hand edits included
+    super::backup::backup_settings().map_err(AppError::io)?;
 
-    // Update provider's is_applied status
-    let now = Local::now().to_rfc3339();
+    // Flags first, in one transaction; put them back if the file cannot be written
+    let previous: Vec<String> = db
+        .query("SELECT VALUE type::string(id) FROM claude_provider WHERE is_applied = true")
+        .await?
+        .take(0)?;
+    set_applied_flag(db, Some(provider_id)).await?;
 
-    // Mark all providers as not applied (only update the currently applied one)
-    db.query("UPDATE claude_provider SET is_applied = false, updated_at = $now WHERE is_applied = true")
-        .bind(("now", now.clone()))
-        .await
-        .map_err(|e| format!("Failed to reset applied status: {}", e))?;
+    // 应用配置到文件
+    if let Err(e) = apply_config_to_file(db, provider_id).await {
+        let previous = previous.first().map(|id| crate::coding::db_clean_id(id));
+        if let Err(rollback) = set_applied_flag(db, previous.as_deref()).await {
+            log::warn!("Failed to restore applied status: {}", rollback);
+        }
+        return Err(e);
+    }
+    crate::coding::switch_analytics::record_switch(db, "claude_provider", provider_id).await;
+    super::provider_history::record_version_by_id(db, provider_id, "apply").await;
+    super::apply_history::record(db, provider_id, if from_tray { "tray" } else { "window" }).await;
 
-    // Mark target provider as applied
-    db.query("UPDATE claude_provider SET is_applied = true, updated_at = $now WHERE id = type::thing('claude_provider', $id)")
+    // Record in recent actions
+    let provider_name: Option<String> = db
+        .query("SELECT VALUE name FROM claude_provider WHERE id = type::thing('claude_provider', $id) LIMIT 1")
         .bind(("id", provider_id.to_string()))
-        .bind(("now", now))
         .await
-        .map_err(|e| format!("Failed to set applied status: {}", e))?;
+        .ok()
+        .and_then(|mut result| result.take::<Vec<String>>(0).ok())
+        .and_then(|names| names.into_iter().next());
+    crate::activity_log::record(
+        app,
+        "provider",
+        format!("Claude Code 已切换到 {}", provider_name.as_deref().unwrap_or(provider_id)),
+    );
 
     // Notify based on source
     let payload = if from_tray { "tray" } else { "window" };
-    let _ = app.emit("config-changed", payload);
+    emit_change(app, "claude_provider", ChangeAction::Applied, None, payload);
 
     // Trigger WSL sync via event (Windows only)
     #[cfg(target_os = "windows")]
@@ -716,13 +1331,12 @@ pub async fn apply_config_internal<R: tauri::Runtime>(
 #[tauri::command]
 pub async fn get_claude_common_config(
     state: tauri::State<'_, DbState>,
-) -> Result<Option<ClaudeCommonConfig>, String> {
+) -> AppResult<Option<ClaudeCommonConfig>> {
     let db = state.0.lock().await;
 
     let records_result: Result<Vec<Value>, _> = db
         .query("SELECT *, type::string(id) as id FROM claude_common_config:`common` LIMIT 1")
-        .await
-        .map_err(|e| format!("Failed to query common config: {}", e))?
+        .await?
         .take(0);
 
 match records_result {
@@ -744,7 +1358,7 @@ match records_result {
                 Ok(Some(temp_common))
             } else {
                 // 反序列化失败，删除旧数据以修复版本冲突
-                eprintln!("⚠️ Claude common config has incompatible format, cleaning up: {}", e);
+                log::warn!("Claude common config has incompatible format, cleaning up: {}", e);
                 let _ = db.query("DELETE claude_common_config:`common`").await;
                 Ok(None)
             }
@@ -809,26 +1423,26 @@ pub async fn save_claude_common_config(
     state: tauri::State<'_, DbState>,
     app: tauri::AppHandle,
     config: String,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let db = state.0.lock().await;
 
-    // Validate JSON
-    let _: serde_json::Value =
-        serde_json::from_str(&config).map_err(|e| format!("Invalid JSON: {}", e))?;
+    // Validate JSON and its merge rules
+    let value: serde_json::Value = serde_json::from_str(&config).map_err(AppError::invalid)?;
+    if let serde_json::Value::Object(mut map) = value {
+        super::merge::take_rules(&mut map)?;
+    }
 
     let json_data = adapter::to_db_value_common(&config);
 
     // Use UPSERT to handle both update and create
     db.query("UPSERT claude_common_config:`common` CONTENT $data")
         .bind(("data", json_data))
-        .await
-        .map_err(|e| format!("Failed to save common config: {}", e))?;
+        .await?;
 
     // 查找当前应用的 provider，如果存在则重新应用到文件
     let applied_result: Result<Vec<Value>, _> = db
         .query("SELECT *, type::string(id) as id FROM claude_provider WHERE is_applied = true LIMIT 1")
-        .await
-        .map_err(|e| format!("Failed to query applied provider: {}", e))?
+        .await?
         .take(0);
 
     if let Ok(records) = applied_result {
@@ -836,14 +1450,14 @@ pub async fn save_claude_common_config(
             let applied_provider = adapter::from_db_value_provider(record.clone());
             // 重新应用配置到文件（不改变数据库中的 is_applied 状态）
             if let Err(e) = apply_config_to_file(&db, &applied_provider.id).await {
-                eprintln!("Failed to auto-apply config after common config update: {}", e);
+                log::warn!("Failed to auto-apply config after common config update: {}", e);
                 // 不中断保存流程，只记录错误
             }
         }
     }
 
     // Notify frontend to refresh
-    let _ = app.emit("config-changed", "window");
+    emit_change(&app, "claude_common_config", ChangeAction::Updated, None, "window");
 
     Ok(())
 }
@@ -856,11 +1470,11 @@ pub async fn save_claude_local_config(
     state: tauri::State<'_, DbState>,
     app: tauri::AppHandle,
     input: ClaudeLocalConfigInput,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let db = state.0.lock().await;
 
     // Load base provider/common from local settings
-    let base_provider = load_temp_provider_from_file().await?;
+    let base_provider = load_temp_provider_from_file().await.map_err(AppError::io)?;
     let base_common = load_temp_common_config_from_file().await.ok();
 
     let provider_input = input.provider;
@@ -890,8 +1504,7 @@ pub async fn save_claude_local_config(
 
     let common_config = if let Some(config) = input.common_config {
         // Validate JSON
-        let _: serde_json::Value =
-            serde_json::from_str(&config).map_err(|e| format!("Invalid JSON: {}", e))?;
+        let _: serde_json::Value = serde_json::from_str(&config).map_err(AppError::invalid)?;
         config
     } else if let Some(common) = base_common {
         common.config
@@ -912,6 +1525,7 @@ pub async fn save_claude_local_config(
         sort_index: provider_sort_index,
         is_applied: true,
         is_disabled: false,
+        is_archived: false,
         created_at: now.clone(),
         updated_at: now,
     };
@@ -919,31 +1533,28 @@ pub async fn save_claude_local_config(
     let provider_json = adapter::to_db_value_provider(&provider_content);
     db.query("CREATE claude_provider CONTENT $data")
         .bind(("data", provider_json))
-        .await
-        .map_err(|e| format!("Failed to create provider: {}", e))?;
+        .await?;
 
     let common_json = adapter::to_db_value_common(&common_config);
     db.query("UPSERT claude_common_config:`common` CONTENT $data")
         .bind(("data", common_json))
-        .await
-        .map_err(|e| format!("Failed to save common config: {}", e))?;
+        .await?;
 
     // Re-apply config to file using the newly created provider
     let created_result: Result<Vec<Value>, _> = db
         .query("SELECT *, type::string(id) as id FROM claude_provider ORDER BY created_at DESC LIMIT 1")
-        .await
-        .map_err(|e| format!("Failed to fetch created provider: {}", e))?
+        .await?
         .take(0);
     if let Ok(records) = created_result {
         if let Some(record) = records.first() {
             let created_provider = adapter::from_db_value_provider(record.clone());
             if let Err(e) = apply_config_to_file(&db, &created_provider.id).await {
-                eprintln!("Failed to apply config after local save: {}", e);
+                log::warn!("Failed to apply config after local save: {}", e);
             }
         }
     }
 
-    let _ = app.emit("config-changed", "window");
+    emit_change(&app, "claude_local_config", ChangeAction::Updated, None, "window");
     Ok(())
 }
 
@@ -954,11 +1565,7 @@ pub async fn save_claude_local_config(
 
 /// Get Claude plugin config path (~/.claude/config.json)
 fn get_claude_plugin_config_path() -> Result<std::path::PathBuf, String> {
-    let home_dir = std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .map_err(|_| "Failed to get home directory".to_string())?;
-
-    Ok(std::path::Path::new(&home_dir).join(".claude").join("config.json"))
+    Ok(get_claude_config_dir()?.join("config.json"))
 }
 
 /// Check if plugin config has primaryApiKey = "any"
@@ -1045,7 +1652,7 @@ pub async fn apply_claude_plugin_config(enabled: bool) -> Result<bool, String> {
     let serialized = serde_json::to_string_pretty(&serde_json::Value::Object(obj))
         .map_err(|e| format!("Failed to serialize config: {}", e))?;
 
-    fs::write(&config_path, format!("{serialized}\n"))
+    change_history::write_config("claude_code", &config_path, format!("{serialized}\n"))
         .map_err(|e| format!("Failed to write config file: {}", e))?;
 
     Ok(true)
@@ -1201,6 +1808,7 @@ pub async fn init_claude_provider_from_settings(
         sort_index: Some(0),
         is_applied: true,
         is_disabled: false,
+        is_archived: false,
         created_at: now.clone(),
         updated_at: now,
     };
@@ -1213,7 +1821,7 @@ pub async fn init_claude_provider_from_settings(
         .await
         .map_err(|e| format!("Failed to create default provider: {}", e))?;
 
-    println!("✅ Imported Claude Code settings from settings.json as default provider");
+    log::info!("Imported Claude Code settings from settings.json as default provider");
 
     Ok(())
 }
@@ -1224,11 +1832,7 @@ pub async fn init_claude_provider_from_settings(
 
 /// Get the Claude MCP config path (~/.claude.json)
 fn get_claude_mcp_config_path() -> Result<std::path::PathBuf, String> {
-    let home_dir = std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .map_err(|_| "Failed to get home directory".to_string())?;
-
-    Ok(std::path::Path::new(&home_dir).join(".claude.json"))
+    get_claude_state_path()
 }
 
 /// Get Claude onboarding status
@@ -1302,7 +1906,7 @@ pub async fn apply_claude_onboarding_skip() -> Result<bool, String> {
     let serialized = serde_json::to_string_pretty(&serde_json::Value::Object(obj))
         .map_err(|e| format!("Failed to serialize config: {}", e))?;
 
-    fs::write(&config_path, format!("{serialized}\n"))
+    change_history::write_config("claude_code", &config_path, format!("{serialized}\n"))
         .map_err(|e| format!("Failed to write config file: {}", e))?;
 
     Ok(true)
@@ -1338,8 +1942,152 @@ pub async fn clear_claude_onboarding_skip() -> Result<bool, String> {
     let serialized = serde_json::to_string_pretty(&serde_json::Value::Object(obj))
         .map_err(|e| format!("Failed to serialize config: {}", e))?;
 
-    fs::write(&config_path, format!("{serialized}\n"))
+    change_history::write_config("claude_code", &config_path, format!("{serialized}\n"))
         .map_err(|e| format!("Failed to write config file: {}", e))?;
 
     Ok(true)
 }
+
+// ============================================================================
+// Provider Registration Helpers
+// ============================================================================
+
+/// Create or update a Claude Code provider by name for an Anthropic-compatible endpoint
+/// Used by local inference integrations (Ollama, LM Studio, ...) to register themselves
+/// Returns the provider id
+pub async fn upsert_claude_provider_by_name(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    name: &str,
+    base_url: &str,
+    auth_token: &str,
+    model: Option<&String>,
+    website_url: Option<&str>,
+) -> Result<String, String> {
+    let mut env = serde_json::Map::new();
+    env.insert("ANTHROPIC_BASE_URL".to_string(), Value::String(base_url.to_string()));
+    env.insert("ANTHROPIC_AUTH_TOKEN".to_string(), Value::String(auth_token.to_string()));
+    let mut settings_config = serde_json::Map::new();
+    settings_config.insert("env".to_string(), Value::Object(env));
+    if let Some(model) = model {
+        settings_config.insert("model".to_string(), Value::String(model.clone()));
+    }
+    let settings_config = serde_json::to_string(&settings_config)
+        .map_err(|e| format!("Failed to serialize: {}", e))?;
+
+    let existing: Result<Vec<Value>, _> = db
+        .query("SELECT *, type::string(id) as id FROM claude_provider WHERE name = $name LIMIT 1")
+        .bind(("name", name.to_string()))
+        .await
+        .map_err(|e| format!("Failed to query provider: {}", e))?
+        .take(0);
+
+    let now = Local::now().to_rfc3339();
+    match existing.ok().and_then(|records| records.into_iter().next()) {
+        Some(record) => {
+            let provider = adapter::from_db_value_provider(record);
+            db.query(format!(
+                "UPDATE claude_provider:`{}` SET settings_config = $settings_config, updated_at = $updated_at",
+                provider.id
+            ))
+            .bind(("settings_config", settings_config))
+            .bind(("updated_at", now))
+            .await
+            .map_err(|e| format!("Failed to update provider: {}", e))?;
+            Ok(provider.id)
+        }
+        None => {
+            let content = ClaudeCodeProviderContent {
+                name: name.to_string(),
+                category: "custom".to_string(),
+                settings_config,
+                source_provider_id: None,
+                website_url: website_url.map(|s| s.to_string()),
+                notes: None,
+                icon: None,
+                icon_color: None,
+                sort_index: None,
+                is_applied: false,
+                is_disabled: false,
+                is_archived: false,
+                created_at: now.clone(),
+                updated_at: now,
+            };
+            db.query("CREATE claude_provider CONTENT $data")
+                .bind(("data", adapter::to_db_value_provider(&content)))
+                .await
+                .map_err(|e| format!("Failed to create provider: {}", e))?;
+
+            // Fetch the created record to get the auto-generated ID
+            let created: Vec<Value> = db
+                .query("SELECT *, type::string(id) as id FROM claude_provider WHERE name = $name LIMIT 1")
+                .bind(("name", name.to_string()))
+                .await
+                .map_err(|e| format!("Failed to fetch created provider: {}", e))?
+                .take(0)
+                .map_err(|e| format!("Failed to fetch created provider: {}", e))?;
+            created
+                .into_iter()
+                .next()
+                .map(|record| adapter::from_db_value_provider(record).id)
+                .ok_or_else(|| "Failed to retrieve created provider".to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn provider_conditions_follow_filters() {
+        let query = normalize_query(None);
+        assert_eq!(provider_conditions(false, &query), " WHERE is_archived != true");
+        assert_eq!(provider_conditions(true, &query), "");
+
+        let query = normalize_query(Some(ClaudeProviderQuery {
+            category: Some("custom".to_string()),
+            search: Some("  Relay ".to_string()),
+            archived: Some(true),
+            ..Default::default()
+        }));
+        assert_eq!(query.search.as_deref(), Some("relay"));
+        let conditions = provider_conditions(false, &query);
+        assert!(conditions.starts_with(" WHERE (is_archived ?? false) = $archived AND category = $category AND "));
+        assert!(conditions.contains("$search"));
+
+        let blank = normalize_query(Some(ClaudeProviderQuery {
+            category: Some(" ".to_string()),
+            search: Some(String::new()),
+            ..Default::default()
+        }));
+        assert_eq!(provider_conditions(true, &blank), "");
+    }
+
+    #[test]
+    fn proxy_env_only_with_proxy() {
+        let env = provider_env(&serde_json::json!({
+            "env": { "ANTHROPIC_BASE_URL": "https://relay.example.com" },
+            "proxy": " 127.0.0.1:7890 ",
+            "noProxy": "localhost,127.0.0.1"
+        }));
+        assert_eq!(env["HTTPS_PROXY"], "http://127.0.0.1:7890");
+        assert_eq!(env["HTTP_PROXY"], "http://127.0.0.1:7890");
+        assert_eq!(env["NO_PROXY"], "localhost,127.0.0.1");
+
+        let env = provider_env(&serde_json::json!({ "proxy": "", "noProxy": "localhost" }));
+        assert!(env.is_empty());
+
+        // Removed from a merged settings file even when no provider sets them now
+        let managed = managed_env_keys([]);
+        assert!(["HTTPS_PROXY", "HTTP_PROXY", "NO_PROXY"].iter().all(|key| managed.contains(*key)));
+    }
+
+    #[test]
+    fn expand_home_only_touches_leading_tilde() {
+        let home = home_dir().unwrap();
+        assert_eq!(expand_home("~"), home);
+        assert_eq!(expand_home("~/claude-work"), home.join("claude-work"));
+        assert_eq!(expand_home("/opt/claude"), PathBuf::from("/opt/claude"));
+        assert_eq!(expand_home("~other/claude"), PathBuf::from("~other/claude"));
+    }
+}
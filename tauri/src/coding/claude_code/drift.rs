@@ -0,0 +1,172 @@
+//! Drift between settings.json and the applied provider
+//!
+//! settings.json can be edited by hand or by other tools after a provider is
+//! applied. The drift check rebuilds what applying the current provider would
+//! write (see `build_settings`) and compares it with the live file key by key:
+//! objects are compared per key, arrays and scalars as whole values. With the
+//! "local" apply target settings.local.json is checked instead.
+
+use std::fs;
+use std::path::PathBuf;
+
+use serde_json::{Map, Value};
+
+use crate::db::DbState;
+use crate::error::{AppError, AppResult};
+use crate::secret_scan;
+use super::adapter;
+use super::commands::{build_settings, get_claude_config_path};
+use super::local_settings::{load_apply_target, local_settings_path};
+use super::types::{ClaudeConfigDrift, ClaudeConfigDriftEntry};
+
+/// Mask string values under secret-looking keys so the report can be shown as is
+fn display_value(key: &str, value: &Value) -> Value {
+    match value {
+        Value::String(text) if secret_scan::is_secret_field(key) => Value::String(secret_scan::mask(text)),
+        _ => value.clone(),
+    }
+}
+
+fn entry(path: &str, key: &str, kind: &str, expected: Option<&Value>, actual: Option<&Value>) -> ClaudeConfigDriftEntry {
+    ClaudeConfigDriftEntry {
+        key: path.to_string(),
+        kind: kind.to_string(),
+        expected: expected.map(|v| display_value(key, v)),
+        actual: actual.map(|v| display_value(key, v)),
+    }
+}
+
+/// Differences between `expected` and `actual`, by dot path in key order of
+/// `expected` followed by the unexpected keys of `actual`
+pub fn diff(expected: &Map<String, Value>, actual: &Map<String, Value>) -> Vec<ClaudeConfigDriftEntry> {
+    let mut entries = Vec::new();
+    diff_at("", expected, actual, &mut entries);
+    entries
+}
+
+fn diff_at(
+    prefix: &str,
+    expected: &Map<String, Value>,
+    actual: &Map<String, Value>,
+    entries: &mut Vec<ClaudeConfigDriftEntry>,
+) {
+    for (key, want) in expected {
+        let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+        match (want, actual.get(key)) {
+            (_, None) => entries.push(entry(&path, key, "missing", Some(want), None)),
+            (Value::Object(want), Some(Value::Object(have))) => diff_at(&path, want, have, entries),
+            (want, Some(have)) if want != have => entries.push(entry(&path, key, "changed", Some(want), Some(have))),
+            _ => {}
+        }
+    }
+    for (key, have) in actual {
+        if !expected.contains_key(key) {
+            let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+            entries.push(entry(&path, key, "unexpected", None, Some(have)));
+        }
+    }
+}
+
+// ============================================================================
+// Claude Config Drift Commands
+// ============================================================================
+
+/// Compare the live settings.json with what the applied provider and the
+/// common config would produce
+#[tauri::command]
+pub async fn check_claude_config_drift(state: tauri::State<'_, DbState>) -> AppResult<ClaudeConfigDrift> {
+    let db = state.0.lock().await;
+    let local_target = load_apply_target(&db).await == "local";
+    let config_path = if local_target {
+        local_settings_path()?
+    } else {
+        PathBuf::from(get_claude_config_path().map_err(AppError::io)?)
+    };
+    let file_exists = config_path.exists();
+
+    let records: Vec<Value> = db
+        .query("SELECT *, type::string(id) as id FROM claude_provider WHERE is_applied = true LIMIT 1")
+        .await?
+        .take(0)?;
+    let Some(provider) = records.into_iter().next().map(adapter::from_db_value_provider) else {
+        return Ok(ClaudeConfigDrift {
+            provider_id: None,
+            provider_name: None,
+            file_exists,
+            in_sync: true,
+            entries: Vec::new(),
+        });
+    };
+    let expected = build_settings(&db, &provider.id).await?;
+    drop(db);
+
+    let actual = if file_exists {
+        let content = fs::read_to_string(&config_path)?;
+        match serde_json::from_str(&content) {
+            Ok(Value::Object(map)) => map,
+            Ok(_) => return Err(AppError::invalid(format!("{} is not a JSON object", config_path.display()))),
+            Err(e) => return Err(AppError::invalid(format!("{}: {}", config_path.display(), e))),
+        }
+    } else {
+        Map::new()
+    };
+
+    let mut entries = diff(&expected, &actual);
+    if local_target {
+        // Apply merges into settings.local.json, so keys of its own are expected
+        entries.retain(|entry| entry.kind != "unexpected");
+    }
+    Ok(ClaudeConfigDrift {
+        provider_id: Some(provider.id),
+        provider_name: Some(provider.name),
+        file_exists,
+        in_sync: entries.is_empty(),
+        entries,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn reports_changed_missing_and_unexpected_keys() {
+        let expected = object(serde_json::json!({
+            "env": {
+                "ANTHROPIC_BASE_URL": "https://relay.example.com",
+                "ANTHROPIC_AUTH_TOKEN": "sk-relay-0123456789abcdef",
+                "API_TIMEOUT_MS": "600000"
+            },
+            "permissions": { "allow": ["Read"] },
+            "includeCoAuthoredBy": false
+        }));
+        let actual = object(serde_json::json!({
+            "env": {
+                "ANTHROPIC_BASE_URL": "https://relay.example.com",
+                "ANTHROPIC_AUTH_TOKEN": "sk-other-0123456789abcdef",
+                "DISABLE_TELEMETRY": "1"
+            },
+            "permissions": { "allow": ["Read", "WebFetch"] },
+            "includeCoAuthoredBy": false
+        }));
+
+        let entries = diff(&expected, &actual);
+        let summary: Vec<(&str, &str)> = entries.iter().map(|e| (e.key.as_str(), e.kind.as_str())).collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("env.ANTHROPIC_AUTH_TOKEN", "changed"),
+                ("env.API_TIMEOUT_MS", "missing"),
+                ("env.DISABLE_TELEMETRY", "unexpected"),
+                ("permissions.allow", "changed"),
+            ]
+        );
+        assert_eq!(entries[0].expected, Some(Value::String("sk-rel…cdef".to_string())));
+        assert_eq!(entries[0].actual, Some(Value::String("sk-oth…cdef".to_string())));
+        assert!(diff(&expected, &expected).is_empty());
+    }
+}
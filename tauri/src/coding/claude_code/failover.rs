@@ -0,0 +1,283 @@
+//! Claude provider health monitor and failover
+//!
+//! While enabled, a background task (started from setup) pings the base URL
+//! of the applied provider every `interval_secs`. Any HTTP response below 500
+//! counts as healthy; connection errors, timeouts and 5xx count as failures.
+//! After `failure_threshold` consecutive failures the next enabled provider in
+//! `chain` is applied and a `claude-provider-failover` event plus a
+//! notification are emitted. Results are kept in `claude_provider_health`.
+
+use std::time::{Duration, Instant};
+
+use chrono::Local;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+use crate::db::DbState;
+use crate::error::{AppError, AppResult};
+use crate::notifications::{self, NotificationCategory};
+use super::commands::{apply_config_internal, provider_env};
+use super::provider_io::load_providers;
+use super::types::*;
+
+type Db = surrealdb::Surreal<surrealdb::engine::local::Db>;
+
+const PING_TIMEOUT_SECS: u64 = 10;
+const MIN_INTERVAL_SECS: u64 = 60;
+const DEFAULT_BASE_URL: &str = "https://api.anthropic.com";
+
+pub async fn load_config(db: &Db) -> ClaudeFailoverConfig {
+    let records: Vec<Value> = db
+        .query("SELECT * OMIT id FROM claude_failover:`config` LIMIT 1")
+        .await
+        .and_then(|mut result| result.take(0))
+        .unwrap_or_default();
+    records
+        .into_iter()
+        .next()
+        .and_then(|record| serde_json::from_value(record).ok())
+        .unwrap_or_default()
+}
+
+async fn load_health(db: &Db) -> AppResult<Vec<ClaudeProviderHealth>> {
+    let records: Vec<Value> = db
+        .query("SELECT * OMIT id FROM claude_provider_health")
+        .await?
+        .take(0)?;
+    Ok(records
+        .into_iter()
+        .filter_map(|record| serde_json::from_value(record).ok())
+        .collect())
+}
+
+async fn save_health(db: &Db, health: &ClaudeProviderHealth) -> AppResult<()> {
+    db.query("UPSERT type::thing('claude_provider_health', $id) CONTENT $data")
+        .bind(("id", health.provider_id.clone()))
+        .bind((
+            "data",
+            serde_json::json!({
+                "provider_id": health.provider_id,
+                "consecutive_failures": health.consecutive_failures,
+                "last_checked_at": health.last_checked_at,
+                "last_ok_at": health.last_ok_at,
+                "last_error": health.last_error,
+                "latency_ms": health.latency_ms,
+            }),
+        ))
+        .await?;
+    Ok(())
+}
+
+/// Seconds to wait before the next check
+pub async fn interval(app: &AppHandle) -> Duration {
+    let state = app.state::<DbState>();
+    let db = state.0.lock().await;
+    let config = load_config(&db).await;
+    Duration::from_secs(config.interval_secs.max(MIN_INTERVAL_SECS))
+}
+
+/// Provider to switch to: the first usable one after `current` in the chain,
+/// wrapping around (from the start when `current` is not in the chain)
+fn next_in_chain(chain: &[String], current: &str, usable: impl Fn(&str) -> bool) -> Option<String> {
+    let start = chain.iter().position(|id| id == current).map(|i| i + 1).unwrap_or(0);
+    (0..chain.len())
+        .map(|offset| &chain[(start + offset) % chain.len()])
+        .find(|id| id.as_str() != current && usable(id))
+        .cloned()
+}
+
+/// Base URL the provider sends requests to (the regional endpoint for Bedrock / Vertex)
+fn base_url(provider: &ClaudeCodeProvider) -> AppResult<String> {
+    let mut config: Value = serde_json::from_str(&provider.settings_config).map_err(AppError::invalid)?;
+    crate::coding::vault::resolve_references(&mut config)?;
+    if let Some(endpoint) = super::cloud::endpoint(&config) {
+        return Ok(endpoint);
+    }
+    Ok(provider_env(&config)
+        .get("ANTHROPIC_BASE_URL")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .unwrap_or(DEFAULT_BASE_URL)
+        .to_string())
+}
+
+/// Latency of a reachable endpoint, or why it is not
+async fn ping(client: &reqwest::Client, url: &str) -> Result<u64, String> {
+    let start = Instant::now();
+    let response = client.get(url).send().await.map_err(|e| e.to_string())?;
+    let status = response.status();
+    if status.is_server_error() {
+        return Err(format!("HTTP {}", status.as_u16()));
+    }
+    Ok(start.elapsed().as_millis() as u64)
+}
+
+/// Check the applied provider and fail over when it stays down (run periodically from setup)
+pub async fn check<R: Runtime>(app: &AppHandle<R>) -> AppResult<()> {
+    let state = app.state::<DbState>();
+    let (config, providers, previous) = {
+        let db = state.0.lock().await;
+        let config = load_config(&db).await;
+        if !config.enabled {
+            return Ok(());
+        }
+        (config, load_providers(&db).await?, load_health(&db).await?)
+    };
+    let Some(current) = providers.iter().find(|p| p.is_applied) else {
+        return Ok(());
+    };
+
+    let client = crate::http_client::client_with_timeout(&state, PING_TIMEOUT_SECS)
+        .await
+        .map_err(AppError::invalid)?;
+    let result = match base_url(current) {
+        Ok(url) => ping(&client, &crate::http_client::resolve_url(&state, &url).await).await,
+        Err(e) => Err(e.to_string()),
+    };
+
+    let now = Local::now().to_rfc3339();
+    let mut health = previous
+        .into_iter()
+        .find(|h| h.provider_id == current.id)
+        .unwrap_or(ClaudeProviderHealth {
+            provider_id: current.id.clone(),
+            consecutive_failures: 0,
+            last_checked_at: String::new(),
+            last_ok_at: None,
+            last_error: None,
+            latency_ms: None,
+        });
+    health.last_checked_at = now.clone();
+    match &result {
+        Ok(latency) => {
+            health.consecutive_failures = 0;
+            health.last_ok_at = Some(now);
+            health.last_error = None;
+            health.latency_ms = Some(*latency);
+        }
+        Err(e) => {
+            health.consecutive_failures += 1;
+            health.last_error = Some(e.clone());
+            health.latency_ms = None;
+        }
+    }
+
+    let db = state.0.lock().await;
+    save_health(&db, &health).await?;
+    if health.consecutive_failures < config.failure_threshold.max(1) {
+        return Ok(());
+    }
+
+    let usable = |id: &str| providers.iter().any(|p| p.id == id && !p.is_disabled && !p.is_archived);
+    let Some(next_id) = next_in_chain(&config.chain, &current.id, usable) else {
+        log::warn!("Claude provider {} is down but no failover provider is configured", current.name);
+        return Ok(());
+    };
+    let next_name = providers
+        .iter()
+        .find(|p| p.id == next_id)
+        .map(|p| p.name.clone())
+        .unwrap_or_default();
+
+    apply_config_internal(&db, app, &next_id, false).await?;
+    // Give the new provider a clean slate; the failed one keeps its count
+    db.query("DELETE type::thing('claude_provider_health', $id)")
+        .bind(("id", next_id.clone()))
+        .await?;
+
+    let error = health.last_error.clone().unwrap_or_default();
+    let _ = app.emit(
+        "claude-provider-failover",
+        ClaudeFailoverEvent {
+            from_id: current.id.clone(),
+            from_name: current.name.clone(),
+            to_id: next_id,
+            to_name: next_name.clone(),
+            failures: health.consecutive_failures,
+            error: error.clone(),
+        },
+    );
+    notifications::notify(
+        app,
+        NotificationCategory::ProviderHealth,
+        "Claude Code 供应商已自动切换",
+        format!("{} 连续 {} 次检查失败（{}），已切换到 {}", current.name, health.consecutive_failures, error, next_name),
+    );
+    Ok(())
+}
+
+// ============================================================================
+// Claude Failover Commands
+// ============================================================================
+
+#[tauri::command]
+pub async fn get_claude_failover_config(state: tauri::State<'_, DbState>) -> AppResult<ClaudeFailoverConfig> {
+    let db = state.0.lock().await;
+    Ok(load_config(&db).await)
+}
+
+/// Save the monitor settings and failover order; unknown provider ids are dropped
+#[tauri::command]
+pub async fn save_claude_failover_config(
+    state: tauri::State<'_, DbState>,
+    mut config: ClaudeFailoverConfig,
+) -> AppResult<ClaudeFailoverConfig> {
+    if config.failure_threshold == 0 {
+        return Err(AppError::invalid("failure threshold must be at least 1"));
+    }
+    config.interval_secs = config.interval_secs.max(MIN_INTERVAL_SECS);
+
+    let db = state.0.lock().await;
+    let providers = load_providers(&db).await?;
+    let mut chain: Vec<String> = Vec::new();
+    for id in config.chain {
+        if providers.iter().any(|p| p.id == id) && !chain.contains(&id) {
+            chain.push(id);
+        }
+    }
+    config.chain = chain;
+
+    db.query("UPSERT claude_failover:`config` CONTENT $data")
+        .bind((
+            "data",
+            serde_json::json!({
+                "enabled": config.enabled,
+                "interval_secs": config.interval_secs,
+                "failure_threshold": config.failure_threshold,
+                "chain": config.chain,
+            }),
+        ))
+        .await?;
+    Ok(config)
+}
+
+/// Latest health check results, one per checked provider
+#[tauri::command]
+pub async fn list_claude_provider_health(
+    state: tauri::State<'_, DbState>,
+) -> AppResult<Vec<ClaudeProviderHealth>> {
+    let db = state.0.lock().await;
+    load_health(&db).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn next_in_chain_wraps_and_skips_unusable() {
+        let order = chain(&["a", "b", "c"]);
+        assert_eq!(next_in_chain(&order, "a", |_| true).as_deref(), Some("b"));
+        assert_eq!(next_in_chain(&order, "c", |_| true).as_deref(), Some("a"));
+        assert_eq!(next_in_chain(&order, "a", |id| id != "b").as_deref(), Some("c"));
+        // Not in the chain: start from the top
+        assert_eq!(next_in_chain(&order, "x", |_| true).as_deref(), Some("a"));
+        assert_eq!(next_in_chain(&chain(&["a"]), "a", |_| true), None);
+        assert_eq!(next_in_chain(&[], "a", |_| true), None);
+    }
+}
@@ -0,0 +1,82 @@
+//! YAML front matter of Claude markdown files (slash commands, sub-agents)
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Split `---\n<yaml>\n---\n<body>` into the YAML and the body; files without
+/// front matter are all body
+pub fn split(content: &str) -> (Option<&str>, &str) {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let Some(rest) = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))
+    else {
+        return (None, content);
+    };
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            let body = &rest[offset + line.len()..];
+            return (Some(&rest[..offset]), body);
+        }
+        offset += line.len();
+    }
+    (None, content)
+}
+
+/// Parse the front matter into `T` (default when missing) and return the body
+pub fn parse<T: DeserializeOwned + Default>(content: &str) -> Result<(T, String), String> {
+    let (yaml, body) = split(content);
+    let meta = match yaml {
+        Some(yaml) if !yaml.trim().is_empty() => {
+            serde_yaml::from_str(yaml).map_err(|e| format!("Invalid front matter: {}", e))?
+        }
+        _ => T::default(),
+    };
+    Ok((meta, body.trim_start_matches(['\r', '\n']).to_string()))
+}
+
+/// Markdown file with `meta` as front matter (omitted when it has no fields)
+pub fn render<T: Serialize>(meta: &T, body: &str) -> Result<String, String> {
+    let value = serde_yaml::to_value(meta).map_err(|e| format!("Failed to serialize front matter: {}", e))?;
+    let body = body.trim_start_matches(['\r', '\n']);
+    let mut output = String::new();
+    if value.as_mapping().is_some_and(|m| !m.is_empty()) {
+        let yaml = serde_yaml::to_string(&value).map_err(|e| format!("Failed to serialize front matter: {}", e))?;
+        output.push_str("---\n");
+        output.push_str(&yaml);
+        output.push_str("---\n\n");
+    }
+    output.push_str(body);
+    if !output.ends_with('\n') {
+        output.push('\n');
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+    struct Meta {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+    }
+
+    #[test]
+    fn round_trips_front_matter() {
+        let (meta, body) = parse::<Meta>("---\ndescription: Review a PR\n---\n\nReview $ARGUMENTS\n").unwrap();
+        assert_eq!(meta.description.as_deref(), Some("Review a PR"));
+        assert_eq!(body, "Review $ARGUMENTS\n");
+        assert_eq!(
+            render(&meta, &body).unwrap(),
+            "---\ndescription: Review a PR\n---\n\nReview $ARGUMENTS\n"
+        );
+
+        let (meta, body) = parse::<Meta>("Just a prompt").unwrap();
+        assert_eq!(meta, Meta::default());
+        assert_eq!(render(&meta, &body).unwrap(), "Just a prompt\n");
+    }
+}
@@ -0,0 +1,302 @@
+//! Claude hook sets
+//!
+//! Reusable groups of `hooks` entries (PreToolUse / PostToolUse /
+//! Notification … matchers with their commands) stored in `claude_hook_set`.
+//! On apply, the matchers of every enabled set are appended to the `hooks`
+//! section coming from the common config.
+
+use chrono::Local;
+use serde_json::{Map, Value};
+
+use crate::coding::db_extract_id;
+use crate::db::DbState;
+use crate::events::{emit_change, ChangeAction};
+use super::commands::apply_config_to_file_public;
+use super::types::{ClaudeHookMatcher, ClaudeHookSet, ClaudeHookSetInput};
+
+type Db = surrealdb::Surreal<surrealdb::engine::local::Db>;
+
+/// Hook events Claude Code understands
+pub const HOOK_EVENTS: [&str; 9] = [
+    "PreToolUse",
+    "PostToolUse",
+    "Notification",
+    "UserPromptSubmit",
+    "Stop",
+    "SubagentStop",
+    "PreCompact",
+    "SessionStart",
+    "SessionEnd",
+];
+
+fn validate(input: &ClaudeHookSetInput) -> Result<(), String> {
+    if input.name.trim().is_empty() {
+        return Err("Hook set name is required".to_string());
+    }
+    for (event, matchers) in &input.hooks {
+        if !HOOK_EVENTS.contains(&event.as_str()) {
+            return Err(format!("Unknown hook event: {}", event));
+        }
+        for matcher in matchers {
+            if matcher.hooks.is_empty() {
+                return Err(format!("{} matcher '{}' has no commands", event, matcher.matcher));
+            }
+            if matcher.hooks.iter().any(|hook| hook.command.trim().is_empty()) {
+                return Err(format!("{} matcher '{}' has an empty command", event, matcher.matcher));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn from_db_value(mut value: Value) -> Option<ClaudeHookSet> {
+    let id = db_extract_id(&value);
+    value.as_object_mut()?.insert("id".to_string(), Value::String(id));
+    serde_json::from_value(value).ok()
+}
+
+/// All hook sets, by sort order then name
+pub async fn load_hook_sets(db: &Db) -> Result<Vec<ClaudeHookSet>, String> {
+    let records: Vec<Value> = db
+        .query("SELECT *, type::string(id) as id FROM claude_hook_set")
+        .await
+        .map_err(|e| format!("Failed to query hook sets: {}", e))?
+        .take(0)
+        .map_err(|e| format!("Failed to parse hook sets: {}", e))?;
+    let mut sets: Vec<ClaudeHookSet> = records.into_iter().filter_map(from_db_value).collect();
+    sets.sort_by(|a, b| {
+        a.sort_index
+            .unwrap_or(i32::MAX)
+            .cmp(&b.sort_index.unwrap_or(i32::MAX))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    Ok(sets)
+}
+
+async fn get_hook_set(db: &Db, id: &str) -> Result<ClaudeHookSet, String> {
+    let records: Vec<Value> = db
+        .query("SELECT *, type::string(id) as id FROM type::thing('claude_hook_set', $id)")
+        .bind(("id", id.to_string()))
+        .await
+        .map_err(|e| format!("Failed to query hook set: {}", e))?
+        .take(0)
+        .map_err(|e| format!("Failed to parse hook set: {}", e))?;
+    records
+        .into_iter()
+        .next()
+        .and_then(from_db_value)
+        .ok_or_else(|| format!("Hook set not found: {}", id))
+}
+
+/// Append the matchers of enabled sets to `settings.hooks`, skipping ones already present
+pub fn merge_into_settings(sets: &[ClaudeHookSet], settings: &mut Map<String, Value>) {
+    let enabled: Vec<&ClaudeHookSet> = sets.iter().filter(|set| set.enabled).collect();
+    if enabled.is_empty() {
+        return;
+    }
+    let hooks = settings
+        .entry("hooks".to_string())
+        .or_insert_with(|| Value::Object(Map::new()));
+    if !hooks.is_object() {
+        *hooks = Value::Object(Map::new());
+    }
+    let hooks = hooks.as_object_mut().unwrap();
+
+    for set in enabled {
+        for (event, matchers) in &set.hooks {
+            let entries = hooks
+                .entry(event.clone())
+                .or_insert_with(|| Value::Array(Vec::new()));
+            if !entries.is_array() {
+                *entries = Value::Array(Vec::new());
+            }
+            let entries = entries.as_array_mut().unwrap();
+            for matcher in matchers {
+                let Ok(value) = serde_json::to_value(matcher) else {
+                    continue;
+                };
+                if !entries.contains(&value) {
+                    entries.push(value);
+                }
+            }
+        }
+    }
+}
+
+/// Rewrite settings.json of the applied provider so hook / permission changes take effect
+pub(super) async fn reapply(db: &Db) {
+    let applied: Vec<String> = match db
+        .query("SELECT VALUE type::string(id) FROM claude_provider WHERE is_applied = true LIMIT 1")
+        .await
+        .and_then(|mut result| result.take(0))
+    {
+        Ok(ids) => ids,
+        Err(e) => {
+            log::warn!("Failed to query applied provider: {}", e);
+            return;
+        }
+    };
+    if let Some(id) = applied.first() {
+        let id = crate::coding::db_clean_id(id);
+        if let Err(e) = apply_config_to_file_public(db, &id).await {
+            log::warn!("Failed to auto-apply config after settings preset update: {}", e);
+        }
+    }
+}
+
+fn to_db_value(input: &ClaudeHookSetInput, created_at: &str) -> Value {
+    serde_json::json!({
+        "name": input.name.trim(),
+        "description": input.description,
+        "enabled": input.enabled,
+        "hooks": input.hooks,
+        "sort_index": input.sort_index,
+        "created_at": created_at,
+        "updated_at": Local::now().to_rfc3339(),
+    })
+}
+
+// ============================================================================
+// Claude Hook Set Commands
+// ============================================================================
+
+#[tauri::command]
+pub async fn list_claude_hook_sets(state: tauri::State<'_, DbState>) -> Result<Vec<ClaudeHookSet>, String> {
+    let db = state.0.lock().await;
+    load_hook_sets(&db).await
+}
+
+#[tauri::command]
+pub async fn create_claude_hook_set(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    input: ClaudeHookSetInput,
+) -> Result<ClaudeHookSet, String> {
+    validate(&input)?;
+    let id = uuid::Uuid::new_v4().to_string();
+    let db = state.0.lock().await;
+    db.query("CREATE type::thing('claude_hook_set', $id) CONTENT $data")
+        .bind(("id", id.clone()))
+        .bind(("data", to_db_value(&input, &Local::now().to_rfc3339())))
+        .await
+        .map_err(|e| format!("Failed to create hook set: {}", e))?;
+    let set = get_hook_set(&db, &id).await?;
+
+    if set.enabled {
+        reapply(&db).await;
+    }
+    emit_change(&app, "claude_hook_set", ChangeAction::Created, Some(&set.id), "window");
+    Ok(set)
+}
+
+#[tauri::command]
+pub async fn update_claude_hook_set(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+    input: ClaudeHookSetInput,
+) -> Result<ClaudeHookSet, String> {
+    validate(&input)?;
+    let db = state.0.lock().await;
+    let existing = get_hook_set(&db, &id).await?;
+
+    db.query("UPDATE type::thing('claude_hook_set', $id) CONTENT $data")
+        .bind(("id", id.clone()))
+        .bind(("data", to_db_value(&input, &existing.created_at)))
+        .await
+        .map_err(|e| format!("Failed to update hook set: {}", e))?;
+    let set = get_hook_set(&db, &id).await?;
+
+    if set.enabled || existing.enabled {
+        reapply(&db).await;
+    }
+    emit_change(&app, "claude_hook_set", ChangeAction::Updated, Some(&id), "window");
+    Ok(set)
+}
+
+#[tauri::command]
+pub async fn delete_claude_hook_set(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+) -> Result<(), String> {
+    let db = state.0.lock().await;
+    db.query("DELETE type::thing('claude_hook_set', $id)")
+        .bind(("id", id.clone()))
+        .await
+        .map_err(|e| format!("Failed to delete hook set: {}", e))?;
+    reapply(&db).await;
+    emit_change(&app, "claude_hook_set", ChangeAction::Deleted, Some(&id), "window");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::claude_code::types::ClaudeHookCommand;
+    use std::collections::BTreeMap;
+
+    fn hook_set(enabled: bool, event: &str, matcher: &str, command: &str) -> ClaudeHookSet {
+        let mut hooks = BTreeMap::new();
+        hooks.insert(
+            event.to_string(),
+            vec![ClaudeHookMatcher {
+                matcher: matcher.to_string(),
+                hooks: vec![ClaudeHookCommand {
+                    kind: "command".to_string(),
+                    command: command.to_string(),
+                    timeout: None,
+                }],
+            }],
+        );
+        ClaudeHookSet {
+            id: "id".to_string(),
+            name: "set".to_string(),
+            description: None,
+            enabled,
+            hooks,
+            sort_index: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn merge_appends_enabled_sets_after_common_hooks() {
+        let mut settings = serde_json::json!({
+            "hooks": {
+                "PreToolUse": [
+                    { "matcher": "Bash", "hooks": [{ "type": "command", "command": "audit.sh" }] }
+                ]
+            }
+        })
+        .as_object()
+        .unwrap()
+        .clone();
+        let sets = vec![
+            hook_set(true, "PreToolUse", "Edit", "lint.sh"),
+            hook_set(true, "PreToolUse", "Bash", "audit.sh"),
+            hook_set(false, "Notification", "", "notify.sh"),
+        ];
+        merge_into_settings(&sets, &mut settings);
+
+        let pre = settings["hooks"]["PreToolUse"].as_array().unwrap();
+        assert_eq!(pre.len(), 2);
+        assert_eq!(pre[1]["matcher"], "Edit");
+        assert_eq!(pre[1]["hooks"][0]["type"], "command");
+        assert!(settings["hooks"].get("Notification").is_none());
+    }
+
+    #[test]
+    fn validate_rejects_unknown_events() {
+        let set = hook_set(true, "BeforeEverything", "", "x");
+        let input = ClaudeHookSetInput {
+            name: "x".to_string(),
+            description: None,
+            enabled: true,
+            hooks: set.hooks,
+            sort_index: None,
+        };
+        assert!(validate(&input).unwrap_err().contains("BeforeEverything"));
+    }
+}
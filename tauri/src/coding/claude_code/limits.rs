@@ -0,0 +1,180 @@
+//! Output / thinking token limits
+//!
+//! `CLAUDE_CODE_MAX_OUTPUT_TOKENS`, `MAX_THINKING_TOKENS` and
+//! `MAX_MCP_OUTPUT_TOKENS` as typed fields instead of env entries typed into
+//! the common config. Global limits live in `claude_token_limits:config`; a
+//! provider overrides any of them with a `limits` object in its settings_config:
+//!
+//! ```json
+//! { "limits": { "maxOutputTokens": 32000, "maxThinkingTokens": 8000 } }
+//! ```
+//!
+//! On apply the provider's limits win over the global ones, which win over the
+//! same keys in the common config's env.
+
+use serde_json::{Map, Value};
+
+use crate::db::DbState;
+use crate::error::{AppError, AppResult};
+use crate::events::{emit_change, ChangeAction};
+use super::hooks::reapply;
+use super::types::ClaudeTokenLimits;
+
+type Db = surrealdb::Surreal<surrealdb::engine::local::Db>;
+
+pub const LIMITS_KEY: &str = "limits";
+
+/// settings_config field -> env var
+const LIMIT_FIELDS: [(&str, &str); 3] = [
+    ("maxOutputTokens", "CLAUDE_CODE_MAX_OUTPUT_TOKENS"),
+    ("maxThinkingTokens", "MAX_THINKING_TOKENS"),
+    ("maxMcpOutputTokens", "MAX_MCP_OUTPUT_TOKENS"),
+];
+
+/// Every env var a limit can be written to
+pub fn env_vars() -> impl Iterator<Item = &'static str> {
+    LIMIT_FIELDS.iter().map(|(_, var)| *var)
+}
+
+fn values(limits: &ClaudeTokenLimits) -> [Option<u64>; 3] {
+    [limits.max_output_tokens, limits.max_thinking_tokens, limits.max_mcp_output_tokens]
+}
+
+/// Env vars of the set limits
+pub fn limits_env(limits: &ClaudeTokenLimits) -> Map<String, Value> {
+    LIMIT_FIELDS
+        .iter()
+        .zip(values(limits))
+        .filter_map(|((_, var), value)| value.map(|v| (var.to_string(), Value::String(v.to_string()))))
+        .collect()
+}
+
+/// The provider's own limits; empty when it has none or they are malformed
+pub fn provider_limits(provider_config: &Value) -> ClaudeTokenLimits {
+    provider_config
+        .get(LIMITS_KEY)
+        .filter(|v| v.is_object())
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Write the global limits the provider does not override
+pub fn apply_global(global: &ClaudeTokenLimits, provider_config: &Value, env: &mut Map<String, Value>) {
+    let own = limits_env(&provider_limits(provider_config));
+    for (var, value) in limits_env(global) {
+        if !own.contains_key(&var) {
+            env.insert(var, value);
+        }
+    }
+}
+
+pub async fn load_global(db: &Db) -> ClaudeTokenLimits {
+    let records: Vec<Value> = db
+        .query("SELECT * OMIT id FROM claude_token_limits:`config` LIMIT 1")
+        .await
+        .and_then(|mut result| result.take(0))
+        .unwrap_or_default();
+    records
+        .into_iter()
+        .next()
+        .and_then(|record| serde_json::from_value(record).ok())
+        .unwrap_or_default()
+}
+
+fn validate(limits: &ClaudeTokenLimits) -> AppResult<()> {
+    for ((field, _), value) in LIMIT_FIELDS.iter().zip(values(limits)) {
+        if value == Some(0) {
+            return Err(AppError::invalid(format!("{} must be a positive integer", field)));
+        }
+    }
+    Ok(())
+}
+
+/// (field, rule code) of a malformed `limits` object, see `settings_schema`
+pub fn field_errors(provider_config: &Value) -> Vec<(String, &'static str)> {
+    let section = match provider_config.get(LIMITS_KEY) {
+        None | Some(Value::Null) => return Vec::new(),
+        Some(Value::Object(section)) => section,
+        Some(_) => return vec![(LIMITS_KEY.to_string(), "object")],
+    };
+    let mut errors = Vec::new();
+    for (name, value) in section {
+        let field = format!("{}.{}", LIMITS_KEY, name);
+        if !LIMIT_FIELDS.iter().any(|(known, _)| known == name) {
+            errors.push((field, "unknown"));
+        } else if !value.is_null() && !value.as_u64().is_some_and(|v| v > 0) {
+            errors.push((field, "integer"));
+        }
+    }
+    errors
+}
+
+// ============================================================================
+// Claude Token Limit Commands
+// ============================================================================
+
+#[tauri::command]
+pub async fn get_claude_token_limits(state: tauri::State<'_, DbState>) -> AppResult<ClaudeTokenLimits> {
+    let db = state.0.lock().await;
+    Ok(load_global(&db).await)
+}
+
+/// Save the global limits and rewrite settings.json of the applied provider
+#[tauri::command]
+pub async fn save_claude_token_limits(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    limits: ClaudeTokenLimits,
+) -> AppResult<()> {
+    validate(&limits)?;
+    let data = serde_json::to_value(&limits).map_err(AppError::invalid)?;
+    let db = state.0.lock().await;
+    db.query("UPSERT claude_token_limits:`config` CONTENT $data")
+        .bind(("data", data))
+        .await?;
+    reapply(&db).await;
+    emit_change(&app, "claude_token_limits", ChangeAction::Updated, None, "window");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn provider_limits_override_global_ones() {
+        let global = ClaudeTokenLimits {
+            max_output_tokens: Some(16000),
+            max_thinking_tokens: Some(4000),
+            max_mcp_output_tokens: None,
+        };
+        let provider = serde_json::json!({ "limits": { "maxThinkingTokens": 10000 } });
+        let mut env = super::super::commands::provider_env(&provider);
+        env.insert("MAX_MCP_OUTPUT_TOKENS".to_string(), Value::String("25000".to_string()));
+        apply_global(&global, &provider, &mut env);
+        assert_eq!(
+            Value::Object(env),
+            serde_json::json!({
+                "CLAUDE_CODE_MAX_OUTPUT_TOKENS": "16000",
+                "MAX_THINKING_TOKENS": "10000",
+                "MAX_MCP_OUTPUT_TOKENS": "25000"
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_non_integer_limits() {
+        let config = serde_json::json!({ "limits": { "maxOutputTokens": "32k", "maxThinkingTokens": 0, "maxTokens": 1 } });
+        let mut errors = field_errors(&config);
+        errors.sort();
+        assert_eq!(
+            errors,
+            vec![
+                ("limits.maxOutputTokens".to_string(), "integer"),
+                ("limits.maxThinkingTokens".to_string(), "integer"),
+                ("limits.maxTokens".to_string(), "unknown"),
+            ]
+        );
+        assert!(validate(&ClaudeTokenLimits { max_output_tokens: Some(0), ..Default::default() }).is_err());
+    }
+}
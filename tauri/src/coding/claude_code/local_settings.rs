@@ -0,0 +1,238 @@
+//! settings.local.json next to settings.json
+//!
+//! Many users keep keys in `settings.local.json`, which overrides
+//! settings.json. The apply target (`claude_apply_target:config`) decides where
+//! a provider goes: "settings" rewrites settings.json as before, "local" merges
+//! the built settings into settings.local.json and leaves everything else in
+//! that file (and settings.json) alone, except the env vars a provider apply
+//! manages, which are replaced as a whole so nothing of the previous provider
+//! (proxy, extra env, cloud or limit vars) carries over.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::{Map, Value};
+
+use crate::coding::change_history;
+use crate::db::DbState;
+use crate::error::{AppError, AppResult};
+use crate::events::{emit_change, ChangeAction};
+use super::commands::get_claude_config_dir;
+use super::merge::{merge, MergeRules};
+use super::types::ClaudeLocalSettings;
+
+type Db = surrealdb::Surreal<surrealdb::engine::local::Db>;
+
+/// Files a provider can be applied to
+pub const APPLY_TARGETS: [&str; 2] = ["settings", "local"];
+pub const DEFAULT_APPLY_TARGET: &str = "settings";
+
+pub fn local_settings_path() -> AppResult<PathBuf> {
+    Ok(get_claude_config_dir().map_err(AppError::io)?.join("settings.local.json"))
+}
+
+/// Where apply writes: "settings" (default) or "local"
+pub async fn load_apply_target(db: &Db) -> String {
+    let records: Vec<Value> = db
+        .query("SELECT VALUE target FROM claude_apply_target:`config`")
+        .await
+        .and_then(|mut result| result.take(0))
+        .unwrap_or_default();
+    records
+        .into_iter()
+        .next()
+        .and_then(|target| target.as_str().map(|t| t.to_string()))
+        .filter(|target| APPLY_TARGETS.contains(&target.as_str()))
+        .unwrap_or_else(|| DEFAULT_APPLY_TARGET.to_string())
+}
+
+/// JSON object in `path`; empty when the file is missing or blank
+pub fn read_object(path: &Path) -> AppResult<Map<String, Value>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Map::new()),
+        Err(e) => return Err(AppError::io(format!("{}: {}", path.display(), e))),
+    };
+    if content.trim().is_empty() {
+        return Ok(Map::new());
+    }
+    match serde_json::from_str(&content) {
+        Ok(Value::Object(map)) => Ok(map),
+        Ok(_) => Err(AppError::invalid(format!("{} is not a JSON object", path.display()))),
+        Err(e) => Err(AppError::invalid(format!("{}: {}", path.display(), e))),
+    }
+}
+
+fn write_object(path: &Path, settings: &Map<String, Value>) -> AppResult<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let content = serde_json::to_string_pretty(settings).map_err(AppError::invalid)?;
+    change_history::write_config("claude_code", path, content)?;
+    Ok(())
+}
+
+/// Merge `patch` into the JSON object in `path`: objects key by key, other
+/// values replaced. Returns the merged content.
+pub fn merge_into_file(path: &Path, patch: Map<String, Value>) -> AppResult<Map<String, Value>> {
+    let mut settings = read_object(path)?;
+    merge(&mut settings, patch, &MergeRules::new());
+    write_object(path, &settings)?;
+    Ok(settings)
+}
+
+/// Merge a provider's built settings into the JSON object in `path` after
+/// removing the `managed_env` vars, so a switch does not keep any of the
+/// previous provider's env. Returns the merged content.
+pub fn apply_into_file(
+    path: &Path,
+    settings: Map<String, Value>,
+    managed_env: &BTreeSet<String>,
+) -> AppResult<Map<String, Value>> {
+    let mut merged = read_object(path)?;
+    if let Some(Value::Object(env)) = merged.get_mut("env") {
+        env.retain(|key, _| !managed_env.contains(key));
+    }
+    merge(&mut merged, settings, &MergeRules::new());
+    write_object(path, &merged)?;
+    Ok(merged)
+}
+
+fn as_object(settings: Value) -> AppResult<Map<String, Value>> {
+    match settings {
+        Value::Object(map) => Ok(map),
+        _ => Err(AppError::invalid("Claude settings must be a JSON object")),
+    }
+}
+
+// ============================================================================
+// Claude Local Settings Commands
+// ============================================================================
+
+#[tauri::command]
+pub fn get_claude_local_settings() -> AppResult<ClaudeLocalSettings> {
+    let path = local_settings_path()?;
+    Ok(ClaudeLocalSettings {
+        exists: path.exists(),
+        settings: Value::Object(read_object(&path)?),
+        path: path.to_string_lossy().to_string(),
+    })
+}
+
+/// Replace settings.local.json
+#[tauri::command]
+pub fn save_claude_local_settings(app: tauri::AppHandle, settings: Value) -> AppResult<()> {
+    write_object(&local_settings_path()?, &as_object(settings)?)?;
+    emit_change(&app, "claude_local_settings", ChangeAction::Updated, None, "window");
+    Ok(())
+}
+
+/// Merge `patch` into settings.local.json and return the result
+#[tauri::command]
+pub fn merge_claude_local_settings(app: tauri::AppHandle, patch: Value) -> AppResult<Value> {
+    let merged = merge_into_file(&local_settings_path()?, as_object(patch)?)?;
+    emit_change(&app, "claude_local_settings", ChangeAction::Updated, None, "window");
+    Ok(Value::Object(merged))
+}
+
+#[tauri::command]
+pub async fn get_claude_apply_target(state: tauri::State<'_, DbState>) -> AppResult<String> {
+    let db = state.0.lock().await;
+    Ok(load_apply_target(&db).await)
+}
+
+/// Choose the file later applies write to ("settings" or "local")
+#[tauri::command]
+pub async fn set_claude_apply_target(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    target: String,
+) -> AppResult<()> {
+    if !APPLY_TARGETS.contains(&target.as_str()) {
+        return Err(AppError::invalid(format!("unknown apply target '{}'", target)));
+    }
+    let db = state.0.lock().await;
+    db.query("UPSERT claude_apply_target:`config` CONTENT $data")
+        .bind(("data", serde_json::json!({ "target": target })))
+        .await?;
+    emit_change(&app, "claude_apply_target", ChangeAction::Updated, None, "window");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_keeps_existing_local_keys() {
+        let path = std::env::temp_dir().join(format!("claude-local-{}.json", uuid::Uuid::new_v4()));
+        fs::write(
+            &path,
+            r#"{"env":{"MY_SECRET":"s3cret","ANTHROPIC_MODEL":"old"},"permissions":{"allow":["Read"]}}"#,
+        )
+        .unwrap();
+
+        let patch = serde_json::json!({
+            "env": { "ANTHROPIC_MODEL": "glm-4.6", "ANTHROPIC_BASE_URL": "https://relay.example.com" },
+            "permissions": { "deny": ["Edit"] }
+        });
+        // Dry run: the write is only captured
+        let mut merged = Map::new();
+        let diffs = change_history::preview_sync(true, || {
+            merged = merge_into_file(&path, as_object(patch)?)?;
+            Ok::<_, AppError>(())
+        })
+        .unwrap();
+        let _ = fs::remove_file(&path);
+        assert_eq!(diffs.len(), 1);
+
+        assert_eq!(merged["env"]["MY_SECRET"], "s3cret");
+        assert_eq!(merged["env"]["ANTHROPIC_MODEL"], "glm-4.6");
+        assert_eq!(merged["env"]["ANTHROPIC_BASE_URL"], "https://relay.example.com");
+        assert_eq!(merged["permissions"], serde_json::json!({ "allow": ["Read"], "deny": ["Edit"] }));
+    }
+
+    #[test]
+    fn switching_providers_drops_the_previous_provider_env() {
+        let path = std::env::temp_dir().join(format!("claude-local-{}.json", uuid::Uuid::new_v4()));
+        let proxied = serde_json::json!({
+            "env": { "ANTHROPIC_AUTH_TOKEN": "sk-a", "ANTHROPIC_BASE_URL": "https://a.example.com" },
+            "proxy": "127.0.0.1:7890",
+            "noProxy": "localhost",
+            "extraEnv": { "A_ONLY": "1" }
+        });
+        let direct = serde_json::json!({
+            "env": { "ANTHROPIC_AUTH_TOKEN": "sk-b", "ANTHROPIC_BASE_URL": "https://b.example.com" }
+        });
+        let managed = super::super::commands::managed_env_keys([&proxied, &direct]);
+        let settings = |config: &Value| {
+            let mut map = Map::new();
+            map.insert("env".to_string(), Value::Object(super::super::commands::provider_env(config)));
+            map
+        };
+
+        // The file as the proxied provider left it, plus a key of the user's own
+        fs::write(&path, serde_json::to_string(&settings(&proxied)).unwrap()).unwrap();
+        let mut local = read_object(&path).unwrap();
+        local["env"].as_object_mut().unwrap().insert("MY_SECRET".to_string(), Value::from("s3cret"));
+        fs::write(&path, serde_json::to_string(&local).unwrap()).unwrap();
+
+        let mut merged = Map::new();
+        change_history::preview_sync(true, || {
+            merged = apply_into_file(&path, settings(&direct), &managed)?;
+            Ok::<_, AppError>(())
+        })
+        .unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(
+            merged["env"],
+            serde_json::json!({
+                "ANTHROPIC_AUTH_TOKEN": "sk-b",
+                "ANTHROPIC_BASE_URL": "https://b.example.com",
+                "MY_SECRET": "s3cret"
+            })
+        );
+    }
+}
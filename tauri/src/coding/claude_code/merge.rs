@@ -0,0 +1,173 @@
+//! Merge of the common config with a provider's settings
+//!
+//! settings.json is the common config overlaid with the provider's settings:
+//! its env (see `provider_env`) plus any other settings.json keys it carries.
+//! By default objects are merged key by key, while arrays and scalars from the
+//! provider replace the common value. A `$merge` object in the common config
+//! overrides this per dot-separated key path:
+//!
+//! ```json
+//! { "$merge": { "permissions.allow": "append", "statusLine": "replace", "env.API_TIMEOUT_MS": "keep" } }
+//! ```
+//!
+//! - `deep`: merge objects key by key (the default for objects)
+//! - `replace`: the provider value replaces the common one (default otherwise)
+//! - `append`: concatenate arrays, skipping values already present
+//! - `keep`: the common value wins
+//!
+//! `$merge` itself is never written to settings.json.
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use crate::error::{AppError, AppResult};
+use super::commands::provider_env;
+
+pub const MERGE_KEY: &str = "$merge";
+
+/// Provider fields that are not settings.json keys (they become env vars)
+const PROVIDER_ONLY_KEYS: [&str; 10] = [
+    "model",
+    "haikuModel",
+    "sonnetModel",
+    "opusModel",
+    "extraEnv",
+    "proxy",
+    "noProxy",
+    super::limits::LIMITS_KEY,
+    super::cloud::BEDROCK_KEY,
+    super::cloud::VERTEX_KEY,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MergeStrategy {
+    Deep,
+    Replace,
+    Append,
+    Keep,
+}
+
+pub type MergeRules = BTreeMap<String, MergeStrategy>;
+
+/// Remove `$merge` from the common config and parse it
+pub fn take_rules(common: &mut Map<String, Value>) -> AppResult<MergeRules> {
+    match common.remove(MERGE_KEY) {
+        None | Some(Value::Null) => Ok(MergeRules::new()),
+        Some(rules) => serde_json::from_value(rules).map_err(|e| {
+            AppError::invalid(format!("{} (expected key path -> deep/replace/append/keep): {}", MERGE_KEY, e))
+        }),
+    }
+}
+
+/// Settings the provider contributes: its settings.json keys with the resolved env
+pub fn provider_overlay(provider_config: &Value) -> Map<String, Value> {
+    let mut overlay = provider_config.as_object().cloned().unwrap_or_default();
+    for key in PROVIDER_ONLY_KEYS {
+        overlay.remove(key);
+    }
+    let mut env = overlay
+        .remove("env")
+        .and_then(|env| match env {
+            Value::Object(env) => Some(env),
+            _ => None,
+        })
+        .unwrap_or_default();
+    // Legacy key, written as ANTHROPIC_AUTH_TOKEN by provider_env
+    env.remove("ANTHROPIC_API_KEY");
+    if super::cloud::is_cloud(provider_config) {
+        env.remove("ANTHROPIC_BASE_URL");
+        env.remove("ANTHROPIC_AUTH_TOKEN");
+    }
+    env.extend(provider_env(provider_config));
+    overlay.insert("env".to_string(), Value::Object(env));
+    overlay
+}
+
+/// Overlay `overlay` onto `base` following `rules`
+pub fn merge(base: &mut Map<String, Value>, overlay: Map<String, Value>, rules: &MergeRules) {
+    merge_at("", base, overlay, rules);
+}
+
+fn merge_at(prefix: &str, base: &mut Map<String, Value>, overlay: Map<String, Value>, rules: &MergeRules) {
+    for (key, value) in overlay {
+        let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+        let Some(existing) = base.get_mut(&key) else {
+            base.insert(key, value);
+            continue;
+        };
+        match (rules.get(&path).copied(), existing, value) {
+            (Some(MergeStrategy::Keep), _, _) => {}
+            (Some(MergeStrategy::Append), Value::Array(items), Value::Array(extra)) => {
+                for item in extra {
+                    if !items.contains(&item) {
+                        items.push(item);
+                    }
+                }
+            }
+            (None | Some(MergeStrategy::Deep), Value::Object(inner), Value::Object(extra)) => {
+                merge_at(&path, inner, extra, rules);
+            }
+            (_, existing, value) => *existing = value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn merges_by_rule() {
+        let mut common = object(serde_json::json!({
+            "$merge": {
+                "permissions.allow": "append",
+                "statusLine": "replace",
+                "env.API_TIMEOUT_MS": "keep"
+            },
+            "permissions": { "allow": ["Read"], "deny": ["Bash(rm:*)"] },
+            "statusLine": { "type": "command", "command": "status.sh", "padding": 0 },
+            "env": { "API_TIMEOUT_MS": "600000", "DISABLE_TELEMETRY": "1" }
+        }));
+        let rules = take_rules(&mut common).unwrap();
+        assert!(!common.contains_key(MERGE_KEY));
+
+        let overlay = provider_overlay(&serde_json::json!({
+            "env": { "ANTHROPIC_API_KEY": "sk-1", "API_TIMEOUT_MS": "30000" },
+            "model": "glm-4.6",
+            "extraEnv": { "ANTHROPIC_SMALL_FAST_MODEL": "glm-4.5-air", "ANTHROPIC_MODEL": "ignored" },
+            "permissions": { "allow": ["Read", "WebFetch"], "deny": ["Edit"] },
+            "statusLine": { "type": "command", "command": "relay-status.sh" }
+        }));
+        merge(&mut common, overlay, &rules);
+
+        assert_eq!(common["permissions"]["allow"], serde_json::json!(["Read", "WebFetch"]));
+        // Arrays without a rule are replaced
+        assert_eq!(common["permissions"]["deny"], serde_json::json!(["Edit"]));
+        assert_eq!(common["statusLine"], serde_json::json!({ "type": "command", "command": "relay-status.sh" }));
+        assert_eq!(
+            common["env"],
+            serde_json::json!({
+                "API_TIMEOUT_MS": "600000",
+                "DISABLE_TELEMETRY": "1",
+                "ANTHROPIC_AUTH_TOKEN": "sk-1",
+                "ANTHROPIC_MODEL": "glm-4.6",
+                "ANTHROPIC_SMALL_FAST_MODEL": "glm-4.5-air"
+            })
+        );
+        assert!(common.get("model").is_none());
+        assert!(common.get("extraEnv").is_none());
+    }
+
+    #[test]
+    fn rejects_unknown_strategy() {
+        let mut common = object(serde_json::json!({ "$merge": { "env": "union" } }));
+        assert!(take_rules(&mut common).unwrap_err().to_string().contains("$merge"));
+    }
+}
@@ -1,5 +1,26 @@
 pub mod adapter;
+pub mod agents;
+pub mod apply_history;
+pub mod backup;
+pub mod batch_test;
+pub mod cli_install;
+pub mod cloud;
 pub mod commands;
+pub mod drift;
+pub mod failover;
+pub mod front_matter;
+pub mod hooks;
+pub mod limits;
+pub mod local_settings;
+pub mod merge;
+pub mod permissions;
+pub mod provider_history;
+pub mod provider_io;
+pub mod sessions;
+pub mod settings_schema;
+pub mod shell_profile;
+pub mod slash_commands;
+pub mod templates;
 pub mod types;
 pub mod tray_support;
 
@@ -0,0 +1,299 @@
+//! Claude permission presets
+//!
+//! Named sets of `permissions` rules (allow / deny / ask tool rules,
+//! `defaultMode`, `additionalDirectories`) stored in
+//! `claude_permission_preset`. On apply, the rules of every enabled preset are
+//! appended to the `permissions` section coming from the common config; the
+//! `defaultMode` of the last enabled preset that sets one wins.
+
+use chrono::Local;
+use serde_json::{Map, Value};
+
+use crate::coding::db_extract_id;
+use crate::db::DbState;
+use crate::error::{AppError, AppResult};
+use crate::events::{emit_change, ChangeAction};
+use super::hooks::reapply;
+use super::types::{ClaudePermissionPreset, ClaudePermissionPresetInput};
+
+type Db = surrealdb::Surreal<surrealdb::engine::local::Db>;
+
+/// Permission modes Claude Code understands
+pub const PERMISSION_MODES: [&str; 4] = ["default", "acceptEdits", "plan", "bypassPermissions"];
+
+/// `Tool` or `Tool(specifier)`, e.g. `Bash(npm run test:*)`, `Read(~/.ssh/**)`, `mcp__github`
+fn is_valid_rule(rule: &str) -> bool {
+    let (tool, specifier) = match rule.find('(') {
+        Some(open) => (&rule[..open], Some(&rule[open..])),
+        None => (rule, None),
+    };
+    let tool_ok = tool.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+        && tool.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    tool_ok && specifier.is_none_or(|s| s.len() > 2 && s.ends_with(')'))
+}
+
+fn validate(input: &ClaudePermissionPresetInput) -> AppResult<()> {
+    if input.name.trim().is_empty() {
+        return Err(AppError::validation("NAME_REQUIRED", &[]));
+    }
+    let permissions = &input.permissions;
+    if let Some(mode) = &permissions.default_mode {
+        if !PERMISSION_MODES.contains(&mode.as_str()) {
+            return Err(AppError::invalid(format!("unknown permission mode '{}'", mode)));
+        }
+    }
+    for (list, rules) in [("allow", &permissions.allow), ("deny", &permissions.deny), ("ask", &permissions.ask)] {
+        if let Some(rule) = rules.iter().find(|rule| !is_valid_rule(rule.trim())) {
+            return Err(AppError::invalid(format!("{} rule '{}'", list, rule)));
+        }
+    }
+    if permissions.additional_directories.iter().any(|dir| dir.trim().is_empty()) {
+        return Err(AppError::invalid("additional directories must not be empty"));
+    }
+    Ok(())
+}
+
+fn from_db_value(mut value: Value) -> Option<ClaudePermissionPreset> {
+    let id = db_extract_id(&value);
+    value.as_object_mut()?.insert("id".to_string(), Value::String(id));
+    serde_json::from_value(value).ok()
+}
+
+/// All permission presets, by sort order then name
+pub async fn load_presets(db: &Db) -> AppResult<Vec<ClaudePermissionPreset>> {
+    let records: Vec<Value> = db
+        .query("SELECT *, type::string(id) as id FROM claude_permission_preset")
+        .await?
+        .take(0)?;
+    let mut presets: Vec<ClaudePermissionPreset> = records.into_iter().filter_map(from_db_value).collect();
+    presets.sort_by(|a, b| {
+        a.sort_index
+            .unwrap_or(i32::MAX)
+            .cmp(&b.sort_index.unwrap_or(i32::MAX))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    Ok(presets)
+}
+
+async fn get_preset(db: &Db, id: &str) -> AppResult<ClaudePermissionPreset> {
+    let records: Vec<Value> = db
+        .query("SELECT *, type::string(id) as id FROM type::thing('claude_permission_preset', $id)")
+        .bind(("id", id.to_string()))
+        .await?
+        .take(0)?;
+    records
+        .into_iter()
+        .next()
+        .and_then(from_db_value)
+        .ok_or_else(|| AppError::not_found("PRESET_NOT_FOUND", &[("id", id)]))
+}
+
+/// Append `values` to the string array `permissions[key]`, skipping ones already present
+fn append_unique(permissions: &mut Map<String, Value>, key: &str, values: &[String]) {
+    if values.is_empty() {
+        return;
+    }
+    let entries = permissions
+        .entry(key.to_string())
+        .or_insert_with(|| Value::Array(Vec::new()));
+    if !entries.is_array() {
+        *entries = Value::Array(Vec::new());
+    }
+    let entries = entries.as_array_mut().unwrap();
+    for value in values {
+        let value = Value::String(value.trim().to_string());
+        if !entries.contains(&value) {
+            entries.push(value);
+        }
+    }
+}
+
+/// Merge the enabled presets into `settings.permissions`
+pub fn merge_into_settings(presets: &[ClaudePermissionPreset], settings: &mut Map<String, Value>) {
+    let enabled: Vec<&ClaudePermissionPreset> = presets.iter().filter(|preset| preset.enabled).collect();
+    if enabled.is_empty() {
+        return;
+    }
+    let permissions = settings
+        .entry("permissions".to_string())
+        .or_insert_with(|| Value::Object(Map::new()));
+    if !permissions.is_object() {
+        *permissions = Value::Object(Map::new());
+    }
+    let permissions = permissions.as_object_mut().unwrap();
+
+    for preset in enabled {
+        let rules = &preset.permissions;
+        append_unique(permissions, "allow", &rules.allow);
+        append_unique(permissions, "deny", &rules.deny);
+        append_unique(permissions, "ask", &rules.ask);
+        append_unique(permissions, "additionalDirectories", &rules.additional_directories);
+        if let Some(mode) = &rules.default_mode {
+            permissions.insert("defaultMode".to_string(), Value::String(mode.clone()));
+        }
+    }
+}
+
+fn to_db_value(input: &ClaudePermissionPresetInput, created_at: &str) -> Value {
+    serde_json::json!({
+        "name": input.name.trim(),
+        "description": input.description,
+        "enabled": input.enabled,
+        "permissions": input.permissions,
+        "sort_index": input.sort_index,
+        "created_at": created_at,
+        "updated_at": Local::now().to_rfc3339(),
+    })
+}
+
+// ============================================================================
+// Claude Permission Preset Commands
+// ============================================================================
+
+#[tauri::command]
+pub async fn list_claude_permission_presets(
+    state: tauri::State<'_, DbState>,
+) -> AppResult<Vec<ClaudePermissionPreset>> {
+    let db = state.0.lock().await;
+    load_presets(&db).await
+}
+
+#[tauri::command]
+pub async fn create_claude_permission_preset(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    input: ClaudePermissionPresetInput,
+) -> AppResult<ClaudePermissionPreset> {
+    validate(&input)?;
+    let id = uuid::Uuid::new_v4().to_string();
+    let db = state.0.lock().await;
+    db.query("CREATE type::thing('claude_permission_preset', $id) CONTENT $data")
+        .bind(("id", id.clone()))
+        .bind(("data", to_db_value(&input, &Local::now().to_rfc3339())))
+        .await?;
+    let preset = get_preset(&db, &id).await?;
+
+    if preset.enabled {
+        reapply(&db).await;
+    }
+    emit_change(&app, "claude_permission_preset", ChangeAction::Created, Some(&preset.id), "window");
+    Ok(preset)
+}
+
+#[tauri::command]
+pub async fn update_claude_permission_preset(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+    input: ClaudePermissionPresetInput,
+) -> AppResult<ClaudePermissionPreset> {
+    validate(&input)?;
+    let db = state.0.lock().await;
+    let existing = get_preset(&db, &id).await?;
+
+    db.query("UPDATE type::thing('claude_permission_preset', $id) CONTENT $data")
+        .bind(("id", id.clone()))
+        .bind(("data", to_db_value(&input, &existing.created_at)))
+        .await?;
+    let preset = get_preset(&db, &id).await?;
+
+    if preset.enabled || existing.enabled {
+        reapply(&db).await;
+    }
+    emit_change(&app, "claude_permission_preset", ChangeAction::Updated, Some(&id), "window");
+    Ok(preset)
+}
+
+#[tauri::command]
+pub async fn delete_claude_permission_preset(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+    db.query("DELETE type::thing('claude_permission_preset', $id)")
+        .bind(("id", id.clone()))
+        .await?;
+    reapply(&db).await;
+    emit_change(&app, "claude_permission_preset", ChangeAction::Deleted, Some(&id), "window");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::claude_code::types::ClaudePermissions;
+
+    fn preset(enabled: bool, permissions: ClaudePermissions) -> ClaudePermissionPreset {
+        ClaudePermissionPreset {
+            id: "id".to_string(),
+            name: "preset".to_string(),
+            description: None,
+            enabled,
+            permissions,
+            sort_index: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn merge_appends_rules_and_overrides_mode() {
+        let mut settings = serde_json::json!({
+            "permissions": { "allow": ["Bash(git status)"], "defaultMode": "default" }
+        })
+        .as_object()
+        .unwrap()
+        .clone();
+        let presets = vec![
+            preset(
+                true,
+                ClaudePermissions {
+                    allow: vec!["Bash(git status)".to_string(), "Bash(npm run test:*)".to_string()],
+                    deny: vec!["Read(./.env)".to_string()],
+                    default_mode: Some("acceptEdits".to_string()),
+                    additional_directories: vec!["../shared".to_string()],
+                    ..Default::default()
+                },
+            ),
+            preset(
+                false,
+                ClaudePermissions {
+                    ask: vec!["WebFetch".to_string()],
+                    default_mode: Some("plan".to_string()),
+                    ..Default::default()
+                },
+            ),
+        ];
+        merge_into_settings(&presets, &mut settings);
+
+        let permissions = &settings["permissions"];
+        assert_eq!(permissions["allow"], serde_json::json!(["Bash(git status)", "Bash(npm run test:*)"]));
+        assert_eq!(permissions["deny"], serde_json::json!(["Read(./.env)"]));
+        assert_eq!(permissions["additionalDirectories"], serde_json::json!(["../shared"]));
+        assert_eq!(permissions["defaultMode"], "acceptEdits");
+        assert!(permissions.get("ask").is_none());
+    }
+
+    #[test]
+    fn validate_checks_rules_and_mode() {
+        let mut input = ClaudePermissionPresetInput {
+            name: "x".to_string(),
+            description: None,
+            enabled: true,
+            permissions: ClaudePermissions {
+                allow: vec!["Bash(npm run build)".to_string(), "mcp__github".to_string()],
+                ..Default::default()
+            },
+            sort_index: None,
+        };
+        assert!(validate(&input).is_ok());
+
+        input.permissions.deny = vec!["Bash(".to_string()];
+        assert!(validate(&input).unwrap_err().to_string().contains("Bash("));
+
+        input.permissions.deny.clear();
+        input.permissions.default_mode = Some("yolo".to_string());
+        assert!(validate(&input).unwrap_err().to_string().contains("yolo"));
+    }
+}
@@ -0,0 +1,218 @@
+//! Claude provider version history
+//!
+//! `update_claude_provider` and `apply_claude_config` snapshot the provider into
+//! `claude_provider_history` (the state before an update as well as after, so
+//! the first edit can be undone too). A snapshot identical to the latest one
+//! is not stored again, and only the newest `MAX_VERSIONS` are kept.
+
+use chrono::Local;
+use serde_json::Value;
+
+use crate::db::DbState;
+use crate::error::{AppError, AppResult};
+use crate::events::{emit_change, ChangeAction};
+use super::adapter;
+use super::commands::apply_config_to_file_public;
+use super::types::{ClaudeCodeProvider, ClaudeProviderVersion};
+
+type Db = surrealdb::Surreal<surrealdb::engine::local::Db>;
+
+const MAX_VERSIONS: u32 = 50;
+
+async fn load_versions(db: &Db, provider_id: &str, limit: Option<u32>) -> AppResult<Vec<ClaudeProviderVersion>> {
+    let mut sql = "SELECT * OMIT id FROM claude_provider_history WHERE provider_id = $id ORDER BY version DESC".to_string();
+    if let Some(limit) = limit {
+        sql.push_str(&format!(" LIMIT {}", limit));
+    }
+    let records: Vec<Value> = db
+        .query(sql)
+        .bind(("id", provider_id.to_string()))
+        .await?
+        .take(0)?;
+    Ok(records
+        .into_iter()
+        .filter_map(|record| serde_json::from_value(record).ok())
+        .collect())
+}
+
+fn same_content(version: &ClaudeProviderVersion, provider: &ClaudeCodeProvider) -> bool {
+    version.name == provider.name
+        && version.category == provider.category
+        && version.settings_config == provider.settings_config
+        && version.website_url == provider.website_url
+        && version.notes == provider.notes
+        && version.icon == provider.icon
+        && version.icon_color == provider.icon_color
+}
+
+/// Snapshot `provider` unless it matches its latest version
+/// Failures are logged; history must never block an update or apply.
+pub async fn record_version(db: &Db, provider: &ClaudeCodeProvider, reason: &str) {
+    if let Err(e) = try_record_version(db, provider, reason).await {
+        log::warn!("Failed to record provider version: {}", e);
+    }
+}
+
+async fn try_record_version(db: &Db, provider: &ClaudeCodeProvider, reason: &str) -> AppResult<()> {
+    let latest = load_versions(db, &provider.id, Some(1)).await?.into_iter().next();
+    if latest.as_ref().is_some_and(|v| same_content(v, provider)) {
+        return Ok(());
+    }
+    let version = latest.map(|v| v.version + 1).unwrap_or(1);
+
+    db.query("CREATE claude_provider_history CONTENT $data")
+        .bind((
+            "data",
+            serde_json::json!({
+                "provider_id": provider.id,
+                "version": version,
+                "name": provider.name,
+                "category": provider.category,
+                "settings_config": provider.settings_config,
+                "website_url": provider.website_url,
+                "notes": provider.notes,
+                "icon": provider.icon,
+                "icon_color": provider.icon_color,
+                "reason": reason,
+                "created_at": Local::now().to_rfc3339(),
+            }),
+        ))
+        .await?;
+
+    if version > MAX_VERSIONS {
+        db.query("DELETE claude_provider_history WHERE provider_id = $id AND version <= $cutoff")
+            .bind(("id", provider.id.clone()))
+            .bind(("cutoff", version - MAX_VERSIONS))
+            .await?;
+    }
+    Ok(())
+}
+
+/// Snapshot the provider with `id` as stored in the database
+pub async fn record_version_by_id(db: &Db, id: &str, reason: &str) {
+    let records: Vec<Value> = match db
+        .query("SELECT *, type::string(id) as id FROM type::thing('claude_provider', $id)")
+        .bind(("id", id.to_string()))
+        .await
+        .and_then(|mut result| result.take(0))
+    {
+        Ok(records) => records,
+        Err(e) => {
+            log::warn!("Failed to query provider for history: {}", e);
+            return;
+        }
+    };
+    if let Some(record) = records.into_iter().next() {
+        record_version(db, &adapter::from_db_value_provider(record), reason).await;
+    }
+}
+
+/// Drop the history of a deleted provider
+pub async fn delete_history(db: &Db, provider_id: &str) {
+    if let Err(e) = db
+        .query("DELETE claude_provider_history WHERE provider_id = $id")
+        .bind(("id", provider_id.to_string()))
+        .await
+    {
+        log::warn!("Failed to delete provider history: {}", e);
+    }
+}
+
+// ============================================================================
+// Claude Provider History Commands
+// ============================================================================
+
+/// Versions of a provider, newest first
+#[tauri::command]
+pub async fn list_claude_provider_history(
+    state: tauri::State<'_, DbState>,
+    id: String,
+) -> AppResult<Vec<ClaudeProviderVersion>> {
+    let db = state.0.lock().await;
+    load_versions(&db, &id, None).await
+}
+
+/// Put a stored version back into the provider; the restore is itself recorded
+/// and settings.json is rewritten when the provider is applied
+#[tauri::command]
+pub async fn restore_claude_provider_version(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+    version: u32,
+) -> AppResult<ClaudeCodeProvider> {
+    let db = state.0.lock().await;
+    let snapshot = load_versions(&db, &id, None)
+        .await?
+        .into_iter()
+        .find(|v| v.version == version)
+        .ok_or_else(|| {
+            AppError::not_found("PROVIDER_VERSION_NOT_FOUND", &[("id", id.as_str()), ("version", &version.to_string())])
+        })?;
+
+    db.query("UPDATE type::thing('claude_provider', $id) SET name = $name, category = $category, settings_config = $settings_config, website_url = $website_url, notes = $notes, icon = $icon, icon_color = $icon_color, updated_at = $now")
+        .bind(("id", id.clone()))
+        .bind(("name", snapshot.name))
+        .bind(("category", snapshot.category))
+        .bind(("settings_config", snapshot.settings_config))
+        .bind(("website_url", snapshot.website_url))
+        .bind(("notes", snapshot.notes))
+        .bind(("icon", snapshot.icon))
+        .bind(("icon_color", snapshot.icon_color))
+        .bind(("now", Local::now().to_rfc3339()))
+        .await?;
+
+    let records: Vec<Value> = db
+        .query("SELECT *, type::string(id) as id FROM type::thing('claude_provider', $id)")
+        .bind(("id", id.clone()))
+        .await?
+        .take(0)?;
+    let provider = records
+        .into_iter()
+        .next()
+        .map(adapter::from_db_value_provider)
+        .ok_or_else(|| AppError::not_found("PROVIDER_NOT_FOUND", &[("id", id.as_str())]))?;
+    record_version(&db, &provider, "restore").await;
+
+    if provider.is_applied {
+        if let Err(e) = apply_config_to_file_public(&db, &id).await {
+            log::warn!("Failed to auto-apply restored config: {}", e);
+        }
+    }
+    emit_change(&app, "claude_provider", ChangeAction::Updated, Some(&id), "window");
+    Ok(provider)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_content_ignores_applied_state_and_timestamps() {
+        let provider = adapter::from_db_value_provider(serde_json::json!({
+            "id": "claude_provider:abc",
+            "name": "Relay",
+            "category": "custom",
+            "settings_config": "{\"env\":{}}",
+            "is_applied": true,
+            "updated_at": "2026-01-02T00:00:00+08:00"
+        }));
+        let version: ClaudeProviderVersion = serde_json::from_value(serde_json::json!({
+            "provider_id": "abc",
+            "version": 3,
+            "name": "Relay",
+            "category": "custom",
+            "settings_config": "{\"env\":{}}",
+            "reason": "update",
+            "created_at": "2026-01-01T00:00:00+08:00"
+        }))
+        .unwrap();
+        assert!(same_content(&version, &provider));
+
+        let edited = ClaudeCodeProvider {
+            settings_config: "{\"env\":{\"ANTHROPIC_MODEL\":\"x\"}}".to_string(),
+            ..provider
+        };
+        assert!(!same_content(&version, &edited));
+    }
+}
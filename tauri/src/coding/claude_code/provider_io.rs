@@ -0,0 +1,542 @@
+//! Claude provider export / import
+//!
+//! Providers are shared as a JSON bundle of `ClaudeCodeProviderInput`s. With
+//! redaction on, keys in `settings_config` and notes are replaced with
+//! `${FIELD}` placeholders; importing a bundle into a provider of the same
+//! name keeps the keys it already has for those placeholders. Without it the
+//! bundle goes through the secret scan like backups and share strings do.
+//!
+//! Bulk import takes provider inputs or a CSV of name/base_url/api_key/model
+//! and creates every valid row with a single INSERT.
+
+use chrono::Local;
+use serde_json::Value;
+
+use crate::db::DbState;
+use crate::error::{AppError, AppResult};
+use crate::events::{emit_change, ChangeAction};
+use crate::secret_scan::{self, ScanMode, SecretFinding};
+use super::adapter;
+use super::commands::apply_config_to_file_public;
+use super::types::*;
+
+type Db = surrealdb::Surreal<surrealdb::engine::local::Db>;
+
+const BUNDLE_VERSION: u32 = 1;
+
+/// Column order of a CSV without a header row
+const CSV_COLUMNS: [&str; 4] = ["name", "base_url", "api_key", "model"];
+
+/// All providers from the database, by sort order
+pub(super) async fn load_providers(db: &Db) -> AppResult<Vec<ClaudeCodeProvider>> {
+    let records: Vec<Value> = db
+        .query("SELECT *, type::string(id) as id FROM claude_provider")
+        .await?
+        .take(0)?;
+    let mut providers: Vec<ClaudeCodeProvider> = records.into_iter().map(adapter::from_db_value_provider).collect();
+    providers.sort_by_key(|p| p.sort_index.unwrap_or(0));
+    Ok(providers)
+}
+
+/// Sort index after the last provider
+pub(super) async fn next_sort_index(db: &Db) -> AppResult<i32> {
+    let indexes: Vec<Value> = db
+        .query("SELECT VALUE sort_index FROM claude_provider")
+        .await?
+        .take(0)?;
+    Ok(indexes
+        .iter()
+        .filter_map(|i| i.as_i64())
+        .max()
+        .map(|max| max as i32 + 1)
+        .unwrap_or(0))
+}
+
+fn parse_settings(name: &str, settings_config: &str) -> AppResult<Value> {
+    let value: Value = serde_json::from_str(settings_config)
+        .map_err(|e| AppError::invalid(format!("settings_config of {}: {}", name, e)))?;
+    if !value.is_object() {
+        return Err(AppError::invalid(format!("settings_config of {} is not an object", name)));
+    }
+    Ok(value)
+}
+
+fn to_export(provider: ClaudeCodeProvider, redact: bool) -> AppResult<ClaudeCodeProviderInput> {
+    let mut settings = parse_settings(&provider.name, &provider.settings_config)?;
+    let mut notes = provider.notes.map(Value::String);
+    if redact {
+        secret_scan::redact_value(&mut settings);
+        if let Some(notes) = notes.as_mut() {
+            secret_scan::redact_value(notes);
+        }
+    } else {
+        // Vault references only resolve on this machine
+        crate::coding::vault::resolve_references(&mut settings)?;
+    }
+    Ok(ClaudeCodeProviderInput {
+        id: None,
+        name: provider.name,
+        category: provider.category,
+        settings_config: settings.to_string(),
+        source_provider_id: provider.source_provider_id,
+        website_url: provider.website_url,
+        notes: notes.and_then(|n| n.as_str().map(String::from)),
+        icon: provider.icon,
+        icon_color: provider.icon_color,
+        sort_index: provider.sort_index,
+    })
+}
+
+/// Keys in the settings and notes of exported providers
+fn scan_export(providers: &[ClaudeCodeProviderInput]) -> Vec<SecretFinding> {
+    providers
+        .iter()
+        .flat_map(|provider| {
+            let source = format!("export:{}", provider.name);
+            let settings = serde_json::from_str(&provider.settings_config).unwrap_or(Value::Null);
+            let mut findings = secret_scan::scan_value(&source, &settings);
+            if let Some(notes) = &provider.notes {
+                findings.extend(secret_scan::scan_text(&source, notes));
+            }
+            findings
+        })
+        .collect()
+}
+
+/// Replace placeholders in `incoming` with the values at the same place in `existing`
+fn fill_placeholders(incoming: &mut Value, existing: &Value) {
+    match incoming {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                if let Some(current) = existing.get(key) {
+                    fill_placeholders(child, current);
+                }
+            }
+        }
+        Value::String(text) if secret_scan::has_placeholder(text) => {
+            if let Some(current) = existing.as_str().filter(|c| !secret_scan::has_placeholder(c)) {
+                *text = current.to_string();
+            }
+        }
+        _ => {}
+    }
+}
+
+fn has_placeholders(value: &Value) -> bool {
+    match value {
+        Value::Object(map) => map.values().any(has_placeholders),
+        Value::Array(items) => items.iter().any(has_placeholders),
+        Value::String(text) => secret_scan::has_placeholder(text),
+        _ => false,
+    }
+}
+
+/// Split CSV text into records; quoted fields may contain commas, `""` and newlines.
+/// Each record carries its 1-based line number; blank lines are dropped.
+fn parse_csv(text: &str) -> Vec<(usize, Vec<String>)> {
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut line = 1;
+    let mut start_line = 1;
+    let mut chars = text.trim_start_matches('\u{feff}').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.is_empty() => quoted = true,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            '\r' if !quoted => {}
+            '\n' if !quoted => {
+                fields.push(std::mem::take(&mut field));
+                if fields.iter().any(|f| !f.trim().is_empty()) {
+                    records.push((start_line, std::mem::take(&mut fields)));
+                }
+                fields.clear();
+                line += 1;
+                start_line = line;
+            }
+            _ => {
+                if c == '\n' {
+                    line += 1;
+                }
+                field.push(c);
+            }
+        }
+    }
+    fields.push(field);
+    if fields.iter().any(|f| !f.trim().is_empty()) {
+        records.push((start_line, fields));
+    }
+    records
+}
+
+/// Provider inputs from CSV rows, by line number; a header row may reorder the columns
+fn csv_to_inputs(text: &str) -> Vec<(usize, ClaudeCodeProviderInput)> {
+    let mut records = parse_csv(text).into_iter().peekable();
+    let header: Option<Vec<String>> = records
+        .peek()
+        .map(|(_, fields)| fields.iter().map(|f| f.trim().to_lowercase()).collect::<Vec<_>>())
+        .filter(|fields| fields.iter().any(|f| f == "name") && fields.iter().any(|f| f == "base_url"));
+    if header.is_some() {
+        records.next();
+    }
+    let column = |name: &str| match &header {
+        Some(header) => header.iter().position(|h| h == name),
+        None => CSV_COLUMNS.iter().position(|c| *c == name),
+    };
+    let (name_col, url_col, key_col, model_col) =
+        (column("name"), column("base_url"), column("api_key"), column("model"));
+
+    records
+        .map(|(line, fields)| {
+            let get = |col: Option<usize>| {
+                col.and_then(|i| fields.get(i))
+                    .map(|f| f.trim().to_string())
+                    .unwrap_or_default()
+            };
+            let mut env = serde_json::Map::new();
+            env.insert("ANTHROPIC_BASE_URL".to_string(), Value::String(get(url_col)));
+            env.insert("ANTHROPIC_AUTH_TOKEN".to_string(), Value::String(get(key_col)));
+            let mut settings = serde_json::Map::new();
+            settings.insert("env".to_string(), Value::Object(env));
+            let model = get(model_col);
+            if !model.is_empty() {
+                settings.insert("model".to_string(), Value::String(model));
+            }
+            let input = ClaudeCodeProviderInput {
+                id: None,
+                name: get(name_col),
+                category: "custom".to_string(),
+                settings_config: Value::Object(settings).to_string(),
+                source_provider_id: None,
+                website_url: None,
+                notes: None,
+                icon: None,
+                icon_color: None,
+                sort_index: None,
+            };
+            (line, input)
+        })
+        .collect()
+}
+
+/// Why a bulk import row cannot be created
+fn validate_bulk_row(input: &ClaudeCodeProviderInput, taken: &[String]) -> AppResult<()> {
+    let name = input.name.trim();
+    if name.is_empty() {
+        return Err(AppError::validation("NAME_REQUIRED", &[]));
+    }
+    if taken.iter().any(|t| t == name) {
+        return Err(AppError::conflict("PROVIDER_ALREADY_EXISTS", &[("id", name)]));
+    }
+    let settings = parse_settings(name, &input.settings_config)?;
+    match settings.pointer("/env/ANTHROPIC_BASE_URL").and_then(|u| u.as_str()) {
+        Some(url) if !url.is_empty() && !url.starts_with("http://") && !url.starts_with("https://") => {
+            Err(AppError::validation("INVALID_BASE_URL", &[("url", url)]))
+        }
+        _ => super::settings_schema::validate(&input.settings_config),
+    }
+}
+
+// ============================================================================
+// Claude Provider Export Commands
+// ============================================================================
+
+/// Export providers (all when `ids` is None) as a bundle for sharing
+/// An unredacted export is checked by the secret scan: with "block" it fails
+/// unless `allow_secrets` confirms it, with "warn" it is logged.
+#[tauri::command]
+pub async fn export_claude_providers(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    ids: Option<Vec<String>>,
+    redact_secrets: bool,
+    allow_secrets: Option<bool>,
+) -> AppResult<ClaudeProviderBundle> {
+    let providers = {
+        let db = state.0.lock().await;
+        load_providers(&db).await?
+    };
+    let providers = providers
+        .into_iter()
+        .filter(|p| ids.as_ref().map(|ids| ids.contains(&p.id)).unwrap_or(true))
+        .map(|p| to_export(p, redact_secrets))
+        .collect::<AppResult<Vec<_>>>()?;
+
+    if !redact_secrets {
+        let mode = secret_scan::scan_mode(&state).await;
+        if mode != ScanMode::Off {
+            let findings = scan_export(&providers);
+            secret_scan::enforce(mode, &findings, allow_secrets.unwrap_or(false))?;
+            if !findings.is_empty() {
+                log::warn!(
+                    "Provider export contains {} possible API keys: {}",
+                    findings.len(),
+                    secret_scan::describe(&findings)
+                );
+                crate::activity_log::record(
+                    &app,
+                    "provider",
+                    format!("导出的供应商中含 {} 处密钥", findings.len()),
+                );
+            }
+        }
+    }
+
+    Ok(ClaudeProviderBundle {
+        version: BUNDLE_VERSION,
+        exported_at: Local::now().to_rfc3339(),
+        redacted: redact_secrets,
+        providers,
+    })
+}
+
+/// Import a bundle: providers with a known name are updated (keeping their keys
+/// where the bundle has placeholders), the rest are appended
+#[tauri::command]
+pub async fn import_claude_providers(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    bundle: ClaudeProviderBundle,
+) -> AppResult<ClaudeProviderImportResult> {
+    if bundle.version > BUNDLE_VERSION {
+        return Err(AppError::invalid(format!("unsupported provider bundle version {}", bundle.version)));
+    }
+    let db = state.0.lock().await;
+    let existing = load_providers(&db).await?;
+    let mut sort_index = next_sort_index(&db).await?;
+    let now = Local::now().to_rfc3339();
+    let mut result = ClaudeProviderImportResult {
+        created: 0,
+        updated: 0,
+        needs_secrets: Vec::new(),
+    };
+    let mut reapply = None;
+
+    for provider in bundle.providers {
+        let name = provider.name.trim().to_string();
+        if name.is_empty() {
+            return Err(AppError::validation("NAME_REQUIRED", &[]));
+        }
+        let mut settings = parse_settings(&name, &provider.settings_config)?;
+
+        match existing.iter().find(|p| p.name == name) {
+            Some(current) => {
+                if let Ok(current_settings) = serde_json::from_str::<Value>(&current.settings_config) {
+                    fill_placeholders(&mut settings, &current_settings);
+                }
+                db.query("UPDATE type::thing('claude_provider', $id) SET category = $category, settings_config = $settings_config, website_url = $website_url, notes = $notes, icon = $icon, icon_color = $icon_color, updated_at = $now")
+                    .bind(("id", current.id.clone()))
+                    .bind(("category", provider.category))
+                    .bind(("settings_config", settings.to_string()))
+                    .bind(("website_url", provider.website_url))
+                    .bind(("notes", provider.notes))
+                    .bind(("icon", provider.icon))
+                    .bind(("icon_color", provider.icon_color))
+                    .bind(("now", now.clone()))
+                    .await?;
+                if current.is_applied {
+                    reapply = Some(current.id.clone());
+                }
+                result.updated += 1;
+            }
+            None => {
+                let content = ClaudeCodeProviderContent {
+                    name: name.clone(),
+                    category: provider.category,
+                    settings_config: settings.to_string(),
+                    source_provider_id: provider.source_provider_id,
+                    website_url: provider.website_url,
+                    notes: provider.notes,
+                    icon: provider.icon,
+                    icon_color: provider.icon_color,
+                    sort_index: Some(sort_index),
+                    is_applied: false,
+                    is_disabled: false,
+                    is_archived: false,
+                    created_at: now.clone(),
+                    updated_at: now.clone(),
+                };
+                db.query("CREATE claude_provider CONTENT $data")
+                    .bind(("data", adapter::to_db_value_provider(&content)))
+                    .await?;
+                sort_index += 1;
+                result.created += 1;
+            }
+        }
+        if has_placeholders(&settings) {
+            result.needs_secrets.push(name);
+        }
+    }
+
+    if let Some(id) = reapply {
+        if let Err(e) = apply_config_to_file_public(&db, &id).await {
+            log::warn!("Failed to auto-apply config after provider import: {}", e);
+        }
+    }
+    crate::activity_log::record(
+        &app,
+        "import",
+        format!("已导入 {} 个 Claude Code 供应商", result.created + result.updated),
+    );
+    emit_change(&app, "claude_provider", ChangeAction::Imported, None, "window");
+    Ok(result)
+}
+
+/// Create many providers at once from `providers` or a `csv` string
+/// (name,base_url,api_key,model, optionally with a header row). Invalid rows
+/// and names that already exist are reported and skipped; the rest are
+/// inserted in one statement.
+#[tauri::command]
+pub async fn bulk_import_claude_providers(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    providers: Option<Vec<ClaudeCodeProviderInput>>,
+    csv: Option<String>,
+) -> AppResult<ClaudeBulkImportResult> {
+    let inputs: Vec<(usize, ClaudeCodeProviderInput)> = match (providers, csv) {
+        (Some(providers), _) => providers.into_iter().enumerate().map(|(i, p)| (i + 1, p)).collect(),
+        (None, Some(csv)) => csv_to_inputs(&csv),
+        (None, None) => return Err(AppError::invalid("nothing to import")),
+    };
+
+    let db = state.0.lock().await;
+    let mut taken: Vec<String> = load_providers(&db).await?.into_iter().map(|p| p.name).collect();
+    let mut sort_index = next_sort_index(&db).await?;
+    let now = Local::now().to_rfc3339();
+
+    let mut rows = Vec::new();
+    let mut records = Vec::new();
+    for (row, input) in inputs {
+        let name = input.name.trim().to_string();
+        if let Err(error) = validate_bulk_row(&input, &taken) {
+            rows.push(ClaudeBulkImportRow { row, name, success: false, id: None, error: Some(error.to_string()) });
+            continue;
+        }
+        let id = uuid::Uuid::new_v4().simple().to_string();
+        let content = ClaudeCodeProviderContent {
+            name: name.clone(),
+            category: input.category,
+            settings_config: input.settings_config,
+            source_provider_id: input.source_provider_id,
+            website_url: input.website_url,
+            notes: input.notes,
+            icon: input.icon,
+            icon_color: input.icon_color,
+            sort_index: Some(sort_index),
+            is_applied: false,
+            is_disabled: false,
+            is_archived: false,
+            created_at: now.clone(),
+            updated_at: now.clone(),
+        };
+        let mut record = adapter::to_db_value_provider(&content);
+        if let Some(record) = record.as_object_mut() {
+            record.insert("id".to_string(), Value::String(id.clone()));
+        }
+        records.push(record);
+        sort_index += 1;
+        taken.push(name.clone());
+        rows.push(ClaudeBulkImportRow { row, name, success: true, id: Some(id), error: None });
+    }
+
+    if !records.is_empty() {
+        db.query("INSERT INTO claude_provider $records")
+            .bind(("records", records))
+            .await
+            .and_then(|response| response.check())?;
+    }
+
+    let created = rows.iter().filter(|r| r.success).count();
+    if created > 0 {
+        crate::activity_log::record(&app, "import", format!("已批量导入 {} 个 Claude Code 供应商", created));
+        emit_change(&app, "claude_provider", ChangeAction::Imported, None, "window");
+    }
+    Ok(ClaudeBulkImportResult {
+        created,
+        failed: rows.len() - created,
+        rows,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn import_keeps_existing_keys_for_placeholders() {
+        let mut incoming = serde_json::json!({
+            "env": {
+                "ANTHROPIC_AUTH_TOKEN": "${ANTHROPIC_AUTH_TOKEN}",
+                "ANTHROPIC_BASE_URL": "https://new.example.com"
+            },
+            "apiKeyHelper": "${APIKEYHELPER}"
+        });
+        let existing = serde_json::json!({
+            "env": {
+                "ANTHROPIC_AUTH_TOKEN": "relay-token-0123456789abcdef",
+                "ANTHROPIC_BASE_URL": "https://old.example.com"
+            }
+        });
+        fill_placeholders(&mut incoming, &existing);
+        assert_eq!(incoming["env"]["ANTHROPIC_AUTH_TOKEN"], "relay-token-0123456789abcdef");
+        assert_eq!(incoming["env"]["ANTHROPIC_BASE_URL"], "https://new.example.com");
+        // Nothing to fill it from
+        assert!(has_placeholders(&incoming));
+    }
+
+    #[test]
+    fn unredacted_export_is_scanned() {
+        let provider = ClaudeCodeProviderInput {
+            id: None,
+            name: "Relay".to_string(),
+            category: "custom".to_string(),
+            settings_config: r#"{"env":{"ANTHROPIC_AUTH_TOKEN":"sk-ant-REDACTED"}}"#.to_string(),
+            source_provider_id: None,
+            website_url: None,
+            notes: None,
+            icon: None,
+            icon_color: None,
+            sort_index: None,
+        };
+        let findings = scan_export(std::slice::from_ref(&provider));
+        assert!(!findings.is_empty());
+        assert!(findings.iter().all(|f| f.location.starts_with("export:Relay")));
+        assert!(secret_scan::enforce(ScanMode::Block, &findings, false).is_err());
+    }
+
+    #[test]
+    fn csv_rows_map_to_provider_inputs() {
+        let csv = "model,name,base_url,api_key\r\n\
+                   claude-sonnet-4,\"Relay, EU\",https://eu.example.com,sk-eu\r\n\
+                   \r\n\
+                   ,\"Say \"\"hi\"\"\",ftp://bad,\n";
+        let inputs = csv_to_inputs(csv);
+        assert_eq!(inputs.len(), 2);
+        assert_eq!(inputs[0].0, 2);
+        assert_eq!(inputs[0].1.name, "Relay, EU");
+        let settings: Value = serde_json::from_str(&inputs[0].1.settings_config).unwrap();
+        assert_eq!(settings["env"]["ANTHROPIC_BASE_URL"], "https://eu.example.com");
+        assert_eq!(settings["env"]["ANTHROPIC_AUTH_TOKEN"], "sk-eu");
+        assert_eq!(settings["model"], "claude-sonnet-4");
+
+        assert_eq!(inputs[1].0, 4);
+        assert_eq!(inputs[1].1.name, "Say \"hi\"");
+        assert!(validate_bulk_row(&inputs[1].1, &[]).unwrap_err().to_string().contains("ftp://bad"));
+        assert!(validate_bulk_row(&inputs[0].1, &["Relay, EU".to_string()]).is_err());
+    }
+
+    #[test]
+    fn csv_without_header_uses_default_columns() {
+        let inputs = csv_to_inputs("Relay,https://relay.example.com,sk-relay");
+        assert_eq!(inputs.len(), 1);
+        assert_eq!(inputs[0].1.name, "Relay");
+        assert!(validate_bulk_row(&inputs[0].1, &[]).is_ok());
+    }
+}
@@ -0,0 +1,386 @@
+//! Claude Code session transcripts
+//!
+//! Claude Code logs each session to `~/.claude/projects/<project>/<session>.jsonl`,
+//! one line per message (assistant replies are split into one line per
+//! content block). The lines are parsed into a transcript that can be
+//! exported as Markdown or a self-contained HTML page.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+use walkdir::WalkDir;
+
+use super::commands::get_claude_config_path;
+use super::types::{ClaudeSessionExport, ClaudeSessionSummary};
+
+const DEFAULT_SESSION_LIMIT: usize = 50;
+const TITLE_MAX_CHARS: usize = 80;
+
+/// One content block of a message
+#[derive(Debug, Clone, PartialEq)]
+enum Block {
+    Text(String),
+    Thinking(String),
+    ToolUse { name: String, input: String },
+    ToolResult { content: String, is_error: bool },
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    role: String,
+    timestamp: String,
+    model: Option<String>,
+    blocks: Vec<Block>,
+}
+
+#[derive(Debug, Default)]
+struct Transcript {
+    session_id: String,
+    cwd: Option<String>,
+    entries: Vec<Entry>,
+}
+
+fn projects_dir() -> Result<PathBuf, String> {
+    let settings = PathBuf::from(get_claude_config_path()?);
+    Ok(settings.parent().unwrap_or(Path::new(".")).join("projects"))
+}
+
+/// Text of a tool_result `content` (a string or a list of text blocks)
+fn result_text(content: Option<&Value>) -> String {
+    match content {
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(|item| item.get("text").and_then(|t| t.as_str()))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+fn parse_blocks(content: &Value) -> Vec<Block> {
+    let items = match content {
+        Value::String(text) => return vec![Block::Text(text.clone())],
+        Value::Array(items) => items,
+        _ => return Vec::new(),
+    };
+    items
+        .iter()
+        .filter_map(|item| {
+            let text = |key: &str| item.get(key).and_then(|t| t.as_str()).unwrap_or_default().to_string();
+            match item.get("type").and_then(|t| t.as_str())? {
+                "text" => Some(Block::Text(text("text"))),
+                "thinking" => Some(Block::Thinking(text("thinking"))),
+                "tool_use" => Some(Block::ToolUse {
+                    name: text("name"),
+                    input: item
+                        .get("input")
+                        .and_then(|input| serde_json::to_string_pretty(input).ok())
+                        .unwrap_or_default(),
+                }),
+                "tool_result" => Some(Block::ToolResult {
+                    content: result_text(item.get("content")),
+                    is_error: item.get("is_error").and_then(|e| e.as_bool()).unwrap_or(false),
+                }),
+                _ => None,
+            }
+        })
+        .filter(|block| !matches!(block, Block::Text(t) | Block::Thinking(t) if t.trim().is_empty()))
+        .collect()
+}
+
+fn parse_transcript(session_id: &str, content: &str) -> Transcript {
+    let mut transcript = Transcript {
+        session_id: session_id.to_string(),
+        ..Default::default()
+    };
+    for line in content.lines() {
+        let Ok(value) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        let kind = value.get("type").and_then(|t| t.as_str()).unwrap_or_default();
+        if kind != "user" && kind != "assistant" {
+            continue;
+        }
+        // Injected context (command output, reminders) is not part of the conversation
+        if value.get("isMeta").and_then(|m| m.as_bool()).unwrap_or(false) {
+            continue;
+        }
+        if transcript.cwd.is_none() {
+            transcript.cwd = value.get("cwd").and_then(|c| c.as_str()).map(String::from);
+        }
+        let Some(message) = value.get("message") else {
+            continue;
+        };
+        let blocks = parse_blocks(message.get("content").unwrap_or(&Value::Null));
+        if blocks.is_empty() {
+            continue;
+        }
+        transcript.entries.push(Entry {
+            role: kind.to_string(),
+            timestamp: value.get("timestamp").and_then(|t| t.as_str()).unwrap_or_default().to_string(),
+            model: message.get("model").and_then(|m| m.as_str()).map(String::from),
+            blocks,
+        });
+    }
+    transcript
+}
+
+/// First user text, shortened, as the session title
+fn title(transcript: &Transcript) -> String {
+    let text = transcript
+        .entries
+        .iter()
+        .filter(|e| e.role == "user")
+        .flat_map(|e| e.blocks.iter())
+        .find_map(|block| match block {
+            Block::Text(text) => Some(text.trim().replace('\n', " ")),
+            _ => None,
+        })
+        .unwrap_or_default();
+    if text.chars().count() > TITLE_MAX_CHARS {
+        format!("{}…", text.chars().take(TITLE_MAX_CHARS).collect::<String>())
+    } else {
+        text
+    }
+}
+
+fn is_session_id(id: &str) -> bool {
+    !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn session_files(dir: &Path) -> impl Iterator<Item = PathBuf> {
+    WalkDir::new(dir)
+        .max_depth(2)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .map(|e| e.into_path())
+        .filter(|path| path.extension().and_then(|e| e.to_str()) == Some("jsonl"))
+}
+
+fn find_session(dir: &Path, session_id: &str) -> Result<PathBuf, String> {
+    if !is_session_id(session_id) {
+        return Err(format!("Invalid session id: {}", session_id));
+    }
+    session_files(dir)
+        .find(|path| path.file_stem().and_then(|s| s.to_str()) == Some(session_id))
+        .ok_or_else(|| format!("Session not found: {}", session_id))
+}
+
+fn fence(text: &str, lang: &str) -> String {
+    // Longer fence than any backtick run inside the text
+    let longest = text
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    let ticks = "`".repeat(longest.max(2) + 1);
+    format!("{ticks}{lang}\n{}\n{ticks}\n", text.trim_end())
+}
+
+fn role_heading(entry: &Entry) -> String {
+    match (entry.role.as_str(), &entry.model) {
+        ("assistant", Some(model)) => format!("Assistant ({})", model),
+        ("assistant", None) => "Assistant".to_string(),
+        _ => "User".to_string(),
+    }
+}
+
+fn to_markdown(transcript: &Transcript) -> String {
+    let mut out = format!("# Claude Code session {}\n\n", transcript.session_id);
+    if let Some(cwd) = &transcript.cwd {
+        out.push_str(&format!("- Project: `{}`\n", cwd));
+    }
+    if let Some(first) = transcript.entries.first() {
+        out.push_str(&format!("- Started: {}\n", first.timestamp));
+    }
+    for entry in &transcript.entries {
+        out.push_str(&format!("\n## {}\n\n", role_heading(entry)));
+        if !entry.timestamp.is_empty() {
+            out.push_str(&format!("_{}_\n\n", entry.timestamp));
+        }
+        for block in &entry.blocks {
+            match block {
+                Block::Text(text) => out.push_str(&format!("{}\n\n", text.trim_end())),
+                Block::Thinking(text) => {
+                    for line in text.trim_end().lines() {
+                        out.push_str(&format!("> {}\n", line));
+                    }
+                    out.push('\n');
+                }
+                Block::ToolUse { name, input } => {
+                    out.push_str(&format!("**Tool: {}**\n\n{}\n", name, fence(input, "json")));
+                }
+                Block::ToolResult { content, is_error } => {
+                    let label = if *is_error { "Tool error" } else { "Tool result" };
+                    out.push_str(&format!("**{}**\n\n{}\n", label, fence(content, "")));
+                }
+            }
+        }
+    }
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn to_html(transcript: &Transcript) -> String {
+    let mut body = String::new();
+    for entry in &transcript.entries {
+        body.push_str(&format!(
+            "<section class=\"{}\"><h2>{}</h2><time>{}</time>\n",
+            escape_html(&entry.role),
+            escape_html(&role_heading(entry)),
+            escape_html(&entry.timestamp)
+        ));
+        for block in &entry.blocks {
+            match block {
+                Block::Text(text) => body.push_str(&format!("<div class=\"text\">{}</div>\n", escape_html(text.trim_end()))),
+                Block::Thinking(text) => {
+                    body.push_str(&format!("<blockquote>{}</blockquote>\n", escape_html(text.trim_end())))
+                }
+                Block::ToolUse { name, input } => body.push_str(&format!(
+                    "<details><summary>Tool: {}</summary><pre>{}</pre></details>\n",
+                    escape_html(name),
+                    escape_html(input)
+                )),
+                Block::ToolResult { content, is_error } => body.push_str(&format!(
+                    "<details{}><summary>{}</summary><pre>{}</pre></details>\n",
+                    if *is_error { " class=\"error\"" } else { "" },
+                    if *is_error { "Tool error" } else { "Tool result" },
+                    escape_html(content)
+                )),
+            }
+        }
+        body.push_str("</section>\n");
+    }
+    format!(
+        r#"<!doctype html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Claude Code session {id}</title>
+<style>
+  body {{ font-family: -apple-system, system-ui, sans-serif; max-width: 860px; margin: 24px auto; padding: 0 16px; color: #222; }}
+  section {{ border-radius: 8px; padding: 8px 16px; margin: 12px 0; }}
+  section.user {{ background: #f0f5ff; }}
+  section.assistant {{ background: #f6f6f6; }}
+  h2 {{ font-size: 15px; margin: 8px 0 0; }}
+  time {{ font-size: 12px; color: #888; }}
+  .text {{ white-space: pre-wrap; margin: 8px 0; }}
+  blockquote {{ white-space: pre-wrap; color: #666; border-left: 3px solid #ccc; margin: 8px 0; padding-left: 12px; }}
+  pre {{ white-space: pre-wrap; background: #fff; padding: 8px; border-radius: 4px; font-size: 12px; }}
+  details.error summary {{ color: #cf1322; }}
+</style>
+</head>
+<body>
+<h1>Claude Code session {id}</h1>
+<p>{cwd}</p>
+{body}</body>
+</html>
+"#,
+        id = escape_html(&transcript.session_id),
+        cwd = escape_html(transcript.cwd.as_deref().unwrap_or_default()),
+        body = body
+    )
+}
+
+// ============================================================================
+// Claude Session Commands
+// ============================================================================
+
+/// Recent sessions from ~/.claude/projects, newest first
+#[tauri::command]
+pub fn list_claude_sessions(limit: Option<usize>) -> Result<Vec<ClaudeSessionSummary>, String> {
+    let dir = projects_dir()?;
+    let mut files: Vec<(PathBuf, std::time::SystemTime)> = session_files(&dir)
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((path, modified))
+        })
+        .collect();
+    files.sort_by(|a, b| b.1.cmp(&a.1));
+
+    Ok(files
+        .into_iter()
+        .take(limit.unwrap_or(DEFAULT_SESSION_LIMIT))
+        .filter_map(|(path, modified)| {
+            let session_id = path.file_stem()?.to_string_lossy().to_string();
+            let transcript = parse_transcript(&session_id, &fs::read_to_string(&path).ok()?);
+            Some(ClaudeSessionSummary {
+                title: title(&transcript),
+                started_at: transcript.entries.first().map(|e| e.timestamp.clone()),
+                updated_at: chrono::DateTime::<chrono::Local>::from(modified).to_rfc3339(),
+                message_count: transcript.entries.len(),
+                project: transcript.cwd,
+                session_id,
+            })
+        })
+        .collect())
+}
+
+/// Write a session transcript to `path` as "markdown" (default) or "html"
+#[tauri::command]
+pub fn export_claude_session(
+    session_id: String,
+    format: Option<String>,
+    path: String,
+) -> Result<ClaudeSessionExport, String> {
+    let source = find_session(&projects_dir()?, &session_id)?;
+    let content = fs::read_to_string(&source).map_err(|e| format!("Failed to read session: {}", e))?;
+    let transcript = parse_transcript(&session_id, &content);
+
+    let output = match format.as_deref().unwrap_or("markdown") {
+        "markdown" | "md" => to_markdown(&transcript),
+        "html" => to_html(&transcript),
+        other => return Err(format!("Unsupported export format: {}", other)),
+    };
+    fs::write(&path, output).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    Ok(ClaudeSessionExport {
+        path,
+        message_count: transcript.entries.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SESSION: &str = r#"{"type":"summary","summary":"x"}
+{"type":"user","cwd":"/work/app","timestamp":"2026-03-01T10:00:00Z","message":{"role":"user","content":"Fix the <login> bug"}}
+{"type":"user","isMeta":true,"timestamp":"2026-03-01T10:00:00Z","message":{"role":"user","content":"<system-reminder>"}}
+{"type":"assistant","timestamp":"2026-03-01T10:00:05Z","message":{"model":"claude-sonnet-4","content":[{"type":"text","text":"Looking."},{"type":"tool_use","name":"Read","input":{"file_path":"src/login.rs"}}]}}
+{"type":"user","timestamp":"2026-03-01T10:00:06Z","message":{"role":"user","content":[{"type":"tool_result","content":"fn login() {}","is_error":false}]}}
+"#;
+
+    #[test]
+    fn parses_conversation_lines() {
+        let transcript = parse_transcript("abc", SESSION);
+        assert_eq!(transcript.cwd.as_deref(), Some("/work/app"));
+        assert_eq!(transcript.entries.len(), 3);
+        assert_eq!(transcript.entries[1].model.as_deref(), Some("claude-sonnet-4"));
+        assert_eq!(
+            transcript.entries[2].blocks[0],
+            Block::ToolResult { content: "fn login() {}".to_string(), is_error: false }
+        );
+        assert_eq!(title(&transcript), "Fix the <login> bug");
+    }
+
+    #[test]
+    fn renders_markdown_and_escaped_html() {
+        let transcript = parse_transcript("abc", SESSION);
+        let markdown = to_markdown(&transcript);
+        assert!(markdown.contains("## Assistant (claude-sonnet-4)"));
+        assert!(markdown.contains("**Tool: Read**"));
+        assert!(markdown.contains("```json\n{\n  \"file_path\": \"src/login.rs\"\n}\n```"));
+
+        let html = to_html(&transcript);
+        assert!(html.contains("Fix the &lt;login&gt; bug"));
+        assert!(!html.contains("<login>"));
+    }
+}
@@ -0,0 +1,181 @@
+//! Schema check of a provider's `settings_config`
+//!
+//! Catches malformed provider settings when they are saved instead of when
+//! they are applied. Failures are returned as one `INVALID_PROVIDER_SETTINGS`
+//! validation error whose params map each offending field (e.g.
+//! `env.ANTHROPIC_BASE_URL`) to the rule it broke, so the form can mark it.
+
+use serde_json::Value;
+
+use crate::coding::vault::VAULT_REFERENCE_PREFIX;
+use crate::error::AppError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Rule {
+    /// Any string
+    Text,
+    /// http(s) URL
+    Url,
+    /// Model id: no whitespace
+    Model,
+    /// http(s) or socks5(h) proxy URL, scheme optional
+    Proxy,
+}
+
+impl Rule {
+    fn code(self) -> &'static str {
+        match self {
+            Rule::Text => "string",
+            Rule::Url => "url",
+            Rule::Model => "model",
+            Rule::Proxy => "proxy",
+        }
+    }
+}
+
+/// Known fields by JSON pointer; absent or empty fields are fine
+const FIELDS: &[(&str, Rule)] = &[
+    ("/env/ANTHROPIC_BASE_URL", Rule::Url),
+    ("/env/ANTHROPIC_AUTH_TOKEN", Rule::Text),
+    ("/env/ANTHROPIC_API_KEY", Rule::Text),
+    ("/env/ANTHROPIC_MODEL", Rule::Model),
+    ("/env/ANTHROPIC_DEFAULT_HAIKU_MODEL", Rule::Model),
+    ("/env/ANTHROPIC_DEFAULT_SONNET_MODEL", Rule::Model),
+    ("/env/ANTHROPIC_DEFAULT_OPUS_MODEL", Rule::Model),
+    ("/model", Rule::Model),
+    ("/haikuModel", Rule::Model),
+    ("/sonnetModel", Rule::Model),
+    ("/opusModel", Rule::Model),
+    ("/proxy", Rule::Proxy),
+    ("/noProxy", Rule::Text),
+];
+
+fn field_name(pointer: &str) -> String {
+    pointer.trim_start_matches('/').replace('/', ".")
+}
+
+fn check_rule(rule: Rule, value: &Value) -> bool {
+    let Some(text) = value.as_str() else {
+        return false;
+    };
+    let text = text.trim();
+    if text.is_empty() {
+        return true;
+    }
+    match rule {
+        Rule::Text => true,
+        // Vault references are resolved on apply
+        Rule::Url => {
+            text.starts_with(VAULT_REFERENCE_PREFIX)
+                || reqwest::Url::parse(text).is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.has_host())
+        }
+        Rule::Model => !text.contains(char::is_whitespace),
+        Rule::Proxy => reqwest::Url::parse(&crate::http_client::normalize_proxy_url(text))
+            .is_ok_and(|url| matches!(url.scheme(), "http" | "https" | "socks5" | "socks5h") && url.has_host()),
+    }
+}
+
+fn is_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// (field, rule code) of every violation, in document order of the checks
+pub fn field_errors(settings_config: &str) -> Vec<(String, &'static str)> {
+    let value: Value = match serde_json::from_str(settings_config) {
+        Ok(value) => value,
+        Err(_) => return vec![("settingsConfig".to_string(), "json")],
+    };
+    if !value.is_object() {
+        return vec![("settingsConfig".to_string(), "object")];
+    }
+
+    let mut errors = Vec::new();
+    for section in ["env", "extraEnv"] {
+        match value.get(section) {
+            None | Some(Value::Null) => {}
+            Some(Value::Object(env)) => {
+                for (key, entry) in env {
+                    if !is_env_name(key) {
+                        errors.push((format!("{}.{}", section, key), "env_name"));
+                    } else if !entry.is_string() {
+                        errors.push((format!("{}.{}", section, key), "string"));
+                    }
+                }
+            }
+            Some(_) => errors.push((section.to_string(), "object")),
+        }
+    }
+    for (pointer, rule) in FIELDS {
+        let name = field_name(pointer);
+        if errors.iter().any(|(field, _)| *field == name) {
+            continue;
+        }
+        match value.pointer(pointer) {
+            None | Some(Value::Null) => {}
+            Some(field) if check_rule(*rule, field) => {}
+            Some(_) => errors.push((name, rule.code())),
+        }
+    }
+    errors.extend(super::cloud::field_errors(&value));
+    errors.extend(super::limits::field_errors(&value));
+    errors
+}
+
+/// Reject settings that break the schema before they are stored
+pub fn validate(settings_config: &str) -> Result<(), AppError> {
+    let errors = field_errors(settings_config);
+    if errors.is_empty() {
+        return Ok(());
+    }
+    let fields = errors.iter().map(|(field, _)| field.as_str()).collect::<Vec<_>>().join(", ");
+    let mut params: Vec<(&str, &str)> = vec![("fields", fields.as_str())];
+    params.extend(errors.iter().map(|(field, rule)| (field.as_str(), *rule)));
+    Err(AppError::validation("INVALID_PROVIDER_SETTINGS", &params))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_each_bad_field() {
+        let errors = field_errors(
+            r#"{
+                "env": {
+                    "ANTHROPIC_BASE_URL": "relay.example.com",
+                    "ANTHROPIC_AUTH_TOKEN": "sk-x",
+                    "BAD KEY": "1",
+                    "API_TIMEOUT_MS": 600000
+                },
+                "model": "claude sonnet",
+                "haikuModel": "",
+                "extraEnv": { "HTTPS_PROXY": "http://127.0.0.1:7890", "2FAST": "1" }
+            }"#,
+        );
+        assert_eq!(
+            errors,
+            vec![
+                ("env.API_TIMEOUT_MS".to_string(), "string"),
+                ("env.BAD KEY".to_string(), "env_name"),
+                ("extraEnv.2FAST".to_string(), "env_name"),
+                ("env.ANTHROPIC_BASE_URL".to_string(), "url"),
+                ("model".to_string(), "model"),
+            ]
+        );
+    }
+
+    #[test]
+    fn accepts_valid_and_vault_settings() {
+        assert!(validate(r#"{"env":{"ANTHROPIC_BASE_URL":"https://relay.example.com/api","ANTHROPIC_AUTH_TOKEN":"vault://relay"},"model":"glm-4.6"}"#).is_ok());
+        assert!(validate(r#"{"env":{"ANTHROPIC_BASE_URL":"vault://relay-url"}}"#).is_ok());
+        assert!(validate("{}").is_ok());
+        assert!(validate(r#"{"proxy":"127.0.0.1:7890","noProxy":"localhost"}"#).is_ok());
+        assert_eq!(field_errors(r#"{"proxy":"ftp://proxy.example.com"}"#), vec![("proxy".to_string(), "proxy")]);
+
+        let error = validate("[]").unwrap_err();
+        assert_eq!(error.detail().code, "INVALID_PROVIDER_SETTINGS");
+        assert_eq!(error.detail().params["settingsConfig"], "object");
+    }
+}
@@ -0,0 +1,230 @@
+//! Provider env in shell profiles
+//!
+//! Alternative to settings.json for users who configure Claude Code through
+//! environment variables: the provider's `ANTHROPIC_*` variables are written
+//! as exports into ~/.zshrc, ~/.bashrc and/or the PowerShell profile, inside a
+//! block between `MARKER_START` and `MARKER_END`. Applying again replaces the
+//! block; removing it leaves the rest of the profile untouched.
+//!
+//! Note that an `env` entry in settings.json still takes precedence over the
+//! shell for the same variable.
+
+use std::fs;
+use std::path::PathBuf;
+
+use serde_json::{Map, Value};
+
+use crate::coding::change_history;
+use crate::db::DbState;
+use crate::error::{AppError, AppResult};
+use super::commands::provider_env;
+use super::provider_io::load_providers;
+use super::types::ClaudeShellProfileResult;
+
+const MARKER_START: &str = "# >>> ai-toolbox claude env >>>";
+const MARKER_END: &str = "# <<< ai-toolbox claude env <<<";
+
+/// Shells with a profile we can manage
+pub const SHELLS: [&str; 3] = ["zsh", "bash", "powershell"];
+
+fn profile_path(shell: &str) -> AppResult<PathBuf> {
+    let home = dirs::home_dir().ok_or_else(|| AppError::io("Failed to get home directory"))?;
+    match shell {
+        "zsh" => Ok(home.join(".zshrc")),
+        "bash" => Ok(home.join(".bashrc")),
+        "powershell" if cfg!(target_os = "windows") => Ok(dirs::document_dir()
+            .unwrap_or_else(|| home.join("Documents"))
+            .join("PowerShell")
+            .join("Microsoft.PowerShell_profile.ps1")),
+        "powershell" => Ok(home
+            .join(".config")
+            .join("powershell")
+            .join("Microsoft.PowerShell_profile.ps1")),
+        other => Err(AppError::invalid(format!("unsupported shell '{}'", other))),
+    }
+}
+
+/// Shells to write when none are given: PowerShell on Windows, otherwise
+/// every existing zsh/bash profile (or the login shell's when there is none)
+fn default_shells() -> Vec<String> {
+    if cfg!(target_os = "windows") {
+        return vec!["powershell".to_string()];
+    }
+    let existing: Vec<String> = ["zsh", "bash"]
+        .iter()
+        .filter(|shell| profile_path(shell).is_ok_and(|path| path.exists()))
+        .map(|shell| shell.to_string())
+        .collect();
+    if !existing.is_empty() {
+        return existing;
+    }
+    let login = std::env::var("SHELL").unwrap_or_default();
+    vec![if login.ends_with("bash") { "bash" } else { "zsh" }.to_string()]
+}
+
+fn resolve_shells(shells: Option<Vec<String>>) -> AppResult<Vec<String>> {
+    let shells = shells.filter(|s| !s.is_empty()).unwrap_or_else(default_shells);
+    if let Some(unknown) = shells.iter().find(|shell| !SHELLS.contains(&shell.as_str())) {
+        return Err(AppError::invalid(format!("unsupported shell '{}'", unknown)));
+    }
+    Ok(shells)
+}
+
+/// Managed block setting `env` in the syntax of `shell`
+fn render_block(shell: &str, env: &Map<String, Value>) -> String {
+    let mut lines = vec![
+        MARKER_START.to_string(),
+        "# Managed by AI Toolbox; changes inside this block are overwritten".to_string(),
+    ];
+    for (key, value) in env {
+        let Some(value) = value.as_str() else {
+            continue;
+        };
+        lines.push(if shell == "powershell" {
+            format!("$env:{} = '{}'", key, value.replace('\'', "''"))
+        } else {
+            format!("export {}='{}'", key, value.replace('\'', "'\\''"))
+        });
+    }
+    lines.push(MARKER_END.to_string());
+    lines.join("\n")
+}
+
+/// `content` with its managed block replaced by `block` (appended when there is
+/// none yet), or removed when `block` is None
+fn replace_block(content: &str, block: Option<&str>) -> String {
+    let range = content.find(MARKER_START).and_then(|start| {
+        content[start..]
+            .find(MARKER_END)
+            .map(|end| (start, start + end + MARKER_END.len()))
+    });
+    match (range, block) {
+        (Some((start, end)), Some(block)) => format!("{}{}{}", &content[..start], block, &content[end..]),
+        (Some((start, end)), None) => {
+            let before = content[..start].trim_end_matches('\n');
+            let after = content[end..].trim_start_matches('\n');
+            match (before.is_empty(), after.is_empty()) {
+                (true, _) => after.to_string(),
+                (false, true) => format!("{}\n", before),
+                (false, false) => format!("{}\n\n{}", before, after),
+            }
+        }
+        (None, Some(block)) if content.trim().is_empty() => format!("{}\n", block),
+        (None, Some(block)) => format!("{}\n\n{}\n", content.trim_end_matches('\n'), block),
+        (None, None) => content.to_string(),
+    }
+}
+
+fn update_profile(shell: &str, block: Option<&str>) -> AppResult<ClaudeShellProfileResult> {
+    let path = profile_path(shell)?;
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(AppError::io(format!("{}: {}", path.display(), e))),
+    };
+    let updated = replace_block(&content, block);
+    let changed = updated != content;
+    if changed {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        change_history::write_config("claude_code", &path, &updated)?;
+    }
+    Ok(ClaudeShellProfileResult {
+        shell: shell.to_string(),
+        path: path.to_string_lossy().to_string(),
+        changed,
+    })
+}
+
+// ============================================================================
+// Claude Shell Profile Commands
+// ============================================================================
+
+/// Write the provider's env vars into the managed block of the shell profiles
+/// `shells` ("zsh", "bash", "powershell") defaults to the ones in use here.
+#[tauri::command]
+pub async fn apply_claude_provider_to_shell(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    provider_id: String,
+    shells: Option<Vec<String>>,
+) -> AppResult<Vec<ClaudeShellProfileResult>> {
+    let shells = resolve_shells(shells)?;
+    let provider = {
+        let db = state.0.lock().await;
+        load_providers(&db)
+            .await?
+            .into_iter()
+            .find(|p| p.id == provider_id)
+            .ok_or_else(|| AppError::not_found("PROVIDER_NOT_FOUND", &[("id", provider_id.as_str())]))?
+    };
+    if provider.is_disabled {
+        return Err(AppError::conflict("PROVIDER_DISABLED", &[("id", provider.name.as_str())]));
+    }
+
+    let mut config: Value = serde_json::from_str(&provider.settings_config).map_err(AppError::invalid)?;
+    crate::coding::vault::resolve_references(&mut config)?;
+    let env = provider_env(&config);
+
+    let results = shells
+        .iter()
+        .map(|shell| update_profile(shell, Some(&render_block(shell, &env))))
+        .collect::<AppResult<Vec<_>>>()?;
+    crate::activity_log::record(
+        &app,
+        "provider",
+        format!("Claude Code 环境变量已写入 Shell 配置：{}", provider.name),
+    );
+    Ok(results)
+}
+
+/// Remove the managed block from the shell profiles (all supported ones by default)
+#[tauri::command]
+pub async fn remove_claude_shell_env(shells: Option<Vec<String>>) -> AppResult<Vec<ClaudeShellProfileResult>> {
+    let shells = match shells.filter(|s| !s.is_empty()) {
+        Some(shells) => resolve_shells(Some(shells))?,
+        None => SHELLS.iter().map(|shell| shell.to_string()).collect(),
+    };
+    shells.iter().map(|shell| update_profile(shell, None)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env() -> Map<String, Value> {
+        serde_json::json!({
+            "ANTHROPIC_AUTH_TOKEN": "sk-it's",
+            "ANTHROPIC_BASE_URL": "https://relay.example.com",
+        })
+        .as_object()
+        .unwrap()
+        .clone()
+    }
+
+    #[test]
+    fn renders_posix_and_powershell_quoting() {
+        let posix = render_block("zsh", &env());
+        assert!(posix.contains("export ANTHROPIC_AUTH_TOKEN='sk-it'\\''s'"));
+        let ps = render_block("powershell", &env());
+        assert!(ps.contains("$env:ANTHROPIC_AUTH_TOKEN = 'sk-it''s'"));
+        assert!(ps.starts_with(MARKER_START) && ps.ends_with(MARKER_END));
+    }
+
+    #[test]
+    fn replaces_and_removes_only_the_managed_block() {
+        let block = render_block("bash", &env());
+        let original = "alias ll='ls -l'\n";
+        let applied = replace_block(original, Some(&block));
+        assert_eq!(applied, format!("alias ll='ls -l'\n\n{}\n", block));
+
+        let edited = format!("{}export PATH=$PATH:~/bin\n", applied);
+        let reapplied = replace_block(&edited, Some("NEW"));
+        assert_eq!(reapplied, "alias ll='ls -l'\n\nNEW\nexport PATH=$PATH:~/bin\n");
+
+        assert_eq!(replace_block(&applied, None), original);
+        assert_eq!(replace_block(original, None), original);
+        assert_eq!(replace_block("", Some("B")), "B\n");
+    }
+}
@@ -0,0 +1,413 @@
+//! Custom slash commands
+//!
+//! Markdown command files under `~/.claude/commands` (sub-directories are
+//! namespaces, so `frontend/review.md` is the command `frontend/review`).
+//! Reusable templates live in `claude_command_template` and can be
+//! installed as files; commands and templates export to one JSON bundle.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::coding::{change_history, db_extract_id};
+use crate::db::DbState;
+use crate::events::{emit_change, ChangeAction};
+use super::commands::get_claude_config_path;
+use super::front_matter;
+use super::types::{
+    ClaudeCommandBundle, ClaudeCommandImportResult, ClaudeCommandTemplate, ClaudeSlashCommand,
+    ClaudeSlashCommandInput,
+};
+
+type Db = surrealdb::Surreal<surrealdb::engine::local::Db>;
+
+const BUNDLE_VERSION: u32 = 1;
+
+/// Front matter keys Claude Code reads from command files
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CommandMeta {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(rename = "allowed-tools", default, skip_serializing_if = "Option::is_none")]
+    allowed_tools: Option<String>,
+    #[serde(rename = "argument-hint", default, skip_serializing_if = "Option::is_none")]
+    argument_hint: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    model: Option<String>,
+}
+
+fn commands_dir() -> Result<PathBuf, String> {
+    let settings = PathBuf::from(get_claude_config_path()?);
+    Ok(settings.parent().unwrap_or(Path::new(".")).join("commands"))
+}
+
+/// Names are '/'-separated segments of letters, digits, '-', '_' and '.', without ".."
+fn validate_name(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty()
+        && name.split('/').all(|segment| {
+            !segment.is_empty()
+                && segment != "."
+                && segment != ".."
+                && segment
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        });
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid command name: {}", name))
+    }
+}
+
+fn command_path(dir: &Path, name: &str) -> PathBuf {
+    let path = name.split('/').fold(dir.to_path_buf(), |path, segment| path.join(segment));
+    // Not `with_extension`: names may contain dots
+    let mut file = path.into_os_string();
+    file.push(".md");
+    PathBuf::from(file)
+}
+
+fn render(input: &ClaudeSlashCommandInput) -> Result<String, String> {
+    let clean = |value: &Option<String>| value.as_ref().map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+    let meta = CommandMeta {
+        description: clean(&input.description),
+        allowed_tools: clean(&input.allowed_tools),
+        argument_hint: clean(&input.argument_hint),
+        model: clean(&input.model),
+    };
+    front_matter::render(&meta, &input.content)
+}
+
+fn read_command(dir: &Path, path: &Path) -> Option<ClaudeSlashCommand> {
+    let relative = path
+        .strip_prefix(dir)
+        .ok()?
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect::<Vec<_>>()
+        .join("/");
+    let name = relative.strip_suffix(".md")?.to_string();
+    let content = fs::read_to_string(path).ok()?;
+    let (meta, body) = match front_matter::parse::<CommandMeta>(&content) {
+        Ok(parsed) => parsed,
+        // Keep files with broken front matter editable as plain prompts
+        Err(_) => (CommandMeta::default(), content),
+    };
+    let updated_at = fs::metadata(path)
+        .and_then(|m| m.modified())
+        .map(|time| DateTime::<Local>::from(time).to_rfc3339())
+        .unwrap_or_default();
+    Some(ClaudeSlashCommand {
+        name,
+        description: meta.description,
+        allowed_tools: meta.allowed_tools,
+        argument_hint: meta.argument_hint,
+        model: meta.model,
+        content: body,
+        path: path.to_string_lossy().to_string(),
+        updated_at,
+    })
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else if path.extension().and_then(|e| e.to_str()) == Some("md") {
+            files.push(path);
+        }
+    }
+}
+
+/// Command files in `dir`, by name
+fn list_in(dir: &Path) -> Vec<ClaudeSlashCommand> {
+    let mut files = Vec::new();
+    collect_files(dir, &mut files);
+    let mut commands: Vec<ClaudeSlashCommand> = files.iter().filter_map(|path| read_command(dir, path)).collect();
+    commands.sort_by(|a, b| a.name.cmp(&b.name));
+    commands
+}
+
+fn write_command(dir: &Path, input: &ClaudeSlashCommandInput) -> Result<ClaudeSlashCommand, String> {
+    validate_name(&input.name)?;
+    let path = command_path(dir, &input.name);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create commands directory: {}", e))?;
+    }
+    change_history::write_config("claude_code", &path, render(input)?)
+        .map_err(|e| format!("Failed to write command file: {}", e))?;
+    read_command(dir, &path).ok_or_else(|| format!("Failed to read command file: {}", path.display()))
+}
+
+fn remove_command(dir: &Path, name: &str) -> Result<(), String> {
+    validate_name(name)?;
+    let path = command_path(dir, name);
+    fs::remove_file(&path).map_err(|e| format!("Failed to delete {}: {}", path.display(), e))?;
+    // Drop namespace directories left empty
+    let mut parent = path.parent();
+    while let Some(current) = parent.filter(|p| *p != dir) {
+        if fs::remove_dir(current).is_err() {
+            break;
+        }
+        parent = current.parent();
+    }
+    Ok(())
+}
+
+fn template_from_db_value(mut value: Value) -> Option<ClaudeCommandTemplate> {
+    let id = db_extract_id(&value);
+    value.as_object_mut()?.insert("id".to_string(), Value::String(id));
+    serde_json::from_value(value).ok()
+}
+
+async fn load_templates(db: &Db) -> Result<Vec<ClaudeCommandTemplate>, String> {
+    let records: Vec<Value> = db
+        .query("SELECT *, type::string(id) as id FROM claude_command_template")
+        .await
+        .map_err(|e| format!("Failed to query command templates: {}", e))?
+        .take(0)
+        .map_err(|e| format!("Failed to parse command templates: {}", e))?;
+    let mut templates: Vec<ClaudeCommandTemplate> = records.into_iter().filter_map(template_from_db_value).collect();
+    templates.sort_by(|a, b| a.command.name.cmp(&b.command.name));
+    Ok(templates)
+}
+
+async fn save_template_record(db: &Db, id: &str, command: &ClaudeSlashCommandInput) -> Result<(), String> {
+    db.query("UPSERT type::thing('claude_command_template', $id) SET command = $command, updated_at = $now, created_at = created_at ?? $now")
+        .bind(("id", id.to_string()))
+        .bind(("command", serde_json::to_value(command).map_err(|e| format!("Failed to serialize command: {}", e))?))
+        .bind(("now", Local::now().to_rfc3339()))
+        .await
+        .map_err(|e| format!("Failed to save command template: {}", e))?;
+    Ok(())
+}
+
+// ============================================================================
+// Claude Slash Command Commands
+// ============================================================================
+
+/// Command files under ~/.claude/commands, by name
+#[tauri::command]
+pub fn list_claude_slash_commands() -> Result<Vec<ClaudeSlashCommand>, String> {
+    Ok(list_in(&commands_dir()?))
+}
+
+/// Create or edit a command file; a changed name (from `original_name`) renames it
+#[tauri::command]
+pub fn save_claude_slash_command(
+    app: tauri::AppHandle,
+    input: ClaudeSlashCommandInput,
+    original_name: Option<String>,
+) -> Result<ClaudeSlashCommand, String> {
+    let dir = commands_dir()?;
+    let command = write_command(&dir, &input)?;
+    if let Some(original) = original_name.filter(|n| *n != input.name) {
+        remove_command(&dir, &original)?;
+    }
+    emit_change(&app, "claude_slash_command", ChangeAction::Updated, Some(&command.name), "window");
+    Ok(command)
+}
+
+#[tauri::command]
+pub fn delete_claude_slash_command(app: tauri::AppHandle, name: String) -> Result<(), String> {
+    remove_command(&commands_dir()?, &name)?;
+    emit_change(&app, "claude_slash_command", ChangeAction::Deleted, Some(&name), "window");
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn list_claude_command_templates(
+    state: tauri::State<'_, DbState>,
+) -> Result<Vec<ClaudeCommandTemplate>, String> {
+    let db = state.0.lock().await;
+    load_templates(&db).await
+}
+
+/// Create (without `id`) or update a command template
+#[tauri::command]
+pub async fn save_claude_command_template(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: Option<String>,
+    command: ClaudeSlashCommandInput,
+) -> Result<ClaudeCommandTemplate, String> {
+    validate_name(&command.name)?;
+    let id = id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let db = state.0.lock().await;
+    save_template_record(&db, &id, &command).await?;
+    let template = load_templates(&db)
+        .await?
+        .into_iter()
+        .find(|t| t.id == id)
+        .ok_or_else(|| format!("Command template not found: {}", id))?;
+    emit_change(&app, "claude_command_template", ChangeAction::Updated, Some(&id), "window");
+    Ok(template)
+}
+
+#[tauri::command]
+pub async fn delete_claude_command_template(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+) -> Result<(), String> {
+    let db = state.0.lock().await;
+    db.query("DELETE type::thing('claude_command_template', $id)")
+        .bind(("id", id.clone()))
+        .await
+        .map_err(|e| format!("Failed to delete command template: {}", e))?;
+    emit_change(&app, "claude_command_template", ChangeAction::Deleted, Some(&id), "window");
+    Ok(())
+}
+
+/// Write a template to ~/.claude/commands, optionally under another name
+#[tauri::command]
+pub async fn install_claude_command_template(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+    name: Option<String>,
+) -> Result<ClaudeSlashCommand, String> {
+    let template = {
+        let db = state.0.lock().await;
+        load_templates(&db)
+            .await?
+            .into_iter()
+            .find(|t| t.id == id)
+            .ok_or_else(|| format!("Command template not found: {}", id))?
+    };
+    let mut input = template.command;
+    if let Some(name) = name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()) {
+        input.name = name;
+    }
+    let command = write_command(&commands_dir()?, &input)?;
+    emit_change(&app, "claude_slash_command", ChangeAction::Created, Some(&command.name), "window");
+    Ok(command)
+}
+
+/// Write command files (all, or those in `names`) and every template to a JSON bundle
+#[tauri::command]
+pub async fn export_claude_slash_commands(
+    state: tauri::State<'_, DbState>,
+    path: String,
+    names: Option<Vec<String>>,
+) -> Result<(), String> {
+    let commands = list_in(&commands_dir()?)
+        .into_iter()
+        .filter(|c| names.as_ref().is_none_or(|names| names.contains(&c.name)))
+        .map(ClaudeSlashCommandInput::from)
+        .collect();
+    let templates = {
+        let db = state.0.lock().await;
+        load_templates(&db).await?.into_iter().map(|t| t.command).collect()
+    };
+    let bundle = ClaudeCommandBundle {
+        version: BUNDLE_VERSION,
+        commands,
+        templates,
+    };
+    let json = serde_json::to_string_pretty(&bundle).map_err(|e| format!("Failed to serialize commands: {}", e))?;
+    fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path, e))
+}
+
+/// Import a bundle from `export_claude_slash_commands`; existing command files
+/// and templates with the same name are kept unless `overwrite` is set
+#[tauri::command]
+pub async fn import_claude_slash_commands(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    path: String,
+    overwrite: Option<bool>,
+) -> Result<ClaudeCommandImportResult, String> {
+    let overwrite = overwrite.unwrap_or(false);
+    let content = fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let bundle: ClaudeCommandBundle =
+        serde_json::from_str(&content).map_err(|e| format!("Invalid command bundle: {}", e))?;
+
+    let mut result = ClaudeCommandImportResult::default();
+    let dir = commands_dir()?;
+    for command in &bundle.commands {
+        if validate_name(&command.name).is_err() || (!overwrite && command_path(&dir, &command.name).exists()) {
+            result.skipped.push(command.name.clone());
+            continue;
+        }
+        write_command(&dir, command)?;
+        result.commands += 1;
+    }
+
+    let db = state.0.lock().await;
+    let existing = load_templates(&db).await?;
+    for template in &bundle.templates {
+        if validate_name(&template.name).is_err() {
+            result.skipped.push(template.name.clone());
+            continue;
+        }
+        let id = match existing.iter().find(|t| t.command.name == template.name) {
+            Some(_) if !overwrite => {
+                result.skipped.push(template.name.clone());
+                continue;
+            }
+            Some(found) => found.id.clone(),
+            None => uuid::Uuid::new_v4().to_string(),
+        };
+        save_template_record(&db, &id, template).await?;
+        result.templates += 1;
+    }
+
+    emit_change(&app, "claude_slash_command", ChangeAction::Imported, None, "window");
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(name: &str) -> ClaudeSlashCommandInput {
+        ClaudeSlashCommandInput {
+            name: name.to_string(),
+            description: Some("Review the diff".to_string()),
+            allowed_tools: Some("Bash(git diff:*)".to_string()),
+            argument_hint: None,
+            model: None,
+            content: "Review $ARGUMENTS".to_string(),
+        }
+    }
+
+    #[test]
+    fn writes_lists_and_removes_namespaced_commands() {
+        let dir = std::env::temp_dir().join(format!("aitb-commands-{}", uuid::Uuid::new_v4()));
+        let path = command_path(&dir, "git/review");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, render(&input("git/review")).unwrap()).unwrap();
+
+        let command = read_command(&dir, &path).unwrap();
+        assert_eq!(command.name, "git/review");
+        assert_eq!(command.allowed_tools.as_deref(), Some("Bash(git diff:*)"));
+        assert_eq!(command.content, "Review $ARGUMENTS\n");
+        assert!(fs::read_to_string(dir.join("git").join("review.md"))
+            .unwrap()
+            .contains("allowed-tools: Bash(git diff:*)"));
+
+        assert_eq!(list_in(&dir).len(), 1);
+        remove_command(&dir, "git/review").unwrap();
+        assert!(list_in(&dir).is_empty());
+        assert!(!dir.join("git").exists());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn rejects_names_escaping_the_directory() {
+        assert!(validate_name("review").is_ok());
+        assert!(validate_name("team/review-pr").is_ok());
+        assert!(validate_name("../settings").is_err());
+        assert!(validate_name("a//b").is_err());
+        assert!(validate_name("").is_err());
+    }
+}
@@ -0,0 +1,133 @@
+//! Claude provider template gallery
+//!
+//! A curated list of relay / vendor presets is downloaded from
+//! `provider_template_url` (or the repository copy by default). When the
+//! download fails or does not parse, the list bundled at build time is used.
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::db::DbState;
+use crate::error::AppResult;
+use crate::http_client;
+use super::types::{ClaudeProviderTemplate, ClaudeProviderTemplateList};
+
+const BUNDLED_TEMPLATES_JSON: &str = include_str!("../../../resources/claude_provider_templates.json");
+
+const DEFAULT_TEMPLATES_URL: &str =
+    "https://raw.githubusercontent.com/coulsontl/ai-toolbox/main/tauri/resources/claude_provider_templates.json";
+const FETCH_TIMEOUT_SECS: u64 = 15;
+
+#[derive(Deserialize)]
+struct TemplateFile {
+    templates: Vec<ClaudeProviderTemplate>,
+}
+
+/// Templates of a gallery file; entries without a name or an http(s) base URL are dropped
+fn parse_templates(content: &str) -> Result<Vec<ClaudeProviderTemplate>, String> {
+    let file: TemplateFile =
+        serde_json::from_str(content).map_err(|e| format!("Invalid template list: {}", e))?;
+    let templates: Vec<ClaudeProviderTemplate> = file
+        .templates
+        .into_iter()
+        .filter(|t| !t.id.trim().is_empty() && !t.name.trim().is_empty())
+        .filter(|t| t.base_url.starts_with("https://") || t.base_url.starts_with("http://"))
+        .collect();
+    if templates.is_empty() {
+        return Err("Template list is empty".to_string());
+    }
+    Ok(templates)
+}
+
+pub fn bundled_templates() -> Vec<ClaudeProviderTemplate> {
+    parse_templates(BUNDLED_TEMPLATES_JSON).unwrap_or_else(|e| {
+        log::warn!("Failed to parse bundled provider templates: {}", e);
+        Vec::new()
+    })
+}
+
+async fn template_url(state: &DbState) -> String {
+    let db = state.0.lock().await;
+    let records: Vec<Value> = db
+        .query("SELECT provider_template_url FROM settings:`app` LIMIT 1")
+        .await
+        .and_then(|mut result| result.take(0))
+        .unwrap_or_default();
+    records
+        .first()
+        .and_then(|record| record.get("provider_template_url"))
+        .and_then(|url| url.as_str())
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+        .unwrap_or(DEFAULT_TEMPLATES_URL)
+        .to_string()
+}
+
+async fn fetch_remote(state: &DbState) -> Result<Vec<ClaudeProviderTemplate>, String> {
+    let client = http_client::client_with_timeout(state, FETCH_TIMEOUT_SECS).await?;
+    let url = http_client::resolve_url(state, &template_url(state).await).await;
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch provider templates: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to fetch provider templates: HTTP {}", response.status().as_u16()));
+    }
+    let content = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read provider templates: {}", e))?;
+    parse_templates(&content)
+}
+
+// ============================================================================
+// Claude Provider Template Commands
+// ============================================================================
+
+/// Provider presets from the template gallery, falling back to the bundled list
+#[tauri::command]
+pub async fn fetch_claude_provider_templates(
+    state: tauri::State<'_, DbState>,
+) -> AppResult<ClaudeProviderTemplateList> {
+    match fetch_remote(&state).await {
+        Ok(templates) => Ok(ClaudeProviderTemplateList {
+            templates,
+            source: "remote".to_string(),
+            error: None,
+        }),
+        Err(e) => {
+            log::warn!("{}", e);
+            Ok(ClaudeProviderTemplateList {
+                templates: bundled_templates(),
+                source: "bundled".to_string(),
+                error: Some(e),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_templates_parse() {
+        let templates = bundled_templates();
+        assert!(templates.iter().any(|t| t.id == "anthropic"));
+        assert!(templates.iter().all(|t| t.base_url.starts_with("https://")));
+    }
+
+    #[test]
+    fn invalid_entries_are_dropped() {
+        let templates = parse_templates(
+            r#"{ "templates": [
+                { "id": "a", "name": "A", "category": "third_party", "baseUrl": "https://a.example.com" },
+                { "id": "b", "name": "B", "category": "third_party", "baseUrl": "ftp://b" }
+            ] }"#,
+        )
+        .unwrap();
+        assert_eq!(templates.len(), 1);
+        assert!(parse_templates(r#"{ "templates": [] }"#).is_err());
+    }
+}
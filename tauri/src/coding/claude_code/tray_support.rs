@@ -39,9 +39,9 @@ pub async fn get_claude_code_tray_data<R: Runtime>(
     let state = app.state::<DbState>();
     let db = state.0.lock().await;
 
-    // Query providers from database
+    // Query providers from database (archived ones stay out of the menu)
     let records_result: Result<Vec<Value>, _> = db
-        .query("SELECT *, type::string(id) as id FROM claude_provider")
+        .query("SELECT *, type::string(id) as id FROM claude_provider WHERE is_archived != true")
         .await
         .map_err(|e| format!("Failed to query providers: {}", e))?
         .take(0);
@@ -51,12 +51,14 @@ pub async fn get_claude_code_tray_data<R: Runtime>(
     match records_result {
         Ok(records) => {
             for record in records {
-                if let (Some(raw_id), Some(name), Some(is_applied), sort_index) = (
+                if let (Some(raw_id), Some(name), is_applied, sort_index) = (
                     record.get("id").and_then(|v| v.as_str()),
                     record.get("name").and_then(|v| v.as_str()),
+                    // Providers created before is_applied existed are listed as not applied
                     record.get("is_applied")
                         .or_else(|| record.get("isApplied"))
-                        .and_then(|v| v.as_bool()),
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false),
                     record
                         .get("sort_index")
                         .or_else(|| record.get("sortIndex"))
@@ -83,7 +85,7 @@ pub async fn get_claude_code_tray_data<R: Runtime>(
             }
         }
         Err(e) => {
-            eprintln!("Failed to deserialize providers for tray: {}", e);
+            log::warn!("Failed to deserialize providers for tray: {}", e);
         }
     }
 
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 use surrealdb::sql::Thing;
 
@@ -26,6 +28,8 @@ pub struct ClaudeCodeProviderRecord {
     pub sort_index: Option<i32>,
     pub is_applied: bool,
     pub is_disabled: bool,
+    #[serde(default)]
+    pub is_archived: bool,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -52,6 +56,9 @@ pub struct ClaudeCodeProvider {
     pub sort_index: Option<i32>,
     pub is_applied: bool,
     pub is_disabled: bool,
+    /// Kept but hidden from the active list
+    #[serde(default)]
+    pub is_archived: bool,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -71,6 +78,7 @@ impl From<ClaudeCodeProviderRecord> for ClaudeCodeProvider {
             sort_index: record.sort_index,
             is_applied: record.is_applied,
             is_disabled: record.is_disabled,
+            is_archived: record.is_archived,
             created_at: record.created_at,
             updated_at: record.updated_at,
         }
@@ -97,6 +105,8 @@ pub struct ClaudeCodeProviderContent {
     pub sort_index: Option<i32>,
     pub is_applied: bool,
     pub is_disabled: bool,
+    #[serde(default)]
+    pub is_archived: bool,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -124,6 +134,297 @@ pub struct ClaudeCodeProviderInput {
     pub sort_index: Option<i32>,
 }
 
+/// Filters and paging of `list_claude_providers`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeProviderQuery {
+    #[serde(default)]
+    pub category: Option<String>,
+    /// Case-insensitive match on name or notes
+    #[serde(default)]
+    pub search: Option<String>,
+    /// Only archived (true) or only active (false) providers
+    #[serde(default)]
+    pub archived: Option<bool>,
+    /// 1-based, used with `limit`
+    #[serde(default)]
+    pub page: Option<u32>,
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+/// Providers exported for sharing; with `redacted` the keys are `${FIELD}` placeholders
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeProviderBundle {
+    pub version: u32,
+    pub exported_at: String,
+    #[serde(default)]
+    pub redacted: bool,
+    pub providers: Vec<ClaudeCodeProviderInput>,
+}
+
+/// Result of importing a provider bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeProviderImportResult {
+    pub created: usize,
+    pub updated: usize,
+    /// Providers whose keys are still placeholders and need to be filled in
+    pub needs_secrets: Vec<String>,
+}
+
+/// Outcome of one row of a bulk import
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeBulkImportRow {
+    /// 1-based position in the input (the CSV line number for CSV)
+    pub row: usize,
+    pub name: String,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeBulkImportResult {
+    pub created: usize,
+    pub failed: usize,
+    pub rows: Vec<ClaudeBulkImportRow>,
+}
+
+/// One apply of a provider and the settings file it produced (see `apply_history`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeApplyHistoryEntry {
+    pub id: String,
+    #[serde(alias = "provider_id")]
+    pub provider_id: String,
+    #[serde(default, alias = "provider_name")]
+    pub provider_name: String,
+    /// Apply target at the time: "settings" or "local"
+    pub target: String,
+    /// File that was written
+    pub path: String,
+    /// SHA-256 of the written file
+    #[serde(alias = "settings_hash")]
+    pub settings_hash: String,
+    /// "window", "tray" or "revert"
+    pub source: String,
+    #[serde(default, alias = "created_at")]
+    pub created_at: String,
+}
+
+/// Snapshot of a provider, taken when it is updated or applied
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeProviderVersion {
+    #[serde(alias = "provider_id")]
+    pub provider_id: String,
+    /// Increases by one per snapshot of the provider
+    pub version: u32,
+    pub name: String,
+    pub category: String,
+    #[serde(alias = "settings_config")]
+    pub settings_config: String,
+    #[serde(default, alias = "website_url", skip_serializing_if = "Option::is_none")]
+    pub website_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    #[serde(default, alias = "icon_color", skip_serializing_if = "Option::is_none")]
+    pub icon_color: Option<String>,
+    /// "update", "apply" or "restore"
+    pub reason: String,
+    #[serde(default, alias = "created_at")]
+    pub created_at: String,
+}
+
+// ============================================================================
+// Claude Provider Template Types
+// ============================================================================
+
+/// Preset for creating a provider (relay / vendor endpoint)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeProviderTemplate {
+    pub id: String,
+    pub name: String,
+    pub category: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub website_url: Option<String>,
+    pub base_url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub haiku_model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sonnet_model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub opus_model: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeProviderTemplateList {
+    pub templates: Vec<ClaudeProviderTemplate>,
+    /// "remote" or "bundled"
+    pub source: String,
+    /// Why the remote list could not be used
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+// ============================================================================
+// Claude Failover Types
+// ============================================================================
+
+fn default_failover_interval() -> u64 {
+    300
+}
+
+fn default_failure_threshold() -> u32 {
+    3
+}
+
+/// Health monitoring of the applied provider and the failover order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeFailoverConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Seconds between health checks
+    #[serde(default = "default_failover_interval", alias = "interval_secs")]
+    pub interval_secs: u64,
+    /// Consecutive failed checks before switching
+    #[serde(default = "default_failure_threshold", alias = "failure_threshold")]
+    pub failure_threshold: u32,
+    /// Provider ids in failover order; after the last one it wraps around
+    #[serde(default)]
+    pub chain: Vec<String>,
+}
+
+impl Default for ClaudeFailoverConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_secs: default_failover_interval(),
+            failure_threshold: default_failure_threshold(),
+            chain: Vec::new(),
+        }
+    }
+}
+
+/// Latest health check result of a provider
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeProviderHealth {
+    #[serde(alias = "provider_id")]
+    pub provider_id: String,
+    #[serde(default, alias = "consecutive_failures")]
+    pub consecutive_failures: u32,
+    #[serde(default, alias = "last_checked_at")]
+    pub last_checked_at: String,
+    #[serde(default, alias = "last_ok_at", skip_serializing_if = "Option::is_none")]
+    pub last_ok_at: Option<String>,
+    #[serde(default, alias = "last_error", skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    #[serde(default, alias = "latency_ms", skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+}
+
+/// Payload of the `claude-provider-failover` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeFailoverEvent {
+    pub from_id: String,
+    pub from_name: String,
+    pub to_id: String,
+    pub to_name: String,
+    pub failures: u32,
+    pub error: String,
+}
+
+// ============================================================================
+// Claude CLI Types
+// ============================================================================
+
+/// Installation state of the `claude` binary
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeCliStatus {
+    pub installed: bool,
+    pub path: Option<String>,
+    pub version: Option<String>,
+    /// "npm", "bun", "native" or "unknown"
+    pub install_method: Option<String>,
+    /// Latest release on npm; None when the registry is unreachable
+    pub latest_version: Option<String>,
+    pub update_available: bool,
+    /// Package managers found on this system ("npm", "bun")
+    pub package_managers: Vec<String>,
+}
+
+/// Payload of the `claude-cli-install-progress` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeCliInstallProgress {
+    /// "stdout" or "stderr"
+    pub stream: String,
+    pub line: String,
+}
+
+// ============================================================================
+// Claude Shell Profile Types
+// ============================================================================
+
+/// Outcome of writing / removing the managed env block in one shell profile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeShellProfileResult {
+    /// "zsh", "bash" or "powershell"
+    pub shell: String,
+    pub path: String,
+    /// False when the profile already had this content
+    pub changed: bool,
+}
+
+// ============================================================================
+// Claude Config Drift Types
+// ============================================================================
+
+/// One key whose live value differs from what the applied provider produces
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeConfigDriftEntry {
+    /// Dot path, e.g. `env.ANTHROPIC_BASE_URL`
+    pub key: String,
+    /// "changed", "missing" (expected but absent) or "unexpected" (present but not expected)
+    pub kind: String,
+    /// Secret-looking values are masked
+    pub expected: Option<serde_json::Value>,
+    pub actual: Option<serde_json::Value>,
+}
+
+/// Result of `check_claude_config_drift`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeConfigDrift {
+    /// Applied provider; None when no provider is applied (nothing to compare)
+    pub provider_id: Option<String>,
+    pub provider_name: Option<String>,
+    /// Whether settings.json exists
+    pub file_exists: bool,
+    pub in_sync: bool,
+    pub entries: Vec<ClaudeConfigDriftEntry>,
+}
+
 // ============================================================================
 // ClaudeCode Common Config Types
 // ============================================================================
@@ -164,10 +465,327 @@ pub struct ClaudeSettings {
     pub other: serde_json::Map<String, serde_json::Value>,
 }
 
+/// Copy of settings.json taken before a provider apply
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeSettingsBackup {
+    /// File stem, e.g. "settings-20260301-101500-123"
+    pub id: String,
+    pub path: String,
+    pub created_at: String,
+    pub size: u64,
+}
+
+// ============================================================================
+// Claude Hook Types
+// ============================================================================
+
+fn default_hook_type() -> String {
+    "command".to_string()
+}
+
+/// One hook command, as in settings.json `hooks.<event>[].hooks[]`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClaudeHookCommand {
+    #[serde(rename = "type", default = "default_hook_type")]
+    pub kind: String,
+    pub command: String,
+    /// Seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u32>,
+}
+
+/// Commands run for the tools matching `matcher` (empty for events without tools)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClaudeHookMatcher {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub matcher: String,
+    pub hooks: Vec<ClaudeHookCommand>,
+}
+
+/// Reusable hook set, merged into settings.json on apply while enabled
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeHookSet {
+    pub id: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub enabled: bool,
+    /// Event name ("PreToolUse", "PostToolUse", "Notification", …) -> matchers
+    #[serde(default)]
+    pub hooks: BTreeMap<String, Vec<ClaudeHookMatcher>>,
+    #[serde(default, alias = "sort_index", skip_serializing_if = "Option::is_none")]
+    pub sort_index: Option<i32>,
+    #[serde(default, alias = "created_at")]
+    pub created_at: String,
+    #[serde(default, alias = "updated_at")]
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeHookSetInput {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub hooks: BTreeMap<String, Vec<ClaudeHookMatcher>>,
+    #[serde(default)]
+    pub sort_index: Option<i32>,
+}
+
+// ============================================================================
+// Claude Permission Types
+// ============================================================================
+
+/// The `permissions` block of settings.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudePermissions {
+    /// Tool rules such as `Bash(npm run test:*)`, `Read(~/.zshrc)` or `WebFetch`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ask: Vec<String>,
+    /// "default", "acceptEdits", "plan" or "bypassPermissions"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_mode: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub additional_directories: Vec<String>,
+}
+
+/// Reusable permission preset, merged into settings.json on apply while enabled
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudePermissionPreset {
+    pub id: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub permissions: ClaudePermissions,
+    #[serde(default, alias = "sort_index", skip_serializing_if = "Option::is_none")]
+    pub sort_index: Option<i32>,
+    #[serde(default, alias = "created_at")]
+    pub created_at: String,
+    #[serde(default, alias = "updated_at")]
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudePermissionPresetInput {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub permissions: ClaudePermissions,
+    #[serde(default)]
+    pub sort_index: Option<i32>,
+}
+
+// ============================================================================
+// Claude Slash Command Types
+// ============================================================================
+
+/// Command file under ~/.claude/commands
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeSlashCommand {
+    /// Path below the commands directory without ".md", e.g. "git/review"
+    pub name: String,
+    pub description: Option<String>,
+    pub allowed_tools: Option<String>,
+    pub argument_hint: Option<String>,
+    pub model: Option<String>,
+    /// Prompt after the front matter
+    pub content: String,
+    pub path: String,
+    pub updated_at: String,
+}
+
+/// Command file contents; also the body of a command template
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeSlashCommandInput {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub allowed_tools: Option<String>,
+    #[serde(default)]
+    pub argument_hint: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub content: String,
+}
+
+impl From<ClaudeSlashCommand> for ClaudeSlashCommandInput {
+    fn from(command: ClaudeSlashCommand) -> Self {
+        Self {
+            name: command.name,
+            description: command.description,
+            allowed_tools: command.allowed_tools,
+            argument_hint: command.argument_hint,
+            model: command.model,
+            content: command.content,
+        }
+    }
+}
+
+/// Reusable command stored in the database
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeCommandTemplate {
+    pub id: String,
+    pub command: ClaudeSlashCommandInput,
+    #[serde(default, alias = "created_at")]
+    pub created_at: String,
+    #[serde(default, alias = "updated_at")]
+    pub updated_at: String,
+}
+
+/// Export file of slash commands and templates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaudeCommandBundle {
+    pub version: u32,
+    #[serde(default)]
+    pub commands: Vec<ClaudeSlashCommandInput>,
+    #[serde(default)]
+    pub templates: Vec<ClaudeSlashCommandInput>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeCommandImportResult {
+    pub commands: usize,
+    pub templates: usize,
+    /// Names that already existed (without overwrite) or were invalid
+    pub skipped: Vec<String>,
+}
+
+// ============================================================================
+// Claude Agent Types
+// ============================================================================
+
+/// Sub-agent definition file under ~/.claude/agents
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeAgent {
+    pub name: String,
+    /// File name without ".md" (usually equal to `name`)
+    pub file_name: String,
+    pub description: String,
+    /// Empty = all tools
+    pub tools: Vec<String>,
+    pub model: Option<String>,
+    /// System prompt after the front matter
+    pub prompt: String,
+    pub path: String,
+    pub updated_at: String,
+}
+
+/// Agent file contents; also the body of an agent preset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeAgentInput {
+    pub name: String,
+    pub description: String,
+    #[serde(default)]
+    pub tools: Vec<String>,
+    /// e.g. "sonnet", "opus", "inherit"
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub prompt: String,
+}
+
+/// Reusable agent stored in the database; enabled presets are written on apply
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeAgentPreset {
+    pub id: String,
+    pub agent: ClaudeAgentInput,
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default, alias = "created_at")]
+    pub created_at: String,
+    #[serde(default, alias = "updated_at")]
+    pub updated_at: String,
+}
+
+// ============================================================================
+// Claude Session Types
+// ============================================================================
+
+/// Session transcript under ~/.claude/projects
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeSessionSummary {
+    pub session_id: String,
+    pub title: String,
+    pub project: Option<String>,
+    pub started_at: Option<String>,
+    pub updated_at: String,
+    pub message_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeSessionExport {
+    pub path: String,
+    pub message_count: usize,
+}
+
 // ============================================================================
 // Claude Plugin Integration Types
 // ============================================================================
 
+/// Token limits written to the env block (see `limits`); unset ones are left alone
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeTokenLimits {
+    /// CLAUDE_CODE_MAX_OUTPUT_TOKENS
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u64>,
+    /// MAX_THINKING_TOKENS
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_thinking_tokens: Option<u64>,
+    /// MAX_MCP_OUTPUT_TOKENS
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_mcp_output_tokens: Option<u64>,
+}
+
+/// settings.local.json in the Claude config directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeLocalSettings {
+    pub path: String,
+    pub exists: bool,
+    /// File content; an empty object when missing
+    pub settings: serde_json::Value,
+}
+
+/// Resolved Claude config directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeConfigDirInfo {
+    pub path: String,
+    /// "custom" (app settings), "env" (CLAUDE_CONFIG_DIR) or "default" (~/.claude)
+    pub source: String,
+}
+
 /// ClaudePluginStatus - API response for plugin integration status
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -177,3 +795,40 @@ pub struct ClaudePluginStatus {
     /// Whether ~/.claude/config.json exists
     pub has_config_file: bool,
 }
+
+// ============================================================================
+// Claude Provider Test Types
+// ============================================================================
+
+/// Result of `test_claude_provider`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeProviderTestResult {
+    pub success: bool,
+    /// HTTP status; None when no response was received
+    pub status: Option<u16>,
+    pub latency_ms: u64,
+    /// Model that answered, as reported by the response
+    pub model: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Latest batch test result of one provider (see `batch_test`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeProviderTestEntry {
+    pub provider_id: String,
+    pub provider_name: String,
+    #[serde(flatten)]
+    pub result: ClaudeProviderTestResult,
+    pub tested_at: String,
+}
+
+/// Progress of `test_all_claude_providers`, emitted as each provider finishes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeProviderTestProgress {
+    pub done: usize,
+    pub total: usize,
+    pub entry: ClaudeProviderTestEntry,
+}
@@ -0,0 +1,115 @@
+//! CLI binary detection helpers
+//!
+//! Locates coding tool executables (cursor-agent, goose, crush, ...) on PATH and in
+//! common install directories, and reads their reported version.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+/// Windows CREATE_NO_WINDOW flag to prevent console window from appearing
+#[cfg(target_os = "windows")]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// Detected CLI binary
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliBinaryInfo {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+/// Install directories (relative to home) checked in addition to PATH.
+/// GUI apps on macOS don't inherit the shell PATH, so these matter there.
+const EXTRA_BIN_DIRS: [&str; 6] = [
+    ".local/bin",
+    ".cargo/bin",
+    ".bun/bin",
+    ".npm-global/bin",
+    "bin",
+    "AppData/Roaming/npm",
+];
+
+const SYSTEM_BIN_DIRS: [&str; 3] = ["/usr/local/bin", "/opt/homebrew/bin", "/usr/bin"];
+
+fn executable_names(name: &str) -> Vec<String> {
+    if cfg!(target_os = "windows") {
+        vec![
+            format!("{}.exe", name),
+            format!("{}.cmd", name),
+            name.to_string(),
+        ]
+    } else {
+        vec![name.to_string()]
+    }
+}
+
+/// Find the first executable matching any of `names`
+pub fn find_binary(names: &[&str]) -> Option<PathBuf> {
+    let mut dirs: Vec<PathBuf> = std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).collect())
+        .unwrap_or_default();
+    if let Some(home) = dirs::home_dir() {
+        dirs.extend(EXTRA_BIN_DIRS.iter().map(|d| home.join(d)));
+    }
+    dirs.extend(SYSTEM_BIN_DIRS.iter().map(PathBuf::from));
+
+    for name in names {
+        for dir in &dirs {
+            for exe in executable_names(name) {
+                let candidate = dir.join(&exe);
+                if candidate.is_file() {
+                    return Some(candidate);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Run `<binary> --version` and return the first version-looking token
+pub fn get_binary_version(path: &Path) -> Option<String> {
+    let mut cmd = Command::new(path);
+    cmd.arg("--version")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(target_os = "windows")]
+    cmd.creation_flags(CREATE_NO_WINDOW);
+
+    let output = cmd.output().ok()?;
+    let text = if output.stdout.is_empty() {
+        String::from_utf8_lossy(&output.stderr).to_string()
+    } else {
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    parse_version(&text)
+}
+
+/// Extract a version number (e.g. "1.2.3") from command output
+fn parse_version(text: &str) -> Option<String> {
+    text.split_whitespace()
+        .map(|token| token.trim_start_matches('v').trim_end_matches(','))
+        .find(|token| {
+            token.contains('.') && token.chars().next().is_some_and(|c| c.is_ascii_digit())
+        })
+        .map(|s| s.to_string())
+        .or_else(|| {
+            let line = text.lines().next()?.trim();
+            (!line.is_empty()).then(|| line.to_string())
+        })
+}
+
+/// Detect a CLI binary and its version
+pub fn detect_cli(names: &[&str]) -> Option<CliBinaryInfo> {
+    let path = find_binary(names)?;
+    Some(CliBinaryInfo {
+        version: get_binary_version(&path),
+        path: path.to_string_lossy().to_string(),
+    })
+}
@@ -0,0 +1,219 @@
+//! Installed CLI scan
+//!
+//! Periodically (and on demand) checks which supported coding CLIs are on the
+//! system. A CLI that shows up after the first scan emits `cli-detected` so the
+//! frontend can suggest enabling its module; every result is stored in the
+//! `cli_detection` table for the doctor report.
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::coding::cli_detect;
+use crate::coding::db_extract_id;
+use crate::db::DbState;
+use crate::error::{AppError, AppResult};
+
+type Db = surrealdb::Surreal<surrealdb::engine::local::Db>;
+
+/// Event emitted with the newly installed CLIs
+pub const CLI_DETECTED_EVENT: &str = "cli-detected";
+
+/// A supported CLI: tool key, display name, binary names and the module to enable
+struct KnownCli {
+    tool: &'static str,
+    name: &'static str,
+    binaries: &'static [&'static str],
+    module: Option<&'static str>,
+}
+
+const KNOWN_CLIS: [KnownCli; 14] = [
+    KnownCli { tool: "claude", name: "Claude Code", binaries: &["claude"], module: Some("claude") },
+    KnownCli { tool: "opencode", name: "OpenCode", binaries: &["opencode"], module: Some("opencode") },
+    KnownCli { tool: "codex", name: "Codex", binaries: &["codex"], module: Some("codex") },
+    KnownCli { tool: "gemini", name: "Gemini CLI", binaries: &["gemini"], module: Some("gemini_cli") },
+    KnownCli { tool: "qwen", name: "Qwen Code", binaries: &["qwen"], module: Some("qwen_code") },
+    KnownCli { tool: "iflow", name: "iFlow CLI", binaries: &["iflow"], module: Some("iflow") },
+    KnownCli { tool: "droid", name: "Droid", binaries: &["droid"], module: Some("droid") },
+    KnownCli { tool: "copilot", name: "GitHub Copilot CLI", binaries: &["copilot"], module: Some("github_copilot") },
+    KnownCli { tool: "goose", name: "Goose", binaries: &["goose"], module: Some("goose") },
+    KnownCli { tool: "crush", name: "Crush", binaries: &["crush"], module: Some("crush") },
+    KnownCli { tool: "cursor-agent", name: "Cursor CLI", binaries: &["cursor-agent"], module: Some("cursor") },
+    KnownCli { tool: "amp", name: "Amp", binaries: &["amp"], module: Some("amp") },
+    KnownCli { tool: "ccr", name: "Claude Code Router", binaries: &["ccr"], module: Some("ccr") },
+    KnownCli { tool: "aider", name: "Aider", binaries: &["aider"], module: None },
+];
+
+/// Scan result of one CLI
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CliDetection {
+    pub tool: String,
+    pub name: String,
+    /// Module suggested for enabling, if the app has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub module: Option<String>,
+    pub installed: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// When the CLI was first seen installed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_seen: Option<String>,
+    pub last_checked: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CliScanResult {
+    pub detections: Vec<CliDetection>,
+    /// CLIs that were not installed at the previous scan
+    pub newly_installed: Vec<CliDetection>,
+}
+
+fn detect_all(previous: &[CliDetection], now: &str) -> Vec<CliDetection> {
+    KNOWN_CLIS
+        .iter()
+        .map(|cli| {
+            let binary = cli_detect::detect_cli(cli.binaries);
+            let first_seen = previous
+                .iter()
+                .find(|p| p.tool == cli.tool && p.installed)
+                .and_then(|p| p.first_seen.clone())
+                .or_else(|| binary.as_ref().map(|_| now.to_string()));
+            CliDetection {
+                tool: cli.tool.to_string(),
+                name: cli.name.to_string(),
+                module: cli.module.map(String::from),
+                installed: binary.is_some(),
+                path: binary.as_ref().map(|b| b.path.clone()),
+                version: binary.and_then(|b| b.version),
+                first_seen,
+                last_checked: now.to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Installed now but not at the previous scan; the very first scan is only a baseline
+pub fn newly_installed(previous: &[CliDetection], current: &[CliDetection]) -> Vec<CliDetection> {
+    if previous.is_empty() {
+        return Vec::new();
+    }
+    current
+        .iter()
+        .filter(|c| c.installed)
+        .filter(|c| !previous.iter().any(|p| p.tool == c.tool && p.installed))
+        .cloned()
+        .collect()
+}
+
+fn from_db_value(mut value: Value) -> Option<CliDetection> {
+    let tool = db_extract_id(&value);
+    let map = value.as_object_mut()?;
+    map.remove("id");
+    map.insert("tool".to_string(), Value::String(tool));
+    serde_json::from_value(value).ok()
+}
+
+async fn load_detections(db: &Db) -> AppResult<Vec<CliDetection>> {
+    let records: Vec<Value> = db
+        .query("SELECT *, type::string(id) as id FROM cli_detection")
+        .await?
+        .take(0)?;
+    Ok(records.into_iter().filter_map(from_db_value).collect())
+}
+
+async fn save_detections(db: &Db, detections: &[CliDetection]) -> AppResult<()> {
+    for detection in detections {
+        let mut data = serde_json::to_value(detection).map_err(AppError::invalid)?;
+        if let Some(map) = data.as_object_mut() {
+            map.remove("tool");
+        }
+        db.query("UPSERT type::thing('cli_detection', $id) CONTENT $data")
+            .bind(("id", detection.tool.clone()))
+            .bind(("data", data))
+            .await?;
+    }
+    Ok(())
+}
+
+/// Scan for installed CLIs, store the results and announce new ones
+pub async fn scan(app: &AppHandle) -> AppResult<CliScanResult> {
+    let state = app.state::<DbState>();
+    let previous = {
+        let db = state.0.lock().await;
+        load_detections(&db).await?
+    };
+
+    let now = Local::now().to_rfc3339();
+    let baseline = previous.clone();
+    let detections = tauri::async_runtime::spawn_blocking(move || detect_all(&baseline, &now))
+        .await
+        .map_err(AppError::io)?;
+    let newly_installed = newly_installed(&previous, &detections);
+
+    {
+        let db = state.0.lock().await;
+        save_detections(&db, &detections).await?;
+    }
+
+    if !newly_installed.is_empty() {
+        let _ = app.emit(CLI_DETECTED_EVENT, &newly_installed);
+        for cli in &newly_installed {
+            crate::activity_log::record(app, "tool", format!("检测到新安装的 {}", cli.name));
+        }
+    }
+
+    Ok(CliScanResult {
+        detections,
+        newly_installed,
+    })
+}
+
+// ============================================================================
+// CLI Scan Commands
+// ============================================================================
+
+/// Scan now instead of waiting for the periodic check
+#[tauri::command]
+pub async fn scan_installed_clis(app: AppHandle) -> AppResult<CliScanResult> {
+    scan(&app).await
+}
+
+/// Results of the last scan, for the doctor report
+#[tauri::command]
+pub async fn list_cli_detections(state: tauri::State<'_, DbState>) -> AppResult<Vec<CliDetection>> {
+    let db = state.0.lock().await;
+    let mut detections = load_detections(&db).await?;
+    detections.sort_by(|a, b| b.installed.cmp(&a.installed).then_with(|| a.name.cmp(&b.name)));
+    Ok(detections)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detection(tool: &str, installed: bool) -> CliDetection {
+        CliDetection {
+            tool: tool.to_string(),
+            name: tool.to_string(),
+            module: None,
+            installed,
+            path: None,
+            version: None,
+            first_seen: None,
+            last_checked: String::new(),
+        }
+    }
+
+    #[test]
+    fn newly_installed_skips_baseline_and_known() {
+        let current = vec![detection("claude", true), detection("codex", true), detection("aider", false)];
+        assert!(newly_installed(&[], &current).is_empty());
+
+        let previous = vec![detection("claude", true), detection("codex", false), detection("aider", false)];
+        let tools: Vec<String> = newly_installed(&previous, &current).into_iter().map(|c| c.tool).collect();
+        assert_eq!(tools, vec!["codex"]);
+    }
+}
@@ -0,0 +1,291 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde_json::{Map, Value};
+
+use crate::coding::change_history;
+use crate::coding::claude_code::adapter as claude_adapter;
+use crate::coding::cli_detect;
+use crate::db::DbState;
+use super::types::*;
+
+/// Cline keeps separate provider/model selections for plan and act mode
+const CLINE_MODES: [&str; 2] = ["planMode", "actMode"];
+
+// ============================================================================
+// Cline Path Helpers
+// ============================================================================
+
+/// Get the Cline CLI state directory (~/.cline/data)
+///
+/// The CLI keeps its settings in `globalState.json` and its keys in `secrets.json`
+/// here. The VS Code extension uses the editor's own (encrypted) storage instead
+/// and is not configured by the toolbox.
+fn get_cline_data_dir() -> Result<PathBuf, String> {
+    let home_dir = std::env::var("USERPROFILE")
+        .or_else(|_| std::env::var("HOME"))
+        .map_err(|_| "Failed to get home directory".to_string())?;
+
+    Ok(PathBuf::from(home_dir).join(".cline").join("data"))
+}
+
+fn read_json_object(path: &Path) -> Result<Map<String, Value>, String> {
+    if !path.exists() {
+        return Ok(Map::new());
+    }
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    if content.trim().is_empty() {
+        return Ok(Map::new());
+    }
+    match serde_json::from_str::<Value>(&content) {
+        Ok(Value::Object(obj)) => Ok(obj),
+        Ok(_) => Err(format!("Invalid format in {}", path.display())),
+        Err(e) => Err(format!("Failed to parse {}: {}", path.display(), e)),
+    }
+}
+
+fn write_json_object(path: &Path, obj: &Map<String, Value>) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(obj)
+        .map_err(|e| format!("Failed to serialize: {}", e))?;
+    change_history::write_config("cline", path, content)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Keys used by Cline for a given provider: (base url key, api key secret, model id suffix)
+fn provider_keys(api_provider: &str) -> (Option<&'static str>, Option<&'static str>, &'static str) {
+    match api_provider {
+        "anthropic" => (Some("anthropicBaseUrl"), Some("apiKey"), "ApiModelId"),
+        "openai" => (Some("openAiBaseUrl"), Some("openAiApiKey"), "OpenAiModelId"),
+        "openrouter" => (None, Some("openRouterApiKey"), "OpenRouterModelId"),
+        "ollama" => (Some("ollamaBaseUrl"), None, "OllamaModelId"),
+        "lmstudio" => (Some("lmStudioBaseUrl"), None, "LmStudioModelId"),
+        "litellm" => (Some("liteLlmBaseUrl"), Some("liteLlmApiKey"), "LiteLlmModelId"),
+        _ => (None, None, "ApiModelId"),
+    }
+}
+
+// ============================================================================
+// Cline Commands
+// ============================================================================
+
+/// Detect the Cline CLI and its state directory
+#[tauri::command]
+pub fn get_cline_status() -> Result<ClineStatus, String> {
+    let data_dir = get_cline_data_dir()?;
+    Ok(ClineStatus {
+        binary: cli_detect::detect_cli(&["cline"]),
+        data_exists: data_dir.exists(),
+        data_dir: data_dir.to_string_lossy().to_string(),
+    })
+}
+
+/// Read the current Cline CLI API configuration (act mode)
+#[tauri::command]
+pub fn read_cline_api_config() -> Result<Option<ClineApiConfig>, String> {
+    let data_dir = get_cline_data_dir()?;
+    let state = read_json_object(&data_dir.join("globalState.json"))?;
+    let secrets = read_json_object(&data_dir.join("secrets.json"))?;
+
+    let Some(api_provider) = state
+        .get("actModeApiProvider")
+        .or_else(|| state.get("apiProvider"))
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+    else {
+        return Ok(None);
+    };
+
+    let (base_url_key, api_key_key, model_suffix) = provider_keys(&api_provider);
+    let get_str = |obj: &Map<String, Value>, key: &str| {
+        obj.get(key).and_then(|v| v.as_str()).map(|s| s.to_string())
+    };
+
+    Ok(Some(ClineApiConfig {
+        base_url: base_url_key.and_then(|k| get_str(&state, k)),
+        api_key: api_key_key.and_then(|k| get_str(&secrets, k)),
+        model_id: get_str(&state, &format!("actMode{}", model_suffix)),
+        api_provider,
+    }))
+}
+
+/// Set the provider, model, base URL and key of `config` in Cline's state and
+/// secrets (both plan and act mode)
+fn set_api_config(
+    config: &ClineApiConfig,
+    state: &mut Map<String, Value>,
+    secrets: &mut Map<String, Value>,
+) {
+    let (base_url_key, api_key_key, model_suffix) = provider_keys(&config.api_provider);
+
+    for mode in CLINE_MODES {
+        state.insert(
+            format!("{}ApiProvider", mode),
+            Value::String(config.api_provider.clone()),
+        );
+        if let Some(model) = config.model_id.as_ref().filter(|m| !m.is_empty()) {
+            state.insert(format!("{}{}", mode, model_suffix), Value::String(model.clone()));
+        }
+    }
+
+    if let Some(key) = base_url_key {
+        match config.base_url.as_ref().filter(|u| !u.is_empty()) {
+            Some(url) => {
+                state.insert(key.to_string(), Value::String(url.clone()));
+            }
+            None => {
+                state.remove(key);
+            }
+        }
+    }
+
+    if let (Some(key), Some(api_key)) = (api_key_key, config.api_key.as_ref()) {
+        secrets.insert(key.to_string(), Value::String(api_key.clone()));
+    }
+}
+
+/// Write API configuration to the Cline CLI state files (both plan and act mode)
+/// With `dry_run` the diffs of the files are returned instead.
+#[tauri::command]
+pub fn apply_cline_api_config(
+    config: ClineApiConfig,
+    dry_run: Option<bool>,
+) -> Result<Vec<change_history::ConfigDiff>, String> {
+    let data_dir = get_cline_data_dir()?;
+    let state_path = data_dir.join("globalState.json");
+    let secrets_path = data_dir.join("secrets.json");
+
+    let mut state = read_json_object(&state_path)?;
+    let mut secrets = read_json_object(&secrets_path)?;
+    set_api_config(&config, &mut state, &mut secrets);
+
+    change_history::preview_sync(dry_run.unwrap_or(false), || {
+        write_json_object(&state_path, &state)?;
+        write_json_object(&secrets_path, &secrets)
+    })
+}
+
+/// Apply a Claude Code provider from the toolbox to the Cline CLI (anthropic provider)
+#[tauri::command]
+pub async fn apply_claude_provider_to_cline(
+    state: tauri::State<'_, DbState>,
+    provider_id: String,
+    dry_run: Option<bool>,
+) -> Result<Vec<change_history::ConfigDiff>, String> {
+    let db = state.0.lock().await;
+
+    let records_result: Result<Vec<Value>, _> = db
+        .query("SELECT *, type::string(id) as id FROM claude_provider WHERE id = type::thing('claude_provider', $id) LIMIT 1")
+        .bind(("id", provider_id.clone()))
+        .await
+        .map_err(|e| format!("Failed to query provider: {}", e))?
+        .take(0);
+
+    let provider = match records_result {
+        Ok(records) => records
+            .first()
+            .map(|record| claude_adapter::from_db_value_provider(record.clone()))
+            .ok_or_else(|| format!("Claude Code provider '{}' not found", provider_id))?,
+        Err(e) => return Err(format!("Failed to deserialize provider: {}", e)),
+    };
+    drop(db);
+
+    let mut settings: Value = serde_json::from_str(&provider.settings_config)
+        .map_err(|e| format!("Failed to parse provider config: {}", e))?;
+    crate::coding::vault::resolve_references(&mut settings)?;
+    let env = settings.get("env");
+    let get_env = |key: &str| {
+        env.and_then(|e| e.get(key))
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+    };
+
+    apply_cline_api_config(
+        ClineApiConfig {
+            api_provider: "anthropic".to_string(),
+            base_url: get_env("ANTHROPIC_BASE_URL"),
+            api_key: get_env("ANTHROPIC_AUTH_TOKEN").or_else(|| get_env("ANTHROPIC_API_KEY")),
+            model_id: settings
+                .get("model")
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string()),
+        },
+        dry_run,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_file(content: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("aitb-cline-{}.json", uuid::Uuid::new_v4()));
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn sets_plan_and_act_mode_keys_for_the_provider() {
+        let mut state = Map::new();
+        let mut secrets = Map::new();
+        state.insert("openAiBaseUrl".to_string(), Value::String("https://old.example.com".to_string()));
+        let config = ClineApiConfig {
+            api_provider: "openai".to_string(),
+            base_url: None,
+            api_key: Some("sk-relay".to_string()),
+            model_id: Some("gpt-4o".to_string()),
+        };
+
+        set_api_config(&config, &mut state, &mut secrets);
+
+        for mode in CLINE_MODES {
+            assert_eq!(state[&format!("{}ApiProvider", mode)], "openai");
+            assert_eq!(state[&format!("{}OpenAiModelId", mode)], "gpt-4o");
+        }
+        assert!(!state.contains_key("openAiBaseUrl"));
+        assert_eq!(secrets["openAiApiKey"], "sk-relay");
+    }
+
+    #[test]
+    fn anthropic_uses_api_model_id_and_keeps_other_state() {
+        let mut state = Map::new();
+        let mut secrets = Map::new();
+        state.insert("telemetrySetting".to_string(), Value::String("disabled".to_string()));
+        let config = ClineApiConfig {
+            api_provider: "anthropic".to_string(),
+            base_url: Some("https://relay.example.com".to_string()),
+            api_key: None,
+            model_id: None,
+        };
+
+        set_api_config(&config, &mut state, &mut secrets);
+
+        assert_eq!(state["actModeApiProvider"], "anthropic");
+        assert_eq!(state["anthropicBaseUrl"], "https://relay.example.com");
+        assert!(!state.contains_key("actModeApiModelId"));
+        assert_eq!(state["telemetrySetting"], "disabled");
+        assert!(secrets.is_empty());
+    }
+
+    #[test]
+    fn read_json_object_treats_missing_and_empty_files_as_empty() {
+        let missing = std::env::temp_dir().join(format!("aitb-cline-{}.json", uuid::Uuid::new_v4()));
+        assert!(read_json_object(&missing).unwrap().is_empty());
+
+        let empty = temp_file("  \n");
+        assert!(read_json_object(&empty).unwrap().is_empty());
+        fs::remove_file(&empty).unwrap();
+    }
+
+    #[test]
+    fn read_json_object_rejects_non_objects() {
+        let path = temp_file(r#"{"apiKey":"sk-1"}"#);
+        assert_eq!(read_json_object(&path).unwrap()["apiKey"], "sk-1");
+        fs::write(&path, "[1, 2]").unwrap();
+        assert!(read_json_object(&path).unwrap_err().contains("Invalid format"));
+        fs::write(&path, "{not json").unwrap();
+        assert!(read_json_object(&path).unwrap_err().contains("Failed to parse"));
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+use crate::coding::cli_detect::CliBinaryInfo;
+
+// ============================================================================
+// Cline Types
+// ============================================================================
+
+/// Cline CLI detection result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClineStatus {
+    /// `cline` binary, None when the CLI is not installed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub binary: Option<CliBinaryInfo>,
+    /// Cline CLI state directory (~/.cline/data)
+    pub data_dir: String,
+    pub data_exists: bool,
+}
+
+/// Cline API configuration (subset managed by the toolbox)
+///
+/// `api_provider` follows Cline's provider ids: "anthropic" | "openai" | "openrouter" | ...
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClineApiConfig {
+    pub api_provider: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_id: Option<String>,
+}
@@ -2,18 +2,28 @@ use std::fs;
 use std::path::Path;
 use serde_json::Value;
 
+use crate::coding::change_history;
+use crate::coding::config_format;
 use crate::db::DbState;
 use super::adapter;
 use super::types::*;
 use tauri::Emitter;
 use chrono::Local;
+use crate::events::{emit_change, ChangeAction};
 
 // ============================================================================
 // Codex Config Path Commands
 // ============================================================================
 
-/// Get Codex config directory path (~/.codex/)
+/// Get Codex config directory path
+/// Respects CODEX_HOME (same as the Codex CLI), otherwise ~/.codex/
 fn get_codex_config_dir() -> Result<std::path::PathBuf, String> {
+    if let Ok(codex_home) = std::env::var("CODEX_HOME") {
+        if !codex_home.trim().is_empty() {
+            return Ok(std::path::PathBuf::from(codex_home.trim()));
+        }
+    }
+
     let home_dir = std::env::var("USERPROFILE")
         .or_else(|_| std::env::var("HOME"))
         .map_err(|_| "Failed to get home directory".to_string())?;
@@ -22,12 +32,12 @@ fn get_codex_config_dir() -> Result<std::path::PathBuf, String> {
 }
 
 /// Get Codex auth.json path
-fn get_codex_auth_path() -> Result<std::path::PathBuf, String> {
+pub(crate) fn get_codex_auth_path() -> Result<std::path::PathBuf, String> {
     Ok(get_codex_config_dir()?.join("auth.json"))
 }
 
 /// Get Codex config.toml path
-fn get_codex_config_path() -> Result<std::path::PathBuf, String> {
+pub(crate) fn get_codex_config_path() -> Result<std::path::PathBuf, String> {
     Ok(get_codex_config_dir()?.join("config.toml"))
 }
 
@@ -120,7 +130,7 @@ match records_result {
             }
         }
         Err(e) => {
-            eprintln!("Failed to deserialize providers: {}", e);
+            log::warn!("Failed to deserialize providers: {}", e);
             // Try to load from local files as fallback
             if let Ok(temp_provider) = load_temp_provider_from_files().await {
                 return Ok(vec![temp_provider]);
@@ -239,7 +249,7 @@ pub async fn create_codex_provider(
         .take(0);
 
     // Notify to refresh tray menu
-    let _ = app.emit("config-changed", "window");
+    emit_change(&app, "codex_provider", ChangeAction::Created, None, "window");
 
     match result {
         Ok(records) => {
@@ -332,12 +342,12 @@ pub async fn update_codex_provider(
     // If this provider is applied, re-apply to config file
     if content.is_applied {
         if let Err(e) = apply_config_to_file(&db, &id).await {
-            eprintln!("Failed to auto-apply updated config: {}", e);
+            log::warn!("Failed to auto-apply updated config: {}", e);
         }
     }
 
     // Notify frontend and tray to refresh
-    let _ = app.emit("config-changed", "window");
+    emit_change(&app, "codex_provider", ChangeAction::Updated, None, "window");
 
         Ok(CodexProvider {
             id,
@@ -370,7 +380,7 @@ pub async fn delete_codex_provider(
         .await
         .map_err(|e| format!("Failed to delete codex provider: {}", e))?;
 
-    let _ = app.emit("config-changed", "window");
+    emit_change(&app, "codex_provider", ChangeAction::Deleted, None, "window");
     Ok(())
 }
 
@@ -441,7 +451,7 @@ pub async fn select_codex_provider(
     let db = state.0.lock().await;
     update_is_applied_status(&db, &id).await?;
 
-    let _ = app.emit("config-changed", "window");
+    emit_change(&app, "codex_provider", ChangeAction::Applied, None, "window");
     Ok(())
 }
 
@@ -511,9 +521,10 @@ pub async fn apply_config_to_file_public(
         return Err(format!("Provider '{}' is disabled and cannot be applied", provider_id));
     }
 
-    // Parse provider settings_config
-    let provider_config: serde_json::Value = serde_json::from_str(&provider.settings_config)
+    // Parse provider settings_config, resolving vault:// key references in auth
+    let mut provider_config: serde_json::Value = serde_json::from_str(&provider.settings_config)
         .map_err(|e| format!("Failed to parse provider config: {}", e))?;
+    crate::coding::vault::resolve_references(&mut provider_config)?;
 
     // Get common config
     let common_config_result: Result<Vec<Value>, _> = db
@@ -582,7 +593,7 @@ fn write_codex_config_files(auth: &serde_json::Value, config_toml: &str) -> Resu
     let auth_path = config_dir.join("auth.json");
     let auth_content = serde_json::to_string_pretty(auth)
         .map_err(|e| format!("Failed to serialize auth: {}", e))?;
-    fs::write(&auth_path, auth_content)
+    change_history::write_config("codex", &auth_path, auth_content)
         .map_err(|e| format!("Failed to write auth.json: {}", e))?;
 
     // Write config.toml with partial update (preserve mcp_servers)
@@ -594,29 +605,13 @@ fn write_codex_config_files(auth: &serde_json::Value, config_toml: &str) -> Resu
 
 /// Write config.toml while preserving mcp_servers and other unrelated fields
 fn write_codex_config_toml_preserve_mcp(config_path: &std::path::Path, new_config: &str) -> Result<(), String> {
-    use toml_edit::DocumentMut;
-
     // Parse new config
-    let new_doc: DocumentMut = if new_config.trim().is_empty() {
-        DocumentMut::new()
-    } else {
-        new_config.parse()
-            .map_err(|e| format!("Failed to parse new config: {}", e))?
-    };
+    let new_doc = config_format::toml::parse(new_config)
+        .map_err(|e| format!("Invalid new config: {}", e))?;
 
-    // Read existing config (if exists)
-    let mut existing_doc: DocumentMut = if config_path.exists() {
-        let content = fs::read_to_string(config_path)
-            .map_err(|e| format!("Failed to read config.toml: {}", e))?;
-        if content.trim().is_empty() {
-            DocumentMut::new()
-        } else {
-            content.parse()
-                .map_err(|e| format!("Failed to parse existing config.toml: {}", e))?
-        }
-    } else {
-        DocumentMut::new()
-    };
+    // Read existing config (missing file is an empty document)
+    let mut existing_doc = config_format::toml::parse(&config_format::read_file(config_path)?)
+        .map_err(|e| format!("Invalid existing config.toml: {}", e))?;
 
     // Preserve mcp_servers from existing config
     let preserved_mcp = existing_doc.get("mcp_servers").cloned();
@@ -640,21 +635,24 @@ fn write_codex_config_toml_preserve_mcp(config_path: &std::path::Path, new_confi
     } else {
         format!("#:schema none\n{}", doc_content)
     };
-    fs::write(config_path, final_content)
-        .map_err(|e| format!("Failed to write config.toml: {}", e))?;
-
-    Ok(())
+    config_format::write_file("codex", config_path, &final_content)
 }
 
 /// Apply Codex config to files
+/// `dry_run` returns the diff of the files instead of writing them.
 #[tauri::command]
 pub async fn apply_codex_config(
     state: tauri::State<'_, DbState>,
     app: tauri::AppHandle,
     provider_id: String,
-) -> Result<(), String> {
+    dry_run: Option<bool>,
+) -> Result<Vec<change_history::ConfigDiff>, String> {
     let db = state.0.lock().await;
-    apply_config_internal(&db, &app, &provider_id, false).await
+    if dry_run.unwrap_or(false) {
+        return change_history::preview(true, apply_config_to_file(&db, &provider_id)).await;
+    }
+    apply_config_internal(&db, &app, &provider_id, false).await?;
+    Ok(Vec::new())
 }
 
 /// Toggle is_disabled status for a provider
@@ -716,9 +714,24 @@ pub async fn apply_config_internal<R: tauri::Runtime>(
 
     // Update is_applied status using DELETE + CREATE pattern
     update_is_applied_status(db, provider_id).await?;
+    crate::coding::switch_analytics::record_switch(db, "codex_provider", provider_id).await;
+
+    // Record in recent actions
+    let provider_name: Option<String> = db
+        .query("SELECT VALUE name FROM codex_provider WHERE id = type::thing('codex_provider', $id) LIMIT 1")
+        .bind(("id", provider_id.to_string()))
+        .await
+        .ok()
+        .and_then(|mut result| result.take::<Vec<String>>(0).ok())
+        .and_then(|names| names.into_iter().next());
+    crate::activity_log::record(
+        app,
+        "provider",
+        format!("Codex 已切换到 {}", provider_name.as_deref().unwrap_or(provider_id)),
+    );
 
     let payload = if from_tray { "tray" } else { "window" };
-    let _ = app.emit("config-changed", payload);
+    emit_change(app, "codex_provider", ChangeAction::Applied, None, payload);
 
     // Trigger WSL sync via event (Windows only)
     #[cfg(target_os = "windows")]
@@ -752,6 +765,116 @@ pub async fn read_codex_settings() -> Result<CodexSettings, String> {
     Ok(CodexSettings { auth, config })
 }
 
+// ============================================================================
+// Codex Model Provider Profile Commands
+// ============================================================================
+
+/// Parse model provider profiles from config.toml content
+pub(crate) fn parse_model_providers(config_toml: &str) -> Result<CodexModelProvidersInfo, String> {
+    if config_toml.trim().is_empty() {
+        return Ok(CodexModelProvidersInfo::default());
+    }
+
+    let table: toml::Table = toml::from_str(config_toml)
+        .map_err(|e| format!("Invalid TOML: {}", e))?;
+
+    let get_str = |t: &toml::Table, key: &str| {
+        t.get(key).and_then(|v| v.as_str()).map(|s| s.to_string())
+    };
+
+    let providers = table
+        .get("model_providers")
+        .and_then(|v| v.as_table())
+        .map(|providers| {
+            providers
+                .iter()
+                .filter_map(|(key, value)| {
+                    let entry = value.as_table()?;
+                    Some(CodexModelProviderProfile {
+                        key: key.clone(),
+                        name: get_str(entry, "name"),
+                        base_url: get_str(entry, "base_url"),
+                        env_key: get_str(entry, "env_key"),
+                        wire_api: get_str(entry, "wire_api"),
+                        requires_openai_auth: entry
+                            .get("requires_openai_auth")
+                            .and_then(|v| v.as_bool())
+                            .unwrap_or(false),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(CodexModelProvidersInfo {
+        model_provider: get_str(&table, "model_provider"),
+        model: get_str(&table, "model"),
+        providers,
+    })
+}
+
+/// Get model provider profiles
+/// Parses the given config TOML, or the current config.toml when not provided
+#[tauri::command]
+pub async fn get_codex_model_providers(
+    config: Option<String>,
+) -> Result<CodexModelProvidersInfo, String> {
+    let content = match config {
+        Some(config) => config,
+        None => {
+            let config_path = get_codex_config_path()?;
+            if !config_path.exists() {
+                return Ok(CodexModelProvidersInfo::default());
+            }
+            fs::read_to_string(&config_path)
+                .map_err(|e| format!("Failed to read config.toml: {}", e))?
+        }
+    };
+
+    parse_model_providers(&content)
+}
+
+/// Build provider config TOML from a model provider profile
+/// Produces `model_provider`/`model` plus the `[model_providers.<key>]` table
+#[tauri::command]
+pub fn build_codex_provider_config(
+    profile: CodexModelProviderProfile,
+    model: Option<String>,
+) -> Result<String, String> {
+    use toml_edit::{value, DocumentMut, Item, Table};
+
+    let key = profile.key.trim();
+    if key.is_empty() {
+        return Err("Model provider key is required".to_string());
+    }
+
+    let mut doc = DocumentMut::new();
+    doc["model_provider"] = value(key);
+    if let Some(model) = model.filter(|m| !m.trim().is_empty()) {
+        doc["model"] = value(model);
+    }
+
+    let mut entry = Table::new();
+    entry["name"] = value(profile.name.unwrap_or_else(|| key.to_string()));
+    if let Some(base_url) = profile.base_url {
+        entry["base_url"] = value(base_url);
+    }
+    if let Some(env_key) = profile.env_key {
+        entry["env_key"] = value(env_key);
+    }
+    entry["wire_api"] = value(profile.wire_api.unwrap_or_else(|| "responses".to_string()));
+    if profile.requires_openai_auth {
+        entry["requires_openai_auth"] = value(true);
+    }
+
+    let mut providers = Table::new();
+    providers.set_implicit(true);
+    providers.insert(key, Item::Table(entry));
+    doc["model_providers"] = Item::Table(providers);
+
+    Ok(doc.to_string())
+}
+
 // ============================================================================
 // Codex Common Config Commands
 // ============================================================================
@@ -781,7 +904,7 @@ pub async fn get_codex_common_config(
         }
         Err(e) => {
             // 反序列化失败，删除旧数据以修复版本冲突
-            eprintln!("⚠️ Codex common config has incompatible format, cleaning up: {}", e);
+            log::warn!("Codex common config has incompatible format, cleaning up: {}", e);
             let _ = db.query("DELETE codex_common_config:`common`").await;
             Ok(None)
         }
@@ -822,13 +945,13 @@ pub async fn save_codex_common_config(
         if let Some(record) = records.first() {
             let provider = adapter::from_db_value_provider(record.clone());
             if let Err(e) = apply_config_to_file(&db, &provider.id).await {
-                eprintln!("Failed to re-apply config: {}", e);
+                log::warn!("Failed to re-apply config: {}", e);
             }
         }
     }
 
     // Emit config-changed event to notify frontend
-    let _ = app.emit("config-changed", "window");
+    emit_change(&app, "codex_common_config", ChangeAction::Updated, None, "window");
 
     Ok(())
 }
@@ -916,12 +1039,12 @@ pub async fn save_codex_local_config(
         if let Some(record) = records.first() {
             let created_provider = adapter::from_db_value_provider(record.clone());
             if let Err(e) = apply_config_to_file(&db, &created_provider.id).await {
-                eprintln!("Failed to apply config after local save: {}", e);
+                log::warn!("Failed to apply config after local save: {}", e);
             }
         }
     }
 
-    let _ = app.emit("config-changed", "window");
+    emit_change(&app, "codex_local_config", ChangeAction::Updated, None, "window");
     Ok(())
 }
 
@@ -1004,6 +1127,166 @@ pub async fn init_codex_provider_from_settings(
         .await
         .map_err(|e| format!("Failed to create provider: {}", e))?;
 
-    println!("✅ Imported Codex settings as default provider");
+    log::info!("Imported Codex settings as default provider");
     Ok(())
 }
+
+// ============================================================================
+// Provider Registration Helpers
+// ============================================================================
+
+/// Create or update a Codex provider by name with the given settings_config
+/// (`{ auth, config }` JSON). Returns the provider id
+pub async fn upsert_codex_provider_by_name(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    name: &str,
+    settings_config: String,
+) -> Result<String, String> {
+    let find_by_name = || async move {
+        let records: Vec<Value> = db
+            .query("SELECT *, type::string(id) as id FROM codex_provider WHERE name = $name LIMIT 1")
+            .bind(("name", name.to_string()))
+            .await
+            .map_err(|e| format!("Failed to query provider: {}", e))?
+            .take(0)
+            .map_err(|e| format!("Failed to query provider: {}", e))?;
+        Ok::<_, String>(records.into_iter().next().map(adapter::from_db_value_provider))
+    };
+
+    let now = Local::now().to_rfc3339();
+    if let Some(provider) = find_by_name().await? {
+        db.query("UPDATE type::thing('codex_provider', $id) SET settings_config = $settings_config, updated_at = $updated_at")
+            .bind(("id", provider.id.clone()))
+            .bind(("settings_config", settings_config))
+            .bind(("updated_at", now))
+            .await
+            .map_err(|e| format!("Failed to update provider: {}", e))?;
+        return Ok(provider.id);
+    }
+
+    let content = CodexProviderContent {
+        name: name.to_string(),
+        category: "custom".to_string(),
+        settings_config,
+        source_provider_id: None,
+        website_url: None,
+        notes: None,
+        icon: None,
+        icon_color: None,
+        sort_index: None,
+        is_applied: false,
+        is_disabled: false,
+        created_at: now.clone(),
+        updated_at: now,
+    };
+    db.query("CREATE codex_provider CONTENT $data")
+        .bind(("data", adapter::to_db_value_provider(&content)))
+        .await
+        .map_err(|e| format!("Failed to create provider: {}", e))?;
+
+    find_by_name()
+        .await?
+        .map(|provider| provider.id)
+        .ok_or_else(|| "Failed to retrieve created provider".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"model_provider = "relay"
+model = "gpt-5"
+approval_policy = "on-request"
+
+[model_providers.relay]
+name = "Relay"
+base_url = "https://relay.example.com/v1"
+env_key = "RELAY_API_KEY"
+wire_api = "chat"
+requires_openai_auth = true
+
+[model_providers.local]
+base_url = "http://localhost:11434/v1"
+
+[mcp_servers.docs]
+command = "docs-mcp"
+"#;
+
+    fn profile<'a>(info: &'a CodexModelProvidersInfo, key: &str) -> &'a CodexModelProviderProfile {
+        info.providers.iter().find(|p| p.key == key).unwrap()
+    }
+
+    #[test]
+    fn test_parse_model_providers() {
+        let info = parse_model_providers(CONFIG).unwrap();
+        assert_eq!(info.model_provider.as_deref(), Some("relay"));
+        assert_eq!(info.model.as_deref(), Some("gpt-5"));
+        assert_eq!(info.providers.len(), 2);
+
+        let relay = profile(&info, "relay");
+        assert_eq!(relay.env_key.as_deref(), Some("RELAY_API_KEY"));
+        assert_eq!(relay.wire_api.as_deref(), Some("chat"));
+        assert!(relay.requires_openai_auth);
+
+        let local = profile(&info, "local");
+        assert_eq!(local.name, None);
+        assert_eq!(local.wire_api, None);
+        assert!(!local.requires_openai_auth);
+    }
+
+    #[test]
+    fn test_build_provider_config_round_trip() {
+        let info = parse_model_providers(CONFIG).unwrap();
+        let relay = profile(&info, "relay").clone();
+        let built = build_codex_provider_config(relay, Some("gpt-5".to_string())).unwrap();
+        let rebuilt = parse_model_providers(&built).unwrap();
+
+        assert_eq!(rebuilt.model_provider.as_deref(), Some("relay"));
+        assert_eq!(rebuilt.model.as_deref(), Some("gpt-5"));
+        assert_eq!(rebuilt.providers.len(), 1);
+        let relay = profile(&rebuilt, "relay");
+        assert_eq!(relay.name.as_deref(), Some("Relay"));
+        assert_eq!(relay.base_url.as_deref(), Some("https://relay.example.com/v1"));
+        assert_eq!(relay.wire_api.as_deref(), Some("chat"));
+        assert!(relay.requires_openai_auth);
+    }
+
+    #[test]
+    fn test_build_provider_config_defaults() {
+        let info = parse_model_providers(CONFIG).unwrap();
+        let built = build_codex_provider_config(profile(&info, "local").clone(), None).unwrap();
+        let table: toml::Table = toml::from_str(&built).unwrap();
+        let entry = table["model_providers"]["local"].as_table().unwrap();
+
+        assert!(table.get("model").is_none());
+        assert_eq!(entry["name"].as_str(), Some("local"));
+        assert_eq!(entry["wire_api"].as_str(), Some("responses"));
+        assert!(entry.get("requires_openai_auth").is_none());
+    }
+
+    #[test]
+    fn test_build_provider_config_preserves_unrelated_keys() {
+        let dir = std::env::temp_dir().join(format!("aitb-codex-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let config_path = dir.join("config.toml");
+        fs::write(&config_path, CONFIG).unwrap();
+
+        let info = parse_model_providers(CONFIG).unwrap();
+        let local = profile(&info, "local").clone();
+        let built = build_codex_provider_config(local, Some("qwen3".to_string())).unwrap();
+        let diffs = change_history::preview_sync(true, || {
+            write_codex_config_toml_preserve_mcp(&config_path, &built)
+        })
+        .unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let table: toml::Table = toml::from_str(&diffs[0].after).unwrap();
+        assert_eq!(table["approval_policy"].as_str(), Some("on-request"));
+        assert_eq!(table["mcp_servers"]["docs"]["command"].as_str(), Some("docs-mcp"));
+        assert_eq!(table["model_provider"].as_str(), Some("local"));
+        assert_eq!(table["model"].as_str(), Some("qwen3"));
+
+        let merged = parse_model_providers(&diffs[0].after).unwrap();
+        assert_eq!(profile(&merged, "local").wire_api.as_deref(), Some("responses"));
+    }
+}
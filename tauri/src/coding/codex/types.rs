@@ -166,3 +166,40 @@ pub struct CodexSettings {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub config: Option<String>,
 }
+
+// ============================================================================
+// Codex Model Provider Profile Types
+// ============================================================================
+
+/// A `[model_providers.<key>]` entry from config.toml
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexModelProviderProfile {
+    /// Table key under model_providers (referenced by `model_provider = "<key>"`)
+    pub key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// Environment variable holding the API key
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env_key: Option<String>,
+    /// "chat" or "responses"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wire_api: Option<String>,
+    #[serde(default)]
+    pub requires_openai_auth: bool,
+}
+
+/// Model provider profiles parsed from a config.toml, plus the active selection
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexModelProvidersInfo {
+    /// Value of top-level `model_provider`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_provider: Option<String>,
+    /// Value of top-level `model`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    pub providers: Vec<CodexModelProviderProfile>,
+}
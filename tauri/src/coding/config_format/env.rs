@@ -0,0 +1,108 @@
+//! Minimal `.env` file helpers
+//!
+//! Gemini CLI, Qwen Code and custom tools read provider credentials from a
+//! dotenv file. These helpers update individual keys while keeping comments,
+//! blank lines and unrelated variables intact.
+
+use serde_json::{Map, Value};
+
+/// Parse dotenv content into ordered key-value pairs.
+/// Supports `export KEY=value`, single/double quoted values and `#` comments.
+pub fn parse_env(content: &str) -> Vec<(String, String)> {
+    content
+        .lines()
+        .filter_map(parse_env_line)
+        .collect()
+}
+
+/// Parse dotenv content into a JSON map (for API responses)
+pub fn parse_env_to_map(content: &str) -> Map<String, Value> {
+    parse_env(content)
+        .into_iter()
+        .map(|(k, v)| (k, Value::String(v)))
+        .collect()
+}
+
+fn parse_env_line(line: &str) -> Option<(String, String)> {
+    let trimmed = line.trim();
+    if trimmed.is_empty() || trimmed.starts_with('#') {
+        return None;
+    }
+    let trimmed = trimmed.strip_prefix("export ").unwrap_or(trimmed);
+    let (key, value) = trimmed.split_once('=')?;
+    let key = key.trim();
+    if key.is_empty() {
+        return None;
+    }
+    Some((key.to_string(), unquote(value.trim())))
+}
+
+fn unquote(value: &str) -> String {
+    let bytes = value.as_bytes();
+    if bytes.len() >= 2
+        && ((bytes[0] == b'"' && bytes[bytes.len() - 1] == b'"')
+            || (bytes[0] == b'\'' && bytes[bytes.len() - 1] == b'\''))
+    {
+        return value[1..value.len() - 1].to_string();
+    }
+    // Strip trailing inline comment for unquoted values
+    match value.find(" #") {
+        Some(pos) => value[..pos].trim_end().to_string(),
+        None => value.to_string(),
+    }
+}
+
+fn quote_if_needed(value: &str) -> String {
+    if value.is_empty()
+        || value.contains(char::is_whitespace)
+        || value.contains('#')
+        || value.contains('"')
+        || value.contains('\'')
+    {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Update dotenv content.
+///
+/// - Keys in `managed_keys` that are not present in `values` are removed
+/// - Keys in `values` are replaced in place, or appended if missing
+/// - Everything else (comments, unrelated keys) is preserved
+pub fn update_env(content: &str, values: &[(String, String)], managed_keys: &[&str]) -> String {
+    let mut written: Vec<&str> = Vec::new();
+    let mut lines: Vec<String> = Vec::new();
+
+    for line in content.lines() {
+        match parse_env_line(line) {
+            Some((key, _)) => {
+                if let Some((k, v)) = values.iter().find(|(k, _)| *k == key) {
+                    if !written.contains(&k.as_str()) {
+                        lines.push(format!("{}={}", k, quote_if_needed(v)));
+                        written.push(k.as_str());
+                    }
+                } else if !managed_keys.contains(&key.as_str()) {
+                    lines.push(line.to_string());
+                }
+            }
+            None => lines.push(line.to_string()),
+        }
+    }
+
+    for (k, v) in values {
+        if !written.contains(&k.as_str()) {
+            lines.push(format!("{}={}", k, quote_if_needed(v)));
+        }
+    }
+
+    // Drop trailing blank lines, keep a single newline at EOF
+    while lines.last().map(|l| l.trim().is_empty()).unwrap_or(false) {
+        lines.pop();
+    }
+    if lines.is_empty() {
+        String::new()
+    } else {
+        format!("{}\n", lines.join("\n"))
+    }
+}
@@ -0,0 +1,62 @@
+//! JSON / JSONC key-path edits on `serde_json` values
+
+use serde_json::{Map, Value};
+
+use super::{split_path, ConfigFormat};
+
+/// Parse JSON (or JSONC with comments and trailing commas); empty content is an empty object
+pub fn parse(content: &str, format: ConfigFormat) -> Result<Value, String> {
+    if content.trim().is_empty() {
+        return Ok(Value::Object(Map::new()));
+    }
+    if format == ConfigFormat::Jsonc {
+        json5::from_str(content).map_err(|e| format!("Failed to parse JSONC: {}", e))
+    } else {
+        serde_json::from_str(content).map_err(|e| format!("Failed to parse JSON: {}", e))
+    }
+}
+
+pub fn get_value(root: &Value, path: &str) -> Option<String> {
+    let mut current = root;
+    for key in split_path(path) {
+        current = current.get(key)?;
+    }
+    match current {
+        Value::String(s) => Some(s.clone()),
+        Value::Null => None,
+        other => Some(other.to_string()),
+    }
+}
+
+/// Set (or with `None` remove) a string value by key path, creating parent objects
+pub fn set_value(root: &mut Value, path: &str, value: Option<&str>) -> Result<(), String> {
+    let keys = split_path(path);
+    let Some((last, parents)) = keys.split_last() else {
+        return Err(format!("Invalid key path: {}", path));
+    };
+
+    let mut current = root;
+    for key in parents {
+        if !current.is_object() {
+            *current = Value::Object(Map::new());
+        }
+        current = current
+            .as_object_mut()
+            .unwrap()
+            .entry(key.to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+    }
+    if !current.is_object() {
+        *current = Value::Object(Map::new());
+    }
+    let map = current.as_object_mut().unwrap();
+    match value {
+        Some(value) => {
+            map.insert(last.to_string(), Value::String(value.to_string()));
+        }
+        None => {
+            map.remove(*last);
+        }
+    }
+    Ok(())
+}
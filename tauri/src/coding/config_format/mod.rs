@@ -0,0 +1,228 @@
+//! Config File Formats
+//!
+//! Shared read / targeted-edit / write layer for the config files tool modules
+//! manage, so a new integration does not reimplement file handling:
+//!
+//! - Structured formats (JSON, JSONC, TOML, YAML) are addressed by
+//!   dot-separated key paths (`provider.baseUrl`), dotenv files by variable name
+//! - TOML and dotenv edits keep comments and layout; YAML and JSON are
+//!   re-serialized (their parsers drop comments)
+//! - `write_file` replaces the file atomically and records the change history
+
+pub mod env;
+pub mod json;
+pub mod toml;
+pub mod yaml;
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::coding::change_history;
+
+/// Supported config file formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Json,
+    Jsonc,
+    Toml,
+    Yaml,
+    Env,
+}
+
+impl ConfigFormat {
+    /// Parse a format name: "json", "jsonc", "toml", "yaml" / "yml" or "env"
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "jsonc" | "json5" => Some(Self::Jsonc),
+            "toml" => Some(Self::Toml),
+            "yaml" | "yml" => Some(Self::Yaml),
+            "env" | "dotenv" => Some(Self::Env),
+            _ => None,
+        }
+    }
+
+    /// Guess the format from a file name (`.env`, `config.toml`, ...)
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+        if name == ".env" || name.starts_with(".env.") {
+            return Some(Self::Env);
+        }
+        Self::parse(path.extension()?.to_str()?)
+    }
+}
+
+/// Split a dot-separated key path, ignoring empty segments
+pub fn split_path(path: &str) -> Vec<&str> {
+    path.split('.').filter(|s| !s.is_empty()).collect()
+}
+
+/// Read a value by key path (non-string values are returned as their JSON text)
+pub fn get_value(content: &str, format: ConfigFormat, path: &str) -> Result<Option<String>, String> {
+    match format {
+        ConfigFormat::Env => Ok(env::parse_env(content)
+            .into_iter()
+            .find(|(key, _)| key == path)
+            .map(|(_, value)| value)),
+        ConfigFormat::Toml => toml::get_value(content, path),
+        ConfigFormat::Yaml => yaml::get_value(content, path),
+        ConfigFormat::Json | ConfigFormat::Jsonc => {
+            Ok(json::get_value(&json::parse(content, format)?, path))
+        }
+    }
+}
+
+/// Apply targeted edits and return the new content.
+/// `None` removes the key; everything not listed is kept.
+pub fn set_values(
+    content: &str,
+    format: ConfigFormat,
+    edits: &[(&str, Option<&str>)],
+) -> Result<String, String> {
+    match format {
+        ConfigFormat::Env => {
+            let values: Vec<(String, String)> = edits
+                .iter()
+                .filter_map(|(key, value)| Some((key.to_string(), value?.to_string())))
+                .collect();
+            let removed: Vec<&str> = edits
+                .iter()
+                .filter(|(_, value)| value.is_none())
+                .map(|(key, _)| *key)
+                .collect();
+            Ok(env::update_env(content, &values, &removed))
+        }
+        ConfigFormat::Toml => toml::set_values(content, edits),
+        ConfigFormat::Yaml => yaml::set_values(content, edits),
+        ConfigFormat::Json | ConfigFormat::Jsonc => {
+            let mut root = json::parse(content, format)?;
+            for (path, value) in edits {
+                json::set_value(&mut root, path, *value)?;
+            }
+            serde_json::to_string_pretty(&root).map_err(|e| format!("Failed to serialize JSON: {}", e))
+        }
+    }
+}
+
+/// Read a config file; a missing file is empty content
+pub fn read_file(path: &Path) -> Result<String, String> {
+    if !path.exists() {
+        return Ok(String::new());
+    }
+    fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+}
+
+/// Replace a file atomically: write a sibling temp file, then rename it over the target,
+/// so a crash, a full disk or a concurrent reader never sees a half-written config.
+/// A symlinked config (e.g. from a dotfiles repo) is written through to its target.
+pub fn atomic_write(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let target = match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_symlink() => fs::canonicalize(path)?,
+        _ => path.to_path_buf(),
+    };
+    let parent = target
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    fs::create_dir_all(parent)?;
+
+    let file_name = target.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    // Unique per write: two threads replacing the same file must not share a temp file
+    let temp_path = parent.join(format!(".{}.{}.tmp", file_name, uuid::Uuid::new_v4().simple()));
+    let result = (|| {
+        let mut file = fs::File::create(&temp_path)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        // Keep the permissions of the file being replaced (e.g. 0600 on auth files)
+        if let Ok(metadata) = fs::metadata(&target) {
+            fs::set_permissions(&temp_path, metadata.permissions())?;
+        }
+        fs::rename(&temp_path, &target)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+        return result;
+    }
+    // Persist the rename itself; best effort, not every platform can open a directory
+    #[cfg(unix)]
+    if let Ok(dir) = fs::File::open(parent) {
+        let _ = dir.sync_all();
+    }
+    Ok(())
+}
+
+/// Write a config file atomically and record it in the change history of `scope`
+pub fn write_file(scope: &str, path: &Path, contents: &str) -> Result<(), String> {
+    change_history::write_config(scope, path, contents)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Read, edit and write back a config file in one step
+pub fn edit_file(
+    scope: &str,
+    path: &Path,
+    format: ConfigFormat,
+    edits: &[(&str, Option<&str>)],
+) -> Result<(), String> {
+    let content = read_file(path)?;
+    let new_content = set_values(&content, format, edits)?;
+    if new_content == content {
+        return Ok(());
+    }
+    write_file(scope, path, &new_content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_values_edits_and_removes_per_format() {
+        let toml = "# keep\nmodel = \"a\"\n[provider]\nkey = \"x\"\n";
+        let result = set_values(toml, ConfigFormat::Toml, &[("model", Some("b")), ("provider.key", None)]).unwrap();
+        assert!(result.starts_with("# keep\nmodel = \"b\""));
+        assert_eq!(get_value(&result, ConfigFormat::Toml, "provider.key").unwrap(), None);
+
+        let yaml = set_values("name: x\n", ConfigFormat::Yaml, &[("models.default", Some("gpt-4o"))]).unwrap();
+        assert_eq!(get_value(&yaml, ConfigFormat::Yaml, "models.default").unwrap().as_deref(), Some("gpt-4o"));
+
+        let env = set_values("# c\nA=1\nB=2\n", ConfigFormat::Env, &[("A", None), ("C", Some("3"))]).unwrap();
+        assert_eq!(env, "# c\nB=2\nC=3\n");
+    }
+
+    #[test]
+    fn atomic_write_replaces_file() {
+        let dir = std::env::temp_dir().join(format!("config_format_test_{}", std::process::id()));
+        let path = dir.join("config.toml");
+        atomic_write(&path, b"a = 1\n").unwrap();
+        atomic_write(&path, b"a = 2\n").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a = 2\n");
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn atomic_write_keeps_symlinks() {
+        let dir = std::env::temp_dir().join(format!("config_format_link_test_{}", std::process::id()));
+        fs::create_dir_all(dir.join("dotfiles")).unwrap();
+        let real = dir.join("dotfiles").join("settings.json");
+        let link = dir.join("settings.json");
+        fs::write(&real, "{}").unwrap();
+        std::os::unix::fs::symlink(&real, &link).unwrap();
+
+        atomic_write(&link, b"{\"a\":1}").unwrap();
+        assert!(fs::symlink_metadata(&link).unwrap().file_type().is_symlink());
+        assert_eq!(fs::read_to_string(&real).unwrap(), "{\"a\":1}");
+        assert_eq!(fs::read_dir(dir.join("dotfiles")).unwrap().count(), 1);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn format_from_path() {
+        assert_eq!(ConfigFormat::from_path(Path::new("/home/u/.gemini/.env")), Some(ConfigFormat::Env));
+        assert_eq!(ConfigFormat::from_path(Path::new("config.yml")), Some(ConfigFormat::Yaml));
+        assert_eq!(ConfigFormat::from_path(Path::new("README")), None);
+    }
+}
@@ -0,0 +1,61 @@
+//! TOML key-path edits on a `toml_edit` document, so comments and layout survive
+
+use toml_edit::{value as toml_value, DocumentMut, Item, Table};
+
+use super::split_path;
+
+/// Parse TOML content (empty content is an empty document)
+pub fn parse(content: &str) -> Result<DocumentMut, String> {
+    content
+        .parse::<DocumentMut>()
+        .map_err(|e| format!("Failed to parse TOML: {}", e))
+}
+
+pub fn get_value(content: &str, path: &str) -> Result<Option<String>, String> {
+    let doc = parse(content)?;
+    let mut item = doc.as_item();
+    for key in split_path(path) {
+        match item.get(key) {
+            Some(next) => item = next,
+            None => return Ok(None),
+        }
+    }
+    Ok(match item {
+        Item::Value(value) => Some(
+            value
+                .as_str()
+                .map(String::from)
+                .unwrap_or_else(|| value.to_string().trim().to_string()),
+        ),
+        _ => None,
+    })
+}
+
+/// Set (or with `None` remove) string values by key path, creating parent tables
+pub fn set_values(content: &str, edits: &[(&str, Option<&str>)]) -> Result<String, String> {
+    let mut doc = parse(content)?;
+
+    for (path, value) in edits {
+        let keys = split_path(path);
+        let Some((last, parents)) = keys.split_last() else {
+            return Err(format!("Invalid key path: {}", path));
+        };
+
+        let mut table = doc.as_table_mut();
+        for key in parents {
+            let item = table.entry(key).or_insert(Item::Table(Table::new()));
+            if !item.is_table() {
+                *item = Item::Table(Table::new());
+            }
+            table = item.as_table_mut().unwrap();
+        }
+        match value {
+            Some(value) => table[*last] = toml_value(*value),
+            None => {
+                table.remove(last);
+            }
+        }
+    }
+
+    Ok(doc.to_string())
+}
@@ -0,0 +1,74 @@
+//! YAML key-path edits (serde_yaml drops comments, so the file is re-serialized)
+
+use serde_yaml::{Mapping, Value as YamlValue};
+
+use super::split_path;
+
+/// Parse YAML content (empty content is an empty mapping)
+pub fn parse(content: &str) -> Result<YamlValue, String> {
+    if content.trim().is_empty() {
+        return Ok(YamlValue::Mapping(Mapping::new()));
+    }
+    serde_yaml::from_str(content).map_err(|e| format!("Failed to parse YAML: {}", e))
+}
+
+pub fn to_string(root: &YamlValue) -> Result<String, String> {
+    serde_yaml::to_string(root).map_err(|e| format!("Failed to serialize YAML: {}", e))
+}
+
+pub fn get_value(content: &str, path: &str) -> Result<Option<String>, String> {
+    let root = parse(content)?;
+    let mut current = &root;
+    for key in split_path(path) {
+        match current.get(key) {
+            Some(next) => current = next,
+            None => return Ok(None),
+        }
+    }
+    Ok(match current {
+        YamlValue::String(s) => Some(s.clone()),
+        YamlValue::Null => None,
+        other => serde_yaml::to_string(other).ok().map(|s| s.trim().to_string()),
+    })
+}
+
+/// Set (or with `None` remove) a string value by key path, creating parent mappings
+pub fn set_value(root: &mut YamlValue, path: &str, value: Option<&str>) -> Result<(), String> {
+    let keys = split_path(path);
+    let Some((last, parents)) = keys.split_last() else {
+        return Err(format!("Invalid key path: {}", path));
+    };
+
+    let mut current = root;
+    for key in parents {
+        if !current.is_mapping() {
+            *current = YamlValue::Mapping(Mapping::new());
+        }
+        current = current
+            .as_mapping_mut()
+            .unwrap()
+            .entry(YamlValue::String(key.to_string()))
+            .or_insert_with(|| YamlValue::Mapping(Mapping::new()));
+    }
+    if !current.is_mapping() {
+        *current = YamlValue::Mapping(Mapping::new());
+    }
+    let map = current.as_mapping_mut().unwrap();
+    match value {
+        Some(value) => {
+            map.insert(YamlValue::String(last.to_string()), YamlValue::String(value.to_string()));
+        }
+        None => {
+            map.remove(*last);
+        }
+    }
+    Ok(())
+}
+
+pub fn set_values(content: &str, edits: &[(&str, Option<&str>)]) -> Result<String, String> {
+    let mut root = parse(content)?;
+    for (path, value) in edits {
+        set_value(&mut root, path, *value)?;
+    }
+    to_string(&root)
+}
@@ -0,0 +1,287 @@
+use std::path::{Path, PathBuf};
+use serde_yaml::{Mapping, Value as YamlValue};
+
+use crate::coding::{change_history, config_format};
+use crate::coding::open_code::get_favorite_provider_internal;
+use crate::coding::open_code::types::OpenCodeFavoriteProvider;
+use crate::coding::vault;
+use crate::db::DbState;
+use super::types::*;
+
+/// Roles assigned to models added by the toolbox
+const DEFAULT_ROLES: [&str; 3] = ["chat", "edit", "apply"];
+
+// ============================================================================
+// Continue.dev Path Helpers
+// ============================================================================
+
+/// Get Continue config directory (~/.continue/, or CONTINUE_GLOBAL_DIR if set)
+fn get_continue_config_dir() -> Result<PathBuf, String> {
+    if let Ok(dir) = std::env::var("CONTINUE_GLOBAL_DIR") {
+        if !dir.trim().is_empty() {
+            return Ok(PathBuf::from(dir));
+        }
+    }
+
+    let home_dir = std::env::var("USERPROFILE")
+        .or_else(|_| std::env::var("HOME"))
+        .map_err(|_| "Failed to get home directory".to_string())?;
+
+    Ok(Path::new(&home_dir).join(".continue"))
+}
+
+fn get_continue_config_file() -> Result<PathBuf, String> {
+    Ok(get_continue_config_dir()?.join("config.yaml"))
+}
+
+fn read_config_content() -> Result<String, String> {
+    config_format::read_file(&get_continue_config_file()?)
+}
+
+fn parse_config(content: &str) -> Result<YamlValue, String> {
+    if content.trim().is_empty() {
+        let mut root = Mapping::new();
+        root.insert("name".into(), "Local Assistant".into());
+        root.insert("version".into(), "1.0.0".into());
+        root.insert("schema".into(), "v1".into());
+        return Ok(YamlValue::Mapping(root));
+    }
+    config_format::yaml::parse(content)
+}
+
+/// Map an OpenCode SDK package to a Continue provider id
+fn continue_provider_for_npm(npm: &str) -> &'static str {
+    match npm {
+        "@ai-sdk/anthropic" => "anthropic",
+        "@ai-sdk/google" => "gemini",
+        "@ai-sdk/mistral" => "mistral",
+        "@ai-sdk/deepseek" => "deepseek",
+        _ => "openai",
+    }
+}
+
+/// Build Continue model entries for a toolbox provider (vault keys resolved)
+fn build_models(provider: &OpenCodeFavoriteProvider, model_ids: &[String]) -> Result<Vec<ContinueModel>, String> {
+    let config = &provider.provider_config;
+    let options = config.options.as_ref();
+    let api_base = options
+        .and_then(|o| o.base_url.clone())
+        .filter(|s| !s.is_empty());
+    let api_key = match options.and_then(|o| o.api_key.clone()).filter(|s| !s.is_empty()) {
+        Some(key) => {
+            let mut key = serde_json::Value::String(key);
+            vault::resolve_references(&mut key)?;
+            key.as_str().map(str::to_string)
+        }
+        None => None,
+    };
+
+    let mut ids: Vec<String> = if model_ids.is_empty() {
+        config.models.keys().cloned().collect()
+    } else {
+        model_ids.to_vec()
+    };
+    ids.sort();
+
+    Ok(ids
+        .into_iter()
+        .map(|model_id| ContinueModel {
+            name: format!("{}/{}", provider.provider_id, model_id),
+            provider: continue_provider_for_npm(&provider.npm).to_string(),
+            model: model_id,
+            api_base: api_base.clone(),
+            api_key: api_key.clone(),
+            roles: DEFAULT_ROLES.iter().map(|r| r.to_string()).collect(),
+        })
+        .collect())
+}
+
+/// Replace the provider's models in config.yaml content, keeping everything else
+///
+/// Models added by the toolbox are named `{provider_id}/{model_id}`; existing entries
+/// with that prefix are replaced, other models are left untouched.
+fn merge_models(content: &str, provider_id: &str, models: &[ContinueModel]) -> Result<String, String> {
+    let mut root = parse_config(content)?;
+    let root_map = root
+        .as_mapping_mut()
+        .ok_or_else(|| "Invalid config.yaml format".to_string())?;
+
+    let prefix = format!("{}/", provider_id);
+    let mut entries: Vec<YamlValue> = root_map
+        .get("models")
+        .and_then(|v| v.as_sequence())
+        .cloned()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|entry| {
+            !entry
+                .get("name")
+                .and_then(|v| v.as_str())
+                .map(|name| name.starts_with(&prefix))
+                .unwrap_or(false)
+        })
+        .collect();
+
+    for model in models {
+        let value = serde_yaml::to_value(model)
+            .map_err(|e| format!("Failed to serialize model: {}", e))?;
+        entries.push(value);
+    }
+
+    root_map.insert("models".into(), YamlValue::Sequence(entries));
+    config_format::yaml::to_string(&root)
+}
+
+// ============================================================================
+// Continue.dev Commands
+// ============================================================================
+
+/// Get Continue config.yaml path
+#[tauri::command]
+pub fn get_continue_config_path() -> Result<String, String> {
+    Ok(get_continue_config_file()?.to_string_lossy().to_string())
+}
+
+/// Read models from Continue config.yaml
+#[tauri::command]
+pub fn read_continue_models() -> Result<Vec<ContinueModel>, String> {
+    let content = read_config_content()?;
+    if content.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let root = parse_config(&content)?;
+
+    Ok(root
+        .get("models")
+        .and_then(|v| v.as_sequence())
+        .map(|models| {
+            models
+                .iter()
+                // Entries may be `uses:` blocks referencing hub models; skip those
+                .filter_map(|m| serde_yaml::from_value::<ContinueModel>(m.clone()).ok())
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// Preview applying a toolbox provider to Continue config.yaml
+#[tauri::command]
+pub async fn preview_continue_config(
+    state: tauri::State<'_, DbState>,
+    provider_id: String,
+    model_ids: Vec<String>,
+) -> Result<ContinueApplyPreview, String> {
+    let provider = {
+        let db = state.0.lock().await;
+        get_favorite_provider_internal(&db, &provider_id).await?
+    };
+
+    let current_content = read_config_content()?;
+    let models = build_models(&provider, &model_ids)?;
+    let new_content = merge_models(&current_content, &provider.provider_id, &models)?;
+
+    Ok(ContinueApplyPreview {
+        config_path: get_continue_config_file()?.to_string_lossy().to_string(),
+        has_changes: current_content.trim() != new_content.trim(),
+        current_content,
+        new_content,
+    })
+}
+
+/// Apply a toolbox provider's models to Continue config.yaml
+/// If `model_ids` is empty, all models of the provider are added; `dry_run`
+/// returns the diff instead of writing
+#[tauri::command]
+pub async fn apply_continue_config(
+    state: tauri::State<'_, DbState>,
+    provider_id: String,
+    model_ids: Vec<String>,
+    dry_run: Option<bool>,
+) -> Result<Vec<change_history::ConfigDiff>, String> {
+    let provider = {
+        let db = state.0.lock().await;
+        get_favorite_provider_internal(&db, &provider_id).await?
+    };
+
+    let current_content = read_config_content()?;
+    let models = build_models(&provider, &model_ids)?;
+    if models.is_empty() {
+        return Err(format!("Provider '{}' has no models to apply", provider_id));
+    }
+    let new_content = merge_models(&current_content, &provider.provider_id, &models)?;
+    change_history::preview_sync(dry_run.unwrap_or(false), || {
+        config_format::write_file("continue_dev", &get_continue_config_file()?, &new_content)
+    })
+}
+
+/// Remove models added for a provider from Continue config.yaml
+#[tauri::command]
+pub fn remove_continue_provider_models(
+    provider_id: String,
+    dry_run: Option<bool>,
+) -> Result<Vec<change_history::ConfigDiff>, String> {
+    let current_content = read_config_content()?;
+    if current_content.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let new_content = merge_models(&current_content, &provider_id, &[])?;
+    change_history::preview_sync(dry_run.unwrap_or(false), || {
+        config_format::write_file("continue_dev", &get_continue_config_file()?, &new_content)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(name: &str) -> ContinueModel {
+        ContinueModel {
+            name: name.to_string(),
+            provider: "openai".to_string(),
+            model: "gpt-4o".to_string(),
+            api_base: Some("https://api.example.com/v1".to_string()),
+            api_key: None,
+            roles: vec!["chat".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_merge_models_replaces_only_provider_entries() {
+        let content = r#"name: My Assistant
+version: 1.0.0
+schema: v1
+models:
+  - name: Local Llama
+    provider: ollama
+    model: llama3
+  - name: example/old-model
+    provider: openai
+    model: old-model
+context:
+  - provider: code
+"#;
+        let result = merge_models(content, "example", &[model("example/gpt-4o")]).unwrap();
+        let root: YamlValue = serde_yaml::from_str(&result).unwrap();
+        let models = root.get("models").unwrap().as_sequence().unwrap();
+
+        let names: Vec<&str> = models
+            .iter()
+            .filter_map(|m| m.get("name").and_then(|v| v.as_str()))
+            .collect();
+        assert_eq!(names, vec!["Local Llama", "example/gpt-4o"]);
+        assert_eq!(
+            models[1].get("apiBase").and_then(|v| v.as_str()),
+            Some("https://api.example.com/v1")
+        );
+        assert!(root.get("context").is_some());
+        assert_eq!(root.get("name").and_then(|v| v.as_str()), Some("My Assistant"));
+    }
+
+    #[test]
+    fn test_merge_models_creates_config() {
+        let result = merge_models("", "example", &[model("example/gpt-4o")]).unwrap();
+        let root: YamlValue = serde_yaml::from_str(&result).unwrap();
+        assert_eq!(root.get("schema").and_then(|v| v.as_str()), Some("v1"));
+        assert_eq!(root.get("models").unwrap().as_sequence().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// Continue.dev Types
+// ============================================================================
+
+/// Model entry in ~/.continue/config.yaml
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContinueModel {
+    pub name: String,
+    pub provider: String,
+    pub model: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_base: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub roles: Vec<String>,
+}
+
+/// Preview of what applying a provider would write (current vs new)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContinueApplyPreview {
+    pub config_path: String,
+    pub current_content: String,
+    pub new_content: String,
+    pub has_changes: bool,
+}
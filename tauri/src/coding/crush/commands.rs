@@ -0,0 +1,330 @@
+use std::fs;
+use std::path::PathBuf;
+use serde_json::{Map, Value};
+
+use crate::coding::change_history;
+use crate::coding::vault;
+use crate::coding::open_code::adapter as opencode_adapter;
+use crate::coding::open_code::free_models;
+use crate::coding::open_code::types::OpenCodeFavoriteProvider;
+use crate::db::DbState;
+use crate::error::AppResult;
+use super::types::*;
+
+/// Provider key used for injected free models
+const FREE_PROVIDER_ID: &str = "opencode";
+const FREE_PROVIDER_API: &str = "https://opencode.ai/zen/v1";
+
+// ============================================================================
+// Crush Path Helpers
+// ============================================================================
+
+/// Resolve the global crush.json path
+///
+/// - CRUSH_GLOBAL_CONFIG (directory) takes precedence
+/// - Windows: %LOCALAPPDATA%/crush/crush.json
+/// - Others: $XDG_CONFIG_HOME/crush/crush.json or ~/.config/crush/crush.json
+fn get_crush_config_file() -> Result<(PathBuf, &'static str), String> {
+    if let Ok(dir) = std::env::var("CRUSH_GLOBAL_CONFIG") {
+        if !dir.trim().is_empty() {
+            return Ok((PathBuf::from(dir).join("crush.json"), "env"));
+        }
+    }
+
+    if cfg!(target_os = "windows") {
+        let dir = dirs::data_local_dir()
+            .ok_or_else(|| "Failed to get local app data directory".to_string())?;
+        return Ok((dir.join("crush").join("crush.json"), "default"));
+    }
+
+    let config_dir = match std::env::var("XDG_CONFIG_HOME") {
+        Ok(dir) if !dir.trim().is_empty() => PathBuf::from(dir),
+        _ => dirs::home_dir()
+            .ok_or_else(|| "Failed to get home directory".to_string())?
+            .join(".config"),
+    };
+    Ok((config_dir.join("crush").join("crush.json"), "default"))
+}
+
+fn read_crush_config_value() -> Result<Value, String> {
+    let (config_path, _) = get_crush_config_file()?;
+    if !config_path.exists() {
+        return Ok(serde_json::json!({ "$schema": "https://charm.land/crush.json" }));
+    }
+    let content = fs::read_to_string(&config_path)
+        .map_err(|e| format!("Failed to read crush.json: {}", e))?;
+    if content.trim().is_empty() {
+        return Ok(serde_json::json!({ "$schema": "https://charm.land/crush.json" }));
+    }
+    json5::from_str(&content).map_err(|e| format!("Failed to parse crush.json: {}", e))
+}
+
+fn write_crush_config_value(config: &Value) -> Result<(), String> {
+    let (config_path, _) = get_crush_config_file()?;
+    let content = serde_json::to_string_pretty(config)
+        .map_err(|e| format!("Failed to serialize crush.json: {}", e))?;
+    change_history::write_config("crush", &config_path, content)
+        .map_err(|e| format!("Failed to write crush.json: {}", e))
+}
+
+/// Map an OpenCode SDK package to a Crush provider type
+fn crush_provider_type(npm: &str) -> &'static str {
+    match npm {
+        "@ai-sdk/anthropic" => "anthropic",
+        "@ai-sdk/openai" => "openai",
+        "@ai-sdk/google" => "gemini",
+        _ => "openai-compat",
+    }
+}
+
+/// Convert a toolbox provider into a Crush provider entry
+fn to_crush_provider(provider: &OpenCodeFavoriteProvider) -> Value {
+    let config = &provider.provider_config;
+    let options = config.options.as_ref();
+
+    let mut model_ids: Vec<&String> = config.models.keys().collect();
+    model_ids.sort();
+    let models: Vec<Value> = model_ids
+        .into_iter()
+        .map(|id| {
+            let model = &config.models[id];
+            let mut entry = Map::new();
+            entry.insert("id".to_string(), Value::String(id.clone()));
+            entry.insert(
+                "name".to_string(),
+                Value::String(model.name.clone().unwrap_or_else(|| id.clone())),
+            );
+            if let Some(limit) = &model.limit {
+                if let Some(context) = limit.context {
+                    entry.insert("context_window".to_string(), Value::from(context));
+                }
+                if let Some(output) = limit.output {
+                    entry.insert("default_max_tokens".to_string(), Value::from(output));
+                }
+            }
+            Value::Object(entry)
+        })
+        .collect();
+
+    let mut entry = Map::new();
+    entry.insert(
+        "name".to_string(),
+        Value::String(config.name.clone().unwrap_or_else(|| provider.provider_id.clone())),
+    );
+    entry.insert(
+        "type".to_string(),
+        Value::String(crush_provider_type(&provider.npm).to_string()),
+    );
+    if let Some(base_url) = options.and_then(|o| o.base_url.clone()).filter(|s| !s.is_empty()) {
+        entry.insert("base_url".to_string(), Value::String(base_url));
+    }
+    if let Some(api_key) = options.and_then(|o| o.api_key.clone()).filter(|s| !s.is_empty()) {
+        entry.insert("api_key".to_string(), Value::String(api_key));
+    }
+    entry.insert("models".to_string(), Value::Array(models));
+    Value::Object(entry)
+}
+
+/// Get (or create) the `providers` object of crush.json
+fn providers_mut(config: &mut Value) -> Result<&mut Map<String, Value>, String> {
+    let root = config
+        .as_object_mut()
+        .ok_or_else(|| "Invalid crush.json format".to_string())?;
+    let providers = root
+        .entry("providers".to_string())
+        .or_insert_with(|| serde_json::json!({}));
+    if !providers.is_object() {
+        *providers = serde_json::json!({});
+    }
+    Ok(providers.as_object_mut().unwrap())
+}
+
+/// Put `providers` into crush.json; vault references are resolved with `resolve`
+fn merge_providers(
+    config: &mut Value,
+    providers: &[OpenCodeFavoriteProvider],
+    resolve: &dyn Fn(&mut Value) -> AppResult<()>,
+) -> Result<CrushSyncResult, String> {
+    let crush_providers = providers_mut(config)?;
+
+    let mut result = CrushSyncResult {
+        providers: 0,
+        models: 0,
+        changes: Vec::new(),
+    };
+    for provider in providers {
+        let mut entry = to_crush_provider(provider);
+        resolve(&mut entry)?;
+        result.providers += 1;
+        result.models += provider.provider_config.models.len();
+        crush_providers.insert(provider.provider_id.clone(), entry);
+    }
+    Ok(result)
+}
+
+// ============================================================================
+// Crush Commands
+// ============================================================================
+
+/// Get crush.json path info
+#[tauri::command]
+pub fn get_crush_config_path() -> Result<CrushConfigPathInfo, String> {
+    let (path, source) = get_crush_config_file()?;
+    Ok(CrushConfigPathInfo {
+        exists: path.exists(),
+        path: path.to_string_lossy().to_string(),
+        source: source.to_string(),
+    })
+}
+
+/// Read crush.json
+#[tauri::command]
+pub fn read_crush_config() -> Result<Value, String> {
+    read_crush_config_value()
+}
+
+/// Sync toolbox providers into crush.json
+/// If `provider_ids` is None, all saved providers are synced; with `dry_run`
+/// nothing is written and the result carries the diff
+#[tauri::command]
+pub async fn sync_crush_providers(
+    state: tauri::State<'_, DbState>,
+    provider_ids: Option<Vec<String>>,
+    dry_run: Option<bool>,
+) -> Result<CrushSyncResult, String> {
+    let providers: Vec<OpenCodeFavoriteProvider> = {
+        let db = state.0.lock().await;
+        let records_result: Result<Vec<Value>, _> = db
+            .query("SELECT *, type::string(id) as id FROM opencode_favorite_provider ORDER BY created_at ASC")
+            .await
+            .map_err(|e| format!("Failed to query favorite providers: {}", e))?
+            .take(0);
+        records_result
+            .map_err(|e| format!("Failed to deserialize favorite providers: {}", e))?
+            .into_iter()
+            .filter_map(opencode_adapter::from_db_value_favorite_provider)
+            .filter(|p| {
+                provider_ids
+                    .as_ref()
+                    .map(|ids| ids.contains(&p.provider_id))
+                    .unwrap_or(true)
+            })
+            .collect()
+    };
+
+    let mut config = read_crush_config_value()?;
+    let mut result = merge_providers(&mut config, &providers, &vault::resolve_references)?;
+    result.changes = change_history::preview_sync(dry_run.unwrap_or(false), || write_crush_config_value(&config))?;
+    Ok(result)
+}
+
+/// Inject free models (OpenCode Zen) into crush.json as the `opencode` provider
+/// The API key is referenced from $OPENCODE_API_KEY unless already configured
+#[tauri::command]
+pub async fn inject_crush_free_models(state: tauri::State<'_, DbState>) -> Result<usize, String> {
+    let (free_models, _, _) = free_models::get_free_models(&state, false).await?;
+
+    let provider_data = free_models::get_default_provider_data();
+    let base_url = provider_data
+        .get("api")
+        .and_then(|v| v.as_str())
+        .unwrap_or(FREE_PROVIDER_API)
+        .to_string();
+
+    let mut config = read_crush_config_value()?;
+    let crush_providers = providers_mut(&mut config)?;
+
+    let existing_key = crush_providers
+        .get(FREE_PROVIDER_ID)
+        .and_then(|p| p.get("api_key"))
+        .cloned()
+        .unwrap_or_else(|| Value::String("$OPENCODE_API_KEY".to_string()));
+
+    let models: Vec<Value> = free_models
+        .iter()
+        .map(|m| {
+            let mut entry = serde_json::json!({ "id": m.id, "name": m.name });
+            if let Some(context) = m.context {
+                entry["context_window"] = Value::from(context);
+            }
+            entry
+        })
+        .collect();
+    let count = models.len();
+
+    crush_providers.insert(
+        FREE_PROVIDER_ID.to_string(),
+        serde_json::json!({
+            "name": "OpenCode Zen",
+            "type": "openai-compat",
+            "base_url": base_url,
+            "api_key": existing_key,
+            "models": models,
+        }),
+    );
+
+    write_crush_config_value(&config)?;
+    Ok(count)
+}
+
+/// Set the large/small model selection in crush.json (`dry_run` returns the diff instead)
+#[tauri::command]
+pub fn set_crush_model(
+    model_type: String,
+    provider_id: String,
+    model_id: String,
+    dry_run: Option<bool>,
+) -> Result<Vec<change_history::ConfigDiff>, String> {
+    if model_type != "large" && model_type != "small" {
+        return Err(format!("Invalid model type: {}", model_type));
+    }
+
+    let mut config = read_crush_config_value()?;
+    let root = config
+        .as_object_mut()
+        .ok_or_else(|| "Invalid crush.json format".to_string())?;
+    let models = root
+        .entry("models".to_string())
+        .or_insert_with(|| serde_json::json!({}));
+    if !models.is_object() {
+        *models = serde_json::json!({});
+    }
+    models.as_object_mut().unwrap().insert(
+        model_type,
+        serde_json::json!({ "provider": provider_id, "model": model_id }),
+    );
+
+    change_history::preview_sync(dry_run.unwrap_or(false), || write_crush_config_value(&config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fake_secret(id: &str) -> AppResult<String> {
+        Ok(format!("sk-live-{}", id))
+    }
+
+    #[test]
+    fn vault_keys_never_reach_crush_json() {
+        let provider = opencode_adapter::from_db_value_favorite_provider(serde_json::json!({
+            "id": "opencode_favorite_provider:relay",
+            "provider_id": "relay",
+            "npm": "@ai-sdk/openai-compatible",
+            "provider_config": {
+                "npm": "@ai-sdk/openai-compatible",
+                "options": { "baseURL": "https://relay.example.com/v1", "apiKey": "vault://k1" }
+            }
+        }))
+        .unwrap();
+        let resolve = |value: &mut Value| vault::resolve_references_with(value, &fake_secret);
+
+        let mut config = serde_json::json!({});
+        merge_providers(&mut config, &[provider], &resolve).unwrap();
+        let changes = change_history::preview_sync(true, || write_crush_config_value(&config)).unwrap();
+
+        assert_eq!(changes.len(), 1);
+        assert!(!changes[0].after.contains("vault://"));
+        assert!(changes[0].after.contains("sk-live-k1"));
+    }
+}
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// Crush Types
+// ============================================================================
+
+/// Crush config path info
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrushConfigPathInfo {
+    pub path: String,
+    pub exists: bool,
+    /// "env" (CRUSH_GLOBAL_CONFIG) | "default"
+    pub source: String,
+}
+
+/// Result of syncing providers into crush.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrushSyncResult {
+    pub providers: usize,
+    pub models: usize,
+    /// Diff of crush.json on a dry run
+    #[serde(default)]
+    pub changes: Vec<crate::coding::change_history::ConfigDiff>,
+}
@@ -0,0 +1,145 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde_json::Value;
+
+use crate::coding::change_history;
+use crate::coding::cli_detect;
+use crate::coding::open_code::get_favorite_provider_internal;
+use crate::db::DbState;
+use super::types::*;
+
+/// SDK packages whose providers speak the OpenAI API
+const OPENAI_COMPATIBLE_NPM: [&str; 2] = ["@ai-sdk/openai", "@ai-sdk/openai-compatible"];
+
+// ============================================================================
+// Cursor CLI Path Helpers
+// ============================================================================
+
+/// Get Cursor config directory (~/.cursor/)
+fn get_cursor_config_dir() -> Result<PathBuf, String> {
+    let home_dir = std::env::var("USERPROFILE")
+        .or_else(|_| std::env::var("HOME"))
+        .map_err(|_| "Failed to get home directory".to_string())?;
+
+    Ok(Path::new(&home_dir).join(".cursor"))
+}
+
+/// Get cursor-agent config file (~/.cursor/cli-config.json)
+fn get_cursor_cli_config_file() -> Result<PathBuf, String> {
+    Ok(get_cursor_config_dir()?.join("cli-config.json"))
+}
+
+fn read_cli_config() -> Result<Value, String> {
+    let config_path = get_cursor_cli_config_file()?;
+    if !config_path.exists() {
+        return Ok(serde_json::json!({ "version": 1 }));
+    }
+    let content = fs::read_to_string(&config_path)
+        .map_err(|e| format!("Failed to read cli-config.json: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse cli-config.json: {}", e))
+}
+
+fn write_cli_config(config: &Value) -> Result<(), String> {
+    let config_dir = get_cursor_config_dir()?;
+    if !config_dir.exists() {
+        fs::create_dir_all(&config_dir)
+            .map_err(|e| format!("Failed to create .cursor directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(config)
+        .map_err(|e| format!("Failed to serialize cli-config.json: {}", e))?;
+    change_history::write_config("cursor", get_cursor_cli_config_file()?, content)
+        .map_err(|e| format!("Failed to write cli-config.json: {}", e))
+}
+
+// ============================================================================
+// Cursor CLI Commands
+// ============================================================================
+
+/// Detect cursor-agent and read current model selection
+#[tauri::command]
+pub fn get_cursor_cli_status() -> Result<CursorCliStatus, String> {
+    let config_path = get_cursor_cli_config_file()?;
+    let config_exists = config_path.exists();
+    let model = if config_exists {
+        read_cli_config()?
+            .pointer("/model/modelId")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+    } else {
+        None
+    };
+
+    Ok(CursorCliStatus {
+        binary: cli_detect::detect_cli(&["cursor-agent"]),
+        config_path: config_path.to_string_lossy().to_string(),
+        config_exists,
+        model,
+    })
+}
+
+/// Set the default model used by cursor-agent (`dry_run` returns the diff instead)
+#[tauri::command]
+pub fn set_cursor_cli_model(
+    model_id: String,
+    display_name: Option<String>,
+    dry_run: Option<bool>,
+) -> Result<Vec<change_history::ConfigDiff>, String> {
+    let model_id = model_id.trim().to_string();
+    if model_id.is_empty() {
+        return Err("Model id is required".to_string());
+    }
+
+    let mut config = read_cli_config()?;
+    let obj = config
+        .as_object_mut()
+        .ok_or_else(|| "Invalid cli-config.json format".to_string())?;
+
+    let display_name = display_name
+        .filter(|s| !s.trim().is_empty())
+        .unwrap_or_else(|| model_id.clone());
+    obj.insert(
+        "model".to_string(),
+        serde_json::json!({
+            "modelId": model_id,
+            "displayModelId": model_id,
+            "displayName": display_name,
+            "displayNameShort": display_name,
+        }),
+    );
+
+    change_history::preview_sync(dry_run.unwrap_or(false), || write_cli_config(&config))
+}
+
+/// Apply a toolbox provider's model to cursor-agent
+///
+/// Cursor only routes custom models through its OpenAI override, so only
+/// OpenAI-compatible providers are accepted. The CLI itself has no endpoint
+/// setting; the model selection is written and the endpoint is left to the
+/// "Override OpenAI Base URL" option in Cursor settings.
+#[tauri::command]
+pub async fn apply_cursor_cli_provider(
+    state: tauri::State<'_, DbState>,
+    provider_id: String,
+    model_id: String,
+    dry_run: Option<bool>,
+) -> Result<Vec<change_history::ConfigDiff>, String> {
+    let provider = {
+        let db = state.0.lock().await;
+        get_favorite_provider_internal(&db, &provider_id).await?
+    };
+
+    if !OPENAI_COMPATIBLE_NPM.contains(&provider.npm.as_str()) {
+        return Err(format!(
+            "Provider '{}' ({}) is not OpenAI-compatible and cannot be used by Cursor",
+            provider_id, provider.npm
+        ));
+    }
+
+    let model = provider.provider_config.models.get(&model_id);
+    if model.is_none() && !provider.provider_config.models.is_empty() {
+        return Err(format!("Model '{}' not found in provider '{}'", model_id, provider_id));
+    }
+    let display_name = model.and_then(|m| m.name.clone());
+
+    set_cursor_cli_model(model_id, display_name, dry_run)
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+use crate::coding::cli_detect::CliBinaryInfo;
+
+// ============================================================================
+// Cursor CLI Types
+// ============================================================================
+
+/// Cursor CLI detection result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CursorCliStatus {
+    /// cursor-agent binary (None if not installed)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub binary: Option<CliBinaryInfo>,
+    /// ~/.cursor/cli-config.json path
+    pub config_path: String,
+    pub config_exists: bool,
+    /// Currently selected model id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+}
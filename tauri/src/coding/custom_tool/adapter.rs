@@ -0,0 +1,80 @@
+use serde_json::Value;
+
+use super::types::{CustomTool, CustomToolMapping};
+use crate::coding::db_id::db_extract_id;
+
+// ============================================================================
+// Custom Tool Adapter Functions
+// ============================================================================
+
+fn get_string(value: &Value, key: &str) -> String {
+    value
+        .get(key)
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string()
+}
+
+fn get_optional_string(value: &Value, key: &str) -> Option<String> {
+    value
+        .get(key)
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+}
+
+/// Convert database value to CustomTool
+pub fn from_db_value_tool(value: Value) -> CustomTool {
+    let mapping = value
+        .get("mapping")
+        .cloned()
+        .and_then(|m| serde_json::from_value::<CustomToolMapping>(m).ok())
+        .unwrap_or_default();
+
+    CustomTool {
+        id: db_extract_id(&value),
+        name: get_string(&value, "name"),
+        config_path: get_string(&value, "config_path"),
+        format: get_string(&value, "format"),
+        mapping,
+        applied_provider_id: get_optional_string(&value, "applied_provider_id"),
+        applied_model_id: get_optional_string(&value, "applied_model_id"),
+        created_at: get_string(&value, "created_at"),
+        updated_at: get_string(&value, "updated_at"),
+    }
+}
+
+/// Convert CustomTool to database value (id is stored as the record key)
+pub fn to_db_value_tool(tool: &CustomTool) -> Value {
+    let mut map = serde_json::Map::new();
+    map.insert("name".to_string(), Value::String(tool.name.clone()));
+    map.insert(
+        "config_path".to_string(),
+        Value::String(tool.config_path.clone()),
+    );
+    map.insert("format".to_string(), Value::String(tool.format.clone()));
+    map.insert(
+        "mapping".to_string(),
+        serde_json::to_value(&tool.mapping).unwrap_or_else(|_| serde_json::json!({})),
+    );
+    if let Some(ref provider_id) = tool.applied_provider_id {
+        map.insert(
+            "applied_provider_id".to_string(),
+            Value::String(provider_id.clone()),
+        );
+    }
+    if let Some(ref model_id) = tool.applied_model_id {
+        map.insert(
+            "applied_model_id".to_string(),
+            Value::String(model_id.clone()),
+        );
+    }
+    map.insert(
+        "created_at".to_string(),
+        Value::String(tool.created_at.clone()),
+    );
+    map.insert(
+        "updated_at".to_string(),
+        Value::String(tool.updated_at.clone()),
+    );
+    Value::Object(map)
+}
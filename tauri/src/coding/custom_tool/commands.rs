@@ -0,0 +1,337 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, SystemTime};
+use chrono::Local;
+use serde_json::Value;
+use tauri::Emitter;
+
+use crate::coding::change_history;
+use crate::coding::open_code::get_favorite_provider_internal;
+use crate::db::DbState;
+use super::adapter;
+use super::formats;
+use super::types::*;
+use crate::events::{emit_change, ChangeAction};
+
+/// Poll interval for watched config files
+const WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Running watchers keyed by tool id (flag set to false to stop)
+static WATCHERS: OnceLock<Mutex<HashMap<String, Arc<AtomicBool>>>> = OnceLock::new();
+
+fn watchers() -> &'static Mutex<HashMap<String, Arc<AtomicBool>>> {
+    WATCHERS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+// ============================================================================
+// Custom Tool Helpers
+// ============================================================================
+
+/// Expand a leading `~` to the home directory
+fn resolve_config_path(config_path: &str) -> Result<PathBuf, String> {
+    let trimmed = config_path.trim();
+    if trimmed.is_empty() {
+        return Err("Config path is required".to_string());
+    }
+    if trimmed == "~" || trimmed.starts_with("~/") || trimmed.starts_with("~\\") {
+        let home = dirs::home_dir().ok_or_else(|| "Failed to get home directory".to_string())?;
+        return Ok(home.join(trimmed[1..].trim_start_matches(['/', '\\'])));
+    }
+    Ok(PathBuf::from(trimmed))
+}
+
+fn validate_input(input: &CustomToolInput) -> Result<(), String> {
+    if input.name.trim().is_empty() {
+        return Err("Tool name is required".to_string());
+    }
+    if !CUSTOM_TOOL_FORMATS.contains(&input.format.as_str()) {
+        return Err(format!("Unsupported format: {}", input.format));
+    }
+    resolve_config_path(&input.config_path)?;
+    Ok(())
+}
+
+fn read_config_content(tool: &CustomTool) -> Result<String, String> {
+    let path = resolve_config_path(&tool.config_path)?;
+    if !path.exists() {
+        return Ok(String::new());
+    }
+    fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+}
+
+async fn get_tool(db: &surrealdb::Surreal<surrealdb::engine::local::Db>, id: &str) -> Result<CustomTool, String> {
+    let records_result: Result<Vec<Value>, _> = db
+        .query("SELECT *, type::string(id) as id FROM custom_tool WHERE id = type::thing('custom_tool', $id) LIMIT 1")
+        .bind(("id", id.to_string()))
+        .await
+        .map_err(|e| format!("Failed to query custom tool: {}", e))?
+        .take(0);
+
+    records_result
+        .map_err(|e| format!("Failed to deserialize custom tool: {}", e))?
+        .into_iter()
+        .next()
+        .map(adapter::from_db_value_tool)
+        .ok_or_else(|| format!("Custom tool '{}' not found", id))
+}
+
+async fn save_tool(db: &surrealdb::Surreal<surrealdb::engine::local::Db>, tool: &CustomTool) -> Result<(), String> {
+    db.query("UPSERT type::thing('custom_tool', $id) CONTENT $data")
+        .bind(("id", tool.id.clone()))
+        .bind(("data", adapter::to_db_value_tool(tool)))
+        .await
+        .map_err(|e| format!("Failed to save custom tool: {}", e))?;
+    Ok(())
+}
+
+/// Build the values a provider/model would write to a tool
+async fn provider_values(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    provider_id: &str,
+    model_id: Option<String>,
+) -> Result<CustomToolValues, String> {
+    let provider = get_favorite_provider_internal(db, provider_id).await?;
+    let options = provider.provider_config.options.as_ref();
+
+    let model = match model_id.filter(|m| !m.is_empty()) {
+        Some(model) => Some(model),
+        None => {
+            let mut ids: Vec<&String> = provider.provider_config.models.keys().collect();
+            ids.sort();
+            ids.first().map(|s| s.to_string())
+        }
+    };
+
+    Ok(CustomToolValues {
+        base_url: options.and_then(|o| o.base_url.clone()).filter(|s| !s.is_empty()),
+        api_key: options.and_then(|o| o.api_key.clone()).filter(|s| !s.is_empty()),
+        model,
+    })
+}
+
+// ============================================================================
+// Custom Tool CRUD Commands
+// ============================================================================
+
+/// List all custom tools
+#[tauri::command]
+pub async fn list_custom_tools(state: tauri::State<'_, DbState>) -> Result<Vec<CustomTool>, String> {
+    let db = state.0.lock().await;
+
+    let records_result: Result<Vec<Value>, _> = db
+        .query("SELECT *, type::string(id) as id FROM custom_tool ORDER BY created_at ASC")
+        .await
+        .map_err(|e| format!("Failed to query custom tools: {}", e))?
+        .take(0);
+
+    match records_result {
+        Ok(records) => Ok(records.into_iter().map(adapter::from_db_value_tool).collect()),
+        Err(e) => {
+            log::warn!("Failed to deserialize custom tools: {}", e);
+            Ok(Vec::new())
+        }
+    }
+}
+
+/// Create a custom tool
+#[tauri::command]
+pub async fn create_custom_tool(
+    state: tauri::State<'_, DbState>,
+    tool: CustomToolInput,
+) -> Result<CustomTool, String> {
+    validate_input(&tool)?;
+    let db = state.0.lock().await;
+
+    let now = Local::now().to_rfc3339();
+    let created = CustomTool {
+        id: tool.id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+        name: tool.name.trim().to_string(),
+        config_path: tool.config_path.trim().to_string(),
+        format: tool.format,
+        mapping: tool.mapping,
+        applied_provider_id: None,
+        applied_model_id: None,
+        created_at: now.clone(),
+        updated_at: now,
+    };
+    save_tool(&db, &created).await?;
+    Ok(created)
+}
+
+/// Update a custom tool (applied state is kept)
+#[tauri::command]
+pub async fn update_custom_tool(
+    state: tauri::State<'_, DbState>,
+    tool: CustomToolInput,
+) -> Result<CustomTool, String> {
+    validate_input(&tool)?;
+    let id = tool
+        .id
+        .clone()
+        .ok_or_else(|| "ID is required for update".to_string())?;
+    let db = state.0.lock().await;
+
+    let existing = get_tool(&db, &id).await?;
+    let updated = CustomTool {
+        name: tool.name.trim().to_string(),
+        config_path: tool.config_path.trim().to_string(),
+        format: tool.format,
+        mapping: tool.mapping,
+        updated_at: Local::now().to_rfc3339(),
+        ..existing
+    };
+    save_tool(&db, &updated).await?;
+    Ok(updated)
+}
+
+/// Delete a custom tool (its config file is left untouched)
+#[tauri::command]
+pub async fn delete_custom_tool(state: tauri::State<'_, DbState>, id: String) -> Result<(), String> {
+    stop_watcher(&id);
+    let db = state.0.lock().await;
+    db.query("DELETE type::thing('custom_tool', $id)")
+        .bind(("id", id))
+        .await
+        .map_err(|e| format!("Failed to delete custom tool: {}", e))?;
+    Ok(())
+}
+
+// ============================================================================
+// Custom Tool Apply Commands
+// ============================================================================
+
+/// Read the mapped values currently in the tool's config file
+#[tauri::command]
+pub async fn read_custom_tool_values(
+    state: tauri::State<'_, DbState>,
+    id: String,
+) -> Result<CustomToolValues, String> {
+    let tool = {
+        let db = state.0.lock().await;
+        get_tool(&db, &id).await?
+    };
+    let content = read_config_content(&tool)?;
+    formats::read_values(&content, &tool.format, &tool.mapping)
+}
+
+/// Preview the config file after applying a provider
+#[tauri::command]
+pub async fn preview_custom_tool_apply(
+    state: tauri::State<'_, DbState>,
+    id: String,
+    provider_id: String,
+    model_id: Option<String>,
+) -> Result<CustomToolPreview, String> {
+    let db = state.0.lock().await;
+    let tool = get_tool(&db, &id).await?;
+    let values = provider_values(&db, &provider_id, model_id).await?;
+    drop(db);
+
+    let path = resolve_config_path(&tool.config_path)?;
+    let content = read_config_content(&tool)?;
+    let new_content = formats::apply_values(&content, &tool.format, &tool.mapping, &values)?;
+    let changes = change_history::preview_sync(true, || {
+        change_history::write_config("custom_tool", &path, &new_content)
+    })
+    .map_err(|e| format!("Failed to preview {}: {}", path.display(), e))?;
+    Ok(CustomToolPreview {
+        path: path.to_string_lossy().to_string(),
+        content: new_content,
+        changes,
+    })
+}
+
+/// Apply a provider to a custom tool's config file and record it as applied
+#[tauri::command]
+pub async fn apply_custom_tool(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+    provider_id: String,
+    model_id: Option<String>,
+) -> Result<CustomTool, String> {
+    let db = state.0.lock().await;
+    let tool = get_tool(&db, &id).await?;
+    let values = provider_values(&db, &provider_id, model_id).await?;
+
+    let path = resolve_config_path(&tool.config_path)?;
+    let content = read_config_content(&tool)?;
+    let new_content = formats::apply_values(&content, &tool.format, &tool.mapping, &values)?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    change_history::write_config("custom_tool", &path, new_content)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+    let applied = CustomTool {
+        applied_provider_id: Some(provider_id),
+        applied_model_id: values.model,
+        updated_at: Local::now().to_rfc3339(),
+        ..tool
+    };
+    save_tool(&db, &applied).await?;
+
+    emit_change(&app, "custom_tool", ChangeAction::Applied, None, "window");
+    Ok(applied)
+}
+
+// ============================================================================
+// Custom Tool Watch Commands
+// ============================================================================
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn stop_watcher(id: &str) {
+    if let Some(flag) = watchers().lock().unwrap().remove(id) {
+        flag.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Watch a custom tool's config file and emit `custom-tool-config-changed`
+/// (payload: tool id) whenever it is modified
+#[tauri::command]
+pub async fn watch_custom_tool(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+) -> Result<(), String> {
+    let tool = {
+        let db = state.0.lock().await;
+        get_tool(&db, &id).await?
+    };
+    let path = resolve_config_path(&tool.config_path)?;
+
+    stop_watcher(&id);
+    let running = Arc::new(AtomicBool::new(true));
+    watchers()
+        .lock()
+        .unwrap()
+        .insert(id.clone(), running.clone());
+
+    std::thread::spawn(move || {
+        let mut last_modified = modified_time(&path);
+        while running.load(Ordering::SeqCst) {
+            std::thread::sleep(WATCH_INTERVAL);
+            let current = modified_time(&path);
+            if current != last_modified {
+                last_modified = current;
+                let _ = app.emit("custom-tool-config-changed", id.clone());
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// Stop watching a custom tool's config file
+#[tauri::command]
+pub fn unwatch_custom_tool(id: String) -> Result<(), String> {
+    stop_watcher(&id);
+    Ok(())
+}
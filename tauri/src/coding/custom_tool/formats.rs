@@ -0,0 +1,104 @@
+//! Format-specific read/write for custom tool config files
+//!
+//! Structured formats are addressed by dot-separated key paths; `env` files by
+//! variable name. Writes only touch the mapped keys and keep everything else.
+
+use crate::coding::config_format::{self, ConfigFormat};
+use super::types::{CustomToolMapping, CustomToolValues};
+
+/// Mapped (key path, value) pairs for the fields that have both
+fn mapped_pairs<'a>(
+    mapping: &'a CustomToolMapping,
+    values: &'a CustomToolValues,
+) -> Vec<(&'a str, Option<&'a str>)> {
+    [
+        (&mapping.base_url, &values.base_url),
+        (&mapping.api_key, &values.api_key),
+        (&mapping.model, &values.model),
+    ]
+    .into_iter()
+    .filter_map(|(path, value)| {
+        let path = path.as_deref().filter(|p| !p.trim().is_empty())?;
+        Some((path.trim(), Some(value.as_deref()?)))
+    })
+    .collect()
+}
+
+fn parse_format(format: &str) -> Result<ConfigFormat, String> {
+    ConfigFormat::parse(format).ok_or_else(|| format!("Unsupported format: {}", format))
+}
+
+/// Read the mapped values from config content
+pub fn read_values(
+    content: &str,
+    format: &str,
+    mapping: &CustomToolMapping,
+) -> Result<CustomToolValues, String> {
+    let format = parse_format(format)?;
+    let read = |path: &Option<String>| -> Result<Option<String>, String> {
+        match path.as_deref().filter(|p| !p.trim().is_empty()) {
+            Some(path) => config_format::get_value(content, format, path.trim()),
+            None => Ok(None),
+        }
+    };
+
+    Ok(CustomToolValues {
+        base_url: read(&mapping.base_url)?,
+        api_key: read(&mapping.api_key)?,
+        model: read(&mapping.model)?,
+    })
+}
+
+/// Write the mapped values into config content and return the new content
+pub fn apply_values(
+    content: &str,
+    format: &str,
+    mapping: &CustomToolMapping,
+    values: &CustomToolValues,
+) -> Result<String, String> {
+    config_format::set_values(content, parse_format(format)?, &mapped_pairs(mapping, values))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping() -> CustomToolMapping {
+        CustomToolMapping {
+            base_url: Some("provider.baseUrl".to_string()),
+            api_key: Some("provider.apiKey".to_string()),
+            model: Some("model".to_string()),
+        }
+    }
+
+    fn values() -> CustomToolValues {
+        CustomToolValues {
+            base_url: Some("https://api.example.com/v1".to_string()),
+            api_key: Some("sk-test".to_string()),
+            model: Some("gpt-4o".to_string()),
+        }
+    }
+
+    #[test]
+    fn apply_toml_keeps_comments_and_round_trips() {
+        let content = "# my tool\ntheme = \"dark\"\n";
+        let result = apply_values(content, "toml", &mapping(), &values()).unwrap();
+
+        assert!(result.contains("# my tool"));
+        assert!(result.contains("theme = \"dark\""));
+        let read = read_values(&result, "toml", &mapping()).unwrap();
+        assert_eq!(read.base_url.as_deref(), Some("https://api.example.com/v1"));
+        assert_eq!(read.model.as_deref(), Some("gpt-4o"));
+    }
+
+    #[test]
+    fn apply_env_skips_unmapped_fields() {
+        let mapping = CustomToolMapping {
+            api_key: Some("TOOL_API_KEY".to_string()),
+            ..Default::default()
+        };
+        let result = apply_values("OTHER=1\n", "env", &mapping, &values()).unwrap();
+
+        assert_eq!(result, "OTHER=1\nTOOL_API_KEY=sk-test\n");
+    }
+}
@@ -0,0 +1,7 @@
+pub mod adapter;
+pub mod commands;
+pub mod formats;
+pub mod types;
+
+pub use commands::*;
+pub use types::*;
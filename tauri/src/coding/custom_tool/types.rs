@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// Custom Tool Types
+// ============================================================================
+
+/// Config file formats supported by custom tools
+pub const CUSTOM_TOOL_FORMATS: [&str; 5] = ["json", "jsonc", "toml", "yaml", "env"];
+
+/// Where provider fields are written in the tool's config file
+///
+/// Values are dot-separated key paths (e.g. `provider.baseUrl`) for structured
+/// formats, or variable names for `env`. Unset fields are left untouched.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomToolMapping {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+}
+
+/// User-defined tool integration stored in the database
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomTool {
+    pub id: String,
+    pub name: String,
+    /// Config file path, `~/` is expanded to the home directory
+    pub config_path: String,
+    /// One of CUSTOM_TOOL_FORMATS
+    pub format: String,
+    pub mapping: CustomToolMapping,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub applied_provider_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub applied_model_id: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Custom tool input from frontend (create/update)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomToolInput {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub name: String,
+    pub config_path: String,
+    pub format: String,
+    pub mapping: CustomToolMapping,
+}
+
+/// Values currently present in a custom tool's config file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomToolValues {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+}
+
+/// Config file content a provider apply would produce
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomToolPreview {
+    pub path: String,
+    pub content: String,
+    /// The same change as a diff
+    #[serde(default)]
+    pub changes: Vec<crate::coding::change_history::ConfigDiff>,
+}
@@ -0,0 +1,292 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde_json::Value;
+
+use crate::coding::change_history;
+use crate::coding::cli_detect;
+use crate::coding::provider_store::{self, ProviderStore};
+use crate::db::DbState;
+use crate::error::AppResult;
+use super::types::*;
+
+/// Factory Droid providers (no common config)
+pub const DROID: ProviderStore = ProviderStore {
+    display_name: "Factory Droid",
+    provider_table: "droid_provider",
+    common_table: None,
+};
+
+// ============================================================================
+// Droid Config Path Commands
+// ============================================================================
+
+/// Get Factory config directory (~/.factory/)
+fn get_droid_config_dir() -> Result<PathBuf, String> {
+    let home_dir = std::env::var("USERPROFILE")
+        .or_else(|_| std::env::var("HOME"))
+        .map_err(|_| "Failed to get home directory".to_string())?;
+
+    Ok(Path::new(&home_dir).join(".factory"))
+}
+
+/// Get Droid config.json path
+fn get_droid_config_file() -> Result<PathBuf, String> {
+    Ok(get_droid_config_dir()?.join("config.json"))
+}
+
+/// Get Droid config.json file path
+#[tauri::command]
+pub fn get_droid_config_path() -> Result<String, String> {
+    Ok(get_droid_config_file()?.to_string_lossy().to_string())
+}
+
+/// Detect droid binary and current custom models
+#[tauri::command]
+pub fn get_droid_status() -> Result<DroidStatus, String> {
+    let config_path = get_droid_config_file()?;
+    let custom_model_count = read_config_value()
+        .ok()
+        .and_then(|c| c.get("custom_models").and_then(|v| v.as_array()).map(|a| a.len()))
+        .unwrap_or(0);
+
+    Ok(DroidStatus {
+        binary: cli_detect::detect_cli(&["droid"]),
+        config_exists: config_path.exists(),
+        config_path: config_path.to_string_lossy().to_string(),
+        custom_model_count,
+    })
+}
+
+// ============================================================================
+// Droid Provider Commands
+// ============================================================================
+
+/// List all Droid providers ordered by sort_index
+#[tauri::command]
+pub async fn list_droid_providers(
+    state: tauri::State<'_, DbState>,
+) -> AppResult<Vec<DroidProvider>> {
+    let db = state.0.lock().await;
+    provider_store::list_providers(&db, &DROID).await
+}
+
+/// Create a new Droid provider
+#[tauri::command]
+pub async fn create_droid_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    provider: DroidProviderInput,
+) -> AppResult<DroidProvider> {
+    let db = state.0.lock().await;
+    provider_store::create_provider(&db, &app, &DROID, provider).await
+}
+
+/// Update an existing Droid provider
+#[tauri::command]
+pub async fn update_droid_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    provider: DroidProvider,
+) -> AppResult<DroidProvider> {
+    let db = state.0.lock().await;
+    let provider = provider_store::update_provider(&db, &app, &DROID, provider).await?;
+
+    // Re-apply to config files if this provider is currently applied
+    if provider.is_applied {
+        if let Err(e) = apply_config_to_file(&db, &provider.id).await {
+            log::warn!("Failed to auto-apply updated Droid config: {}", e);
+        }
+    }
+    Ok(provider)
+}
+
+/// Delete a Droid provider
+#[tauri::command]
+pub async fn delete_droid_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+    provider_store::delete_provider(&db, &app, &DROID, &id).await
+}
+
+/// Reorder Droid providers
+#[tauri::command]
+pub async fn reorder_droid_providers(
+    state: tauri::State<'_, DbState>,
+    ids: Vec<String>,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+    provider_store::reorder_providers(&db, &DROID, &ids).await
+}
+
+/// Select a Droid provider (mark as applied in database, but not write to file)
+#[tauri::command]
+pub async fn select_droid_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+    provider_store::select_provider(&db, &app, &DROID, &id).await
+}
+
+/// Toggle is_disabled status for a provider
+#[tauri::command]
+pub async fn toggle_droid_provider_disabled(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    provider_id: String,
+    is_disabled: bool,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+    provider_store::toggle_provider_disabled(&db, &app, &DROID, &provider_id, is_disabled).await
+}
+
+// ============================================================================
+// Droid Config File Commands
+// ============================================================================
+
+fn read_config_value() -> Result<Value, String> {
+    let config_path = get_droid_config_file()?;
+    if !config_path.exists() {
+        return Ok(serde_json::json!({}));
+    }
+    let content = fs::read_to_string(&config_path)
+        .map_err(|e| format!("Failed to read config.json: {}", e))?;
+    if content.trim().is_empty() {
+        return Ok(serde_json::json!({}));
+    }
+    json5::from_str(&content).map_err(|e| format!("Failed to parse config.json: {}", e))
+}
+
+/// Read current Droid config.json
+#[tauri::command]
+pub async fn read_droid_settings() -> Result<Value, String> {
+    read_config_value()
+}
+
+/// Extract custom model entries from a provider settings_config string
+fn custom_models_of(settings_config: &str) -> Result<Vec<Value>, String> {
+    let value: Value = serde_json::from_str(settings_config)
+        .map_err(|e| format!("Failed to parse settings config: {}", e))?;
+    Ok(value
+        .get("customModels")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default())
+}
+
+fn display_name_of(model: &Value) -> Option<&str> {
+    model.get("model_display_name").and_then(|v| v.as_str())
+}
+
+/// Final file content computed for a provider
+struct DroidFiles {
+    config: String,
+}
+
+/// Build config.json content for a provider without writing it
+///
+/// Custom models defined by any Droid provider (matched by `model_display_name`)
+/// are treated as managed: they are removed first, then the selected provider's
+/// models are appended. Models added by hand are kept.
+async fn build_config_files(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    provider_id: &str,
+) -> Result<DroidFiles, String> {
+    let provider = provider_store::get_enabled_provider(db, &DROID, provider_id).await?;
+    let providers = provider_store::list_providers(db, &DROID).await?;
+
+    let mut managed_names: Vec<String> = Vec::new();
+    for other in &providers {
+        if let Ok(models) = custom_models_of(&other.settings_config) {
+            managed_names.extend(models.iter().filter_map(display_name_of).map(|s| s.to_string()));
+        }
+    }
+
+    let mut config = read_config_value()?;
+    let obj = config
+        .as_object_mut()
+        .ok_or_else(|| "Invalid config.json format".to_string())?;
+
+    let mut custom_models: Vec<Value> = obj
+        .get("custom_models")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|m| {
+            display_name_of(m)
+                .map(|name| !managed_names.iter().any(|n| n == name))
+                .unwrap_or(true)
+        })
+        .collect();
+    custom_models.extend(custom_models_of(&provider.settings_config)?);
+    obj.insert("custom_models".to_string(), Value::Array(custom_models));
+
+    let config = serde_json::to_string_pretty(&config)
+        .map_err(|e| format!("Failed to serialize config.json: {}", e))?;
+
+    Ok(DroidFiles { config })
+}
+
+/// Internal function: apply provider custom models to config.json
+async fn apply_config_to_file(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    provider_id: &str,
+) -> Result<(), String> {
+    let files = build_config_files(db, provider_id).await?;
+
+    change_history::write_config("droid", get_droid_config_file()?, files.config)
+        .map_err(|e| format!("Failed to write config.json: {}", e))
+}
+
+/// Preview the changes applying a provider would make (diff source for the frontend)
+#[tauri::command]
+pub async fn preview_droid_config(
+    state: tauri::State<'_, DbState>,
+    provider_id: String,
+) -> Result<DroidApplyPreview, String> {
+    let db = state.0.lock().await;
+    let files = build_config_files(&db, &provider_id).await?;
+
+    let config_path = get_droid_config_file()?;
+    let current_config = fs::read_to_string(&config_path).unwrap_or_default();
+
+    Ok(DroidApplyPreview {
+        config_path: config_path.to_string_lossy().to_string(),
+        has_changes: current_config.trim() != files.config.trim(),
+        current_config,
+        new_config: files.config,
+    })
+}
+
+/// Apply Droid provider configuration
+/// `dry_run` returns the diff of the files instead of writing them.
+#[tauri::command]
+pub async fn apply_droid_config(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    provider_id: String,
+    dry_run: Option<bool>,
+) -> Result<Vec<change_history::ConfigDiff>, String> {
+    let db = state.0.lock().await;
+    if dry_run.unwrap_or(false) {
+        return change_history::preview(true, apply_config_to_file(&db, &provider_id)).await;
+    }
+    apply_config_internal(&db, &app, &provider_id, false).await?;
+    Ok(Vec::new())
+}
+
+/// Internal function to apply config: writes files and updates database
+pub async fn apply_config_internal<R: tauri::Runtime>(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    app: &tauri::AppHandle<R>,
+    provider_id: &str,
+    from_tray: bool,
+) -> Result<(), String> {
+    apply_config_to_file(db, provider_id).await?;
+    Ok(provider_store::mark_applied(db, app, &DROID, provider_id, from_tray).await?)
+}
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+use crate::coding::provider_store::{StoredProvider, StoredProviderInput};
+
+// ============================================================================
+// Droid Provider Types
+// ============================================================================
+
+/// DroidProvider - API response
+///
+/// `settings_config` is a JSON string:
+/// `{ "customModels": [{ "model_display_name": "...", "model": "...", "base_url": "...", "api_key": "...", "provider": "generic-chat-completion-api" }] }`
+/// where `customModels` entries are written to `custom_models` in ~/.factory/config.json
+pub type DroidProvider = StoredProvider;
+
+/// DroidProvider - Input from frontend (for create operation)
+pub type DroidProviderInput = StoredProviderInput;
+
+// ============================================================================
+// Droid Status Types
+// ============================================================================
+
+/// Droid detection result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DroidStatus {
+    /// droid binary (None if not installed)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub binary: Option<crate::coding::cli_detect::CliBinaryInfo>,
+    /// ~/.factory/config.json path
+    pub config_path: String,
+    pub config_exists: bool,
+    /// Number of custom models currently configured
+    pub custom_model_count: usize,
+}
+
+/// Preview of what applying a provider would write (current vs new)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DroidApplyPreview {
+    pub config_path: String,
+    pub current_config: String,
+    pub new_config: String,
+    pub has_changes: bool,
+}
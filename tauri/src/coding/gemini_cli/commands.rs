@@ -0,0 +1,196 @@
+use serde_json::Value;
+
+use crate::coding::change_history;
+use crate::coding::gemini_style::{self, GeminiStyleTool};
+use crate::coding::provider_store::{self, ProviderStore};
+use crate::db::DbState;
+use crate::error::AppResult;
+use super::types::*;
+
+/// Gemini CLI config layout: ~/.gemini/settings.json and ~/.gemini/.env
+pub const GEMINI_CLI: GeminiStyleTool = GeminiStyleTool {
+    key: "gemini_cli",
+    store: ProviderStore {
+        display_name: "Gemini CLI",
+        provider_table: "gemini_cli_provider",
+        common_table: Some("gemini_cli_common_config"),
+    },
+    config_dir: ".gemini",
+    env_fields: &[
+        "GEMINI_API_KEY",
+        "GOOGLE_GEMINI_BASE_URL",
+        "GEMINI_MODEL",
+        "GOOGLE_API_KEY",
+    ],
+    api_key_fields: &["GEMINI_API_KEY", "GOOGLE_API_KEY"],
+    api_key_auth_type: "gemini-api-key",
+    model_env_field: None,
+};
+
+// ============================================================================
+// Gemini CLI Config Path Commands
+// ============================================================================
+
+/// Get Gemini CLI settings.json file path
+#[tauri::command]
+pub fn get_gemini_cli_config_path() -> Result<String, String> {
+    Ok(GEMINI_CLI.settings_path()?.to_string_lossy().to_string())
+}
+
+/// Reveal Gemini CLI config folder in file explorer
+#[tauri::command]
+pub fn reveal_gemini_cli_config_folder() -> Result<(), String> {
+    gemini_style::reveal_config_folder(&GEMINI_CLI)
+}
+
+// ============================================================================
+// Gemini CLI Provider Commands
+// ============================================================================
+
+/// List all Gemini CLI providers ordered by sort_index
+#[tauri::command]
+pub async fn list_gemini_cli_providers(
+    state: tauri::State<'_, DbState>,
+) -> AppResult<Vec<GeminiCliProvider>> {
+    let db = state.0.lock().await;
+    provider_store::list_providers(&db, &GEMINI_CLI.store).await
+}
+
+/// Create a new Gemini CLI provider
+#[tauri::command]
+pub async fn create_gemini_cli_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    provider: GeminiCliProviderInput,
+) -> AppResult<GeminiCliProvider> {
+    let db = state.0.lock().await;
+    provider_store::create_provider(&db, &app, &GEMINI_CLI.store, provider).await
+}
+
+/// Update an existing Gemini CLI provider
+#[tauri::command]
+pub async fn update_gemini_cli_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    provider: GeminiCliProvider,
+) -> AppResult<GeminiCliProvider> {
+    let db = state.0.lock().await;
+    gemini_style::update_provider(&db, &app, &GEMINI_CLI, provider).await
+}
+
+/// Delete a Gemini CLI provider
+#[tauri::command]
+pub async fn delete_gemini_cli_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+    provider_store::delete_provider(&db, &app, &GEMINI_CLI.store, &id).await
+}
+
+/// Reorder Gemini CLI providers
+#[tauri::command]
+pub async fn reorder_gemini_cli_providers(
+    state: tauri::State<'_, DbState>,
+    ids: Vec<String>,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+    provider_store::reorder_providers(&db, &GEMINI_CLI.store, &ids).await
+}
+
+/// Select a Gemini CLI provider (mark as applied in database, but not write to file)
+#[tauri::command]
+pub async fn select_gemini_cli_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+    provider_store::select_provider(&db, &app, &GEMINI_CLI.store, &id).await
+}
+
+/// Toggle is_disabled status for a provider
+#[tauri::command]
+pub async fn toggle_gemini_cli_provider_disabled(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    provider_id: String,
+    is_disabled: bool,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+    provider_store::toggle_provider_disabled(&db, &app, &GEMINI_CLI.store, &provider_id, is_disabled).await
+}
+
+// ============================================================================
+// Gemini CLI Config File Commands
+// ============================================================================
+
+/// Read current Gemini CLI settings.json and .env
+#[tauri::command]
+pub async fn read_gemini_cli_settings() -> Result<GeminiCliSettings, String> {
+    gemini_style::read_settings(&GEMINI_CLI)
+}
+
+/// Get the mcpServers section of settings.json
+#[tauri::command]
+pub async fn get_gemini_cli_mcp_servers() -> Result<Value, String> {
+    gemini_style::get_mcp_servers(&GEMINI_CLI)
+}
+
+/// Preview the changes applying a provider would make (diff source for the frontend)
+#[tauri::command]
+pub async fn preview_gemini_cli_config(
+    state: tauri::State<'_, DbState>,
+    provider_id: String,
+) -> AppResult<GeminiCliApplyPreview> {
+    let db = state.0.lock().await;
+    gemini_style::preview_config(&db, &GEMINI_CLI, &provider_id).await
+}
+
+/// Apply Gemini CLI provider configuration to settings.json and .env
+/// `dry_run` returns the diff of the files instead of writing them.
+#[tauri::command]
+pub async fn apply_gemini_cli_config(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    provider_id: String,
+    dry_run: Option<bool>,
+) -> AppResult<Vec<change_history::ConfigDiff>> {
+    let db = state.0.lock().await;
+    gemini_style::apply_config(&db, &app, &GEMINI_CLI, &provider_id, dry_run.unwrap_or(false)).await
+}
+
+/// Internal function to apply config: writes files and updates database
+pub async fn apply_config_internal<R: tauri::Runtime>(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    app: &tauri::AppHandle<R>,
+    provider_id: &str,
+    from_tray: bool,
+) -> AppResult<()> {
+    gemini_style::apply_config_internal(db, app, &GEMINI_CLI, provider_id, from_tray).await
+}
+
+// ============================================================================
+// Gemini CLI Common Config Commands
+// ============================================================================
+
+/// Get Gemini CLI common config
+#[tauri::command]
+pub async fn get_gemini_cli_common_config(
+    state: tauri::State<'_, DbState>,
+) -> AppResult<Option<GeminiCliCommonConfig>> {
+    let db = state.0.lock().await;
+    provider_store::get_common_config(&db, &GEMINI_CLI.store).await
+}
+
+/// Save Gemini CLI common config
+#[tauri::command]
+pub async fn save_gemini_cli_common_config(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    config: String,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+    gemini_style::save_common_config(&db, &app, &GEMINI_CLI, &config).await
+}
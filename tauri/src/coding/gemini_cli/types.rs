@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+
+use crate::coding::provider_store::{StoredCommonConfig, StoredProvider, StoredProviderInput};
+
+// ============================================================================
+// Gemini CLI Provider Types
+// ============================================================================
+
+/// GeminiCliProvider - API response
+///
+/// `settings_config` is a JSON string:
+/// `{ "env": { "GEMINI_API_KEY": "...", "GOOGLE_GEMINI_BASE_URL": "...", "GEMINI_MODEL": "..." }, "config": { ... } }`
+/// where `env` is written to ~/.gemini/.env and `config` is merged into ~/.gemini/settings.json
+pub type GeminiCliProvider = StoredProvider;
+
+/// GeminiCliProvider - Input from frontend (for create operation)
+pub type GeminiCliProviderInput = StoredProviderInput;
+
+// ============================================================================
+// Gemini CLI Common Config Types
+// ============================================================================
+
+/// GeminiCliCommonConfig - API response
+pub type GeminiCliCommonConfig = StoredCommonConfig;
+
+/// Gemini CLI config files (for reading current state)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiCliSettings {
+    /// ~/.gemini/settings.json content
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub settings: Option<serde_json::Value>,
+    /// ~/.gemini/.env key-value pairs
+    pub env: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Preview of what applying a provider would write (current vs new)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiCliApplyPreview {
+    pub settings_path: String,
+    pub env_path: String,
+    pub current_settings: String,
+    pub new_settings: String,
+    pub current_env: String,
+    pub new_env: String,
+    pub has_changes: bool,
+}
@@ -0,0 +1,310 @@
+use std::fs;
+use serde_json::Value;
+
+use super::tool::{merge_json, GeminiStyleTool};
+use crate::coding::change_history;
+use crate::coding::config_format;
+use crate::coding::gemini_cli::types::*;
+use crate::coding::provider_store;
+use crate::error::{AppError, AppResult};
+
+type Db = surrealdb::Surreal<surrealdb::engine::local::Db>;
+
+// ============================================================================
+// Config Path
+// ============================================================================
+
+/// Reveal the tool's config folder in file explorer
+pub fn reveal_config_folder(tool: &GeminiStyleTool) -> Result<(), String> {
+    let config_dir = tool.config_dir()?;
+
+    if !config_dir.exists() {
+        fs::create_dir_all(&config_dir)
+            .map_err(|e| format!("Failed to create {} directory: {}", tool.config_dir, e))?;
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        std::process::Command::new("explorer")
+            .arg(&config_dir)
+            .spawn()
+            .map_err(|e| format!("Failed to open folder: {}", e))?;
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        std::process::Command::new("open")
+            .arg(&config_dir)
+            .spawn()
+            .map_err(|e| format!("Failed to open folder: {}", e))?;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        std::process::Command::new("xdg-open")
+            .arg(&config_dir)
+            .spawn()
+            .map_err(|e| format!("Failed to open folder: {}", e))?;
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// Providers
+// ============================================================================
+
+/// Update an existing provider and re-apply it if it is the current one
+pub async fn update_provider(
+    db: &Db,
+    app: &tauri::AppHandle,
+    tool: &GeminiStyleTool,
+    provider: GeminiCliProvider,
+) -> AppResult<GeminiCliProvider> {
+    let provider = provider_store::update_provider(db, app, &tool.store, provider).await?;
+
+    if provider.is_applied {
+        if let Err(e) = apply_config_to_file(db, tool, &provider.id).await {
+            log::warn!("Failed to auto-apply updated {} config: {}", tool.store.display_name, e);
+        }
+    }
+    Ok(provider)
+}
+
+// ============================================================================
+// Config Files
+// ============================================================================
+
+/// Read current settings.json and .env
+pub fn read_settings(tool: &GeminiStyleTool) -> Result<GeminiCliSettings, String> {
+    let settings_path = tool.settings_path()?;
+    let env_path = tool.env_path()?;
+
+    let settings = if settings_path.exists() {
+        let content = fs::read_to_string(&settings_path)
+            .map_err(|e| format!("Failed to read settings.json: {}", e))?;
+        Some(
+            serde_json::from_str(&content)
+                .map_err(|e| format!("Failed to parse settings.json: {}", e))?,
+        )
+    } else {
+        None
+    };
+
+    let env = if env_path.exists() {
+        let content = fs::read_to_string(&env_path)
+            .map_err(|e| format!("Failed to read .env: {}", e))?;
+        config_format::env::parse_env_to_map(&content)
+    } else {
+        serde_json::Map::new()
+    };
+
+    Ok(GeminiCliSettings { settings, env })
+}
+
+/// Get the mcpServers section of settings.json
+pub fn get_mcp_servers(tool: &GeminiStyleTool) -> Result<Value, String> {
+    let settings = read_settings(tool)?;
+    Ok(settings
+        .settings
+        .and_then(|s| s.get("mcpServers").cloned())
+        .unwrap_or_else(|| serde_json::json!({})))
+}
+
+/// Final file contents computed for a provider
+struct ConfigFiles {
+    settings: String,
+    env: String,
+}
+
+/// Build the settings.json and .env contents for a provider without writing them
+async fn build_config_files(
+    db: &Db,
+    tool: &GeminiStyleTool,
+    provider_id: &str,
+) -> AppResult<ConfigFiles> {
+    let provider = provider_store::get_enabled_provider(db, &tool.store, provider_id).await?;
+
+    let provider_config: Value =
+        serde_json::from_str(&provider.settings_config).map_err(AppError::invalid)?;
+
+    // Common config (settings.json fragment shared by all providers)
+    let common_config_text = provider_store::common_config_text(db, &tool.store).await?;
+    let common_config: Value = if common_config_text.trim().is_empty() {
+        serde_json::json!({})
+    } else {
+        serde_json::from_str(&common_config_text).map_err(AppError::invalid)?
+    };
+
+    // Existing settings.json (mcpServers is managed by the MCP module and must be preserved)
+    let settings_path = tool.settings_path().map_err(AppError::io)?;
+    let existing_settings: Value = if settings_path.exists() {
+        let content = fs::read_to_string(&settings_path)?;
+        serde_json::from_str(&content).unwrap_or_else(|_| serde_json::json!({}))
+    } else {
+        serde_json::json!({})
+    };
+
+    let mut final_settings = common_config;
+    if !final_settings.is_object() {
+        final_settings = serde_json::json!({});
+    }
+    if let Some(config) = provider_config.get("config") {
+        merge_json(&mut final_settings, config);
+    }
+
+    // Collect provider env
+    let env_values: Vec<(String, String)> = provider_config
+        .get("env")
+        .and_then(|v| v.as_object())
+        .map(|env| {
+            env.iter()
+                .filter_map(|(k, v)| {
+                    v.as_str()
+                        .filter(|s| !s.is_empty())
+                        .map(|s| (k.clone(), s.to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // API key providers need the matching auth type unless explicitly configured
+    let has_api_key = env_values
+        .iter()
+        .any(|(k, _)| tool.api_key_fields.contains(&k.as_str()));
+    if has_api_key
+        && final_settings
+            .pointer("/security/auth/selectedType")
+            .is_none()
+    {
+        merge_json(
+            &mut final_settings,
+            &serde_json::json!({ "security": { "auth": { "selectedType": tool.api_key_auth_type } } }),
+        );
+    }
+
+    // Keep settings.json model in sync with the provider model
+    if let Some(model_field) = tool.model_env_field {
+        if let Some((_, model)) = env_values.iter().find(|(k, _)| k == model_field) {
+            if final_settings.pointer("/model/name").is_none() {
+                merge_json(
+                    &mut final_settings,
+                    &serde_json::json!({ "model": { "name": model } }),
+                );
+            }
+        }
+    }
+
+    if let Some(mcp) = existing_settings.get("mcpServers") {
+        if let Some(obj) = final_settings.as_object_mut() {
+            obj.entry("mcpServers".to_string()).or_insert_with(|| mcp.clone());
+        }
+    }
+
+    let settings = serde_json::to_string_pretty(&final_settings).map_err(AppError::invalid)?;
+
+    let env_path = tool.env_path().map_err(AppError::io)?;
+    let current_env = if env_path.exists() {
+        fs::read_to_string(&env_path)?
+    } else {
+        String::new()
+    };
+    let env = config_format::env::update_env(&current_env, &env_values, tool.env_fields);
+
+    Ok(ConfigFiles { settings, env })
+}
+
+/// Internal function: apply provider config to settings.json and .env
+pub async fn apply_config_to_file(
+    db: &Db,
+    tool: &GeminiStyleTool,
+    provider_id: &str,
+) -> AppResult<()> {
+    let files = build_config_files(db, tool, provider_id).await?;
+
+    change_history::write_config(
+        tool.key,
+        tool.settings_path().map_err(AppError::io)?,
+        format!("{}\n", files.settings),
+    )?;
+    change_history::write_config(tool.key, tool.env_path().map_err(AppError::io)?, files.env)?;
+
+    Ok(())
+}
+
+/// Preview the changes applying a provider would make (diff source for the frontend)
+pub async fn preview_config(
+    db: &Db,
+    tool: &GeminiStyleTool,
+    provider_id: &str,
+) -> AppResult<GeminiCliApplyPreview> {
+    let files = build_config_files(db, tool, provider_id).await?;
+
+    let settings_path = tool.settings_path().map_err(AppError::io)?;
+    let env_path = tool.env_path().map_err(AppError::io)?;
+    let current_settings = fs::read_to_string(&settings_path).unwrap_or_default();
+    let current_env = fs::read_to_string(&env_path).unwrap_or_default();
+    let new_settings = format!("{}\n", files.settings);
+
+    let has_changes = current_settings.trim() != new_settings.trim()
+        || current_env.trim() != files.env.trim();
+
+    Ok(GeminiCliApplyPreview {
+        settings_path: settings_path.to_string_lossy().to_string(),
+        env_path: env_path.to_string_lossy().to_string(),
+        current_settings,
+        new_settings,
+        current_env,
+        new_env: files.env,
+        has_changes,
+    })
+}
+
+/// Apply provider config to the files, or return the diff when `dry_run` is set
+pub async fn apply_config(
+    db: &Db,
+    app: &tauri::AppHandle,
+    tool: &GeminiStyleTool,
+    provider_id: &str,
+    dry_run: bool,
+) -> AppResult<Vec<change_history::ConfigDiff>> {
+    if dry_run {
+        return change_history::preview(true, apply_config_to_file(db, tool, provider_id)).await;
+    }
+    apply_config_internal(db, app, tool, provider_id, false).await?;
+    Ok(Vec::new())
+}
+
+/// Internal function to apply config: writes files and updates database
+pub async fn apply_config_internal<R: tauri::Runtime>(
+    db: &Db,
+    app: &tauri::AppHandle<R>,
+    tool: &GeminiStyleTool,
+    provider_id: &str,
+    from_tray: bool,
+) -> AppResult<()> {
+    apply_config_to_file(db, tool, provider_id).await?;
+    provider_store::mark_applied(db, app, &tool.store, provider_id, from_tray).await
+}
+
+// ============================================================================
+// Common Config
+// ============================================================================
+
+/// Save the common config and re-apply the current provider
+pub async fn save_common_config(
+    db: &Db,
+    app: &tauri::AppHandle,
+    tool: &GeminiStyleTool,
+    config: &str,
+) -> AppResult<()> {
+    provider_store::save_common_config(db, app, &tool.store, config).await?;
+
+    // Re-apply current provider so the merged config reaches the files
+    if let Ok(Some(provider)) = provider_store::applied_provider(db, &tool.store).await {
+        if let Err(e) = apply_config_to_file(db, tool, &provider.id).await {
+            log::warn!("Failed to re-apply {} config: {}", tool.store.display_name, e);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,12 @@
+//! Shared provider logic for tools that use the Gemini CLI config layout
+//!
+//! Gemini CLI and its forks (Qwen Code) keep a `settings.json` and a `.env` in a
+//! dot-directory under home. Providers are kept in the shared
+//! [`provider_store`](crate::coding::provider_store); the apply logic here is
+//! identical apart from the values in [`GeminiStyleTool`].
+
+pub mod commands;
+pub mod tool;
+
+pub use commands::*;
+pub use tool::*;
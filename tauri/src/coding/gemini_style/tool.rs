@@ -0,0 +1,62 @@
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+use crate::coding::provider_store::ProviderStore;
+
+/// Describes one tool that uses the Gemini CLI config layout
+pub struct GeminiStyleTool {
+    /// Module key (change history scope, tray module)
+    pub key: &'static str,
+    /// Where the tool's providers and common config are stored
+    pub store: ProviderStore,
+    /// Config directory name under home (e.g. `.gemini`)
+    pub config_dir: &'static str,
+    /// Env keys managed by provider switching (written to `.env`)
+    pub env_fields: &'static [&'static str],
+    /// Env keys that mark an API key provider
+    pub api_key_fields: &'static [&'static str],
+    /// `security.auth.selectedType` used for API key providers
+    pub api_key_auth_type: &'static str,
+    /// Env key whose value is mirrored to `model.name` in settings.json
+    pub model_env_field: Option<&'static str>,
+}
+
+impl GeminiStyleTool {
+    /// Config directory (e.g. ~/.gemini/)
+    pub fn config_dir(&self) -> Result<PathBuf, String> {
+        let home_dir = std::env::var("USERPROFILE")
+            .or_else(|_| std::env::var("HOME"))
+            .map_err(|_| "Failed to get home directory".to_string())?;
+
+        Ok(Path::new(&home_dir).join(self.config_dir))
+    }
+
+    /// settings.json path
+    pub fn settings_path(&self) -> Result<PathBuf, String> {
+        Ok(self.config_dir()?.join("settings.json"))
+    }
+
+    /// .env path
+    pub fn env_path(&self) -> Result<PathBuf, String> {
+        Ok(self.config_dir()?.join(".env"))
+    }
+}
+
+/// Deep-merge `source` into `target` (objects are merged, other values replaced)
+pub fn merge_json(target: &mut Value, source: &Value) {
+    match (target, source) {
+        (Value::Object(target_map), Value::Object(source_map)) => {
+            for (key, value) in source_map {
+                match target_map.get_mut(key) {
+                    Some(existing) if existing.is_object() && value.is_object() => {
+                        merge_json(existing, value);
+                    }
+                    _ => {
+                        target_map.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (target, source) => *target = source.clone(),
+    }
+}
@@ -0,0 +1,228 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde_json::{Map, Value};
+
+use crate::coding::change_history;
+use crate::coding::cli_detect;
+use crate::coding::open_code::{get_favorite_provider_internal, OpenCodeProvider};
+use crate::coding::vscode;
+use crate::db::DbState;
+use super::types::*;
+
+/// VS Code setting holding Copilot Chat BYOK (OpenAI-compatible) models
+const CUSTOM_MODELS_SETTING: &str = "github.copilot.chat.customOAIModels";
+
+/// Editors that run Copilot Chat: (display name, user data folder name)
+const COPILOT_EDITORS: [(&str, &str); 2] = [("VS Code", "Code"), ("VS Code Insiders", "Code - Insiders")];
+
+// ============================================================================
+// Copilot CLI Path Helpers
+// ============================================================================
+
+/// Get Copilot CLI config directory ($XDG_CONFIG_HOME/.copilot or ~/.copilot)
+fn get_copilot_config_dir() -> Result<PathBuf, String> {
+    if let Ok(dir) = std::env::var("XDG_CONFIG_HOME") {
+        if !dir.trim().is_empty() {
+            return Ok(PathBuf::from(dir).join(".copilot"));
+        }
+    }
+
+    let home_dir = std::env::var("USERPROFILE")
+        .or_else(|_| std::env::var("HOME"))
+        .map_err(|_| "Failed to get home directory".to_string())?;
+
+    Ok(Path::new(&home_dir).join(".copilot"))
+}
+
+fn get_copilot_config_file() -> Result<PathBuf, String> {
+    Ok(get_copilot_config_dir()?.join("config.json"))
+}
+
+fn read_cli_config() -> Result<Value, String> {
+    let config_path = get_copilot_config_file()?;
+    if !config_path.exists() {
+        return Ok(serde_json::json!({}));
+    }
+    let content = fs::read_to_string(&config_path)
+        .map_err(|e| format!("Failed to read Copilot config: {}", e))?;
+    if content.trim().is_empty() {
+        return Ok(serde_json::json!({}));
+    }
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse Copilot config: {}", e))
+}
+
+/// Editors where Copilot Chat is available (user directory exists)
+fn installed_editors() -> Vec<(&'static str, &'static str)> {
+    COPILOT_EDITORS
+        .iter()
+        .filter(|(_, folder)| {
+            vscode::get_editor_user_dir(folder)
+                .map(|dir| dir.exists())
+                .unwrap_or(false)
+        })
+        .copied()
+        .collect()
+}
+
+fn custom_models(folder: &str) -> Map<String, Value> {
+    vscode::read_editor_settings(folder)
+        .ok()
+        .and_then(|settings| settings.get(CUSTOM_MODELS_SETTING).cloned())
+        .and_then(|v| v.as_object().cloned())
+        .unwrap_or_default()
+}
+
+// ============================================================================
+// GitHub Copilot Commands
+// ============================================================================
+
+/// Detect Copilot CLI and BYOK configuration
+#[tauri::command]
+pub fn get_copilot_status() -> Result<CopilotStatus, String> {
+    let config_path = get_copilot_config_file()?;
+    let model = read_cli_config()
+        .ok()
+        .and_then(|c| c.get("model").and_then(|v| v.as_str()).map(|s| s.to_string()));
+
+    Ok(CopilotStatus {
+        binary: cli_detect::detect_cli(&["copilot"]),
+        config_exists: config_path.exists(),
+        config_path: config_path.to_string_lossy().to_string(),
+        model,
+        byok_editors: installed_editors()
+            .into_iter()
+            .filter(|(_, folder)| !custom_models(folder).is_empty())
+            .map(|(editor, _)| editor.to_string())
+            .collect(),
+    })
+}
+
+/// Set the model used by Copilot CLI (`dry_run` returns the diff instead)
+#[tauri::command]
+pub fn set_copilot_cli_model(model: String, dry_run: Option<bool>) -> Result<Vec<change_history::ConfigDiff>, String> {
+    let mut config = read_cli_config()?;
+    let obj = config
+        .as_object_mut()
+        .ok_or_else(|| "Invalid Copilot config format".to_string())?;
+
+    if model.trim().is_empty() {
+        obj.remove("model");
+    } else {
+        obj.insert("model".to_string(), Value::String(model.trim().to_string()));
+    }
+
+    let config_dir = get_copilot_config_dir()?;
+    if !config_dir.exists() {
+        fs::create_dir_all(&config_dir)
+            .map_err(|e| format!("Failed to create .copilot directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(&config)
+        .map_err(|e| format!("Failed to serialize Copilot config: {}", e))?;
+    change_history::preview_sync(dry_run.unwrap_or(false), || {
+        change_history::write_config("github_copilot", get_copilot_config_file()?, content)
+            .map_err(|e| format!("Failed to write Copilot config: {}", e))
+    })
+}
+
+/// Add a toolbox provider's models to Copilot Chat as BYOK models
+///
+/// Models are registered through `github.copilot.chat.customOAIModels`. VS Code keeps
+/// API keys in its secret storage, so Copilot asks for the key on first use.
+/// If `model_ids` is empty, all models of the provider are added. With `dry_run`
+/// nothing is written and the result carries the diffs.
+#[tauri::command]
+pub async fn apply_copilot_byok_provider(
+    state: tauri::State<'_, DbState>,
+    provider_id: String,
+    model_ids: Vec<String>,
+    dry_run: Option<bool>,
+) -> Result<CopilotByokResult, String> {
+    let provider = {
+        let db = state.0.lock().await;
+        get_favorite_provider_internal(&db, &provider_id).await?
+    };
+
+    let config = &provider.provider_config;
+    let base_url = config
+        .options
+        .as_ref()
+        .and_then(|o| o.base_url.clone())
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| format!("Provider '{}' has no base URL", provider_id))?;
+    let url = format!("{}/chat/completions", base_url.trim_end_matches('/'));
+
+    let ids: Vec<String> = if model_ids.is_empty() {
+        config.models.keys().cloned().collect()
+    } else {
+        model_ids
+    };
+    if ids.is_empty() {
+        return Err(format!("Provider '{}' has no models to apply", provider_id));
+    }
+
+    let editors = installed_editors();
+    if editors.is_empty() {
+        return Err("VS Code is not installed".to_string());
+    }
+
+    let changes = change_history::preview_sync(dry_run.unwrap_or(false), || {
+        write_byok_models(&editors, config, &ids, &url)
+    })?;
+
+    Ok(CopilotByokResult {
+        editors: editors.iter().map(|(editor, _)| editor.to_string()).collect(),
+        models: ids.len(),
+        changes,
+    })
+}
+
+fn write_byok_models(
+    editors: &[(&str, &str)],
+    config: &OpenCodeProvider,
+    ids: &[String],
+    url: &str,
+) -> Result<(), String> {
+    for (_, folder) in editors {
+        let mut models = custom_models(folder);
+        for id in ids {
+            let model = config.models.get(id);
+            let limit = model.and_then(|m| m.limit.as_ref());
+            let mut entry = Map::new();
+            entry.insert(
+                "name".to_string(),
+                Value::String(model.and_then(|m| m.name.clone()).unwrap_or_else(|| id.clone())),
+            );
+            entry.insert("url".to_string(), Value::String(url.to_string()));
+            entry.insert("toolCalling".to_string(), Value::Bool(true));
+            entry.insert("vision".to_string(), Value::Bool(false));
+            entry.insert(
+                "maxInputTokens".to_string(),
+                Value::from(limit.and_then(|l| l.context).unwrap_or(128000)),
+            );
+            entry.insert(
+                "maxOutputTokens".to_string(),
+                Value::from(limit.and_then(|l| l.output).unwrap_or(8192)),
+            );
+            entry.insert("requiresAPIKey".to_string(), Value::Bool(true));
+            models.insert(id.clone(), Value::Object(entry));
+        }
+        vscode::set_editor_setting(folder, CUSTOM_MODELS_SETTING, Value::Object(models))?;
+    }
+    Ok(())
+}
+
+/// Remove BYOK models from Copilot Chat
+#[tauri::command]
+pub fn remove_copilot_byok_models(model_ids: Vec<String>) -> Result<(), String> {
+    for (_, folder) in installed_editors() {
+        let mut models = custom_models(folder);
+        let before = models.len();
+        for id in &model_ids {
+            models.remove(id);
+        }
+        if models.len() != before {
+            vscode::set_editor_setting(folder, CUSTOM_MODELS_SETTING, Value::Object(models))?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+use crate::coding::cli_detect::CliBinaryInfo;
+
+// ============================================================================
+// GitHub Copilot Types
+// ============================================================================
+
+/// Copilot CLI detection result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CopilotStatus {
+    /// copilot binary (None if not installed)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub binary: Option<CliBinaryInfo>,
+    /// Copilot CLI config.json path
+    pub config_path: String,
+    pub config_exists: bool,
+    /// Model selected in Copilot CLI
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Editors with BYOK models configured by the toolbox
+    pub byok_editors: Vec<String>,
+}
+
+/// Result of applying a provider as Copilot BYOK models
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CopilotByokResult {
+    pub editors: Vec<String>,
+    pub models: usize,
+    /// Diffs of the editor settings on a dry run
+    #[serde(default)]
+    pub changes: Vec<crate::coding::change_history::ConfigDiff>,
+}
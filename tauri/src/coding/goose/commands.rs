@@ -0,0 +1,372 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde_json::Value;
+use serde_yaml::{Mapping, Value as YamlValue};
+
+use crate::coding::change_history;
+use crate::coding::cli_detect;
+use crate::coding::provider_store::{self, ProviderStore};
+use crate::db::DbState;
+use crate::error::AppResult;
+use super::types::*;
+
+/// Goose providers and common config
+pub const GOOSE: ProviderStore = ProviderStore {
+    display_name: "Goose",
+    provider_table: "goose_provider",
+    common_table: Some("goose_common_config"),
+};
+
+// ============================================================================
+// Goose Config Path Commands
+// ============================================================================
+
+/// Get Goose config directory
+///
+/// - Windows: %APPDATA%/Block/goose/config
+/// - Others: $XDG_CONFIG_HOME/goose or ~/.config/goose
+fn get_goose_config_dir() -> Result<PathBuf, String> {
+    if cfg!(target_os = "windows") {
+        let dir = dirs::config_dir()
+            .ok_or_else(|| "Failed to get config directory".to_string())?;
+        return Ok(dir.join("Block").join("goose").join("config"));
+    }
+
+    let config_dir = match std::env::var("XDG_CONFIG_HOME") {
+        Ok(dir) if !dir.trim().is_empty() => PathBuf::from(dir),
+        _ => dirs::home_dir()
+            .ok_or_else(|| "Failed to get home directory".to_string())?
+            .join(".config"),
+    };
+    Ok(config_dir.join("goose"))
+}
+
+/// Get Goose config.yaml path
+fn get_goose_config_file() -> Result<PathBuf, String> {
+    Ok(get_goose_config_dir()?.join("config.yaml"))
+}
+
+/// Get Goose secrets.yaml path (used when the system keyring is disabled)
+fn get_goose_secrets_file() -> Result<PathBuf, String> {
+    Ok(get_goose_config_dir()?.join("secrets.yaml"))
+}
+
+/// Get Goose config.yaml file path
+#[tauri::command]
+pub fn get_goose_config_path() -> Result<String, String> {
+    Ok(get_goose_config_file()?.to_string_lossy().to_string())
+}
+
+/// Detect goose binary and read current provider/model
+#[tauri::command]
+pub fn get_goose_status() -> Result<GooseStatus, String> {
+    let config_path = get_goose_config_file()?;
+    let config = read_yaml_mapping(&config_path).unwrap_or_default();
+    let get = |key: &str| config.get(key).and_then(|v| v.as_str()).map(|s| s.to_string());
+
+    Ok(GooseStatus {
+        binary: cli_detect::detect_cli(&["goose"]),
+        config_exists: config_path.exists(),
+        config_path: config_path.to_string_lossy().to_string(),
+        provider: get("GOOSE_PROVIDER"),
+        model: get("GOOSE_MODEL"),
+    })
+}
+
+// ============================================================================
+// Goose Provider Commands
+// ============================================================================
+
+/// List all Goose providers ordered by sort_index
+#[tauri::command]
+pub async fn list_goose_providers(
+    state: tauri::State<'_, DbState>,
+) -> AppResult<Vec<GooseProvider>> {
+    let db = state.0.lock().await;
+    provider_store::list_providers(&db, &GOOSE).await
+}
+
+/// Create a new Goose provider
+#[tauri::command]
+pub async fn create_goose_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    provider: GooseProviderInput,
+) -> AppResult<GooseProvider> {
+    let db = state.0.lock().await;
+    provider_store::create_provider(&db, &app, &GOOSE, provider).await
+}
+
+/// Update an existing Goose provider
+#[tauri::command]
+pub async fn update_goose_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    provider: GooseProvider,
+) -> AppResult<GooseProvider> {
+    let db = state.0.lock().await;
+    let provider = provider_store::update_provider(&db, &app, &GOOSE, provider).await?;
+
+    // Re-apply to config files if this provider is currently applied
+    if provider.is_applied {
+        if let Err(e) = apply_config_to_file(&db, &provider.id).await {
+            log::warn!("Failed to auto-apply updated Goose config: {}", e);
+        }
+    }
+    Ok(provider)
+}
+
+/// Delete a Goose provider
+#[tauri::command]
+pub async fn delete_goose_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+    provider_store::delete_provider(&db, &app, &GOOSE, &id).await
+}
+
+/// Reorder Goose providers
+#[tauri::command]
+pub async fn reorder_goose_providers(
+    state: tauri::State<'_, DbState>,
+    ids: Vec<String>,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+    provider_store::reorder_providers(&db, &GOOSE, &ids).await
+}
+
+/// Select a Goose provider (mark as applied in database, but not write to file)
+#[tauri::command]
+pub async fn select_goose_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+    provider_store::select_provider(&db, &app, &GOOSE, &id).await
+}
+
+/// Toggle is_disabled status for a provider
+#[tauri::command]
+pub async fn toggle_goose_provider_disabled(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    provider_id: String,
+    is_disabled: bool,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+    provider_store::toggle_provider_disabled(&db, &app, &GOOSE, &provider_id, is_disabled).await
+}
+
+// ============================================================================
+// Goose Config File Commands
+// ============================================================================
+
+fn read_yaml_mapping(path: &Path) -> Result<Mapping, String> {
+    if !path.exists() {
+        return Ok(Mapping::new());
+    }
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    if content.trim().is_empty() {
+        return Ok(Mapping::new());
+    }
+    match serde_yaml::from_str::<YamlValue>(&content) {
+        Ok(YamlValue::Mapping(map)) => Ok(map),
+        Ok(YamlValue::Null) => Ok(Mapping::new()),
+        Ok(_) => Err(format!("Invalid format in {}", path.display())),
+        Err(e) => Err(format!("Failed to parse {}: {}", path.display(), e)),
+    }
+}
+
+fn json_to_yaml(value: &Value) -> Result<YamlValue, String> {
+    serde_yaml::to_value(value).map_err(|e| format!("Failed to convert value: {}", e))
+}
+
+/// Read current Goose config.yaml (as JSON for the frontend)
+#[tauri::command]
+pub async fn read_goose_settings() -> Result<Value, String> {
+    let config = read_yaml_mapping(&get_goose_config_file()?)?;
+    serde_json::to_value(&config).map_err(|e| format!("Failed to convert config: {}", e))
+}
+
+/// Get the extensions section of config.yaml (Goose's MCP servers)
+#[tauri::command]
+pub async fn get_goose_mcp_servers() -> Result<Value, String> {
+    let config = read_yaml_mapping(&get_goose_config_file()?)?;
+    match config.get("extensions") {
+        Some(extensions) => serde_json::to_value(extensions)
+            .map_err(|e| format!("Failed to convert extensions: {}", e)),
+        None => Ok(serde_json::json!({})),
+    }
+}
+
+/// Extract a section object from a provider/common settings_config string
+fn config_section(settings_config: &str, section: &str) -> Result<serde_json::Map<String, Value>, String> {
+    if settings_config.trim().is_empty() {
+        return Ok(serde_json::Map::new());
+    }
+    let value: Value = serde_json::from_str(settings_config)
+        .map_err(|e| format!("Failed to parse settings config: {}", e))?;
+    Ok(value
+        .get(section)
+        .and_then(|v| v.as_object())
+        .cloned()
+        .unwrap_or_default())
+}
+
+/// Final file contents computed for a provider
+struct GooseFiles {
+    config: String,
+    secrets: Option<String>,
+}
+
+/// Build config.yaml (and secrets.yaml) contents for a provider without writing them
+///
+/// Keys set by any Goose provider are treated as managed: they are removed first,
+/// then the common config and the selected provider's keys are written.
+async fn build_config_files(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    provider_id: &str,
+) -> Result<GooseFiles, String> {
+    let provider = provider_store::get_enabled_provider(db, &GOOSE, provider_id).await?;
+    let providers = provider_store::list_providers(db, &GOOSE).await?;
+    let common_config = provider_store::common_config_text(db, &GOOSE).await?;
+    let common_config = config_section(&common_config, "config")?;
+
+    let mut config = read_yaml_mapping(&get_goose_config_file()?)?;
+    let secrets_path = get_goose_secrets_file()?;
+    let mut secrets = read_yaml_mapping(&secrets_path)?;
+    let original_secrets = secrets.clone();
+
+    for other in &providers {
+        for key in config_section(&other.settings_config, "config")?.keys() {
+            config.shift_remove(key.as_str());
+        }
+        for key in config_section(&other.settings_config, "secrets")?.keys() {
+            secrets.shift_remove(key.as_str());
+        }
+    }
+    for (key, value) in common_config {
+        config.insert(YamlValue::String(key), json_to_yaml(&value)?);
+    }
+    for (key, value) in config_section(&provider.settings_config, "config")? {
+        config.insert(YamlValue::String(key), json_to_yaml(&value)?);
+    }
+    for (key, value) in config_section(&provider.settings_config, "secrets")? {
+        secrets.insert(YamlValue::String(key), json_to_yaml(&value)?);
+    }
+
+    let config = serde_yaml::to_string(&config)
+        .map_err(|e| format!("Failed to serialize config.yaml: {}", e))?;
+    let secrets = if secrets != original_secrets {
+        Some(
+            serde_yaml::to_string(&secrets)
+                .map_err(|e| format!("Failed to serialize secrets.yaml: {}", e))?,
+        )
+    } else {
+        None
+    };
+
+    Ok(GooseFiles { config, secrets })
+}
+
+/// Internal function: apply provider config to config.yaml / secrets.yaml
+async fn apply_config_to_file(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    provider_id: &str,
+) -> Result<(), String> {
+    let files = build_config_files(db, provider_id).await?;
+
+    change_history::write_config("goose", get_goose_config_file()?, files.config)
+        .map_err(|e| format!("Failed to write config.yaml: {}", e))?;
+    if let Some(secrets) = files.secrets {
+        change_history::write_config("goose", get_goose_secrets_file()?, secrets)
+            .map_err(|e| format!("Failed to write secrets.yaml: {}", e))?;
+    }
+
+    Ok(())
+}
+
+/// Preview the changes applying a provider would make (diff source for the frontend)
+/// Only config.yaml is included; secrets are never sent to the preview
+#[tauri::command]
+pub async fn preview_goose_config(
+    state: tauri::State<'_, DbState>,
+    provider_id: String,
+) -> Result<GooseApplyPreview, String> {
+    let db = state.0.lock().await;
+    let files = build_config_files(&db, &provider_id).await?;
+
+    let config_path = get_goose_config_file()?;
+    let current_config = fs::read_to_string(&config_path).unwrap_or_default();
+
+    Ok(GooseApplyPreview {
+        config_path: config_path.to_string_lossy().to_string(),
+        has_changes: current_config.trim() != files.config.trim() || files.secrets.is_some(),
+        current_config,
+        new_config: files.config,
+    })
+}
+
+/// Apply Goose provider configuration
+/// `dry_run` returns the diff of the files instead of writing them.
+#[tauri::command]
+pub async fn apply_goose_config(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    provider_id: String,
+    dry_run: Option<bool>,
+) -> Result<Vec<change_history::ConfigDiff>, String> {
+    let db = state.0.lock().await;
+    if dry_run.unwrap_or(false) {
+        return change_history::preview(true, apply_config_to_file(&db, &provider_id)).await;
+    }
+    apply_config_internal(&db, &app, &provider_id, false).await?;
+    Ok(Vec::new())
+}
+
+/// Internal function to apply config: writes files and updates database
+pub async fn apply_config_internal<R: tauri::Runtime>(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    app: &tauri::AppHandle<R>,
+    provider_id: &str,
+    from_tray: bool,
+) -> Result<(), String> {
+    apply_config_to_file(db, provider_id).await?;
+    Ok(provider_store::mark_applied(db, app, &GOOSE, provider_id, from_tray).await?)
+}
+
+// ============================================================================
+// Goose Common Config Commands
+// ============================================================================
+
+/// Get Goose common config
+#[tauri::command]
+pub async fn get_goose_common_config(
+    state: tauri::State<'_, DbState>,
+) -> AppResult<Option<GooseCommonConfig>> {
+    let db = state.0.lock().await;
+    provider_store::get_common_config(&db, &GOOSE).await
+}
+
+/// Save Goose common config
+#[tauri::command]
+pub async fn save_goose_common_config(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    config: String,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+    provider_store::save_common_config(&db, &app, &GOOSE, &config).await?;
+
+    // Re-apply current provider so the merged config reaches the files
+    if let Ok(Some(provider)) = provider_store::applied_provider(&db, &GOOSE).await {
+        if let Err(e) = apply_config_to_file(&db, &provider.id).await {
+            log::warn!("Failed to re-apply Goose config: {}", e);
+        }
+    }
+    Ok(())
+}
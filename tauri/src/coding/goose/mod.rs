@@ -0,0 +1,6 @@
+pub mod commands;
+pub mod tray_support;
+pub mod types;
+
+pub use commands::*;
+pub use types::*;
@@ -0,0 +1,36 @@
+//! Goose Tray Support Module
+//!
+//! Provides standardized API for tray menu integration.
+
+use crate::coding::goose::{apply_config_internal, GOOSE};
+use crate::coding::provider_store;
+use crate::db::DbState;
+use tauri::{AppHandle, Manager, Runtime};
+
+pub use crate::coding::provider_store::{TrayProviderData, TrayProviderItem};
+
+/// Get tray provider data for Goose
+pub async fn get_goose_tray_data<R: Runtime>(
+    app: &AppHandle<R>,
+) -> Result<TrayProviderData, String> {
+    let state = app.state::<DbState>();
+    let db = state.0.lock().await;
+
+    Ok(provider_store::tray_data(&db, &GOOSE).await?)
+}
+
+/// Apply provider selection from tray menu
+pub async fn apply_goose_provider<R: Runtime>(
+    app: &AppHandle<R>,
+    provider_id: &str,
+) -> Result<(), String> {
+    let state = app.state::<DbState>();
+    let db = state.0.lock().await;
+
+    apply_config_internal(&db, app, provider_id, true).await
+}
+
+/// Check if Goose should be shown in tray menu
+pub async fn is_enabled_for_tray<R: Runtime>(_app: &AppHandle<R>) -> bool {
+    true
+}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+use crate::coding::provider_store::{StoredCommonConfig, StoredProvider, StoredProviderInput};
+
+// ============================================================================
+// Goose Provider Types
+// ============================================================================
+
+/// GooseProvider - API response
+///
+/// `settings_config` is a JSON string:
+/// `{ "config": { "GOOSE_PROVIDER": "openai", "GOOSE_MODEL": "...", "OPENAI_HOST": "..." }, "secrets": { "OPENAI_API_KEY": "..." } }`
+/// where `config` keys are written to config.yaml and `secrets` keys to secrets.yaml
+pub type GooseProvider = StoredProvider;
+
+/// GooseProvider - Input from frontend (for create operation)
+pub type GooseProviderInput = StoredProviderInput;
+
+// ============================================================================
+// Goose Common Config Types
+// ============================================================================
+
+/// GooseCommonConfig - API response
+pub type GooseCommonConfig = StoredCommonConfig;
+
+/// Goose detection result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GooseStatus {
+    /// goose binary (None if not installed)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub binary: Option<crate::coding::cli_detect::CliBinaryInfo>,
+    /// config.yaml path
+    pub config_path: String,
+    pub config_exists: bool,
+    /// Current GOOSE_PROVIDER / GOOSE_MODEL from config.yaml
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+}
+
+/// Preview of what applying a provider would write (current vs new)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GooseApplyPreview {
+    pub config_path: String,
+    pub current_config: String,
+    pub new_config: String,
+    pub has_changes: bool,
+}
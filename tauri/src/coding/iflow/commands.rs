@@ -0,0 +1,183 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde_json::Value;
+
+use crate::coding::change_history;
+use crate::coding::cli_detect;
+use crate::coding::open_code::adapter as opencode_adapter;
+use crate::coding::open_code::get_favorite_provider_internal;
+use crate::coding::vault;
+use crate::db::DbState;
+use super::types::*;
+
+/// Auth type used by iFlow for custom OpenAI-compatible endpoints
+const OPENAI_COMPATIBLE_AUTH: &str = "openai-compatible";
+
+// ============================================================================
+// iFlow Path Helpers
+// ============================================================================
+
+/// Get iFlow config directory (~/.iflow/)
+fn get_iflow_config_dir() -> Result<PathBuf, String> {
+    let home_dir = std::env::var("USERPROFILE")
+        .or_else(|_| std::env::var("HOME"))
+        .map_err(|_| "Failed to get home directory".to_string())?;
+
+    Ok(Path::new(&home_dir).join(".iflow"))
+}
+
+fn get_iflow_settings_file() -> Result<PathBuf, String> {
+    Ok(get_iflow_config_dir()?.join("settings.json"))
+}
+
+fn read_settings_value() -> Result<Value, String> {
+    let settings_path = get_iflow_settings_file()?;
+    if !settings_path.exists() {
+        return Ok(serde_json::json!({}));
+    }
+    let content = fs::read_to_string(&settings_path)
+        .map_err(|e| format!("Failed to read settings.json: {}", e))?;
+    if content.trim().is_empty() {
+        return Ok(serde_json::json!({}));
+    }
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse settings.json: {}", e))
+}
+
+fn write_settings_value(settings: &Value) -> Result<(), String> {
+    let config_dir = get_iflow_config_dir()?;
+    if !config_dir.exists() {
+        fs::create_dir_all(&config_dir)
+            .map_err(|e| format!("Failed to create .iflow directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    change_history::write_config("iflow", get_iflow_settings_file()?, content)
+        .map_err(|e| format!("Failed to write settings.json: {}", e))
+}
+
+fn get_str(settings: &Value, key: &str) -> Option<String> {
+    settings
+        .get(key)
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+}
+
+// ============================================================================
+// iFlow Commands
+// ============================================================================
+
+/// Get iFlow settings.json path
+#[tauri::command]
+pub fn get_iflow_config_path() -> Result<String, String> {
+    Ok(get_iflow_settings_file()?.to_string_lossy().to_string())
+}
+
+/// Read iFlow settings.json
+#[tauri::command]
+pub fn read_iflow_settings() -> Result<Value, String> {
+    read_settings_value()
+}
+
+/// Detect iFlow CLI and the provider currently configured
+#[tauri::command]
+pub async fn get_iflow_status(state: tauri::State<'_, DbState>) -> Result<IFlowStatus, String> {
+    let settings_path = get_iflow_settings_file()?;
+    let settings = read_settings_value().unwrap_or_else(|_| serde_json::json!({}));
+    let base_url = get_str(&settings, "baseUrl");
+
+    // Match the configured endpoint against saved providers
+    let applied_provider_id = match &base_url {
+        Some(url) => {
+            let db = state.0.lock().await;
+            let records_result: Result<Vec<Value>, _> = db
+                .query("SELECT *, type::string(id) as id FROM opencode_favorite_provider")
+                .await
+                .map_err(|e| format!("Failed to query favorite providers: {}", e))?
+                .take(0);
+            records_result
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(opencode_adapter::from_db_value_favorite_provider)
+                .find(|p| p.base_url.trim_end_matches('/') == url.trim_end_matches('/'))
+                .map(|p| p.provider_id)
+        }
+        None => None,
+    };
+
+    Ok(IFlowStatus {
+        binary: cli_detect::detect_cli(&["iflow"]),
+        settings_exists: settings_path.exists(),
+        settings_path: settings_path.to_string_lossy().to_string(),
+        auth_type: get_str(&settings, "selectedAuthType"),
+        model_name: get_str(&settings, "modelName"),
+        base_url,
+        applied_provider_id,
+    })
+}
+
+/// Apply a toolbox provider to iFlow (OpenAI-compatible endpoint, key and default model)
+/// With `dry_run` the settings.json diff is returned instead of written.
+#[tauri::command]
+pub async fn apply_iflow_provider(
+    state: tauri::State<'_, DbState>,
+    provider_id: String,
+    model_id: Option<String>,
+    dry_run: Option<bool>,
+) -> Result<Vec<change_history::ConfigDiff>, String> {
+    let provider = {
+        let db = state.0.lock().await;
+        get_favorite_provider_internal(&db, &provider_id).await?
+    };
+
+    let options = provider.provider_config.options.as_ref();
+    let base_url = options
+        .and_then(|o| o.base_url.clone())
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| format!("Provider '{}' has no base URL", provider_id))?;
+    let mut api_key = Value::String(options.and_then(|o| o.api_key.clone()).unwrap_or_default());
+    vault::resolve_references(&mut api_key)?;
+
+    // Default to the first model (sorted) when none is given
+    let model_name = match model_id.filter(|m| !m.is_empty()) {
+        Some(model) => Some(model),
+        None => {
+            let mut ids: Vec<&String> = provider.provider_config.models.keys().collect();
+            ids.sort();
+            ids.first().map(|s| s.to_string())
+        }
+    };
+
+    let mut settings = read_settings_value()?;
+    let obj = settings
+        .as_object_mut()
+        .ok_or_else(|| "Invalid settings.json format".to_string())?;
+    obj.insert(
+        "selectedAuthType".to_string(),
+        Value::String(OPENAI_COMPATIBLE_AUTH.to_string()),
+    );
+    obj.insert("baseUrl".to_string(), Value::String(base_url));
+    obj.insert("apiKey".to_string(), api_key);
+    if let Some(model) = model_name {
+        obj.insert("modelName".to_string(), Value::String(model));
+    }
+
+    change_history::preview_sync(dry_run.unwrap_or(false), || write_settings_value(&settings))
+}
+
+/// Set iFlow default model (`dry_run` returns the diff instead of writing)
+#[tauri::command]
+pub fn set_iflow_model(model_name: String, dry_run: Option<bool>) -> Result<Vec<change_history::ConfigDiff>, String> {
+    let model_name = model_name.trim().to_string();
+    if model_name.is_empty() {
+        return Err("Model name is required".to_string());
+    }
+
+    let mut settings = read_settings_value()?;
+    let obj = settings
+        .as_object_mut()
+        .ok_or_else(|| "Invalid settings.json format".to_string())?;
+    obj.insert("modelName".to_string(), Value::String(model_name));
+
+    change_history::preview_sync(dry_run.unwrap_or(false), || write_settings_value(&settings))
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+use crate::coding::cli_detect::CliBinaryInfo;
+
+// ============================================================================
+// iFlow CLI Types
+// ============================================================================
+
+/// iFlow CLI detection result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IFlowStatus {
+    /// iflow binary (None if not installed)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub binary: Option<CliBinaryInfo>,
+    /// ~/.iflow/settings.json path
+    pub settings_path: String,
+    pub settings_exists: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_name: Option<String>,
+    /// Toolbox provider whose base URL matches the current settings
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub applied_provider_id: Option<String>,
+}
@@ -0,0 +1,221 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use regex::Regex;
+
+use crate::coding::change_history;
+use crate::coding::open_code::get_favorite_provider_internal;
+use crate::db::DbState;
+use super::types::*;
+
+/// ProxyAI (CodeGPT) custom OpenAI service settings, stored under `<ide config>/options`
+const PROXY_AI_SETTINGS_FILE: &str = "CodeGPT_CustomServiceSettings.xml";
+const PROXY_AI_COMPONENT: &str = "CodeGPT_CustomServiceSettings";
+
+/// Plugin directory names used by ProxyAI across versions
+const PROXY_AI_PLUGIN_DIRS: [&str; 2] = ["ProxyAI", "CodeGPT"];
+
+// ============================================================================
+// JetBrains Path Helpers
+// ============================================================================
+
+/// JetBrains base directories
+///
+/// - Windows: %APPDATA%/JetBrains
+/// - macOS: ~/Library/Application Support/JetBrains
+/// - Linux: ~/.config/JetBrains
+fn get_jetbrains_base_dirs() -> Vec<PathBuf> {
+    let mut dirs_list = Vec::new();
+    if let Some(config) = dirs::config_dir() {
+        dirs_list.push(config.join("JetBrains"));
+        dirs_list.push(config.join("Google"));
+    }
+    dirs_list
+}
+
+/// Plugins directory for an IDE (Linux keeps plugins under the data directory)
+fn get_plugin_dirs(config_dir: &Path) -> Vec<PathBuf> {
+    let mut result = vec![config_dir.join("plugins")];
+    if let (Some(data), Some(name)) = (dirs::data_dir(), config_dir.file_name()) {
+        result.push(data.join("JetBrains").join(name));
+    }
+    result
+}
+
+fn has_proxy_ai(config_dir: &Path) -> bool {
+    get_plugin_dirs(config_dir).iter().any(|plugins| {
+        PROXY_AI_PLUGIN_DIRS
+            .iter()
+            .any(|name| plugins.join(name).exists())
+    })
+}
+
+/// IDE config directories look like `<Product><Year>.<Minor>` (e.g. PyCharm2024.3)
+fn is_ide_config_dir(name: &str) -> bool {
+    let re = Regex::new(r"^[A-Za-z]+\d{4}\.\d+$").unwrap();
+    re.is_match(name)
+}
+
+fn escape_xml_attr(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn unescape_xml_attr(value: &str) -> String {
+    value
+        .replace("&quot;", "\"")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+/// Read an `<option name="..." value="..."/>` value from settings XML
+fn read_option(content: &str, name: &str) -> Option<String> {
+    let re = Regex::new(&format!(r#"<option\s+name="{}"\s+value="([^"]*)""#, regex::escape(name))).ok()?;
+    re.captures(content)
+        .and_then(|c| c.get(1))
+        .map(|m| unescape_xml_attr(m.as_str()))
+}
+
+/// Set (or insert) an `<option name="..." value="..."/>` in settings XML
+fn set_option(content: &str, name: &str, value: &str) -> String {
+    let escaped = escape_xml_attr(value);
+    let re = Regex::new(&format!(r#"(<option\s+name="{}"\s+value=")[^"]*(")"#, regex::escape(name))).unwrap();
+    if re.is_match(content) {
+        return re
+            .replace(content, |caps: &regex::Captures| {
+                format!("{}{}{}", &caps[1], escaped, &caps[2])
+            })
+            .to_string();
+    }
+
+    let option = format!("    <option name=\"{}\" value=\"{}\" />\n", name, escaped);
+    match content.find("</component>") {
+        Some(pos) => {
+            // Keep indentation of the closing tag
+            let line_start = content[..pos].rfind('\n').map(|i| i + 1).unwrap_or(pos);
+            let mut result = content.to_string();
+            result.insert_str(line_start, &option);
+            result
+        }
+        None => content.to_string(),
+    }
+}
+
+fn empty_settings_xml() -> String {
+    format!(
+        "<application>\n  <component name=\"{}\">\n  </component>\n</application>\n",
+        PROXY_AI_COMPONENT
+    )
+}
+
+// ============================================================================
+// JetBrains Commands
+// ============================================================================
+
+/// List JetBrains IDE config directories on this machine
+#[tauri::command]
+pub fn list_jetbrains_ides() -> Result<Vec<JetBrainsIde>, String> {
+    let mut ides = Vec::new();
+
+    for base in get_jetbrains_base_dirs() {
+        let Ok(entries) = fs::read_dir(&base) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !path.is_dir() || !is_ide_config_dir(&name) {
+                continue;
+            }
+            let proxy_ai_url = fs::read_to_string(path.join("options").join(PROXY_AI_SETTINGS_FILE))
+                .ok()
+                .and_then(|content| read_option(&content, "url"));
+            ides.push(JetBrainsIde {
+                has_proxy_ai: has_proxy_ai(&path),
+                config_dir: path.to_string_lossy().to_string(),
+                proxy_ai_url,
+                name,
+            });
+        }
+    }
+
+    ides.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(ides)
+}
+
+/// Apply a toolbox provider to ProxyAI's custom OpenAI service in the given IDEs
+///
+/// Only the endpoint and model are written; JetBrains keeps API keys in the IDE
+/// password safe, so the key has to be entered once in the plugin settings.
+/// The IDE must be restarted (or closed while applying) to pick up the change.
+/// With `dry_run` nothing is written and the result carries the diffs.
+#[tauri::command]
+pub async fn apply_jetbrains_proxy_ai_provider(
+    state: tauri::State<'_, DbState>,
+    provider_id: String,
+    model_id: String,
+    config_dirs: Vec<String>,
+    dry_run: Option<bool>,
+) -> Result<JetBrainsApplyResult, String> {
+    let provider = {
+        let db = state.0.lock().await;
+        get_favorite_provider_internal(&db, &provider_id).await?
+    };
+
+    let base_url = provider
+        .provider_config
+        .options
+        .as_ref()
+        .and_then(|o| o.base_url.clone())
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| format!("Provider '{}' has no base URL", provider_id))?;
+    let url = format!("{}/chat/completions", base_url.trim_end_matches('/'));
+
+    let mut result = JetBrainsApplyResult {
+        updated: Vec::new(),
+        failed: Vec::new(),
+        changes: Vec::new(),
+    };
+
+    result.changes = change_history::preview_sync(dry_run.unwrap_or(false), || {
+        write_proxy_ai_settings(config_dirs, &url, &model_id, &mut result.updated, &mut result.failed);
+        Ok::<_, String>(())
+    })?;
+    Ok(result)
+}
+
+/// Write the endpoint and model into each IDE's ProxyAI settings, sorting the
+/// config dirs into `updated` and `failed`
+fn write_proxy_ai_settings(
+    config_dirs: Vec<String>,
+    url: &str,
+    model_id: &str,
+    updated: &mut Vec<String>,
+    failed: &mut Vec<String>,
+) {
+    for config_dir in config_dirs {
+        let options_dir = Path::new(&config_dir).join("options");
+        let settings_path = options_dir.join(PROXY_AI_SETTINGS_FILE);
+
+        let outcome = (|| -> Result<(), String> {
+            fs::create_dir_all(&options_dir)
+                .map_err(|e| format!("Failed to create options directory: {}", e))?;
+            let content = fs::read_to_string(&settings_path).unwrap_or_else(|_| empty_settings_xml());
+            let content = set_option(&content, "url", url);
+            let content = set_option(&content, "model", model_id);
+            change_history::write_config("jetbrains", &settings_path, content)
+                .map_err(|e| format!("Failed to write {}: {}", PROXY_AI_SETTINGS_FILE, e))
+        })();
+
+        match outcome {
+            Ok(()) => updated.push(config_dir),
+            Err(e) => {
+                log::warn!("Failed to apply provider to {}: {}", config_dir, e);
+                failed.push(config_dir);
+            }
+        }
+    }
+}
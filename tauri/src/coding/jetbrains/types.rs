@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// JetBrains Types
+// ============================================================================
+
+/// Detected JetBrains IDE configuration directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JetBrainsIde {
+    /// Directory name, e.g. "IntelliJIdea2024.3"
+    pub name: String,
+    /// IDE config directory
+    pub config_dir: String,
+    /// ProxyAI (formerly CodeGPT) plugin installed
+    pub has_proxy_ai: bool,
+    /// Base URL currently configured for ProxyAI's custom OpenAI service
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy_ai_url: Option<String>,
+}
+
+/// Result of applying a provider to JetBrains IDEs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JetBrainsApplyResult {
+    pub updated: Vec<String>,
+    pub failed: Vec<String>,
+    /// Diffs of the settings files on a dry run
+    #[serde(default)]
+    pub changes: Vec<crate::coding::change_history::ConfigDiff>,
+}
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// Kilo Code Types
+// ============================================================================
+
+/// Kilo Code provider profile (one entry of `providerProfiles.apiConfigs`)
+///
+/// `api_provider` follows Kilo Code's provider ids: "anthropic" | "openai" | "openrouter" | ...
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KiloCodeProfile {
+    pub name: String,
+    pub api_provider: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_id: Option<String>,
+}
+
+/// Profiles read from a Kilo Code settings export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KiloCodeProfilesInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_profile: Option<String>,
+    pub profiles: Vec<KiloCodeProfile>,
+}
+
+/// Kilo Code detection result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KiloCodeStatus {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extension_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extension_version: Option<String>,
+    /// Editors that have Kilo Code globalStorage
+    pub editors: Vec<String>,
+    /// Settings file managed by the toolbox (auto-imported by Kilo Code on startup)
+    pub settings_path: String,
+}
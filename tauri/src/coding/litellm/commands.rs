@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use serde_yaml::{Mapping, Value as YamlValue};
+
+use crate::coding::change_history;
+use crate::coding::open_code::get_favorite_provider_internal;
+use crate::coding::open_code::types::OpenCodeFavoriteProvider;
+use crate::db::DbState;
+use super::types::*;
+
+// ============================================================================
+// LiteLLM Helpers
+// ============================================================================
+
+/// LiteLLM model prefix for an OpenCode SDK package
+fn litellm_prefix(npm: &str) -> &'static str {
+    match npm {
+        "@ai-sdk/anthropic" => "anthropic",
+        "@ai-sdk/google" => "gemini",
+        "@ai-sdk/azure" => "azure",
+        _ => "openai",
+    }
+}
+
+fn yaml_str(value: &str) -> YamlValue {
+    YamlValue::String(value.to_string())
+}
+
+/// Build `model_list` entries for one provider
+fn model_entries(provider: &OpenCodeFavoriteProvider, model_ids: &[String]) -> Vec<YamlValue> {
+    let options = provider.provider_config.options.as_ref();
+    let prefix = litellm_prefix(&provider.npm);
+
+    let mut ids: Vec<String> = if model_ids.is_empty() {
+        provider.provider_config.models.keys().cloned().collect()
+    } else {
+        model_ids.to_vec()
+    };
+    ids.sort();
+
+    ids.into_iter()
+        .map(|id| {
+            let mut params = Mapping::new();
+            params.insert(yaml_str("model"), yaml_str(&format!("{}/{}", prefix, id)));
+            if let Some(base_url) = options.and_then(|o| o.base_url.as_ref()).filter(|s| !s.is_empty()) {
+                params.insert(yaml_str("api_base"), yaml_str(base_url));
+            }
+            if let Some(api_key) = options.and_then(|o| o.api_key.as_ref()).filter(|s| !s.is_empty()) {
+                params.insert(yaml_str("api_key"), yaml_str(api_key));
+            }
+
+            let mut entry = Mapping::new();
+            entry.insert(yaml_str("model_name"), yaml_str(&id));
+            entry.insert(yaml_str("litellm_params"), YamlValue::Mapping(params));
+            YamlValue::Mapping(entry)
+        })
+        .collect()
+}
+
+/// Check a parsed LiteLLM config; returns (model count, errors, warnings)
+fn check_config(config: &YamlValue) -> (usize, Vec<String>, Vec<String>) {
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+
+    let Some(model_list) = config.get("model_list").and_then(|v| v.as_sequence()) else {
+        errors.push("`model_list` is missing or not a list".to_string());
+        return (0, errors, warnings);
+    };
+
+    let mut names: HashMap<String, usize> = HashMap::new();
+    for (index, entry) in model_list.iter().enumerate() {
+        let name = entry.get("model_name").and_then(|v| v.as_str());
+        let params = entry.get("litellm_params");
+        match name {
+            Some(name) if !name.is_empty() => *names.entry(name.to_string()).or_default() += 1,
+            _ => errors.push(format!("model_list[{}]: `model_name` is required", index)),
+        }
+        match params.and_then(|p| p.get("model")).and_then(|v| v.as_str()) {
+            Some(model) if model.contains('/') => {}
+            Some(model) => warnings.push(format!(
+                "model_list[{}]: `{}` has no provider prefix (e.g. openai/{})",
+                index, model, model
+            )),
+            None => errors.push(format!("model_list[{}]: `litellm_params.model` is required", index)),
+        }
+        if params.and_then(|p| p.get("api_key")).is_none() {
+            warnings.push(format!(
+                "model_list[{}]: no `api_key`, LiteLLM will fall back to environment variables",
+                index
+            ));
+        }
+    }
+
+    // Repeated names are load-balanced by LiteLLM, which may not be intended
+    let mut duplicates: Vec<_> = names.into_iter().filter(|(_, count)| *count > 1).collect();
+    duplicates.sort();
+    for (name, count) in duplicates {
+        warnings.push(format!(
+            "`{}` appears {} times and will be load-balanced across deployments",
+            name, count
+        ));
+    }
+
+    (model_list.len(), errors, warnings)
+}
+
+// ============================================================================
+// LiteLLM Commands
+// ============================================================================
+
+/// Generate a LiteLLM proxy `config.yaml` from toolbox providers
+/// Writes the file when `output_path` is given (unless `dry_run`, which returns
+/// its diff), otherwise returns a preview.
+/// Existing settings in the target file (router_settings, general_settings, ...)
+/// are preserved; only `model_list` is replaced.
+#[tauri::command]
+pub async fn generate_litellm_config(
+    state: tauri::State<'_, DbState>,
+    selections: Vec<LiteLlmModelSelection>,
+    output_path: Option<String>,
+    dry_run: Option<bool>,
+) -> Result<LiteLlmConfigResult, String> {
+    if selections.is_empty() {
+        return Err("Select at least one provider".to_string());
+    }
+
+    let mut model_list = Vec::new();
+    {
+        let db = state.0.lock().await;
+        for selection in &selections {
+            let provider = get_favorite_provider_internal(&db, &selection.provider_id).await?;
+            model_list.extend(model_entries(&provider, &selection.model_ids));
+        }
+    }
+
+    let output_path = output_path
+        .filter(|p| !p.trim().is_empty())
+        .map(|p| PathBuf::from(p.trim()));
+
+    let mut config = match &output_path {
+        Some(path) if path.exists() => {
+            let content = fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read LiteLLM config: {}", e))?;
+            serde_yaml::from_str::<YamlValue>(&content)
+                .map_err(|e| format!("Failed to parse LiteLLM config: {}", e))?
+        }
+        _ => YamlValue::Mapping(Mapping::new()),
+    };
+    if !config.is_mapping() {
+        config = YamlValue::Mapping(Mapping::new());
+    }
+    config
+        .as_mapping_mut()
+        .unwrap()
+        .insert(yaml_str("model_list"), YamlValue::Sequence(model_list));
+
+    let (models, errors, warnings) = check_config(&config);
+    if !errors.is_empty() {
+        return Err(errors.join("\n"));
+    }
+
+    let content = serde_yaml::to_string(&config)
+        .map_err(|e| format!("Failed to serialize LiteLLM config: {}", e))?;
+
+    let mut changes = Vec::new();
+    if let Some(path) = &output_path {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+        }
+        changes = change_history::preview_sync(dry_run.unwrap_or(false), || {
+            change_history::write_config("litellm", path, &content)
+                .map_err(|e| format!("Failed to write LiteLLM config: {}", e))
+        })?;
+    }
+
+    Ok(LiteLlmConfigResult {
+        content,
+        path: output_path.map(|p| p.to_string_lossy().to_string()),
+        models,
+        warnings,
+        changes,
+    })
+}
+
+/// Validate a LiteLLM config file
+#[tauri::command]
+pub fn validate_litellm_config(path: String) -> Result<LiteLlmValidation, String> {
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read LiteLLM config: {}", e))?;
+
+    let config: YamlValue = match serde_yaml::from_str(&content) {
+        Ok(config) => config,
+        Err(e) => {
+            return Ok(LiteLlmValidation {
+                valid: false,
+                models: 0,
+                errors: vec![format!("Invalid YAML: {}", e)],
+                warnings: Vec::new(),
+            })
+        }
+    };
+
+    let (models, errors, warnings) = check_config(&config);
+    Ok(LiteLlmValidation {
+        valid: errors.is_empty(),
+        models,
+        errors,
+        warnings,
+    })
+}
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// LiteLLM Types
+// ============================================================================
+
+/// Provider and the models to expose through LiteLLM
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiteLlmModelSelection {
+    pub provider_id: String,
+    /// Empty means all models of the provider
+    #[serde(default)]
+    pub model_ids: Vec<String>,
+}
+
+/// Generated LiteLLM config
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiteLlmConfigResult {
+    pub content: String,
+    /// Path written to (None for preview)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    pub models: usize,
+    pub warnings: Vec<String>,
+    /// Diff of the output file on a dry run
+    #[serde(default)]
+    pub changes: Vec<crate::coding::change_history::ConfigDiff>,
+}
+
+/// Result of validating a LiteLLM config file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiteLlmValidation {
+    pub valid: bool,
+    pub models: usize,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
@@ -0,0 +1,238 @@
+use std::fs;
+use serde_json::Value;
+
+use crate::coding::claude_code::upsert_claude_provider_by_name;
+use crate::coding::open_code::{
+    apply_config_internal as apply_opencode_config, build_openai_compatible_provider,
+    read_opencode_config, upsert_opencode_favorite_provider, OpenCodeModelLimit, OpenCodeProvider,
+    ReadConfigResult,
+};
+use crate::db::DbState;
+use crate::http_client;
+use super::types::*;
+use crate::events::{emit_change, ChangeAction};
+
+const DEFAULT_LM_STUDIO_PORT: u16 = 1234;
+
+/// Provider id / name used when registering LM Studio
+const LM_STUDIO_PROVIDER_ID: &str = "lmstudio";
+const LM_STUDIO_PROVIDER_NAME: &str = "LM Studio";
+
+/// LM Studio accepts any key, but clients require one
+const LM_STUDIO_API_KEY: &str = "lm-studio";
+
+const API_TIMEOUT_SECS: u64 = 10;
+
+// ============================================================================
+// LM Studio Helpers
+// ============================================================================
+
+/// Read the server port from ~/.lmstudio/.internal/http-server-config.json
+fn get_lm_studio_port() -> u16 {
+    dirs::home_dir()
+        .map(|home| {
+            home.join(".lmstudio")
+                .join(".internal")
+                .join("http-server-config.json")
+        })
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+        .and_then(|config| config.get("port").and_then(|v| v.as_u64()))
+        .and_then(|port| u16::try_from(port).ok())
+        .unwrap_or(DEFAULT_LM_STUDIO_PORT)
+}
+
+fn get_lm_studio_host() -> (String, u16) {
+    let port = get_lm_studio_port();
+    (format!("http://127.0.0.1:{}", port), port)
+}
+
+/// Local server requests bypass the proxy
+fn local_client() -> Result<reqwest::Client, String> {
+    http_client::create_client_no_proxy(API_TIMEOUT_SECS)
+}
+
+async fn get_json(url: &str) -> Result<Value, String> {
+    let response = local_client()?
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to LM Studio: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("LM Studio returned HTTP {}", response.status()));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse LM Studio response: {}", e))
+}
+
+/// List models via the v0 REST API (with load state), falling back to /v1/models
+async fn fetch_models(host: &str) -> Result<Vec<LmStudioModel>, String> {
+    if let Ok(response) = get_json(&format!("{}/api/v0/models", host)).await {
+        if let Some(items) = response.get("data").and_then(|v| v.as_array()) {
+            return Ok(items
+                .iter()
+                .filter_map(|m| {
+                    Some(LmStudioModel {
+                        id: m.get("id")?.as_str()?.to_string(),
+                        model_type: m.get("type").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                        loaded: m.get("state").and_then(|v| v.as_str()) == Some("loaded"),
+                        max_context_length: m.get("max_context_length").and_then(|v| v.as_i64()),
+                    })
+                })
+                .collect());
+        }
+    }
+
+    let response = get_json(&format!("{}/v1/models", host)).await?;
+    Ok(response
+        .get("data")
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|m| {
+                    Some(LmStudioModel {
+                        id: m.get("id")?.as_str()?.to_string(),
+                        model_type: None,
+                        loaded: true,
+                        max_context_length: None,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// Build the OpenCode provider config from chat-capable models
+fn build_provider(host: &str, models: &[LmStudioModel]) -> OpenCodeProvider {
+    let chat_models: Vec<&LmStudioModel> = models
+        .iter()
+        .filter(|m| m.model_type.as_deref() != Some("embeddings"))
+        .collect();
+    let ids: Vec<String> = chat_models.iter().map(|m| m.id.clone()).collect();
+
+    let mut provider = build_openai_compatible_provider(
+        LM_STUDIO_PROVIDER_NAME,
+        &format!("{}/v1", host),
+        Some(LM_STUDIO_API_KEY.to_string()),
+        &ids,
+    );
+    for model in chat_models {
+        if let (Some(context), Some(entry)) = (model.max_context_length, provider.models.get_mut(&model.id)) {
+            entry.limit = Some(OpenCodeModelLimit {
+                context: Some(context),
+                output: None,
+            });
+        }
+    }
+    provider
+}
+
+/// Save the provider to the favorite store and to opencode.json (if it exists)
+async fn save_opencode_provider(
+    state: &tauri::State<'_, DbState>,
+    app: &tauri::AppHandle,
+    provider: OpenCodeProvider,
+) -> Result<(), String> {
+    upsert_opencode_favorite_provider(
+        state.clone(),
+        LM_STUDIO_PROVIDER_ID.to_string(),
+        provider.clone(),
+        None,
+    )
+    .await?;
+
+    if let ReadConfigResult::Success { mut config } = read_opencode_config(state.clone()).await? {
+        config
+            .provider
+            .get_or_insert_with(Default::default)
+            .insert(LM_STUDIO_PROVIDER_ID.to_string(), provider);
+        apply_opencode_config(state.clone(), app, config, false).await?;
+    }
+    Ok(())
+}
+
+// ============================================================================
+// LM Studio Commands
+// ============================================================================
+
+/// Detect the LM Studio local server
+#[tauri::command]
+pub async fn get_lm_studio_status() -> Result<LmStudioStatus, String> {
+    let (host, port) = get_lm_studio_host();
+    let running = get_json(&format!("{}/v1/models", host)).await.is_ok();
+    Ok(LmStudioStatus { host, port, running })
+}
+
+/// List models available on the LM Studio server
+#[tauri::command]
+pub async fn list_lm_studio_models() -> Result<Vec<LmStudioModel>, String> {
+    let (host, _) = get_lm_studio_host();
+    fetch_models(&host).await
+}
+
+/// Register LM Studio as a provider in the given targets ("opencode", "claude")
+#[tauri::command]
+pub async fn register_lm_studio_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    targets: Vec<String>,
+) -> Result<Vec<LmStudioRegisterResult>, String> {
+    let (host, _) = get_lm_studio_host();
+    let models = fetch_models(&host).await?;
+    let provider = build_provider(&host, &models);
+
+    // Prefer a loaded model as the Claude Code default
+    let default_model = models
+        .iter()
+        .filter(|m| m.model_type.as_deref() != Some("embeddings"))
+        .max_by_key(|m| m.loaded)
+        .map(|m| m.id.clone());
+
+    let mut results = Vec::new();
+    for target in targets {
+        let outcome = match target.as_str() {
+            "opencode" => save_opencode_provider(&state, &app, provider.clone()).await,
+            "claude" => {
+                let db = state.0.lock().await;
+                upsert_claude_provider_by_name(
+                    &db,
+                    LM_STUDIO_PROVIDER_NAME,
+                    &host,
+                    LM_STUDIO_API_KEY,
+                    default_model.as_ref(),
+                    Some("https://lmstudio.ai"),
+                )
+                .await
+                .map(|_| ())
+            }
+            other => Err(format!("Unsupported target: {}", other)),
+        };
+        results.push(LmStudioRegisterResult {
+            target,
+            success: outcome.is_ok(),
+            error: outcome.err(),
+        });
+    }
+
+    emit_change(&app, "claude_provider", ChangeAction::Created, None, "window");
+    Ok(results)
+}
+
+/// Refresh the registered LM Studio provider's model list from the server
+/// Returns the number of models now registered
+#[tauri::command]
+pub async fn sync_lm_studio_models(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+) -> Result<usize, String> {
+    let (host, _) = get_lm_studio_host();
+    let models = fetch_models(&host).await?;
+    let provider = build_provider(&host, &models);
+    let count = provider.models.len();
+
+    save_opencode_provider(&state, &app, provider).await?;
+    Ok(count)
+}
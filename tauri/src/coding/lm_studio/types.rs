@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// LM Studio Types
+// ============================================================================
+
+/// LM Studio local server detection result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LmStudioStatus {
+    /// Server URL, e.g. http://127.0.0.1:1234
+    pub host: String,
+    pub port: u16,
+    pub running: bool,
+}
+
+/// Model known to the LM Studio server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LmStudioModel {
+    pub id: String,
+    /// "llm" | "vlm" | "embeddings" (None on servers without the v0 API)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_type: Option<String>,
+    pub loaded: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_context_length: Option<i64>,
+}
+
+/// Per-target result of registering LM Studio as a provider
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LmStudioRegisterResult {
+    /// "opencode" | "claude"
+    pub target: String,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
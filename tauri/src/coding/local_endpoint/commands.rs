@@ -0,0 +1,227 @@
+use futures_util::future::join_all;
+use serde_json::Value;
+
+use crate::coding::open_code::{
+    build_openai_compatible_provider, upsert_opencode_favorite_provider, OpenCodeFavoriteProvider,
+};
+use crate::db::DbState;
+use crate::http_client;
+use super::types::*;
+
+/// Ports tried when a host is given without one:
+/// vLLM, llama.cpp server, Ollama, LM Studio, TGI / text-generation-webui
+const COMMON_PORTS: [u16; 6] = [8000, 8080, 11434, 1234, 3000, 5000];
+
+const PROBE_TIMEOUT_SECS: u64 = 3;
+
+// ============================================================================
+// Discovery Helpers
+// ============================================================================
+
+/// Expand user input into candidate `/v1` base URLs
+///
+/// - `host` -> every common port
+/// - `host:port`, `http://host:port`, `http://host:port/v1` -> that endpoint
+fn candidate_base_urls(input: &str) -> Vec<String> {
+    let input = input.trim().trim_end_matches('/');
+    if input.is_empty() {
+        return Vec::new();
+    }
+
+    let (scheme, rest) = match input.split_once("://") {
+        Some((scheme, rest)) => (scheme, rest),
+        None => ("http", input),
+    };
+    let (authority, path) = match rest.split_once('/') {
+        Some((authority, path)) => (authority, format!("/{}", path)),
+        None => (rest, String::new()),
+    };
+    let path = if path.is_empty() { "/v1".to_string() } else { path };
+
+    // IPv6 literals keep their brackets; a port follows the closing bracket
+    let has_port = match authority.rfind(']') {
+        Some(end) => authority[end..].contains(':'),
+        None => authority.contains(':'),
+    };
+    if has_port {
+        return vec![format!("{}://{}{}", scheme, authority, path)];
+    }
+    COMMON_PORTS
+        .iter()
+        .map(|port| format!("{}://{}:{}{}", scheme, authority, port, path))
+        .collect()
+}
+
+/// Server root (base URL without the trailing /v1)
+fn server_root(base_url: &str) -> &str {
+    base_url.strip_suffix("/v1").unwrap_or(base_url)
+}
+
+async fn get_json(client: &reqwest::Client, url: &str) -> Option<Value> {
+    let response = client.get(url).send().await.ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+    response.json().await.ok()
+}
+
+/// Identify the inference backend from `owned_by` and backend-specific routes
+async fn identify_backend(client: &reqwest::Client, base_url: &str, models: &Value) -> String {
+    let owned_by = models
+        .get("data")
+        .and_then(|v| v.as_array())
+        .and_then(|items| items.first())
+        .and_then(|m| m.get("owned_by"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    match owned_by {
+        "vllm" => return "vllm".to_string(),
+        "llamacpp" => return "llama.cpp".to_string(),
+        _ => {}
+    }
+
+    let root = server_root(base_url);
+    if get_json(client, &format!("{}/api/version", root)).await.is_some() {
+        return "ollama".to_string();
+    }
+    if get_json(client, &format!("{}/api/v0/models", root)).await.is_some() {
+        return "lmstudio".to_string();
+    }
+    if let Some(props) = get_json(client, &format!("{}/props", root)).await {
+        if props.get("default_generation_settings").is_some() {
+            return "llama.cpp".to_string();
+        }
+    }
+    if let Some(info) = get_json(client, &format!("{}/info", root)).await {
+        if info.get("model_id").is_some() {
+            return "tgi".to_string();
+        }
+    }
+    "openai-compatible".to_string()
+}
+
+async fn probe(client: &reqwest::Client, base_url: String) -> EndpointProbeResult {
+    let response = match client.get(format!("{}/models", base_url)).send().await {
+        Ok(response) => response,
+        Err(e) => {
+            return EndpointProbeResult {
+                base_url,
+                reachable: false,
+                backend: None,
+                models: Vec::new(),
+                error: Some(e.to_string()),
+            }
+        }
+    };
+
+    let status = response.status();
+    let models: Option<Value> = if status.is_success() {
+        response.json().await.ok()
+    } else {
+        None
+    };
+    let Some(models) = models.filter(|m| m.get("data").is_some_and(|d| d.is_array())) else {
+        return EndpointProbeResult {
+            base_url,
+            reachable: true,
+            backend: None,
+            models: Vec::new(),
+            error: Some(format!("Not an OpenAI-compatible /models endpoint (HTTP {})", status)),
+        };
+    };
+
+    let mut model_ids: Vec<String> = models["data"]
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|m| m.get("id").and_then(|v| v.as_str()).map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    model_ids.sort();
+
+    EndpointProbeResult {
+        backend: Some(identify_backend(client, &base_url, &models).await),
+        base_url,
+        reachable: true,
+        models: model_ids,
+        error: None,
+    }
+}
+
+// ============================================================================
+// Discovery Commands
+// ============================================================================
+
+/// Probe user-specified hosts/endpoints for OpenAI-compatible `/v1/models`
+/// Hosts without a port are scanned on common inference server ports; only
+/// responding endpoints are returned for scanned hosts.
+#[tauri::command]
+pub async fn probe_local_endpoints(endpoints: Vec<String>) -> Result<Vec<EndpointProbeResult>, String> {
+    let client = http_client::create_client_no_proxy(PROBE_TIMEOUT_SECS)?;
+
+    let mut results = Vec::new();
+    for input in endpoints {
+        let candidates = candidate_base_urls(&input);
+        let scanned = candidates.len() > 1;
+        let probes = join_all(candidates.into_iter().map(|url| probe(&client, url))).await;
+        results.extend(probes.into_iter().filter(|r| !scanned || r.reachable));
+    }
+    Ok(results)
+}
+
+/// Create (or update) a provider from a discovered endpoint with its current models
+#[tauri::command]
+pub async fn create_provider_from_endpoint(
+    state: tauri::State<'_, DbState>,
+    base_url: String,
+    provider_id: String,
+    name: Option<String>,
+    api_key: Option<String>,
+) -> Result<OpenCodeFavoriteProvider, String> {
+    let provider_id = provider_id.trim().to_string();
+    if provider_id.is_empty() {
+        return Err("Provider ID is required".to_string());
+    }
+
+    let client = http_client::create_client_no_proxy(PROBE_TIMEOUT_SECS)?;
+    let result = probe(&client, base_url.trim().trim_end_matches('/').to_string()).await;
+    if let Some(error) = result.error {
+        return Err(format!("Endpoint is not usable: {}", error));
+    }
+
+    let name = name
+        .filter(|n| !n.trim().is_empty())
+        .unwrap_or_else(|| provider_id.clone());
+    let provider_config = build_openai_compatible_provider(
+        &name,
+        &result.base_url,
+        // Local servers usually ignore the key, but clients require one
+        Some(api_key.filter(|k| !k.is_empty()).unwrap_or_else(|| "sk-local".to_string())),
+        &result.models,
+    );
+
+    upsert_opencode_favorite_provider(state, provider_id, provider_config, None).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn candidate_base_urls_expands_bare_hosts() {
+        assert_eq!(
+            candidate_base_urls("192.168.1.20:8000"),
+            vec!["http://192.168.1.20:8000/v1"]
+        );
+        assert_eq!(
+            candidate_base_urls("https://gpu.lan:8443/v1/"),
+            vec!["https://gpu.lan:8443/v1"]
+        );
+
+        let scanned = candidate_base_urls("gpu.lan");
+        assert_eq!(scanned.len(), COMMON_PORTS.len());
+        assert_eq!(scanned[0], "http://gpu.lan:8000/v1");
+    }
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// Local Endpoint Discovery Types
+// ============================================================================
+
+/// Result of probing one OpenAI-compatible endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EndpointProbeResult {
+    /// Probed base URL (ending in /v1)
+    pub base_url: String,
+    pub reachable: bool,
+    /// "vllm" | "llama.cpp" | "ollama" | "lmstudio" | "tgi" | "openai-compatible"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backend: Option<String>,
+    pub models: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
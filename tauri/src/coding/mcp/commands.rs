@@ -11,11 +11,13 @@ use super::types::{
     CreateMcpServerInput, McpDiscoveredServerDto, McpImportResultDto, McpScanResultDto, McpServer, McpServerDto,
     McpSyncDetail, McpSyncResultDto, UpdateMcpServerInput, FavoriteMcp, FavoriteMcpDto, FavoriteMcpInput, now_ms,
 };
+use crate::coding::change_history;
 use crate::coding::tools::{
     custom_store, get_mcp_runtime_tools, runtime_tool_by_key, RuntimeToolDto, is_tool_installed,
     to_runtime_tool_dto, resolve_mcp_config_path,
 };
 use crate::DbState;
+use crate::events::{emit_change, ChangeAction};
 
 // ==================== MCP Server CRUD ====================
 
@@ -96,7 +98,7 @@ pub async fn mcp_create_server<R: Runtime>(
         .ok_or("Failed to get created server")?;
 
     // Emit mcp-changed for WSL sync
-    let _ = app.emit("config-changed", "window");
+    emit_change(&app, "mcp_server", ChangeAction::Created, None, "window");
     let _ = app.emit("mcp-changed", "window");
 
     let sync_details = parse_sync_details_dto(&created);
@@ -181,7 +183,7 @@ pub async fn mcp_update_server<R: Runtime>(
         .ok_or("Failed to get updated server")?;
 
     // Emit mcp-changed for WSL sync
-    let _ = app.emit("config-changed", "window");
+    emit_change(&app, "mcp_server", ChangeAction::Updated, None, "window");
     let _ = app.emit("mcp-changed", "window");
 
     let sync_details = parse_sync_details_dto(&updated);
@@ -222,7 +224,7 @@ pub async fn mcp_delete_server<R: Runtime>(
     mcp_store::delete_mcp_server(&state, &serverId).await?;
 
     // Emit mcp-changed for WSL sync
-    let _ = app.emit("config-changed", "window");
+    emit_change(&app, "mcp_server", ChangeAction::Deleted, None, "window");
     let _ = app.emit("mcp-changed", "window");
 
     Ok(())
@@ -274,7 +276,7 @@ pub async fn mcp_toggle_tool<R: Runtime>(
     }
 
     // Emit config-changed and mcp-changed events
-    let _ = app.emit("config-changed", "window");
+    emit_change(&app, "mcp_server", ChangeAction::Updated, None, "window");
     let _ = app.emit("mcp-changed", "window");
 
     Ok(is_enabled)
@@ -342,7 +344,7 @@ pub async fn mcp_sync_to_tool<R: Runtime>(
     }
 
     // Emit config-changed and mcp-changed events
-    let _ = app.emit("config-changed", "window");
+    emit_change(&app, "mcp_server", ChangeAction::Synced, None, "window");
     let _ = app.emit("mcp-changed", "window");
 
     Ok(results)
@@ -396,12 +398,41 @@ pub async fn mcp_sync_all<R: Runtime>(
     }
 
     // Emit config-changed and mcp-changed events
-    let _ = app.emit("config-changed", "window");
+    emit_change(&app, "mcp_server", ChangeAction::Synced, None, "window");
     let _ = app.emit("mcp-changed", "window");
 
     Ok(results)
 }
 
+/// Config changes `mcp_sync_to_tool` (or, without `toolKey`, `mcp_sync_all`)
+/// would make, as diffs; nothing is written
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn mcp_preview_sync(
+    state: State<'_, DbState>,
+    toolKey: Option<String>,
+) -> Result<Vec<change_history::ConfigDiff>, String> {
+    let custom_tools = custom_store::get_custom_tools(&state).await.unwrap_or_default();
+    let servers = mcp_store::get_mcp_servers(&state).await?;
+
+    change_history::preview_sync(true, || {
+        for server in &servers {
+            for tool_key in &server.enabled_tools {
+                if toolKey.as_ref().is_some_and(|key| key != tool_key) {
+                    continue;
+                }
+                let Some(tool) = runtime_tool_by_key(tool_key, &custom_tools) else {
+                    continue;
+                };
+                if is_tool_installed(&tool) {
+                    sync_server_to_tool(server, &tool)?;
+                }
+            }
+        }
+        Ok(())
+    })
+}
+
 /// Import MCP servers from a tool's config file
 /// After import, automatically sync to specified tools (or preferred tools if not specified)
 /// If a server with the same name exists but has different config, create with suffix
@@ -574,13 +605,13 @@ async fn mcp_scan_servers_inner(state: &DbState) -> Result<McpScanResultDto, Str
                 continue;
             }
 
-            eprintln!("[DEBUG][mcp_scan_servers] scanning tool: {}", tool.key);
+            log::debug!("[mcp_scan_servers] scanning tool: {}", tool.key);
             total_tools_scanned += 1;
 
             // Try to import servers from this tool
             match import_servers_from_tool(tool) {
                 Ok(imported) => {
-                    eprintln!("[DEBUG][mcp_scan_servers] {} imported {} servers", tool.key, imported.len());
+                    log::debug!("[mcp_scan_servers] {} imported {} servers", tool.key, imported.len());
                     for server in imported {
                         // Skip servers that already exist in the database
                         if existing_names.contains(&server.name) {
@@ -596,7 +627,7 @@ async fn mcp_scan_servers_inner(state: &DbState) -> Result<McpScanResultDto, Str
                 }
                 Err(e) => {
                     // Log error but continue scanning
-                    eprintln!("Failed to scan {}: {}", tool.key, e);
+                    log::warn!("Failed to scan {}: {}", tool.key, e);
                 }
             }
         }
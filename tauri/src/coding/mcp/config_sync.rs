@@ -11,6 +11,7 @@ use serde_json::Value;
 use super::command_normalize;
 use super::format_configs::get_format_config;
 use super::types::{McpServer, McpSyncDetail, now_ms};
+use crate::coding::change_history;
 use crate::coding::tools::{resolve_mcp_config_path, McpFormatConfig, RuntimeTool};
 
 /// Sync an MCP server to a specific tool's config file
@@ -68,8 +69,8 @@ fn sync_server_to_json(
     format_config: Option<&McpFormatConfig>,
 ) -> Result<(), String> {
     // Read existing config or create new (json5 handles both JSON and JSONC)
-    let mut config: Value = if config_path.exists() {
-        let content = std::fs::read_to_string(config_path)
+    let mut config: Value = if change_history::config_exists(config_path) {
+        let content = change_history::read_config(config_path)
             .map_err(|e| format!("Failed to read config file: {}", e))?;
         let content = content.trim();
         if content.is_empty() {
@@ -109,7 +110,7 @@ fn sync_server_to_json(
     // which is valid JSON5 (JSON is a subset of JSON5)
     let content = serde_json::to_string_pretty(&config)
         .map_err(|e| format!("Failed to serialize config: {}", e))?;
-    std::fs::write(config_path, content)
+    change_history::write_config("mcp", config_path, content)
         .map_err(|e| format!("Failed to write config file: {}", e))?;
 
     Ok(())
@@ -121,11 +122,11 @@ fn remove_server_from_json(
     server_name: &str,
     field: &str,
 ) -> Result<(), String> {
-    if !config_path.exists() {
+    if !change_history::config_exists(config_path) {
         return Ok(()); // Nothing to remove
     }
 
-    let content = std::fs::read_to_string(config_path)
+    let content = change_history::read_config(config_path)
         .map_err(|e| format!("Failed to read config file: {}", e))?;
     let content = content.trim();
     if content.is_empty() {
@@ -144,7 +145,7 @@ fn remove_server_from_json(
     // Write back to file
     let content = serde_json::to_string_pretty(&config)
         .map_err(|e| format!("Failed to serialize config: {}", e))?;
-    std::fs::write(config_path, content)
+    change_history::write_config("mcp", config_path, content)
         .map_err(|e| format!("Failed to write config file: {}", e))?;
 
     Ok(())
@@ -165,8 +166,8 @@ fn sync_server_to_toml(
     }
 
     // Read existing config or create new document
-    let mut doc = if config_path.exists() {
-        let content = std::fs::read_to_string(config_path)
+    let mut doc = if change_history::config_exists(config_path) {
+        let content = change_history::read_config(config_path)
             .map_err(|e| format!("Failed to read config file: {}", e))?;
         if content.trim().is_empty() {
             toml_edit::DocumentMut::new()
@@ -191,7 +192,7 @@ fn sync_server_to_toml(
 
     // Write back to file
     let content = doc.to_string();
-    std::fs::write(config_path, content)
+    change_history::write_config("mcp", config_path, content)
         .map_err(|e| format!("Failed to write config file: {}", e))?;
 
     Ok(())
@@ -203,11 +204,11 @@ fn remove_server_from_toml(
     server_name: &str,
     field: &str,
 ) -> Result<(), String> {
-    if !config_path.exists() {
+    if !change_history::config_exists(config_path) {
         return Ok(()); // Nothing to remove
     }
 
-    let content = std::fs::read_to_string(config_path)
+    let content = change_history::read_config(config_path)
         .map_err(|e| format!("Failed to read config file: {}", e))?;
 
     let mut doc = match content.parse::<toml_edit::DocumentMut>() {
@@ -222,7 +223,7 @@ fn remove_server_from_toml(
 
     // Write back to file
     let content = doc.to_string();
-    std::fs::write(config_path, content)
+    change_history::write_config("mcp", config_path, content)
         .map_err(|e| format!("Failed to write config file: {}", e))?;
 
     Ok(())
@@ -7,6 +7,7 @@ use tauri::{AppHandle, Emitter, Manager, Runtime};
 use super::mcp_store;
 use crate::coding::tools::{custom_store, get_mcp_runtime_tools, is_tool_installed};
 use crate::DbState;
+use crate::events::{emit_change, ChangeAction};
 
 /// Tray data for MCP servers section
 #[derive(Debug)]
@@ -123,7 +124,7 @@ pub async fn apply_mcp_tool_toggle<R: Runtime>(
     }
 
     // Emit config-changed event (from tray)
-    let _ = app.emit("config-changed", "tray");
+    emit_change(app, "mcp_server", ChangeAction::Updated, None, "tray");
     let _ = app.emit("mcp-changed", "tray");
 
     Ok(())
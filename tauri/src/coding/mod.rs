@@ -1,12 +1,52 @@
+pub mod balance;
+pub mod change_history;
 pub mod claude_code;
+pub mod cli_detect;
+pub mod cli_scan;
+pub mod config_format;
+pub mod cline;
 pub mod codex;
+pub mod continue_dev;
+pub mod crush;
+pub mod custom_tool;
+pub mod cursor;
+pub mod gemini_cli;
+pub mod gemini_style;
+pub mod github_copilot;
+pub mod qwen_code;
+pub mod relay;
+pub mod windsurf;
+pub mod goose;
+pub mod iflow;
+pub mod jetbrains;
+pub mod kilo_code;
+pub mod litellm;
+pub mod lm_studio;
+pub mod local_endpoint;
+pub mod amp;
+pub mod ccr;
+pub mod droid;
+pub mod roo_code;
+pub mod vault;
+pub mod warp;
 pub mod open_code;
+pub mod ollama;
+pub mod projects;
+pub mod provider_apply;
+pub mod provider_import;
+pub mod provider_store;
 pub mod oh_my_opencode;
 pub mod oh_my_opencode_slim;
 pub mod skills;
+pub mod speed_test;
+pub mod subscriptions;
+pub mod switch_analytics;
 pub mod tools;
+pub mod usage;
 pub mod mcp;
+pub mod model_rules;
 pub mod wsl;
+pub mod vscode;
 
 mod db_id;
 pub use db_id::{db_clean_id, db_extract_id, db_extract_id_opt, db_build_id};
@@ -0,0 +1,94 @@
+use crate::db::DbState;
+use crate::error::{AppError, AppResult};
+use super::rules;
+use super::types::*;
+
+// ============================================================================
+// Model Rule Commands
+// ============================================================================
+
+/// All rules in match order
+#[tauri::command]
+pub async fn list_model_rules(state: tauri::State<'_, DbState>) -> AppResult<Vec<ModelRule>> {
+    let db = state.0.lock().await;
+    Ok(rules::list_rules(&db).await?)
+}
+
+/// Create or update a rule; new rules are appended to the end of the match order
+#[tauri::command]
+pub async fn save_model_rule(
+    state: tauri::State<'_, DbState>,
+    input: ModelRuleInput,
+) -> AppResult<ModelRule> {
+    let pattern = input.pattern.trim().to_string();
+    let target_model = input.target_model.trim().to_string();
+    if pattern.is_empty() || target_model.is_empty() {
+        return Err(AppError::validation("MODEL_RULE_INCOMPLETE", &[]));
+    }
+
+    let db = state.0.lock().await;
+    let sort_index = match input.sort_index {
+        Some(index) => index,
+        None => {
+            let existing = rules::list_rules(&db).await?;
+            input
+                .id
+                .as_deref()
+                .and_then(|id| existing.iter().find(|r| r.id == id))
+                .map(|r| r.sort_index)
+                .unwrap_or_else(|| existing.iter().map(|r| r.sort_index + 1).max().unwrap_or(0))
+        }
+    };
+    let rule = ModelRule {
+        id: input.id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+        pattern,
+        target_provider: input.target_provider.map(|p| p.trim().to_string()).filter(|p| !p.is_empty()),
+        target_model,
+        enabled: input.enabled,
+        note: input.note.filter(|n| !n.trim().is_empty()),
+        sort_index,
+    };
+
+    let mut data = serde_json::to_value(&rule).map_err(AppError::invalid)?;
+    if let Some(map) = data.as_object_mut() {
+        map.remove("id");
+    }
+    db.query("UPSERT type::thing('model_rule', $id) CONTENT $data")
+        .bind(("id", rule.id.clone()))
+        .bind(("data", data))
+        .await?;
+    Ok(rule)
+}
+
+#[tauri::command]
+pub async fn delete_model_rule(state: tauri::State<'_, DbState>, id: String) -> AppResult<()> {
+    let db = state.0.lock().await;
+    db.query("DELETE type::thing('model_rule', $id)")
+        .bind(("id", id))
+        .await?;
+    Ok(())
+}
+
+/// Set the match order from a list of rule ids
+#[tauri::command]
+pub async fn reorder_model_rules(state: tauri::State<'_, DbState>, ids: Vec<String>) -> AppResult<()> {
+    let db = state.0.lock().await;
+    for (index, id) in ids.into_iter().enumerate() {
+        db.query("UPDATE type::thing('model_rule', $id) SET sort_index = $index")
+            .bind(("id", id))
+            .bind(("index", index as i64))
+            .await?;
+    }
+    Ok(())
+}
+
+/// Rule that would apply to a requested model, for previewing in the UI
+#[tauri::command]
+pub async fn resolve_model_route(
+    state: tauri::State<'_, DbState>,
+    model: String,
+) -> AppResult<Option<ModelRule>> {
+    let db = state.0.lock().await;
+    let active = rules::load_rules(&db).await;
+    Ok(rules::resolve(&active, &model).cloned())
+}
@@ -0,0 +1,20 @@
+//! Model Routing Rules
+//!
+//! Rules map a requested model name (exact or `*` wildcard, case-insensitive)
+//! to a concrete model, optionally on another provider — e.g. send
+//! "claude-3-5-haiku*" to a cheaper relay model. They are applied whenever
+//! config files are generated:
+//! - Claude Code env model variables (`ANTHROPIC_MODEL`, `ANTHROPIC_DEFAULT_*_MODEL`, ...)
+//! - CCR `Router` targets ("provider,model")
+//! - OpenCode `model` / `small_model` ("provider/model")
+//!
+//! Claude Code talks to a single provider, so only the model of a rule is
+//! used there. The first enabled rule in `sort_index` order wins.
+
+pub mod commands;
+pub mod rules;
+pub mod types;
+
+pub use commands::*;
+pub use rules::*;
+pub use types::*;
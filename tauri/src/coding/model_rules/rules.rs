@@ -0,0 +1,172 @@
+//! Rule matching and rewriting of the model references in generated configs
+
+use serde_json::{Map, Value};
+use surrealdb::engine::local::Db;
+use surrealdb::Surreal;
+
+use crate::coding::db_extract_id;
+use super::types::ModelRule;
+
+/// Claude Code env variables that name a model
+pub const CLAUDE_MODEL_ENV_KEYS: [&str; 6] = [
+    "ANTHROPIC_MODEL",
+    "ANTHROPIC_SMALL_FAST_MODEL",
+    "ANTHROPIC_DEFAULT_HAIKU_MODEL",
+    "ANTHROPIC_DEFAULT_SONNET_MODEL",
+    "ANTHROPIC_DEFAULT_OPUS_MODEL",
+    "CLAUDE_CODE_SUBAGENT_MODEL",
+];
+
+pub fn from_db_value(mut value: Value) -> Option<ModelRule> {
+    let id = db_extract_id(&value);
+    value.as_object_mut()?.insert("id".to_string(), Value::String(id));
+    serde_json::from_value(value).ok()
+}
+
+/// All rules in match order
+pub async fn list_rules(db: &Surreal<Db>) -> Result<Vec<ModelRule>, surrealdb::Error> {
+    let records: Vec<Value> = db
+        .query("SELECT *, type::string(id) as id FROM model_rule ORDER BY sort_index ASC")
+        .await?
+        .take(0)?;
+    Ok(records.into_iter().filter_map(from_db_value).collect())
+}
+
+/// Enabled rules for config generation; a broken table never blocks an apply
+pub async fn load_rules(db: &Surreal<Db>) -> Vec<ModelRule> {
+    match list_rules(db).await {
+        Ok(rules) => rules.into_iter().filter(|r| r.enabled).collect(),
+        Err(e) => {
+            log::warn!("Failed to load model rules: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Case-insensitive match where `*` stands for any run of characters
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.trim().to_lowercase();
+    let text = text.trim().to_lowercase();
+    let parts: Vec<&str> = pattern.split('*').collect();
+    if parts.len() == 1 {
+        return pattern == text;
+    }
+
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if !text.starts_with(first) || text.len() < first.len() + last.len() || !text.ends_with(last) {
+        return false;
+    }
+    let mut rest = &text[first.len()..text.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+/// First enabled rule matching the requested model
+pub fn resolve<'a>(rules: &'a [ModelRule], model: &str) -> Option<&'a ModelRule> {
+    if model.trim().is_empty() {
+        return None;
+    }
+    rules.iter().find(|r| r.enabled && glob_match(&r.pattern, model))
+}
+
+/// Rewrite a bare model name (the provider part of a rule is ignored)
+pub fn rewrite_model(rules: &[ModelRule], model: &str) -> String {
+    resolve(rules, model)
+        .map(|r| r.target_model.clone())
+        .unwrap_or_else(|| model.to_string())
+}
+
+/// Rewrite a provider-qualified reference such as "provider/model" (OpenCode)
+/// or "provider,model" (CCR); the rule's provider replaces the current one when set
+pub fn rewrite_qualified(rules: &[ModelRule], reference: &str, separator: char) -> String {
+    let Some((provider, model)) = reference.split_once(separator) else {
+        return reference.to_string();
+    };
+    match resolve(rules, model) {
+        Some(rule) => format!(
+            "{}{}{}",
+            rule.target_provider.as_deref().filter(|p| !p.is_empty()).unwrap_or(provider),
+            separator,
+            rule.target_model
+        ),
+        None => reference.to_string(),
+    }
+}
+
+/// Apply the rules to the model variables of a Claude Code env map
+pub fn apply_to_claude_env(rules: &[ModelRule], env: &mut Map<String, Value>) {
+    for key in CLAUDE_MODEL_ENV_KEYS {
+        if let Some(Value::String(model)) = env.get(key) {
+            let rewritten = rewrite_model(rules, model);
+            env.insert(key.to_string(), Value::String(rewritten));
+        }
+    }
+}
+
+/// Apply the rules to `model` and `small_model` of an OpenCode config
+pub fn apply_to_opencode_config(rules: &[ModelRule], config: &mut Value) {
+    for key in ["model", "small_model"] {
+        if let Some(Value::String(reference)) = config.get(key) {
+            let rewritten = rewrite_qualified(rules, reference, '/');
+            config[key] = Value::String(rewritten);
+        }
+    }
+}
+
+/// Apply the rules to every target of a CCR `Router` object
+pub fn apply_to_ccr_router(rules: &[ModelRule], config: &mut Value) {
+    let Some(router) = config.get_mut("Router").and_then(|r| r.as_object_mut()) else {
+        return;
+    };
+    for value in router.values_mut() {
+        if let Value::String(target) = value {
+            *target = rewrite_qualified(rules, target, ',');
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(pattern: &str, provider: Option<&str>, model: &str) -> ModelRule {
+        ModelRule {
+            id: pattern.to_string(),
+            pattern: pattern.to_string(),
+            target_provider: provider.map(String::from),
+            target_model: model.to_string(),
+            enabled: true,
+            note: None,
+            sort_index: 0,
+        }
+    }
+
+    #[test]
+    fn glob_match_handles_wildcards() {
+        assert!(glob_match("claude-3-5-haiku*", "Claude-3-5-Haiku-20241022"));
+        assert!(glob_match("*haiku*", "claude-haiku-4-5"));
+        assert!(glob_match("gpt-4o", "gpt-4o"));
+        assert!(!glob_match("gpt-4o", "gpt-4o-mini"));
+        assert!(!glob_match("a*b*a", "aa"));
+    }
+
+    #[test]
+    fn rewrite_uses_first_matching_rule() {
+        let rules = vec![
+            rule("claude-3-5-haiku*", Some("relay"), "glm-4.5-air"),
+            rule("*haiku*", None, "other"),
+        ];
+        assert_eq!(rewrite_model(&rules, "claude-3-5-haiku-latest"), "glm-4.5-air");
+        assert_eq!(
+            rewrite_qualified(&rules, "anthropic/claude-3-5-haiku-latest", '/'),
+            "relay/glm-4.5-air"
+        );
+        assert_eq!(rewrite_qualified(&rules, "ds,claude-haiku-4-5", ','), "ds,other");
+        assert_eq!(rewrite_qualified(&rules, "ds,deepseek-chat", ','), "ds,deepseek-chat");
+    }
+}
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelRule {
+    pub id: String,
+    /// Requested model name; `*` matches any characters
+    pub pattern: String,
+    /// Provider to route to (OpenCode provider id / CCR provider name); None keeps the provider
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_provider: Option<String>,
+    pub target_model: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    #[serde(default)]
+    pub sort_index: i64,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Input for creating or updating a rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelRuleInput {
+    pub id: Option<String>,
+    pub pattern: String,
+    #[serde(default)]
+    pub target_provider: Option<String>,
+    pub target_model: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub note: Option<String>,
+    #[serde(default)]
+    pub sort_index: Option<i64>,
+}
@@ -103,7 +103,7 @@ pub fn from_db_value(value: Value) -> OhMyOpenCodeConfig {
 /// Convert OhMyOpenCodeConfigContent to database Value
 pub fn to_db_value(content: &OhMyOpenCodeConfigContent) -> Value {
     serde_json::to_value(content).unwrap_or_else(|e| {
-        eprintln!("Failed to serialize oh-my-opencode config content: {}", e);
+        log::warn!("Failed to serialize oh-my-opencode config content: {}", e);
         json!({})
     })
 }
@@ -195,7 +195,7 @@ pub fn global_config_from_db_value(value: Value) -> OhMyOpenCodeGlobalConfig {
 /// Convert OhMyOpenCodeGlobalConfigContent to database Value
 pub fn global_config_to_db_value(content: &OhMyOpenCodeGlobalConfigContent) -> Value {
     serde_json::to_value(content).unwrap_or_else(|e| {
-        eprintln!(
+        log::warn!(
             "Failed to serialize oh-my-opencode global config content: {}",
             e
         );
@@ -2,10 +2,12 @@ use chrono::Local;
 use std::fs;
 use serde_json::Value;
 
+use crate::coding::change_history;
 use crate::db::DbState;
 use super::adapter;
 use super::types::*;
 use tauri::Emitter;
+use crate::events::{emit_change, ChangeAction};
 
 // ============================================================================
 // Oh My OpenCode Config Commands
@@ -49,7 +51,7 @@ pub async fn list_oh_my_opencode_configs(
             Ok(result)
         }
         Err(e) => {
-            eprintln!("Failed to deserialize configs: {}", e);
+            log::warn!("Failed to deserialize configs: {}", e);
             // Try to load from local file as fallback
             if let Ok(temp_config) = load_temp_config_from_file() {
                 return Ok(vec![temp_config]);
@@ -308,7 +310,7 @@ pub async fn create_oh_my_opencode_config(
         .take(0);
 
     // Notify to refresh tray menu
-    let _ = app.emit("config-changed", "window");
+    emit_change(&app, "oh_my_opencode_config", ChangeAction::Created, None, "window");
 
     match records_result {
         Ok(records) => {
@@ -425,7 +427,7 @@ pub async fn update_oh_my_opencode_config(
     // 如果该配置当前是应用状态，立即重新写入到配置文件
     if is_applied_value {
         if let Err(e) = apply_config_to_file(&db, &config_id).await {
-            eprintln!("Failed to auto-apply updated config: {}", e);
+            log::warn!("Failed to auto-apply updated config: {}", e);
             // 不中断更新流程，只记录错误
         } else {
             // Trigger WSL sync via event (Windows only)
@@ -464,7 +466,7 @@ pub async fn delete_oh_my_opencode_config(
         .map_err(|e| format!("Failed to delete config: {}", e))?;
 
     // Notify to refresh tray menu
-    let _ = app.emit("config-changed", "window");
+    emit_change(&app, "oh_my_opencode_config", ChangeAction::Deleted, None, "window");
 
     Ok(())
 }
@@ -477,11 +479,12 @@ async fn apply_config_to_file(
     apply_config_to_file_public(db, config_id).await
 }
 
-/// Public version of apply_config_to_file for tray module
-pub async fn apply_config_to_file_public(
+/// Final oh-my-opencode.json content for a config: global config merged with the profile
+/// Also used for project-level configs (see `projects`).
+pub async fn build_final_config(
     db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
     config_id: &str,
-) -> Result<(), String> {
+) -> Result<Value, String> {
     // Get the config from database using direct ID format (like ClaudeCode)
     let records_result: Result<Vec<Value>, _> = db
         .query(format!(
@@ -507,17 +510,6 @@ pub async fn apply_config_to_file_public(
         return Err(format!("Config '{}' is disabled and cannot be applied", config_id));
     }
 
-    // Get config path using unified function
-    let config_path = get_oh_my_opencode_config_path()?;
-
-    // Ensure parent directory exists
-    if let Some(parent) = config_path.parent() {
-        if !parent.exists() {
-            fs::create_dir_all(parent)
-                .map_err(|e| format!("Failed to create opencode config directory: {}", e))?;
-        }
-    }
-
     // 获取 Global Config
     let global_records_result: Result<Vec<Value>, _> = db
         .query("SELECT *, type::string(id) as id FROM oh_my_opencode_global_config:`global` LIMIT 1")
@@ -649,25 +641,51 @@ pub async fn apply_config_to_file_public(
     // 清理空值：删除空对象和空数组
     adapter::clean_empty_values(&mut final_json);
 
+    Ok(final_json)
+}
+
+/// Public version of apply_config_to_file for tray module
+pub async fn apply_config_to_file_public(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    config_id: &str,
+) -> Result<(), String> {
+    let final_json = build_final_config(db, config_id).await?;
+
+    // Get config path using unified function
+    let config_path = get_oh_my_opencode_config_path()?;
+
+    // Ensure parent directory exists
+    if let Some(parent) = config_path.parent() {
+        if !parent.exists() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create opencode config directory: {}", e))?;
+        }
+    }
+
     // Write to file with pretty formatting
     let json_content = serde_json::to_string_pretty(&final_json)
         .map_err(|e| format!("Failed to serialize final config: {}", e))?;
 
-    fs::write(&config_path, json_content)
+    change_history::write_config("oh_my_opencode", &config_path, json_content)
         .map_err(|e| format!("Failed to write config file: {}", e))?;
 
     Ok(())
 }
 /// Apply an oh-my-opencode config to the JSON file
+/// `dry_run` returns the diff of the file instead of writing it.
 #[tauri::command]
 pub async fn apply_oh_my_opencode_config(
     state: tauri::State<'_, DbState>,
     app: tauri::AppHandle,
     config_id: String,
-) -> Result<(), String> {
+    dry_run: Option<bool>,
+) -> Result<Vec<change_history::ConfigDiff>, String> {
     let db = state.0.lock().await;
+    if dry_run.unwrap_or(false) {
+        return change_history::preview(true, apply_config_to_file(&db, &config_id)).await;
+    }
     apply_config_internal(&db, &app, &config_id, false).await?;
-    Ok(())
+    Ok(Vec::new())
 }
 
 /// Internal function to apply config: writes to file and updates database
@@ -699,7 +717,7 @@ pub async fn apply_config_internal<R: tauri::Runtime>(
 
     // Notify based on source
     let payload = if from_tray { "tray" } else { "window" };
-    let _ = app.emit("config-changed", payload);
+    emit_change(app, "oh_my_opencode_config", ChangeAction::Applied, None, payload);
 
     // Trigger WSL sync via event (Windows only)
     #[cfg(target_os = "windows")]
@@ -846,7 +864,7 @@ pub async fn get_oh_my_opencode_global_config(
             }
         }
         Err(e) => {
-            eprintln!("Failed to get global config: {}", e);
+            log::warn!("Failed to get global config: {}", e);
             // Try to load from local file as fallback
             if let Ok(temp_config) = load_temp_global_config_from_file() {
                 return Ok(temp_config);
@@ -1083,11 +1101,11 @@ pub async fn save_oh_my_opencode_local_config(
         if let Some(record) = records.first() {
             let created_config = adapter::from_db_value(record.clone());
             if let Err(e) = apply_config_to_file(&db, &created_config.id).await {
-                eprintln!("Failed to apply config after local save: {}", e);
+                log::warn!("Failed to apply config after local save: {}", e);
             }
         }
     }
 
-    let _ = app.emit("config-changed", "window");
+    emit_change(&app, "oh_my_opencode_local_config", ChangeAction::Updated, None, "window");
     Ok(())
 }
@@ -80,7 +80,7 @@ pub async fn get_oh_my_opencode_tray_data<R: Runtime>(
             }
         }
         Err(e) => {
-            eprintln!("Failed to deserialize configs for tray: {}", e);
+            log::warn!("Failed to deserialize configs for tray: {}", e);
         }
     }
 
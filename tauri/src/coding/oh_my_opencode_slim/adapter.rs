@@ -101,7 +101,7 @@ pub fn from_db_value(value: Value) -> OhMyOpenCodeSlimConfig {
 /// Convert OhMyOpenCodeSlimConfigContent to database Value
 pub fn to_db_value(content: &OhMyOpenCodeSlimConfigContent) -> Value {
     serde_json::to_value(content).unwrap_or_else(|e| {
-        eprintln!("Failed to serialize oh-my-opencode-slim config content: {}", e);
+        log::warn!("Failed to serialize oh-my-opencode-slim config content: {}", e);
         json!({})
     })
 }
@@ -174,7 +174,7 @@ pub fn global_config_from_db_value(value: Value) -> OhMyOpenCodeSlimGlobalConfig
 /// Convert OhMyOpenCodeSlimGlobalConfigContent to database Value
 pub fn global_config_to_db_value(content: &OhMyOpenCodeSlimGlobalConfigContent) -> Value {
     serde_json::to_value(content).unwrap_or_else(|e| {
-        eprintln!("Failed to serialize oh-my-opencode-slim global config content: {}", e);
+        log::warn!("Failed to serialize oh-my-opencode-slim global config content: {}", e);
         json!({})
     })
 }
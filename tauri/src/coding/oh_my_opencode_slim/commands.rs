@@ -2,10 +2,12 @@ use chrono::Local;
 use std::fs;
 use serde_json::Value;
 
+use crate::coding::change_history;
 use crate::db::DbState;
 use super::adapter;
 use super::types::*;
 use tauri::Emitter;
+use crate::events::{emit_change, ChangeAction};
 
 // ============================================================================
 // Oh My OpenCode Slim Config Commands
@@ -49,7 +51,7 @@ pub async fn list_oh_my_opencode_slim_configs(
             Ok(result)
         }
         Err(e) => {
-            eprintln!("Failed to deserialize configs: {}", e);
+            log::warn!("Failed to deserialize configs: {}", e);
             // Try to load from local file as fallback
             if let Ok(temp_config) = load_temp_config_from_file() {
                 return Ok(vec![temp_config]);
@@ -244,7 +246,7 @@ pub async fn create_oh_my_opencode_slim_config(
         .map_err(|e| format!("Failed to query new config: {}", e))?
         .take(0);
 
-    let _ = app.emit("config-changed", "window");
+    emit_change(&app, "oh_my_opencode_slim_config", ChangeAction::Created, None, "window");
 
     match records_result {
         Ok(records) => {
@@ -351,7 +353,7 @@ pub async fn update_oh_my_opencode_slim_config(
 
     if is_applied_value {
         if let Err(e) = apply_config_to_file(&db, &config_id).await {
-            eprintln!("Failed to auto-apply updated config: {}", e);
+            log::warn!("Failed to auto-apply updated config: {}", e);
         } else {
             #[cfg(target_os = "windows")]
             let _ = app.emit("wsl-sync-request-opencode", ());
@@ -384,7 +386,7 @@ pub async fn delete_oh_my_opencode_slim_config(
         .await
         .map_err(|e| format!("Failed to delete config: {}", e))?;
 
-    let _ = app.emit("config-changed", "window");
+    emit_change(&app, "oh_my_opencode_slim_config", ChangeAction::Deleted, None, "window");
 
     Ok(())
 }
@@ -525,22 +527,27 @@ pub async fn apply_config_to_file_public(
     let json_content = serde_json::to_string_pretty(&final_json)
         .map_err(|e| format!("Failed to serialize final config: {}", e))?;
 
-    fs::write(&config_path, json_content)
+    change_history::write_config("oh_my_opencode_slim", &config_path, json_content)
         .map_err(|e| format!("Failed to write config file: {}", e))?;
 
     Ok(())
 }
 
 /// Apply an oh-my-opencode-slim config to the JSON file
+/// `dry_run` returns the diff of the file instead of writing it.
 #[tauri::command]
 pub async fn apply_oh_my_opencode_slim_config(
     state: tauri::State<'_, DbState>,
     app: tauri::AppHandle,
     config_id: String,
-) -> Result<(), String> {
+    dry_run: Option<bool>,
+) -> Result<Vec<change_history::ConfigDiff>, String> {
     let db = state.0.lock().await;
+    if dry_run.unwrap_or(false) {
+        return change_history::preview(true, apply_config_to_file(&db, &config_id)).await;
+    }
     apply_config_internal(&db, &app, &config_id, false).await?;
-    Ok(())
+    Ok(Vec::new())
 }
 
 /// Internal function to apply config
@@ -566,7 +573,7 @@ pub async fn apply_config_internal<R: tauri::Runtime>(
         .map_err(|e| format!("Failed to update applied flag: {}", e))?;
 
     let payload = if from_tray { "tray" } else { "window" };
-    let _ = app.emit("config-changed", payload);
+    emit_change(app, "oh_my_opencode_slim_config", ChangeAction::Applied, None, payload);
 
     #[cfg(target_os = "windows")]
     let _ = app.emit("wsl-sync-request-opencode", ());
@@ -662,7 +669,7 @@ pub async fn get_oh_my_opencode_slim_global_config(
             }
         }
         Err(e) => {
-            eprintln!("Failed to get global config: {}", e);
+            log::warn!("Failed to get global config: {}", e);
             // Try to load from local file as fallback
             if let Ok(temp_config) = load_temp_global_config_from_file() {
                 return Ok(temp_config);
@@ -898,11 +905,11 @@ pub async fn save_oh_my_opencode_slim_local_config(
         if let Some(record) = records.first() {
             let created_config = adapter::from_db_value(record.clone());
             if let Err(e) = apply_config_to_file(&db, &created_config.id).await {
-                eprintln!("Failed to apply config after local save: {}", e);
+                log::warn!("Failed to apply config after local save: {}", e);
             }
         }
     }
 
-    let _ = app.emit("config-changed", "window");
+    emit_change(&app, "oh_my_opencode_slim_local_config", ChangeAction::Updated, None, "window");
     Ok(())
 }
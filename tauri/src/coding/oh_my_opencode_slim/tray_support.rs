@@ -78,7 +78,7 @@ pub async fn get_oh_my_opencode_slim_tray_data<R: Runtime>(
             }
         }
         Err(e) => {
-            eprintln!("Failed to deserialize configs for tray: {}", e);
+            log::warn!("Failed to deserialize configs for tray: {}", e);
         }
     }
 
@@ -0,0 +1,261 @@
+use futures_util::StreamExt;
+use serde_json::Value;
+use tauri::Emitter;
+
+use crate::coding::claude_code::upsert_claude_provider_by_name;
+use crate::coding::open_code::{build_openai_compatible_provider, upsert_opencode_favorite_provider};
+use crate::db::DbState;
+use crate::http_client;
+use super::types::*;
+use crate::events::{emit_change, ChangeAction};
+
+const DEFAULT_OLLAMA_HOST: &str = "http://127.0.0.1:11434";
+
+/// Provider id / name used when registering Ollama
+const OLLAMA_PROVIDER_ID: &str = "ollama";
+const OLLAMA_PROVIDER_NAME: &str = "Ollama";
+
+/// Ollama ignores the key, but clients require one
+const OLLAMA_API_KEY: &str = "ollama";
+
+/// Timeout for quick API calls; pulls use a much longer one
+const API_TIMEOUT_SECS: u64 = 10;
+const PULL_TIMEOUT_SECS: u64 = 6 * 60 * 60;
+
+// ============================================================================
+// Ollama Helpers
+// ============================================================================
+
+/// Resolve the Ollama server URL from OLLAMA_HOST (`host:port`, `0.0.0.0`, or a full URL)
+fn get_ollama_host() -> String {
+    let Ok(raw) = std::env::var("OLLAMA_HOST") else {
+        return DEFAULT_OLLAMA_HOST.to_string();
+    };
+    let raw = raw.trim().trim_end_matches('/');
+    if raw.is_empty() {
+        return DEFAULT_OLLAMA_HOST.to_string();
+    }
+
+    let (scheme, rest) = match raw.split_once("://") {
+        Some((scheme, rest)) => (scheme, rest),
+        None => ("http", raw),
+    };
+    let rest = rest.replacen("0.0.0.0", "127.0.0.1", 1);
+    if rest.contains(':') {
+        format!("{}://{}", scheme, rest)
+    } else {
+        format!("{}://{}:11434", scheme, rest)
+    }
+}
+
+/// Local server requests bypass the proxy
+fn local_client(timeout_secs: u64) -> Result<reqwest::Client, String> {
+    http_client::create_client_no_proxy(timeout_secs)
+}
+
+async fn fetch_models(host: &str) -> Result<Vec<OllamaModel>, String> {
+    let response: Value = local_client(API_TIMEOUT_SECS)?
+        .get(format!("{}/api/tags", host))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to Ollama: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse Ollama models: {}", e))?;
+
+    let models = response
+        .get("models")
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .map(|m| {
+                    let details = m.get("details");
+                    let detail = |key: &str| {
+                        details
+                            .and_then(|d| d.get(key))
+                            .and_then(|v| v.as_str())
+                            .map(|s| s.to_string())
+                    };
+                    OllamaModel {
+                        name: m
+                            .get("name")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or("")
+                            .to_string(),
+                        size: m.get("size").and_then(|v| v.as_u64()).unwrap_or(0),
+                        parameter_size: detail("parameter_size"),
+                        quantization_level: detail("quantization_level"),
+                        modified_at: m
+                            .get("modified_at")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or("")
+                            .to_string(),
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    Ok(models)
+}
+
+// ============================================================================
+// Ollama Commands
+// ============================================================================
+
+/// Detect a running Ollama server
+#[tauri::command]
+pub async fn get_ollama_status() -> Result<OllamaStatus, String> {
+    let host = get_ollama_host();
+    let version = match local_client(API_TIMEOUT_SECS)?
+        .get(format!("{}/api/version", host))
+        .send()
+        .await
+    {
+        Ok(response) => response
+            .json::<Value>()
+            .await
+            .ok()
+            .and_then(|v| v.get("version").and_then(|v| v.as_str()).map(|s| s.to_string())),
+        Err(_) => None,
+    };
+
+    Ok(OllamaStatus {
+        running: version.is_some(),
+        host,
+        version,
+    })
+}
+
+/// List installed Ollama models
+#[tauri::command]
+pub async fn list_ollama_models() -> Result<Vec<OllamaModel>, String> {
+    fetch_models(&get_ollama_host()).await
+}
+
+/// Pull a model, emitting `ollama-pull-progress` events until it finishes
+#[tauri::command]
+pub async fn pull_ollama_model(app: tauri::AppHandle, model: String) -> Result<(), String> {
+    let model = model.trim().to_string();
+    if model.is_empty() {
+        return Err("Model name is required".to_string());
+    }
+
+    let response = local_client(PULL_TIMEOUT_SECS)?
+        .post(format!("{}/api/pull", get_ollama_host()))
+        .json(&serde_json::json!({ "model": model, "stream": true }))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to Ollama: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to pull model: HTTP {}", response.status()));
+    }
+
+    // The response is newline-delimited JSON
+    let mut buffer = String::new();
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let bytes = chunk.map_err(|e| format!("Failed to read pull progress: {}", e))?;
+        buffer.push_str(&String::from_utf8_lossy(&bytes));
+
+        while let Some(pos) = buffer.find('\n') {
+            let line: String = buffer.drain(..=pos).collect();
+            let Ok(event) = serde_json::from_str::<Value>(line.trim()) else {
+                continue;
+            };
+            if let Some(error) = event.get("error").and_then(|v| v.as_str()) {
+                return Err(format!("Failed to pull model: {}", error));
+            }
+            let _ = app.emit(
+                "ollama-pull-progress",
+                OllamaPullProgress {
+                    model: model.clone(),
+                    status: event
+                        .get("status")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("")
+                        .to_string(),
+                    completed: event.get("completed").and_then(|v| v.as_u64()),
+                    total: event.get("total").and_then(|v| v.as_u64()),
+                },
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Delete an installed model
+#[tauri::command]
+pub async fn delete_ollama_model(model: String) -> Result<(), String> {
+    let response = local_client(API_TIMEOUT_SECS)?
+        .delete(format!("{}/api/delete", get_ollama_host()))
+        .json(&serde_json::json!({ "model": model }))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to Ollama: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to delete model: HTTP {}", response.status()));
+    }
+    Ok(())
+}
+
+/// Register Ollama as a provider in the given targets ("opencode", "claude")
+/// The model list is taken from the installed models.
+#[tauri::command]
+pub async fn register_ollama_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    targets: Vec<String>,
+) -> Result<Vec<OllamaRegisterResult>, String> {
+    let host = get_ollama_host();
+    let model_ids: Vec<String> = fetch_models(&host)
+        .await?
+        .into_iter()
+        .map(|m| m.name)
+        .collect();
+
+    let mut results = Vec::new();
+    for target in targets {
+        let outcome = match target.as_str() {
+            "opencode" => {
+                let provider_config = build_openai_compatible_provider(
+                    OLLAMA_PROVIDER_NAME,
+                    &format!("{}/v1", host),
+                    Some(OLLAMA_API_KEY.to_string()),
+                    &model_ids,
+                );
+                upsert_opencode_favorite_provider(
+                    state.clone(),
+                    OLLAMA_PROVIDER_ID.to_string(),
+                    provider_config,
+                    None,
+                )
+                .await
+                .map(|_| ())
+            }
+            "claude" => {
+                let db = state.0.lock().await;
+                upsert_claude_provider_by_name(
+                    &db,
+                    OLLAMA_PROVIDER_NAME,
+                    &host,
+                    OLLAMA_API_KEY,
+                    model_ids.first(),
+                    Some("https://ollama.com"),
+                )
+                .await
+                .map(|_| ())
+            }
+            other => Err(format!("Unsupported target: {}", other)),
+        };
+        results.push(OllamaRegisterResult {
+            target,
+            success: outcome.is_ok(),
+            error: outcome.err(),
+        });
+    }
+
+    emit_change(&app, "claude_provider", ChangeAction::Created, None, "window");
+    Ok(results)
+}
@@ -6,7 +6,9 @@ use tauri::Emitter;
 
 use super::adapter;
 use super::types::*;
+use crate::coding::{change_history, model_rules};
 use crate::db::DbState;
+use crate::events::{emit_change, ChangeAction};
 
 // ============================================================================
 // Helper Functions
@@ -239,13 +241,19 @@ pub async fn backup_opencode_config(state: tauri::State<'_, DbState>) -> Result<
 }
 
 /// Save OpenCode configuration file
+/// With `dry_run` nothing is written and the diff of the config file is returned.
 #[tauri::command]
 pub async fn save_opencode_config<R: tauri::Runtime>(
     state: tauri::State<'_, DbState>,
     app: tauri::AppHandle<R>,
     config: OpenCodeConfig,
-) -> Result<(), String> {
-    apply_config_internal(state, &app, config, false).await
+    dry_run: Option<bool>,
+) -> Result<Vec<change_history::ConfigDiff>, String> {
+    if dry_run.unwrap_or(false) {
+        return change_history::preview(true, write_config_file(state, config)).await;
+    }
+    apply_config_internal(state, &app, config, false).await?;
+    Ok(Vec::new())
 }
 
 /// Internal function to save config and emit events
@@ -255,6 +263,25 @@ pub async fn apply_config_internal<R: tauri::Runtime>(
     config: OpenCodeConfig,
     from_tray: bool,
 ) -> Result<(), String> {
+    write_config_file(state, config).await?;
+
+    // Notify based on source
+    let payload = if from_tray { "tray" } else { "window" };
+    emit_change(app, "opencode_config", ChangeAction::Applied, None, payload);
+
+    // Trigger WSL sync via event (Windows only)
+    #[cfg(target_os = "windows")]
+    let _ = app.emit("wsl-sync-request-opencode", ());
+
+    Ok(())
+}
+
+/// Write the OpenCode config file (model rules applied, vault references resolved)
+async fn write_config_file(state: tauri::State<'_, DbState>, config: OpenCodeConfig) -> Result<(), String> {
+    let rules = {
+        let db = state.0.lock().await;
+        model_rules::load_rules(&db).await
+    };
     let config_path_str = get_opencode_config_path(state).await?;
     let config_path = Path::new(&config_path_str);
 
@@ -273,22 +300,23 @@ pub async fn apply_config_internal<R: tauri::Runtime>(
     // Clean up empty objects in models (options, variants, modalities)
     clean_empty_objects(&mut json_value);
 
-    // Serialize with pretty printing
-    let json_content = serde_json::to_string_pretty(&json_value)
-        .map_err(|e| format!("Failed to serialize config: {}", e))?;
+    // Route `model` / `small_model` through the model rules
+    model_rules::apply_to_opencode_config(&rules, &mut json_value);
 
-    fs::write(config_path, json_content)
-        .map_err(|e| format!("Failed to write config file: {}", e))?;
+    // The file is read by OpenCode itself, so vault:// references become real keys
+    crate::coding::vault::resolve_references(&mut json_value)?;
 
-    // Notify based on source
-    let payload = if from_tray { "tray" } else { "window" };
-    let _ = app.emit("config-changed", payload);
-
-    // Trigger WSL sync via event (Windows only)
-    #[cfg(target_os = "windows")]
-    let _ = app.emit("wsl-sync-request-opencode", ());
+    // Edit the existing file in place so the user's comments survive; write it
+    // whole (pretty printed) when there is none or it cannot be parsed
+    let existing = fs::read_to_string(config_path).ok();
+    let json_content = match existing.as_deref().and_then(|source| super::jsonc::patch(source, &json_value)) {
+        Some(content) => content,
+        None => serde_json::to_string_pretty(&json_value)
+            .map_err(|e| format!("Failed to serialize config: {}", e))?,
+    };
 
-    Ok(())
+    change_history::write_config("open_code", config_path, json_content)
+        .map_err(|e| format!("Failed to write config file: {}", e))
 }
 
 // ============================================================================
@@ -318,7 +346,7 @@ pub async fn get_opencode_common_config(
         }
         Err(e) => {
             // 反序列化失败，删除旧数据以修复版本冲突
-            eprintln!("⚠️ OpenCode common config has incompatible format, cleaning up: {}", e);
+            log::warn!("OpenCode common config has incompatible format, cleaning up: {}", e);
             let _ = db.query("DELETE opencode_common_config:`common`").await;
             Ok(None)
         }
@@ -756,3 +784,67 @@ pub async fn delete_opencode_favorite_provider(
 
     Ok(())
 }
+
+/// Load a favorite provider by provider_id
+/// Used by other coding tools to apply providers saved in the toolbox
+pub async fn get_favorite_provider_internal(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    provider_id: &str,
+) -> Result<OpenCodeFavoriteProvider, String> {
+    let records_result: Result<Vec<Value>, _> = db
+        .query("SELECT *, type::string(id) as id FROM opencode_favorite_provider WHERE provider_id = $provider_id LIMIT 1")
+        .bind(("provider_id", provider_id.to_string()))
+        .await
+        .map_err(|e| format!("Failed to query favorite provider: {}", e))?
+        .take(0);
+
+    match records_result {
+        Ok(records) => records
+            .into_iter()
+            .next()
+            .and_then(adapter::from_db_value_favorite_provider)
+            .ok_or_else(|| format!("Provider '{}' not found", provider_id)),
+        Err(e) => Err(format!("Failed to deserialize favorite provider: {}", e)),
+    }
+}
+
+/// Build an OpenAI-compatible provider config for the favorite provider store
+/// Used by integrations that discover endpoints (relays, local inference servers)
+pub fn build_openai_compatible_provider(
+    name: &str,
+    base_url: &str,
+    api_key: Option<String>,
+    model_ids: &[String],
+) -> OpenCodeProvider {
+    let models = model_ids
+        .iter()
+        .map(|id| {
+            (
+                id.clone(),
+                OpenCodeModel {
+                    name: Some(id.clone()),
+                    limit: None,
+                    modalities: None,
+                    options: None,
+                    variants: None,
+                },
+            )
+        })
+        .collect();
+
+    OpenCodeProvider {
+        npm: Some("@ai-sdk/openai-compatible".to_string()),
+        name: Some(name.to_string()),
+        options: Some(OpenCodeProviderOptions {
+            base_url: Some(base_url.trim_end_matches('/').to_string()),
+            api_key: api_key.filter(|k| !k.is_empty()),
+            headers: None,
+            timeout: None,
+            set_cache_key: None,
+            extra: serde_json::Map::new(),
+        }),
+        models,
+        whitelist: None,
+        blacklist: None,
+    }
+}
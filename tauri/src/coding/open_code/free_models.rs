@@ -1,5 +1,6 @@
 use crate::db::DbState;
 use crate::http_client;
+use crate::notifications::NotificationCategory;
 use super::types::{FreeModel, ProviderModelsData, UnifiedModelOption, OpenCodeProvider, OfficialModel, OfficialProvider, GetAuthProvidersResponse};
 use serde::Deserialize;
 use std::collections::{HashMap, HashSet};
@@ -19,7 +20,7 @@ const CACHE_DURATION_HOURS: u64 = 6; // 6 hours cache duration
 /// Returns the complete JSON object containing all providers
 fn get_all_default_providers_data() -> serde_json::Value {
     serde_json::from_str(DEFAULT_MODELS_JSON).unwrap_or_else(|e| {
-        eprintln!("Failed to parse default models.json: {}", e);
+        log::warn!("Failed to parse default models.json: {}", e);
         serde_json::json!({})
     })
 }
@@ -50,10 +51,11 @@ pub fn get_default_free_models() -> Vec<FreeModel> {
 /// Fetch all providers data from API
 /// Returns the complete JSON object containing all providers
 async fn fetch_all_providers_from_api(state: &DbState) -> Result<serde_json::Value, String> {
-    let client = http_client::client_with_timeout(state, 30).await?;
+    let client = http_client::client(state).await?;
+    let url = http_client::resolve_url(state, MODELS_API_URL).await;
 
     let response = client
-        .get(MODELS_API_URL)
+        .get(&url)
         .send()
         .await
         .map_err(|e| format!("Failed to fetch models API: {}", e))?;
@@ -239,7 +241,7 @@ async fn save_all_provider_models_to_db(state: &DbState, all_providers: &serde_j
             .await
         {
             Ok(_) => saved_count += 1,
-            Err(e) => eprintln!("Failed to save record for {}: {}", provider_id, e),
+            Err(e) => log::warn!("Failed to save record for {}: {}", provider_id, e),
         }
     }
 
@@ -280,19 +282,19 @@ pub async fn get_free_models(state: &DbState, force_refresh: bool) -> Result<(Ve
                 // Cache expired: return filtered free models from cached data, then refresh in background
                 let cached_models = filter_free_models(OPENCODE_PROVIDER_ID, &cached_data.value);
                 let updated_at = cached_data.updated_at.clone();
-                eprintln!("[CACHE EXPIRED] (updated_at: {}), returning {} stale models and refreshing in background...", updated_at, cached_models.len());
+                log::info!("[CACHE EXPIRED] (updated_at: {}), returning {} stale models and refreshing in background...", updated_at, cached_models.len());
 
                 // Spawn background task to refresh cache
                 let db_arc = state.0.clone();
                 let db_state = DbState(db_arc);
                 tauri::async_runtime::spawn(async move {
-                    eprintln!("[Background] Starting all providers data refresh...");
+                    log::debug!("[Background] Starting all providers data refresh...");
                     match fetch_and_update_all_providers(&db_state).await {
                         Ok(count) => {
-                            eprintln!("[Background] Successfully refreshed {} providers", count);
+                            log::info!("[Background] Successfully refreshed {} providers", count);
                         }
                         Err(e) => {
-                            eprintln!("[Background] Failed to refresh providers: {}", e);
+                            log::warn!("[Background] Failed to refresh providers: {}", e);
                         }
                     }
                 });
@@ -300,16 +302,16 @@ pub async fn get_free_models(state: &DbState, force_refresh: bool) -> Result<(Ve
                 return Ok((cached_models, true, Some(updated_at)));
             }
             Ok(None) => {
-                eprintln!("[CACHE MISS] No cached data found, will fetch from API");
+                log::info!("[CACHE MISS] No cached data found, will fetch from API");
             }
             Err(e) => {
-                eprintln!("[CACHE ERROR] Failed to read cache: {}, will fetch from API", e);
+                log::warn!("[CACHE ERROR] Failed to read cache: {}, will fetch from API", e);
             }
         }
     }
 
     // 2. No cache or force_refresh: fetch all providers from API (synchronous)
-    eprintln!("[FETCH] No cache or force_refresh, fetching all providers from API...");
+    log::info!("[FETCH] No cache or force_refresh, fetching all providers from API...");
     fetch_and_update_all_providers(state).await?;
 
     // 3. Read opencode provider from database and filter free models
@@ -332,7 +334,7 @@ async fn fetch_and_update_all_providers(state: &DbState) -> Result<usize, String
 
     // If API returned empty, use default providers data
     let final_providers = if all_providers.as_object().map(|m| m.is_empty()).unwrap_or(true) {
-        eprintln!("API returned empty providers, using default data");
+        log::warn!("API returned empty providers, using default data");
         get_all_default_providers_data()
     } else {
         all_providers
@@ -340,12 +342,42 @@ async fn fetch_and_update_all_providers(state: &DbState) -> Result<usize, String
 
     // Log provider IDs being saved
     if let Some(providers_obj) = final_providers.as_object() {
-        eprintln!("Saving {} providers to database", providers_obj.len());
+        log::debug!("Saving {} providers to database", providers_obj.len());
     }
 
+    // Remember current free models to detect new ones
+    let previous_free_ids: Option<HashSet<String>> = read_provider_models_from_db(state, OPENCODE_PROVIDER_ID)
+        .await
+        .ok()
+        .flatten()
+        .map(|data| {
+            filter_free_models(OPENCODE_PROVIDER_ID, &data.value)
+                .into_iter()
+                .map(|m| m.id)
+                .collect()
+        });
+
     // Save all providers to database
     let updated_at = chrono::Utc::now().to_rfc3339();
-    save_all_provider_models_to_db(state, &final_providers, &updated_at).await
+    let count = save_all_provider_models_to_db(state, &final_providers, &updated_at).await?;
+
+    // Only notify when there was a previous list to compare with
+    if let (Some(previous), Some(opencode)) = (previous_free_ids, final_providers.get(OPENCODE_PROVIDER_ID)) {
+        let new_models: Vec<String> = filter_free_models(OPENCODE_PROVIDER_ID, opencode)
+            .into_iter()
+            .filter(|m| !previous.contains(&m.id))
+            .map(|m| m.name)
+            .collect();
+        if !new_models.is_empty() {
+            crate::notifications::notify_global(
+                NotificationCategory::FreeModels,
+                "发现新的免费模型",
+                new_models.join(", "),
+            );
+        }
+    }
+
+    Ok(count)
 }
 
 /// Initialize default provider models in database (called on app startup)
@@ -354,27 +386,27 @@ pub async fn init_default_provider_models(state: &DbState) -> Result<(), String>
     // Check if opencode provider exists as indicator for all providers
     match read_provider_models_from_db(state, OPENCODE_PROVIDER_ID).await {
         Ok(Some(data)) => {
-            eprintln!("Provider models cache already exists (updated_at: {}), skipping initialization", data.updated_at);
+            log::debug!("Provider models cache already exists (updated_at: {}), skipping initialization", data.updated_at);
             Ok(())
         }
         Ok(None) => {
-            eprintln!("No provider models cache found, initializing with default data for all providers");
+            log::info!("No provider models cache found, initializing with default data for all providers");
             let all_providers = get_all_default_providers_data();
             let updated_at = chrono::Utc::now().to_rfc3339();
 
             match save_all_provider_models_to_db(state, &all_providers, &updated_at).await {
                 Ok(count) => {
-                    eprintln!("Successfully initialized {} providers with default data", count);
+                    log::info!("Successfully initialized {} providers with default data", count);
                     Ok(())
                 }
                 Err(e) => {
-                    eprintln!("Failed to initialize providers: {}", e);
+                    log::warn!("Failed to initialize providers: {}", e);
                     Err(e)
                 }
             }
         }
         Err(e) => {
-            eprintln!("Failed to check provider models cache: {}, skipping initialization", e);
+            log::warn!("Failed to check provider models cache: {}, skipping initialization", e);
             Ok(())
         }
     }
@@ -683,7 +715,7 @@ pub async fn get_unified_models(
                 models.extend(free_vec);
             }
             Err(e) => {
-                eprintln!("Failed to load free models: {}", e);
+                log::warn!("Failed to load free models: {}", e);
             }
         }
     }
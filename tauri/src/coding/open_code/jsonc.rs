@@ -0,0 +1,435 @@
+//! Comment-preserving writes of opencode.jsonc
+//!
+//! Re-serializing the whole config drops every comment in the user's file.
+//! `patch` instead compares the new config with the file as it is and edits
+//! only what changed: scalars are replaced in place, removed members are cut
+//! out and new members are appended to their object in the file's
+//! indentation. Comments and formatting of untouched parts survive; an array
+//! that changed is rewritten as a whole.
+
+use serde_json::Value;
+
+/// Parsed value with its byte range in the source
+enum Node {
+    Object { start: usize, end: usize, members: Vec<Member> },
+    Array { start: usize, end: usize },
+    Scalar { start: usize, end: usize },
+}
+
+impl Node {
+    fn start(&self) -> usize {
+        match self {
+            Node::Object { start, .. } | Node::Array { start, .. } | Node::Scalar { start, .. } => *start,
+        }
+    }
+
+    fn end(&self) -> usize {
+        match self {
+            Node::Object { end, .. } | Node::Array { end, .. } | Node::Scalar { end, .. } => *end,
+        }
+    }
+}
+
+struct Member {
+    key: String,
+    key_start: usize,
+    value: Node,
+    /// Position of the comma after the value, if any
+    comma: Option<usize>,
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(src: &'a str) -> Self {
+        Parser { src, bytes: src.as_bytes(), pos: 0 }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    /// Skip whitespace and `//` / `/* */` comments
+    fn skip_trivia(&mut self) -> Result<(), String> {
+        loop {
+            match (self.peek(), self.bytes.get(self.pos + 1).copied()) {
+                (Some(b), _) if b.is_ascii_whitespace() => self.pos += 1,
+                (Some(b'/'), Some(b'/')) => {
+                    while self.peek().is_some_and(|b| b != b'\n') {
+                        self.pos += 1;
+                    }
+                }
+                (Some(b'/'), Some(b'*')) => {
+                    let close = self.src[self.pos + 2..]
+                        .find("*/")
+                        .ok_or("Unterminated block comment")?;
+                    self.pos += close + 4;
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), String> {
+        if self.peek() != Some(byte) {
+            return Err(format!("Expected '{}' at byte {}", byte as char, self.pos));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn string(&mut self) -> Result<(), String> {
+        let quote = self.peek().ok_or("Unexpected end of input")?;
+        self.pos += 1;
+        loop {
+            match self.peek() {
+                None => return Err("Unterminated string".to_string()),
+                Some(b'\\') => self.pos += 2,
+                Some(b) if b == quote => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                Some(_) => self.pos += 1,
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Node, String> {
+        self.skip_trivia()?;
+        let start = self.pos;
+        match self.peek() {
+            None => Err("Unexpected end of input".to_string()),
+            Some(b'{') => self.object(),
+            Some(b'[') => {
+                self.pos += 1;
+                loop {
+                    self.skip_trivia()?;
+                    if self.peek() == Some(b']') {
+                        self.pos += 1;
+                        return Ok(Node::Array { start, end: self.pos });
+                    }
+                    self.value()?;
+                    self.skip_trivia()?;
+                    if self.peek() == Some(b',') {
+                        self.pos += 1;
+                    } else if self.peek() != Some(b']') {
+                        return Err(format!("Expected ',' or ']' at byte {}", self.pos));
+                    }
+                }
+            }
+            Some(b'"' | b'\'') => {
+                self.string()?;
+                Ok(Node::Scalar { start, end: self.pos })
+            }
+            Some(_) => {
+                while self
+                    .peek()
+                    .is_some_and(|b| !b.is_ascii_whitespace() && !matches!(b, b',' | b'}' | b']' | b'/'))
+                {
+                    self.pos += 1;
+                }
+                if self.pos == start {
+                    return Err(format!("Unexpected character at byte {}", start));
+                }
+                Ok(Node::Scalar { start, end: self.pos })
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Node, String> {
+        let start = self.pos;
+        self.expect(b'{')?;
+        let mut members = Vec::new();
+        loop {
+            self.skip_trivia()?;
+            if self.peek() == Some(b'}') {
+                self.pos += 1;
+                return Ok(Node::Object { start, end: self.pos, members });
+            }
+            let key_start = self.pos;
+            let key = if matches!(self.peek(), Some(b'"' | b'\'')) {
+                self.string()?;
+                json5::from_str::<String>(&self.src[key_start..self.pos]).map_err(|e| e.to_string())?
+            } else {
+                while self.peek().is_some_and(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'$') {
+                    self.pos += 1;
+                }
+                self.src[key_start..self.pos].to_string()
+            };
+            if key_start == self.pos {
+                return Err(format!("Expected a key at byte {}", key_start));
+            }
+            self.skip_trivia()?;
+            self.expect(b':')?;
+            let value = self.value()?;
+            self.skip_trivia()?;
+            let comma = match self.peek() {
+                Some(b',') => {
+                    self.pos += 1;
+                    Some(self.pos - 1)
+                }
+                Some(b'}') => None,
+                _ => return Err(format!("Expected ',' or '}}' at byte {}", self.pos)),
+            };
+            members.push(Member { key, key_start, value, comma });
+        }
+    }
+}
+
+/// Replacement of `start..end` with `text`
+struct Edit {
+    start: usize,
+    end: usize,
+    text: String,
+}
+
+struct Patcher<'a> {
+    src: &'a str,
+    newline: &'static str,
+    edits: Vec<Edit>,
+}
+
+impl Patcher<'_> {
+    fn line_start(&self, pos: usize) -> usize {
+        self.src[..pos].rfind('\n').map(|i| i + 1).unwrap_or(0)
+    }
+
+    /// Whitespace at the start of the line containing `pos`
+    fn indent_at(&self, pos: usize) -> &str {
+        let line = &self.src[self.line_start(pos)..];
+        &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+    }
+
+    fn blank_before(&self, pos: usize) -> bool {
+        self.src[self.line_start(pos)..pos].trim().is_empty()
+    }
+
+    /// Position of the line break after `pos`, skipping spaces and a trailing
+    /// comment; `pos` itself when something else follows on the line
+    fn end_of_line(&self, pos: usize) -> usize {
+        let rest = &self.src[pos..];
+        let trimmed = rest.trim_start_matches([' ', '\t']);
+        let after_comment = if trimmed.starts_with("//") {
+            trimmed.trim_start_matches(|c: char| c != '\n' && c != '\r')
+        } else {
+            trimmed
+        };
+        if after_comment.starts_with('\n') || after_comment.starts_with('\r') || after_comment.is_empty() {
+            pos + rest.len() - after_comment.len()
+        } else {
+            pos
+        }
+    }
+
+    /// Past the line break at `pos`, if there is one
+    fn past_newline(&self, pos: usize) -> usize {
+        let rest = &self.src[pos..];
+        if rest.starts_with("\r\n") {
+            pos + 2
+        } else if rest.starts_with('\n') {
+            pos + 1
+        } else {
+            pos
+        }
+    }
+
+    /// Pretty JSON of `value`, continuation lines indented by `indent`
+    fn render(&self, value: &Value, indent: &str) -> String {
+        let pretty = serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string());
+        pretty.replace('\n', &format!("{}{}", self.newline, indent))
+    }
+
+    fn replace(&mut self, node: &Node, value: &Value) {
+        let text = self.render(value, self.indent_at(node.start()));
+        self.edits.push(Edit { start: node.start(), end: node.end(), text });
+    }
+
+    fn diff(&mut self, node: &Node, new: &Value) {
+        let old: Option<Value> = json5::from_str(&self.src[node.start()..node.end()]).ok();
+        if old.as_ref() == Some(new) {
+            return;
+        }
+        match (node, new) {
+            (Node::Object { start, members, .. }, Value::Object(map)) => {
+                let kept: Vec<usize> = (0..members.len()).filter(|&i| map.contains_key(&members[i].key)).collect();
+                let Some(&last_kept) = kept.last() else {
+                    self.replace(node, new);
+                    return;
+                };
+                for (i, member) in members.iter().enumerate() {
+                    if let Some(value) = map.get(&member.key) {
+                        self.diff(&member.value, value);
+                    } else if i < last_kept {
+                        self.remove(member);
+                    }
+                }
+
+                let indent = if self.blank_before(members[0].key_start) {
+                    self.indent_at(members[0].key_start).to_string()
+                } else {
+                    format!("{}  ", self.indent_at(*start))
+                };
+                let added: Vec<String> = map
+                    .iter()
+                    .filter(|(key, _)| !members.iter().any(|m| &m.key == *key))
+                    .map(|(key, value)| {
+                        format!(
+                            "{}{}{}: {}",
+                            self.newline,
+                            indent,
+                            serde_json::to_string(key).unwrap_or_default(),
+                            self.render(value, &indent)
+                        )
+                    })
+                    .collect();
+                self.extend_tail(members, last_kept, added);
+            }
+            _ => self.replace(node, new),
+        }
+    }
+
+    /// Cut out a member followed by a comma, with its line when it has one to itself
+    fn remove(&mut self, member: &Member) {
+        let Some(comma) = member.comma else {
+            return;
+        };
+        let (start, end) = if self.blank_before(member.key_start) {
+            let eol = self.end_of_line(comma + 1);
+            (self.line_start(member.key_start), self.past_newline(eol))
+        } else {
+            (member.key_start, comma + 1)
+        };
+        self.edits.push(Edit { start, end, text: String::new() });
+    }
+
+    /// Drop the removed members after the last kept one and append `added`
+    fn extend_tail(&mut self, members: &[Member], last_kept: usize, added: Vec<String>) {
+        let kept = &members[last_kept];
+        let last = members.last().expect("object has members");
+        let trailing_comma = last.comma.is_some();
+        let mut text = added.join(",");
+        if !added.is_empty() && trailing_comma {
+            text.push(',');
+        }
+
+        match kept.comma {
+            // Removed members follow: everything after the kept member's line goes,
+            // up to the end of the last member's line
+            Some(comma) if last_kept + 1 < members.len() => {
+                let start = self.end_of_line(comma + 1);
+                let end = self.end_of_line(last.comma.map(|c| c + 1).unwrap_or(last.value.end()));
+                if added.is_empty() && !trailing_comma {
+                    self.edits.push(Edit { start: comma, end: comma + 1, text: String::new() });
+                }
+                self.edits.push(Edit { start, end, text });
+            }
+            _ if added.is_empty() => {}
+            Some(comma) => {
+                let at = self.end_of_line(comma + 1);
+                self.edits.push(Edit { start: at, end: at, text });
+            }
+            None => {
+                let value_end = kept.value.end();
+                let at = self.end_of_line(value_end);
+                if at == value_end {
+                    self.edits.push(Edit { start: at, end: at, text: format!(",{}", text) });
+                } else {
+                    self.edits.push(Edit { start: value_end, end: value_end, text: ",".to_string() });
+                    self.edits.push(Edit { start: at, end: at, text });
+                }
+            }
+        }
+    }
+}
+
+/// `source` edited to hold `new`, keeping its comments and layout; None when
+/// `source` cannot be parsed or is not an object (write it whole instead)
+pub fn patch(source: &str, new: &Value) -> Option<String> {
+    let mut parser = Parser::new(source);
+    let root = parser.value().ok()?;
+    parser.skip_trivia().ok()?;
+    if parser.pos != source.len() || !matches!(root, Node::Object { .. }) || !new.is_object() {
+        return None;
+    }
+
+    let mut patcher = Patcher {
+        src: source,
+        newline: if source.contains("\r\n") { "\r\n" } else { "\n" },
+        edits: Vec::new(),
+    };
+    patcher.diff(&root, new);
+
+    let mut edits = patcher.edits;
+    edits.sort_by_key(|edit| (edit.start, edit.end));
+    let mut result = source.to_string();
+    for edit in edits.iter().rev() {
+        result.replace_range(edit.start..edit.end, &edit.text);
+    }
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"{
+  // Keys live in the vault
+  "$schema": "https://opencode.ai/config.json",
+  "model": "relay/glm-4.6", // default model
+  "provider": {
+    /* Main relay */
+    "relay": {
+      "npm": "@ai-sdk/openai-compatible",
+      "options": { "baseURL": "https://relay.example.com/v1" },
+      "models": {
+        "glm-4.6": {},
+        "old-model": {} // retired
+      }
+    },
+    "unused": { "npm": "@ai-sdk/openai-compatible" },
+    "backup": {}
+  }
+}
+"#;
+
+    fn json(source: &str) -> Value {
+        json5::from_str(source).unwrap()
+    }
+
+    #[test]
+    fn edits_keep_comments() {
+        let mut config = json(SOURCE);
+        config["model"] = Value::String("relay/kimi-k2".to_string());
+        config["provider"]["relay"]["options"]["baseURL"] = Value::String("https://relay.example.com/api".to_string());
+        let models = config["provider"]["relay"]["models"].as_object_mut().unwrap();
+        models.remove("old-model");
+        models.insert("kimi-k2".to_string(), serde_json::json!({ "name": "Kimi K2" }));
+        config["provider"].as_object_mut().unwrap().remove("unused");
+        config["small_model"] = Value::String("relay/glm-4.5-air".to_string());
+
+        let patched = patch(SOURCE, &config).unwrap();
+        assert_eq!(json(&patched), config);
+        assert!(patched.contains("// Keys live in the vault"));
+        assert!(patched.contains(r#""model": "relay/kimi-k2", // default model"#));
+        assert!(patched.contains("/* Main relay */"));
+        assert!(!patched.contains("unused"));
+        assert!(!patched.contains("old-model"));
+        assert!(!patched.contains("// retired"));
+        assert!(patched.contains("\n    \"backup\": {}\n  },\n  \"small_model\": \"relay/glm-4.5-air\"\n}"));
+    }
+
+    #[test]
+    fn unchanged_config_is_left_alone() {
+        assert_eq!(patch(SOURCE, &json(SOURCE)).as_deref(), Some(SOURCE));
+    }
+
+    #[test]
+    fn falls_back_on_unparsable_source() {
+        assert!(patch("{ \"model\": ", &serde_json::json!({})).is_none());
+        assert!(patch("[]", &serde_json::json!({})).is_none());
+    }
+}
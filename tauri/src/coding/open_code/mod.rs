@@ -1,7 +1,9 @@
 pub mod adapter;
 pub mod commands;
 pub mod free_models;
+pub mod jsonc;
 pub mod models_api;
+pub mod schema;
 pub mod shell_env;
 pub mod tray_support;
 pub mod types;
@@ -9,4 +11,5 @@ pub mod types;
 pub use commands::*;
 pub use free_models::*;
 pub use models_api::*;
+pub use schema::{refresh_opencode_schema, validate_opencode_config};
 pub use types::*;
@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::db::DbState;
 use crate::http_client;
+use crate::notifications::NotificationCategory;
 use futures_util::StreamExt;
 use serde_json::{json, Value};
 use std::collections::BTreeMap;
@@ -222,7 +223,7 @@ pub async fn fetch_provider_models(
     request: FetchModelsRequest,
 ) -> Result<FetchModelsResponse, String> {
     // Create HTTP client with timeout and proxy support
-    let client = http_client::client_with_timeout(&state, 30).await?;
+    let client = http_client::client(&state).await?;
 
     // Build request URL based on API type and SDK type
     // Use custom_url if provided, otherwise calculate it
@@ -838,10 +839,11 @@ async fn run_connectivity_test_for_model(
 
 #[tauri::command]
 pub async fn test_provider_model_connectivity(
+    app: tauri::AppHandle,
     state: tauri::State<'_, DbState>,
     request: ConnectivityTestRequest,
 ) -> Result<ConnectivityTestResponse, String> {
-    let timeout_secs = request.timeout_secs.unwrap_or(30);
+    let timeout_secs = request.timeout_secs.unwrap_or(0);
     let client = http_client::client_with_timeout(&state, timeout_secs).await?;
 
     let mut results = Vec::new();
@@ -850,6 +852,20 @@ pub async fn test_provider_model_connectivity(
         results.push(result);
     }
 
+    let failed: Vec<&str> = results
+        .iter()
+        .filter(|r| r.status != "success")
+        .map(|r| r.model_id.as_str())
+        .collect();
+    if !failed.is_empty() {
+        crate::notifications::notify(
+            &app,
+            NotificationCategory::ProviderHealth,
+            "供应商连通性测试失败",
+            format!("{}: {}", request.base_url, failed.join(", ")),
+        );
+    }
+
     Ok(ConnectivityTestResponse { results })
 }
 
@@ -0,0 +1,544 @@
+//! opencode.json schema validation
+//!
+//! Checks a config against the opencode JSON schema before OpenCode gets to
+//! refuse it. The schema bundled at build time covers the well-known fields;
+//! `refresh_opencode_schema` downloads the one the config's `$schema` points to
+//! (https://opencode.ai/config.json by default) and caches it in
+//! `opencode_schema_cache:config`, which is used from then on for that URL.
+//!
+//! Only the keywords opencode's schema relies on are implemented: type, enum,
+//! const, string / number / array / object bounds, pattern, properties,
+//! patternProperties, propertyNames, required, additionalProperties, items,
+//! allOf / anyOf / oneOf / not and local `$ref`s. Annotations (description,
+//! default, format, ...) need no checking. Any other keyword is logged and
+//! returned as unchecked, and a config is only reported valid when every
+//! keyword its schema uses was checked.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use chrono::Local;
+use regex::Regex;
+use serde_json::{Map, Value};
+
+use crate::db::DbState;
+use crate::error::{AppError, AppResult};
+use crate::http_client;
+use super::commands::get_opencode_config_path;
+use super::types::{OpenCodeSchemaError, OpenCodeSchemaInfo, OpenCodeValidationResult};
+
+type Db = surrealdb::Surreal<surrealdb::engine::local::Db>;
+
+const BUNDLED_SCHEMA_JSON: &str = include_str!("../../../resources/opencode_schema.json");
+
+const DEFAULT_SCHEMA_URL: &str = "https://opencode.ai/config.json";
+const FETCH_TIMEOUT_SECS: u64 = 15;
+/// Guards against `$ref` cycles
+const MAX_DEPTH: usize = 64;
+
+/// Keywords `Validator` checks
+const CHECKED_KEYWORDS: [&str; 27] = [
+    "$ref", "type", "enum", "const", "minLength", "maxLength", "pattern", "minimum", "maximum",
+    "exclusiveMinimum", "exclusiveMaximum", "minItems", "maxItems", "uniqueItems", "prefixItems",
+    "items", "required", "properties", "patternProperties", "additionalProperties", "propertyNames",
+    "minProperties", "maxProperties", "allOf", "anyOf", "oneOf", "not",
+];
+
+/// Keywords that only annotate or hold definitions, nothing to check
+const ANNOTATION_KEYWORDS: [&str; 15] = [
+    "$schema", "$id", "$comment", "$defs", "definitions", "$anchor", "title", "description",
+    "markdownDescription", "default", "examples", "deprecated", "readOnly", "writeOnly", "format",
+];
+
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn has_type(value: &Value, expected: &str) -> bool {
+    match expected {
+        "integer" => value.as_f64().is_some_and(|n| n.fract() == 0.0),
+        "number" => value.is_number(),
+        other => type_name(value) == other,
+    }
+}
+
+struct Validator<'a> {
+    root: &'a Value,
+    errors: Vec<OpenCodeSchemaError>,
+    /// Keywords met in the schema that were not checked
+    unchecked: BTreeSet<String>,
+}
+
+impl<'a> Validator<'a> {
+    fn new(root: &'a Value) -> Self {
+        Self { root, errors: Vec::new(), unchecked: BTreeSet::new() }
+    }
+
+    fn error(&mut self, pointer: &str, keyword: &str, message: String) {
+        self.errors.push(OpenCodeSchemaError {
+            pointer: pointer.to_string(),
+            keyword: keyword.to_string(),
+            message,
+        });
+    }
+
+    /// Errors of `value` against a subschema, without recording them
+    fn try_schema(&mut self, schema: &'a Value, value: &Value, pointer: &str, depth: usize) -> Vec<OpenCodeSchemaError> {
+        let mut branch = Validator::new(self.root);
+        branch.check(schema, value, pointer, depth);
+        self.unchecked.extend(branch.unchecked);
+        branch.errors
+    }
+
+    fn check(&mut self, schema: &'a Value, value: &Value, pointer: &str, depth: usize) {
+        if depth > MAX_DEPTH {
+            return;
+        }
+        let schema = match schema {
+            Value::Bool(false) => return self.error(pointer, "false", "is not allowed here".to_string()),
+            Value::Object(schema) => schema,
+            _ => return,
+        };
+        for keyword in schema.keys() {
+            if !CHECKED_KEYWORDS.contains(&keyword.as_str()) && !ANNOTATION_KEYWORDS.contains(&keyword.as_str()) {
+                self.unchecked.insert(keyword.clone());
+            }
+        }
+
+        if let Some(target) = schema.get("$ref").and_then(Value::as_str) {
+            let root = self.root;
+            match target.strip_prefix('#').and_then(|path| root.pointer(path)) {
+                Some(resolved) => self.check(resolved, value, pointer, depth + 1),
+                None => log::warn!("Unresolvable $ref in opencode schema: {}", target),
+            }
+        }
+
+        if let Some(expected) = schema.get("type") {
+            let allowed: Vec<&str> = match expected {
+                Value::String(t) => vec![t.as_str()],
+                Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+                _ => Vec::new(),
+            };
+            if !allowed.is_empty() && !allowed.iter().any(|t| has_type(value, t)) {
+                // The remaining keywords would only repeat the mismatch
+                return self.error(
+                    pointer,
+                    "type",
+                    format!("must be {}, found {}", allowed.join(" or "), type_name(value)),
+                );
+            }
+        }
+        if let Some(options) = schema.get("enum").and_then(Value::as_array) {
+            if !options.contains(value) {
+                let options: Vec<String> = options.iter().map(Value::to_string).collect();
+                self.error(pointer, "enum", format!("must be one of {}", options.join(", ")));
+            }
+        }
+        if let Some(expected) = schema.get("const") {
+            if expected != value {
+                self.error(pointer, "const", format!("must be {}", expected));
+            }
+        }
+
+        match value {
+            Value::String(s) => self.check_string(schema, s, pointer),
+            Value::Number(_) => self.check_number(schema, value.as_f64().unwrap_or_default(), pointer),
+            Value::Array(items) => self.check_array(schema, items, pointer, depth),
+            Value::Object(members) => self.check_object(schema, members, pointer, depth),
+            _ => {}
+        }
+
+        self.check_combinators(schema, value, pointer, depth);
+    }
+
+    fn check_string(&mut self, schema: &Map<String, Value>, s: &str, pointer: &str) {
+        let length = s.chars().count() as u64;
+        if let Some(min) = schema.get("minLength").and_then(Value::as_u64).filter(|min| length < *min) {
+            self.error(pointer, "minLength", format!("must be at least {} characters long", min));
+        }
+        if let Some(max) = schema.get("maxLength").and_then(Value::as_u64).filter(|max| length > *max) {
+            self.error(pointer, "maxLength", format!("must be at most {} characters long", max));
+        }
+        if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+            match Regex::new(pattern) {
+                Ok(re) if !re.is_match(s) => self.error(pointer, "pattern", format!("must match {}", pattern)),
+                Ok(_) => {}
+                Err(e) => log::warn!("Invalid pattern in opencode schema: {}", e),
+            }
+        }
+    }
+
+    fn check_number(&mut self, schema: &Map<String, Value>, n: f64, pointer: &str) {
+        let bounds: [(&str, &str, fn(f64, f64) -> bool); 4] = [
+            ("minimum", ">=", |n, bound| n >= bound),
+            ("maximum", "<=", |n, bound| n <= bound),
+            ("exclusiveMinimum", ">", |n, bound| n > bound),
+            ("exclusiveMaximum", "<", |n, bound| n < bound),
+        ];
+        for (keyword, op, ok) in bounds {
+            if let Some(bound) = schema.get(keyword).and_then(Value::as_f64) {
+                if !ok(n, bound) {
+                    self.error(pointer, keyword, format!("must be {} {}", op, bound));
+                }
+            }
+        }
+    }
+
+    fn check_array(&mut self, schema: &'a Map<String, Value>, items: &[Value], pointer: &str, depth: usize) {
+        let count = items.len() as u64;
+        if let Some(min) = schema.get("minItems").and_then(Value::as_u64).filter(|min| count < *min) {
+            self.error(pointer, "minItems", format!("must have at least {} items", min));
+        }
+        if let Some(max) = schema.get("maxItems").and_then(Value::as_u64).filter(|max| count > *max) {
+            self.error(pointer, "maxItems", format!("must have at most {} items", max));
+        }
+        if schema.get("uniqueItems").and_then(Value::as_bool) == Some(true) {
+            if let Some(i) = (1..items.len()).find(|&i| items[..i].contains(&items[i])) {
+                self.error(&format!("{}/{}", pointer, i), "uniqueItems", "duplicates an earlier item".to_string());
+            }
+        }
+
+        let prefix = schema.get("prefixItems").and_then(Value::as_array);
+        let prefix_len = prefix.map_or(0, Vec::len);
+        for (i, item) in items.iter().enumerate() {
+            let item_pointer = format!("{}/{}", pointer, i);
+            if let Some(item_schema) = prefix.and_then(|prefix| prefix.get(i)) {
+                self.check(item_schema, item, &item_pointer, depth + 1);
+            } else if let Some(item_schema) = schema.get("items").filter(|_| i >= prefix_len) {
+                self.check(item_schema, item, &item_pointer, depth + 1);
+            }
+        }
+    }
+
+    fn check_object(&mut self, schema: &'a Map<String, Value>, members: &Map<String, Value>, pointer: &str, depth: usize) {
+        let count = members.len() as u64;
+        if let Some(min) = schema.get("minProperties").and_then(Value::as_u64).filter(|min| count < *min) {
+            self.error(pointer, "minProperties", format!("must have at least {} properties", min));
+        }
+        if let Some(max) = schema.get("maxProperties").and_then(Value::as_u64).filter(|max| count > *max) {
+            self.error(pointer, "maxProperties", format!("must have at most {} properties", max));
+        }
+        if let Some(required) = schema.get("required").and_then(Value::as_array) {
+            for name in required.iter().filter_map(Value::as_str) {
+                if !members.contains_key(name) {
+                    self.error(pointer, "required", format!("missing required property \"{}\"", name));
+                }
+            }
+        }
+
+        let properties = schema.get("properties").and_then(Value::as_object);
+        let patterns: Vec<(Regex, &'a Value)> = schema
+            .get("patternProperties")
+            .and_then(Value::as_object)
+            .map(|patterns| {
+                patterns
+                    .iter()
+                    .filter_map(|(pattern, sub)| Regex::new(pattern).ok().map(|re| (re, sub)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let additional = schema.get("additionalProperties");
+        let names = schema.get("propertyNames");
+
+        for (name, member) in members {
+            let member_pointer = format!("{}/{}", pointer, escape_pointer(name));
+            if let Some(names) = names {
+                self.check(names, &Value::String(name.clone()), &member_pointer, depth + 1);
+            }
+            let mut matched = false;
+            if let Some(sub) = properties.and_then(|properties| properties.get(name)) {
+                matched = true;
+                self.check(sub, member, &member_pointer, depth + 1);
+            }
+            for (re, sub) in &patterns {
+                if re.is_match(name) {
+                    matched = true;
+                    self.check(*sub, member, &member_pointer, depth + 1);
+                }
+            }
+            match additional {
+                Some(Value::Bool(false)) if !matched => {
+                    self.error(&member_pointer, "additionalProperties", format!("unknown property \"{}\"", name));
+                }
+                Some(sub) if !matched && sub.is_object() => self.check(sub, member, &member_pointer, depth + 1),
+                _ => {}
+            }
+        }
+    }
+
+    fn check_combinators(&mut self, schema: &'a Map<String, Value>, value: &Value, pointer: &str, depth: usize) {
+        if let Some(all) = schema.get("allOf").and_then(Value::as_array) {
+            for sub in all {
+                self.check(sub, value, pointer, depth + 1);
+            }
+        }
+        if let Some(not) = schema.get("not") {
+            if self.try_schema(not, value, pointer, depth + 1).is_empty() {
+                self.error(pointer, "not", "matches a schema it must not match".to_string());
+            }
+        }
+        for keyword in ["anyOf", "oneOf"] {
+            let Some(branches) = schema.get(keyword).and_then(Value::as_array) else {
+                continue;
+            };
+            let results: Vec<Vec<OpenCodeSchemaError>> = branches
+                .iter()
+                .map(|sub| self.try_schema(sub, value, pointer, depth + 1))
+                .collect();
+            let matches = results.iter().filter(|errors| errors.is_empty()).count();
+            if keyword == "oneOf" && matches > 1 {
+                self.error(pointer, keyword, format!("matches {} alternatives, expected exactly one", matches));
+            } else if matches == 0 {
+                self.report_closest(keyword, pointer, results);
+            }
+        }
+    }
+
+    /// When one alternative fails with fewer errors than all others, the value
+    /// was most likely meant to be that one; report its errors instead of a
+    /// bare "matches none"
+    fn report_closest(&mut self, keyword: &str, pointer: &str, mut results: Vec<Vec<OpenCodeSchemaError>>) {
+        results.sort_by_key(Vec::len);
+        match results.as_slice() {
+            [closest] => self.errors.extend(closest.iter().cloned()),
+            [closest, next, ..] if closest.len() < next.len() => self.errors.extend(closest.iter().cloned()),
+            _ => self.error(pointer, keyword, "does not match any of the allowed forms".to_string()),
+        }
+    }
+}
+
+/// Errors of `config` against `schema` and the schema keywords that were
+/// not checked; valid only when both are empty
+pub fn validate(schema: &Value, config: &Value) -> (Vec<OpenCodeSchemaError>, Vec<String>) {
+    let mut validator = Validator::new(schema);
+    validator.check(schema, config, "", 0);
+    if !validator.unchecked.is_empty() {
+        log::warn!(
+            "opencode schema keywords not checked: {}",
+            validator.unchecked.iter().cloned().collect::<Vec<_>>().join(", ")
+        );
+    }
+    (validator.errors, validator.unchecked.into_iter().collect())
+}
+
+fn bundled_schema() -> Value {
+    serde_json::from_str(BUNDLED_SCHEMA_JSON).unwrap_or_else(|e| {
+        log::warn!("Failed to parse bundled opencode schema: {}", e);
+        Value::Bool(true)
+    })
+}
+
+/// The config's `$schema` when it is an http(s) URL, else the default one
+fn schema_url(config: Option<&Value>) -> String {
+    config
+        .and_then(|config| config.get("$schema"))
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|url| url.starts_with("https://") || url.starts_with("http://"))
+        .unwrap_or(DEFAULT_SCHEMA_URL)
+        .to_string()
+}
+
+/// The cached schema of `url`, falling back to the bundled one
+async fn load_schema(db: &Db, url: &str) -> (Value, OpenCodeSchemaInfo) {
+    let records: Vec<Value> = db
+        .query("SELECT * OMIT id FROM opencode_schema_cache:`config` LIMIT 1")
+        .await
+        .and_then(|mut result| result.take(0))
+        .unwrap_or_default();
+    let cached = records.into_iter().next().filter(|record| record.get("url").and_then(Value::as_str) == Some(url));
+    if let Some(record) = cached {
+        let schema = record
+            .get("content")
+            .and_then(Value::as_str)
+            .and_then(|content| serde_json::from_str::<Value>(content).ok());
+        if let Some(schema) = schema {
+            let info = OpenCodeSchemaInfo {
+                url: url.to_string(),
+                source: "remote".to_string(),
+                updated_at: record.get("updated_at").and_then(Value::as_str).map(str::to_string),
+            };
+            return (schema, info);
+        }
+    }
+    let info = OpenCodeSchemaInfo {
+        url: DEFAULT_SCHEMA_URL.to_string(),
+        source: "bundled".to_string(),
+        updated_at: None,
+    };
+    (bundled_schema(), info)
+}
+
+/// The current config file; Ok(None) when it does not exist
+async fn read_current(state: &tauri::State<'_, DbState>) -> AppResult<Option<Result<Value, String>>> {
+    let path = get_opencode_config_path(state.clone()).await.map_err(AppError::io)?;
+    if !Path::new(&path).exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path)?;
+    Ok(Some(json5::from_str::<Value>(&content).map_err(|e| e.to_string())))
+}
+
+// ============================================================================
+// OpenCode Schema Commands
+// ============================================================================
+
+/// Validate `config`, or the current config file when None, against the
+/// opencode schema. A file that does not parse is reported as a single
+/// "parse" error at the root. Not valid either when the schema uses keywords
+/// that were not checked (`uncheckedKeywords`).
+#[tauri::command]
+pub async fn validate_opencode_config(
+    state: tauri::State<'_, DbState>,
+    config: Option<Value>,
+) -> AppResult<OpenCodeValidationResult> {
+    let config = match config {
+        Some(config) => Ok(config),
+        None => read_current(&state)
+            .await?
+            .ok_or_else(|| AppError::not_found("CONFIG_FILE_NOT_FOUND", &[("tool", "OpenCode")]))?,
+    };
+
+    let db = state.0.lock().await;
+    let (schema, info) = load_schema(&db, &schema_url(config.as_ref().ok())).await;
+    let (errors, unchecked_keywords) = match config {
+        Ok(config) => validate(&schema, &config),
+        Err(e) => {
+            let error = OpenCodeSchemaError {
+                pointer: String::new(),
+                keyword: "parse".to_string(),
+                message: e,
+            };
+            (vec![error], Vec::new())
+        }
+    };
+    Ok(OpenCodeValidationResult {
+        valid: errors.is_empty() && unchecked_keywords.is_empty(),
+        errors,
+        unchecked_keywords,
+        schema: info,
+    })
+}
+
+/// Download the schema the current config's `$schema` points to and cache it
+#[tauri::command]
+pub async fn refresh_opencode_schema(state: tauri::State<'_, DbState>) -> AppResult<OpenCodeSchemaInfo> {
+    let config = read_current(&state).await?.and_then(Result::ok);
+    let url = schema_url(config.as_ref());
+
+    let client = http_client::client_with_timeout(&state, FETCH_TIMEOUT_SECS)
+        .await
+        .map_err(AppError::invalid)?;
+    let response = client.get(http_client::resolve_url(&state, &url).await).send().await?;
+    if !response.status().is_success() {
+        return Err(AppError::http(format!("{}: HTTP {}", url, response.status().as_u16())));
+    }
+    let content = response.text().await?;
+    match serde_json::from_str::<Value>(&content) {
+        Ok(Value::Object(_)) => {}
+        _ => return Err(AppError::invalid(format!("{} is not a JSON schema", url))),
+    }
+
+    let updated_at = Local::now().to_rfc3339();
+    let db = state.0.lock().await;
+    db.query("UPSERT opencode_schema_cache:`config` CONTENT $data")
+        .bind((
+            "data",
+            serde_json::json!({ "url": url, "content": content, "updated_at": updated_at }),
+        ))
+        .await?;
+    Ok(OpenCodeSchemaInfo {
+        url,
+        source: "remote".to_string(),
+        updated_at: Some(updated_at),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pointers(errors: &[OpenCodeSchemaError]) -> Vec<(&str, &str)> {
+        errors.iter().map(|e| (e.pointer.as_str(), e.keyword.as_str())).collect()
+    }
+
+    #[test]
+    fn bundled_schema_accepts_a_typical_config() {
+        let config = serde_json::json!({
+            "$schema": "https://opencode.ai/config.json",
+            "model": "relay/claude-sonnet-4",
+            "plugin": ["oh-my-opencode"],
+            "provider": {
+                "relay": {
+                    "npm": "@ai-sdk/openai-compatible",
+                    "options": { "baseURL": "https://relay.example.com/v1", "timeout": false },
+                    "models": { "claude-sonnet-4": { "limit": { "context": 200000, "output": 64000 } } }
+                }
+            },
+            "mcp": {
+                "fs": { "type": "local", "command": ["npx", "mcp-fs"] },
+                "docs": { "enabled": false }
+            },
+            "someFutureField": 1
+        });
+        assert_eq!(validate(&bundled_schema(), &config), (Vec::new(), Vec::new()));
+    }
+
+    #[test]
+    fn reports_errors_with_json_pointers() {
+        let config = serde_json::json!({
+            "model": 42,
+            "share": "sometimes",
+            "provider": {
+                "a/b": { "models": { "m": { "limit": { "context": 1000 }, "modalities": { "input": ["text", "smell"] } } } }
+            },
+            "mcp": { "fs": { "type": "local" } }
+        });
+        let (mut errors, _) = validate(&bundled_schema(), &config);
+        errors.sort_by(|a, b| a.pointer.cmp(&b.pointer));
+        assert_eq!(
+            pointers(&errors),
+            vec![
+                ("/mcp/fs", "required"),
+                ("/model", "type"),
+                ("/provider/a~1b/models/m/limit", "required"),
+                ("/provider/a~1b/models/m/modalities/input/1", "enum"),
+                ("/share", "enum"),
+            ]
+        );
+    }
+
+    #[test]
+    fn ambiguous_alternatives_report_the_combinator() {
+        let (errors, _) = validate(&bundled_schema(), &serde_json::json!({ "autoupdate": "yes" }));
+        assert_eq!(pointers(&errors), vec![("/autoupdate", "anyOf")]);
+        assert_eq!(schema_url(Some(&serde_json::json!({ "$schema": "./local.json" }))), DEFAULT_SCHEMA_URL);
+    }
+
+    #[test]
+    fn reports_keywords_it_does_not_check() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "description": "annotations need no checking",
+            "propertyNames": { "pattern": "^[a-z]+$" },
+            "dependentRequired": { "url": ["headers"] },
+            "properties": { "url": { "type": "string", "format": "uri", "contentSchema": {} } }
+        });
+        let (errors, unchecked) = validate(&schema, &serde_json::json!({ "url": "https://x", "Bad": 1 }));
+        assert_eq!(pointers(&errors), vec![("/Bad", "pattern")]);
+        assert_eq!(unchecked, vec!["contentSchema".to_string(), "dependentRequired".to_string()]);
+    }
+}
@@ -150,13 +150,11 @@ pub async fn apply_opencode_model<R: Runtime>(
     item_id: &str,    // Format: "provider/model"
 ) -> Result<(), String> {
     // Parse item_id to get provider_id and model_id
-    let parts: Vec<&str> = item_id.split('/').collect();
-    if parts.len() != 2 {
-        return Err(format!("Invalid model ID format: {}", item_id));
-    }
-
-    let provider_id = parts[0];
-    let model_id = parts[1];
+    // Model IDs may contain '/' themselves (e.g. "openrouter/anthropic/claude-sonnet-4")
+    let (provider_id, model_id) = item_id
+        .split_once('/')
+        .filter(|(provider, model)| !provider.is_empty() && !model.is_empty())
+        .ok_or_else(|| format!("Invalid model ID format: {}", item_id))?;
 
     // Read current config
     let result = read_opencode_config(app.state()).await?;
@@ -177,6 +175,9 @@ pub async fn apply_opencode_model<R: Runtime>(
     // Save config from tray (will emit "tray" event)
     super::commands::apply_config_internal(app.state(), app, config, true).await?;
 
+    let label = if model_type == "main" { "主模型" } else { "小模型" };
+    crate::activity_log::record(app, "provider", format!("OpenCode {}已切换到 {}", label, item_id));
+
     Ok(())
 }
 
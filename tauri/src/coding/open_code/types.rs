@@ -300,3 +300,40 @@ pub struct OpenCodeFavoriteProvider {
     pub created_at: String,
     pub updated_at: String,
 }
+
+// ============================================================================
+// Schema Validation Types
+// ============================================================================
+
+/// A config value that violates the opencode JSON schema
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenCodeSchemaError {
+    /// JSON pointer of the offending value, "" for the root
+    pub pointer: String,
+    /// Schema keyword that failed, e.g. "type", "required", or "parse"
+    pub keyword: String,
+    pub message: String,
+}
+
+/// The schema a config was validated against
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenCodeSchemaInfo {
+    pub url: String,
+    pub source: String, // "remote" | "bundled"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenCodeValidationResult {
+    /// No errors and every schema keyword was checked
+    pub valid: bool,
+    pub errors: Vec<OpenCodeSchemaError>,
+    /// Schema keywords the validator does not implement, so parts of the config went unchecked
+    #[serde(default)]
+    pub unchecked_keywords: Vec<String>,
+    pub schema: OpenCodeSchemaInfo,
+}
@@ -0,0 +1,408 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::Local;
+use serde_json::{Map, Value};
+use tauri::AppHandle;
+
+use crate::coding::{change_history, claude_code, db_extract_id, model_rules, oh_my_opencode, open_code, vault};
+use crate::db::DbState;
+use crate::error::{AppError, AppResult};
+use crate::events::{emit_change, ChangeAction};
+use super::types::*;
+
+type Db = surrealdb::Surreal<surrealdb::engine::local::Db>;
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Compare paths without trailing separators
+fn normalize_path(path: &str) -> String {
+    let trimmed = path.trim();
+    let stripped = trimmed.trim_end_matches(['/', '\\']);
+    if stripped.is_empty() {
+        trimmed.to_string()
+    } else {
+        stripped.to_string()
+    }
+}
+
+/// Drop profiles without a provider
+fn clean_profile(profile: Option<ProjectToolProfile>) -> Option<ProjectToolProfile> {
+    profile
+        .map(|p| ProjectToolProfile {
+            provider_id: p.provider_id.trim().to_string(),
+            model: p.model.map(|m| m.trim().to_string()).filter(|m| !m.is_empty()),
+        })
+        .filter(|p| !p.provider_id.is_empty())
+}
+
+fn from_db_value(mut value: Value) -> Option<Project> {
+    let id = db_extract_id(&value);
+    value.as_object_mut()?.insert("id".to_string(), Value::String(id));
+    serde_json::from_value(value).ok()
+}
+
+async fn load_projects(db: &Db) -> AppResult<Vec<Project>> {
+    let records: Vec<Value> = db
+        .query("SELECT *, type::string(id) as id FROM project ORDER BY name ASC")
+        .await?
+        .take(0)?;
+    Ok(records.into_iter().filter_map(from_db_value).collect())
+}
+
+/// Existing JSON(C) object in a project file, empty when the file is missing
+fn read_json_object(path: &Path) -> AppResult<Map<String, Value>> {
+    if !path.exists() {
+        return Ok(Map::new());
+    }
+    let content = fs::read_to_string(path)?;
+    if content.trim().is_empty() {
+        return Ok(Map::new());
+    }
+    match json5::from_str::<Value>(&content).map_err(AppError::invalid)? {
+        Value::Object(map) => Ok(map),
+        _ => Err(AppError::invalid(format!("{} is not a JSON object", path.display()))),
+    }
+}
+
+fn write_json(path: &Path, value: Value) -> AppResult<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let content = serde_json::to_string_pretty(&value).map_err(AppError::invalid)?;
+    change_history::write_config("projects", path, format!("{}\n", content))?;
+    Ok(())
+}
+
+async fn load_project(db: &Db, id: &str) -> AppResult<Project> {
+    let project = load_projects(db)
+        .await?
+        .into_iter()
+        .find(|p| p.id == id)
+        .ok_or_else(|| AppError::not_found("PROJECT_NOT_FOUND", &[("id", id)]))?;
+    if !Path::new(&project.path).is_dir() {
+        return Err(AppError::not_found("PROJECT_DIR_NOT_FOUND", &[("path", project.path.as_str())]));
+    }
+    Ok(project)
+}
+
+/// `.claude/settings.json` (shared, committed) or `.claude/settings.local.json` of a project
+fn claude_settings_path(project: &Project, local: bool) -> PathBuf {
+    let file = if local { "settings.local.json" } else { "settings.json" };
+    Path::new(&project.path).join(".claude").join(file)
+}
+
+fn read_claude_settings_file(path: PathBuf) -> AppResult<ProjectClaudeSettingsFile> {
+    Ok(ProjectClaudeSettingsFile {
+        exists: path.exists(),
+        settings: Value::Object(read_json_object(&path)?),
+        path: path.to_string_lossy().to_string(),
+    })
+}
+
+// ============================================================================
+// Project-level Configs
+// ============================================================================
+
+/// Merge the provider's env into `.claude/settings.local.json`
+async fn apply_claude(db: &Db, dir: &Path, profile: &ProjectToolProfile) -> AppResult<PathBuf> {
+    let records: Vec<Value> = db
+        .query("SELECT *, type::string(id) as id FROM claude_provider WHERE id = type::thing('claude_provider', $id) LIMIT 1")
+        .bind(("id", profile.provider_id.clone()))
+        .await?
+        .take(0)?;
+    let provider = records
+        .into_iter()
+        .next()
+        .map(claude_code::adapter::from_db_value_provider)
+        .ok_or_else(|| AppError::not_found("PROVIDER_NOT_FOUND", &[("id", profile.provider_id.as_str())]))?;
+
+    let mut provider_config: Value = serde_json::from_str(&provider.settings_config).map_err(AppError::invalid)?;
+    vault::resolve_references(&mut provider_config)?;
+    let mut env = claude_code::provider_env(&provider_config);
+    if let Some(model) = &profile.model {
+        env.insert("ANTHROPIC_MODEL".to_string(), Value::String(model.clone()));
+    }
+    model_rules::apply_to_claude_env(&model_rules::load_rules(db).await, &mut env);
+
+    let path = dir.join(".claude").join("settings.local.json");
+    let mut settings = read_json_object(&path)?;
+    let mut merged_env = settings
+        .get("env")
+        .and_then(|v| v.as_object())
+        .cloned()
+        .unwrap_or_default();
+    merged_env.extend(env);
+    settings.insert("env".to_string(), Value::Object(merged_env));
+    write_json(&path, Value::Object(settings))?;
+    Ok(path)
+}
+
+/// Put the favorite provider (and model) into the project's `opencode.json`
+async fn apply_opencode(db: &Db, dir: &Path, profile: &ProjectToolProfile) -> AppResult<PathBuf> {
+    let favorite = open_code::get_favorite_provider_internal(db, &profile.provider_id)
+        .await
+        .map_err(|_| AppError::not_found("PROVIDER_NOT_FOUND", &[("id", profile.provider_id.as_str())]))?;
+    let mut provider_config = serde_json::to_value(&favorite.provider_config).map_err(AppError::invalid)?;
+    vault::resolve_references(&mut provider_config)?;
+
+    let path = dir.join("opencode.json");
+    let mut config = read_json_object(&path)?;
+    config
+        .entry("$schema")
+        .or_insert_with(|| Value::String("https://opencode.ai/config.json".to_string()));
+    let providers = config
+        .entry("provider")
+        .or_insert_with(|| Value::Object(Map::new()));
+    if !providers.is_object() {
+        *providers = Value::Object(Map::new());
+    }
+    if let Some(providers) = providers.as_object_mut() {
+        providers.insert(profile.provider_id.clone(), provider_config);
+    }
+    if let Some(model) = &profile.model {
+        config.insert(
+            "model".to_string(),
+            Value::String(format!("{}/{}", profile.provider_id, model)),
+        );
+    }
+    let mut config = Value::Object(config);
+    model_rules::apply_to_opencode_config(&model_rules::load_rules(db).await, &mut config);
+    write_json(&path, config)?;
+    Ok(path)
+}
+
+/// Write the whole oh-my-opencode config to `.opencode/oh-my-opencode.json`
+async fn apply_oh_my_opencode(db: &Db, dir: &Path, config_id: &str) -> AppResult<PathBuf> {
+    let config = oh_my_opencode::build_final_config(db, config_id)
+        .await
+        .map_err(AppError::invalid)?;
+    let path = dir.join(".opencode").join("oh-my-opencode.json");
+    write_json(&path, config)?;
+    Ok(path)
+}
+
+// ============================================================================
+// Project Commands
+// ============================================================================
+
+#[tauri::command]
+pub async fn list_projects(state: tauri::State<'_, DbState>) -> AppResult<Vec<Project>> {
+    let db = state.0.lock().await;
+    load_projects(&db).await
+}
+
+/// Register a project directory or edit an existing project
+#[tauri::command]
+pub async fn save_project(
+    state: tauri::State<'_, DbState>,
+    app: AppHandle,
+    input: ProjectInput,
+) -> AppResult<Project> {
+    let path = normalize_path(&input.path);
+    let dir = PathBuf::from(&path);
+    if !dir.is_dir() {
+        return Err(AppError::not_found("PROJECT_DIR_NOT_FOUND", &[("path", path.as_str())]));
+    }
+    let name = match input.name.trim() {
+        "" => dir
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.clone()),
+        name => name.to_string(),
+    };
+
+    let db = state.0.lock().await;
+    let existing = load_projects(&db).await?;
+    if existing
+        .iter()
+        .any(|p| p.path == path && Some(&p.id) != input.id.as_ref())
+    {
+        return Err(AppError::conflict("PROJECT_ALREADY_REGISTERED", &[("path", path.as_str())]));
+    }
+
+    let now = Local::now().to_rfc3339();
+    let current = input
+        .id
+        .as_ref()
+        .and_then(|id| existing.iter().find(|p| &p.id == id));
+    let project = Project {
+        id: input.id.clone().unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+        name,
+        path,
+        claude: clean_profile(input.claude),
+        opencode: clean_profile(input.opencode),
+        oh_my_opencode: input
+            .oh_my_opencode
+            .map(|id| id.trim().to_string())
+            .filter(|id| !id.is_empty()),
+        last_applied_at: current.and_then(|p| p.last_applied_at.clone()),
+        created_at: current.map(|p| p.created_at.clone()).unwrap_or_else(|| now.clone()),
+        updated_at: now,
+    };
+
+    let mut data = serde_json::to_value(&project).map_err(AppError::invalid)?;
+    if let Some(map) = data.as_object_mut() {
+        map.remove("id");
+    }
+    db.query("UPSERT type::thing('project', $id) CONTENT $data")
+        .bind(("id", project.id.clone()))
+        .bind(("data", data))
+        .await?;
+
+    let action = if current.is_some() { ChangeAction::Updated } else { ChangeAction::Created };
+    emit_change(&app, "project", action, Some(&project.id), "window");
+    Ok(project)
+}
+
+#[tauri::command]
+pub async fn delete_project(state: tauri::State<'_, DbState>, app: AppHandle, id: String) -> AppResult<()> {
+    let db = state.0.lock().await;
+    db.query("DELETE type::thing('project', $id)")
+        .bind(("id", id.clone()))
+        .await?;
+    emit_change(&app, "project", ChangeAction::Deleted, Some(&id), "window");
+    Ok(())
+}
+
+/// Write the project-level configs of the project registered at `path`
+/// Only tools with a profile are written; existing project files are merged.
+/// With `dry_run` nothing is written and the result carries the diffs.
+#[tauri::command]
+pub async fn apply_project_profile(
+    state: tauri::State<'_, DbState>,
+    app: AppHandle,
+    path: String,
+    dry_run: Option<bool>,
+) -> AppResult<ProjectApplyResult> {
+    let path = normalize_path(&path);
+    let db = state.0.lock().await;
+    let project = load_projects(&db)
+        .await?
+        .into_iter()
+        .find(|p| p.path == path)
+        .ok_or_else(|| AppError::not_found("PROJECT_NOT_REGISTERED", &[("path", path.as_str())]))?;
+
+    let dir = PathBuf::from(&project.path);
+    if !dir.is_dir() {
+        return Err(AppError::not_found("PROJECT_DIR_NOT_FOUND", &[("path", project.path.as_str())]));
+    }
+
+    let dry_run = dry_run.unwrap_or(false);
+    let mut files = Vec::new();
+    let changes = change_history::preview(dry_run, async {
+        if let Some(profile) = &project.claude {
+            files.push(apply_claude(&db, &dir, profile).await?);
+        }
+        if let Some(profile) = &project.opencode {
+            files.push(apply_opencode(&db, &dir, profile).await?);
+        }
+        if let Some(config_id) = &project.oh_my_opencode {
+            files.push(apply_oh_my_opencode(&db, &dir, config_id).await?);
+        }
+        Ok::<_, AppError>(())
+    })
+    .await?;
+    let files: Vec<String> = files.iter().map(|f| f.to_string_lossy().to_string()).collect();
+    if dry_run {
+        return Ok(ProjectApplyResult {
+            project_id: project.id,
+            files,
+            changes,
+        });
+    }
+
+    db.query("UPDATE type::thing('project', $id) SET last_applied_at = $now")
+        .bind(("id", project.id.clone()))
+        .bind(("now", Local::now().to_rfc3339()))
+        .await?;
+    drop(db);
+
+    crate::activity_log::record(&app, "apply", format!("已应用项目配置：{}", project.name));
+    emit_change(&app, "project", ChangeAction::Applied, Some(&project.id), "window");
+    Ok(ProjectApplyResult {
+        project_id: project.id,
+        files,
+        changes,
+    })
+}
+
+/// Both Claude settings files of a registered project
+#[tauri::command]
+pub async fn get_project_claude_settings(
+    state: tauri::State<'_, DbState>,
+    id: String,
+) -> AppResult<ProjectClaudeSettings> {
+    let db = state.0.lock().await;
+    let project = load_project(&db, &id).await?;
+    Ok(ProjectClaudeSettings {
+        project_id: project.id.clone(),
+        shared: read_claude_settings_file(claude_settings_path(&project, false))?,
+        local: read_claude_settings_file(claude_settings_path(&project, true))?,
+    })
+}
+
+/// Replace a project's `.claude/settings.json`, or `.claude/settings.local.json` with `local`
+#[tauri::command]
+pub async fn save_project_claude_settings(
+    state: tauri::State<'_, DbState>,
+    app: AppHandle,
+    id: String,
+    local: bool,
+    settings: Value,
+) -> AppResult<()> {
+    if !settings.is_object() {
+        return Err(AppError::invalid("Claude settings must be a JSON object"));
+    }
+    let db = state.0.lock().await;
+    let project = load_project(&db, &id).await?;
+    write_json(&claude_settings_path(&project, local), settings)?;
+    emit_change(&app, "project", ChangeAction::Updated, Some(&project.id), "window");
+    Ok(())
+}
+
+/// Merge `patch` into a project's `.claude/settings.json` (or `settings.local.json`
+/// with `local`): objects key by key, other values replaced
+#[tauri::command]
+pub async fn merge_project_claude_settings(
+    state: tauri::State<'_, DbState>,
+    app: AppHandle,
+    id: String,
+    local: bool,
+    patch: Value,
+) -> AppResult<Value> {
+    let Value::Object(patch) = patch else {
+        return Err(AppError::invalid("Claude settings must be a JSON object"));
+    };
+    let db = state.0.lock().await;
+    let project = load_project(&db, &id).await?;
+    let path = claude_settings_path(&project, local);
+    let mut settings = read_json_object(&path)?;
+    claude_code::merge::merge(&mut settings, patch, &claude_code::merge::MergeRules::new());
+    write_json(&path, Value::Object(settings.clone()))?;
+    emit_change(&app, "project", ChangeAction::Updated, Some(&project.id), "window");
+    Ok(Value::Object(settings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_path_strips_trailing_separators() {
+        assert_eq!(normalize_path("/home/me/app/"), "/home/me/app");
+        assert_eq!(normalize_path("C:\\code\\app\\"), "C:\\code\\app");
+        assert_eq!(normalize_path("/"), "/");
+    }
+
+    #[test]
+    fn clean_profile_drops_empty_provider() {
+        let profile = ProjectToolProfile {
+            provider_id: " ".to_string(),
+            model: None,
+        };
+        assert!(clean_profile(Some(profile)).is_none());
+    }
+}
@@ -0,0 +1,16 @@
+//! Projects
+//!
+//! Registered project directories, each with the provider / model to use per
+//! tool. `apply_project_profile` writes the project-level configs in one go:
+//! - Claude Code: `.claude/settings.local.json` (env only, other keys kept)
+//! - OpenCode: `opencode.json` (provider entry and model, other keys kept)
+//! - oh-my-opencode: `.opencode/oh-my-opencode.json` (the whole config)
+//!
+//! The project's Claude `settings.json` / `settings.local.json` can also be
+//! read and edited directly.
+
+pub mod commands;
+pub mod types;
+
+pub use commands::*;
+pub use types::*;
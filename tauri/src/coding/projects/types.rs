@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+
+/// Provider (and optional model override) used by one tool in a project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectToolProfile {
+    /// Claude: claude_provider id; OpenCode: favorite provider_id
+    pub provider_id: String,
+    /// Overrides the provider's default model
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Project {
+    pub id: String,
+    pub name: String,
+    /// Absolute project directory
+    pub path: String,
+    #[serde(default)]
+    pub claude: Option<ProjectToolProfile>,
+    #[serde(default)]
+    pub opencode: Option<ProjectToolProfile>,
+    /// oh-my-opencode config id
+    #[serde(default)]
+    pub oh_my_opencode: Option<String>,
+    #[serde(default)]
+    pub last_applied_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Input for registering a project or editing an existing one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectInput {
+    pub id: Option<String>,
+    /// Defaults to the directory name
+    #[serde(default)]
+    pub name: String,
+    pub path: String,
+    #[serde(default)]
+    pub claude: Option<ProjectToolProfile>,
+    #[serde(default)]
+    pub opencode: Option<ProjectToolProfile>,
+    #[serde(default)]
+    pub oh_my_opencode: Option<String>,
+}
+
+/// Result of `apply_project_profile`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectApplyResult {
+    pub project_id: String,
+    /// Files written (or that would be written on a dry run)
+    pub files: Vec<String>,
+    /// Diffs of the files on a dry run; empty otherwise
+    #[serde(default)]
+    pub changes: Vec<crate::coding::change_history::ConfigDiff>,
+}
+
+/// One Claude settings file of a project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectClaudeSettingsFile {
+    pub path: String,
+    pub exists: bool,
+    /// File content; an empty object when missing
+    pub settings: serde_json::Value,
+}
+
+/// Result of `get_project_claude_settings`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectClaudeSettings {
+    pub project_id: String,
+    /// `.claude/settings.json`, shared with the team
+    pub shared: ProjectClaudeSettingsFile,
+    /// `.claude/settings.local.json`, personal (provider env goes here)
+    pub local: ProjectClaudeSettingsFile,
+}
@@ -0,0 +1,323 @@
+use std::fs;
+use std::path::PathBuf;
+use indexmap::IndexMap;
+use serde_json::Value;
+
+use crate::coding::claude_code;
+use crate::coding::codex;
+use crate::coding::open_code::{
+    self, get_favorite_provider_internal, read_opencode_config, OpenCodeConfig,
+    OpenCodeFavoriteProvider, ReadConfigResult,
+};
+use crate::db::DbState;
+use super::types::*;
+use crate::events::{emit_change, ChangeAction};
+
+const SUPPORTED_TARGETS: [&str; 3] = ["opencode", "claude", "codex"];
+
+// ============================================================================
+// Snapshot Helpers
+// ============================================================================
+
+/// Config file content before applying (None = file did not exist)
+struct FileSnapshot {
+    path: PathBuf,
+    content: Option<Vec<u8>>,
+}
+
+fn take_snapshot(path: PathBuf) -> FileSnapshot {
+    let content = fs::read(&path).ok();
+    FileSnapshot { path, content }
+}
+
+fn restore_snapshot(snapshot: &FileSnapshot) -> Result<(), String> {
+    match &snapshot.content {
+        Some(content) => fs::write(&snapshot.path, content),
+        None if snapshot.path.exists() => fs::remove_file(&snapshot.path),
+        None => Ok(()),
+    }
+    .map_err(|e| format!("Failed to restore {}: {}", snapshot.path.display(), e))
+}
+
+/// Config files written when applying to a target
+async fn target_files(state: &tauri::State<'_, DbState>, target: &str) -> Result<Vec<PathBuf>, String> {
+    match target {
+        "opencode" => Ok(vec![PathBuf::from(
+            open_code::get_opencode_config_path(state.clone()).await?,
+        )]),
+        "claude" => Ok(vec![PathBuf::from(claude_code::get_claude_config_path()?)]),
+        "codex" => {
+            let dir = PathBuf::from(codex::get_codex_config_dir_path()?);
+            Ok(vec![dir.join("auth.json"), dir.join("config.toml")])
+        }
+        other => Err(format!("Unsupported target: {}", other)),
+    }
+}
+
+/// Provider table for targets that track an applied provider
+fn provider_table(target: &str) -> Option<&'static str> {
+    match target {
+        "claude" => Some("claude_provider"),
+        "codex" => Some("codex_provider"),
+        _ => None,
+    }
+}
+
+async fn get_applied_provider_id(state: &DbState, table: &str) -> Option<String> {
+    let db = state.0.lock().await;
+    let records: Vec<Value> = db
+        .query(format!(
+            "SELECT *, type::string(id) as id FROM {} WHERE is_applied = true LIMIT 1",
+            table
+        ))
+        .await
+        .ok()?
+        .take(0)
+        .ok()?;
+    records.first().map(crate::coding::db_extract_id)
+}
+
+/// Put the applied flag back on the previously applied provider
+async fn restore_applied_flag(state: &DbState, table: &str, previous: Option<&String>) -> Result<(), String> {
+    let db = state.0.lock().await;
+    db.query(format!("UPDATE {} SET is_applied = false WHERE is_applied = true", table))
+        .await
+        .map_err(|e| format!("Failed to reset applied status: {}", e))?;
+    if let Some(id) = previous {
+        db.query(format!(
+            "UPDATE {} SET is_applied = true WHERE id = type::thing('{}', $id)",
+            table, table
+        ))
+        .bind(("id", id.clone()))
+        .await
+        .map_err(|e| format!("Failed to restore applied status: {}", e))?;
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Target Converters
+// ============================================================================
+
+/// Base URL and API key of a central provider
+fn provider_endpoint(provider: &OpenCodeFavoriteProvider) -> (String, String) {
+    let options = provider.provider_config.options.as_ref();
+    let base_url = options
+        .and_then(|o| o.base_url.clone())
+        .filter(|u| !u.is_empty())
+        .unwrap_or_else(|| provider.base_url.clone());
+    let api_key = options.and_then(|o| o.api_key.clone()).unwrap_or_default();
+    (base_url.trim_end_matches('/').to_string(), api_key)
+}
+
+fn provider_name(provider: &OpenCodeFavoriteProvider) -> String {
+    provider
+        .provider_config
+        .name
+        .clone()
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| provider.provider_id.clone())
+}
+
+/// Add the provider to opencode.json and select the model
+async fn apply_to_opencode(
+    state: &tauri::State<'_, DbState>,
+    app: &tauri::AppHandle,
+    provider: &OpenCodeFavoriteProvider,
+    model: Option<&String>,
+) -> Result<(), String> {
+    let mut config = match read_opencode_config(state.clone()).await? {
+        ReadConfigResult::Success { config } => config,
+        ReadConfigResult::NotFound { .. } => OpenCodeConfig {
+            schema: None,
+            provider: Some(IndexMap::new()),
+            model: None,
+            small_model: None,
+            plugin: None,
+            mcp: None,
+            other: serde_json::Map::new(),
+        },
+        ReadConfigResult::ParseError { error, .. } | ReadConfigResult::Error { error } => {
+            return Err(format!("Failed to read OpenCode config: {}", error));
+        }
+    };
+
+    config
+        .provider
+        .get_or_insert_with(Default::default)
+        .insert(provider.provider_id.clone(), provider.provider_config.clone());
+    if let Some(model) = model {
+        config.model = Some(format!("{}/{}", provider.provider_id, model));
+    }
+    open_code::apply_config_internal(state.clone(), app, config, false).await
+}
+
+/// Create/update a Claude Code provider for the endpoint and apply it
+async fn apply_to_claude(
+    state: &DbState,
+    app: &tauri::AppHandle,
+    provider: &OpenCodeFavoriteProvider,
+    model: Option<&String>,
+) -> Result<(), String> {
+    let (base_url, api_key) = provider_endpoint(provider);
+    // ANTHROPIC_BASE_URL is the server root; the SDK style base URL ends with /v1
+    let base_url = base_url.strip_suffix("/v1").unwrap_or(&base_url);
+
+    let db = state.0.lock().await;
+    let id = claude_code::upsert_claude_provider_by_name(
+        &db,
+        &provider_name(provider),
+        base_url,
+        &api_key,
+        model,
+        None,
+    )
+    .await?;
+    Ok(claude_code::apply_config_internal(&db, app, &id, false).await?)
+}
+
+/// Create/update a Codex provider for the endpoint and apply it
+async fn apply_to_codex(
+    state: &DbState,
+    app: &tauri::AppHandle,
+    provider: &OpenCodeFavoriteProvider,
+    model: Option<&String>,
+) -> Result<(), String> {
+    let (base_url, api_key) = provider_endpoint(provider);
+    let wire_api = match provider.npm.as_str() {
+        "@ai-sdk/openai" => "responses",
+        _ => "chat",
+    };
+    let config = codex::build_codex_provider_config(
+        codex::CodexModelProviderProfile {
+            key: provider.provider_id.clone(),
+            name: Some(provider_name(provider)),
+            base_url: Some(base_url),
+            env_key: None,
+            wire_api: Some(wire_api.to_string()),
+            requires_openai_auth: true,
+        },
+        model.cloned(),
+    )?;
+    let settings_config = serde_json::json!({
+        "auth": { "OPENAI_API_KEY": api_key },
+        "config": config,
+    })
+    .to_string();
+
+    let db = state.0.lock().await;
+    let id = codex::upsert_codex_provider_by_name(&db, &provider_name(provider), settings_config).await?;
+    codex::apply_config_internal(&db, app, &id, false).await
+}
+
+// ============================================================================
+// Unified Apply Commands
+// ============================================================================
+
+/// Apply one central provider to several tools ("opencode", "claude", "codex")
+/// Targets are applied in order; if one fails, every target already applied is
+/// restored (config files and applied provider) so the tools stay consistent.
+#[tauri::command]
+pub async fn apply_provider_everywhere(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    provider_id: String,
+    targets: Vec<String>,
+    model_id: Option<String>,
+) -> Result<ProviderApplyReport, String> {
+    if targets.is_empty() {
+        return Err("Select at least one target".to_string());
+    }
+    if let Some(target) = targets.iter().find(|t| !SUPPORTED_TARGETS.contains(&t.as_str())) {
+        return Err(format!("Unsupported target: {}", target));
+    }
+
+    let provider = {
+        let db = state.0.lock().await;
+        get_favorite_provider_internal(&db, &provider_id).await?
+    };
+    let model = model_id.filter(|m| !m.is_empty()).or_else(|| {
+        let mut ids: Vec<&String> = provider.provider_config.models.keys().collect();
+        ids.sort();
+        ids.first().map(|id| id.to_string())
+    });
+
+    // Snapshot everything the targets may touch before writing anything
+    let mut snapshots: Vec<(String, Vec<FileSnapshot>, Option<String>)> = Vec::new();
+    for target in &targets {
+        let files = target_files(&state, target).await?;
+        let previous = match provider_table(target) {
+            Some(table) => get_applied_provider_id(&state, table).await,
+            None => None,
+        };
+        snapshots.push((
+            target.clone(),
+            files.into_iter().map(take_snapshot).collect(),
+            previous,
+        ));
+    }
+
+    let mut results: Vec<ProviderApplyTargetResult> = Vec::new();
+    let mut failed = false;
+    let mut attempted = 0;
+    for target in &targets {
+        if failed {
+            results.push(ProviderApplyTargetResult {
+                target: target.clone(),
+                success: false,
+                error: Some("Skipped because a previous target failed".to_string()),
+                rolled_back: false,
+            });
+            continue;
+        }
+
+        attempted += 1;
+        let outcome = match target.as_str() {
+            "opencode" => apply_to_opencode(&state, &app, &provider, model.as_ref()).await,
+            "claude" => apply_to_claude(&state, &app, &provider, model.as_ref()).await,
+            "codex" => apply_to_codex(&state, &app, &provider, model.as_ref()).await,
+            other => Err(format!("Unsupported target: {}", other)),
+        };
+        failed = outcome.is_err();
+        results.push(ProviderApplyTargetResult {
+            target: target.clone(),
+            success: outcome.is_ok(),
+            error: outcome.err(),
+            rolled_back: false,
+        });
+    }
+
+    if failed {
+        // Roll back every attempted target, including the one that failed halfway
+        for (result, (target, files, previous)) in results.iter_mut().zip(&snapshots).take(attempted) {
+            for snapshot in files {
+                if let Err(e) = restore_snapshot(snapshot) {
+                    log::warn!("Rollback of {} failed: {}", target, e);
+                }
+            }
+            if let Some(table) = provider_table(target) {
+                if let Err(e) = restore_applied_flag(&state, table, previous.as_ref()).await {
+                    log::warn!("Rollback of {} failed: {}", target, e);
+                }
+            }
+            if result.success {
+                result.success = false;
+                result.rolled_back = true;
+            }
+        }
+    } else {
+        crate::activity_log::record(
+            &app,
+            "provider",
+            format!("{} 已应用到 {}", provider_name(&provider), targets.join(", ")),
+        );
+    }
+
+    emit_change(&app, "opencode_favorite_provider", ChangeAction::Applied, None, "window");
+    Ok(ProviderApplyReport {
+        provider_id,
+        model,
+        applied: !failed,
+        results,
+    })
+}
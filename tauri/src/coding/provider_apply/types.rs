@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+/// Result of applying a provider to one target tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderApplyTargetResult {
+    /// "opencode", "claude" or "codex"
+    pub target: String,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The target was applied but restored after another target failed
+    pub rolled_back: bool,
+}
+
+/// Report of `apply_provider_everywhere`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderApplyReport {
+    pub provider_id: String,
+    /// Model used for the targets
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// True when every target was applied; false means all changes were rolled back
+    pub applied: bool,
+    pub results: Vec<ProviderApplyTargetResult>,
+}
@@ -0,0 +1,553 @@
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+use serde_json::Value;
+
+use tauri::{Emitter, Manager, Runtime};
+
+use crate::coding::claude_code;
+use crate::coding::codex;
+use crate::coding::open_code::{self, OpenCodeModel, OpenCodeProvider, OpenCodeProviderOptions};
+use crate::db::DbState;
+use super::migration;
+use super::onboarding;
+use super::share;
+use super::text_parse;
+use super::types::*;
+use crate::events::{emit_change, ChangeAction};
+use crate::error::{AppError, AppResult};
+use crate::secret_scan::{self, ScanMode, SecretFinding};
+
+const SUPPORTED_TOOLS: [&str; 3] = ["claude", "codex", "opencode"];
+
+/// Imports staged by deep links, waiting for confirmation
+fn pending() -> &'static Mutex<Vec<PendingProviderImport>> {
+    static PENDING: OnceLock<Mutex<Vec<PendingProviderImport>>> = OnceLock::new();
+    PENDING.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+// ============================================================================
+// Validation Helpers
+// ============================================================================
+
+/// Check and normalize an import before staging or applying it
+pub fn validate_import(mut provider: ProviderImport) -> AppResult<ProviderImport> {
+    provider.tool = provider.tool.trim().to_lowercase();
+    provider.name = provider.name.trim().to_string();
+    provider.base_url = provider.base_url.trim().trim_end_matches('/').to_string();
+    provider.api_key = provider.api_key.trim().to_string();
+    provider.model = provider.model.map(|m| m.trim().to_string()).filter(|m| !m.is_empty());
+
+    if !SUPPORTED_TOOLS.contains(&provider.tool.as_str()) {
+        return Err(AppError::validation("UNSUPPORTED_TOOL", &[("tool", provider.tool.as_str())]));
+    }
+    if provider.name.is_empty() {
+        return Err(AppError::validation("NAME_REQUIRED", &[]));
+    }
+    if !provider.base_url.starts_with("http://") && !provider.base_url.starts_with("https://") {
+        return Err(AppError::validation("INVALID_BASE_URL", &[("url", provider.base_url.as_str())]));
+    }
+    Ok(provider)
+}
+
+/// Mask a key for display: first 3 and last 4 characters
+pub fn mask_api_key(api_key: &str) -> String {
+    let chars: Vec<char> = api_key.chars().collect();
+    match chars.len() {
+        0 => String::new(),
+        1..=8 => "…".to_string(),
+        len => format!(
+            "{}…{}",
+            chars[..3].iter().collect::<String>(),
+            chars[len - 4..].iter().collect::<String>()
+        ),
+    }
+}
+
+/// Pending import as shown in the UI (without the key)
+fn for_display(item: &PendingProviderImport) -> PendingProviderImport {
+    let mut item = item.clone();
+    item.provider.api_key = String::new();
+    item
+}
+
+/// OpenCode provider id derived from the provider name
+fn opencode_provider_id(name: &str) -> String {
+    let id: String = name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let id = id.trim_matches('-').to_string();
+    if id.is_empty() {
+        "imported".to_string()
+    } else {
+        id
+    }
+}
+
+// ============================================================================
+// Import
+// ============================================================================
+
+/// Add the provider to its tool (without applying it); returns the provider id
+pub async fn import_provider(state: tauri::State<'_, DbState>, provider: &ProviderImport) -> AppResult<String> {
+    match provider.tool.as_str() {
+        "claude" => {
+            let db = state.0.lock().await;
+            claude_code::upsert_claude_provider_by_name(
+                &db,
+                &provider.name,
+                &provider.base_url,
+                &provider.api_key,
+                provider.model.as_ref(),
+                provider.website_url.as_deref(),
+            )
+            .await
+            .map_err(AppError::db)
+        }
+        "codex" => {
+            let wire_api = match provider.npm.as_deref() {
+                Some("@ai-sdk/openai") | Some("responses") => "responses",
+                _ => "chat",
+            };
+            let config = codex::build_codex_provider_config(
+                codex::CodexModelProviderProfile {
+                    key: opencode_provider_id(&provider.name),
+                    name: Some(provider.name.clone()),
+                    base_url: Some(provider.base_url.clone()),
+                    env_key: None,
+                    wire_api: Some(wire_api.to_string()),
+                    requires_openai_auth: true,
+                },
+                provider.model.clone(),
+            )
+            .map_err(AppError::invalid)?;
+            let settings_config = serde_json::json!({
+                "auth": { "OPENAI_API_KEY": provider.api_key },
+                "config": config,
+            })
+            .to_string();
+            let db = state.0.lock().await;
+            codex::upsert_codex_provider_by_name(&db, &provider.name, settings_config)
+                .await
+                .map_err(AppError::db)
+        }
+        "opencode" => {
+            let provider_id = opencode_provider_id(&provider.name);
+            let mut models = HashMap::new();
+            if let Some(model) = &provider.model {
+                models.insert(
+                    model.clone(),
+                    OpenCodeModel {
+                        name: Some(model.clone()),
+                        limit: None,
+                        modalities: None,
+                        options: None,
+                        variants: None,
+                    },
+                );
+            }
+            let config = OpenCodeProvider {
+                npm: Some(
+                    provider
+                        .npm
+                        .clone()
+                        .unwrap_or_else(|| "@ai-sdk/openai-compatible".to_string()),
+                ),
+                name: Some(provider.name.clone()),
+                options: Some(OpenCodeProviderOptions {
+                    base_url: Some(provider.base_url.clone()),
+                    api_key: Some(provider.api_key.clone()).filter(|k| !k.is_empty()),
+                    headers: None,
+                    timeout: None,
+                    set_cache_key: None,
+                    extra: serde_json::Map::new(),
+                }),
+                models,
+                whitelist: None,
+                blacklist: None,
+            };
+            open_code::upsert_opencode_favorite_provider(state, provider_id.clone(), config, None)
+                .await
+                .map_err(AppError::db)?;
+            Ok(provider_id)
+        }
+        other => Err(AppError::validation("UNSUPPORTED_TOOL", &[("tool", other)])),
+    }
+}
+
+/// Stage an import for confirmation and bring the main window up
+pub fn stage_import<R: Runtime>(
+    app: &tauri::AppHandle<R>,
+    source: &str,
+    provider: ProviderImport,
+) -> AppResult<PendingProviderImport> {
+    let provider = validate_import(provider)?;
+    let item = PendingProviderImport {
+        id: uuid::Uuid::new_v4().to_string(),
+        source: source.to_string(),
+        masked_api_key: mask_api_key(&provider.api_key),
+        provider,
+    };
+    pending().lock().unwrap().push(item.clone());
+
+    let display = for_display(&item);
+    let _ = app.emit("provider-import-pending", &display);
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+    Ok(display)
+}
+
+// ============================================================================
+// Provider Import Commands
+// ============================================================================
+
+/// Imports waiting for confirmation (e.g. from links opened before the UI loaded)
+#[tauri::command]
+pub fn list_pending_provider_imports() -> Vec<PendingProviderImport> {
+    pending().lock().unwrap().iter().map(for_display).collect()
+}
+
+/// Confirm a staged import: add the provider to its tool
+#[tauri::command]
+pub async fn confirm_provider_import(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+) -> AppResult<String> {
+    let item = {
+        let mut items = pending().lock().unwrap();
+        let index = items
+            .iter()
+            .position(|item| item.id == id)
+            .ok_or_else(|| AppError::not_found("IMPORT_NOT_FOUND", &[]))?;
+        items.remove(index)
+    };
+
+    let provider_id = import_provider(state, &item.provider).await?;
+    crate::activity_log::record(
+        &app,
+        "import",
+        format!("已导入 {} ({})", item.provider.name, item.provider.tool),
+    );
+    emit_change(&app, &format!("{}_provider", item.provider.tool), ChangeAction::Imported, None, "window");
+    Ok(provider_id)
+}
+
+/// Discard a staged import
+#[tauri::command]
+pub fn discard_provider_import(id: String) {
+    pending().lock().unwrap().retain(|item| item.id != id);
+}
+
+/// Encode a provider of `tool` into a share string / QR payload
+/// Without `include_api_key` the receiver has to fill in their own key.
+/// Keys found anywhere else in the payload are reported, or block the share
+/// in "block" scan mode unless `allow_secrets` is set.
+#[tauri::command]
+pub async fn encode_provider_share(
+    state: tauri::State<'_, DbState>,
+    tool: String,
+    provider_id: String,
+    include_api_key: bool,
+    allow_secrets: Option<bool>,
+) -> AppResult<ProviderShare> {
+    let mut provider = {
+        let db = state.0.lock().await;
+        share::export_provider(&db, &tool, &provider_id).await?
+    };
+
+    if include_api_key {
+        // Vault references only resolve on this machine
+        let mut api_key = serde_json::Value::String(provider.api_key);
+        crate::coding::vault::resolve_references(&mut api_key)?;
+        provider.api_key = api_key.as_str().unwrap_or_default().to_string();
+    } else {
+        provider.api_key = String::new();
+    }
+
+    let secret_findings = match secret_scan::scan_mode(&state).await {
+        ScanMode::Off => Vec::new(),
+        mode => {
+            let payload = serde_json::to_value(&provider).map_err(AppError::invalid)?;
+            let findings = secret_scan::scan_value("share", &payload);
+            // The API key itself was included on purpose
+            let unexpected: Vec<SecretFinding> = findings
+                .iter()
+                .filter(|f| !(include_api_key && f.location == "share:$.api_key"))
+                .cloned()
+                .collect();
+            secret_scan::enforce(mode, &unexpected, allow_secrets.unwrap_or(false))?;
+            findings
+        }
+    };
+
+    let share_string = share::encode_share(&provider)?;
+    Ok(ProviderShare {
+        qr_payload: share::qr_payload(&share_string),
+        share_string,
+        includes_api_key: include_api_key && !provider.api_key.is_empty(),
+        secret_findings,
+    })
+}
+
+/// Decode a share string and stage it for confirmation like a deep link
+#[tauri::command]
+pub fn import_provider_share(app: tauri::AppHandle, share: String) -> AppResult<PendingProviderImport> {
+    let provider = share::decode_share(&share)?;
+    stage_import(&app, "share", provider)
+}
+
+/// Pull a base URL, key and model out of pasted text (e.g. a relay's welcome email)
+/// and stage them for confirmation; `tool` overrides the guessed tool
+#[tauri::command]
+pub fn parse_provider_from_text(
+    app: tauri::AppHandle,
+    text: String,
+    tool: Option<String>,
+) -> AppResult<PendingProviderImport> {
+    let provider = text_parse::parse_provider_text(&text, tool.as_deref())
+        .ok_or_else(|| AppError::validation("NO_PROVIDER_DETAILS", &[]))?;
+    stage_import(&app, "clipboard", provider)
+}
+
+/// Whether the provider's tool already has a provider that importing it would overwrite
+async fn provider_exists(state: &DbState, provider: &ProviderImport) -> AppResult<bool> {
+    let (table, field, value) = match provider.tool.as_str() {
+        "claude" => ("claude_provider", "name", provider.name.clone()),
+        "codex" => ("codex_provider", "name", provider.name.clone()),
+        _ => ("opencode_favorite_provider", "provider_id", opencode_provider_id(&provider.name)),
+    };
+    let db = state.0.lock().await;
+    let records: Vec<Value> = db
+        .query(format!("SELECT type::string(id) as id FROM {} WHERE {} = $value LIMIT 1", table, field))
+        .bind(("value", value))
+        .await?
+        .take(0)?;
+    Ok(!records.is_empty())
+}
+
+/// Provider switcher apps we can import from and whether their data was found
+#[tauri::command]
+pub fn detect_migration_sources() -> Vec<MigrationSource> {
+    migration::detect_sources()
+}
+
+/// Import the providers of a switcher app (e.g. cc-switch)
+/// With `dry_run` nothing is written and the report shows what would happen.
+/// `path` overrides the source's default data file.
+#[tauri::command]
+pub async fn import_from_switcher(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    source: String,
+    path: Option<String>,
+    dry_run: bool,
+) -> AppResult<MigrationReport> {
+    let path = match path.map(|p| p.trim().to_string()).filter(|p| !p.is_empty()) {
+        Some(path) => PathBuf::from(path),
+        None => migration::source_path(&source)?,
+    };
+    let data = migration::read_source(&source, &path)?;
+    let path = path.to_string_lossy().to_string();
+    run_import(&state, &app, source, path, data, dry_run).await
+}
+
+/// Validate the providers read from a source, report what importing them does
+/// and (unless `dry_run`) import them
+async fn run_import(
+    state: &tauri::State<'_, DbState>,
+    app: &tauri::AppHandle,
+    source: String,
+    path: String,
+    data: migration::SourceData,
+    dry_run: bool,
+) -> AppResult<MigrationReport> {
+    let mut items = Vec::new();
+    let mut seen = HashSet::new();
+    for provider in data.providers {
+        let masked_api_key = mask_api_key(&provider.api_key);
+        let item = match validate_import(provider.clone()) {
+            Ok(provider) => {
+                // A second provider with the same name in the source overwrites the first
+                let first_in_source = seen.insert((provider.tool.clone(), provider.name.clone()));
+                let exists = !first_in_source || provider_exists(state, &provider).await?;
+                MigrationItem {
+                    provider,
+                    masked_api_key,
+                    action: if exists { "update" } else { "create" }.to_string(),
+                    reason: None,
+                }
+            }
+            Err(e) => MigrationItem {
+                provider,
+                masked_api_key,
+                action: "skip".to_string(),
+                reason: Some(e.to_string()),
+            },
+        };
+        items.push(item);
+    }
+
+    let mut imported = 0;
+    if !dry_run {
+        let mut tools = HashSet::new();
+        for item in items.iter_mut().filter(|item| item.action != "skip") {
+            match import_provider(state.clone(), &item.provider).await {
+                Ok(_) => {
+                    imported += 1;
+                    tools.insert(item.provider.tool.clone());
+                }
+                Err(e) => {
+                    item.action = "failed".to_string();
+                    item.reason = Some(e.to_string());
+                }
+            }
+        }
+        if imported > 0 {
+            crate::activity_log::record(app, "import", format!("已从 {} 导入 {} 个供应商", source, imported));
+        }
+        for tool in tools {
+            emit_change(app, &format!("{}_provider", tool), ChangeAction::Imported, None, "window");
+        }
+    }
+
+    for item in &mut items {
+        item.provider.api_key = String::new();
+    }
+    Ok(MigrationReport {
+        source,
+        path,
+        dry_run,
+        items,
+        warnings: data.warnings,
+        imported,
+    })
+}
+
+// ============================================================================
+// First-run Onboarding
+// ============================================================================
+
+/// Onboarding is pending until it is finished once and while no provider is stored
+async fn is_first_launch(state: &DbState) -> AppResult<bool> {
+    let db = state.0.lock().await;
+    let finished: Vec<Value> = db
+        .query("SELECT completed_at FROM onboarding:`app` LIMIT 1")
+        .await?
+        .take(0)?;
+    if !finished.is_empty() {
+        return Ok(false);
+    }
+    for table in ["claude_provider", "codex_provider", "opencode_favorite_provider"] {
+        let records: Vec<Value> = db
+            .query(format!("SELECT type::string(id) as id FROM {} LIMIT 1", table))
+            .await?
+            .take(0)?;
+        if !records.is_empty() {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+async fn finish(state: &DbState) -> AppResult<()> {
+    let db = state.0.lock().await;
+    db.query("UPSERT onboarding:`app` MERGE { completed_at: $now }")
+        .bind(("now", chrono::Local::now().to_rfc3339()))
+        .await?;
+    Ok(())
+}
+
+async fn scan_existing_configs(
+    state: &tauri::State<'_, DbState>,
+) -> (String, Vec<OnboardingSource>, migration::SourceData) {
+    let opencode_path = open_code::get_opencode_config_path(state.clone()).await.ok().map(PathBuf::from);
+    let (sources, data) = onboarding::scan(opencode_path.as_deref());
+    let paths: Vec<&str> = sources
+        .iter()
+        .filter(|s| s.provider_count > 0)
+        .map(|s| s.path.as_str())
+        .filter(|p| !p.is_empty())
+        .collect();
+    (paths.join("; "), sources, data)
+}
+
+/// Existing Claude Code / Codex / OpenCode configs and environment variables,
+/// with a dry-run report of importing all of them
+#[tauri::command]
+pub async fn get_onboarding_scan(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+) -> AppResult<OnboardingScan> {
+    let first_launch = is_first_launch(&state).await?;
+    let (path, sources, data) = scan_existing_configs(&state).await;
+    let report = run_import(&state, &app, "onboarding".to_string(), path, data, true).await?;
+    Ok(OnboardingScan {
+        first_launch,
+        sources,
+        report,
+    })
+}
+
+/// Import everything `get_onboarding_scan` found and finish onboarding
+#[tauri::command]
+pub async fn run_onboarding_import(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+) -> AppResult<MigrationReport> {
+    let (path, _, data) = scan_existing_configs(&state).await;
+    let report = run_import(&state, &app, "onboarding".to_string(), path, data, false).await?;
+    finish(&state).await?;
+    Ok(report)
+}
+
+/// Finish onboarding without importing
+#[tauri::command]
+pub async fn skip_onboarding(state: tauri::State<'_, DbState>) -> AppResult<()> {
+    finish(&state).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> ProviderImport {
+        ProviderImport {
+            tool: " Claude ".to_string(),
+            name: "Relay".to_string(),
+            base_url: "https://relay.example.com/".to_string(),
+            api_key: "sk-1234567890".to_string(),
+            model: Some(String::new()),
+            npm: None,
+            website_url: None,
+        }
+    }
+
+    #[test]
+    fn validate_normalizes_fields() {
+        let provider = validate_import(sample()).unwrap();
+        assert_eq!(provider.tool, "claude");
+        assert_eq!(provider.base_url, "https://relay.example.com");
+        assert_eq!(provider.model, None);
+    }
+
+    #[test]
+    fn validate_rejects_bad_urls() {
+        let mut provider = sample();
+        provider.base_url = "javascript:alert(1)".to_string();
+        assert!(validate_import(provider).is_err());
+    }
+
+    #[test]
+    fn mask_keeps_prefix_and_suffix() {
+        assert_eq!(mask_api_key("sk-1234567890"), "sk-…7890");
+        assert_eq!(mask_api_key("short"), "…");
+        assert_eq!(mask_api_key(""), "");
+    }
+}
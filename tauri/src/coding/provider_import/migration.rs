@@ -0,0 +1,370 @@
+//! Migration from other provider switchers
+//!
+//! Reads the provider records of apps like cc-switch and maps them to
+//! `ProviderImport`s. Nothing is written here; `import_from_switcher`
+//! validates the result, reports it (dry run) and imports it.
+//!
+//! cc-switch keeps its data in `~/.cc-switch/config.json`, either per app
+//! (`{ "claude": { "providers": {...} }, "codex": {...} }`) or, in old
+//! versions, Claude only (`{ "providers": {...} }`). Newer versions use a
+//! SQLite database, which is reported but not read.
+//!
+//! claude-code-router keeps its upstreams in the `Providers` array of
+//! `~/.claude-code-router/config.json`; they become Claude providers.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::error::{AppError, AppResult};
+use super::types::{MigrationSource, ProviderImport};
+
+/// Switcher apps we can import from: (id, display name, config file under home)
+const SOURCES: &[(&str, &str, &str)] = &[
+    ("cc_switch", "CC Switch", ".cc-switch/config.json"),
+    ("claude_code_router", "claude-code-router", ".claude-code-router/config.json"),
+];
+
+/// cc-switch database used by versions that no longer write config.json
+const CC_SWITCH_DB: &str = ".cc-switch/cc-switch.db";
+
+fn home_path(relative: &str) -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(relative))
+}
+
+/// Known switcher apps and whether their data exists on this machine
+pub fn detect_sources() -> Vec<MigrationSource> {
+    SOURCES
+        .iter()
+        .map(|(id, name, relative)| {
+            let path = home_path(relative);
+            MigrationSource {
+                id: id.to_string(),
+                name: name.to_string(),
+                found: path.as_ref().map(|p| p.exists()).unwrap_or(false),
+                path: path.map(|p| p.to_string_lossy().to_string()).unwrap_or_default(),
+            }
+        })
+        .collect()
+}
+
+/// Default data file of a source
+pub fn source_path(source: &str) -> AppResult<PathBuf> {
+    let (_, _, relative) = SOURCES
+        .iter()
+        .find(|(id, _, _)| *id == source)
+        .ok_or_else(|| AppError::validation("UNKNOWN_MIGRATION_SOURCE", &[("source", source)]))?;
+    home_path(relative).ok_or_else(|| AppError::io("Failed to get home directory"))
+}
+
+/// Records read from a source: mapped providers (not validated yet) and
+/// notes about data that could not be read
+pub struct SourceData {
+    pub providers: Vec<ProviderImport>,
+    pub warnings: Vec<String>,
+}
+
+/// Read the providers of `source` from `path`
+pub fn read_source(source: &str, path: &Path) -> AppResult<SourceData> {
+    match source {
+        "cc_switch" => read_cc_switch(path),
+        "claude_code_router" => read_ccr(path),
+        other => Err(AppError::validation("UNKNOWN_MIGRATION_SOURCE", &[("source", other)])),
+    }
+}
+
+fn read_cc_switch(path: &Path) -> AppResult<SourceData> {
+    let mut warnings = Vec::new();
+    if !path.exists() {
+        if home_path(CC_SWITCH_DB).map(|db| db.exists()).unwrap_or(false) {
+            warnings.push(crate::i18n::error("MIGRATION_DB_UNSUPPORTED", &[("path", CC_SWITCH_DB)]).message);
+            return Ok(SourceData { providers: Vec::new(), warnings });
+        }
+        return Err(AppError::not_found(
+            "MIGRATION_SOURCE_NOT_FOUND",
+            &[("path", &path.to_string_lossy())],
+        ));
+    }
+
+    let content = fs::read_to_string(path)?;
+    let config: Value = serde_json::from_str(&content).map_err(AppError::invalid)?;
+    Ok(SourceData {
+        providers: parse_cc_switch(&config),
+        warnings,
+    })
+}
+
+/// Map a cc-switch config.json to imports; unknown apps keep their name as
+/// `tool` so they show up as unsupported in the report
+pub fn parse_cc_switch(config: &Value) -> Vec<ProviderImport> {
+    // v1: Claude providers at the top level
+    if config.get("providers").is_some() {
+        return providers_of(config)
+            .filter_map(|p| map_cc_switch_provider("claude", p))
+            .collect();
+    }
+
+    let Some(apps) = config.as_object() else {
+        return Vec::new();
+    };
+    apps.iter()
+        .filter(|(_, manager)| manager.get("providers").is_some())
+        .flat_map(|(app, manager)| {
+            providers_of(manager)
+                .filter_map(|p| map_cc_switch_provider(app, p))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn providers_of(manager: &Value) -> impl Iterator<Item = &Value> {
+    manager
+        .get("providers")
+        .and_then(|p| p.as_object())
+        .into_iter()
+        .flat_map(|providers| providers.values())
+}
+
+fn str_at<'a>(value: &'a Value, path: &[&str]) -> Option<&'a str> {
+    path.iter()
+        .try_fold(value, |v, key| v.get(key))
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+}
+
+fn map_cc_switch_provider(app: &str, provider: &Value) -> Option<ProviderImport> {
+    let name = str_at(provider, &["name"])?.to_string();
+    let settings = provider.get("settingsConfig").cloned().unwrap_or(Value::Null);
+    let website_url = str_at(provider, &["websiteUrl"]).map(String::from);
+
+    let mut item = ProviderImport {
+        tool: app.to_string(),
+        name,
+        base_url: String::new(),
+        api_key: String::new(),
+        model: None,
+        npm: None,
+        website_url,
+    };
+
+    match app {
+        "claude" => {
+            item.base_url = str_at(&settings, &["env", "ANTHROPIC_BASE_URL"]).unwrap_or_default().to_string();
+            item.api_key = str_at(&settings, &["env", "ANTHROPIC_AUTH_TOKEN"])
+                .or_else(|| str_at(&settings, &["env", "ANTHROPIC_API_KEY"]))
+                .unwrap_or_default()
+                .to_string();
+            item.model = str_at(&settings, &["env", "ANTHROPIC_MODEL"])
+                .or_else(|| str_at(&settings, &["model"]))
+                .map(String::from);
+        }
+        "codex" => {
+            item.api_key = str_at(&settings, &["auth", "OPENAI_API_KEY"]).unwrap_or_default().to_string();
+            let config: toml::Table = str_at(&settings, &["config"])
+                .and_then(|c| toml::from_str(c).ok())
+                .unwrap_or_default();
+            item.model = config.get("model").and_then(|m| m.as_str()).map(String::from);
+            let provider = config
+                .get("model_provider")
+                .and_then(|key| key.as_str())
+                .and_then(|key| config.get("model_providers")?.get(key));
+            if let Some(provider) = provider {
+                item.base_url = provider
+                    .get("base_url")
+                    .and_then(|u| u.as_str())
+                    .unwrap_or_default()
+                    .to_string();
+                if provider.get("wire_api").and_then(|w| w.as_str()) == Some("responses") {
+                    item.npm = Some("responses".to_string());
+                }
+            }
+        }
+        _ => {}
+    }
+    Some(item)
+}
+
+fn read_ccr(path: &Path) -> AppResult<SourceData> {
+    if !path.exists() {
+        return Err(AppError::not_found(
+            "MIGRATION_SOURCE_NOT_FOUND",
+            &[("path", &path.to_string_lossy())],
+        ));
+    }
+    // CCR accepts comments and trailing commas
+    let content = fs::read_to_string(path)?;
+    let config: Value = json5::from_str(&content).map_err(AppError::invalid)?;
+    Ok(parse_ccr(&config, |var| std::env::var(var).ok()))
+}
+
+/// Transformer names of a CCR provider (`{"use": ["openrouter", ["maxtoken", {...}]]}`)
+fn ccr_transformers(provider: &Value) -> Vec<String> {
+    provider
+        .get("transformer")
+        .and_then(|t| t.get("use"))
+        .and_then(|u| u.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|item| match item {
+            Value::String(name) => Some(name.clone()),
+            Value::Array(parts) => parts.first().and_then(|n| n.as_str()).map(String::from),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Map the `Providers` of a CCR config.json to Claude imports
+/// `$VAR` / `${VAR}` keys are resolved through `env`. Anthropic-transformer
+/// providers map directly; others keep their base URL with a warning, since
+/// Claude Code only talks the Anthropic API.
+pub fn parse_ccr(config: &Value, env: impl Fn(&str) -> Option<String>) -> SourceData {
+    let default_route = str_at(config, &["Router", "default"]).and_then(|r| r.split_once(','));
+    let mut data = SourceData {
+        providers: Vec::new(),
+        warnings: Vec::new(),
+    };
+
+    let providers = config.get("Providers").and_then(|p| p.as_array()).into_iter().flatten();
+    for provider in providers {
+        let Some(name) = str_at(provider, &["name"]) else {
+            continue;
+        };
+        let endpoint = str_at(provider, &["api_base_url"]).unwrap_or_default().trim_end_matches('/');
+        let anthropic = ccr_transformers(provider)
+            .iter()
+            .any(|t| t.eq_ignore_ascii_case("anthropic"))
+            || endpoint.ends_with("/messages");
+        let base_url = if anthropic {
+            endpoint.trim_end_matches("/messages").trim_end_matches("/v1")
+        } else {
+            data.warnings.push(crate::i18n::error("CCR_OPENAI_FORMAT", &[("name", name)]).message);
+            endpoint.trim_end_matches("/chat/completions")
+        };
+
+        let mut api_key = str_at(provider, &["api_key"]).unwrap_or_default().to_string();
+        if let Some(var) = api_key.strip_prefix('$') {
+            let var = var.trim_start_matches('{').trim_end_matches('}').to_string();
+            api_key = env(&var).unwrap_or_default();
+            if api_key.is_empty() {
+                data.warnings.push(crate::i18n::error("CCR_ENV_KEY_MISSING", &[("name", name), ("var", &var)]).message);
+            }
+        }
+
+        // The router's default model when it points at this provider, else the first one
+        let model = default_route
+            .filter(|(route_provider, _)| *route_provider == name)
+            .map(|(_, model)| model.to_string())
+            .or_else(|| {
+                provider
+                    .get("models")
+                    .and_then(|m| m.as_array())
+                    .and_then(|m| m.first())
+                    .and_then(|m| m.as_str())
+                    .map(String::from)
+            });
+
+        data.providers.push(ProviderImport {
+            tool: "claude".to_string(),
+            name: name.to_string(),
+            base_url: base_url.to_string(),
+            api_key,
+            model,
+            npm: None,
+            website_url: None,
+        });
+    }
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cc_switch_v2_config() {
+        let config = serde_json::json!({
+            "version": 2,
+            "claude": {
+                "providers": {
+                    "a": {
+                        "id": "a",
+                        "name": "Relay",
+                        "settingsConfig": { "env": {
+                            "ANTHROPIC_BASE_URL": "https://relay.example.com",
+                            "ANTHROPIC_AUTH_TOKEN": "sk-relay"
+                        } },
+                        "websiteUrl": "https://example.com"
+                    }
+                },
+                "current": "a"
+            },
+            "codex": {
+                "providers": {
+                    "b": {
+                        "name": "Codex Relay",
+                        "settingsConfig": {
+                            "auth": { "OPENAI_API_KEY": "sk-codex" },
+                            "config": "model_provider = \"relay\"\nmodel = \"gpt-5\"\n\n[model_providers.relay]\nbase_url = \"https://codex.example.com/v1\"\nwire_api = \"responses\"\n"
+                        }
+                    }
+                }
+            }
+        });
+
+        let mut providers = parse_cc_switch(&config);
+        providers.sort_by(|a, b| a.tool.cmp(&b.tool));
+        assert_eq!(providers.len(), 2);
+        assert_eq!(providers[0].tool, "claude");
+        assert_eq!(providers[0].api_key, "sk-relay");
+        assert_eq!(providers[1].base_url, "https://codex.example.com/v1");
+        assert_eq!(providers[1].model.as_deref(), Some("gpt-5"));
+        assert_eq!(providers[1].npm.as_deref(), Some("responses"));
+    }
+
+    #[test]
+    fn parses_cc_switch_v1_config_as_claude() {
+        let config = serde_json::json!({
+            "providers": { "x": { "name": "Official", "settingsConfig": { "env": {} } } },
+            "current": "x"
+        });
+        let providers = parse_cc_switch(&config);
+        assert_eq!(providers.len(), 1);
+        assert_eq!(providers[0].tool, "claude");
+        assert!(providers[0].base_url.is_empty());
+    }
+
+    #[test]
+    fn parses_ccr_providers_as_claude() {
+        let config = serde_json::json!({
+            "Providers": [
+                {
+                    "name": "deepseek",
+                    "api_base_url": "https://api.deepseek.com/chat/completions",
+                    "api_key": "$DEEPSEEK_KEY",
+                    "models": ["deepseek-chat", "deepseek-reasoner"],
+                    "transformer": { "use": ["deepseek"] }
+                },
+                {
+                    "name": "relay",
+                    "api_base_url": "https://relay.example.com/v1/messages",
+                    "api_key": "sk-relay",
+                    "models": ["claude-sonnet-4"],
+                    "transformer": { "use": ["Anthropic", ["maxtoken", { "max_tokens": 8192 }]] }
+                }
+            ],
+            "Router": { "default": "relay,claude-opus-4" }
+        });
+
+        let data = parse_ccr(&config, |var| (var == "DEEPSEEK_KEY").then_some("sk-ds".to_string()));
+        assert_eq!(data.providers.len(), 2);
+        assert!(data.providers.iter().all(|p| p.tool == "claude"));
+        assert_eq!(data.providers[0].base_url, "https://api.deepseek.com");
+        assert_eq!(data.providers[0].api_key, "sk-ds");
+        assert_eq!(data.providers[0].model.as_deref(), Some("deepseek-chat"));
+        assert_eq!(data.providers[1].base_url, "https://relay.example.com");
+        assert_eq!(data.providers[1].model.as_deref(), Some("claude-opus-4"));
+        // Only the OpenAI-format provider is flagged
+        assert_eq!(data.warnings.len(), 1);
+    }
+}
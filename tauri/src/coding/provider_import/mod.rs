@@ -0,0 +1,9 @@
+pub mod commands;
+pub mod migration;
+pub mod onboarding;
+pub mod share;
+pub mod text_parse;
+pub mod types;
+
+pub use commands::*;
+pub use types::*;
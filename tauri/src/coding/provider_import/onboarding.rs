@@ -0,0 +1,267 @@
+//! First-run onboarding
+//!
+//! Reads the providers the coding CLIs are already configured with, so a new
+//! user can import them in one step: `~/.claude/settings.json`, the Codex
+//! `config.toml` / `auth.json`, the OpenCode config and the `ANTHROPIC_*` /
+//! `OPENAI_*` environment variables. Like `migration`, nothing is written here;
+//! the onboarding commands validate, report and import the result.
+
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::coding::{claude_code, codex};
+use super::migration::SourceData;
+use super::text_parse::name_from_url;
+use super::types::{OnboardingSource, ProviderImport};
+
+const ANTHROPIC_BASE_URL: &str = "https://api.anthropic.com";
+const OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
+
+fn str_at<'a>(value: &'a Value, path: &[&str]) -> Option<&'a str> {
+    path.iter()
+        .try_fold(value, |current, key| current.get(key))?
+        .as_str()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+fn provider(tool: &str, name: Option<String>, base_url: &str, api_key: &str, model: Option<&str>) -> ProviderImport {
+    ProviderImport {
+        tool: tool.to_string(),
+        name: name
+            .or_else(|| name_from_url(base_url))
+            .unwrap_or_else(|| tool.to_string()),
+        base_url: base_url.to_string(),
+        api_key: api_key.to_string(),
+        model: model.map(String::from),
+        npm: None,
+        website_url: None,
+    }
+}
+
+/// Provider in the `env` section of Claude Code's settings.json
+pub fn parse_claude_settings(settings: &Value) -> Option<ProviderImport> {
+    let env = settings.get("env")?;
+    let api_key = str_at(env, &["ANTHROPIC_AUTH_TOKEN"]).or_else(|| str_at(env, &["ANTHROPIC_API_KEY"]))?;
+    let base_url = str_at(env, &["ANTHROPIC_BASE_URL"]).unwrap_or(ANTHROPIC_BASE_URL);
+    Some(provider("claude", None, base_url, api_key, str_at(env, &["ANTHROPIC_MODEL"])))
+}
+
+/// Active model provider of Codex's config.toml with the key from auth.json
+/// (or the provider's `env_key` variable); without a custom provider the
+/// OpenAI key in auth.json is the official API
+pub fn parse_codex_config(
+    config_toml: &str,
+    auth: &Value,
+    env: impl Fn(&str) -> Option<String>,
+) -> Option<ProviderImport> {
+    let info = codex::commands::parse_model_providers(config_toml).ok()?;
+    let auth_key = str_at(auth, &["OPENAI_API_KEY"]).map(String::from);
+
+    let Some(active) = info
+        .model_provider
+        .as_ref()
+        .and_then(|key| info.providers.iter().find(|p| &p.key == key))
+    else {
+        let api_key = auth_key?;
+        return Some(provider("codex", None, OPENAI_BASE_URL, &api_key, info.model.as_deref()));
+    };
+    let base_url = active.base_url.as_deref()?;
+    let api_key = auth_key
+        .or_else(|| active.env_key.as_deref().and_then(&env))
+        .unwrap_or_default();
+    let mut imported = provider(
+        "codex",
+        active.name.clone().or_else(|| Some(active.key.clone())),
+        base_url,
+        &api_key,
+        info.model.as_deref(),
+    );
+    imported.npm = active.wire_api.clone();
+    Some(imported)
+}
+
+/// Custom providers of an OpenCode config (`provider.<id>.options.baseURL`);
+/// `{env:NAME}` keys are resolved from the environment
+pub fn parse_opencode_config(config: &Value, env: impl Fn(&str) -> Option<String>) -> Vec<ProviderImport> {
+    let Some(providers) = config.get("provider").and_then(|p| p.as_object()) else {
+        return Vec::new();
+    };
+    providers
+        .iter()
+        .filter_map(|(id, entry)| {
+            let base_url = str_at(entry, &["options", "baseURL"])?;
+            let api_key = match str_at(entry, &["options", "apiKey"]) {
+                Some(key) => match key.strip_prefix("{env:").and_then(|k| k.strip_suffix('}')) {
+                    Some(name) => env(name).unwrap_or_default(),
+                    None => key.to_string(),
+                },
+                None => String::new(),
+            };
+            let model = entry
+                .get("models")
+                .and_then(|m| m.as_object())
+                .and_then(|m| m.keys().next())
+                .map(String::as_str);
+            let name = str_at(entry, &["name"]).unwrap_or(id);
+            let mut imported = provider("opencode", Some(name.to_string()), base_url, &api_key, model);
+            imported.npm = str_at(entry, &["npm"]).map(String::from);
+            Some(imported)
+        })
+        .collect()
+}
+
+/// Providers set through `ANTHROPIC_*` (Claude Code) and `OPENAI_*` (Codex) variables
+pub fn parse_env(env: impl Fn(&str) -> Option<String>) -> Vec<ProviderImport> {
+    let var = |name: &str| env(name).map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+    let mut providers = Vec::new();
+    if let Some(api_key) = var("ANTHROPIC_AUTH_TOKEN").or_else(|| var("ANTHROPIC_API_KEY")) {
+        let base_url = var("ANTHROPIC_BASE_URL").unwrap_or_else(|| ANTHROPIC_BASE_URL.to_string());
+        providers.push(provider("claude", None, &base_url, &api_key, var("ANTHROPIC_MODEL").as_deref()));
+    }
+    if let Some(api_key) = var("OPENAI_API_KEY") {
+        let base_url = var("OPENAI_BASE_URL").unwrap_or_else(|| OPENAI_BASE_URL.to_string());
+        providers.push(provider("codex", None, &base_url, &api_key, None));
+    }
+    providers
+}
+
+fn env_var(name: &str) -> Option<String> {
+    std::env::var(name).ok()
+}
+
+fn read_json(path: &Path, warnings: &mut Vec<String>) -> Option<Value> {
+    let content = fs::read_to_string(path).ok()?;
+    match json5::from_str(&content) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            warnings.push(format!("{}: {}", path.display(), e));
+            None
+        }
+    }
+}
+
+fn source(id: &str, name: &str, path: &str, found: bool, provider_count: usize) -> OnboardingSource {
+    OnboardingSource {
+        id: id.to_string(),
+        name: name.to_string(),
+        path: path.to_string(),
+        found,
+        provider_count,
+    }
+}
+
+/// Read every known config; `opencode_path` is the resolved OpenCode config file
+pub fn scan(opencode_path: Option<&Path>) -> (Vec<OnboardingSource>, SourceData) {
+    let mut sources = Vec::new();
+    let mut data = SourceData {
+        providers: Vec::new(),
+        warnings: Vec::new(),
+    };
+
+    if let Ok(path) = claude_code::get_claude_config_path() {
+        let path = Path::new(&path);
+        let found = path.exists();
+        let providers: Vec<_> = read_json(path, &mut data.warnings)
+            .and_then(|settings| parse_claude_settings(&settings))
+            .into_iter()
+            .collect();
+        sources.push(source("claude", "Claude Code", &path.to_string_lossy(), found, providers.len()));
+        data.providers.extend(providers);
+    }
+
+    if let (Ok(config_path), Ok(auth_path)) = (
+        codex::commands::get_codex_config_path(),
+        codex::commands::get_codex_auth_path(),
+    ) {
+        let found = config_path.exists() || auth_path.exists();
+        let config = fs::read_to_string(&config_path).unwrap_or_default();
+        let auth = read_json(&auth_path, &mut data.warnings).unwrap_or(Value::Null);
+        let providers: Vec<_> = parse_codex_config(&config, &auth, env_var).into_iter().collect();
+        let dir = config_path.parent().unwrap_or(&config_path).to_string_lossy().to_string();
+        sources.push(source("codex", "Codex", &dir, found, providers.len()));
+        data.providers.extend(providers);
+    }
+
+    if let Some(path) = opencode_path {
+        let found = path.exists();
+        let providers = read_json(path, &mut data.warnings)
+            .map(|config| parse_opencode_config(&config, env_var))
+            .unwrap_or_default();
+        sources.push(source("opencode", "OpenCode", &path.to_string_lossy(), found, providers.len()));
+        data.providers.extend(providers);
+    }
+
+    let providers = parse_env(env_var);
+    sources.push(source("env", "Environment variables", "", !providers.is_empty(), providers.len()));
+    data.providers.extend(providers);
+
+    (sources, data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn no_env(_: &str) -> Option<String> {
+        None
+    }
+
+    #[test]
+    fn parses_claude_and_opencode_configs() {
+        let claude = parse_claude_settings(&json!({
+            "env": {
+                "ANTHROPIC_BASE_URL": "https://relay.example.com",
+                "ANTHROPIC_AUTH_TOKEN": "sk-relay",
+                "ANTHROPIC_MODEL": "claude-sonnet-4"
+            }
+        }))
+        .unwrap();
+        assert_eq!(claude.name, "example");
+        assert_eq!(claude.api_key, "sk-relay");
+        assert_eq!(claude.model.as_deref(), Some("claude-sonnet-4"));
+        assert!(parse_claude_settings(&json!({ "env": {} })).is_none());
+
+        let opencode = parse_opencode_config(
+            &json!({
+                "provider": {
+                    "deepseek": {
+                        "npm": "@ai-sdk/openai-compatible",
+                        "options": { "baseURL": "https://api.deepseek.com/v1", "apiKey": "{env:DEEPSEEK_KEY}" },
+                        "models": { "deepseek-chat": {} }
+                    },
+                    "builtin": { "options": { "apiKey": "sk-x" } }
+                }
+            }),
+            |name| (name == "DEEPSEEK_KEY").then(|| "sk-ds".to_string()),
+        );
+        assert_eq!(opencode.len(), 1);
+        assert_eq!(opencode[0].name, "deepseek");
+        assert_eq!(opencode[0].api_key, "sk-ds");
+        assert_eq!(opencode[0].model.as_deref(), Some("deepseek-chat"));
+    }
+
+    #[test]
+    fn parses_codex_active_provider() {
+        let config = r#"
+model_provider = "relay"
+model = "gpt-5"
+
+[model_providers.relay]
+name = "Relay"
+base_url = "https://relay.example.com/v1"
+wire_api = "responses"
+"#;
+        let codex = parse_codex_config(config, &json!({ "OPENAI_API_KEY": "sk-codex" }), no_env).unwrap();
+        assert_eq!(codex.name, "Relay");
+        assert_eq!(codex.base_url, "https://relay.example.com/v1");
+        assert_eq!(codex.npm.as_deref(), Some("responses"));
+
+        let official = parse_codex_config("", &json!({ "OPENAI_API_KEY": "sk-openai" }), no_env).unwrap();
+        assert_eq!(official.base_url, OPENAI_BASE_URL);
+        assert!(parse_codex_config("", &Value::Null, no_env).is_none());
+    }
+}
@@ -0,0 +1,186 @@
+//! Provider share strings
+//!
+//! A share string is `aitb1:` followed by the URL-safe base64 of a
+//! `ProviderImport` JSON. The QR payload wraps it in an
+//! `aitoolbox://import-share?data=...` link so scanning it opens the app.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde_json::Value;
+
+use crate::coding::{claude_code, codex, open_code};
+use crate::error::{AppError, AppResult};
+use super::types::ProviderImport;
+
+/// Version prefix of share strings
+pub const SHARE_PREFIX: &str = "aitb1:";
+
+type Db = surrealdb::Surreal<surrealdb::engine::local::Db>;
+
+// ============================================================================
+// Encoding
+// ============================================================================
+
+pub fn encode_share(provider: &ProviderImport) -> AppResult<String> {
+    let json = serde_json::to_vec(provider).map_err(AppError::invalid)?;
+    Ok(format!("{}{}", SHARE_PREFIX, URL_SAFE_NO_PAD.encode(json)))
+}
+
+/// Decode a share string; also accepts the QR link form
+pub fn decode_share(share: &str) -> AppResult<ProviderImport> {
+    let share = share.trim();
+    let share = match share.strip_prefix("aitoolbox://import-share?data=") {
+        Some(data) => data,
+        None => share,
+    };
+    let encoded = share
+        .strip_prefix(SHARE_PREFIX)
+        .ok_or_else(|| AppError::validation("INVALID_SHARE_STRING", &[]))?;
+    let json = URL_SAFE_NO_PAD
+        .decode(encoded.trim_end_matches('='))
+        .map_err(|_| AppError::validation("INVALID_SHARE_STRING", &[]))?;
+    serde_json::from_slice(&json).map_err(|_| AppError::validation("INVALID_SHARE_STRING", &[]))
+}
+
+pub fn qr_payload(share: &str) -> String {
+    // Base64 URL-safe output needs no further escaping in a query string
+    format!("aitoolbox://import-share?data={}", share)
+}
+
+// ============================================================================
+// Export From Tools
+// ============================================================================
+
+fn str_at<'a>(value: &'a Value, path: &[&str]) -> Option<&'a str> {
+    path.iter()
+        .try_fold(value, |v, key| v.get(key))
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+}
+
+async fn query_provider(db: &Db, table: &str, id: &str) -> AppResult<Value> {
+    let records: Vec<Value> = db
+        .query(format!(
+            "SELECT *, type::string(id) as id FROM {} WHERE id = type::thing('{}', $id) LIMIT 1",
+            table, table
+        ))
+        .bind(("id", id.to_string()))
+        .await?
+        .take(0)?;
+    records
+        .into_iter()
+        .next()
+        .ok_or_else(|| AppError::not_found("PROVIDER_NOT_FOUND", &[("id", id)]))
+}
+
+async fn export_claude(db: &Db, id: &str) -> AppResult<ProviderImport> {
+    let provider = claude_code::adapter::from_db_value_provider(query_provider(db, "claude_provider", id).await?);
+    let config: Value = serde_json::from_str(&provider.settings_config).map_err(AppError::invalid)?;
+
+    Ok(ProviderImport {
+        tool: "claude".to_string(),
+        base_url: str_at(&config, &["env", "ANTHROPIC_BASE_URL"])
+            .ok_or_else(|| AppError::invalid("Provider has no ANTHROPIC_BASE_URL"))?
+            .to_string(),
+        api_key: str_at(&config, &["env", "ANTHROPIC_AUTH_TOKEN"])
+            .or_else(|| str_at(&config, &["env", "ANTHROPIC_API_KEY"]))
+            .unwrap_or("")
+            .to_string(),
+        model: str_at(&config, &["model"])
+            .or_else(|| str_at(&config, &["env", "ANTHROPIC_MODEL"]))
+            .map(String::from),
+        npm: None,
+        website_url: provider.website_url,
+        name: provider.name,
+    })
+}
+
+async fn export_codex(db: &Db, id: &str) -> AppResult<ProviderImport> {
+    let provider = codex::adapter::from_db_value_provider(query_provider(db, "codex_provider", id).await?);
+    let settings: Value = serde_json::from_str(&provider.settings_config).map_err(AppError::invalid)?;
+    let config: toml::Table =
+        toml::from_str(str_at(&settings, &["config"]).unwrap_or("")).map_err(AppError::invalid)?;
+
+    let key = config.get("model_provider").and_then(|v| v.as_str()).unwrap_or("");
+    let model_provider = config
+        .get("model_providers")
+        .and_then(|v| v.get(key))
+        .ok_or_else(|| AppError::invalid("Provider has no custom model_provider"))?;
+    let toml_str = |v: &toml::Value, k: &str| v.get(k).and_then(|v| v.as_str()).map(String::from);
+
+    Ok(ProviderImport {
+        tool: "codex".to_string(),
+        base_url: toml_str(model_provider, "base_url")
+            .ok_or_else(|| AppError::invalid("Provider has no base_url"))?,
+        api_key: str_at(&settings, &["auth", "OPENAI_API_KEY"]).unwrap_or("").to_string(),
+        model: config.get("model").and_then(|v| v.as_str()).map(String::from),
+        npm: toml_str(model_provider, "wire_api"),
+        website_url: provider.website_url,
+        name: provider.name,
+    })
+}
+
+async fn export_opencode(db: &Db, provider_id: &str) -> AppResult<ProviderImport> {
+    let records: Vec<Value> = db
+        .query("SELECT *, type::string(id) as id FROM opencode_favorite_provider WHERE provider_id = $provider_id LIMIT 1")
+        .bind(("provider_id", provider_id.to_string()))
+        .await?
+        .take(0)?;
+    let favorite = records
+        .into_iter()
+        .next()
+        .and_then(open_code::adapter::from_db_value_favorite_provider)
+        .ok_or_else(|| AppError::not_found("PROVIDER_NOT_FOUND", &[("id", provider_id)]))?;
+    let config = &favorite.provider_config;
+    let options = config.options.as_ref();
+    let mut models: Vec<&String> = config.models.keys().collect();
+    models.sort();
+
+    Ok(ProviderImport {
+        tool: "opencode".to_string(),
+        name: config.name.clone().unwrap_or_else(|| favorite.provider_id.clone()),
+        base_url: options
+            .and_then(|o| o.base_url.clone())
+            .filter(|u| !u.is_empty())
+            .unwrap_or_else(|| favorite.base_url.clone()),
+        api_key: options.and_then(|o| o.api_key.clone()).unwrap_or_default(),
+        model: models.first().map(|m| m.to_string()),
+        npm: config.npm.clone(),
+        website_url: None,
+    })
+}
+
+/// Read a provider of `tool` as a portable import
+pub async fn export_provider(db: &Db, tool: &str, provider_id: &str) -> AppResult<ProviderImport> {
+    match tool {
+        "claude" => export_claude(db, provider_id).await,
+        "codex" => export_codex(db, provider_id).await,
+        "opencode" => export_opencode(db, provider_id).await,
+        other => Err(AppError::validation("UNSUPPORTED_TOOL", &[("tool", other)])),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn share_round_trip() {
+        let provider = ProviderImport {
+            tool: "claude".to_string(),
+            name: "Relay".to_string(),
+            base_url: "https://relay.example.com".to_string(),
+            api_key: String::new(),
+            model: Some("claude-sonnet-4".to_string()),
+            npm: None,
+            website_url: None,
+        };
+        let share = encode_share(&provider).unwrap();
+        assert!(share.starts_with(SHARE_PREFIX));
+
+        let decoded = decode_share(&qr_payload(&share)).unwrap();
+        assert_eq!(decoded.name, "Relay");
+        assert_eq!(decoded.model.as_deref(), Some("claude-sonnet-4"));
+        assert!(decode_share("hello").is_err());
+    }
+}
@@ -0,0 +1,175 @@
+//! Provider details from free text
+//!
+//! Pulls a base URL, API key and model out of pasted text such as a relay's
+//! welcome email or a docs snippet. Pure heuristics: the result is staged
+//! for the user to confirm like any other import.
+
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::secret_scan;
+use super::types::ProviderImport;
+
+/// Endpoint paths people paste along with the base URL
+const ENDPOINT_SUFFIXES: [&str; 5] = [
+    "/chat/completions",
+    "/completions",
+    "/messages",
+    "/responses",
+    "/models",
+];
+
+/// URL words that point at a website page rather than the API
+const WEBSITE_HINTS: [&str; 8] = [
+    "console", "dashboard", "login", "register", "docs", "pricing", "token", "topup",
+];
+
+/// Model name prefixes of the common families
+const MODEL_PREFIXES: &str = r"claude|gpt|gemini|deepseek|qwen|glm|kimi|moonshot|doubao|grok|o[134]";
+
+fn url_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"https?://[^\s"'<>()\[\]{}，。、；：]+"#).unwrap())
+}
+
+/// "API Key: xxx", "令牌：xxx", "token = xxx"
+fn labelled_key_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"(?i)(?:api[ _-]?key|key|token|令牌|密钥)\s*[:：=]\s*([A-Za-z0-9][A-Za-z0-9_\-.]{15,})").unwrap()
+    })
+}
+
+/// "Model: xxx", "模型：xxx"
+fn labelled_model_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"(?i)(?:model|模型)\s*[:：=]\s*([A-Za-z0-9][A-Za-z0-9._:/\-]*)").unwrap())
+}
+
+fn model_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(&format!(r"(?i)\b(?:{})[-.][A-Za-z0-9._\-]*[A-Za-z0-9]", MODEL_PREFIXES)).unwrap())
+}
+
+/// Drop trailing punctuation and pasted endpoint paths
+fn clean_url(url: &str) -> String {
+    let mut url = url.trim_end_matches(['.', ',', ';', ':', '!', '?', '/']).to_string();
+    for suffix in ENDPOINT_SUFFIXES {
+        if let Some(stripped) = url.strip_suffix(suffix) {
+            url = stripped.trim_end_matches('/').to_string();
+        }
+    }
+    url
+}
+
+fn is_website(url: &str) -> bool {
+    let lower = url.to_lowercase();
+    WEBSITE_HINTS.iter().any(|hint| lower.contains(hint))
+}
+
+/// Higher is more likely the API base URL
+fn api_score(url: &str) -> i32 {
+    let lower = url.to_lowercase();
+    let mut score = 0;
+    if lower.contains("/v1") || lower.ends_with("/anthropic") || lower.contains("/api") {
+        score += 2;
+    }
+    if lower.contains("://api.") {
+        score += 1;
+    }
+    if is_website(&lower) {
+        score -= 3;
+    }
+    score
+}
+
+/// Provider name from the host: "api.deepseek.com" -> "deepseek"
+pub(super) fn name_from_url(url: &str) -> Option<String> {
+    let host = reqwest::Url::parse(url).ok()?.host_str()?.to_string();
+    let labels: Vec<&str> = host.split('.').collect();
+    if labels.len() >= 2 && labels.iter().any(|l| l.chars().any(|c| c.is_ascii_alphabetic())) {
+        Some(labels[labels.len() - 2].to_string())
+    } else {
+        Some(host)
+    }
+}
+
+/// Best guess at a provider in `text`; `tool` defaults to "claude" for Anthropic-style
+/// URLs / keys and "opencode" otherwise
+pub fn parse_provider_text(text: &str, tool: Option<&str>) -> Option<ProviderImport> {
+    let urls: Vec<String> = url_re().find_iter(text).map(|m| clean_url(m.as_str())).collect();
+    let base_url = urls.iter().max_by_key(|url| api_score(url))?.clone();
+    let website_url = urls
+        .iter()
+        .find(|url| **url != base_url && is_website(url))
+        .cloned();
+
+    // Known key formats win over "key: ..." labels
+    let api_key = secret_scan::find_keys(text.as_bytes())
+        .into_iter()
+        .map(|(_, _, key)| key)
+        .next()
+        .or_else(|| {
+            labelled_key_re()
+                .captures_iter(text)
+                .map(|c| c[1].to_string())
+                .find(|key| !urls.iter().any(|url| url.contains(key.as_str())))
+        })
+        .unwrap_or_default();
+
+    let without_urls = url_re().replace_all(text, " ");
+    let model = labelled_model_re()
+        .captures(&without_urls)
+        .map(|c| c[1].to_string())
+        .or_else(|| model_re().find(&without_urls).map(|m| m.as_str().to_string()))
+        .filter(|model| api_key.is_empty() || !api_key.contains(model.as_str()));
+
+    let anthropic_style = api_key.starts_with("sk-ant-")
+        || base_url.to_lowercase().contains("anthropic")
+        || base_url.to_lowercase().contains("claude");
+    let tool = tool
+        .map(|t| t.trim().to_lowercase())
+        .filter(|t| !t.is_empty())
+        .unwrap_or_else(|| if anthropic_style { "claude" } else { "opencode" }.to_string());
+    let npm = (tool == "opencode" && anthropic_style).then(|| "@ai-sdk/anthropic".to_string());
+
+    Some(ProviderImport {
+        name: name_from_url(&base_url).unwrap_or_else(|| "imported".to_string()),
+        tool,
+        base_url,
+        api_key,
+        model,
+        npm,
+        website_url,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_relay_welcome_email() {
+        let text = "欢迎使用！\n控制台: https://console.relay-example.com/dashboard\n\
+                    接口地址：https://api.relay-example.com/v1/chat/completions\n\
+                    API Key: sk-abcdefghijklmnopqrstuvwx123456\n推荐模型: glm-4.5-air";
+        let provider = parse_provider_text(text, None).unwrap();
+        assert_eq!(provider.tool, "opencode");
+        assert_eq!(provider.name, "relay-example");
+        assert_eq!(provider.base_url, "https://api.relay-example.com/v1");
+        assert_eq!(provider.api_key, "sk-abcdefghijklmnopqrstuvwx123456");
+        assert_eq!(provider.model.as_deref(), Some("glm-4.5-air"));
+        assert_eq!(provider.website_url.as_deref(), Some("https://console.relay-example.com/dashboard"));
+    }
+
+    #[test]
+    fn detects_anthropic_style_and_labelled_key() {
+        let text = "ANTHROPIC_BASE_URL=https://open.bigmodel.cn/api/anthropic\ntoken = 0123456789abcdef.ghijKLMN";
+        let provider = parse_provider_text(text, None).unwrap();
+        assert_eq!(provider.tool, "claude");
+        assert_eq!(provider.name, "bigmodel");
+        assert_eq!(provider.api_key, "0123456789abcdef.ghijKLMN");
+        assert!(parse_provider_text("no links here", None).is_none());
+    }
+}
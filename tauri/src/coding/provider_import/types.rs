@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+
+use crate::secret_scan::SecretFinding;
+
+/// A provider to add to one tool, from a deep link or a share string
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderImport {
+    /// "claude", "codex" or "opencode"
+    pub tool: String,
+    pub name: String,
+    pub base_url: String,
+    /// Empty when the sender left the key out; the user fills it in later
+    #[serde(default)]
+    pub api_key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// OpenCode SDK package / Codex wire API hint, e.g. "@ai-sdk/openai"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub npm: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub website_url: Option<String>,
+}
+
+/// Import waiting for the user's confirmation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingProviderImport {
+    pub id: String,
+    /// Where the import came from, e.g. "deep_link"
+    pub source: String,
+    pub provider: ProviderImport,
+    /// The key for display, e.g. "sk-…a1b2" (the full key is never sent to the UI)
+    pub masked_api_key: String,
+}
+
+/// Result of `encode_provider_share`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderShare {
+    /// `aitb1:<base64>` string to paste on the other machine
+    pub share_string: String,
+    /// `aitoolbox://import-share?data=...` link to render as a QR code
+    pub qr_payload: String,
+    pub includes_api_key: bool,
+    /// Possible keys in the payload (including the API key when it was included)
+    #[serde(default)]
+    pub secret_findings: Vec<SecretFinding>,
+}
+
+/// A provider switcher app we can import from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationSource {
+    /// e.g. "cc_switch"
+    pub id: String,
+    pub name: String,
+    /// Default data file
+    pub path: String,
+    /// Whether the data file exists on this machine
+    pub found: bool,
+}
+
+/// One provider found in a source and what importing it does
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationItem {
+    /// Mapped provider, without the key
+    pub provider: ProviderImport,
+    pub masked_api_key: String,
+    /// "create", "update" (same name exists), "skip" or "failed" (import error)
+    pub action: String,
+    /// Why the provider is skipped
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Result of `import_from_switcher`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationReport {
+    pub source: String,
+    pub path: String,
+    /// True when nothing was written
+    pub dry_run: bool,
+    pub items: Vec<MigrationItem>,
+    /// Data in the source that could not be read
+    pub warnings: Vec<String>,
+    /// Providers imported (0 on a dry run)
+    pub imported: usize,
+}
+
+/// A config read during first-run onboarding
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OnboardingSource {
+    /// "claude", "codex", "opencode" or "env"
+    pub id: String,
+    pub name: String,
+    /// Config file or directory (empty for environment variables)
+    pub path: String,
+    pub found: bool,
+    /// Providers read from it
+    pub provider_count: usize,
+}
+
+/// Result of `get_onboarding_scan`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OnboardingScan {
+    /// Onboarding was not finished yet and no provider is stored
+    pub first_launch: bool,
+    pub sources: Vec<OnboardingSource>,
+    /// What importing everything would do (dry run)
+    pub report: MigrationReport,
+}
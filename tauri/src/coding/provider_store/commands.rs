@@ -0,0 +1,449 @@
+use chrono::Local;
+use serde_json::Value;
+
+use super::adapter;
+use super::store::ProviderStore;
+use super::types::*;
+use crate::coding::db_id::db_clean_id;
+use crate::error::{AppError, AppResult};
+use crate::events::{emit_change, ChangeAction};
+
+type Db = surrealdb::Surreal<surrealdb::engine::local::Db>;
+
+// ============================================================================
+// Providers
+// ============================================================================
+
+/// List all providers ordered by sort_index
+pub async fn list_providers(db: &Db, store: &ProviderStore) -> AppResult<Vec<StoredProvider>> {
+    let records_result: Result<Vec<Value>, _> = db
+        .query(format!("SELECT *, type::string(id) as id FROM {}", store.provider_table))
+        .await?
+        .take(0);
+
+    match records_result {
+        Ok(records) => {
+            let mut result: Vec<StoredProvider> = records
+                .into_iter()
+                .map(adapter::from_db_value_provider)
+                .collect();
+            result.sort_by_key(|p| p.sort_index.unwrap_or(0));
+            Ok(result)
+        }
+        Err(e) => {
+            log::warn!("Failed to deserialize {} providers: {}", store.display_name, e);
+            Ok(Vec::new())
+        }
+    }
+}
+
+/// Get a provider by id
+pub async fn get_provider(db: &Db, store: &ProviderStore, id: &str) -> AppResult<StoredProvider> {
+    let records: Vec<Value> = db
+        .query(format!(
+            "SELECT *, type::string(id) as id FROM {0} WHERE id = type::thing('{0}', $id) LIMIT 1",
+            store.provider_table
+        ))
+        .bind(("id", id.to_string()))
+        .await?
+        .take(0)?;
+
+    records
+        .into_iter()
+        .next()
+        .map(adapter::from_db_value_provider)
+        .ok_or_else(|| AppError::not_found("PROVIDER_NOT_FOUND", &[("id", id)]))
+}
+
+/// Get a provider that is about to be applied; disabled providers are rejected
+pub async fn get_enabled_provider(
+    db: &Db,
+    store: &ProviderStore,
+    id: &str,
+) -> AppResult<StoredProvider> {
+    let provider = get_provider(db, store, id).await?;
+    if provider.is_disabled {
+        return Err(AppError::conflict("PROVIDER_DISABLED", &[("id", id)]));
+    }
+    Ok(provider)
+}
+
+/// Create a new provider
+pub async fn create_provider(
+    db: &Db,
+    app: &tauri::AppHandle,
+    store: &ProviderStore,
+    provider: StoredProviderInput,
+) -> AppResult<StoredProvider> {
+    // Validate settings_config JSON
+    let _: Value = serde_json::from_str(&provider.settings_config).map_err(AppError::invalid)?;
+
+    let now = Local::now().to_rfc3339();
+    let content = StoredProviderContent {
+        name: provider.name,
+        category: provider.category,
+        settings_config: provider.settings_config,
+        source_provider_id: provider.source_provider_id,
+        website_url: provider.website_url,
+        notes: provider.notes,
+        icon: provider.icon,
+        icon_color: provider.icon_color,
+        sort_index: provider.sort_index,
+        is_applied: false,
+        is_disabled: false,
+        created_at: now.clone(),
+        updated_at: now,
+    };
+
+    let json_data = adapter::to_db_value_provider(&content);
+
+    db.query(format!("CREATE {} CONTENT $data", store.provider_table))
+        .bind(("data", json_data))
+        .await?;
+
+    let result: Result<Vec<Value>, _> = db
+        .query(format!(
+            "SELECT *, type::string(id) as id FROM {} ORDER BY created_at DESC LIMIT 1",
+            store.provider_table
+        ))
+        .await?
+        .take(0);
+
+    emit_change(app, store.provider_table, ChangeAction::Created, None, "window");
+
+    match result {
+        Ok(records) => records
+            .first()
+            .map(|record| adapter::from_db_value_provider(record.clone()))
+            .ok_or_else(|| AppError::db("Failed to retrieve created provider")),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Update an existing provider
+/// Only the record is saved; the caller re-applies it when `is_applied` is set.
+pub async fn update_provider(
+    db: &Db,
+    app: &tauri::AppHandle,
+    store: &ProviderStore,
+    provider: StoredProvider,
+) -> AppResult<StoredProvider> {
+    let id = provider.id.clone();
+    let now = Local::now().to_rfc3339();
+
+    let _: Value = serde_json::from_str(&provider.settings_config).map_err(AppError::invalid)?;
+
+    let existing_result: Result<Vec<Value>, _> = db
+        .query(format!(
+            "SELECT * OMIT id FROM {0} WHERE id = type::thing('{0}', $id) LIMIT 1",
+            store.provider_table
+        ))
+        .bind(("id", id.clone()))
+        .await?
+        .take(0);
+
+    let existing = match existing_result {
+        Ok(records) => records.into_iter().next(),
+        Err(_) => None,
+    };
+    let Some(existing) = existing else {
+        return Err(AppError::not_found("PROVIDER_NOT_FOUND", &[("id", id.as_str())]));
+    };
+
+    let created_at = if !provider.created_at.is_empty() {
+        provider.created_at
+    } else {
+        existing
+            .get("created_at")
+            .and_then(|v| v.as_str())
+            .unwrap_or(&now)
+            .to_string()
+    };
+    let existing_is_disabled = existing
+        .get("is_disabled")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    let content = StoredProviderContent {
+        name: provider.name,
+        category: provider.category,
+        settings_config: provider.settings_config,
+        source_provider_id: provider.source_provider_id,
+        website_url: provider.website_url,
+        notes: provider.notes,
+        icon: provider.icon,
+        icon_color: provider.icon_color,
+        sort_index: provider.sort_index,
+        is_applied: provider.is_applied,
+        is_disabled: existing_is_disabled,
+        created_at,
+        updated_at: now,
+    };
+
+    let json_data = adapter::to_db_value_provider(&content);
+
+    db.query(format!("UPDATE {}:`{}` CONTENT $data", store.provider_table, id))
+        .bind(("data", json_data))
+        .await?;
+
+    emit_change(app, store.provider_table, ChangeAction::Updated, None, "window");
+
+    Ok(StoredProvider {
+        id,
+        name: content.name,
+        category: content.category,
+        settings_config: content.settings_config,
+        source_provider_id: content.source_provider_id,
+        website_url: content.website_url,
+        notes: content.notes,
+        icon: content.icon,
+        icon_color: content.icon_color,
+        sort_index: content.sort_index,
+        is_applied: content.is_applied,
+        is_disabled: content.is_disabled,
+        created_at: content.created_at,
+        updated_at: content.updated_at,
+    })
+}
+
+/// Delete a provider
+pub async fn delete_provider(
+    db: &Db,
+    app: &tauri::AppHandle,
+    store: &ProviderStore,
+    id: &str,
+) -> AppResult<()> {
+    db.query(format!("DELETE {}:`{}`", store.provider_table, id))
+        .await?;
+
+    emit_change(app, store.provider_table, ChangeAction::Deleted, None, "window");
+    Ok(())
+}
+
+/// Reorder providers
+pub async fn reorder_providers(db: &Db, store: &ProviderStore, ids: &[String]) -> AppResult<()> {
+    let now = Local::now().to_rfc3339();
+
+    for (index, id) in ids.iter().enumerate() {
+        db.query(format!(
+            "UPDATE {0} SET sort_index = $index, updated_at = $now WHERE id = type::thing('{0}', $id)",
+            store.provider_table
+        ))
+        .bind(("index", index as i32))
+        .bind(("now", now.clone()))
+        .bind(("id", id.clone()))
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Select a provider (mark as applied in database, but not write to file)
+pub async fn select_provider(
+    db: &Db,
+    app: &tauri::AppHandle,
+    store: &ProviderStore,
+    id: &str,
+) -> AppResult<()> {
+    update_is_applied_status(db, store, id).await?;
+
+    emit_change(app, store.provider_table, ChangeAction::Applied, None, "window");
+    Ok(())
+}
+
+/// Clear the applied mark (e.g. when the config files match no provider)
+pub async fn unselect_provider(
+    db: &Db,
+    app: &tauri::AppHandle,
+    store: &ProviderStore,
+) -> AppResult<()> {
+    db.query(format!(
+        "UPDATE {} SET is_applied = false WHERE is_applied = true",
+        store.provider_table
+    ))
+    .await?;
+
+    emit_change(app, store.provider_table, ChangeAction::Applied, None, "window");
+    Ok(())
+}
+
+/// Toggle is_disabled status for a provider
+pub async fn toggle_provider_disabled(
+    db: &Db,
+    app: &tauri::AppHandle,
+    store: &ProviderStore,
+    provider_id: &str,
+    is_disabled: bool,
+) -> AppResult<()> {
+    let now = Local::now().to_rfc3339();
+    db.query(format!(
+        "UPDATE {}:`{}` SET is_disabled = $is_disabled, updated_at = $now",
+        store.provider_table, provider_id
+    ))
+    .bind(("is_disabled", is_disabled))
+    .bind(("now", now))
+    .await?;
+
+    emit_change(app, store.provider_table, ChangeAction::Updated, None, "window");
+    Ok(())
+}
+
+/// Get the currently applied provider, if any
+pub async fn applied_provider(db: &Db, store: &ProviderStore) -> AppResult<Option<StoredProvider>> {
+    let records: Vec<Value> = db
+        .query(format!(
+            "SELECT *, type::string(id) as id FROM {} WHERE is_applied = true LIMIT 1",
+            store.provider_table
+        ))
+        .await?
+        .take(0)?;
+
+    Ok(records.into_iter().next().map(adapter::from_db_value_provider))
+}
+
+/// Record a provider whose config has just been written as the applied one
+pub async fn mark_applied<R: tauri::Runtime>(
+    db: &Db,
+    app: &tauri::AppHandle<R>,
+    store: &ProviderStore,
+    provider_id: &str,
+    from_tray: bool,
+) -> AppResult<()> {
+    update_is_applied_status(db, store, provider_id).await?;
+    crate::coding::switch_analytics::record_switch(db, store.provider_table, provider_id).await;
+
+    let payload = if from_tray { "tray" } else { "window" };
+    emit_change(app, store.provider_table, ChangeAction::Applied, None, payload);
+
+    Ok(())
+}
+
+/// Internal function: update is_applied status
+async fn update_is_applied_status(db: &Db, store: &ProviderStore, target_id: &str) -> AppResult<()> {
+    let now = Local::now().to_rfc3339();
+
+    db.query(format!(
+        "UPDATE {} SET is_applied = false, updated_at = $now WHERE is_applied = true",
+        store.provider_table
+    ))
+    .bind(("now", now.clone()))
+    .await?;
+
+    db.query(format!(
+        "UPDATE {0} SET is_applied = true, updated_at = $now WHERE id = type::thing('{0}', $id)",
+        store.provider_table
+    ))
+    .bind(("id", target_id.to_string()))
+    .bind(("now", now))
+    .await?;
+
+    Ok(())
+}
+
+// ============================================================================
+// Common Config
+// ============================================================================
+
+/// Get the common config
+pub async fn get_common_config(
+    db: &Db,
+    store: &ProviderStore,
+) -> AppResult<Option<StoredCommonConfig>> {
+    let common_table = store.common_table()?;
+    let records_result: Result<Vec<Value>, _> = db
+        .query(format!("SELECT *, type::string(id) as id FROM {}:`common` LIMIT 1", common_table))
+        .await?
+        .take(0);
+
+    match records_result {
+        Ok(records) => Ok(records
+            .first()
+            .map(|record| adapter::from_db_value_common(record.clone()))),
+        Err(e) => {
+            log::warn!(
+                "{} common config has incompatible format, cleaning up: {}",
+                store.display_name,
+                e
+            );
+            let _ = db.query(format!("DELETE {}:`common`", common_table)).await;
+            Ok(None)
+        }
+    }
+}
+
+/// The common config text ("" if none is saved)
+pub async fn common_config_text(db: &Db, store: &ProviderStore) -> AppResult<String> {
+    Ok(get_common_config(db, store)
+        .await?
+        .map(|common| common.config)
+        .unwrap_or_default())
+}
+
+/// Save the common config
+/// Only the record is saved; the caller re-applies the current provider.
+pub async fn save_common_config(
+    db: &Db,
+    app: &tauri::AppHandle,
+    store: &ProviderStore,
+    config: &str,
+) -> AppResult<()> {
+    let common_table = store.common_table()?;
+    if !config.trim().is_empty() {
+        let _: Value = serde_json::from_str(config).map_err(AppError::invalid)?;
+    }
+
+    let json_data = adapter::to_db_value_common(config);
+
+    db.query(format!("UPSERT {}:`common` CONTENT $data", common_table))
+        .bind(("data", json_data))
+        .await?;
+
+    emit_change(app, common_table, ChangeAction::Updated, None, "window");
+    Ok(())
+}
+
+// ============================================================================
+// Tray
+// ============================================================================
+
+/// Get tray provider data
+pub async fn tray_data(db: &Db, store: &ProviderStore) -> AppResult<TrayProviderData> {
+    let records_result: Result<Vec<Value>, _> = db
+        .query(format!("SELECT *, type::string(id) as id FROM {}", store.provider_table))
+        .await?
+        .take(0);
+
+    let mut items: Vec<TrayProviderItem> = Vec::new();
+
+    if let Ok(records) = records_result {
+        for record in records {
+            if let (Some(raw_id), Some(name), Some(is_applied), sort_index) = (
+                record.get("id").and_then(|v| v.as_str()),
+                record.get("name").and_then(|v| v.as_str()),
+                record.get("is_applied").and_then(|v| v.as_bool()),
+                record.get("sort_index").and_then(|v| v.as_i64()).unwrap_or(0),
+            ) {
+                let id = db_clean_id(raw_id);
+                let is_disabled = record
+                    .get("is_disabled")
+                    .or_else(|| record.get("isDisabled"))
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
+                items.push(TrayProviderItem {
+                    id,
+                    display_name: name.to_string(),
+                    is_selected: is_applied,
+                    is_disabled,
+                    sort_index,
+                });
+            }
+        }
+    }
+
+    items.sort_by_key(|c| c.sort_index);
+
+    Ok(TrayProviderData {
+        title: format!("──── {} ────", store.display_name),
+        items,
+    })
+}
@@ -0,0 +1,17 @@
+//! Shared provider storage for tools that switch between saved providers
+//!
+//! Tools such as Gemini CLI and Windsurf keep their providers as
+//! `{ name, category, settings_config, ... }` records in a `<tool>_provider`
+//! table and an optional `<tool>_common_config:common` record. The CRUD,
+//! ordering, selection, tray and common-config logic lives here, parameterized
+//! by a [`ProviderStore`]; each tool module only maps `settings_config` to its
+//! own config files.
+
+pub mod adapter;
+pub mod commands;
+pub mod store;
+pub mod types;
+
+pub use commands::*;
+pub use store::*;
+pub use types::*;
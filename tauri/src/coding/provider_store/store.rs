@@ -0,0 +1,19 @@
+use crate::error::{AppError, AppResult};
+
+/// Describes where one tool keeps its providers
+pub struct ProviderStore {
+    /// Human readable name for logs and tray titles
+    pub display_name: &'static str,
+    /// Provider table (also the change event entity)
+    pub provider_table: &'static str,
+    /// Common config table (also the change event entity); None if the tool has none
+    pub common_table: Option<&'static str>,
+}
+
+impl ProviderStore {
+    /// Common config table, or an error for tools without one
+    pub fn common_table(&self) -> AppResult<&'static str> {
+        self.common_table
+            .ok_or_else(|| AppError::invalid(format!("{} has no common config", self.display_name)))
+    }
+}
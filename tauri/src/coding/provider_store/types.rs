@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// Provider Types
+// ============================================================================
+
+/// StoredProvider - API response
+///
+/// `settings_config` is a JSON string whose shape is defined by the tool
+/// (see the alias in each tool's `types.rs`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredProvider {
+    pub id: String,
+    pub name: String,
+    pub category: String,
+    pub settings_config: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_provider_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub website_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_index: Option<i32>,
+    pub is_applied: bool,
+    pub is_disabled: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// StoredProvider - Content for create/update (Database storage)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredProviderContent {
+    pub name: String,
+    pub category: String,
+    pub settings_config: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_provider_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub website_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_index: Option<i32>,
+    pub is_applied: bool,
+    pub is_disabled: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// StoredProvider - Input from frontend (for create operation)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredProviderInput {
+    #[serde(default)]
+    pub id: Option<String>,
+    pub name: String,
+    pub category: String,
+    pub settings_config: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_provider_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub website_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_index: Option<i32>,
+}
+
+// ============================================================================
+// Common Config Types
+// ============================================================================
+
+/// StoredCommonConfig - API response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredCommonConfig {
+    pub config: String,
+    pub updated_at: String,
+}
+
+// ============================================================================
+// Tray Types
+// ============================================================================
+
+/// Item for provider selection in tray menu
+#[derive(Debug, Clone)]
+pub struct TrayProviderItem {
+    pub id: String,
+    pub display_name: String,
+    pub is_selected: bool,
+    pub is_disabled: bool,
+    pub sort_index: i64,
+}
+
+/// Data for provider submenu
+#[derive(Debug, Clone)]
+pub struct TrayProviderData {
+    pub title: String,
+    pub items: Vec<TrayProviderItem>,
+}
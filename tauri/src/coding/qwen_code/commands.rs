@@ -0,0 +1,243 @@
+use chrono::Local;
+use serde_json::Value;
+
+use crate::coding::change_history;
+use crate::coding::gemini_cli::types::*;
+use crate::coding::gemini_style::{self, GeminiStyleTool};
+use crate::coding::provider_store::{self, ProviderStore};
+use crate::db::DbState;
+use crate::error::{AppError, AppResult};
+
+/// Qwen Code follows the Gemini CLI config layout (~/.qwen/settings.json and ~/.qwen/.env)
+/// but talks to OpenAI-compatible endpoints
+pub const QWEN_CODE: GeminiStyleTool = GeminiStyleTool {
+    key: "qwen_code",
+    store: ProviderStore {
+        display_name: "Qwen Code",
+        provider_table: "qwen_code_provider",
+        common_table: Some("qwen_code_common_config"),
+    },
+    config_dir: ".qwen",
+    env_fields: &["OPENAI_API_KEY", "OPENAI_BASE_URL", "OPENAI_MODEL"],
+    api_key_fields: &["OPENAI_API_KEY"],
+    api_key_auth_type: "openai",
+    model_env_field: Some("OPENAI_MODEL"),
+};
+
+// ============================================================================
+// Qwen Code Config Path Commands
+// ============================================================================
+
+/// Get Qwen Code settings.json file path
+#[tauri::command]
+pub fn get_qwen_code_config_path() -> Result<String, String> {
+    Ok(QWEN_CODE.settings_path()?.to_string_lossy().to_string())
+}
+
+/// Reveal Qwen Code config folder in file explorer
+#[tauri::command]
+pub fn reveal_qwen_code_config_folder() -> Result<(), String> {
+    gemini_style::reveal_config_folder(&QWEN_CODE)
+}
+
+// ============================================================================
+// Qwen Code Provider Commands
+// ============================================================================
+
+/// List all Qwen Code providers ordered by sort_index
+#[tauri::command]
+pub async fn list_qwen_code_providers(
+    state: tauri::State<'_, DbState>,
+) -> AppResult<Vec<GeminiCliProvider>> {
+    let db = state.0.lock().await;
+    provider_store::list_providers(&db, &QWEN_CODE.store).await
+}
+
+/// Create a new Qwen Code provider
+#[tauri::command]
+pub async fn create_qwen_code_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    provider: GeminiCliProviderInput,
+) -> AppResult<GeminiCliProvider> {
+    let db = state.0.lock().await;
+    provider_store::create_provider(&db, &app, &QWEN_CODE.store, provider).await
+}
+
+/// Update an existing Qwen Code provider
+#[tauri::command]
+pub async fn update_qwen_code_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    provider: GeminiCliProvider,
+) -> AppResult<GeminiCliProvider> {
+    let db = state.0.lock().await;
+    gemini_style::update_provider(&db, &app, &QWEN_CODE, provider).await
+}
+
+/// Delete a Qwen Code provider
+#[tauri::command]
+pub async fn delete_qwen_code_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+    provider_store::delete_provider(&db, &app, &QWEN_CODE.store, &id).await
+}
+
+/// Reorder Qwen Code providers
+#[tauri::command]
+pub async fn reorder_qwen_code_providers(
+    state: tauri::State<'_, DbState>,
+    ids: Vec<String>,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+    provider_store::reorder_providers(&db, &QWEN_CODE.store, &ids).await
+}
+
+/// Select a Qwen Code provider (mark as applied in database, but not write to file)
+#[tauri::command]
+pub async fn select_qwen_code_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+    provider_store::select_provider(&db, &app, &QWEN_CODE.store, &id).await
+}
+
+/// Toggle is_disabled status for a provider
+#[tauri::command]
+pub async fn toggle_qwen_code_provider_disabled(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    provider_id: String,
+    is_disabled: bool,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+    provider_store::toggle_provider_disabled(&db, &app, &QWEN_CODE.store, &provider_id, is_disabled).await
+}
+
+/// Set the model of the currently applied provider and re-apply it
+#[tauri::command]
+pub async fn set_qwen_code_model(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    model: String,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+
+    let provider = provider_store::applied_provider(&db, &QWEN_CODE.store)
+        .await?
+        .ok_or_else(|| {
+            AppError::not_found("NO_APPLIED_PROVIDER", &[("tool", QWEN_CODE.store.display_name)])
+        })?;
+
+    let mut config: Value =
+        serde_json::from_str(&provider.settings_config).map_err(AppError::invalid)?;
+    if !config.is_object() {
+        config = serde_json::json!({});
+    }
+    let root = config.as_object_mut().unwrap();
+    let env = root
+        .entry("env".to_string())
+        .or_insert_with(|| serde_json::json!({}));
+    if let Some(env) = env.as_object_mut() {
+        if model.trim().is_empty() {
+            env.remove("OPENAI_MODEL");
+        } else {
+            env.insert("OPENAI_MODEL".to_string(), Value::String(model.trim().to_string()));
+        }
+    }
+    if let Some(model_obj) = root
+        .get_mut("config")
+        .and_then(|c| c.get_mut("model"))
+        .and_then(|m| m.as_object_mut())
+    {
+        model_obj.remove("name");
+    }
+
+    let settings_config = serde_json::to_string(&config).map_err(AppError::invalid)?;
+    let now = Local::now().to_rfc3339();
+    db.query("UPDATE qwen_code_provider SET settings_config = $config, updated_at = $now WHERE id = type::thing('qwen_code_provider', $id)")
+        .bind(("config", settings_config))
+        .bind(("now", now))
+        .bind(("id", provider.id.clone()))
+        .await?;
+
+    apply_config_internal(&db, &app, &provider.id, false).await
+}
+
+// ============================================================================
+// Qwen Code Config File Commands
+// ============================================================================
+
+/// Read current Qwen Code settings.json and .env
+#[tauri::command]
+pub async fn read_qwen_code_settings() -> Result<GeminiCliSettings, String> {
+    gemini_style::read_settings(&QWEN_CODE)
+}
+
+/// Get the mcpServers section of settings.json
+#[tauri::command]
+pub async fn get_qwen_code_mcp_servers() -> Result<Value, String> {
+    gemini_style::get_mcp_servers(&QWEN_CODE)
+}
+
+/// Preview the changes applying a provider would make (diff source for the frontend)
+#[tauri::command]
+pub async fn preview_qwen_code_config(
+    state: tauri::State<'_, DbState>,
+    provider_id: String,
+) -> AppResult<GeminiCliApplyPreview> {
+    let db = state.0.lock().await;
+    gemini_style::preview_config(&db, &QWEN_CODE, &provider_id).await
+}
+
+/// Apply Qwen Code provider configuration to settings.json and .env
+/// `dry_run` returns the diff of the files instead of writing them.
+#[tauri::command]
+pub async fn apply_qwen_code_config(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    provider_id: String,
+    dry_run: Option<bool>,
+) -> AppResult<Vec<change_history::ConfigDiff>> {
+    let db = state.0.lock().await;
+    gemini_style::apply_config(&db, &app, &QWEN_CODE, &provider_id, dry_run.unwrap_or(false)).await
+}
+
+/// Internal function to apply config: writes files and updates database
+pub async fn apply_config_internal<R: tauri::Runtime>(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    app: &tauri::AppHandle<R>,
+    provider_id: &str,
+    from_tray: bool,
+) -> AppResult<()> {
+    gemini_style::apply_config_internal(db, app, &QWEN_CODE, provider_id, from_tray).await
+}
+
+// ============================================================================
+// Qwen Code Common Config Commands
+// ============================================================================
+
+/// Get Qwen Code common config
+#[tauri::command]
+pub async fn get_qwen_code_common_config(
+    state: tauri::State<'_, DbState>,
+) -> AppResult<Option<GeminiCliCommonConfig>> {
+    let db = state.0.lock().await;
+    provider_store::get_common_config(&db, &QWEN_CODE.store).await
+}
+
+/// Save Qwen Code common config
+#[tauri::command]
+pub async fn save_qwen_code_common_config(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    config: String,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+    gemini_style::save_common_config(&db, &app, &QWEN_CODE, &config).await
+}
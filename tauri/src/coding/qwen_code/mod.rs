@@ -0,0 +1,4 @@
+pub mod commands;
+pub mod tray_support;
+
+pub use commands::*;
@@ -0,0 +1,381 @@
+use chrono::Local;
+use reqwest::RequestBuilder;
+use serde_json::Value;
+
+use crate::coding::db_id::db_extract_id;
+use crate::coding::open_code::{
+    build_openai_compatible_provider, upsert_opencode_favorite_provider, OpenCodeFavoriteProvider,
+};
+use crate::db::DbState;
+use crate::http_client;
+use super::types::*;
+
+const RELAY_KINDS: [&str; 2] = ["one-api", "new-api"];
+
+/// Page size used when listing channels/tokens
+const PAGE_SIZE: usize = 100;
+
+// ============================================================================
+// Relay Helpers
+// ============================================================================
+
+fn from_db_value_instance(value: Value) -> Option<RelayInstance> {
+    let id = db_extract_id(&value);
+    let mut instance: RelayInstance = serde_json::from_value(value).ok()?;
+    instance.id = id;
+    Some(instance)
+}
+
+async fn get_instance(state: &DbState, id: &str) -> Result<RelayInstance, String> {
+    let db = state.0.lock().await;
+    let records_result: Result<Vec<Value>, _> = db
+        .query("SELECT *, type::string(id) as id FROM relay_instance WHERE id = type::thing('relay_instance', $id) LIMIT 1")
+        .bind(("id", id.to_string()))
+        .await
+        .map_err(|e| format!("Failed to query relay instance: {}", e))?
+        .take(0);
+
+    records_result
+        .map_err(|e| format!("Failed to deserialize relay instance: {}", e))?
+        .into_iter()
+        .next()
+        .and_then(from_db_value_instance)
+        .ok_or_else(|| format!("Relay instance '{}' not found", id))
+}
+
+/// Attach admin authentication headers
+fn authorize(request: RequestBuilder, instance: &RelayInstance) -> RequestBuilder {
+    let request = request.header("Authorization", format!("Bearer {}", instance.access_token));
+    match instance.user_id {
+        Some(user_id) if instance.kind == "new-api" => {
+            request.header("New-Api-User", user_id.to_string())
+        }
+        _ => request,
+    }
+}
+
+/// Call a relay admin endpoint and unwrap the `{ success, message, data }` envelope
+async fn admin_request(
+    state: &DbState,
+    instance: &RelayInstance,
+    method: reqwest::Method,
+    path: &str,
+    body: Option<Value>,
+) -> Result<Value, String> {
+    let client = http_client::client(state).await?;
+    let url = format!("{}{}", instance.base_url.trim_end_matches('/'), path);
+
+    let mut request = authorize(client.request(method, &url), instance);
+    if let Some(body) = body {
+        request = request.json(&body);
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to relay: {}", e))?;
+
+    let status = response.status();
+    let payload: Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Invalid relay response ({}): {}", status, e))?;
+
+    if !payload.get("success").and_then(|v| v.as_bool()).unwrap_or(false) {
+        let message = payload
+            .get("message")
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown error");
+        return Err(format!("Relay request failed: {}", message));
+    }
+    Ok(payload.get("data").cloned().unwrap_or(Value::Null))
+}
+
+/// List data is an array (one-api) or `{ items: [...] }` (new-api)
+fn list_items(data: Value) -> Vec<Value> {
+    match data {
+        Value::Array(items) => items,
+        Value::Object(mut obj) => match obj.remove("items") {
+            Some(Value::Array(items)) => items,
+            _ => Vec::new(),
+        },
+        _ => Vec::new(),
+    }
+}
+
+fn parse_token(value: &Value) -> RelayToken {
+    let get_i64 = |key: &str| value.get(key).and_then(|v| v.as_i64()).unwrap_or(0);
+    let key = value.get("key").and_then(|v| v.as_str()).unwrap_or("");
+    RelayToken {
+        id: get_i64("id"),
+        name: value
+            .get("name")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string(),
+        key: if key.starts_with("sk-") {
+            key.to_string()
+        } else {
+            format!("sk-{}", key)
+        },
+        status: get_i64("status"),
+        remain_quota: get_i64("remain_quota"),
+        used_quota: get_i64("used_quota"),
+        unlimited_quota: value
+            .get("unlimited_quota")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+        expired_time: value
+            .get("expired_time")
+            .and_then(|v| v.as_i64())
+            .unwrap_or(-1),
+    }
+}
+
+async fn fetch_tokens(state: &DbState, instance: &RelayInstance) -> Result<Vec<RelayToken>, String> {
+    let data = admin_request(
+        state,
+        instance,
+        reqwest::Method::GET,
+        &format!("/api/token/?p=0&page_size={}&size={}", PAGE_SIZE, PAGE_SIZE),
+        None,
+    )
+    .await?;
+    Ok(list_items(data).iter().map(parse_token).collect())
+}
+
+// ============================================================================
+// Relay Instance Commands
+// ============================================================================
+
+/// List saved relay instances
+#[tauri::command]
+pub async fn list_relay_instances(state: tauri::State<'_, DbState>) -> Result<Vec<RelayInstance>, String> {
+    let db = state.0.lock().await;
+    let records_result: Result<Vec<Value>, _> = db
+        .query("SELECT *, type::string(id) as id FROM relay_instance ORDER BY created_at ASC")
+        .await
+        .map_err(|e| format!("Failed to query relay instances: {}", e))?
+        .take(0);
+
+    match records_result {
+        Ok(records) => Ok(records.into_iter().filter_map(from_db_value_instance).collect()),
+        Err(e) => {
+            log::warn!("Failed to deserialize relay instances: {}", e);
+            Ok(Vec::new())
+        }
+    }
+}
+
+/// Create or update a relay instance
+#[tauri::command]
+pub async fn save_relay_instance(
+    state: tauri::State<'_, DbState>,
+    instance: RelayInstanceInput,
+) -> Result<RelayInstance, String> {
+    if instance.name.trim().is_empty() {
+        return Err("Name is required".to_string());
+    }
+    if !RELAY_KINDS.contains(&instance.kind.as_str()) {
+        return Err(format!("Unsupported relay type: {}", instance.kind));
+    }
+    if !instance.base_url.starts_with("http://") && !instance.base_url.starts_with("https://") {
+        return Err("Base URL must start with http:// or https://".to_string());
+    }
+
+    let now = Local::now().to_rfc3339();
+    let created_at = match &instance.id {
+        Some(id) => get_instance(&state, id)
+            .await
+            .map(|existing| existing.created_at)
+            .unwrap_or_else(|_| now.clone()),
+        None => now.clone(),
+    };
+
+    let saved = RelayInstance {
+        id: instance.id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+        name: instance.name.trim().to_string(),
+        kind: instance.kind,
+        base_url: instance.base_url.trim().trim_end_matches('/').to_string(),
+        access_token: instance.access_token.trim().to_string(),
+        user_id: instance.user_id,
+        created_at,
+        updated_at: now,
+    };
+
+    let mut data = serde_json::to_value(&saved)
+        .map_err(|e| format!("Failed to serialize relay instance: {}", e))?;
+    if let Some(obj) = data.as_object_mut() {
+        obj.remove("id");
+    }
+
+    let db = state.0.lock().await;
+    db.query("UPSERT type::thing('relay_instance', $id) CONTENT $data")
+        .bind(("id", saved.id.clone()))
+        .bind(("data", data))
+        .await
+        .map_err(|e| format!("Failed to save relay instance: {}", e))?;
+
+    Ok(saved)
+}
+
+/// Delete a relay instance (providers registered from it are kept)
+#[tauri::command]
+pub async fn delete_relay_instance(state: tauri::State<'_, DbState>, id: String) -> Result<(), String> {
+    let db = state.0.lock().await;
+    db.query("DELETE type::thing('relay_instance', $id)")
+        .bind(("id", id))
+        .await
+        .map_err(|e| format!("Failed to delete relay instance: {}", e))?;
+    Ok(())
+}
+
+// ============================================================================
+// Relay Admin Commands
+// ============================================================================
+
+/// List channels (upstream providers) configured on the relay
+#[tauri::command]
+pub async fn list_relay_channels(
+    state: tauri::State<'_, DbState>,
+    id: String,
+) -> Result<Vec<Value>, String> {
+    let instance = get_instance(&state, &id).await?;
+    let data = admin_request(
+        &state,
+        &instance,
+        reqwest::Method::GET,
+        &format!("/api/channel/?p=0&page_size={}&size={}", PAGE_SIZE, PAGE_SIZE),
+        None,
+    )
+    .await?;
+
+    // Never pass upstream keys to the frontend
+    Ok(list_items(data)
+        .into_iter()
+        .map(|mut channel| {
+            if let Some(obj) = channel.as_object_mut() {
+                obj.remove("key");
+            }
+            channel
+        })
+        .collect())
+}
+
+/// List API tokens of the relay account
+#[tauri::command]
+pub async fn list_relay_tokens(
+    state: tauri::State<'_, DbState>,
+    id: String,
+) -> Result<Vec<RelayToken>, String> {
+    let instance = get_instance(&state, &id).await?;
+    fetch_tokens(&state, &instance).await
+}
+
+/// Create an API token on the relay
+/// `remain_quota` of None creates an unlimited token
+#[tauri::command]
+pub async fn create_relay_token(
+    state: tauri::State<'_, DbState>,
+    id: String,
+    name: String,
+    remain_quota: Option<i64>,
+) -> Result<RelayToken, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Token name is required".to_string());
+    }
+
+    let instance = get_instance(&state, &id).await?;
+    admin_request(
+        &state,
+        &instance,
+        reqwest::Method::POST,
+        "/api/token/",
+        Some(serde_json::json!({
+            "name": name,
+            "remain_quota": remain_quota.unwrap_or(0),
+            "unlimited_quota": remain_quota.is_none(),
+            "expired_time": -1,
+        })),
+    )
+    .await?;
+
+    // The create endpoint does not return the key, so look the token up again
+    fetch_tokens(&state, &instance)
+        .await?
+        .into_iter()
+        .filter(|t| t.name == name)
+        .max_by_key(|t| t.id)
+        .ok_or_else(|| "Token was created but could not be found".to_string())
+}
+
+/// Get quota of the relay account behind the access token
+#[tauri::command]
+pub async fn get_relay_quota(state: tauri::State<'_, DbState>, id: String) -> Result<RelayQuota, String> {
+    let instance = get_instance(&state, &id).await?;
+    let data = admin_request(&state, &instance, reqwest::Method::GET, "/api/user/self", None).await?;
+
+    let get_i64 = |key: &str| data.get(key).and_then(|v| v.as_i64()).unwrap_or(0);
+    Ok(RelayQuota {
+        username: data
+            .get("username")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string(),
+        quota: get_i64("quota"),
+        used_quota: get_i64("used_quota"),
+        request_count: get_i64("request_count"),
+    })
+}
+
+/// Register a relay token as a toolbox provider, with models from the relay's /v1/models
+#[tauri::command]
+pub async fn register_relay_token_provider(
+    state: tauri::State<'_, DbState>,
+    id: String,
+    token_id: i64,
+    provider_id: Option<String>,
+) -> Result<OpenCodeFavoriteProvider, String> {
+    let instance = get_instance(&state, &id).await?;
+    let token = fetch_tokens(&state, &instance)
+        .await?
+        .into_iter()
+        .find(|t| t.id == token_id)
+        .ok_or_else(|| format!("Token {} not found", token_id))?;
+
+    let base_url = format!("{}/v1", instance.base_url.trim_end_matches('/'));
+    let client = http_client::client(&state).await?;
+    let models: Value = client
+        .get(format!("{}/models", base_url))
+        .bearer_auth(&token.key)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch relay models: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse relay models: {}", e))?;
+
+    let mut model_ids: Vec<String> = models
+        .get("data")
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|m| m.get("id").and_then(|v| v.as_str()).map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    model_ids.sort();
+
+    let provider_id = provider_id
+        .filter(|p| !p.trim().is_empty())
+        .unwrap_or_else(|| format!("{}-{}", instance.name, token.name).to_lowercase().replace(' ', "-"));
+    let provider_config = build_openai_compatible_provider(
+        &format!("{} ({})", instance.name, token.name),
+        &base_url,
+        Some(token.key),
+        &model_ids,
+    );
+
+    upsert_opencode_favorite_provider(state, provider_id, provider_config, None).await
+}
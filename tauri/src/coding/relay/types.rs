@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// Relay (one-api / new-api) Types
+// ============================================================================
+
+/// Self-hosted one-api / new-api instance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelayInstance {
+    pub id: String,
+    pub name: String,
+    /// "one-api" | "new-api"
+    pub kind: String,
+    /// Instance root URL, e.g. https://relay.example.com
+    pub base_url: String,
+    /// System access token generated in the relay's personal settings
+    pub access_token: String,
+    /// User id, required by new-api alongside the access token
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<i64>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Relay instance input from frontend (create/update)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelayInstanceInput {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub name: String,
+    pub kind: String,
+    pub base_url: String,
+    pub access_token: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<i64>,
+}
+
+/// API token issued by a relay
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelayToken {
+    pub id: i64,
+    pub name: String,
+    /// Full key including the `sk-` prefix
+    pub key: String,
+    pub status: i64,
+    pub remain_quota: i64,
+    pub used_quota: i64,
+    pub unlimited_quota: bool,
+    /// Unix timestamp, -1 for never
+    pub expired_time: i64,
+}
+
+/// Quota of the relay account behind the access token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelayQuota {
+    pub username: String,
+    pub quota: i64,
+    pub used_quota: i64,
+    pub request_count: i64,
+}
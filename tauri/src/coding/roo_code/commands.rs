@@ -0,0 +1,317 @@
+use chrono::Local;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde_json::{Map, Value};
+
+use crate::coding::change_history;
+use crate::coding::claude_code::adapter as claude_adapter;
+use crate::coding::claude_code::types::ClaudeCodeProviderContent;
+use crate::coding::vscode;
+use crate::db::DbState;
+use super::types::*;
+use crate::events::{emit_change, ChangeAction};
+
+/// Roo Code VS Code extension id
+const ROO_CODE_EXTENSION_ID: &str = "rooveterinaryinc.roo-cline";
+
+/// VS Code setting that makes Roo Code import a settings file on startup
+const AUTO_IMPORT_SETTING: &str = "roo-cline.autoImportSettingsPath";
+
+// ============================================================================
+// Roo Code Path Helpers
+// ============================================================================
+
+/// Get the toolbox-managed Roo Code settings file (~/.roo/ai-toolbox-settings.json)
+fn get_roo_code_settings_path() -> Result<PathBuf, String> {
+    let home_dir = std::env::var("USERPROFILE")
+        .or_else(|_| std::env::var("HOME"))
+        .map_err(|_| "Failed to get home directory".to_string())?;
+
+    Ok(Path::new(&home_dir).join(".roo").join("ai-toolbox-settings.json"))
+}
+
+/// Editors (user data folder names) that have Roo Code installed
+fn detected_editors() -> Vec<(&'static str, &'static str)> {
+    vscode::VSCODE_EDITORS
+        .iter()
+        .filter(|(_, folder)| {
+            vscode::get_extension_storage_dir(folder, ROO_CODE_EXTENSION_ID)
+                .map(|p| p.exists())
+                .unwrap_or(false)
+        })
+        .copied()
+        .collect()
+}
+
+/// Keys used by Roo Code for a given provider: (base url key, api key key, model id key)
+fn provider_keys(api_provider: &str) -> (Option<&'static str>, Option<&'static str>, &'static str) {
+    match api_provider {
+        "anthropic" => (Some("anthropicBaseUrl"), Some("apiKey"), "apiModelId"),
+        "openai" => (Some("openAiBaseUrl"), Some("openAiApiKey"), "openAiModelId"),
+        "openrouter" => (None, Some("openRouterApiKey"), "openRouterModelId"),
+        "ollama" => (Some("ollamaBaseUrl"), None, "ollamaModelId"),
+        "lmstudio" => (Some("lmStudioBaseUrl"), None, "lmStudioModelId"),
+        "litellm" => (Some("litellmBaseUrl"), Some("litellmApiKey"), "litellmModelId"),
+        _ => (None, None, "apiModelId"),
+    }
+}
+
+fn read_settings_file(path: &Path) -> Result<Value, String> {
+    if !path.exists() {
+        return Ok(serde_json::json!({}));
+    }
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read Roo Code settings: {}", e))?;
+    if content.trim().is_empty() {
+        return Ok(serde_json::json!({}));
+    }
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse Roo Code settings: {}", e))
+}
+
+fn get_str(obj: &Map<String, Value>, key: &str) -> Option<String> {
+    obj.get(key)
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+}
+
+/// Parse `providerProfiles` from a Roo Code settings export
+fn parse_profiles(settings: &Value) -> RooCodeProfilesInfo {
+    let provider_profiles = settings.get("providerProfiles");
+    let current_profile = provider_profiles
+        .and_then(|p| p.get("currentApiConfigName"))
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+
+    let mut profiles = Vec::new();
+    if let Some(configs) = provider_profiles
+        .and_then(|p| p.get("apiConfigs"))
+        .and_then(|v| v.as_object())
+    {
+        for (name, config) in configs {
+            let Some(config) = config.as_object() else {
+                continue;
+            };
+            let api_provider = get_str(config, "apiProvider").unwrap_or_else(|| "anthropic".to_string());
+            let (base_url_key, api_key_key, model_key) = provider_keys(&api_provider);
+            profiles.push(RooCodeProfile {
+                name: name.clone(),
+                base_url: base_url_key.and_then(|k| get_str(config, k)),
+                api_key: api_key_key.and_then(|k| get_str(config, k)),
+                model_id: get_str(config, model_key),
+                api_provider,
+            });
+        }
+    }
+
+    RooCodeProfilesInfo {
+        current_profile,
+        profiles,
+    }
+}
+
+// ============================================================================
+// Roo Code Commands
+// ============================================================================
+
+/// Detect Roo Code extension and editors it is installed in
+#[tauri::command]
+pub fn get_roo_code_status() -> Result<RooCodeStatus, String> {
+    let extension_path = vscode::find_installed_extension(ROO_CODE_EXTENSION_ID);
+    let extension_version = extension_path
+        .as_deref()
+        .and_then(vscode::extension_version);
+
+    Ok(RooCodeStatus {
+        extension_path: extension_path.map(|p| p.to_string_lossy().to_string()),
+        extension_version,
+        editors: detected_editors()
+            .into_iter()
+            .map(|(editor, _)| editor.to_string())
+            .collect(),
+        settings_path: get_roo_code_settings_path()?.to_string_lossy().to_string(),
+    })
+}
+
+/// Read provider profiles from a Roo Code settings export
+/// (defaults to the toolbox-managed settings file)
+#[tauri::command]
+pub fn read_roo_code_profiles(path: Option<String>) -> Result<RooCodeProfilesInfo, String> {
+    let path = match path {
+        Some(p) if !p.trim().is_empty() => PathBuf::from(p),
+        _ => get_roo_code_settings_path()?,
+    };
+    Ok(parse_profiles(&read_settings_file(&path)?))
+}
+
+/// Push a provider profile into Roo Code and make it the current profile
+///
+/// The profile is written to the toolbox-managed settings file, and every detected
+/// editor is pointed at it through `roo-cline.autoImportSettingsPath`, so Roo Code
+/// imports it the next time the editor starts.
+///
+/// With `dry_run` nothing is written and the diffs of the settings files are returned.
+#[tauri::command]
+pub fn apply_roo_code_profile(
+    profile: RooCodeProfile,
+    dry_run: Option<bool>,
+) -> Result<Vec<change_history::ConfigDiff>, String> {
+    change_history::preview_sync(dry_run.unwrap_or(false), || write_profile(profile))
+}
+
+fn write_profile(profile: RooCodeProfile) -> Result<(), String> {
+    if profile.name.trim().is_empty() {
+        return Err("Profile name is required".to_string());
+    }
+
+    let settings_path = get_roo_code_settings_path()?;
+    let mut settings = read_settings_file(&settings_path)?;
+    if !settings.is_object() {
+        settings = serde_json::json!({});
+    }
+
+    let root = settings.as_object_mut().unwrap();
+    let provider_profiles = root
+        .entry("providerProfiles".to_string())
+        .or_insert_with(|| serde_json::json!({}));
+    if !provider_profiles.is_object() {
+        *provider_profiles = serde_json::json!({});
+    }
+    let provider_profiles = provider_profiles.as_object_mut().unwrap();
+
+    let api_configs = provider_profiles
+        .entry("apiConfigs".to_string())
+        .or_insert_with(|| serde_json::json!({}));
+    if !api_configs.is_object() {
+        *api_configs = serde_json::json!({});
+    }
+    let api_configs = api_configs.as_object_mut().unwrap();
+
+    // Keep the existing profile id so Roo Code updates it in place
+    let existing_id = api_configs
+        .get(&profile.name)
+        .and_then(|c| c.get("id"))
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    let (base_url_key, api_key_key, model_key) = provider_keys(&profile.api_provider);
+    let mut config = Map::new();
+    config.insert("id".to_string(), Value::String(existing_id));
+    config.insert("apiProvider".to_string(), Value::String(profile.api_provider.clone()));
+    if let (Some(key), Some(url)) = (base_url_key, profile.base_url.as_ref().filter(|s| !s.is_empty())) {
+        config.insert(key.to_string(), Value::String(url.clone()));
+        if key == "anthropicBaseUrl" {
+            config.insert("anthropicUseAuthToken".to_string(), Value::Bool(true));
+        }
+    }
+    if let (Some(key), Some(api_key)) = (api_key_key, profile.api_key.as_ref().filter(|s| !s.is_empty())) {
+        config.insert(key.to_string(), Value::String(api_key.clone()));
+    }
+    if let Some(model) = profile.model_id.as_ref().filter(|s| !s.is_empty()) {
+        config.insert(model_key.to_string(), Value::String(model.clone()));
+    }
+
+    api_configs.insert(profile.name.clone(), Value::Object(config));
+    provider_profiles.insert(
+        "currentApiConfigName".to_string(),
+        Value::String(profile.name.clone()),
+    );
+
+    if let Some(parent) = settings_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create .roo directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(&settings)
+        .map_err(|e| format!("Failed to serialize Roo Code settings: {}", e))?;
+    change_history::write_config("roo_code", &settings_path, content)
+        .map_err(|e| format!("Failed to write Roo Code settings: {}", e))?;
+
+    let settings_path_str = settings_path.to_string_lossy().to_string();
+    for (editor, folder) in detected_editors() {
+        if let Err(e) = vscode::set_editor_setting(
+            folder,
+            AUTO_IMPORT_SETTING,
+            Value::String(settings_path_str.clone()),
+        ) {
+            log::warn!("Failed to update {} settings for Roo Code: {}", editor, e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Import Roo Code profiles into the toolbox as Claude Code providers
+///
+/// Only anthropic profiles can be represented as Claude Code providers; profiles
+/// whose name already exists are skipped. Returns the number of imported profiles.
+#[tauri::command]
+pub async fn import_roo_code_profiles(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    path: String,
+) -> Result<usize, String> {
+    let info = parse_profiles(&read_settings_file(Path::new(&path))?);
+    let db = state.0.lock().await;
+
+    let existing_result: Result<Vec<Value>, _> = db
+        .query("SELECT name FROM claude_provider")
+        .await
+        .map_err(|e| format!("Failed to query providers: {}", e))?
+        .take(0);
+    let existing_names: Vec<String> = existing_result
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|r| r.get("name").and_then(|v| v.as_str()).map(|s| s.to_string()))
+        .collect();
+
+    let mut imported = 0;
+    for profile in info.profiles {
+        if profile.api_provider != "anthropic" || existing_names.contains(&profile.name) {
+            continue;
+        }
+
+        let mut env = Map::new();
+        if let Some(base_url) = profile.base_url {
+            env.insert("ANTHROPIC_BASE_URL".to_string(), Value::String(base_url));
+        }
+        if let Some(api_key) = profile.api_key {
+            env.insert("ANTHROPIC_AUTH_TOKEN".to_string(), Value::String(api_key));
+        }
+        let mut settings_config = Map::new();
+        settings_config.insert("env".to_string(), Value::Object(env));
+        if let Some(model) = profile.model_id {
+            settings_config.insert("model".to_string(), Value::String(model));
+        }
+
+        let now = Local::now().to_rfc3339();
+        let content = ClaudeCodeProviderContent {
+            name: profile.name,
+            category: "custom".to_string(),
+            settings_config: serde_json::to_string(&settings_config)
+                .map_err(|e| format!("Failed to serialize: {}", e))?,
+            source_provider_id: None,
+            website_url: None,
+            notes: Some("从 Roo Code 导入".to_string()),
+            icon: None,
+            icon_color: None,
+            sort_index: None,
+            is_applied: false,
+            is_disabled: false,
+            is_archived: false,
+            created_at: now.clone(),
+            updated_at: now,
+        };
+
+        db.query("CREATE claude_provider CONTENT $data")
+            .bind(("data", claude_adapter::to_db_value_provider(&content)))
+            .await
+            .map_err(|e| format!("Failed to create provider: {}", e))?;
+        imported += 1;
+    }
+
+    if imported > 0 {
+        emit_change(&app, "roo_code_profile", ChangeAction::Imported, None, "window");
+    }
+    Ok(imported)
+}
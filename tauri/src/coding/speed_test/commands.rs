@@ -0,0 +1,272 @@
+use std::time::Instant;
+
+use chrono::Local;
+use serde_json::Value;
+
+use crate::coding::{db_extract_id, provider_import, vault};
+use crate::db::DbState;
+use crate::error::{AppError, AppResult};
+use super::stream::{self, Protocol, SseParser};
+use super::types::*;
+
+const DEFAULT_RUNS: u32 = 3;
+const MAX_RUNS: u32 = 10;
+/// Per-request timeout; slow relays can take a while to finish 100 numbers
+const REQUEST_TIMEOUT_SECS: u64 = 120;
+const DEFAULT_HISTORY_LIMIT: usize = 100;
+
+/// Everything needed to send the test request to one target
+struct ResolvedTarget {
+    provider_name: String,
+    protocol: Protocol,
+    url: String,
+    api_key: String,
+    model: String,
+}
+
+async fn resolve_target(state: &DbState, target: &SpeedTestTarget) -> AppResult<ResolvedTarget> {
+    let provider = {
+        let db = state.0.lock().await;
+        provider_import::share::export_provider(&db, &target.tool, &target.provider_id).await?
+    };
+    let protocol = Protocol::for_provider(&target.tool, provider.npm.as_deref())
+        .ok_or_else(|| AppError::validation("SPEED_TEST_UNSUPPORTED_API", &[("name", provider.name.as_str())]))?;
+    let model = target
+        .model
+        .clone()
+        .filter(|m| !m.trim().is_empty())
+        .or(provider.model)
+        .ok_or_else(|| AppError::validation("SPEED_TEST_MODEL_REQUIRED", &[("name", provider.name.as_str())]))?;
+
+    let mut api_key = Value::String(provider.api_key);
+    vault::resolve_references(&mut api_key)?;
+
+    Ok(ResolvedTarget {
+        url: stream::endpoint_url(protocol, &provider.base_url),
+        api_key: api_key.as_str().unwrap_or_default().to_string(),
+        provider_name: provider.name,
+        protocol,
+        model,
+    })
+}
+
+fn failed_run(error: impl ToString) -> SpeedTestRun {
+    SpeedTestRun {
+        success: false,
+        ttft_ms: None,
+        total_ms: None,
+        output_tokens: None,
+        tokens_per_sec: None,
+        error: Some(error.to_string()),
+    }
+}
+
+/// Send one streaming request and time it
+async fn run_once(client: &reqwest::Client, target: &ResolvedTarget) -> SpeedTestRun {
+    let mut request = client
+        .post(&target.url)
+        .header("Accept", "text/event-stream")
+        .json(&stream::request_body(target.protocol, &target.model));
+    request = match target.protocol {
+        Protocol::Anthropic => request
+            .header("x-api-key", &target.api_key)
+            .header("anthropic-version", "2023-06-01")
+            .bearer_auth(&target.api_key),
+        _ => request.bearer_auth(&target.api_key),
+    };
+
+    let start = Instant::now();
+    let mut response = match request.send().await {
+        Ok(response) => response,
+        Err(e) => return failed_run(e),
+    };
+    if !response.status().is_success() {
+        let status = response.status();
+        let body: String = response.text().await.unwrap_or_default().chars().take(200).collect();
+        return failed_run(format!("HTTP {}: {}", status, body));
+    }
+
+    let mut parser = SseParser::default();
+    let mut ttft_ms: Option<u64> = None;
+    let mut text = String::new();
+    let mut output_tokens: Option<u64> = None;
+    loop {
+        let chunk = match response.chunk().await {
+            Ok(Some(chunk)) => chunk,
+            Ok(None) => break,
+            Err(e) => return failed_run(e),
+        };
+        for payload in parser.push(&chunk) {
+            let Ok(event) = serde_json::from_str::<Value>(&payload) else {
+                continue;
+            };
+            let event = stream::parse_event(target.protocol, &event);
+            if let Some(error) = event.error {
+                return failed_run(error);
+            }
+            if let Some(delta) = event.text {
+                if ttft_ms.is_none() {
+                    ttft_ms = Some(start.elapsed().as_millis() as u64);
+                }
+                text.push_str(&delta);
+            }
+            if event.output_tokens.is_some() {
+                output_tokens = event.output_tokens;
+            }
+        }
+    }
+    let total_ms = start.elapsed().as_millis() as u64;
+
+    let Some(ttft) = ttft_ms else {
+        return failed_run("No text in the response stream");
+    };
+    let output_tokens = output_tokens.unwrap_or_else(|| stream::estimate_tokens(&text));
+    let generation_ms = total_ms.saturating_sub(ttft);
+    SpeedTestRun {
+        success: true,
+        ttft_ms: Some(ttft),
+        total_ms: Some(total_ms),
+        output_tokens: Some(output_tokens),
+        tokens_per_sec: (generation_ms > 0).then(|| output_tokens as f64 * 1000.0 / generation_ms as f64),
+        error: None,
+    }
+}
+
+fn average(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let values: Vec<f64> = values.collect();
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.iter().sum::<f64>() / values.len() as f64)
+    }
+}
+
+fn summarize(target: &SpeedTestTarget, resolved: &ResolvedTarget, runs: Vec<SpeedTestRun>) -> SpeedTestResult {
+    let successful: Vec<&SpeedTestRun> = runs.iter().filter(|r| r.success).collect();
+    SpeedTestResult {
+        id: uuid::Uuid::new_v4().to_string(),
+        tool: target.tool.clone(),
+        provider_id: target.provider_id.clone(),
+        provider_name: resolved.provider_name.clone(),
+        model: resolved.model.clone(),
+        failure_rate: if runs.is_empty() {
+            0.0
+        } else {
+            (runs.len() - successful.len()) as f64 / runs.len() as f64
+        },
+        avg_ttft_ms: average(successful.iter().filter_map(|r| r.ttft_ms).map(|v| v as f64)),
+        avg_tokens_per_sec: average(successful.iter().filter_map(|r| r.tokens_per_sec)),
+        created_at: Local::now().to_rfc3339(),
+        runs,
+    }
+}
+
+fn from_db_value(mut value: Value) -> Option<SpeedTestResult> {
+    let id = db_extract_id(&value);
+    value.as_object_mut()?.insert("id".to_string(), Value::String(id));
+    serde_json::from_value(value).ok()
+}
+
+// ============================================================================
+// Speed Test Commands
+// ============================================================================
+
+/// Run the speed test against each target `runs` times (default 3) and store the results
+#[tauri::command]
+pub async fn run_speed_test(
+    state: tauri::State<'_, DbState>,
+    targets: Vec<SpeedTestTarget>,
+    runs: Option<u32>,
+) -> AppResult<Vec<SpeedTestResult>> {
+    let runs = runs.unwrap_or(DEFAULT_RUNS).clamp(1, MAX_RUNS);
+    let client = crate::http_client::client_with_timeout(&state, REQUEST_TIMEOUT_SECS)
+        .await
+        .map_err(AppError::http)?;
+
+    let mut results = Vec::new();
+    for target in &targets {
+        let resolved = resolve_target(&state, target).await?;
+        let mut target_runs = Vec::new();
+        for _ in 0..runs {
+            target_runs.push(run_once(&client, &resolved).await);
+        }
+        let result = summarize(target, &resolved, target_runs);
+
+        let mut data = serde_json::to_value(&result).map_err(AppError::invalid)?;
+        if let Some(map) = data.as_object_mut() {
+            map.remove("id");
+        }
+        let db = state.0.lock().await;
+        db.query("CREATE type::thing('speed_test_result', $id) CONTENT $data")
+            .bind(("id", result.id.clone()))
+            .bind(("data", data))
+            .await?;
+        results.push(result);
+    }
+    Ok(results)
+}
+
+/// Stored results, newest first, optionally for one provider
+#[tauri::command]
+pub async fn list_speed_test_history(
+    state: tauri::State<'_, DbState>,
+    provider_id: Option<String>,
+    limit: Option<usize>,
+) -> AppResult<Vec<SpeedTestResult>> {
+    let db = state.0.lock().await;
+    let query = match provider_id {
+        Some(_) => "SELECT *, type::string(id) as id FROM speed_test_result WHERE provider_id = $provider_id ORDER BY created_at DESC LIMIT $limit",
+        None => "SELECT *, type::string(id) as id FROM speed_test_result ORDER BY created_at DESC LIMIT $limit",
+    };
+    let records: Vec<Value> = db
+        .query(query)
+        .bind(("provider_id", provider_id.unwrap_or_default()))
+        .bind(("limit", limit.unwrap_or(DEFAULT_HISTORY_LIMIT) as i64))
+        .await?
+        .take(0)?;
+    Ok(records.into_iter().filter_map(from_db_value).collect())
+}
+
+#[tauri::command]
+pub async fn clear_speed_test_history(state: tauri::State<'_, DbState>) -> AppResult<()> {
+    let db = state.0.lock().await;
+    db.query("DELETE speed_test_result").await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(success: bool, ttft_ms: u64, tokens_per_sec: f64) -> SpeedTestRun {
+        SpeedTestRun {
+            success,
+            ttft_ms: success.then_some(ttft_ms),
+            total_ms: None,
+            output_tokens: None,
+            tokens_per_sec: success.then_some(tokens_per_sec),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn summarize_averages_successful_runs() {
+        let target = SpeedTestTarget {
+            tool: "claude".to_string(),
+            provider_id: "p".to_string(),
+            model: None,
+        };
+        let resolved = ResolvedTarget {
+            provider_name: "Relay".to_string(),
+            protocol: Protocol::Anthropic,
+            url: String::new(),
+            api_key: String::new(),
+            model: "claude-sonnet-4".to_string(),
+        };
+        let runs = vec![run(true, 400, 50.0), run(true, 600, 70.0), run(false, 0, 0.0), run(false, 0, 0.0)];
+        let result = summarize(&target, &resolved, runs);
+        assert_eq!(result.failure_rate, 0.5);
+        assert_eq!(result.avg_ttft_ms, Some(500.0));
+        assert_eq!(result.avg_tokens_per_sec, Some(60.0));
+    }
+}
@@ -0,0 +1,13 @@
+//! Speed Test
+//!
+//! Sends the same streaming completion to selected providers / models
+//! several times and measures time to first token (first text delta, not
+//! first byte), output tokens per second and failure rate. Each test is
+//! stored in `speed_test_result` for trend charts.
+
+pub mod commands;
+pub mod stream;
+pub mod types;
+
+pub use commands::*;
+pub use types::*;
@@ -0,0 +1,183 @@
+//! Request building and SSE parsing for the supported streaming APIs
+
+use serde_json::{json, Value};
+
+/// Fixed prompt so results are comparable across providers and over time
+pub const SPEED_TEST_PROMPT: &str = "Count from 1 to 100, separated by spaces. Output only the numbers.";
+pub const SPEED_TEST_MAX_TOKENS: u64 = 512;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    /// Anthropic Messages API
+    Anthropic,
+    /// OpenAI Chat Completions
+    OpenAiChat,
+    /// OpenAI Responses API
+    OpenAiResponses,
+}
+
+impl Protocol {
+    /// Protocol of a provider from its tool and SDK / wire API hint
+    pub fn for_provider(tool: &str, npm: Option<&str>) -> Option<Self> {
+        if tool == "claude" {
+            return Some(Self::Anthropic);
+        }
+        match npm {
+            Some("@ai-sdk/anthropic") => Some(Self::Anthropic),
+            Some("@ai-sdk/openai") | Some("responses") => Some(Self::OpenAiResponses),
+            Some("@ai-sdk/google") => None,
+            _ => Some(Self::OpenAiChat),
+        }
+    }
+}
+
+/// Whether the URL already ends with an API version, e.g. "/v1" or "/v4"
+fn has_version_suffix(base_url: &str) -> bool {
+    base_url
+        .rsplit('/')
+        .next()
+        .and_then(|segment| segment.strip_prefix('v'))
+        .map(|rest| !rest.is_empty() && rest.chars().all(|c| c.is_ascii_digit()))
+        .unwrap_or(false)
+}
+
+pub fn endpoint_url(protocol: Protocol, base_url: &str) -> String {
+    let base_url = base_url.trim_end_matches('/');
+    let path = match protocol {
+        Protocol::Anthropic => "messages",
+        Protocol::OpenAiChat => "chat/completions",
+        Protocol::OpenAiResponses => "responses",
+    };
+    if has_version_suffix(base_url) {
+        format!("{}/{}", base_url, path)
+    } else {
+        format!("{}/v1/{}", base_url, path)
+    }
+}
+
+pub fn request_body(protocol: Protocol, model: &str) -> Value {
+    match protocol {
+        Protocol::Anthropic => json!({
+            "model": model,
+            "max_tokens": SPEED_TEST_MAX_TOKENS,
+            "stream": true,
+            "messages": [{ "role": "user", "content": SPEED_TEST_PROMPT }],
+        }),
+        Protocol::OpenAiChat => json!({
+            "model": model,
+            "max_tokens": SPEED_TEST_MAX_TOKENS,
+            "stream": true,
+            "stream_options": { "include_usage": true },
+            "messages": [{ "role": "user", "content": SPEED_TEST_PROMPT }],
+        }),
+        Protocol::OpenAiResponses => json!({
+            "model": model,
+            "max_output_tokens": SPEED_TEST_MAX_TOKENS,
+            "stream": true,
+            "input": SPEED_TEST_PROMPT,
+        }),
+    }
+}
+
+/// Splits a byte stream into SSE `data:` payloads
+#[derive(Default)]
+pub struct SseParser {
+    buffer: String,
+}
+
+impl SseParser {
+    /// Feed a chunk; returns the complete `data:` payloads it finished
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.buffer.push_str(&String::from_utf8_lossy(chunk));
+        let mut payloads = Vec::new();
+        while let Some(pos) = self.buffer.find('\n') {
+            let line: String = self.buffer.drain(..=pos).collect();
+            let line = line.trim_end_matches(['\r', '\n']);
+            if let Some(data) = line.strip_prefix("data:") {
+                let data = data.trim();
+                if !data.is_empty() && data != "[DONE]" {
+                    payloads.push(data.to_string());
+                }
+            }
+        }
+        payloads
+    }
+}
+
+/// What one stream event contributes: text delta and/or final token usage
+#[derive(Debug, Default, PartialEq)]
+pub struct StreamEvent {
+    pub text: Option<String>,
+    pub output_tokens: Option<u64>,
+    /// Error reported inside the stream
+    pub error: Option<String>,
+}
+
+pub fn parse_event(protocol: Protocol, event: &Value) -> StreamEvent {
+    let str_at = |pointer: &str| event.pointer(pointer).and_then(|v| v.as_str()).map(String::from);
+    let u64_at = |pointer: &str| event.pointer(pointer).and_then(|v| v.as_u64());
+
+    let error = event
+        .get("error")
+        .map(|e| e.get("message").and_then(|m| m.as_str()).map(String::from).unwrap_or_else(|| e.to_string()));
+    let (text, output_tokens) = match protocol {
+        Protocol::Anthropic => match event.get("type").and_then(|t| t.as_str()) {
+            Some("content_block_delta") => (str_at("/delta/text"), None),
+            Some("message_delta") => (None, u64_at("/usage/output_tokens")),
+            _ => (None, None),
+        },
+        Protocol::OpenAiChat => (
+            str_at("/choices/0/delta/content"),
+            u64_at("/usage/completion_tokens"),
+        ),
+        Protocol::OpenAiResponses => match event.get("type").and_then(|t| t.as_str()) {
+            Some("response.output_text.delta") => (str_at("/delta"), None),
+            Some("response.completed") => (None, u64_at("/response/usage/output_tokens")),
+            _ => (None, None),
+        },
+    };
+
+    StreamEvent {
+        text: text.filter(|t| !t.is_empty()),
+        output_tokens,
+        error,
+    }
+}
+
+/// Rough token count when the stream reports no usage (~4 characters per token)
+pub fn estimate_tokens(text: &str) -> u64 {
+    (text.chars().count() as u64).div_ceil(4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn endpoint_url_adds_version_when_missing() {
+        assert_eq!(
+            endpoint_url(Protocol::Anthropic, "https://relay.example.com/"),
+            "https://relay.example.com/v1/messages"
+        );
+        assert_eq!(
+            endpoint_url(Protocol::OpenAiChat, "https://open.bigmodel.cn/api/paas/v4"),
+            "https://open.bigmodel.cn/api/paas/v4/chat/completions"
+        );
+    }
+
+    #[test]
+    fn sse_parser_handles_split_chunks() {
+        let mut parser = SseParser::default();
+        assert!(parser.push(b"event: x\ndata: {\"a\"").is_empty());
+        assert_eq!(parser.push(b":1}\r\n\ndata: [DONE]\n"), vec!["{\"a\":1}".to_string()]);
+    }
+
+    #[test]
+    fn parse_event_reads_text_and_usage() {
+        let delta = json!({ "type": "content_block_delta", "delta": { "type": "text_delta", "text": "1 2" } });
+        assert_eq!(parse_event(Protocol::Anthropic, &delta).text.as_deref(), Some("1 2"));
+
+        let usage = json!({ "choices": [], "usage": { "completion_tokens": 42 } });
+        assert_eq!(parse_event(Protocol::OpenAiChat, &usage).output_tokens, Some(42));
+    }
+}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+/// Provider / model to test
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeedTestTarget {
+    /// "claude", "codex" or "opencode"
+    pub tool: String,
+    /// Provider id in its tool (claude/codex provider id, OpenCode provider_id)
+    pub provider_id: String,
+    /// Defaults to the provider's model
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+}
+
+/// One request of a test
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeedTestRun {
+    pub success: bool,
+    /// Time to the first text delta
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ttft_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_ms: Option<u64>,
+    /// From the stream's usage, estimated from the text when missing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_tokens: Option<u64>,
+    /// Output tokens per second after the first token
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tokens_per_sec: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Summary of all runs against one target, as stored in the history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeedTestResult {
+    pub id: String,
+    pub tool: String,
+    pub provider_id: String,
+    pub provider_name: String,
+    pub model: String,
+    pub runs: Vec<SpeedTestRun>,
+    /// Failed runs / all runs (0.0 - 1.0)
+    pub failure_rate: f64,
+    /// Averages over successful runs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_ttft_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_tokens_per_sec: Option<f64>,
+    pub created_at: String,
+}
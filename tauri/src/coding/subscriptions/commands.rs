@@ -0,0 +1,118 @@
+use chrono::Local;
+use serde_json::Value;
+
+use crate::coding::db_extract_id;
+use crate::db::DbState;
+use crate::error::{AppError, AppResult};
+use super::renewal;
+use super::types::*;
+
+type Db = surrealdb::Surreal<surrealdb::engine::local::Db>;
+
+const SUPPORTED_TOOLS: [&str; 3] = ["claude", "codex", "opencode"];
+/// Look-ahead window of the summary's upcoming renewals
+const DEFAULT_UPCOMING_DAYS: i64 = 30;
+
+fn from_db_value(mut value: Value) -> Option<Subscription> {
+    let provider_id = db_extract_id(&value);
+    let map = value.as_object_mut()?;
+    map.remove("id");
+    map.insert("provider_id".to_string(), Value::String(provider_id));
+    serde_json::from_value(value).ok()
+}
+
+pub async fn list_all(db: &Db) -> AppResult<Vec<Subscription>> {
+    let records: Vec<Value> = db
+        .query("SELECT *, type::string(id) as id FROM subscription")
+        .await?
+        .take(0)?;
+    Ok(records.into_iter().filter_map(from_db_value).collect())
+}
+
+pub async fn save_record(db: &Db, subscription: &Subscription) -> AppResult<()> {
+    let mut data = serde_json::to_value(subscription).map_err(AppError::invalid)?;
+    if let Some(map) = data.as_object_mut() {
+        map.remove("provider_id");
+    }
+    db.query("UPSERT type::thing('subscription', $id) CONTENT $data")
+        .bind(("id", subscription.provider_id.clone()))
+        .bind(("data", data))
+        .await?;
+    Ok(())
+}
+
+// ============================================================================
+// Subscription Commands
+// ============================================================================
+
+#[tauri::command]
+pub async fn list_subscriptions(state: tauri::State<'_, DbState>) -> AppResult<Vec<Subscription>> {
+    let db = state.0.lock().await;
+    let mut subscriptions = list_all(&db).await?;
+    subscriptions.sort_by(|a, b| a.renewal_date.cmp(&b.renewal_date));
+    Ok(subscriptions)
+}
+
+/// Create or replace the subscription of a provider
+#[tauri::command]
+pub async fn save_subscription(
+    state: tauri::State<'_, DbState>,
+    subscription: Subscription,
+) -> AppResult<Subscription> {
+    let mut subscription = subscription;
+    subscription.provider_id = subscription.provider_id.trim().to_string();
+    subscription.tool = subscription.tool.trim().to_lowercase();
+    subscription.plan_name = subscription.plan_name.trim().to_string();
+    subscription.currency = subscription
+        .currency
+        .map(|c| c.trim().to_uppercase())
+        .filter(|c| !c.is_empty());
+    subscription.renewal_date = subscription
+        .renewal_date
+        .map(|d| d.trim().to_string())
+        .filter(|d| !d.is_empty());
+
+    if !SUPPORTED_TOOLS.contains(&subscription.tool.as_str()) {
+        return Err(AppError::validation("UNSUPPORTED_TOOL", &[("tool", subscription.tool.as_str())]));
+    }
+    if subscription.provider_id.is_empty() || subscription.plan_name.is_empty() {
+        return Err(AppError::validation("SUBSCRIPTION_INCOMPLETE", &[]));
+    }
+    if let Some(date) = subscription.renewal_date.as_deref() {
+        if renewal::parse_date(date).is_none() {
+            return Err(AppError::validation("INVALID_RENEWAL_DATE", &[("date", date)]));
+        }
+    }
+    // A new date gets its own reminder
+    if subscription.reminded_for != subscription.renewal_date {
+        subscription.reminded_for = None;
+    }
+
+    let db = state.0.lock().await;
+    save_record(&db, &subscription).await?;
+    Ok(subscription)
+}
+
+#[tauri::command]
+pub async fn delete_subscription(state: tauri::State<'_, DbState>, provider_id: String) -> AppResult<()> {
+    let db = state.0.lock().await;
+    db.query("DELETE type::thing('subscription', $id)")
+        .bind(("id", provider_id))
+        .await?;
+    Ok(())
+}
+
+/// Monthly spend per currency and renewals in the next `days` days (default 30)
+#[tauri::command]
+pub async fn get_subscription_summary(
+    state: tauri::State<'_, DbState>,
+    days: Option<i64>,
+) -> AppResult<SubscriptionSummary> {
+    let db = state.0.lock().await;
+    let subscriptions = list_all(&db).await?;
+    Ok(renewal::summarize(
+        &subscriptions,
+        Local::now().date_naive(),
+        days.unwrap_or(DEFAULT_UPCOMING_DAYS),
+    ))
+}
@@ -0,0 +1,13 @@
+//! API Plan Subscriptions
+//!
+//! Subscription metadata per provider (plan, monthly cost, renewal date,
+//! quota) so spend across many relays can be tracked in one place. A
+//! background check notifies `remind_days_before` days ahead of a renewal
+//! and rolls monthly / yearly renewal dates forward once they have passed.
+
+pub mod commands;
+pub mod renewal;
+pub mod types;
+
+pub use commands::*;
+pub use types::*;
@@ -0,0 +1,207 @@
+//! Renewal dates, reminders and the spend summary
+
+use std::collections::BTreeMap;
+
+use chrono::{Local, Months, NaiveDate};
+use tauri::{AppHandle, Manager};
+
+use crate::db::DbState;
+use crate::notifications::{self, NotificationCategory};
+use super::commands::{list_all, save_record};
+use super::types::*;
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+/// Currency assumed when a plan has none
+const DEFAULT_CURRENCY: &str = "USD";
+
+pub fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value.trim(), DATE_FORMAT).ok()
+}
+
+pub fn format_date(date: NaiveDate) -> String {
+    date.format(DATE_FORMAT).to_string()
+}
+
+/// Move a past renewal date forward by whole cycles until it is today or later
+pub fn roll_forward(date: NaiveDate, cycle: BillingCycle, today: NaiveDate) -> NaiveDate {
+    let step = match cycle {
+        BillingCycle::Monthly => Months::new(1),
+        BillingCycle::Yearly => Months::new(12),
+        BillingCycle::None => return date,
+    };
+    let mut next = date;
+    while next < today {
+        match next.checked_add_months(step) {
+            Some(date) => next = date,
+            None => break,
+        }
+    }
+    next
+}
+
+/// Days until the renewal, if the plan has a valid renewal date
+pub fn days_left(subscription: &Subscription, today: NaiveDate) -> Option<i64> {
+    let date = parse_date(subscription.renewal_date.as_deref()?)?;
+    Some((date - today).num_days())
+}
+
+/// Days left when a reminder for the current renewal is due and not yet sent
+pub fn reminder_due(subscription: &Subscription, today: NaiveDate) -> Option<i64> {
+    let days = days_left(subscription, today)?;
+    let already_sent = subscription.reminded_for.is_some()
+        && subscription.reminded_for == subscription.renewal_date;
+    (0..=subscription.remind_days_before as i64)
+        .contains(&days)
+        .then_some(days)
+        .filter(|_| !already_sent)
+}
+
+pub fn summarize(subscriptions: &[Subscription], today: NaiveDate, days: i64) -> SubscriptionSummary {
+    let mut totals: BTreeMap<String, f64> = BTreeMap::new();
+    for subscription in subscriptions {
+        if let Some(cost) = subscription.monthly_cost {
+            let currency = subscription
+                .currency
+                .clone()
+                .filter(|c| !c.trim().is_empty())
+                .unwrap_or_else(|| DEFAULT_CURRENCY.to_string());
+            *totals.entry(currency).or_default() += cost;
+        }
+    }
+
+    let mut upcoming: Vec<UpcomingRenewal> = subscriptions
+        .iter()
+        .filter_map(|subscription| {
+            let days_left = days_left(subscription, today).filter(|d| (0..=days).contains(d))?;
+            Some(UpcomingRenewal {
+                provider_id: subscription.provider_id.clone(),
+                plan_name: subscription.plan_name.clone(),
+                renewal_date: subscription.renewal_date.clone().unwrap_or_default(),
+                days_left,
+                monthly_cost: subscription.monthly_cost,
+                currency: subscription.currency.clone(),
+            })
+        })
+        .collect();
+    upcoming.sort_by_key(|renewal| renewal.days_left);
+
+    SubscriptionSummary {
+        count: subscriptions.len(),
+        monthly_totals: totals
+            .into_iter()
+            .map(|(currency, monthly_cost)| CurrencyTotal { currency, monthly_cost })
+            .collect(),
+        upcoming,
+    }
+}
+
+/// Roll passed renewal dates forward and send due reminders (run periodically from setup)
+pub async fn check_renewals(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<DbState>();
+    let today = Local::now().date_naive();
+    let subscriptions = {
+        let db = state.0.lock().await;
+        list_all(&db).await.map_err(|e| e.to_string())?
+    };
+
+    for mut subscription in subscriptions {
+        let mut changed = false;
+        if let Some(date) = subscription.renewal_date.as_deref().and_then(parse_date) {
+            let next = roll_forward(date, subscription.billing_cycle, today);
+            if next != date {
+                subscription.renewal_date = Some(format_date(next));
+                changed = true;
+            }
+        }
+
+        if let Some(days) = reminder_due(&subscription, today) {
+            let when = match days {
+                0 => "今天".to_string(),
+                1 => "明天".to_string(),
+                n => format!("{} 天后", n),
+            };
+            let cost = match (subscription.monthly_cost, subscription.currency.as_deref()) {
+                (Some(cost), Some(currency)) => format!("（{:.2} {}/月）", cost, currency),
+                (Some(cost), None) => format!("（{:.2}/月）", cost),
+                _ => String::new(),
+            };
+            notifications::notify(
+                app,
+                NotificationCategory::Renewal,
+                "订阅即将续费",
+                format!("{} 将于{}续费{}", subscription.plan_name, when, cost),
+            );
+            subscription.reminded_for = subscription.renewal_date.clone();
+            changed = true;
+        }
+
+        if changed {
+            let db = state.0.lock().await;
+            save_record(&db, &subscription).await.map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        parse_date(value).unwrap()
+    }
+
+    fn subscription(renewal_date: &str, cost: Option<f64>, currency: Option<&str>) -> Subscription {
+        Subscription {
+            provider_id: renewal_date.to_string(),
+            tool: "claude".to_string(),
+            plan_name: "Pro".to_string(),
+            monthly_cost: cost,
+            currency: currency.map(String::from),
+            renewal_date: Some(renewal_date.to_string()),
+            billing_cycle: BillingCycle::Monthly,
+            quota: None,
+            remind_days_before: 3,
+            note: None,
+            reminded_for: None,
+        }
+    }
+
+    #[test]
+    fn roll_forward_keeps_day_of_month() {
+        let today = date("2026-03-20");
+        assert_eq!(roll_forward(date("2026-01-15"), BillingCycle::Monthly, today), date("2026-04-15"));
+        assert_eq!(roll_forward(date("2025-02-01"), BillingCycle::Yearly, today), date("2027-02-01"));
+        assert_eq!(roll_forward(date("2026-01-15"), BillingCycle::None, today), date("2026-01-15"));
+    }
+
+    #[test]
+    fn reminder_is_due_once_inside_window() {
+        let today = date("2026-03-20");
+        let mut plan = subscription("2026-03-22", None, None);
+        assert_eq!(reminder_due(&plan, today), Some(2));
+        plan.reminded_for = plan.renewal_date.clone();
+        assert_eq!(reminder_due(&plan, today), None);
+        assert_eq!(reminder_due(&subscription("2026-03-30", None, None), today), None);
+    }
+
+    #[test]
+    fn summary_totals_per_currency() {
+        let plans = vec![
+            subscription("2026-03-25", Some(20.0), Some("USD")),
+            subscription("2026-04-30", Some(10.0), None),
+            subscription("2026-03-21", Some(99.0), Some("CNY")),
+        ];
+        let summary = summarize(&plans, date("2026-03-20"), 30);
+        assert_eq!(summary.count, 3);
+        assert_eq!(
+            summary.monthly_totals,
+            vec![
+                CurrencyTotal { currency: "CNY".to_string(), monthly_cost: 99.0 },
+                CurrencyTotal { currency: "USD".to_string(), monthly_cost: 30.0 },
+            ]
+        );
+        let upcoming: Vec<i64> = summary.upcoming.iter().map(|u| u.days_left).collect();
+        assert_eq!(upcoming, vec![1, 5]);
+    }
+}
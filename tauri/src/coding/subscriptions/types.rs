@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+
+/// How the renewal date moves on once it has passed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BillingCycle {
+    #[default]
+    Monthly,
+    Yearly,
+    /// One-off plan; the date is left as is
+    None,
+}
+
+/// Subscription of one provider
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Subscription {
+    /// Provider id in its tool (claude/codex provider id, OpenCode provider_id)
+    pub provider_id: String,
+    /// "claude", "codex" or "opencode"
+    pub tool: String,
+    pub plan_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monthly_cost: Option<f64>,
+    /// e.g. "USD", "CNY"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    /// Next renewal, "YYYY-MM-DD"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub renewal_date: Option<String>,
+    #[serde(default)]
+    pub billing_cycle: BillingCycle,
+    /// Free-form quota, e.g. "5h window / 800 prompts"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quota: Option<String>,
+    #[serde(default = "default_remind_days")]
+    pub remind_days_before: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Renewal date the last reminder was sent for, so each renewal is reminded once
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reminded_for: Option<String>,
+}
+
+fn default_remind_days() -> u32 {
+    3
+}
+
+/// Monthly spend in one currency
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CurrencyTotal {
+    pub currency: String,
+    pub monthly_cost: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpcomingRenewal {
+    pub provider_id: String,
+    pub plan_name: String,
+    pub renewal_date: String,
+    pub days_left: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monthly_cost: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+}
+
+/// Result of `get_subscription_summary`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubscriptionSummary {
+    pub count: usize,
+    /// Per currency; plans without a currency count as "USD"
+    pub monthly_totals: Vec<CurrencyTotal>,
+    /// Renewals in the next `days` days, soonest first
+    pub upcoming: Vec<UpcomingRenewal>,
+}
@@ -0,0 +1,83 @@
+use chrono::{Duration, Local};
+use serde_json::Value;
+
+use crate::db::DbState;
+use crate::error::{AppError, AppResult};
+use super::stats;
+use super::types::*;
+
+type Db = surrealdb::Surreal<surrealdb::engine::local::Db>;
+
+/// Switch history older than this is dropped
+const RETENTION_DAYS: i64 = 400;
+const DEFAULT_DAYS: u32 = 30;
+const MAX_DAYS: u32 = 365;
+
+async fn try_record(db: &Db, table: &str, provider_id: &str) -> AppResult<()> {
+    let tool = table.trim_end_matches("_provider");
+
+    // Re-applying the applied provider (edit, toggle) is not a switch
+    let last: Vec<Value> = db
+        .query("SELECT provider_id, applied_at FROM provider_switch WHERE tool = $tool ORDER BY applied_at DESC LIMIT 1")
+        .bind(("tool", tool.to_string()))
+        .await?
+        .take(0)?;
+    if last
+        .first()
+        .and_then(|record| record.get("provider_id"))
+        .and_then(|id| id.as_str())
+        == Some(provider_id)
+    {
+        return Ok(());
+    }
+
+    let names: Vec<String> = db
+        .query("SELECT VALUE name FROM type::thing($table, $id)")
+        .bind(("table", table.to_string()))
+        .bind(("id", provider_id.to_string()))
+        .await?
+        .take(0)?;
+    let event = SwitchEvent {
+        tool: tool.to_string(),
+        provider_id: provider_id.to_string(),
+        provider_name: names.into_iter().next().unwrap_or_else(|| provider_id.to_string()),
+        applied_at: Local::now().to_rfc3339(),
+    };
+    let cutoff = (Local::now() - Duration::days(RETENTION_DAYS)).to_rfc3339();
+    db.query("CREATE provider_switch CONTENT $data; DELETE provider_switch WHERE applied_at < $cutoff")
+        .bind(("data", serde_json::to_value(&event).map_err(AppError::invalid)?))
+        .bind(("cutoff", cutoff))
+        .await?;
+    Ok(())
+}
+
+/// Record that the provider `provider_id` of `table` (e.g. "claude_provider")
+/// was applied; failures are only logged so applying is never blocked
+pub async fn record_switch(db: &Db, table: &str, provider_id: &str) {
+    if let Err(e) = try_record(db, table, provider_id).await {
+        log::warn!("Failed to record provider switch: {}", e);
+    }
+}
+
+// ============================================================================
+// Switch Analytics Commands
+// ============================================================================
+
+/// Switch frequency and time spent on each provider over the last `days` (default 30)
+#[tauri::command]
+pub async fn get_switch_analytics(
+    state: tauri::State<'_, DbState>,
+    days: Option<u32>,
+) -> AppResult<SwitchAnalytics> {
+    let days = days.unwrap_or(DEFAULT_DAYS).clamp(1, MAX_DAYS);
+    let db = state.0.lock().await;
+    let records: Vec<Value> = db
+        .query("SELECT tool, provider_id, provider_name, applied_at FROM provider_switch")
+        .await?
+        .take(0)?;
+    let events: Vec<SwitchEvent> = records
+        .into_iter()
+        .filter_map(|record| serde_json::from_value(record).ok())
+        .collect();
+    Ok(stats::analyze(&events, Local::now().into(), days))
+}
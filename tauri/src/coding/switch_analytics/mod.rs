@@ -0,0 +1,13 @@
+//! Provider Switch Analytics
+//!
+//! Every provider apply is stored in `provider_switch` (tool, provider,
+//! time). `get_switch_analytics` turns that history into switch frequency
+//! and the time each provider stayed active, per tool, so it is easy to see
+//! which relays are actually used before renewing them.
+
+pub mod commands;
+pub mod stats;
+pub mod types;
+
+pub use commands::*;
+pub use types::*;
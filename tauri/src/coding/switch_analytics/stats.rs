@@ -0,0 +1,151 @@
+//! Switch history → per-tool usage statistics
+//!
+//! A provider is active from its apply until the next apply of the same tool
+//! (or now). Only the part of that time inside the analyzed period counts, so
+//! a provider applied before the period still gets the time it stayed active.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Duration, FixedOffset};
+
+use super::types::*;
+
+/// Statistics of the `days` before `now`
+pub fn analyze(events: &[SwitchEvent], now: DateTime<FixedOffset>, days: u32) -> SwitchAnalytics {
+    let since = now - Duration::days(days as i64);
+
+    let mut by_tool: BTreeMap<&str, Vec<(DateTime<FixedOffset>, &SwitchEvent)>> = BTreeMap::new();
+    for event in events {
+        if let Ok(at) = DateTime::parse_from_rfc3339(&event.applied_at) {
+            by_tool.entry(event.tool.as_str()).or_default().push((at, event));
+        }
+    }
+
+    let mut tools = Vec::new();
+    for (tool, mut applies) in by_tool {
+        applies.sort_by_key(|(at, _)| *at);
+
+        // Stats with the number of activations inside the period
+        let mut providers: Vec<(ProviderUsageStats, i64)> = Vec::new();
+        for (index, (at, event)) in applies.iter().enumerate() {
+            let end = applies.get(index + 1).map(|(next, _)| *next).unwrap_or(now);
+            if end <= since {
+                continue;
+            }
+            let index_in_stats = match providers.iter().position(|(p, _)| p.provider_id == event.provider_id) {
+                Some(position) => position,
+                None => {
+                    providers.push((
+                        ProviderUsageStats {
+                            provider_id: event.provider_id.clone(),
+                            provider_name: String::new(),
+                            switches: 0,
+                            active_seconds: 0,
+                            average_seconds: 0,
+                            last_applied_at: String::new(),
+                            current: false,
+                        },
+                        0,
+                    ));
+                    providers.len() - 1
+                }
+            };
+            let (stats, activations) = &mut providers[index_in_stats];
+            stats.provider_name = event.provider_name.clone();
+            stats.last_applied_at = event.applied_at.clone();
+            stats.active_seconds += (end - (*at).max(since)).num_seconds().max(0);
+            stats.current = index + 1 == applies.len();
+            if *at >= since {
+                stats.switches += 1;
+            }
+            *activations += 1;
+        }
+        if providers.is_empty() {
+            continue;
+        }
+
+        let mut providers: Vec<ProviderUsageStats> = providers
+            .into_iter()
+            .map(|(mut stats, activations)| {
+                stats.average_seconds = stats.active_seconds / activations.max(1);
+                stats
+            })
+            .collect();
+        providers.sort_by(|a, b| {
+            b.active_seconds
+                .cmp(&a.active_seconds)
+                .then(b.switches.cmp(&a.switches))
+        });
+        tools.push(ToolSwitchStats {
+            tool: tool.to_string(),
+            switches: providers.iter().map(|p| p.switches).sum(),
+            providers,
+        });
+    }
+
+    let total_switches: u32 = tools.iter().map(|t| t.switches).sum();
+    let per_week = total_switches as f64 / days.max(1) as f64 * 7.0;
+    SwitchAnalytics {
+        days,
+        total_switches,
+        switches_per_week: (per_week * 100.0).round() / 100.0,
+        tools,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(tool: &str, provider: &str, applied_at: &str) -> SwitchEvent {
+        SwitchEvent {
+            tool: tool.to_string(),
+            provider_id: provider.to_string(),
+            provider_name: provider.to_uppercase(),
+            applied_at: applied_at.to_string(),
+        }
+    }
+
+    #[test]
+    fn active_time_is_split_between_switches() {
+        let now = DateTime::parse_from_rfc3339("2026-03-31T00:00:00+00:00").unwrap();
+        let events = vec![
+            // Applied before the period, active for its first 10 days
+            event("claude", "relay-a", "2026-02-01T00:00:00+00:00"),
+            event("claude", "relay-b", "2026-03-11T00:00:00+00:00"),
+            event("claude", "relay-a", "2026-03-21T00:00:00+00:00"),
+            event("codex", "openai", "2026-03-30T00:00:00+00:00"),
+        ];
+        let analytics = analyze(&events, now, 30);
+        assert_eq!(analytics.total_switches, 3);
+        assert_eq!(analytics.switches_per_week, 0.7);
+
+        let claude = &analytics.tools[0];
+        assert_eq!(claude.tool, "claude");
+        assert_eq!(claude.switches, 2);
+        let relay_a = &claude.providers[0];
+        assert_eq!(relay_a.provider_id, "relay-a");
+        assert_eq!(relay_a.switches, 1);
+        assert_eq!(relay_a.active_seconds, 20 * 86400);
+        assert_eq!(relay_a.average_seconds, 10 * 86400);
+        assert!(relay_a.current);
+        assert_eq!(claude.providers[1].active_seconds, 10 * 86400);
+        assert!(!claude.providers[1].current);
+
+        assert_eq!(analytics.tools[1].providers[0].active_seconds, 86400);
+    }
+
+    #[test]
+    fn events_ending_before_the_period_are_ignored() {
+        let now = DateTime::parse_from_rfc3339("2026-03-31T00:00:00+00:00").unwrap();
+        let events = vec![
+            event("claude", "old", "2026-01-01T00:00:00+00:00"),
+            event("claude", "new", "2026-01-02T00:00:00+00:00"),
+        ];
+        let analytics = analyze(&events, now, 7);
+        assert_eq!(analytics.total_switches, 0);
+        assert_eq!(analytics.tools[0].providers.len(), 1);
+        assert_eq!(analytics.tools[0].providers[0].provider_id, "new");
+        assert_eq!(analytics.tools[0].providers[0].active_seconds, 7 * 86400);
+    }
+}
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+/// One provider apply
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwitchEvent {
+    /// Tool key, e.g. "claude", "codex", "gemini_cli"
+    pub tool: String,
+    pub provider_id: String,
+    pub provider_name: String,
+    /// RFC 3339 timestamp
+    pub applied_at: String,
+}
+
+/// How one provider was used within the analyzed period
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProviderUsageStats {
+    pub provider_id: String,
+    pub provider_name: String,
+    /// Times it was switched to
+    pub switches: u32,
+    /// Total time it was the applied provider
+    pub active_seconds: i64,
+    /// Average length of one activation
+    pub average_seconds: i64,
+    pub last_applied_at: String,
+    /// Currently applied
+    pub current: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ToolSwitchStats {
+    pub tool: String,
+    pub switches: u32,
+    /// Most used (longest active) first
+    pub providers: Vec<ProviderUsageStats>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SwitchAnalytics {
+    /// Length of the analyzed period
+    pub days: u32,
+    pub total_switches: u32,
+    pub switches_per_week: f64,
+    pub tools: Vec<ToolSwitchStats>,
+}
@@ -0,0 +1,264 @@
+//! Monthly cost budgets
+//!
+//! Budgets are checked against the aggregated `usage_daily` cost of the
+//! current month. Crossing 50 / 80 / 100 % sends one notification per
+//! threshold and month (checked periodically from setup).
+
+use chrono::Local;
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+
+use crate::coding::db_extract_id;
+use crate::db::DbState;
+use crate::error::{AppError, AppResult};
+use crate::notifications::{self, NotificationCategory};
+use super::commands::{load_records, refresh_usage};
+use super::types::*;
+
+type Db = surrealdb::Surreal<surrealdb::engine::local::Db>;
+
+/// Alert thresholds in percent of the monthly limit
+const THRESHOLDS: [u32; 3] = [50, 80, 100];
+/// Record key of the budget covering all providers
+const GLOBAL_KEY: &str = "*";
+
+fn budget_key(provider: Option<&str>) -> String {
+    provider.unwrap_or(GLOBAL_KEY).to_string()
+}
+
+fn from_db_value(mut value: Value) -> Option<UsageBudget> {
+    let key = db_extract_id(&value);
+    let map = value.as_object_mut()?;
+    map.remove("id");
+    let provider = (key != GLOBAL_KEY).then(|| Value::String(key));
+    map.insert("provider".to_string(), provider.unwrap_or(Value::Null));
+    serde_json::from_value(value).ok()
+}
+
+async fn list_budgets(db: &Db) -> AppResult<Vec<UsageBudget>> {
+    let records: Vec<Value> = db
+        .query("SELECT *, type::string(id) as id FROM usage_budget")
+        .await?
+        .take(0)?;
+    Ok(records.into_iter().filter_map(from_db_value).collect())
+}
+
+async fn save_record(db: &Db, budget: &UsageBudget) -> AppResult<()> {
+    let mut data = serde_json::to_value(budget).map_err(AppError::invalid)?;
+    if let Some(map) = data.as_object_mut() {
+        map.remove("provider");
+    }
+    db.query("UPSERT type::thing('usage_budget', $id) CONTENT $data")
+        .bind(("id", budget_key(budget.provider.as_deref())))
+        .bind(("data", data))
+        .await?;
+    Ok(())
+}
+
+fn current_month() -> String {
+    Local::now().format("%Y-%m").to_string()
+}
+
+/// Highest threshold reached by `spent` of `limit`
+pub fn reached_threshold(spent: f64, limit: f64) -> Option<u32> {
+    if limit <= 0.0 {
+        return None;
+    }
+    let percent = spent / limit * 100.0;
+    THRESHOLDS.iter().rev().find(|t| percent >= **t as f64).copied()
+}
+
+/// Status of every budget from this month's usage records
+pub fn evaluate(budgets: &[UsageBudget], records: &[UsageRecord], month: &str) -> Vec<BudgetStatus> {
+    let mut statuses: Vec<BudgetStatus> = budgets
+        .iter()
+        .map(|budget| {
+            let spent: f64 = records
+                .iter()
+                .filter(|r| r.date.starts_with(month))
+                .filter(|r| budget.provider.as_ref().is_none_or(|p| *p == r.provider))
+                .map(|r| r.cost)
+                .sum();
+            BudgetStatus {
+                provider: budget.provider.clone(),
+                monthly_limit: budget.monthly_limit,
+                spent,
+                percent: if budget.monthly_limit > 0.0 { spent / budget.monthly_limit * 100.0 } else { 0.0 },
+                threshold: reached_threshold(spent, budget.monthly_limit),
+                month: month.to_string(),
+            }
+        })
+        .collect();
+    // Global budget first, then by provider
+    statuses.sort_by(|a, b| a.provider.cmp(&b.provider));
+    statuses
+}
+
+/// Threshold to announce now, if it is higher than the one already announced this month
+pub fn pending_alert(budget: &UsageBudget, status: &BudgetStatus) -> Option<u32> {
+    let threshold = status.threshold?;
+    let announced = if budget.notified_month.as_deref() == Some(status.month.as_str()) {
+        budget.notified_percent
+    } else {
+        0
+    };
+    (threshold > announced).then_some(threshold)
+}
+
+async fn load_status(db: &Db) -> AppResult<(Vec<UsageBudget>, Vec<BudgetStatus>)> {
+    let month = current_month();
+    let start = format!("{}-01", month);
+    let budgets = list_budgets(db).await?;
+    let records = load_records(db, Some(&start)).await.map_err(AppError::db)?;
+    let statuses = evaluate(&budgets, &records, &month);
+    Ok((budgets, statuses))
+}
+
+/// Rescan usage and notify budgets that crossed a new threshold (run periodically from setup)
+pub async fn check_budgets(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<DbState>();
+    {
+        let db = state.0.lock().await;
+        if list_budgets(&db).await.map_err(|e| e.to_string())?.is_empty() {
+            return Ok(());
+        }
+    }
+    refresh_usage(&state).await?;
+
+    let db = state.0.lock().await;
+    let (budgets, statuses) = load_status(&db).await.map_err(|e| e.to_string())?;
+    for mut budget in budgets {
+        let Some(status) = statuses.iter().find(|s| s.provider == budget.provider) else {
+            continue;
+        };
+        let Some(threshold) = pending_alert(&budget, status) else {
+            continue;
+        };
+        let scope = budget.provider.clone().unwrap_or_else(|| "全部供应商".to_string());
+        let title = if threshold >= 100 { "已超出预算" } else { "预算提醒" };
+        notifications::notify(
+            app,
+            NotificationCategory::Budget,
+            title,
+            format!(
+                "{} 本月已花费 ${:.2}，达到预算 ${:.2} 的 {}%",
+                scope, status.spent, status.monthly_limit, threshold
+            ),
+        );
+        budget.notified_month = Some(status.month.clone());
+        budget.notified_percent = threshold;
+        save_record(&db, &budget).await.map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Budget Commands
+// ============================================================================
+
+/// Set the monthly limit (USD) of a provider, or of all providers when `provider` is empty
+#[tauri::command]
+pub async fn save_budget(
+    state: tauri::State<'_, DbState>,
+    provider: Option<String>,
+    monthly_limit: f64,
+) -> AppResult<()> {
+    if !monthly_limit.is_finite() || monthly_limit <= 0.0 {
+        return Err(AppError::validation(
+            "INVALID_BUDGET",
+            &[("limit", &monthly_limit.to_string())],
+        ));
+    }
+    let provider = provider.map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+
+    let db = state.0.lock().await;
+    let existing = list_budgets(&db)
+        .await?
+        .into_iter()
+        .find(|b| b.provider == provider);
+    let budget = UsageBudget {
+        provider,
+        monthly_limit,
+        // Keep this month's announced thresholds when the limit is edited
+        notified_month: existing.as_ref().and_then(|b| b.notified_month.clone()),
+        notified_percent: existing.map(|b| b.notified_percent).unwrap_or(0),
+    };
+    save_record(&db, &budget).await
+}
+
+#[tauri::command]
+pub async fn delete_budget(state: tauri::State<'_, DbState>, provider: Option<String>) -> AppResult<()> {
+    let provider = provider.map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+    let db = state.0.lock().await;
+    db.query("DELETE type::thing('usage_budget', $id)")
+        .bind(("id", budget_key(provider.as_deref())))
+        .await?;
+    Ok(())
+}
+
+/// Spend of every budget this month; `refresh` rescans the tool logs first
+#[tauri::command]
+pub async fn get_budget_status(
+    state: tauri::State<'_, DbState>,
+    refresh: Option<bool>,
+) -> AppResult<Vec<BudgetStatus>> {
+    if refresh.unwrap_or(false) {
+        refresh_usage(&state).await.map_err(AppError::io)?;
+    }
+    let db = state.0.lock().await;
+    Ok(load_status(&db).await?.1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(date: &str, provider: &str, cost: f64) -> UsageRecord {
+        UsageRecord {
+            date: date.to_string(),
+            provider: provider.to_string(),
+            cost,
+            ..Default::default()
+        }
+    }
+
+    fn budget(provider: Option<&str>, limit: f64) -> UsageBudget {
+        UsageBudget {
+            provider: provider.map(String::from),
+            monthly_limit: limit,
+            notified_month: None,
+            notified_percent: 0,
+        }
+    }
+
+    #[test]
+    fn evaluate_sums_month_per_provider() {
+        let records = vec![
+            record("2026-03-01", "anthropic", 6.0),
+            record("2026-03-15", "openrouter", 3.0),
+            record("2026-02-28", "anthropic", 100.0),
+        ];
+        let statuses = evaluate(&[budget(Some("anthropic"), 10.0), budget(None, 10.0)], &records, "2026-03");
+        assert_eq!(statuses[0].provider, None);
+        assert_eq!(statuses[0].spent, 9.0);
+        assert_eq!(statuses[0].threshold, Some(80));
+        assert_eq!(statuses[1].spent, 6.0);
+        assert_eq!(statuses[1].threshold, Some(50));
+    }
+
+    #[test]
+    fn alerts_once_per_threshold_and_month() {
+        let mut plan = budget(None, 10.0);
+        let status = evaluate(&[plan.clone()], &[record("2026-03-02", "x", 8.5)], "2026-03").remove(0);
+        assert_eq!(pending_alert(&plan, &status), Some(80));
+
+        plan.notified_month = Some("2026-03".to_string());
+        plan.notified_percent = 80;
+        assert_eq!(pending_alert(&plan, &status), None);
+
+        plan.notified_month = Some("2026-02".to_string());
+        assert_eq!(pending_alert(&plan, &status), Some(80));
+        assert_eq!(reached_threshold(12.0, 10.0), Some(100));
+        assert_eq!(reached_threshold(4.0, 10.0), None);
+    }
+}
@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+
+use chrono::{Duration, Local};
+use serde_json::Value;
+
+use crate::db::DbState;
+use super::scanner;
+use super::types::*;
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+fn from_db_value_record(value: &Value) -> UsageRecord {
+    let get_str = |key: &str| value.get(key).and_then(|v| v.as_str()).unwrap_or("").to_string();
+    let get_u64 = |key: &str| value.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+    UsageRecord {
+        date: get_str("date"),
+        tool: get_str("tool"),
+        provider: get_str("provider"),
+        model: get_str("model"),
+        requests: get_u64("requests"),
+        input_tokens: get_u64("input_tokens"),
+        output_tokens: get_u64("output_tokens"),
+        cache_read_tokens: get_u64("cache_read_tokens"),
+        cache_write_tokens: get_u64("cache_write_tokens"),
+        cost: value.get("cost").and_then(|v| v.as_f64()).unwrap_or(0.0),
+    }
+}
+
+pub(super) async fn load_records(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    start_date: Option<&String>,
+) -> Result<Vec<UsageRecord>, String> {
+    let query = match start_date {
+        Some(_) => "SELECT * OMIT id FROM usage_daily WHERE date >= $start",
+        None => "SELECT * OMIT id FROM usage_daily",
+    };
+    let records: Vec<Value> = db
+        .query(query)
+        .bind(("start", start_date.cloned().unwrap_or_default()))
+        .await
+        .map_err(|e| format!("Failed to query usage: {}", e))?
+        .take(0)
+        .map_err(|e| format!("Failed to parse usage: {}", e))?;
+    Ok(records.iter().map(from_db_value_record).collect())
+}
+
+/// Merge freshly scanned records into `usage_daily`
+/// Tools prune old logs, so a day is only overwritten when the scan saw
+/// more requests than already stored; pruned history is kept.
+async fn persist_records(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    scanned: Vec<UsageRecord>,
+) -> Result<(), String> {
+    let stored: HashMap<String, UsageRecord> = load_records(db, None)
+        .await?
+        .into_iter()
+        .map(|r| (r.key(), r))
+        .collect();
+
+    let rows: Vec<Value> = scanned
+        .into_iter()
+        .filter(|record| match stored.get(&record.key()) {
+            Some(old) => record.requests > old.requests,
+            None => true,
+        })
+        .map(|record| {
+            serde_json::json!({
+                "key": record.key(),
+                "data": record,
+            })
+        })
+        .collect();
+    if rows.is_empty() {
+        return Ok(());
+    }
+
+    db.query("FOR $row IN $rows { UPSERT type::thing('usage_daily', $row.key) CONTENT $row.data; }")
+        .bind(("rows", rows))
+        .await
+        .map_err(|e| format!("Failed to save usage: {}", e))?;
+    Ok(())
+}
+
+/// Scan tool logs and merge them into `usage_daily`; returns the scanner errors
+pub(super) async fn refresh_usage(state: &DbState) -> Result<Vec<String>, String> {
+    let (scanned, scan_errors) = tokio::task::spawn_blocking(scanner::scan_all)
+        .await
+        .map_err(|e| format!("Failed to scan usage: {}", e))?;
+
+    let db = state.0.lock().await;
+    persist_records(&db, scanned).await?;
+    Ok(scan_errors)
+}
+
+// ============================================================================
+// Aggregation
+// ============================================================================
+
+/// First day of a range ("7d", "30d", "90d"); None for "all"
+fn range_start(range: &str) -> Result<Option<String>, String> {
+    let days = match range {
+        "all" => return Ok(None),
+        "7d" => 7,
+        "30d" => 30,
+        "90d" => 90,
+        other => return Err(format!("Unsupported range: {}", other)),
+    };
+    let start = Local::now().date_naive() - Duration::days(days - 1);
+    Ok(Some(start.format("%Y-%m-%d").to_string()))
+}
+
+fn group_by(records: &[UsageRecord], key: impl Fn(&UsageRecord) -> String) -> Vec<UsageBucket> {
+    let mut buckets: HashMap<String, UsageBucket> = HashMap::new();
+    for record in records {
+        let key = key(record);
+        buckets
+            .entry(key.clone())
+            .or_insert_with(|| UsageBucket { key, ..Default::default() })
+            .add(record);
+    }
+    buckets.into_values().collect()
+}
+
+fn sort_by_tokens(mut buckets: Vec<UsageBucket>) -> Vec<UsageBucket> {
+    buckets.sort_by(|a, b| b.total_tokens.cmp(&a.total_tokens).then_with(|| a.key.cmp(&b.key)));
+    buckets
+}
+
+fn build_dashboard(range: String, start_date: Option<String>, records: &[UsageRecord]) -> UsageDashboard {
+    let mut totals = UsageBucket {
+        key: "total".to_string(),
+        ..Default::default()
+    };
+    for record in records {
+        totals.add(record);
+    }
+
+    let mut by_day = group_by(records, |r| r.date.clone());
+    by_day.sort_by(|a, b| a.key.cmp(&b.key));
+
+    UsageDashboard {
+        range,
+        start_date,
+        totals,
+        by_day,
+        by_model: sort_by_tokens(group_by(records, |r| r.model.clone())),
+        by_provider: sort_by_tokens(group_by(records, |r| r.provider.clone())),
+        by_tool: sort_by_tokens(group_by(records, |r| r.tool.clone())),
+        scan_errors: Vec::new(),
+    }
+}
+
+// ============================================================================
+// Usage Commands
+// ============================================================================
+
+/// Scan tool logs, persist the daily usage and return the dashboard for `range`
+/// (`"7d"`, `"30d"`, `"90d"` or `"all"`)
+#[tauri::command]
+pub async fn get_usage_dashboard(
+    state: tauri::State<'_, DbState>,
+    range: String,
+) -> Result<UsageDashboard, String> {
+    let start_date = range_start(&range)?;
+    let scan_errors = refresh_usage(&state).await?;
+
+    let db = state.0.lock().await;
+    let records = load_records(&db, start_date.as_ref()).await?;
+
+    let mut dashboard = build_dashboard(range, start_date, &records);
+    dashboard.scan_errors = scan_errors;
+    Ok(dashboard)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(date: &str, model: &str, input: u64) -> UsageRecord {
+        UsageRecord {
+            date: date.to_string(),
+            tool: "claude".to_string(),
+            provider: "anthropic".to_string(),
+            model: model.to_string(),
+            requests: 1,
+            input_tokens: input,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn dashboard_groups_and_sorts() {
+        let records = vec![
+            record("2025-01-02", "a", 10),
+            record("2025-01-01", "b", 30),
+            record("2025-01-02", "b", 5),
+        ];
+        let dashboard = build_dashboard("all".to_string(), None, &records);
+
+        assert_eq!(dashboard.totals.total_tokens, 45);
+        assert_eq!(dashboard.by_day[0].key, "2025-01-01");
+        assert_eq!(dashboard.by_model[0].key, "b");
+        assert_eq!(dashboard.by_model[0].requests, 2);
+        assert_eq!(dashboard.by_provider.len(), 1);
+    }
+
+    #[test]
+    fn range_start_rejects_unknown_ranges() {
+        assert!(range_start("all").unwrap().is_none());
+        assert!(range_start("7d").unwrap().is_some());
+        assert!(range_start("1y").is_err());
+    }
+}
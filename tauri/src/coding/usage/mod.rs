@@ -0,0 +1,7 @@
+pub mod budget;
+pub mod commands;
+pub mod scanner;
+pub mod types;
+
+pub use commands::*;
+pub use types::*;
@@ -0,0 +1,234 @@
+//! Usage scanners
+//!
+//! Each scanner reads the local logs of one tool and returns per-day usage
+//! records. To support another tool, add a scanner to `SCANNERS`.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local, TimeZone};
+use serde_json::Value;
+use walkdir::WalkDir;
+
+use super::types::UsageRecord;
+
+/// (tool key, scanner)
+pub const SCANNERS: [(&str, fn(&Path) -> Result<Vec<UsageRecord>, String>); 2] = [
+    ("claude", scan_claude_code),
+    ("opencode", scan_opencode),
+];
+
+/// Run every scanner; returns the records and the scanner errors
+pub fn scan_all() -> (Vec<UsageRecord>, Vec<String>) {
+    let home_dir = match std::env::var("USERPROFILE").or_else(|_| std::env::var("HOME")) {
+        Ok(home) => PathBuf::from(home),
+        Err(_) => return (Vec::new(), vec!["Failed to get home directory".to_string()]),
+    };
+
+    let mut records = Vec::new();
+    let mut errors = Vec::new();
+    for (tool, scanner) in SCANNERS {
+        match scanner(&home_dir) {
+            Ok(mut found) => records.append(&mut found),
+            Err(e) => errors.push(format!("{}: {}", tool, e)),
+        }
+    }
+    (records, errors)
+}
+
+fn local_date<Tz: TimeZone>(time: DateTime<Tz>) -> String {
+    time.with_timezone(&Local).format("%Y-%m-%d").to_string()
+}
+
+fn get_u64(value: &Value, key: &str) -> u64 {
+    value.get(key).and_then(|v| v.as_u64()).unwrap_or(0)
+}
+
+/// Add one request to the per-day aggregation
+fn accumulate(records: &mut HashMap<String, UsageRecord>, record: UsageRecord) {
+    records
+        .entry(record.key())
+        .and_modify(|existing| existing.add(&record))
+        .or_insert(record);
+}
+
+// ============================================================================
+// Claude Code
+// ============================================================================
+
+/// Scan Claude Code session logs (~/.claude/projects/<project>/<session>.jsonl)
+/// Claude Code does not log which endpoint served a request, so usage is
+/// attributed to the "anthropic" provider.
+pub fn scan_claude_code(home_dir: &Path) -> Result<Vec<UsageRecord>, String> {
+    let projects_dir = home_dir.join(".claude").join("projects");
+    if !projects_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut records = HashMap::new();
+    // Streamed responses log the same message several times
+    let mut seen = HashSet::new();
+    for entry in WalkDir::new(&projects_dir).into_iter().filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("jsonl") {
+            continue;
+        }
+        let file = match fs::File::open(path) {
+            Ok(file) => file,
+            Err(e) => {
+                log::warn!("Failed to open {}: {}", path.display(), e);
+                continue;
+            }
+        };
+
+        for line in BufReader::new(file).lines().map_while(Result::ok) {
+            let Ok(value) = serde_json::from_str::<Value>(&line) else {
+                continue;
+            };
+            let Some(message) = value.get("message") else {
+                continue;
+            };
+            let Some(usage) = message.get("usage") else {
+                continue;
+            };
+            let model = message.get("model").and_then(|v| v.as_str()).unwrap_or("");
+            if model.is_empty() || model == "<synthetic>" {
+                continue;
+            }
+            let Some(date) = value
+                .get("timestamp")
+                .and_then(|v| v.as_str())
+                .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+                .map(local_date)
+            else {
+                continue;
+            };
+
+            let message_id = message.get("id").and_then(|v| v.as_str()).unwrap_or("");
+            let request_id = value.get("requestId").and_then(|v| v.as_str()).unwrap_or("");
+            if !message_id.is_empty() && !seen.insert(format!("{}:{}", message_id, request_id)) {
+                continue;
+            }
+
+            accumulate(
+                &mut records,
+                UsageRecord {
+                    date,
+                    tool: "claude".to_string(),
+                    provider: "anthropic".to_string(),
+                    model: model.to_string(),
+                    requests: 1,
+                    input_tokens: get_u64(usage, "input_tokens"),
+                    output_tokens: get_u64(usage, "output_tokens"),
+                    cache_read_tokens: get_u64(usage, "cache_read_input_tokens"),
+                    cache_write_tokens: get_u64(usage, "cache_creation_input_tokens"),
+                    cost: value.get("costUSD").and_then(|v| v.as_f64()).unwrap_or(0.0),
+                },
+            );
+        }
+    }
+    Ok(records.into_values().collect())
+}
+
+// ============================================================================
+// OpenCode
+// ============================================================================
+
+/// OpenCode data directory ($XDG_DATA_HOME/opencode, default ~/.local/share/opencode)
+fn opencode_data_dir(home_dir: &Path) -> PathBuf {
+    std::env::var("XDG_DATA_HOME")
+        .ok()
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home_dir.join(".local").join("share"))
+        .join("opencode")
+}
+
+/// Scan OpenCode message storage (storage/message/<session>/<message>.json)
+pub fn scan_opencode(home_dir: &Path) -> Result<Vec<UsageRecord>, String> {
+    let message_dir = opencode_data_dir(home_dir).join("storage").join("message");
+    if !message_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut records = HashMap::new();
+    for entry in WalkDir::new(&message_dir).into_iter().filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(path) else {
+            continue;
+        };
+        let Ok(value) = serde_json::from_str::<Value>(&content) else {
+            continue;
+        };
+        if value.get("role").and_then(|v| v.as_str()) != Some("assistant") {
+            continue;
+        }
+        let Some(tokens) = value.get("tokens") else {
+            continue;
+        };
+        let Some(date) = value
+            .get("time")
+            .and_then(|t| t.get("created"))
+            .and_then(|v| v.as_i64())
+            .and_then(|ms| Local.timestamp_millis_opt(ms).single())
+            .map(local_date)
+        else {
+            continue;
+        };
+        let cache = tokens.get("cache").cloned().unwrap_or(Value::Null);
+
+        accumulate(
+            &mut records,
+            UsageRecord {
+                date,
+                tool: "opencode".to_string(),
+                provider: value
+                    .get("providerID")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("unknown")
+                    .to_string(),
+                model: value
+                    .get("modelID")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("unknown")
+                    .to_string(),
+                requests: 1,
+                input_tokens: get_u64(tokens, "input"),
+                // Reasoning tokens are billed as output
+                output_tokens: get_u64(tokens, "output") + get_u64(tokens, "reasoning"),
+                cache_read_tokens: get_u64(&cache, "read"),
+                cache_write_tokens: get_u64(&cache, "write"),
+                cost: value.get("cost").and_then(|v| v.as_f64()).unwrap_or(0.0),
+            },
+        );
+    }
+    Ok(records.into_values().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn claude_scanner_dedupes_streamed_messages() {
+        let home = std::env::temp_dir().join(format!("ai-toolbox-usage-{}", uuid::Uuid::new_v4()));
+        let project = home.join(".claude").join("projects").join("demo");
+        fs::create_dir_all(&project).unwrap();
+        let line = r#"{"timestamp":"2025-01-02T10:00:00Z","requestId":"req_1","message":{"id":"msg_1","model":"claude-sonnet-4","usage":{"input_tokens":10,"output_tokens":5,"cache_read_input_tokens":100}}}"#;
+        fs::write(project.join("session.jsonl"), format!("{}\n{}\n", line, line)).unwrap();
+
+        let records = scan_claude_code(&home).unwrap();
+        fs::remove_dir_all(&home).ok();
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].requests, 1);
+        assert_eq!(records[0].input_tokens, 10);
+        assert_eq!(records[0].cache_read_tokens, 100);
+        assert_eq!(records[0].model, "claude-sonnet-4");
+    }
+}
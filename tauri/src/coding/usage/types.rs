@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+
+/// Token usage of one (day, tool, provider, model) combination
+/// Scanners produce these; they are persisted in the `usage_daily` table.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UsageRecord {
+    /// Local date, YYYY-MM-DD
+    pub date: String,
+    /// "claude" or "opencode"
+    pub tool: String,
+    pub provider: String,
+    pub model: String,
+    pub requests: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_write_tokens: u64,
+    /// Cost in USD as reported by the tool (0 when the tool does not record it)
+    pub cost: f64,
+}
+
+impl UsageRecord {
+    pub fn key(&self) -> String {
+        format!("{}|{}|{}|{}", self.date, self.tool, self.provider, self.model)
+    }
+
+    pub fn add(&mut self, other: &UsageRecord) {
+        self.requests += other.requests;
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.cache_read_tokens += other.cache_read_tokens;
+        self.cache_write_tokens += other.cache_write_tokens;
+        self.cost += other.cost;
+    }
+}
+
+/// Aggregated usage for one dashboard group (a day, a model, a provider, ...)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UsageBucket {
+    pub key: String,
+    pub requests: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_write_tokens: u64,
+    pub total_tokens: u64,
+    pub cost: f64,
+}
+
+impl UsageBucket {
+    pub fn add(&mut self, record: &UsageRecord) {
+        self.requests += record.requests;
+        self.input_tokens += record.input_tokens;
+        self.output_tokens += record.output_tokens;
+        self.cache_read_tokens += record.cache_read_tokens;
+        self.cache_write_tokens += record.cache_write_tokens;
+        self.total_tokens += record.input_tokens
+            + record.output_tokens
+            + record.cache_read_tokens
+            + record.cache_write_tokens;
+        self.cost += record.cost;
+    }
+}
+
+/// Result of `get_usage_dashboard`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageDashboard {
+    /// "7d", "30d", "90d" or "all"
+    pub range: String,
+    /// First day included (None for "all")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    pub totals: UsageBucket,
+    /// Sorted by date ascending
+    pub by_day: Vec<UsageBucket>,
+    /// Sorted by total tokens descending
+    pub by_model: Vec<UsageBucket>,
+    pub by_provider: Vec<UsageBucket>,
+    pub by_tool: Vec<UsageBucket>,
+    /// Scanner errors (the dashboard still shows persisted data)
+    #[serde(default)]
+    pub scan_errors: Vec<String>,
+}
+
+/// Monthly cost budget; stored in `usage_budget`, keyed by provider ("*" for the global budget)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageBudget {
+    /// Usage provider key as shown on the dashboard; None for all providers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    /// Limit in USD per calendar month
+    pub monthly_limit: f64,
+    /// Month (YYYY-MM) of the last alert, so each threshold is announced once a month
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notified_month: Option<String>,
+    /// Highest threshold (percent) already announced in `notified_month`
+    #[serde(default)]
+    pub notified_percent: u32,
+}
+
+/// Result of `get_budget_status`, one per budget
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BudgetStatus {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    pub monthly_limit: f64,
+    /// Cost so far this month, in USD
+    pub spent: f64,
+    pub percent: f64,
+    /// Highest threshold reached (50, 80 or 100)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub threshold: Option<u32>,
+    /// Current month, YYYY-MM
+    pub month: String,
+}
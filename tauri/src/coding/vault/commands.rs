@@ -0,0 +1,333 @@
+use chrono::Local;
+use serde_json::Value;
+
+use crate::coding::db_extract_id;
+use crate::db::DbState;
+use crate::error::{AppError, AppResult};
+use super::types::*;
+
+/// Keychain service name; each key is stored under its vault id
+const KEYCHAIN_SERVICE: &str = "ai-toolbox-vault";
+
+/// Provider tables scanned for references: (table, display name)
+const REFERENCE_TABLES: [(&str, &str); 3] = [
+    ("claude_provider", "Claude Code"),
+    ("codex_provider", "Codex"),
+    ("opencode_favorite_provider", "OpenCode"),
+];
+
+// ============================================================================
+// Keychain Helpers
+// ============================================================================
+
+fn keychain_entry(id: &str) -> AppResult<keyring::Entry> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, id).map_err(AppError::io)
+}
+
+fn read_secret(id: &str) -> AppResult<String> {
+    keychain_entry(id)?.get_password().map_err(|e| match e {
+        keyring::Error::NoEntry => AppError::not_found("VAULT_KEY_NOT_FOUND", &[("id", id)]),
+        e => AppError::io(e),
+    })
+}
+
+fn write_secret(id: &str, secret: &str) -> AppResult<()> {
+    keychain_entry(id)?.set_password(secret).map_err(AppError::io)
+}
+
+fn key_hint(secret: &str) -> String {
+    let chars: Vec<char> = secret.chars().collect();
+    if chars.len() <= 8 {
+        return "…".to_string();
+    }
+    format!("…{}", chars[chars.len() - 4..].iter().collect::<String>())
+}
+
+// ============================================================================
+// Reference Resolution
+// ============================================================================
+
+/// Vault id of a `vault://<id>` reference
+pub fn parse_reference(value: &str) -> Option<&str> {
+    value
+        .strip_prefix(VAULT_REFERENCE_PREFIX)
+        .filter(|id| !id.is_empty())
+}
+
+/// Replace every `vault://<id>` string in a config value with the stored key
+/// Called by the apply paths right before config files are written.
+pub fn resolve_references(value: &mut Value) -> AppResult<()> {
+    resolve_references_with(value, &read_secret)
+}
+
+/// `resolve_references` reading the keys through `read` (the keychain outside tests)
+pub fn resolve_references_with(
+    value: &mut Value,
+    read: &dyn Fn(&str) -> AppResult<String>,
+) -> AppResult<()> {
+    match value {
+        Value::String(s) => {
+            if let Some(id) = parse_reference(s) {
+                *s = read(id)?;
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                resolve_references_with(item, read)?;
+            }
+        }
+        Value::Object(map) => {
+            for (_, item) in map.iter_mut() {
+                resolve_references_with(item, read)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// (stored key, reference) of every `vault://<id>` string in a config value
+pub fn referenced_secrets(value: &Value) -> AppResult<Vec<(String, String)>> {
+    let mut secrets = Vec::new();
+    collect_references(value, &mut secrets)?;
+    Ok(secrets)
+}
+
+fn collect_references(value: &Value, secrets: &mut Vec<(String, String)>) -> AppResult<()> {
+    match value {
+        Value::String(s) => {
+            if let Some(id) = parse_reference(s) {
+                let secret = read_secret(id)?;
+                if !secret.is_empty() {
+                    secrets.push((secret, s.clone()));
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_references(item, secrets)?;
+            }
+        }
+        Value::Object(map) => {
+            for item in map.values() {
+                collect_references(item, secrets)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Put the references back where a resolved config holds one of `secrets`,
+/// so what is kept outside the keychain (e.g. apply history) has no keys
+pub fn restore_references(value: &mut Value, secrets: &[(String, String)]) {
+    match value {
+        Value::String(s) => {
+            if let Some((_, reference)) = secrets.iter().find(|(secret, _)| secret == s) {
+                *s = reference.clone();
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                restore_references(item, secrets);
+            }
+        }
+        Value::Object(map) => {
+            for (_, item) in map.iter_mut() {
+                restore_references(item, secrets);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Providers referencing a vault key
+async fn find_usages(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    id: &str,
+) -> Vec<String> {
+    let reference = format!("{}{}", VAULT_REFERENCE_PREFIX, id);
+    let mut usages = Vec::new();
+    for (table, tool) in REFERENCE_TABLES {
+        let records: Vec<Value> = match db
+            .query(format!("SELECT * OMIT id FROM {}", table))
+            .await
+            .and_then(|mut result| result.take(0))
+        {
+            Ok(records) => records,
+            Err(_) => continue,
+        };
+        for record in records {
+            // settings_config is stored as a JSON string, so match on the serialized record
+            if !record.to_string().contains(&reference) {
+                continue;
+            }
+            let name = record
+                .get("name")
+                .or_else(|| record.get("provider_id"))
+                .and_then(|v| v.as_str())
+                .unwrap_or("");
+            usages.push(format!("{}: {}", tool, name));
+        }
+    }
+    usages
+}
+
+fn from_db_value_key(value: Value) -> VaultKey {
+    let get_str = |key: &str| {
+        value
+            .get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string()
+    };
+    let id = db_extract_id(&value);
+    VaultKey {
+        reference: format!("{}{}", VAULT_REFERENCE_PREFIX, id),
+        name: get_str("name"),
+        hint: get_str("hint"),
+        usages: Vec::new(),
+        created_at: get_str("created_at"),
+        updated_at: get_str("updated_at"),
+        id,
+    }
+}
+
+// ============================================================================
+// Vault Commands
+// ============================================================================
+
+/// List vault keys (metadata only) with the providers referencing them
+#[tauri::command]
+pub async fn list_vault_keys(state: tauri::State<'_, DbState>) -> AppResult<Vec<VaultKey>> {
+    let db = state.0.lock().await;
+    let records: Vec<Value> = db
+        .query("SELECT *, type::string(id) as id FROM vault_key ORDER BY name ASC")
+        .await?
+        .take(0)?;
+
+    let mut keys = Vec::new();
+    for record in records {
+        let mut key = from_db_value_key(record);
+        key.usages = find_usages(&db, &key.id).await;
+        keys.push(key);
+    }
+    Ok(keys)
+}
+
+/// Create a vault key, or rename / rotate an existing one
+/// Rotated keys are picked up by every referencing tool config on its next apply.
+#[tauri::command]
+pub async fn save_vault_key(
+    state: tauri::State<'_, DbState>,
+    input: VaultKeyInput,
+) -> AppResult<VaultKey> {
+    let name = input.name.trim().to_string();
+    if name.is_empty() {
+        return Err(AppError::validation("NAME_REQUIRED", &[]));
+    }
+    let secret = input.secret.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+
+    let db = state.0.lock().await;
+    let now = Local::now().to_rfc3339();
+    let id = match input.id {
+        Some(id) => {
+            let mut query = "UPDATE type::thing('vault_key', $id) SET name = $name, updated_at = $now".to_string();
+            if let Some(secret) = &secret {
+                write_secret(&id, secret)?;
+                query.push_str(", hint = $hint");
+            }
+            db.query(query)
+                .bind(("id", id.clone()))
+                .bind(("name", name))
+                .bind(("now", now))
+                .bind(("hint", secret.as_deref().map(key_hint).unwrap_or_default()))
+                .await?;
+            id
+        }
+        None => {
+            let secret = secret.ok_or_else(|| AppError::validation("VAULT_KEY_REQUIRED", &[]))?;
+            let id = uuid::Uuid::new_v4().to_string();
+            write_secret(&id, &secret)?;
+            db.query("CREATE type::thing('vault_key', $id) CONTENT $data")
+                .bind(("id", id.clone()))
+                .bind((
+                    "data",
+                    serde_json::json!({
+                        "name": name,
+                        "hint": key_hint(&secret),
+                        "created_at": now,
+                        "updated_at": now,
+                    }),
+                ))
+                .await?;
+            id
+        }
+    };
+
+    let records: Vec<Value> = db
+        .query("SELECT *, type::string(id) as id FROM type::thing('vault_key', $id)")
+        .bind(("id", id.clone()))
+        .await?
+        .take(0)?;
+    let mut key = records
+        .into_iter()
+        .next()
+        .map(from_db_value_key)
+        .ok_or_else(|| AppError::not_found("VAULT_KEY_NOT_FOUND", &[("id", id.as_str())]))?;
+    key.usages = find_usages(&db, &id).await;
+    Ok(key)
+}
+
+/// Delete a vault key; fails while provider configs still reference it
+#[tauri::command]
+pub async fn delete_vault_key(state: tauri::State<'_, DbState>, id: String) -> AppResult<()> {
+    let db = state.0.lock().await;
+    let usages = find_usages(&db, &id).await;
+    if !usages.is_empty() {
+        return Err(AppError::conflict("VAULT_KEY_IN_USE", &[("usages", usages.join(", ").as_str())]));
+    }
+
+    if let Ok(entry) = keychain_entry(&id) {
+        let _ = entry.delete_credential();
+    }
+    db.query("DELETE type::thing('vault_key', $id)")
+        .bind(("id", id))
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_reference_requires_id() {
+        assert_eq!(parse_reference("vault://abc"), Some("abc"));
+        assert_eq!(parse_reference("vault://"), None);
+        assert_eq!(parse_reference("sk-abc"), None);
+    }
+
+    #[test]
+    fn restores_references_of_resolved_keys() {
+        let secrets = vec![("sk-live-123".to_string(), "vault://k1".to_string())];
+        let mut settings = serde_json::json!({
+            "env": { "ANTHROPIC_AUTH_TOKEN": "sk-live-123", "ANTHROPIC_BASE_URL": "https://api.example.com" },
+            "apiKeyHelper": ["sk-live-123"]
+        });
+        restore_references(&mut settings, &secrets);
+        assert_eq!(
+            settings,
+            serde_json::json!({
+                "env": { "ANTHROPIC_AUTH_TOKEN": "vault://k1", "ANTHROPIC_BASE_URL": "https://api.example.com" },
+                "apiKeyHelper": ["vault://k1"]
+            })
+        );
+    }
+
+    #[test]
+    fn key_hint_hides_short_keys() {
+        assert_eq!(key_hint("sk-123"), "…");
+        assert_eq!(key_hint("sk-abcdef123456"), "…3456");
+    }
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// Prefix of a vault reference stored in tool configs: `vault://<id>`
+pub const VAULT_REFERENCE_PREFIX: &str = "vault://";
+
+/// A key stored in the vault (the secret itself lives in the OS keychain)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultKey {
+    pub id: String,
+    pub name: String,
+    /// Last characters of the key for recognition, e.g. "…a1b2"
+    pub hint: String,
+    /// Reference to put in provider configs: `vault://<id>`
+    pub reference: String,
+    /// Providers whose config references this key, e.g. "Claude Code: My Relay"
+    #[serde(default)]
+    pub usages: Vec<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Input for creating a key or renaming / rotating an existing one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultKeyInput {
+    pub id: Option<String>,
+    pub name: String,
+    /// Required when creating; when updating, a new value rotates the key
+    pub secret: Option<String>,
+}
@@ -0,0 +1,117 @@
+//! VS Code family path helpers
+//!
+//! Shared by integrations that target VS Code extensions (Roo Code, Kilo Code, ...).
+//! Resolves user settings and extension globalStorage directories for every
+//! VS Code based editor on the current platform.
+
+use std::path::PathBuf;
+
+use crate::coding::change_history;
+
+/// VS Code based editors: (display name, user data folder name)
+pub const VSCODE_EDITORS: [(&str, &str); 5] = [
+    ("VS Code", "Code"),
+    ("VS Code Insiders", "Code - Insiders"),
+    ("VSCodium", "VSCodium"),
+    ("Cursor", "Cursor"),
+    ("Windsurf", "Windsurf"),
+];
+
+/// Editor user directory (e.g. %APPDATA%/Code/User, ~/Library/Application Support/Code/User, ~/.config/Code/User)
+pub fn get_editor_user_dir(folder: &str) -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(folder).join("User"))
+}
+
+/// Editor user settings.json path
+pub fn get_editor_settings_path(folder: &str) -> Option<PathBuf> {
+    get_editor_user_dir(folder).map(|dir| dir.join("settings.json"))
+}
+
+/// Extension globalStorage directory for an editor
+pub fn get_extension_storage_dir(folder: &str, extension_id: &str) -> Option<PathBuf> {
+    get_editor_user_dir(folder).map(|dir| dir.join("globalStorage").join(extension_id))
+}
+
+/// Find the installed extension directory (highest version) under ~/.vscode*/extensions
+pub fn find_installed_extension(extension_id: &str) -> Option<PathBuf> {
+    let home = dirs::home_dir()?;
+    let prefix = format!("{}-", extension_id.to_lowercase());
+    let mut found: Vec<PathBuf> = Vec::new();
+
+    for ext_root in [
+        ".vscode/extensions",
+        ".vscode-insiders/extensions",
+        ".vscode-oss/extensions",
+        ".cursor/extensions",
+        ".windsurf/extensions",
+    ] {
+        let Ok(entries) = std::fs::read_dir(home.join(ext_root)) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            if name.starts_with(&prefix) && entry.path().is_dir() {
+                found.push(entry.path());
+            }
+        }
+    }
+
+    // Compare numerically so 3.10.0 sorts after 3.9.0
+    found.sort_by_key(|path| {
+        extension_version(path)
+            .map(|v| {
+                v.split(|c: char| !c.is_ascii_digit())
+                    .filter_map(|part| part.parse::<u64>().ok())
+                    .collect::<Vec<u64>>()
+            })
+            .unwrap_or_default()
+    });
+    found.pop()
+}
+
+/// Extract the version from an extension directory name
+/// (publisher.name-1.2.3 or publisher.name-1.2.3-darwin-arm64)
+pub fn extension_version(path: &std::path::Path) -> Option<String> {
+    let name = path.file_name()?.to_string_lossy().to_string();
+    let bytes = name.as_bytes();
+    let start = (1..bytes.len()).find(|&i| bytes[i - 1] == b'-' && bytes[i].is_ascii_digit())?;
+    let version = name[start..].split('-').next()?;
+    Some(version.to_string())
+}
+
+/// Read an editor's user settings.json (JSONC is accepted)
+pub fn read_editor_settings(folder: &str) -> Result<serde_json::Value, String> {
+    let settings_path = get_editor_settings_path(folder)
+        .ok_or_else(|| "Failed to get editor config directory".to_string())?;
+    if !settings_path.exists() {
+        return Ok(serde_json::json!({}));
+    }
+    let content = std::fs::read_to_string(&settings_path)
+        .map_err(|e| format!("Failed to read settings.json: {}", e))?;
+    if content.trim().is_empty() {
+        return Ok(serde_json::json!({}));
+    }
+    json5::from_str(&content).map_err(|e| format!("Failed to parse settings.json: {}", e))
+}
+
+/// Set a key in an editor's user settings.json (JSONC is accepted, written back as JSON)
+pub fn set_editor_setting(folder: &str, key: &str, value: serde_json::Value) -> Result<(), String> {
+    let settings_path = get_editor_settings_path(folder)
+        .ok_or_else(|| "Failed to get editor config directory".to_string())?;
+
+    let mut settings = read_editor_settings(folder)?;
+
+    let obj = settings
+        .as_object_mut()
+        .ok_or_else(|| "Invalid settings.json format".to_string())?;
+    obj.insert(key.to_string(), value);
+
+    if let Some(parent) = settings_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create settings directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(&settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    change_history::write_config("vscode", &settings_path, content)
+        .map_err(|e| format!("Failed to write settings.json: {}", e))
+}
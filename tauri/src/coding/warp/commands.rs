@@ -0,0 +1,220 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use chrono::Local;
+use serde_json::{Map, Value};
+
+use crate::coding::change_history;
+use crate::coding::cli_detect;
+use crate::coding::open_code::get_favorite_provider_internal;
+use crate::coding::vault;
+use crate::db::DbState;
+use super::types::*;
+
+/// Warp BYOK only talks to the official endpoints: (npm package, key type, official host)
+const WARP_KEY_TYPES: [(&str, &str, &str); 3] = [
+    ("@ai-sdk/anthropic", "anthropic", "api.anthropic.com"),
+    ("@ai-sdk/openai", "openai", "api.openai.com"),
+    ("@ai-sdk/google", "google", "generativelanguage.googleapis.com"),
+];
+
+/// Preference keys considered AI settings
+const AI_PREF_MARKERS: [&str; 3] = ["AI", "Agent", "Autonomy"];
+
+// ============================================================================
+// Warp Path Helpers
+// ============================================================================
+
+/// Locate the Warp application
+fn find_warp_app() -> Option<PathBuf> {
+    if cfg!(target_os = "macos") {
+        let mut candidates = vec![PathBuf::from("/Applications/Warp.app")];
+        if let Some(home) = dirs::home_dir() {
+            candidates.push(home.join("Applications").join("Warp.app"));
+        }
+        return candidates.into_iter().find(|p| p.exists());
+    }
+
+    if cfg!(target_os = "windows") {
+        return dirs::data_local_dir()
+            .map(|dir| dir.join("Programs").join("Warp").join("warp.exe"))
+            .filter(|p| p.exists());
+    }
+
+    cli_detect::find_binary(&["warp-terminal", "warp-terminal-preview"])
+}
+
+/// Warp keeps preferences in a JSON file on Linux only
+/// (macOS uses user defaults, Windows uses the registry)
+fn get_warp_prefs_file() -> Option<PathBuf> {
+    if cfg!(target_os = "linux") {
+        dirs::config_dir().map(|dir| dir.join("warp-terminal").join("user_preferences.json"))
+    } else {
+        None
+    }
+}
+
+fn read_prefs_value(path: &Path) -> Result<Value, String> {
+    if !path.exists() {
+        return Ok(serde_json::json!({ "prefs": {} }));
+    }
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read Warp preferences: {}", e))?;
+    if content.trim().is_empty() {
+        return Ok(serde_json::json!({ "prefs": {} }));
+    }
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse Warp preferences: {}", e))
+}
+
+fn ai_settings(prefs: &Value) -> Map<String, Value> {
+    prefs
+        .get("prefs")
+        .and_then(|p| p.as_object())
+        .map(|p| {
+            p.iter()
+                .filter(|(key, _)| AI_PREF_MARKERS.iter().any(|m| key.contains(m)))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// ============================================================================
+// Warp Commands
+// ============================================================================
+
+/// Detect Warp, its AI preferences and the provider applied from the toolbox
+#[tauri::command]
+pub async fn get_warp_status(state: tauri::State<'_, DbState>) -> Result<WarpStatus, String> {
+    let app_path = find_warp_app();
+    let prefs_path = get_warp_prefs_file();
+    let settings = prefs_path
+        .as_ref()
+        .and_then(|p| read_prefs_value(p).ok())
+        .map(|prefs| ai_settings(&prefs))
+        .unwrap_or_default();
+
+    let db = state.0.lock().await;
+    let records_result: Result<Vec<Value>, _> = db
+        .query("SELECT * OMIT id FROM warp_applied_provider:`current` LIMIT 1")
+        .await
+        .map_err(|e| format!("Failed to query Warp applied provider: {}", e))?
+        .take(0);
+    let applied = records_result
+        .ok()
+        .and_then(|records| records.into_iter().next())
+        .and_then(|record| serde_json::from_value::<WarpAppliedProvider>(record).ok());
+
+    Ok(WarpStatus {
+        installed: app_path.is_some(),
+        app_path: app_path.map(|p| p.to_string_lossy().to_string()),
+        prefs_exists: prefs_path.as_ref().is_some_and(|p| p.exists()),
+        prefs_path: prefs_path.map(|p| p.to_string_lossy().to_string()),
+        ai_settings: settings,
+        applied,
+    })
+}
+
+/// Set a Warp preference in user_preferences.json (Linux)
+///
+/// Warp must be closed, otherwise it overwrites the file on exit. With `dry_run`
+/// the diff is returned instead of written.
+#[tauri::command]
+pub fn set_warp_preference(
+    key: String,
+    value: Value,
+    dry_run: Option<bool>,
+) -> Result<Vec<change_history::ConfigDiff>, String> {
+    let prefs_path = get_warp_prefs_file()
+        .ok_or_else(|| "Warp preferences are not file-based on this platform".to_string())?;
+
+    let mut prefs = read_prefs_value(&prefs_path)?;
+    let root = prefs
+        .as_object_mut()
+        .ok_or_else(|| "Invalid Warp preferences format".to_string())?;
+    let entries = root
+        .entry("prefs".to_string())
+        .or_insert_with(|| serde_json::json!({}));
+    if !entries.is_object() {
+        *entries = serde_json::json!({});
+    }
+    // Warp stores every preference value as a JSON-encoded string
+    let encoded = match value {
+        Value::String(s) => s,
+        other => other.to_string(),
+    };
+    entries
+        .as_object_mut()
+        .unwrap()
+        .insert(key, Value::String(encoded));
+
+    if let Some(parent) = prefs_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create Warp config directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(&prefs)
+        .map_err(|e| format!("Failed to serialize Warp preferences: {}", e))?;
+    change_history::preview_sync(dry_run.unwrap_or(false), || {
+        change_history::write_config("warp", &prefs_path, content)
+            .map_err(|e| format!("Failed to write Warp preferences: {}", e))
+    })
+}
+
+/// Apply a toolbox provider to Warp's bring-your-own-key settings
+///
+/// Warp stores BYOK keys in the system keychain and only accepts official
+/// Anthropic/OpenAI/Google endpoints, so the key is returned for the user to paste
+/// into Settings > AI, and the provider is recorded as applied.
+#[tauri::command]
+pub async fn apply_warp_provider(
+    state: tauri::State<'_, DbState>,
+    provider_id: String,
+) -> Result<WarpApplyResult, String> {
+    let db = state.0.lock().await;
+    let provider = get_favorite_provider_internal(&db, &provider_id).await?;
+
+    let (_, key_type, host) = WARP_KEY_TYPES
+        .iter()
+        .find(|(npm, _, _)| *npm == provider.npm)
+        .ok_or_else(|| "Warp only supports Anthropic, OpenAI and Google API keys".to_string())?;
+
+    let options = provider.provider_config.options.as_ref();
+    if let Some(base_url) = options.and_then(|o| o.base_url.as_ref()).filter(|s| !s.is_empty()) {
+        if !base_url.contains(host) {
+            return Err("Warp does not support custom endpoints".to_string());
+        }
+    }
+    let api_key = options
+        .and_then(|o| o.api_key.clone())
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| format!("Provider '{}' has no API key", provider_id))?;
+    let mut api_key = Value::String(api_key);
+    vault::resolve_references(&mut api_key)?;
+    let api_key = api_key.as_str().unwrap_or_default().to_string();
+
+    let applied = WarpAppliedProvider {
+        provider_id: provider_id.clone(),
+        key_type: key_type.to_string(),
+        applied_at: Local::now().to_rfc3339(),
+    };
+    let data = serde_json::to_value(&applied)
+        .map_err(|e| format!("Failed to serialize applied provider: {}", e))?;
+    db.query("UPSERT warp_applied_provider:`current` CONTENT $data")
+        .bind(("data", data))
+        .await
+        .map_err(|e| format!("Failed to save Warp applied provider: {}", e))?;
+
+    Ok(WarpApplyResult {
+        key_type: key_type.to_string(),
+        api_key,
+    })
+}
+
+/// Clear the provider recorded as applied to Warp
+#[tauri::command]
+pub async fn clear_warp_applied_provider(state: tauri::State<'_, DbState>) -> Result<(), String> {
+    let db = state.0.lock().await;
+    db.query("DELETE warp_applied_provider:`current`")
+        .await
+        .map_err(|e| format!("Failed to clear Warp applied provider: {}", e))?;
+    Ok(())
+}
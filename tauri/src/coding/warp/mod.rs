@@ -0,0 +1,5 @@
+pub mod commands;
+pub mod types;
+
+pub use commands::*;
+pub use types::*;
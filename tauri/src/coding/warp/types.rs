@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+// ============================================================================
+// Warp Types
+// ============================================================================
+
+/// Warp detection result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WarpStatus {
+    pub installed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_path: Option<String>,
+    /// user_preferences.json path (None where Warp stores preferences natively)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefs_path: Option<String>,
+    pub prefs_exists: bool,
+    /// AI-related preferences read from user_preferences.json
+    pub ai_settings: Map<String, Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub applied: Option<WarpAppliedProvider>,
+}
+
+/// Provider last applied to Warp from the toolbox
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WarpAppliedProvider {
+    pub provider_id: String,
+    /// Warp BYOK provider: "anthropic" | "openai" | "google"
+    pub key_type: String,
+    pub applied_at: String,
+}
+
+/// Data returned when applying a provider to Warp
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WarpApplyResult {
+    pub key_type: String,
+    pub api_key: String,
+}
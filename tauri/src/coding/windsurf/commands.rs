@@ -0,0 +1,341 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde_json::Value;
+
+use crate::coding::change_history;
+use crate::coding::cli_detect;
+use crate::coding::provider_store::{self, ProviderStore};
+use crate::coding::vscode;
+use crate::db::DbState;
+use crate::error::AppResult;
+use super::types::*;
+
+/// Windsurf user data folder name (under the platform config directory)
+const WINDSURF_FOLDER: &str = "Windsurf";
+
+/// Windsurf providers and common config
+pub const WINDSURF: ProviderStore = ProviderStore {
+    display_name: "Windsurf",
+    provider_table: "windsurf_provider",
+    common_table: Some("windsurf_common_config"),
+};
+
+// ============================================================================
+// Windsurf Config Path Commands
+// ============================================================================
+
+/// Get Windsurf data directory (~/.codeium/windsurf/)
+fn get_windsurf_data_dir() -> Result<PathBuf, String> {
+    let home_dir = std::env::var("USERPROFILE")
+        .or_else(|_| std::env::var("HOME"))
+        .map_err(|_| "Failed to get home directory".to_string())?;
+
+    Ok(Path::new(&home_dir).join(".codeium").join("windsurf"))
+}
+
+/// Get Windsurf user settings.json path
+fn get_windsurf_settings_path() -> Result<PathBuf, String> {
+    vscode::get_editor_settings_path(WINDSURF_FOLDER)
+        .ok_or_else(|| "Failed to get config directory".to_string())
+}
+
+/// Get Windsurf MCP config path (~/.codeium/windsurf/mcp_config.json)
+fn get_windsurf_mcp_config_path() -> Result<PathBuf, String> {
+    Ok(get_windsurf_data_dir()?.join("mcp_config.json"))
+}
+
+/// Get Windsurf settings.json file path
+#[tauri::command]
+pub fn get_windsurf_config_path() -> Result<String, String> {
+    Ok(get_windsurf_settings_path()?.to_string_lossy().to_string())
+}
+
+/// Detect Windsurf installation
+#[tauri::command]
+pub fn get_windsurf_status() -> Result<WindsurfStatus, String> {
+    let settings_path = get_windsurf_settings_path()?;
+    let mcp_config_path = get_windsurf_mcp_config_path()?;
+    let binary = cli_detect::detect_cli(&["windsurf"]);
+    let installed = binary.is_some()
+        || get_windsurf_data_dir()?.exists()
+        || settings_path.parent().map(|p| p.exists()).unwrap_or(false);
+
+    Ok(WindsurfStatus {
+        binary,
+        installed,
+        settings_exists: settings_path.exists(),
+        settings_path: settings_path.to_string_lossy().to_string(),
+        mcp_config_exists: mcp_config_path.exists(),
+        mcp_config_path: mcp_config_path.to_string_lossy().to_string(),
+    })
+}
+
+// ============================================================================
+// Windsurf Provider Commands
+// ============================================================================
+
+/// List all Windsurf providers ordered by sort_index
+#[tauri::command]
+pub async fn list_windsurf_providers(
+    state: tauri::State<'_, DbState>,
+) -> AppResult<Vec<WindsurfProvider>> {
+    let db = state.0.lock().await;
+    provider_store::list_providers(&db, &WINDSURF).await
+}
+
+/// Create a new Windsurf provider
+#[tauri::command]
+pub async fn create_windsurf_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    provider: WindsurfProviderInput,
+) -> AppResult<WindsurfProvider> {
+    let db = state.0.lock().await;
+    provider_store::create_provider(&db, &app, &WINDSURF, provider).await
+}
+
+/// Update an existing Windsurf provider
+#[tauri::command]
+pub async fn update_windsurf_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    provider: WindsurfProvider,
+) -> AppResult<WindsurfProvider> {
+    let db = state.0.lock().await;
+    let provider = provider_store::update_provider(&db, &app, &WINDSURF, provider).await?;
+
+    // Re-apply to config files if this provider is currently applied
+    if provider.is_applied {
+        if let Err(e) = apply_config_to_file(&db, &provider.id).await {
+            log::warn!("Failed to auto-apply updated Windsurf config: {}", e);
+        }
+    }
+    Ok(provider)
+}
+
+/// Delete a Windsurf provider
+#[tauri::command]
+pub async fn delete_windsurf_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+    provider_store::delete_provider(&db, &app, &WINDSURF, &id).await
+}
+
+/// Reorder Windsurf providers
+#[tauri::command]
+pub async fn reorder_windsurf_providers(
+    state: tauri::State<'_, DbState>,
+    ids: Vec<String>,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+    provider_store::reorder_providers(&db, &WINDSURF, &ids).await
+}
+
+/// Select a Windsurf provider (mark as applied in database, but not write to file)
+#[tauri::command]
+pub async fn select_windsurf_provider(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    id: String,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+    provider_store::select_provider(&db, &app, &WINDSURF, &id).await
+}
+
+/// Toggle is_disabled status for a provider
+#[tauri::command]
+pub async fn toggle_windsurf_provider_disabled(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    provider_id: String,
+    is_disabled: bool,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+    provider_store::toggle_provider_disabled(&db, &app, &WINDSURF, &provider_id, is_disabled).await
+}
+
+// ============================================================================
+// Windsurf Config File Commands
+// ============================================================================
+
+/// Read Windsurf user settings.json (JSONC accepted)
+fn read_settings_value() -> Result<Value, String> {
+    let settings_path = get_windsurf_settings_path()?;
+    if !settings_path.exists() {
+        return Ok(serde_json::json!({}));
+    }
+    let content = fs::read_to_string(&settings_path)
+        .map_err(|e| format!("Failed to read settings.json: {}", e))?;
+    if content.trim().is_empty() {
+        return Ok(serde_json::json!({}));
+    }
+    json5::from_str(&content).map_err(|e| format!("Failed to parse settings.json: {}", e))
+}
+
+/// Read current Windsurf user settings.json
+#[tauri::command]
+pub async fn read_windsurf_settings() -> Result<Value, String> {
+    read_settings_value()
+}
+
+/// Get the mcpServers section of ~/.codeium/windsurf/mcp_config.json
+#[tauri::command]
+pub async fn get_windsurf_mcp_servers() -> Result<Value, String> {
+    let mcp_path = get_windsurf_mcp_config_path()?;
+    if !mcp_path.exists() {
+        return Ok(serde_json::json!({}));
+    }
+    let content = fs::read_to_string(&mcp_path)
+        .map_err(|e| format!("Failed to read mcp_config.json: {}", e))?;
+    let config: Value = json5::from_str(&content)
+        .map_err(|e| format!("Failed to parse mcp_config.json: {}", e))?;
+    Ok(config
+        .get("mcpServers")
+        .cloned()
+        .unwrap_or_else(|| serde_json::json!({})))
+}
+
+/// Extract the `settings` object from a provider/common settings_config string
+fn settings_section(settings_config: &str) -> Result<serde_json::Map<String, Value>, String> {
+    if settings_config.trim().is_empty() {
+        return Ok(serde_json::Map::new());
+    }
+    let value: Value = serde_json::from_str(settings_config)
+        .map_err(|e| format!("Failed to parse settings config: {}", e))?;
+    Ok(value
+        .get("settings")
+        .and_then(|v| v.as_object())
+        .cloned()
+        .unwrap_or_default())
+}
+
+/// Build the settings.json content for a provider without writing it
+///
+/// Keys set by any Windsurf provider are treated as managed: they are removed
+/// first, then the common config and the selected provider's keys are written.
+async fn build_settings_content(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    provider_id: &str,
+) -> Result<String, String> {
+    let provider = provider_store::get_enabled_provider(db, &WINDSURF, provider_id).await?;
+    let providers = provider_store::list_providers(db, &WINDSURF).await?;
+    let common_config = provider_store::common_config_text(db, &WINDSURF).await?;
+    let common_settings = settings_section(&common_config)?;
+
+    let mut settings = read_settings_value()?;
+    let obj = settings
+        .as_object_mut()
+        .ok_or_else(|| "Invalid settings.json format".to_string())?;
+
+    for other in &providers {
+        if let Ok(section) = settings_section(&other.settings_config) {
+            for key in section.keys() {
+                obj.remove(key);
+            }
+        }
+    }
+    for (key, value) in common_settings {
+        obj.insert(key, value);
+    }
+    for (key, value) in settings_section(&provider.settings_config)? {
+        obj.insert(key, value);
+    }
+
+    serde_json::to_string_pretty(&settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))
+}
+
+/// Internal function: apply provider settings to Windsurf settings.json
+async fn apply_config_to_file(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    provider_id: &str,
+) -> Result<(), String> {
+    let content = build_settings_content(db, provider_id).await?;
+    let settings_path = get_windsurf_settings_path()?;
+
+    change_history::write_config("windsurf", &settings_path, content)
+        .map_err(|e| format!("Failed to write settings.json: {}", e))
+}
+
+/// Preview the changes applying a provider would make (diff source for the frontend)
+#[tauri::command]
+pub async fn preview_windsurf_config(
+    state: tauri::State<'_, DbState>,
+    provider_id: String,
+) -> Result<WindsurfApplyPreview, String> {
+    let db = state.0.lock().await;
+    let new_settings = build_settings_content(&db, &provider_id).await?;
+
+    let settings_path = get_windsurf_settings_path()?;
+    let current_settings = fs::read_to_string(&settings_path).unwrap_or_default();
+
+    Ok(WindsurfApplyPreview {
+        settings_path: settings_path.to_string_lossy().to_string(),
+        has_changes: current_settings.trim() != new_settings.trim(),
+        current_settings,
+        new_settings,
+    })
+}
+
+/// Apply Windsurf provider settings
+/// `dry_run` returns the diff of the files instead of writing them.
+#[tauri::command]
+pub async fn apply_windsurf_config(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    provider_id: String,
+    dry_run: Option<bool>,
+) -> Result<Vec<change_history::ConfigDiff>, String> {
+    let db = state.0.lock().await;
+    if dry_run.unwrap_or(false) {
+        return change_history::preview(true, apply_config_to_file(&db, &provider_id)).await;
+    }
+    apply_config_internal(&db, &app, &provider_id, false).await?;
+    Ok(Vec::new())
+}
+
+/// Internal function to apply config: writes files and updates database
+pub async fn apply_config_internal<R: tauri::Runtime>(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    app: &tauri::AppHandle<R>,
+    provider_id: &str,
+    from_tray: bool,
+) -> Result<(), String> {
+    apply_config_to_file(db, provider_id).await?;
+    Ok(provider_store::mark_applied(db, app, &WINDSURF, provider_id, from_tray).await?)
+}
+
+// ============================================================================
+// Windsurf Common Config Commands
+// ============================================================================
+
+/// Get Windsurf common config
+#[tauri::command]
+pub async fn get_windsurf_common_config(
+    state: tauri::State<'_, DbState>,
+) -> AppResult<Option<WindsurfCommonConfig>> {
+    let db = state.0.lock().await;
+    provider_store::get_common_config(&db, &WINDSURF).await
+}
+
+/// Save Windsurf common config
+#[tauri::command]
+pub async fn save_windsurf_common_config(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    config: String,
+) -> AppResult<()> {
+    let db = state.0.lock().await;
+    provider_store::save_common_config(&db, &app, &WINDSURF, &config).await?;
+
+    // Re-apply current provider so the merged config reaches the files
+    if let Ok(Some(provider)) = provider_store::applied_provider(&db, &WINDSURF).await {
+        if let Err(e) = apply_config_to_file(&db, &provider.id).await {
+            log::warn!("Failed to re-apply Windsurf config: {}", e);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+
+use crate::coding::provider_store::{StoredCommonConfig, StoredProvider, StoredProviderInput};
+
+// ============================================================================
+// Windsurf Provider Types
+// ============================================================================
+
+/// WindsurfProvider - API response
+///
+/// `settings_config` is a JSON string: `{ "settings": { ... } }`
+/// where `settings` holds Windsurf user settings.json keys (BYO-key / proxy related)
+pub type WindsurfProvider = StoredProvider;
+
+/// WindsurfProvider - Input from frontend (for create operation)
+pub type WindsurfProviderInput = StoredProviderInput;
+
+// ============================================================================
+// Windsurf Common Config Types
+// ============================================================================
+
+/// WindsurfCommonConfig - API response
+pub type WindsurfCommonConfig = StoredCommonConfig;
+
+/// Windsurf detection result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindsurfStatus {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub binary: Option<crate::coding::cli_detect::CliBinaryInfo>,
+    pub installed: bool,
+    /// Windsurf user settings.json
+    pub settings_path: String,
+    pub settings_exists: bool,
+    /// ~/.codeium/windsurf/mcp_config.json
+    pub mcp_config_path: String,
+    pub mcp_config_exists: bool,
+}
+
+/// Preview of what applying a provider would write (current vs new)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindsurfApplyPreview {
+    pub settings_path: String,
+    pub current_settings: String,
+    pub new_settings: String,
+    pub has_changes: bool,
+}
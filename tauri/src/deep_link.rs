@@ -0,0 +1,121 @@
+//! Deep Link Module
+//!
+//! Handles `aitoolbox://` links so relay websites can offer one-click setup.
+//! Links never change configs directly: the provider is staged and the UI asks
+//! the user to confirm (see `coding::provider_import`).
+//!
+//! Supported links:
+//! - `aitoolbox://import-provider?tool=claude&name=Relay&base_url=https://...&api_key=sk-...&model=...`
+//!   (`tool`: claude, codex or opencode; `api_key`, `model`, `npm`, `website_url` optional)
+//! - `aitoolbox://import-share?data=aitb1:...` (QR payload of a share string)
+
+use tauri::{AppHandle, Runtime, Url};
+use tauri_plugin_deep_link::DeepLinkExt;
+
+use crate::coding::provider_import::{self, ProviderImport};
+
+pub const SCHEME: &str = "aitoolbox";
+
+/// Parse an `import-provider` link
+fn parse_import_provider(url: &Url) -> Result<ProviderImport, String> {
+    let param = |key: &str| {
+        url.query_pairs()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.to_string())
+            .filter(|v| !v.is_empty())
+    };
+    let required = |key: &str| param(key).ok_or_else(|| format!("Missing parameter: {}", key));
+
+    Ok(ProviderImport {
+        tool: required("tool")?,
+        name: required("name")?,
+        base_url: required("base_url")?,
+        api_key: param("api_key").unwrap_or_default(),
+        model: param("model"),
+        npm: param("npm"),
+        website_url: param("website_url"),
+    })
+}
+
+/// Handle one deep link URL
+pub fn handle_url<R: Runtime>(app: &AppHandle<R>, url: &Url) -> Result<(), String> {
+    if url.scheme() != SCHEME {
+        return Err(format!("Unsupported scheme: {}", url.scheme()));
+    }
+    // `aitoolbox://import-provider?...` puts the action in the host part
+    let action = url
+        .host_str()
+        .unwrap_or_else(|| url.path().trim_start_matches('/'));
+
+    match action {
+        "import-provider" => {
+            let provider = parse_import_provider(url)?;
+            provider_import::stage_import(app, "deep_link", provider)?;
+            Ok(())
+        }
+        "import-share" => {
+            let data = url
+                .query_pairs()
+                .find(|(k, _)| k == "data")
+                .map(|(_, v)| v.to_string())
+                .ok_or_else(|| "Missing parameter: data".to_string())?;
+            let provider = provider_import::share::decode_share(&data)?;
+            provider_import::stage_import(app, "share", provider)?;
+            Ok(())
+        }
+        other => Err(format!("Unsupported deep link action: {}", other)),
+    }
+}
+
+fn handle_urls<R: Runtime>(app: &AppHandle<R>, urls: &[Url]) {
+    for url in urls {
+        // Never log the full URL: it may carry an API key
+        if let Err(e) = handle_url(app, url) {
+            log::warn!("Ignored deep link ({}://{}): {}", url.scheme(), url.host_str().unwrap_or(""), e);
+        }
+    }
+}
+
+/// Register the scheme and listen for links (called from setup)
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+    // Installers register the scheme on macOS and Windows; register at runtime
+    // too so dev builds and Linux AppImages work.
+    #[cfg(any(target_os = "linux", target_os = "windows"))]
+    if let Err(e) = app.deep_link().register_all() {
+        log::warn!("Failed to register deep link scheme: {}", e);
+    }
+
+    // Link that launched the app
+    if let Ok(Some(urls)) = app.deep_link().get_current() {
+        handle_urls(app, &urls);
+    }
+
+    let handle = app.clone();
+    app.deep_link().on_open_url(move |event| {
+        handle_urls(&handle, &event.urls());
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_import_provider_link() {
+        let url = Url::parse(
+            "aitoolbox://import-provider?tool=claude&name=My%20Relay&base_url=https%3A%2F%2Frelay.example.com&model=",
+        )
+        .unwrap();
+        let provider = parse_import_provider(&url).unwrap();
+        assert_eq!(provider.name, "My Relay");
+        assert_eq!(provider.base_url, "https://relay.example.com");
+        assert_eq!(provider.api_key, "");
+        assert_eq!(provider.model, None);
+    }
+
+    #[test]
+    fn rejects_link_without_name() {
+        let url = Url::parse("aitoolbox://import-provider?tool=claude&base_url=https://x").unwrap();
+        assert!(parse_import_provider(&url).is_err());
+    }
+}
@@ -0,0 +1,121 @@
+//! Structured Command Errors
+//!
+//! `AppError` tells the frontend what kind of failure happened so it can
+//! branch on it (e.g. offer "create instead?" on `not_found`) instead of
+//! parsing prose. Every variant carries a localized message from the
+//! `i18n` catalog. Serialized as `{ kind, code, params, message }`.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{self, LocalizedError};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AppError {
+    /// The requested record, file or program does not exist
+    NotFound(LocalizedError),
+    /// The operation clashes with existing state (e.g. still referenced)
+    Conflict(LocalizedError),
+    /// File system, process or keychain failure
+    Io(LocalizedError),
+    /// Database query failure
+    Db(LocalizedError),
+    /// Network request failure
+    Http(LocalizedError),
+    /// Input rejected before doing anything
+    Validation(LocalizedError),
+}
+
+pub type AppResult<T> = Result<T, AppError>;
+
+impl AppError {
+    pub fn not_found(code: &str, params: &[(&str, &str)]) -> Self {
+        Self::NotFound(i18n::error(code, params))
+    }
+
+    pub fn conflict(code: &str, params: &[(&str, &str)]) -> Self {
+        Self::Conflict(i18n::error(code, params))
+    }
+
+    pub fn validation(code: &str, params: &[(&str, &str)]) -> Self {
+        Self::Validation(i18n::error(code, params))
+    }
+
+    /// Validation error from a free-form detail (e.g. a parser message)
+    pub fn invalid(detail: impl fmt::Display) -> Self {
+        Self::Validation(i18n::error("INVALID_INPUT", &[("detail", &detail.to_string())]))
+    }
+
+    pub fn io(detail: impl fmt::Display) -> Self {
+        Self::Io(i18n::error("IO_ERROR", &[("detail", &detail.to_string())]))
+    }
+
+    pub fn db(detail: impl fmt::Display) -> Self {
+        Self::Db(i18n::error("DB_ERROR", &[("detail", &detail.to_string())]))
+    }
+
+    pub fn http(detail: impl fmt::Display) -> Self {
+        Self::Http(i18n::error("HTTP_ERROR", &[("detail", &detail.to_string())]))
+    }
+
+    pub fn detail(&self) -> &LocalizedError {
+        match self {
+            Self::NotFound(detail)
+            | Self::Conflict(detail)
+            | Self::Io(detail)
+            | Self::Db(detail)
+            | Self::Http(detail)
+            | Self::Validation(detail) => detail,
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.detail().message)
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl From<surrealdb::Error> for AppError {
+    fn from(e: surrealdb::Error) -> Self {
+        Self::db(e)
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(e: std::io::Error) -> Self {
+        Self::io(e)
+    }
+}
+
+impl From<reqwest::Error> for AppError {
+    fn from(e: reqwest::Error) -> Self {
+        Self::http(e)
+    }
+}
+
+/// For callers still returning `Result<_, String>`: keeps the structure as JSON
+impl From<AppError> for String {
+    fn from(error: AppError) -> Self {
+        serde_json::to_string(&error).unwrap_or_else(|_| error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_kind_with_localized_fields() {
+        let error = AppError::not_found("PROVIDER_NOT_FOUND", &[("id", "abc")]);
+        let value = serde_json::to_value(&error).unwrap();
+        assert_eq!(value["kind"], "not_found");
+        assert_eq!(value["code"], "PROVIDER_NOT_FOUND");
+        assert_eq!(value["params"]["id"], "abc");
+        assert!(value["message"].as_str().unwrap().contains("abc"));
+    }
+}
@@ -0,0 +1,89 @@
+//! Change Events Module
+//!
+//! Every mutating command reports what changed through `emit_change`, which
+//! emits a typed `entity-changed` event so the frontend can refresh just the
+//! affected list. It also emits the legacy `config-changed` event, which
+//! rebuilds the tray menu (see lib.rs) and reloads the UI on tray changes.
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Runtime};
+
+/// Typed change event name
+pub const ENTITY_CHANGED_EVENT: &str = "entity-changed";
+
+/// What happened to the entity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeAction {
+    Created,
+    Updated,
+    Deleted,
+    /// Written to the tool's config files (provider selected, config applied)
+    Applied,
+    Imported,
+    /// Pushed to other tools (e.g. MCP servers)
+    Synced,
+}
+
+impl ChangeAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::Updated => "updated",
+            Self::Deleted => "deleted",
+            Self::Applied => "applied",
+            Self::Imported => "imported",
+            Self::Synced => "synced",
+        }
+    }
+}
+
+/// Payload of `entity-changed`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeEvent {
+    /// Changed entity, named after its table, e.g. "claude_provider", "settings"
+    pub entity: String,
+    pub action: ChangeAction,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// "window" or "tray"
+    pub source: String,
+}
+
+/// Emit a change event for a mutation
+pub fn emit_change<R: Runtime>(
+    app: &AppHandle<R>,
+    entity: &str,
+    action: ChangeAction,
+    id: Option<&str>,
+    source: &str,
+) {
+    crate::telemetry::track(&format!("{}.{}", entity, action.as_str()));
+
+    let event = ChangeEvent {
+        entity: entity.to_string(),
+        action,
+        id: id.map(String::from),
+        source: source.to_string(),
+    };
+    let _ = app.emit(ENTITY_CHANGED_EVENT, &event);
+    let _ = app.emit("config-changed", source);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn change_event_serializes_snake_case() {
+        let event = ChangeEvent {
+            entity: "claude_provider".to_string(),
+            action: ChangeAction::Applied,
+            id: None,
+            source: "tray".to_string(),
+        };
+        let value = serde_json::to_value(&event).unwrap();
+        assert_eq!(value["action"], "applied");
+        assert!(value.get("id").is_none());
+    }
+}
@@ -10,20 +10,28 @@
 //! use crate::http_client;
 //! use crate::db::DbState;
 //!
-//! // Create client with automatic proxy configuration (30s timeout)
+//! // Create client with automatic proxy configuration (default timeout from settings)
 //! let client = http_client::client(&state).await?;
 //!
-//! // Create client with custom timeout
+//! // Create client with custom timeout (0 uses the configured default)
 //! let client = http_client::client_with_timeout(&state, 60).await?;
 //!
+//! // Go through a specific proxy instead of the configured one
+//! let client = http_client::client_with_proxy(&state, Some("socks5://127.0.0.1:1080"), 60).await?;
+//!
 //! // Bypass proxy (special cases only)
 //! let client = http_client::client_no_proxy(30)?;
+//!
+//! // Apply mirror host overrides to a URL before requesting it
+//! let url = http_client::resolve_url(&state, "https://models.dev/api.json").await;
 //! ```
 
 use reqwest::{Client, Proxy};
+use std::net::{IpAddr, SocketAddr};
 use std::time::Duration;
 
 use crate::db::DbState;
+use crate::settings::{HostOverride, HttpClientConfig};
 
 /// Create an HTTP client with automatic proxy configuration.
 ///
@@ -34,7 +42,7 @@ use crate::db::DbState;
 /// * `db_state` - Database state to read proxy settings from
 ///
 /// # Returns
-/// A configured reqwest::Client with 30 second timeout
+/// A configured reqwest::Client using the default request timeout from settings (30s unless changed)
 ///
 /// # Example
 /// ```rust
@@ -42,14 +50,17 @@ use crate::db::DbState;
 /// let response = client.get("https://api.example.com").send().await?;
 /// ```
 pub async fn client(db_state: &DbState) -> Result<Client, String> {
-    client_with_timeout(db_state, 30).await
+    client_with_timeout(db_state, 0).await
 }
 
 /// Create an HTTP client with custom timeout.
 ///
+/// An explicit `timeout_secs` is used as is; 0 falls back to the configured
+/// default request timeout.
+///
 /// # Arguments
 /// * `db_state` - Database state to read proxy settings from
-/// * `timeout_secs` - Request timeout in seconds
+/// * `timeout_secs` - Request timeout in seconds, 0 for the configured default
 ///
 /// # Returns
 /// A configured reqwest::Client
@@ -62,8 +73,41 @@ pub async fn client_with_timeout(
     db_state: &DbState,
     timeout_secs: u64,
 ) -> Result<Client, String> {
-    let proxy_url = get_proxy_from_settings(db_state).await?;
-    build_client(&proxy_url, timeout_secs)
+    let settings = get_client_settings(db_state).await?;
+    let timeout_secs = effective_timeout(timeout_secs, &settings.http);
+    build_client_with_config(
+        &settings.proxy_url,
+        timeout_secs,
+        &settings.host_overrides,
+        &settings.http,
+    )
+}
+
+/// Create an HTTP client that goes through `proxy_url` instead of the
+/// configured proxy, e.g. a provider that is only reachable via its own proxy.
+///
+/// Timeout, host overrides and pool settings still come from settings;
+/// `None` or an empty URL behaves like `client_with_timeout()`.
+pub async fn client_with_proxy(
+    db_state: &DbState,
+    proxy_url: Option<&str>,
+    timeout_secs: u64,
+) -> Result<Client, String> {
+    let settings = get_client_settings(db_state).await?;
+    let timeout_secs = effective_timeout(timeout_secs, &settings.http);
+    let proxy_url = proxy_url
+        .filter(|url| !url.trim().is_empty())
+        .unwrap_or(settings.proxy_url.as_str());
+    build_client_with_config(proxy_url, timeout_secs, &settings.host_overrides, &settings.http)
+}
+
+/// `timeout_secs`, or the configured default when it is 0
+fn effective_timeout(timeout_secs: u64, http_config: &HttpClientConfig) -> u64 {
+    if timeout_secs == 0 {
+        http_config.request_timeout_secs
+    } else {
+        timeout_secs
+    }
 }
 
 /// Build an HTTP client with explicit proxy URL.
@@ -83,7 +127,38 @@ pub async fn client_with_timeout(
 /// 2. System proxy (Windows/macOS) or environment variables (Linux)
 /// 3. Direct connection (if no proxy available)
 fn build_client(proxy_url: &str, timeout_secs: u64) -> Result<Client, String> {
-    let mut builder = Client::builder().timeout(Duration::from_secs(timeout_secs));
+    build_client_with_config(proxy_url, timeout_secs, &[], &HttpClientConfig::default())
+}
+
+/// Build an HTTP client with explicit proxy URL, host overrides and pool settings.
+///
+/// Overrides whose target is an IP address are registered as DNS overrides,
+/// so the original host name is still used for TLS/SNI and the Host header.
+/// Mirror host overrides are handled by `resolve_url()` instead.
+fn build_client_with_config(
+    proxy_url: &str,
+    timeout_secs: u64,
+    host_overrides: &[HostOverride],
+    http_config: &HttpClientConfig,
+) -> Result<Client, String> {
+    let mut builder = Client::builder()
+        .timeout(Duration::from_secs(timeout_secs))
+        .connect_timeout(Duration::from_secs(http_config.connect_timeout_secs))
+        .pool_max_idle_per_host(http_config.pool_max_idle_per_host);
+
+    // 0 disables the idle timeout (keep pooled connections until the server closes them)
+    builder = if http_config.pool_idle_timeout_secs == 0 {
+        builder.pool_idle_timeout(None)
+    } else {
+        builder.pool_idle_timeout(Duration::from_secs(http_config.pool_idle_timeout_secs))
+    };
+
+    for item in host_overrides.iter().filter(|o| o.enabled) {
+        if let Ok(ip) = item.target.parse::<IpAddr>() {
+            // Port 0 means "use the conventional port for the URL scheme"
+            builder = builder.resolve(&item.host, SocketAddr::new(ip, 0));
+        }
+    }
 
     if !proxy_url.is_empty() {
         // User-configured proxy takes priority over system proxy
@@ -182,6 +257,106 @@ pub async fn get_proxy_from_settings(db_state: &DbState) -> Result<String, Strin
     }
 }
 
+/// Read all settings relevant to HTTP client construction in a single query.
+async fn get_client_settings(db_state: &DbState) -> Result<crate::settings::AppSettings, String> {
+    let db = db_state.0.lock().await;
+
+    let mut result = db
+        .query("SELECT * OMIT id FROM settings:`app` LIMIT 1")
+        .await
+        .map_err(|e| format!("Failed to query network settings: {}", e))?;
+
+    let records: Vec<serde_json::Value> = result
+        .take(0)
+        .map_err(|e| format!("Failed to parse network settings: {}", e))?;
+
+    Ok(records
+        .first()
+        .map(|record| crate::settings::adapter::from_db_value(record.clone()))
+        .unwrap_or_default())
+}
+
+/// Read host overrides from database settings.
+///
+/// Returns an empty list if none are configured.
+///
+/// # Arguments
+/// * `db_state` - Database state to read host overrides from
+pub async fn get_host_overrides_from_settings(
+    db_state: &DbState,
+) -> Result<Vec<HostOverride>, String> {
+    let db = db_state.0.lock().await;
+
+    let mut result = db
+        .query("SELECT host_overrides OMIT id FROM settings:`app` LIMIT 1")
+        .await
+        .map_err(|e| format!("Failed to query host overrides: {}", e))?;
+
+    let records: Vec<serde_json::Value> = result
+        .take(0)
+        .map_err(|e| format!("Failed to parse host overrides: {}", e))?;
+
+    Ok(records
+        .first()
+        .map(crate::settings::adapter::get_host_overrides)
+        .unwrap_or_default())
+}
+
+/// Apply mirror host overrides to a URL.
+///
+/// If an enabled override maps the URL's host to another host name (not an IP),
+/// the host is replaced; otherwise the URL is returned unchanged.
+///
+/// # Example
+/// ```rust
+/// let url = http_client::resolve_url(&state, "https://github.com/owner/repo").await;
+/// ```
+pub async fn resolve_url(db_state: &DbState, url: &str) -> String {
+    let host_overrides = get_host_overrides_from_settings(db_state)
+        .await
+        .unwrap_or_default();
+    rewrite_url_host(url, &host_overrides)
+}
+
+/// Rewrite the host part of a URL according to mirror host overrides.
+fn rewrite_url_host(url: &str, host_overrides: &[HostOverride]) -> String {
+    let Some(scheme_end) = url.find("://") else {
+        return url.to_string();
+    };
+    let authority_start = scheme_end + 3;
+    let rest = &url[authority_start..];
+    let authority_end = rest
+        .find(|c| c == '/' || c == '?' || c == '#')
+        .unwrap_or(rest.len());
+    let authority = &rest[..authority_end];
+
+    // Keep userinfo and port intact, only replace the host name
+    let (userinfo, host_port) = match authority.rfind('@') {
+        Some(pos) => (&authority[..=pos], &authority[pos + 1..]),
+        None => ("", authority),
+    };
+    let (host, port) = match host_port.rfind(':') {
+        Some(pos) if !host_port.starts_with('[') => (&host_port[..pos], &host_port[pos..]),
+        _ => (host_port, ""),
+    };
+
+    let mirror = host_overrides.iter().find(|o| {
+        o.enabled && o.host.eq_ignore_ascii_case(host) && o.target.parse::<IpAddr>().is_err()
+    });
+
+    match mirror {
+        Some(item) => format!(
+            "{}{}{}{}{}",
+            &url[..authority_start],
+            userinfo,
+            item.target,
+            port,
+            &rest[authority_end..]
+        ),
+        None => url.to_string(),
+    }
+}
+
 /// Build a reqwest::Proxy from URL string.
 ///
 /// Supports:
@@ -207,7 +382,7 @@ fn build_proxy(url: &str) -> Result<Option<Proxy>, String> {
 /// Normalize proxy URL by ensuring it has a scheme.
 ///
 /// If no scheme is provided, defaults to http://
-fn normalize_proxy_url(url: &str) -> String {
+pub(crate) fn normalize_proxy_url(url: &str) -> String {
     let url_lower = url.to_lowercase();
 
     if url_lower.starts_with("http://")
@@ -250,6 +425,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn explicit_timeouts_are_kept() {
+        let http_config = HttpClientConfig { request_timeout_secs: 30, ..HttpClientConfig::default() };
+        assert_eq!(effective_timeout(10, &http_config), 10);
+        assert_eq!(effective_timeout(120, &http_config), 120);
+        assert_eq!(effective_timeout(0, &http_config), 30);
+    }
+
+    fn host_override(host: &str, target: &str) -> HostOverride {
+        HostOverride {
+            host: host.to_string(),
+            target: target.to_string(),
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn test_rewrite_url_host_mirror() {
+        let overrides = vec![host_override("github.com", "mirror.example.com")];
+        assert_eq!(
+            rewrite_url_host("https://github.com/owner/repo", &overrides),
+            "https://mirror.example.com/owner/repo"
+        );
+        assert_eq!(
+            rewrite_url_host("https://user@GitHub.com:8443?x=1", &overrides),
+            "https://user@mirror.example.com:8443?x=1"
+        );
+    }
+
+    #[test]
+    fn test_rewrite_url_host_ignores_ip_and_disabled() {
+        let mut disabled = host_override("models.dev", "mirror.example.com");
+        disabled.enabled = false;
+        let overrides = vec![host_override("github.com", "1.2.3.4"), disabled];
+        assert_eq!(
+            rewrite_url_host("https://github.com/a", &overrides),
+            "https://github.com/a"
+        );
+        assert_eq!(
+            rewrite_url_host("https://models.dev/api.json", &overrides),
+            "https://models.dev/api.json"
+        );
+    }
+
+    #[test]
+    fn test_build_client_with_ip_override() {
+        let overrides = vec![host_override("models.dev", "1.2.3.4")];
+        assert!(
+            build_client_with_config("", 10, &overrides, &HttpClientConfig::default()).is_ok()
+        );
+    }
+
     #[test]
     fn test_build_proxy_empty() {
         let result = build_proxy("");
@@ -0,0 +1,309 @@
+//! Backend Message Localization
+//!
+//! Errors shown to users are built from a catalog of message keys with
+//! parameters instead of English prose, and rendered in the language chosen
+//! in settings. Errors are carried by `AppError` (see `error.rs`), serialized
+//! as `{ kind, code, params, message }`; when they pass through code still on
+//! `Result<_, String>` they become that JSON string (like `WebDAVError::to_json`).
+//! The frontend reads both forms with `parseBackendError`.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Mutex, OnceLock};
+
+use serde::{Deserialize, Serialize};
+
+/// Language used when settings have not been loaded yet
+const DEFAULT_LANGUAGE: &str = "zh-CN";
+
+/// Message catalog: (code, en-US, zh-CN); `{name}` is replaced by the param
+const CATALOG: &[(&str, &str, &str)] = &[
+    // Generic errors wrapping a low-level detail (see `AppError`)
+    ("IO_ERROR", "File system error: {detail}", "文件系统错误：{detail}"),
+    ("DB_ERROR", "Database error: {detail}", "数据库错误：{detail}"),
+    ("HTTP_ERROR", "Network error: {detail}", "网络错误：{detail}"),
+    ("INVALID_INPUT", "Invalid input: {detail}", "输入无效：{detail}"),
+    // Specific errors
+    ("UNSUPPORTED_TOOL", "Unsupported tool: {tool}", "不支持的工具：{tool}"),
+    ("NAME_REQUIRED", "Name is required", "名称不能为空"),
+    ("INVALID_BASE_URL", "Invalid base URL: {url}", "无效的 Base URL：{url}"),
+    (
+        "IMPORT_NOT_FOUND",
+        "Import not found or already handled",
+        "导入请求不存在或已处理",
+    ),
+    (
+        "INVALID_SHARE_STRING",
+        "Not a valid AI Toolbox share string",
+        "不是有效的 AI Toolbox 分享字符串",
+    ),
+    ("PROVIDER_NOT_FOUND", "Provider '{id}' not found", "供应商 '{id}' 不存在"),
+    ("VAULT_KEY_NOT_FOUND", "Vault key '{id}' not found", "密钥 '{id}' 不存在"),
+    ("VAULT_KEY_REQUIRED", "Key value is required", "密钥不能为空"),
+    (
+        "VAULT_KEY_IN_USE",
+        "Vault key is still referenced by: {usages}",
+        "密钥仍被以下配置引用：{usages}",
+    ),
+    (
+        "NO_TERMINAL_FOUND",
+        "No supported terminal emulator found",
+        "未找到支持的终端",
+    ),
+    ("UNKNOWN_TERMINAL", "Unknown terminal: {terminal}", "未知的终端：{terminal}"),
+    (
+        "PROJECT_DIR_NOT_FOUND",
+        "Project directory does not exist: {path}",
+        "项目目录不存在：{path}",
+    ),
+    (
+        "TOOL_NOT_INSTALLED",
+        "{tool} is not installed (`{binary}` not found)",
+        "{tool} 未安装（找不到 `{binary}`）",
+    ),
+    ("NO_CHANGE_TO_UNDO", "No change to undo", "没有可撤销的修改"),
+    (
+        "CHANGE_MODIFIED_SINCE",
+        "{path} was modified after this change; undo it manually",
+        "{path} 在此次修改后又被改动，请手动恢复",
+    ),
+    (
+        "UNKNOWN_MIGRATION_SOURCE",
+        "Unknown import source: {source}",
+        "未知的导入来源：{source}",
+    ),
+    (
+        "MIGRATION_SOURCE_NOT_FOUND",
+        "No data found at {path}",
+        "未找到数据：{path}",
+    ),
+    (
+        "MIGRATION_DB_UNSUPPORTED",
+        "{path} uses a database format that cannot be imported yet",
+        "{path} 为数据库格式，暂不支持导入",
+    ),
+    (
+        "PROJECT_NOT_REGISTERED",
+        "No project registered at {path}",
+        "该目录未登记为项目：{path}",
+    ),
+    (
+        "PROJECT_ALREADY_REGISTERED",
+        "A project is already registered at {path}",
+        "该目录已登记为项目：{path}",
+    ),
+    (
+        "BALANCE_CHECKER_NOT_FOUND",
+        "No balance checker configured for provider '{id}'",
+        "供应商 '{id}' 未配置余额查询",
+    ),
+    (
+        "BALANCE_CHECKER_INCOMPLETE",
+        "Provider, endpoint and balance path are required",
+        "供应商、查询地址和余额路径不能为空",
+    ),
+    (
+        "BALANCE_VALUE_NOT_FOUND",
+        "No numeric balance at '{path}' in the response",
+        "响应中 '{path}' 处没有数值余额",
+    ),
+    (
+        "SPEED_TEST_MODEL_REQUIRED",
+        "{name} has no default model; choose one to test",
+        "{name} 没有默认模型，请选择要测试的模型",
+    ),
+    (
+        "SPEED_TEST_UNSUPPORTED_API",
+        "{name} uses an API the speed test does not support",
+        "{name} 使用的接口暂不支持测速",
+    ),
+    (
+        "MODEL_RULE_INCOMPLETE",
+        "A model rule needs a pattern and a target model",
+        "模型规则需要填写匹配模式和目标模型",
+    ),
+    (
+        "SECRETS_DETECTED",
+        "Found {count} possible API key(s): {locations}. Move them to the vault or confirm to continue",
+        "发现 {count} 处疑似密钥：{locations}。请将密钥移入保险库，或确认后继续",
+    ),
+    (
+        "NO_PROVIDER_DETAILS",
+        "No API address found in the pasted text",
+        "粘贴的文本中没有找到接口地址",
+    ),
+    (
+        "SUBSCRIPTION_INCOMPLETE",
+        "A subscription needs a provider and a plan name",
+        "订阅需要选择供应商并填写套餐名称",
+    ),
+    (
+        "INVALID_RENEWAL_DATE",
+        "Invalid renewal date: {date} (expected YYYY-MM-DD)",
+        "无效的续费日期：{date}（格式应为 YYYY-MM-DD）",
+    ),
+    (
+        "CLI_SESSION_ACTIVE",
+        "{tools} is running; {job} was deferred so config files are not swapped under the session",
+        "{tools} 正在运行，已推迟 {job}，以免在会话中替换配置文件",
+    ),
+    (
+        "INVALID_BUDGET",
+        "Invalid monthly budget: {limit} (must be greater than 0)",
+        "无效的月度预算：{limit}（必须大于 0）",
+    ),
+    ("PROJECT_NOT_FOUND", "Project '{id}' not found", "项目 '{id}' 不存在"),
+    (
+        "CCR_OPENAI_FORMAT",
+        "{name} uses the OpenAI API format through CCR; Claude Code needs an Anthropic-compatible endpoint, check its base URL",
+        "{name} 在 CCR 中使用 OpenAI 格式；Claude Code 需要 Anthropic 兼容的接口，请检查其 Base URL",
+    ),
+    (
+        "CCR_ENV_KEY_MISSING",
+        "{name}: environment variable {var} for the API key is not set",
+        "{name}：API Key 引用的环境变量 {var} 未设置",
+    ),
+    (
+        "INVALID_PROVIDER_SETTINGS",
+        "Invalid provider settings: {fields}",
+        "供应商配置无效：{fields}",
+    ),
+    (
+        "PROVIDER_ALREADY_EXISTS",
+        "Provider '{id}' already exists",
+        "供应商 '{id}' 已存在",
+    ),
+    ("INVALID_PROVIDER_ID", "Invalid provider ID: {id}", "无效的供应商 ID：{id}"),
+    (
+        "PROVIDER_DISABLED",
+        "Provider '{id}' is disabled and cannot be applied",
+        "供应商 '{id}' 已停用，无法应用",
+    ),
+    (
+        "PROVIDER_ARCHIVED",
+        "Provider '{id}' is archived and cannot be applied",
+        "供应商 '{id}' 已归档，无法应用",
+    ),
+    (
+        "PROVIDER_APPLIED",
+        "Provider '{name}' is applied; switch to another provider first",
+        "供应商 '{name}' 正在使用，请先切换到其他供应商",
+    ),
+    (
+        "NO_APPLIED_PROVIDER",
+        "No {tool} provider is currently applied",
+        "当前没有已应用的 {tool} 供应商",
+    ),
+    (
+        "CLOUD_PROVIDER_NOT_TESTABLE",
+        "{name} runs on Bedrock / Vertex and cannot be tested with an API request",
+        "{name} 运行在 Bedrock / Vertex 上，无法通过 API 请求测试",
+    ),
+    ("PROVIDER_API_KEY_MISSING", "{name} has no API key", "{name} 没有配置 API Key"),
+    (
+        "HISTORY_ENTRY_NOT_FOUND",
+        "History entry '{id}' not found",
+        "历史记录 '{id}' 不存在",
+    ),
+    ("PRESET_NOT_FOUND", "Preset '{id}' not found", "预设 '{id}' 不存在"),
+    (
+        "PROVIDER_VERSION_NOT_FOUND",
+        "Version {version} of provider '{id}' not found",
+        "供应商 '{id}' 的版本 {version} 不存在",
+    ),
+    ("CONFIG_FILE_NOT_FOUND", "{tool} config file not found", "未找到 {tool} 配置文件"),
+];
+
+static LANGUAGE: OnceLock<Mutex<String>> = OnceLock::new();
+
+fn language_store() -> &'static Mutex<String> {
+    LANGUAGE.get_or_init(|| Mutex::new(DEFAULT_LANGUAGE.to_string()))
+}
+
+/// Update the language used for messages (on startup and when settings are saved)
+pub fn set_language(language: &str) {
+    if language.is_empty() {
+        return;
+    }
+    if let Ok(mut current) = language_store().lock() {
+        *current = language.to_string();
+    }
+}
+
+fn is_chinese() -> bool {
+    language_store()
+        .lock()
+        .map(|language| language.starts_with("zh"))
+        .unwrap_or(true)
+}
+
+/// A user-facing error: catalog code, its parameters and the rendered message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocalizedError {
+    pub code: String,
+    pub params: BTreeMap<String, String>,
+    pub message: String,
+}
+
+impl LocalizedError {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_else(|_| self.message.clone())
+    }
+}
+
+impl fmt::Display for LocalizedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl From<LocalizedError> for String {
+    fn from(error: LocalizedError) -> Self {
+        error.to_json()
+    }
+}
+
+fn render(template: &str, params: &BTreeMap<String, String>) -> String {
+    params.iter().fold(template.to_string(), |message, (key, value)| {
+        message.replace(&format!("{{{}}}", key), value)
+    })
+}
+
+/// Build a localized error from a catalog code
+pub fn error(code: &str, params: &[(&str, &str)]) -> LocalizedError {
+    let params: BTreeMap<String, String> = params
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+    let template = CATALOG
+        .iter()
+        .find(|(c, _, _)| *c == code)
+        .map(|(_, en, zh)| if is_chinese() { *zh } else { *en })
+        .unwrap_or(code);
+
+    LocalizedError {
+        code: code.to_string(),
+        message: render(template, &params),
+        params,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_params_into_template() {
+        let params = BTreeMap::from([("tool".to_string(), "cursor".to_string())]);
+        assert_eq!(render("Unsupported tool: {tool}", &params), "Unsupported tool: cursor");
+    }
+
+    #[test]
+    fn serializes_code_params_and_message() {
+        let error = error("UNKNOWN_TERMINAL", &[("terminal", "foo")]);
+        let value: serde_json::Value = serde_json::from_str(&String::from(error)).unwrap();
+        assert_eq!(value["code"], "UNKNOWN_TERMINAL");
+        assert_eq!(value["params"]["terminal"], "foo");
+        assert!(value["message"].as_str().unwrap().contains("foo"));
+    }
+}
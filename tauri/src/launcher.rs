@@ -0,0 +1,108 @@
+//! CLI Launcher Module
+//!
+//! Opens the user's terminal (see `terminal`) in a project directory with a
+//! coding CLI (`opencode`, `claude`) started. The CLIs read the configs applied
+//! by the toolbox; the proxy from settings is passed through the environment.
+
+use std::path::PathBuf;
+
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::coding::cli_detect::find_binary;
+use crate::db::DbState;
+use crate::error::{AppError, AppResult};
+use crate::settings::adapter;
+use crate::terminal;
+
+/// Maximum number of recent project directories kept in settings
+const MAX_RECENT_PROJECTS: usize = 10;
+
+/// Tool key -> (display name, binary names)
+fn launch_tool(tool: &str) -> Option<(&'static str, &'static [&'static str])> {
+    match tool {
+        "opencode" => Some(("OpenCode", &["opencode"])),
+        "claude" => Some(("Claude Code", &["claude"])),
+        _ => None,
+    }
+}
+
+/// Display name of a launchable tool
+pub fn launch_tool_name(tool: &str) -> &str {
+    launch_tool(tool).map(|(name, _)| name).unwrap_or(tool)
+}
+
+/// Environment passed to the launched CLI (proxy from settings)
+async fn launch_env(state: &DbState) -> Vec<(String, String)> {
+    let proxy_url = crate::http_client::get_proxy_from_settings(state)
+        .await
+        .unwrap_or_default();
+    if proxy_url.is_empty() {
+        return Vec::new();
+    }
+    ["HTTP_PROXY", "HTTPS_PROXY", "http_proxy", "https_proxy"]
+        .iter()
+        .map(|key| (key.to_string(), proxy_url.clone()))
+        .collect()
+}
+
+/// Remember a project directory (most recent first)
+async fn remember_project(state: &DbState, cwd: &str) -> AppResult<()> {
+    let db = state.0.lock().await;
+    let records: Vec<serde_json::Value> = db
+        .query("SELECT *, type::string(id) as id FROM settings:`app` LIMIT 1")
+        .await?
+        .take(0)?;
+
+    let mut settings = records
+        .into_iter()
+        .next()
+        .map(adapter::from_db_value)
+        .unwrap_or_default();
+    settings.launch_projects.retain(|p| p != cwd);
+    settings.launch_projects.insert(0, cwd.to_string());
+    settings.launch_projects.truncate(MAX_RECENT_PROJECTS);
+
+    db.query("UPSERT settings:`app` CONTENT $data")
+        .bind(("data", adapter::to_db_value(&settings)))
+        .await?;
+    Ok(())
+}
+
+/// Launch a coding CLI in a terminal (used by the command and the tray)
+pub async fn launch_coding_cli_internal<R: Runtime>(
+    app: &AppHandle<R>,
+    tool: &str,
+    cwd: &str,
+) -> AppResult<()> {
+    let (name, binaries) =
+        launch_tool(tool).ok_or_else(|| AppError::validation("UNSUPPORTED_TOOL", &[("tool", tool)]))?;
+
+    let project_dir = PathBuf::from(cwd);
+    if !project_dir.is_dir() {
+        return Err(AppError::not_found("PROJECT_DIR_NOT_FOUND", &[("path", cwd)]));
+    }
+    let binary = find_binary(binaries)
+        .ok_or_else(|| AppError::not_found("TOOL_NOT_INSTALLED", &[("tool", name), ("binary", binaries[0])]))?;
+
+    let state = app.state::<DbState>();
+    let env = launch_env(&state).await;
+    terminal::open_terminal(&state, &project_dir, &env, &[binary.to_string_lossy().to_string()]).await?;
+
+    remember_project(&state, cwd).await?;
+    let project_name = project_dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| cwd.to_string());
+    crate::activity_log::record(app, "launch", format!("已在 {} 启动 {}", project_name, name));
+    Ok(())
+}
+
+/// Open a terminal in `cwd` with `tool` ("opencode" or "claude") started
+#[tauri::command]
+pub async fn launch_coding_cli<R: Runtime>(
+    app: AppHandle<R>,
+    tool: String,
+    cwd: String,
+) -> AppResult<()> {
+    launch_coding_cli_internal(&app, &tool, &cwd).await
+}
@@ -10,18 +10,31 @@ use surrealdb::Surreal;
 use tokio::sync::Mutex;
 
 use log::{error, info, warn};
-use simplelog::{CombinedLogger, Config, LevelFilter, WriteLogger};
 
 #[cfg(target_os = "linux")]
 use std::sync::Mutex as StdMutex;
 
 // Module declarations
+pub mod activity_log;
 pub mod auto_launch;
 pub mod coding;
 pub mod db;
+pub mod deep_link;
+pub mod error;
+pub mod events;
 pub mod http_client;
+pub mod i18n;
+pub mod launcher;
+pub mod local_api;
+pub mod logging;
+pub mod network_diagnostics;
+pub mod notifications;
+pub mod secret_scan;
+pub mod session_guard;
 pub mod settings;
 pub mod single_instance;
+pub mod telemetry;
+pub mod terminal;
 pub mod tray;
 pub mod update;
 
@@ -86,77 +99,6 @@ fn open_folder(path: String) -> Result<(), String> {
     Ok(())
 }
 
-/// 初始化日志系统，日志文件位于应用数据目录下的 logs 文件夹
-/// 同一天的日志会追加到同一个文件中
-fn init_logging() -> Option<std::path::PathBuf> {
-    // 获取日志目录路径
-    let log_dir = dirs::data_dir()
-        .map(|p| p.join("com.ai-toolbox").join("logs"))
-        .or_else(|| dirs::home_dir().map(|p| p.join(".ai-toolbox").join("logs")));
-
-    let log_dir = match log_dir {
-        Some(dir) => dir,
-        None => return None,
-    };
-
-    // 确保日志目录存在
-    if let Err(e) = fs::create_dir_all(&log_dir) {
-        eprintln!("无法创建日志目录: {}", e);
-        return None;
-    }
-
-    // 使用日期命名日志文件，同一天的日志追加到同一个文件
-    let date = chrono::Local::now().format("%Y%m%d");
-    let log_file = log_dir.join(format!("ai-toolbox_{}.log", date));
-
-    // 以追加模式打开日志文件
-    let file = match std::fs::OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(&log_file)
-    {
-        Ok(f) => f,
-        Err(e) => {
-            eprintln!("无法打开日志文件: {}", e);
-            return None;
-        }
-    };
-
-    // 初始化日志系统
-    if CombinedLogger::init(vec![WriteLogger::new(
-        LevelFilter::Info,
-        Config::default(),
-        file,
-    )])
-    .is_err()
-    {
-        eprintln!("日志系统初始化失败");
-        return None;
-    }
-
-    // 清理旧日志文件（保留最近 7 天）
-    if let Ok(entries) = fs::read_dir(&log_dir) {
-        let mut log_files: Vec<_> = entries
-            .filter_map(|e| e.ok())
-            .filter(|e| {
-                e.path()
-                    .file_name()
-                    .map(|n| n.to_string_lossy().starts_with("ai-toolbox_"))
-                    .unwrap_or(false)
-            })
-            .collect();
-
-        log_files.sort_by_key(|e| std::cmp::Reverse(e.path()));
-
-        // 删除超过 7 天的旧日志
-        for old_log in log_files.into_iter().skip(7) {
-            let _ = fs::remove_file(old_log.path());
-        }
-    }
-
-    Some(log_file)
-}
-
 /// 设置 panic hook，将 panic 信息写入日志
 fn setup_panic_hook() {
     let default_hook = std::panic::take_hook();
@@ -178,10 +120,7 @@ fn setup_panic_hook() {
         error!("PANIC 发生: {} at {}", msg, location);
 
         // 尝试将错误写入单独的崩溃日志文件
-        if let Some(log_dir) = dirs::data_dir()
-            .map(|p| p.join("com.ai-toolbox").join("logs"))
-            .or_else(|| dirs::home_dir().map(|p| p.join(".ai-toolbox").join("logs")))
-        {
+        if let Some(log_dir) = logging::log_dir() {
             let crash_file = log_dir.join("CRASH.log");
             let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
             let crash_msg = format!("[{}] PANIC: {} at {}\n", timestamp, msg, location);
@@ -535,9 +474,8 @@ fn setup_linux_wayland_webview_workaround() -> u8 {
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     // 初始化日志系统
-    let log_file = init_logging();
-    if let Some(ref path) = log_file {
-        eprintln!("日志文件: {:?}", path);
+    if let Some(log_dir) = logging::init() {
+        eprintln!("日志目录: {:?}", log_dir);
     }
 
     // 设置 panic hook
@@ -581,7 +519,13 @@ pub fn run() {
         let lock = match try_acquire_single_instance_lock_with_optional_retry() {
             Ok(lock) => {
                 info!("文件锁单实例检测成功");
-                lock
+                Some(lock)
+            }
+            // Opened via an aitoolbox:// link: let tauri-plugin-single-instance
+            // forward the link to the running instance over D-Bus and exit
+            Err(e) if std::env::args().any(|arg| arg.starts_with("aitoolbox://")) => {
+                warn!("单实例检测失败，转交深度链接: {}", e);
+                None
             }
             Err(e) => {
                 error!("单实例检测失败: {}", e);
@@ -592,7 +536,7 @@ pub fn run() {
         };
 
         if let Ok(mut guard) = single_instance_lock_holder.lock() {
-            *guard = Some(lock);
+            *guard = lock;
         }
     }
 
@@ -611,12 +555,14 @@ pub fn run() {
                 let _ = window.set_focus();
             }
         }))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_os::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
+        .plugin(tauri_plugin_notification::init())
         .setup(move |app| {
             info!("开始执行 setup()...");
             let app_handle = app.handle().clone();
@@ -756,6 +702,18 @@ pub fn run() {
             });
             
             
+            // Notifications from background tasks need the app handle
+            notifications::init(&app_handle);
+
+            // Handle aitoolbox:// links (imports are staged for confirmation)
+            deep_link::init(&app_handle);
+
+            // Start the local REST API if enabled
+            let app_handle_api = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                local_api::start_from_settings(&app_handle_api).await;
+            });
+
             // Enable auto-launch if setting is true
             let app_handle_clone = app_handle.clone();
             tauri::async_runtime::spawn(async move {
@@ -771,6 +729,23 @@ pub fn run() {
                     let records: Result<Vec<serde_json::Value>, _> = res.take(0);
                     if let Ok(records) = records {
                         if let Some(record) = records.first() {
+                            if let Some(language) = record.get("language").and_then(|v| v.as_str()) {
+                                i18n::set_language(language);
+                            }
+                            if let Some(level) = record.get("log_level").and_then(|v| v.as_str()) {
+                                logging::set_level(level);
+                            }
+                            if let Some(dir) = record.get("claude_config_dir").and_then(|v| v.as_str()) {
+                                coding::claude_code::set_config_dir_override(dir);
+                            }
+
+                            let telemetry_enabled = record
+                                .get("telemetry_enabled")
+                                .and_then(|v| v.as_bool())
+                                .unwrap_or(false);
+                            telemetry::set_enabled(&db, telemetry_enabled).await;
+                            telemetry::track("app.started");
+
                             let launch_on_startup = record
                                 .get("launch_on_startup")
                                 .and_then(|v| v.as_bool())
@@ -779,6 +754,24 @@ pub fn run() {
                             if launch_on_startup {
                                 let _ = auto_launch::enable_auto_launch();
                             }
+
+                            // Started at login with "start minimized": stay in the tray
+                            let start_minimized = record
+                                .get("start_minimized")
+                                .and_then(|v| v.as_bool())
+                                .unwrap_or(false);
+                            if start_minimized && auto_launch::is_launched_at_login() {
+                                if let Some(window) = app_handle_clone.get_webview_window("main") {
+                                    let _ = window.hide();
+
+                                    // macOS: Switch to Accessory mode to hide from Dock
+                                    #[cfg(target_os = "macos")]
+                                    {
+                                        use tauri::ActivationPolicy;
+                                        let _ = app_handle_clone.set_activation_policy(ActivationPolicy::Accessory);
+                                    }
+                                }
+                            }
                         }
                     }
                 }
@@ -929,6 +922,83 @@ pub fn run() {
                 });
             }
 
+            // Queue and send opt-in telemetry counters (every 6 hours)
+            {
+                let app_clone = app_handle.clone();
+                tauri::async_runtime::spawn(async move {
+                    tokio::time::sleep(Duration::from_secs(60)).await;
+
+                    loop {
+                        let db_state = app_clone.state::<crate::DbState>();
+                        if let Err(e) = telemetry::flush(&db_state).await {
+                            warn!("Telemetry flush failed: {}", e);
+                        }
+                        tokio::time::sleep(Duration::from_secs(6 * 3600)).await;
+                    }
+                });
+            }
+
+            // Plan renewal reminders (every 6 hours)
+            {
+                let app_clone = app_handle.clone();
+                tauri::async_runtime::spawn(async move {
+                    tokio::time::sleep(Duration::from_secs(90)).await;
+
+                    loop {
+                        if let Err(e) = coding::subscriptions::renewal::check_renewals(&app_clone).await {
+                            warn!("Subscription renewal check failed: {}", e);
+                        }
+                        tokio::time::sleep(Duration::from_secs(6 * 3600)).await;
+                    }
+                });
+            }
+
+            // Detect newly installed coding CLIs
+            {
+                let app_clone = app_handle.clone();
+                tauri::async_runtime::spawn(async move {
+                    tokio::time::sleep(Duration::from_secs(60)).await;
+
+                    loop {
+                        if let Err(e) = coding::cli_scan::scan(&app_clone).await {
+                            warn!("CLI scan failed: {}", e);
+                        }
+                        tokio::time::sleep(Duration::from_secs(3600)).await;
+                    }
+                });
+            }
+
+            // Usage budget alerts (hourly)
+            {
+                let app_clone = app_handle.clone();
+                tauri::async_runtime::spawn(async move {
+                    tokio::time::sleep(Duration::from_secs(120)).await;
+
+                    loop {
+                        if let Err(e) = coding::usage::budget::check_budgets(&app_clone).await {
+                            warn!("Budget check failed: {}", e);
+                        }
+                        tokio::time::sleep(Duration::from_secs(3600)).await;
+                    }
+                });
+            }
+
+            // Claude provider health monitor and failover (interval from its config)
+            {
+                let app_clone = app_handle.clone();
+                tauri::async_runtime::spawn(async move {
+                    tokio::time::sleep(Duration::from_secs(30)).await;
+
+                    loop {
+                        if let Err(e) = coding::claude_code::failover::check(&app_clone).await {
+                            warn!("Claude provider health check failed: {}", e);
+                        }
+                        let interval = coding::claude_code::failover::interval(&app_clone).await;
+                        tokio::time::sleep(interval).await;
+                    }
+                });
+            }
+
             // Check for resync flag after restore (delayed to ensure DB is ready)
             {
                 let app_clone = app_handle.clone();
@@ -945,11 +1015,14 @@ pub fn run() {
                     if resync_flag.exists() {
                         info!("Resync flag detected, starting skills and MCP resync...");
 
+                        let db_state = app_clone.state::<crate::DbState>();
+
+                        // Don't rewrite skills / MCP configs under a running CLI session
+                        session_guard::wait_until_idle(&db_state, "post-restore resync").await;
+
                         // Remove the flag file first to prevent repeated resync
                         let _ = fs::remove_file(&resync_flag);
 
-                        let db_state = app_clone.state::<crate::DbState>();
-
                         // Resync skills
                         match coding::skills::commands::skills_resync_all(app_clone.clone(), db_state.clone()).await {
                             Ok(synced) => {
@@ -1044,6 +1117,8 @@ pub fn run() {
             settings::save_settings,
             settings::set_auto_launch,
             settings::get_auto_launch_status,
+            settings::get_autostart,
+            settings::set_autostart,
             settings::restart_app,
             settings::test_proxy_connection,
             // Backup - Local
@@ -1059,16 +1134,77 @@ pub fn run() {
             settings::backup::delete_webdav_backup,
             // Claude Code
             coding::claude_code::list_claude_providers,
+            coding::claude_code::count_claude_providers,
             coding::claude_code::create_claude_provider,
+            coding::claude_code::clone_claude_provider,
             coding::claude_code::update_claude_provider,
             coding::claude_code::delete_claude_provider,
             coding::claude_code::reorder_claude_providers,
             coding::claude_code::select_claude_provider,
+            coding::claude_code::activate_claude_provider,
+            coding::claude_code::provider_io::export_claude_providers,
+            coding::claude_code::provider_io::import_claude_providers,
+            coding::claude_code::provider_io::bulk_import_claude_providers,
+            coding::claude_code::provider_history::list_claude_provider_history,
+            coding::claude_code::provider_history::restore_claude_provider_version,
+            coding::claude_code::failover::get_claude_failover_config,
+            coding::claude_code::failover::save_claude_failover_config,
+            coding::claude_code::failover::list_claude_provider_health,
+            coding::claude_code::templates::fetch_claude_provider_templates,
+            coding::claude_code::cli_install::get_claude_cli_status,
+            coding::claude_code::cli_install::install_claude_cli,
+            coding::claude_code::shell_profile::apply_claude_provider_to_shell,
+            coding::claude_code::shell_profile::remove_claude_shell_env,
+            coding::claude_code::drift::check_claude_config_drift,
+            coding::claude_code::local_settings::get_claude_local_settings,
+            coding::claude_code::local_settings::save_claude_local_settings,
+            coding::claude_code::local_settings::merge_claude_local_settings,
+            coding::claude_code::local_settings::get_claude_apply_target,
+            coding::claude_code::local_settings::set_claude_apply_target,
+            coding::claude_code::limits::get_claude_token_limits,
+            coding::claude_code::limits::save_claude_token_limits,
             coding::claude_code::get_claude_config_path,
+            coding::claude_code::get_claude_config_dir_info,
             coding::claude_code::reveal_claude_config_folder,
             coding::claude_code::read_claude_settings,
             coding::claude_code::apply_claude_config,
+            coding::claude_code::test_claude_provider,
+            coding::claude_code::batch_test::test_all_claude_providers,
+            coding::claude_code::batch_test::get_claude_provider_test_results,
+            coding::claude_code::query_claude_provider_balance,
+            coding::claude_code::apply_history::list_claude_apply_history,
+            coding::claude_code::apply_history::revert_claude_apply,
+            coding::claude_code::backup::list_claude_settings_backups,
+            coding::claude_code::backup::rollback_claude_settings,
+            coding::claude_code::hooks::list_claude_hook_sets,
+            coding::claude_code::hooks::create_claude_hook_set,
+            coding::claude_code::hooks::update_claude_hook_set,
+            coding::claude_code::hooks::delete_claude_hook_set,
+            coding::claude_code::permissions::list_claude_permission_presets,
+            coding::claude_code::permissions::create_claude_permission_preset,
+            coding::claude_code::permissions::update_claude_permission_preset,
+            coding::claude_code::permissions::delete_claude_permission_preset,
+            coding::claude_code::slash_commands::list_claude_slash_commands,
+            coding::claude_code::slash_commands::save_claude_slash_command,
+            coding::claude_code::slash_commands::delete_claude_slash_command,
+            coding::claude_code::slash_commands::list_claude_command_templates,
+            coding::claude_code::slash_commands::save_claude_command_template,
+            coding::claude_code::slash_commands::delete_claude_command_template,
+            coding::claude_code::slash_commands::install_claude_command_template,
+            coding::claude_code::slash_commands::export_claude_slash_commands,
+            coding::claude_code::slash_commands::import_claude_slash_commands,
+            coding::claude_code::agents::list_claude_agents,
+            coding::claude_code::agents::save_claude_agent,
+            coding::claude_code::agents::delete_claude_agent,
+            coding::claude_code::agents::list_claude_agent_presets,
+            coding::claude_code::agents::save_claude_agent_preset,
+            coding::claude_code::agents::delete_claude_agent_preset,
+            coding::claude_code::agents::sync_claude_agent_presets,
+            coding::claude_code::sessions::list_claude_sessions,
+            coding::claude_code::sessions::export_claude_session,
             coding::claude_code::toggle_claude_code_provider_disabled,
+            coding::claude_code::archive_claude_provider,
+            coding::claude_code::unarchive_claude_provider,
             coding::claude_code::get_claude_common_config,
             coding::claude_code::save_claude_common_config,
             coding::claude_code::save_claude_local_config,
@@ -1091,6 +1227,8 @@ pub fn run() {
             coding::open_code::get_opencode_auth_providers,
             coding::open_code::get_opencode_auth_config_path,
             coding::open_code::backup_opencode_config,
+            coding::open_code::validate_opencode_config,
+            coding::open_code::refresh_opencode_schema,
             coding::open_code::test_provider_model_connectivity,
             coding::open_code::list_opencode_favorite_plugins,
             coding::open_code::add_opencode_favorite_plugin,
@@ -1115,8 +1253,288 @@ pub fn run() {
             coding::codex::get_codex_common_config,
             coding::codex::save_codex_common_config,
             coding::codex::save_codex_local_config,
+            coding::codex::get_codex_model_providers,
+            coding::codex::build_codex_provider_config,
+            // Gemini CLI
+            coding::gemini_cli::get_gemini_cli_config_path,
+            coding::gemini_cli::reveal_gemini_cli_config_folder,
+            coding::gemini_cli::list_gemini_cli_providers,
+            coding::gemini_cli::create_gemini_cli_provider,
+            coding::gemini_cli::update_gemini_cli_provider,
+            coding::gemini_cli::delete_gemini_cli_provider,
+            coding::gemini_cli::reorder_gemini_cli_providers,
+            coding::gemini_cli::select_gemini_cli_provider,
+            coding::gemini_cli::apply_gemini_cli_config,
+            coding::gemini_cli::preview_gemini_cli_config,
+            coding::gemini_cli::toggle_gemini_cli_provider_disabled,
+            coding::gemini_cli::read_gemini_cli_settings,
+            coding::gemini_cli::get_gemini_cli_mcp_servers,
+            coding::gemini_cli::get_gemini_cli_common_config,
+            coding::gemini_cli::save_gemini_cli_common_config,
+            // Qwen Code
+            coding::qwen_code::get_qwen_code_config_path,
+            coding::qwen_code::reveal_qwen_code_config_folder,
+            coding::qwen_code::list_qwen_code_providers,
+            coding::qwen_code::create_qwen_code_provider,
+            coding::qwen_code::update_qwen_code_provider,
+            coding::qwen_code::delete_qwen_code_provider,
+            coding::qwen_code::reorder_qwen_code_providers,
+            coding::qwen_code::select_qwen_code_provider,
+            coding::qwen_code::apply_qwen_code_config,
+            coding::qwen_code::preview_qwen_code_config,
+            coding::qwen_code::toggle_qwen_code_provider_disabled,
+            coding::qwen_code::set_qwen_code_model,
+            coding::qwen_code::read_qwen_code_settings,
+            coding::qwen_code::get_qwen_code_mcp_servers,
+            coding::qwen_code::get_qwen_code_common_config,
+            coding::qwen_code::save_qwen_code_common_config,
+            // Cline CLI
+            coding::cline::get_cline_status,
+            coding::cline::read_cline_api_config,
+            coding::cline::apply_cline_api_config,
+            coding::cline::apply_claude_provider_to_cline,
+            // Roo Code
+            coding::roo_code::get_roo_code_status,
+            coding::roo_code::read_roo_code_profiles,
+            coding::roo_code::apply_roo_code_profile,
+            coding::roo_code::import_roo_code_profiles,
+            // Continue.dev
+            coding::continue_dev::get_continue_config_path,
+            coding::continue_dev::read_continue_models,
+            coding::continue_dev::preview_continue_config,
+            coding::continue_dev::apply_continue_config,
+            coding::continue_dev::remove_continue_provider_models,
+            // Cursor CLI
+            coding::cursor::get_cursor_cli_status,
+            coding::cursor::set_cursor_cli_model,
+            coding::cursor::apply_cursor_cli_provider,
+            // Windsurf
+            coding::windsurf::get_windsurf_config_path,
+            coding::windsurf::get_windsurf_status,
+            coding::windsurf::list_windsurf_providers,
+            coding::windsurf::create_windsurf_provider,
+            coding::windsurf::update_windsurf_provider,
+            coding::windsurf::delete_windsurf_provider,
+            coding::windsurf::reorder_windsurf_providers,
+            coding::windsurf::select_windsurf_provider,
+            coding::windsurf::apply_windsurf_config,
+            coding::windsurf::preview_windsurf_config,
+            coding::windsurf::toggle_windsurf_provider_disabled,
+            coding::windsurf::read_windsurf_settings,
+            coding::windsurf::get_windsurf_mcp_servers,
+            coding::windsurf::get_windsurf_common_config,
+            coding::windsurf::save_windsurf_common_config,
+            // Crush
+            coding::crush::get_crush_config_path,
+            coding::crush::read_crush_config,
+            coding::crush::sync_crush_providers,
+            coding::crush::inject_crush_free_models,
+            coding::crush::set_crush_model,
+            // Goose
+            coding::goose::get_goose_config_path,
+            coding::goose::get_goose_status,
+            coding::goose::list_goose_providers,
+            coding::goose::create_goose_provider,
+            coding::goose::update_goose_provider,
+            coding::goose::delete_goose_provider,
+            coding::goose::reorder_goose_providers,
+            coding::goose::select_goose_provider,
+            coding::goose::apply_goose_config,
+            coding::goose::preview_goose_config,
+            coding::goose::toggle_goose_provider_disabled,
+            coding::goose::read_goose_settings,
+            coding::goose::get_goose_mcp_servers,
+            coding::goose::get_goose_common_config,
+            coding::goose::save_goose_common_config,
+            // GitHub Copilot
+            coding::github_copilot::get_copilot_status,
+            coding::github_copilot::set_copilot_cli_model,
+            coding::github_copilot::apply_copilot_byok_provider,
+            coding::github_copilot::remove_copilot_byok_models,
+            // Amp
+            coding::amp::get_amp_config_path,
+            coding::amp::get_amp_status,
+            coding::amp::list_amp_providers,
+            coding::amp::create_amp_provider,
+            coding::amp::update_amp_provider,
+            coding::amp::delete_amp_provider,
+            coding::amp::reorder_amp_providers,
+            coding::amp::select_amp_provider,
+            coding::amp::apply_amp_config,
+            coding::amp::preview_amp_config,
+            coding::amp::toggle_amp_provider_disabled,
+            coding::amp::read_amp_settings,
+            coding::amp::get_amp_mcp_servers,
+            coding::amp::detect_amp_applied_provider,
+            coding::amp::get_amp_common_config,
+            coding::amp::save_amp_common_config,
+            // iFlow
+            coding::iflow::get_iflow_config_path,
+            coding::iflow::read_iflow_settings,
+            coding::iflow::get_iflow_status,
+            coding::iflow::apply_iflow_provider,
+            coding::iflow::set_iflow_model,
+            // Factory Droid
+            coding::droid::get_droid_config_path,
+            coding::droid::get_droid_status,
+            coding::droid::list_droid_providers,
+            coding::droid::create_droid_provider,
+            coding::droid::update_droid_provider,
+            coding::droid::delete_droid_provider,
+            coding::droid::reorder_droid_providers,
+            coding::droid::select_droid_provider,
+            coding::droid::apply_droid_config,
+            coding::droid::preview_droid_config,
+            coding::droid::toggle_droid_provider_disabled,
+            coding::droid::read_droid_settings,
+            // Kilo Code
+            coding::kilo_code::get_kilo_code_status,
+            coding::kilo_code::read_kilo_code_profiles,
+            coding::kilo_code::apply_kilo_code_profile,
+            coding::kilo_code::import_kilo_code_profiles,
+            // JetBrains
+            coding::jetbrains::list_jetbrains_ides,
+            coding::jetbrains::apply_jetbrains_proxy_ai_provider,
+            // Warp
+            coding::warp::get_warp_status,
+            coding::warp::set_warp_preference,
+            coding::warp::apply_warp_provider,
+            coding::warp::clear_warp_applied_provider,
+            // Custom Tools
+            coding::custom_tool::list_custom_tools,
+            coding::custom_tool::create_custom_tool,
+            coding::custom_tool::update_custom_tool,
+            coding::custom_tool::delete_custom_tool,
+            coding::custom_tool::read_custom_tool_values,
+            coding::custom_tool::preview_custom_tool_apply,
+            coding::custom_tool::apply_custom_tool,
+            coding::custom_tool::watch_custom_tool,
+            coding::custom_tool::unwatch_custom_tool,
+            // claude-code-router
+            coding::ccr::get_ccr_status,
+            coding::ccr::read_ccr_config,
+            coding::ccr::save_ccr_config,
+            coding::ccr::sync_ccr_providers,
+            coding::ccr::set_ccr_router_rule,
+            coding::ccr::start_ccr_service,
+            coding::ccr::stop_ccr_service,
+            coding::ccr::restart_ccr_service,
+            // Relay (one-api / new-api)
+            coding::relay::list_relay_instances,
+            coding::relay::save_relay_instance,
+            coding::relay::delete_relay_instance,
+            coding::relay::list_relay_channels,
+            coding::relay::list_relay_tokens,
+            coding::relay::create_relay_token,
+            coding::relay::get_relay_quota,
+            coding::relay::register_relay_token_provider,
+            // Balance Checks
+            coding::balance::list_balance_templates,
+            coding::balance::list_balance_checkers,
+            coding::balance::save_balance_checker,
+            coding::balance::delete_balance_checker,
+            coding::balance::query_balance,
+            // Speed Test
+            coding::speed_test::run_speed_test,
+            coding::speed_test::list_speed_test_history,
+            coding::speed_test::clear_speed_test_history,
+            // Model Rules
+            coding::model_rules::list_model_rules,
+            coding::model_rules::save_model_rule,
+            coding::model_rules::delete_model_rule,
+            coding::model_rules::reorder_model_rules,
+            coding::model_rules::resolve_model_route,
+            // Subscriptions
+            coding::subscriptions::list_subscriptions,
+            coding::subscriptions::save_subscription,
+            coding::subscriptions::delete_subscription,
+            coding::subscriptions::get_subscription_summary,
+            // Network Diagnostics
+            network_diagnostics::network_diagnostics,
+            // CLI Scan
+            coding::cli_scan::scan_installed_clis,
+            coding::cli_scan::list_cli_detections,
+            // Session Guard
+            session_guard::get_active_cli_sessions,
+            // Usage Budgets
+            coding::usage::budget::save_budget,
+            coding::usage::budget::delete_budget,
+            coding::usage::budget::get_budget_status,
+            // Onboarding
+            coding::provider_import::get_onboarding_scan,
+            coding::provider_import::run_onboarding_import,
+            coding::provider_import::skip_onboarding,
+            // Switch Analytics
+            coding::switch_analytics::get_switch_analytics,
+            // LiteLLM
+            coding::litellm::generate_litellm_config,
+            coding::litellm::validate_litellm_config,
+            // Ollama
+            coding::ollama::get_ollama_status,
+            coding::ollama::list_ollama_models,
+            coding::ollama::pull_ollama_model,
+            coding::ollama::delete_ollama_model,
+            coding::ollama::register_ollama_provider,
+            // LM Studio
+            coding::lm_studio::get_lm_studio_status,
+            coding::lm_studio::list_lm_studio_models,
+            coding::lm_studio::register_lm_studio_provider,
+            coding::lm_studio::sync_lm_studio_models,
+            // Local Endpoint Discovery
+            coding::local_endpoint::probe_local_endpoints,
+            coding::local_endpoint::create_provider_from_endpoint,
+            // Unified Provider Apply
+            coding::provider_apply::apply_provider_everywhere,
+            // API Key Vault
+            coding::vault::list_vault_keys,
+            coding::vault::save_vault_key,
+            coding::vault::delete_vault_key,
+            // Projects
+            coding::projects::list_projects,
+            coding::projects::save_project,
+            coding::projects::delete_project,
+            coding::projects::apply_project_profile,
+            coding::projects::get_project_claude_settings,
+            coding::projects::save_project_claude_settings,
+            coding::projects::merge_project_claude_settings,
+            // Usage Dashboard
+            coding::usage::get_usage_dashboard,
             // Tray
             tray::refresh_tray_menu,
+            tray::rebuild_tray_menu,
+            // Terminal
+            terminal::list_terminals,
+            terminal::set_default_terminal,
+            // Provider Import (deep links, share strings)
+            coding::provider_import::list_pending_provider_imports,
+            coding::provider_import::confirm_provider_import,
+            coding::provider_import::discard_provider_import,
+            coding::provider_import::encode_provider_share,
+            coding::provider_import::import_provider_share,
+            coding::provider_import::parse_provider_from_text,
+            coding::provider_import::detect_migration_sources,
+            coding::provider_import::import_from_switcher,
+            // Secret Scan
+            secret_scan::scan_for_secrets,
+            secret_scan::preview_backup_secrets,
+            // Local REST API
+            local_api::get_local_api_status,
+            local_api::set_local_api_config,
+            // Notifications
+            notifications::send_test_notification,
+            // Logs
+            logging::get_recent_logs,
+            logging::open_log_folder,
+            // Telemetry
+            telemetry::preview_telemetry_payload,
+            // Change History
+            coding::change_history::undo_last_change,
+            coding::change_history::list_change_history,
+            // CLI Launcher
+            launcher::launch_coding_cli,
+            // Recent Actions
+            activity_log::get_activity_log,
+            activity_log::clear_activity_log,
             // Oh My OpenCode
             coding::oh_my_opencode::list_oh_my_opencode_configs,
             coding::oh_my_opencode::create_oh_my_opencode_config,
@@ -1207,6 +1625,7 @@ pub fn run() {
             coding::mcp::mcp_reorder_servers,
             coding::mcp::mcp_sync_to_tool,
             coding::mcp::mcp_sync_all,
+            coding::mcp::mcp_preview_sync,
             coding::mcp::mcp_import_from_tool,
             coding::mcp::mcp_get_tools,
             coding::mcp::mcp_scan_servers,
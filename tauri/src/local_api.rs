@@ -0,0 +1,600 @@
+//! Local REST API Module
+//!
+//! Optional HTTP server on 127.0.0.1 so shell scripts and other apps can drive
+//! the toolbox headlessly. Every request needs `Authorization: Bearer <token>`
+//! with the token from settings.
+//!
+//! LAN mode (`lan_enabled`) listens on all interfaces over HTTPS with a
+//! self-signed certificate kept in the app data folder, and serves a small
+//! control page at `/` for switching providers and backing up from a phone.
+//!
+//! Endpoints:
+//! - `GET  /` (control page, no token needed; its API calls send the token)
+//! - `GET  /api/health`
+//! - `GET  /api/providers/{claude|codex}`
+//! - `POST /api/providers/{claude|codex}/{id}/apply`
+//! - `POST /api/providers/apply-everywhere` (body: provider_id, targets, model_id)
+//! - `POST /api/backup` (uses the backup type configured in settings)
+//! - `GET  /api/usage?range=7d|30d|90d|all`
+//!
+//! Apply and backup answer 409 while a claude / opencode session is running
+//! (see session_guard.rs); add `?force=true` to run them anyway.
+
+use std::fs;
+use std::io::{Read, Write};
+use std::net::{IpAddr, UdpSocket};
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Manager};
+use tiny_http::{Header, Method, Request, Response, Server, SslConfig};
+
+use crate::db::DbState;
+use crate::error::AppError;
+use crate::settings::{adapter, AppSettings, LocalApiConfig, LocalApiStatus};
+
+/// Largest accepted request body
+const MAX_BODY_BYTES: u64 = 64 * 1024;
+
+/// Control page served at `/`
+const CONTROL_PAGE: &str = include_str!("../resources/remote_control.html");
+
+/// LAN mode certificate and key, in the app data folder
+const CERT_FILE: &str = "remote_control_cert.pem";
+const KEY_FILE: &str = "remote_control_key.pem";
+
+#[derive(Default)]
+struct ServerState {
+    server: Option<Arc<Server>>,
+    port: u16,
+    error: Option<String>,
+}
+
+fn server_state() -> &'static Mutex<ServerState> {
+    static STATE: OnceLock<Mutex<ServerState>> = OnceLock::new();
+    STATE.get_or_init(|| Mutex::new(ServerState::default()))
+}
+
+/// API error: HTTP status and message
+struct ApiError(u16, String);
+
+impl From<String> for ApiError {
+    fn from(message: String) -> Self {
+        ApiError(500, message)
+    }
+}
+
+impl From<AppError> for ApiError {
+    fn from(error: AppError) -> Self {
+        let status = match error {
+            AppError::NotFound(_) => 404,
+            AppError::Conflict(_) => 409,
+            AppError::Validation(_) => 400,
+            AppError::Io(_) | AppError::Db(_) | AppError::Http(_) => 500,
+        };
+        ApiError(status, error.to_string())
+    }
+}
+
+// ============================================================================
+// Server Lifecycle
+// ============================================================================
+
+/// Stop the server if it is running
+pub fn stop() {
+    let mut state = server_state().lock().unwrap();
+    if let Some(server) = state.server.take() {
+        server.unblock();
+        log::info!("Local API stopped");
+    }
+    state.error = None;
+}
+
+/// (Re)start the server with `config`; stops it when disabled
+pub fn apply_config(app: &AppHandle, config: &LocalApiConfig) -> Result<(), String> {
+    stop();
+    if !config.enabled {
+        return Ok(());
+    }
+    if config.token.is_empty() {
+        return Err("Local API token is not set".to_string());
+    }
+
+    let server = if config.lan_enabled {
+        load_or_create_certificate(app).and_then(|(certificate, private_key)| {
+            let ssl = SslConfig {
+                certificate: certificate.into_bytes(),
+                private_key: private_key.into_bytes(),
+            };
+            Server::https(("0.0.0.0", config.port), ssl).map_err(|e| e.to_string())
+        })
+    } else {
+        Server::http(("127.0.0.1", config.port)).map_err(|e| e.to_string())
+    };
+    let server = match server {
+        Ok(server) => Arc::new(server),
+        Err(e) => {
+            let error = format!("Failed to start local API on port {}: {}", config.port, e);
+            server_state().lock().unwrap().error = Some(error.clone());
+            return Err(error);
+        }
+    };
+    {
+        let mut state = server_state().lock().unwrap();
+        state.server = Some(server.clone());
+        state.port = config.port;
+    }
+
+    let app = app.clone();
+    let token = config.token.clone();
+    std::thread::spawn(move || {
+        for request in server.incoming_requests() {
+            handle_request(&app, &token, request);
+        }
+    });
+    if config.lan_enabled {
+        log::info!("Local API listening on LAN (https, 0.0.0.0:{})", config.port);
+    } else {
+        log::info!("Local API listening on 127.0.0.1:{}", config.port);
+    }
+    Ok(())
+}
+
+/// Start the server at app startup when enabled in settings
+pub async fn start_from_settings(app: &AppHandle) {
+    let result = match load_settings(&app.state::<DbState>()).await {
+        Ok(settings) => apply_config(app, &settings.local_api),
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        log::warn!("{}", e);
+    }
+}
+
+// ============================================================================
+// LAN Mode
+// ============================================================================
+
+/// Self-signed certificate for LAN mode, created on first use: (cert PEM, key PEM)
+fn load_or_create_certificate(app: &AppHandle) -> Result<(String, String), String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    let cert_path = dir.join(CERT_FILE);
+    let key_path = dir.join(KEY_FILE);
+    if let (Ok(cert), Ok(key)) = (fs::read_to_string(&cert_path), fs::read_to_string(&key_path)) {
+        restrict_to_owner(&key_path)
+            .map_err(|e| format!("Failed to restrict certificate key permissions: {}", e))?;
+        return Ok((cert, key));
+    }
+
+    let mut names = vec!["localhost".to_string()];
+    if let Some(ip) = lan_ip() {
+        names.push(ip.to_string());
+    }
+    let certified = rcgen::generate_simple_self_signed(names)
+        .map_err(|e| format!("Failed to create certificate: {}", e))?;
+    let cert = certified.cert.pem();
+    let key = certified.key_pair.serialize_pem();
+
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create app data dir: {}", e))?;
+    fs::write(&cert_path, &cert).map_err(|e| format!("Failed to save certificate: {}", e))?;
+    write_private_key(&key_path, &key).map_err(|e| format!("Failed to save certificate key: {}", e))?;
+    Ok((cert, key))
+}
+
+/// Write the key so that it is never readable by other users: a new file is
+/// created with mode 0600, an existing one is restricted before it is written
+fn write_private_key(path: &Path, key: &str) -> std::io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    restrict_to_owner(path)?;
+    file.write_all(key.as_bytes())?;
+    file.sync_all()
+}
+
+/// Make a file readable and writable by its owner only (no-op outside unix)
+fn restrict_to_owner(path: &Path) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if fs::metadata(path)?.permissions().mode() & 0o077 != 0 {
+            fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
+
+/// SHA-256 of the certificate (DER) as colon-separated hex, like browsers show it
+fn certificate_fingerprint(cert_pem: &str) -> Option<String> {
+    let body: String = cert_pem
+        .lines()
+        .filter(|line| !line.starts_with("-----"))
+        .collect();
+    let der = STANDARD.decode(body.trim()).ok()?;
+    let digest = Sha256::digest(&der);
+    Some(
+        digest
+            .iter()
+            .map(|b| format!("{:02X}", b))
+            .collect::<Vec<_>>()
+            .join(":"),
+    )
+}
+
+/// This machine's LAN address: the local end of a route to a public IP (nothing is sent)
+fn lan_ip() -> Option<IpAddr> {
+    let socket = UdpSocket::bind(("0.0.0.0", 0)).ok()?;
+    socket.connect(("8.8.8.8", 80)).ok()?;
+    let ip = socket.local_addr().ok()?.ip();
+    (!ip.is_loopback() && !ip.is_unspecified()).then_some(ip)
+}
+
+/// Settings record; a read failure is an error, never the defaults, since the
+/// caller may write part of them back
+async fn load_settings(state: &DbState) -> Result<AppSettings, String> {
+    let db = state.0.lock().await;
+    let records: Vec<Value> = db
+        .query("SELECT *, type::string(id) as id FROM settings:`app` LIMIT 1")
+        .await
+        .and_then(|mut result| result.take(0))
+        .map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(records
+        .into_iter()
+        .next()
+        .map(adapter::from_db_value)
+        .unwrap_or_default())
+}
+
+/// Update only the given top-level settings fields
+async fn merge_settings(state: &DbState, patch: Value) -> Result<(), String> {
+    let db = state.0.lock().await;
+    db.query("UPSERT settings:`app` MERGE $patch")
+        .bind(("patch", patch))
+        .await
+        .map_err(|e| format!("Failed to save settings: {}", e))?;
+    Ok(())
+}
+
+// ============================================================================
+// Request Handling
+// ============================================================================
+
+fn is_authorized(request: &Request, token: &str) -> bool {
+    let header = request
+        .headers()
+        .iter()
+        .find(|h| h.field.equiv("Authorization"))
+        .map(|h| h.value.as_str());
+    bearer_matches(header, token)
+}
+
+/// Whether an Authorization header carries `token`. The SHA-256 digests are
+/// compared in constant time, so response timing reveals nothing about the
+/// token to other hosts on the LAN.
+fn bearer_matches(header: Option<&str>, token: &str) -> bool {
+    let Some(header) = header else {
+        return false;
+    };
+    if token.is_empty() {
+        return false;
+    }
+    let expected = Sha256::digest(format!("Bearer {}", token).as_bytes());
+    let actual = Sha256::digest(header.as_bytes());
+    expected
+        .iter()
+        .zip(actual.iter())
+        .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+        == 0
+}
+
+fn handle_request(app: &AppHandle, token: &str, mut request: Request) {
+    if request.method() == &Method::Get && request.url() == "/" {
+        let header = Header::from_bytes("Content-Type", "text/html; charset=utf-8").unwrap();
+        let response = Response::from_string(CONTROL_PAGE).with_header(header);
+        if let Err(e) = request.respond(response) {
+            log::warn!("Failed to send local API response: {}", e);
+        }
+        return;
+    }
+
+    let result = if is_authorized(&request, token) {
+        let mut body = String::new();
+        match request
+            .as_reader()
+            .take(MAX_BODY_BYTES)
+            .read_to_string(&mut body)
+        {
+            Ok(_) => {
+                let method = request.method().clone();
+                let url = request.url().to_string();
+                tauri::async_runtime::block_on(route(app, &method, &url, &body))
+            }
+            Err(e) => Err(ApiError(400, format!("Failed to read request body: {}", e))),
+        }
+    } else {
+        Err(ApiError(401, "Missing or invalid API token".to_string()))
+    };
+
+    let (status, payload) = match result {
+        Ok(value) => (200, value),
+        Err(ApiError(status, message)) => (status, json!({ "error": message })),
+    };
+    let header = Header::from_bytes("Content-Type", "application/json").unwrap();
+    let response = Response::from_string(payload.to_string())
+        .with_status_code(status)
+        .with_header(header);
+    if let Err(e) = request.respond(response) {
+        log::warn!("Failed to send local API response: {}", e);
+    }
+}
+
+fn parse_body(body: &str) -> Result<Value, ApiError> {
+    if body.trim().is_empty() {
+        return Ok(json!({}));
+    }
+    serde_json::from_str(body).map_err(|e| ApiError(400, format!("Invalid JSON body: {}", e)))
+}
+
+fn query_param(query: &str, key: &str) -> Option<String> {
+    query.split('&').find_map(|pair| {
+        let (k, v) = pair.split_once('=')?;
+        (k == key).then(|| v.to_string())
+    })
+}
+
+fn to_json<T: serde::Serialize>(value: T) -> Result<Value, ApiError> {
+    serde_json::to_value(value).map_err(|e| ApiError(500, format!("Failed to serialize response: {}", e)))
+}
+
+async fn route(app: &AppHandle, method: &Method, url: &str, body: &str) -> Result<Value, ApiError> {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    // Jobs that touch config files are refused while a CLI session runs, unless ?force=true
+    let force = query_param(query, "force").is_some_and(|v| v == "true");
+
+    match (method, segments.as_slice()) {
+        (Method::Get, ["api", "health"]) => Ok(json!({
+            "ok": true,
+            "version": app.package_info().version.to_string(),
+        })),
+        (Method::Get, ["api", "providers", tool]) => list_providers(app, tool).await,
+        (Method::Post, ["api", "providers", "apply-everywhere"]) => {
+            let body = parse_body(body)?;
+            let provider_id = body
+                .get("provider_id")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| ApiError(400, "provider_id is required".to_string()))?
+                .to_string();
+            let targets: Vec<String> = body
+                .get("targets")
+                .and_then(|v| serde_json::from_value(v.clone()).ok())
+                .ok_or_else(|| ApiError(400, "targets must be a list of tool names".to_string()))?;
+            let model_id = body.get("model_id").and_then(|v| v.as_str()).map(String::from);
+            ensure_idle(app, "apply provider", force).await?;
+            let report = crate::coding::provider_apply::apply_provider_everywhere(
+                app.state(),
+                app.clone(),
+                provider_id,
+                targets,
+                model_id,
+            )
+            .await
+            .map_err(|e| ApiError(400, e))?;
+            to_json(report)
+        }
+        (Method::Post, ["api", "providers", tool, id, "apply"]) => {
+            ensure_idle(app, "apply provider", force).await?;
+            apply_provider(app, tool, id).await?;
+            Ok(json!({ "ok": true }))
+        }
+        (Method::Post, ["api", "backup"]) => {
+            ensure_idle(app, "backup", force).await?;
+            run_backup(app).await
+        }
+        (Method::Get, ["api", "usage"]) => {
+            let range = query_param(query, "range").unwrap_or_else(|| "30d".to_string());
+            let dashboard = crate::coding::usage::get_usage_dashboard(app.state(), range)
+                .await
+                .map_err(|e| ApiError(400, e))?;
+            to_json(dashboard)
+        }
+        _ => Err(ApiError(404, format!("Unknown endpoint: {} {}", method, path))),
+    }
+}
+
+/// 409 while a claude / opencode session is running (see session_guard.rs)
+async fn ensure_idle(app: &AppHandle, job: &str, force: bool) -> Result<(), ApiError> {
+    crate::session_guard::ensure_idle(&app.state::<DbState>(), job, force)
+        .await
+        .map_err(|e| ApiError(409, e.to_string()))
+}
+
+async fn list_providers(app: &AppHandle, tool: &str) -> Result<Value, ApiError> {
+    match tool {
+        "claude" => to_json(crate::coding::claude_code::list_claude_providers(app.state(), None, None).await?),
+        "codex" => to_json(crate::coding::codex::list_codex_providers(app.state()).await?),
+        other => Err(ApiError(404, format!("Unsupported tool: {}", other))),
+    }
+}
+
+async fn apply_provider(app: &AppHandle, tool: &str, id: &str) -> Result<(), ApiError> {
+    let state = app.state::<DbState>();
+    let db = state.0.lock().await;
+    match tool {
+        "claude" => crate::coding::claude_code::apply_config_internal(&db, app, id, false).await?,
+        "codex" => crate::coding::codex::apply_config_internal(&db, app, id, false).await?,
+        other => return Err(ApiError(404, format!("Unsupported tool: {}", other))),
+    }
+    Ok(())
+}
+
+/// Back up with the destination configured in settings
+async fn run_backup(app: &AppHandle) -> Result<Value, ApiError> {
+    let state = app.state::<DbState>();
+    let settings = load_settings(&state).await?;
+
+    let location = match settings.backup_type.as_str() {
+        "webdav" => {
+            let webdav = settings.webdav.clone();
+            crate::settings::backup::backup_to_webdav(
+                app.clone(),
+                app.state(),
+                webdav.url,
+                webdav.username,
+                webdav.password,
+                webdav.remote_path,
+                None,
+            )
+            .await?
+        }
+        "local" => {
+            if settings.local_backup_path.is_empty() {
+                return Err(ApiError(400, "Local backup path is not set".to_string()));
+            }
+            crate::settings::backup::backup_database(
+                app.clone(),
+                app.state(),
+                settings.local_backup_path.clone(),
+                None,
+            )
+            .await?
+        }
+        other => return Err(ApiError(400, format!("Backup type '{}' is not supported by the API", other))),
+    };
+
+    merge_settings(&state, json!({ "last_backup_time": chrono::Utc::now().to_rfc3339() })).await?;
+    Ok(json!({ "ok": true, "location": location }))
+}
+
+// ============================================================================
+// Local API Commands
+// ============================================================================
+
+/// Get local API settings and whether the server is running
+#[tauri::command]
+pub async fn get_local_api_status(
+    state: tauri::State<'_, DbState>,
+    app: AppHandle,
+) -> Result<LocalApiStatus, String> {
+    let config = load_settings(&state).await?.local_api;
+    let (lan_url, cert_fingerprint) = if config.lan_enabled {
+        let cert = app
+            .path()
+            .app_data_dir()
+            .ok()
+            .and_then(|dir| fs::read_to_string(dir.join(CERT_FILE)).ok());
+        (
+            lan_ip().map(|ip| format!("https://{}:{}/", ip, config.port)),
+            cert.as_deref().and_then(certificate_fingerprint),
+        )
+    } else {
+        (None, None)
+    };
+
+    let server_state = server_state().lock().unwrap();
+    Ok(LocalApiStatus {
+        enabled: config.enabled,
+        port: config.port,
+        token: config.token,
+        running: server_state.server.is_some() && server_state.port == config.port,
+        error: server_state.error.clone(),
+        lan_enabled: config.lan_enabled,
+        lan_url,
+        cert_fingerprint,
+    })
+}
+
+/// Enable/disable the local API, change its port, switch LAN mode or regenerate its token
+/// The server is restarted with the new settings.
+#[tauri::command]
+pub async fn set_local_api_config(
+    state: tauri::State<'_, DbState>,
+    app: AppHandle,
+    enabled: bool,
+    port: Option<u16>,
+    regenerate_token: Option<bool>,
+    lan_enabled: Option<bool>,
+) -> Result<LocalApiStatus, String> {
+    let mut config = load_settings(&state).await?.local_api;
+    config.enabled = enabled;
+    if let Some(lan_enabled) = lan_enabled {
+        config.lan_enabled = lan_enabled;
+    }
+    if let Some(port) = port.filter(|p| *p > 0) {
+        config.port = port;
+    }
+    if config.token.is_empty() || regenerate_token.unwrap_or(false) {
+        config.token = uuid::Uuid::new_v4().simple().to_string();
+    }
+    merge_settings(&state, json!({ "local_api": config })).await?;
+
+    // A port conflict is reported through the status rather than failing the save
+    let _ = apply_config(&app, &config);
+    get_local_api_status(state, app).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn private_key_is_only_readable_by_the_owner() {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        let dir = std::env::temp_dir().join(format!("aitb-local-api-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let new_key = dir.join("new.pem");
+        write_private_key(&new_key, "KEY").unwrap();
+        assert_eq!(mode(&new_key), 0o600);
+
+        let old_key = dir.join("old.pem");
+        fs::write(&old_key, "OLD").unwrap();
+        fs::set_permissions(&old_key, fs::Permissions::from_mode(0o644)).unwrap();
+        write_private_key(&old_key, "KEY").unwrap();
+        assert_eq!(mode(&old_key), 0o600);
+        assert_eq!(fs::read_to_string(&old_key).unwrap(), "KEY");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn query_param_finds_key() {
+        assert_eq!(query_param("range=7d&x=1", "range"), Some("7d".to_string()));
+        assert_eq!(query_param("x=1", "range"), None);
+        assert_eq!(query_param("", "range"), None);
+    }
+
+    #[test]
+    fn bearer_token_must_match() {
+        assert!(bearer_matches(Some("Bearer s3cret-token"), "s3cret-token"));
+        assert!(!bearer_matches(Some("Bearer wrong-token"), "s3cret-token"));
+        assert!(!bearer_matches(Some("s3cret-token"), "s3cret-token"));
+        assert!(!bearer_matches(None, "s3cret-token"));
+        assert!(!bearer_matches(Some("Bearer "), ""));
+    }
+
+    #[test]
+    fn certificate_fingerprint_hashes_der() {
+        // "hello" base64-encoded as a fake PEM body
+        let pem = "-----BEGIN CERTIFICATE-----\naGVsbG8=\n-----END CERTIFICATE-----\n";
+        let fingerprint = certificate_fingerprint(pem).unwrap();
+        assert!(fingerprint.starts_with("2C:F2:4D:BA:5F:B0:A3:0E"));
+        assert_eq!(fingerprint.split(':').count(), 32);
+    }
+}
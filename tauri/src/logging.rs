@@ -0,0 +1,186 @@
+//! Logging
+//!
+//! `log` records from the app and its dependencies are bridged into
+//! `tracing` and written to a daily rotating file in the logs folder
+//! (7 files kept). Debug builds also log to stderr. The level comes from the
+//! `log_level` setting and can change at runtime.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, reload, EnvFilter, Registry};
+
+/// Log file name prefix; files are named `ai-toolbox.YYYY-MM-DD.log`
+const LOG_FILE_PREFIX: &str = "ai-toolbox";
+const MAX_LOG_FILES: usize = 7;
+pub const DEFAULT_LOG_LEVEL: &str = "info";
+const LOG_LEVELS: [&str; 5] = ["error", "warn", "info", "debug", "trace"];
+
+/// Default and maximum number of lines returned by `get_recent_logs`
+const DEFAULT_RECENT_LINES: usize = 200;
+const MAX_RECENT_LINES: usize = 5000;
+
+static FILTER_HANDLE: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
+/// Logs folder: `<data dir>/com.ai-toolbox/logs`
+pub fn log_dir() -> Option<PathBuf> {
+    dirs::data_dir()
+        .map(|p| p.join("com.ai-toolbox").join("logs"))
+        .or_else(|| dirs::home_dir().map(|p| p.join(".ai-toolbox").join("logs")))
+}
+
+/// Filter directive for a level: debug/trace only apply to our own crate,
+/// dependencies (surrealdb, hyper, ...) stay at info
+fn directive_for(level: &str) -> String {
+    let level = if LOG_LEVELS.contains(&level) { level } else { DEFAULT_LOG_LEVEL };
+    match level {
+        "debug" | "trace" => format!("info,ai_toolbox_lib={}", level),
+        _ => level.to_string(),
+    }
+}
+
+fn filter_for(level: &str) -> EnvFilter {
+    EnvFilter::new(directive_for(level))
+}
+
+/// Initialize logging; returns the logs folder
+pub fn init() -> Option<PathBuf> {
+    let log_dir = log_dir()?;
+    if let Err(e) = fs::create_dir_all(&log_dir) {
+        eprintln!("无法创建日志目录: {}", e);
+        return None;
+    }
+
+    let appender = match RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_FILE_PREFIX)
+        .filename_suffix("log")
+        .max_log_files(MAX_LOG_FILES)
+        .build(&log_dir)
+    {
+        Ok(appender) => appender,
+        Err(e) => {
+            eprintln!("无法打开日志文件: {}", e);
+            return None;
+        }
+    };
+
+    let (filter, handle) = reload::Layer::new(filter_for(DEFAULT_LOG_LEVEL));
+    let stderr_layer = cfg!(debug_assertions).then(|| fmt::layer().with_writer(std::io::stderr));
+
+    if let Err(e) = tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt::layer().with_ansi(false).with_writer(appender))
+        .with(stderr_layer)
+        .try_init()
+    {
+        eprintln!("日志系统初始化失败: {}", e);
+        return None;
+    }
+    let _ = FILTER_HANDLE.set(handle);
+
+    Some(log_dir)
+}
+
+/// Apply the `log_level` setting
+pub fn set_level(level: &str) {
+    if let Some(handle) = FILTER_HANDLE.get() {
+        if let Err(e) = handle.reload(filter_for(level)) {
+            log::warn!("Failed to change log level: {}", e);
+        }
+    }
+}
+
+/// Log files, newest first
+fn log_files(log_dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<(std::time::SystemTime, PathBuf)> = fs::read_dir(log_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|path| {
+                    path.file_name()
+                        .map(|n| n.to_string_lossy().starts_with(LOG_FILE_PREFIX))
+                        .unwrap_or(false)
+                        && path.extension().map(|ext| ext == "log").unwrap_or(false)
+                })
+                .filter_map(|path| {
+                    let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+                    Some((modified, path))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort_by(|a, b| b.0.cmp(&a.0));
+    files.into_iter().map(|(_, path)| path).collect()
+}
+
+/// Last `count` lines across the log files, oldest first
+fn tail_lines(files: &[PathBuf], count: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for file in files {
+        if lines.len() >= count {
+            break;
+        }
+        let content = match fs::read(file) {
+            Ok(bytes) => String::from_utf8_lossy(&bytes).to_string(),
+            Err(_) => continue,
+        };
+        let needed = count - lines.len();
+        let file_lines: Vec<&str> = content.lines().collect();
+        let start = file_lines.len().saturating_sub(needed);
+        // Older file goes in front of what was already collected
+        let mut chunk: Vec<String> = file_lines[start..].iter().map(|l| l.to_string()).collect();
+        chunk.append(&mut lines);
+        lines = chunk;
+    }
+    lines
+}
+
+// ============================================================================
+// Logging Commands
+// ============================================================================
+
+/// Get the last `lines` log lines (default 200), oldest first
+#[tauri::command]
+pub fn get_recent_logs(lines: Option<usize>) -> Result<Vec<String>, String> {
+    let log_dir = log_dir().ok_or_else(|| "Failed to get log directory".to_string())?;
+    let count = lines.unwrap_or(DEFAULT_RECENT_LINES).clamp(1, MAX_RECENT_LINES);
+    Ok(tail_lines(&log_files(&log_dir), count))
+}
+
+/// Open the logs folder in the system file manager
+#[tauri::command]
+pub fn open_log_folder() -> Result<(), String> {
+    let log_dir = log_dir().ok_or_else(|| "Failed to get log directory".to_string())?;
+    crate::open_folder(log_dir.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tail_lines_spans_files_newest_first() {
+        let dir = std::env::temp_dir().join(format!("ai-toolbox-log-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let older = dir.join("ai-toolbox.2026-01-01.log");
+        let newer = dir.join("ai-toolbox.2026-01-02.log");
+        fs::write(&older, "a\nb\nc\n").unwrap();
+        fs::write(&newer, "d\ne\n").unwrap();
+
+        let lines = tail_lines(&[newer, older], 3);
+        assert_eq!(lines, vec!["c", "d", "e"]);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn unknown_level_falls_back_to_default() {
+        assert_eq!(directive_for("verbose"), "info");
+        assert_eq!(directive_for("debug"), "info,ai_toolbox_lib=debug");
+    }
+}
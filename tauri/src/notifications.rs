@@ -0,0 +1,133 @@
+//! Desktop Notifications
+//!
+//! Single entry point for desktop notifications. Each notification belongs to
+//! a category that can be switched off in settings (`notifications`); recent
+//! actions without a dedicated category follow `action_notifications`.
+
+use std::sync::OnceLock;
+
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::db::DbState;
+use crate::settings::types::AppSettings;
+
+/// Handle for code paths that run without one (e.g. background cache refresh)
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationCategory {
+    /// Backup finished or failed
+    Backup,
+    /// New app version available
+    Update,
+    /// Provider connectivity test failed
+    ProviderHealth,
+    /// New free models appeared in the models.dev data
+    FreeModels,
+    /// A plan subscription renews soon
+    Renewal,
+    /// Monthly usage cost crossed a budget threshold
+    Budget,
+    /// Other recent actions (provider switched, CLI launched, ...)
+    Activity,
+}
+
+impl NotificationCategory {
+    /// Category for an activity log kind
+    pub fn for_activity(kind: &str) -> Self {
+        match kind {
+            "backup" => Self::Backup,
+            "update" => Self::Update,
+            _ => Self::Activity,
+        }
+    }
+
+    fn enabled_in(self, settings: &AppSettings) -> bool {
+        match self {
+            Self::Backup => settings.notifications.backup,
+            Self::Update => settings.notifications.update,
+            Self::ProviderHealth => settings.notifications.provider_health,
+            Self::FreeModels => settings.notifications.free_models,
+            Self::Renewal => settings.notifications.renewal,
+            Self::Budget => settings.notifications.budget,
+            Self::Activity => settings.action_notifications,
+        }
+    }
+}
+
+/// Remember the app handle (called from setup)
+pub fn init(app: &AppHandle) {
+    let _ = APP_HANDLE.set(app.clone());
+}
+
+async fn load_settings<R: Runtime>(app: &AppHandle<R>) -> Option<AppSettings> {
+    let state = app.try_state::<DbState>()?;
+    let db = state.0.lock().await;
+    let records: Vec<serde_json::Value> = db
+        .query("SELECT *, type::string(id) as id FROM settings:`app` LIMIT 1")
+        .await
+        .and_then(|mut result| result.take(0))
+        .ok()?;
+    Some(crate::settings::adapter::from_db_value(
+        records.into_iter().next().unwrap_or_default(),
+    ))
+}
+
+fn show<R: Runtime>(app: &AppHandle<R>, title: &str, body: &str) -> Result<(), String> {
+    app.notification()
+        .builder()
+        .title(title)
+        .body(body)
+        .show()
+        .map_err(|e| format!("Failed to show notification: {}", e))
+}
+
+/// Show a notification if its category is enabled
+pub fn notify<R: Runtime>(
+    app: &AppHandle<R>,
+    category: NotificationCategory,
+    title: impl Into<String>,
+    body: impl Into<String>,
+) {
+    let app = app.clone();
+    let title = title.into();
+    let body = body.into();
+    tauri::async_runtime::spawn(async move {
+        let settings = load_settings(&app).await.unwrap_or_default();
+        if !category.enabled_in(&settings) {
+            return;
+        }
+        if let Err(e) = show(&app, &title, &body) {
+            log::warn!("{}", e);
+        }
+    });
+}
+
+/// Same as `notify`, for callers without an app handle
+pub fn notify_global(category: NotificationCategory, title: impl Into<String>, body: impl Into<String>) {
+    if let Some(app) = APP_HANDLE.get() {
+        notify(app, category, title, body);
+    }
+}
+
+/// Show a test notification regardless of the category toggles
+#[tauri::command]
+pub fn send_test_notification(app: AppHandle) -> Result<(), String> {
+    show(&app, "AI Toolbox", "这是一条测试通知")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn activity_kinds_map_to_categories() {
+        let mut settings = AppSettings::default();
+        settings.notifications.backup = false;
+        assert_eq!(NotificationCategory::for_activity("backup"), NotificationCategory::Backup);
+        assert!(!NotificationCategory::for_activity("backup").enabled_in(&settings));
+        assert!(NotificationCategory::for_activity("update").enabled_in(&settings));
+        assert!(!NotificationCategory::for_activity("provider").enabled_in(&settings));
+    }
+}
@@ -0,0 +1,367 @@
+//! Secret Scanning
+//!
+//! Looks for API keys in data about to leave the machine: provider share
+//! strings and backups (which are not encrypted). The `secret_scan_mode`
+//! setting decides what a hit does: "warn" (default) reports it and carries
+//! on, "block" stops until the user confirms, "off" skips scanning.
+//! Findings name the exact location and only show a masked preview.
+
+use std::io::{Cursor, Read};
+use std::sync::OnceLock;
+
+use regex::bytes::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::db::DbState;
+use crate::error::{AppError, AppResult};
+
+/// Larger zip entries (e.g. big skill assets) are not scanned
+const MAX_ENTRY_BYTES: u64 = 20 * 1024 * 1024;
+/// Locations listed in the error message; the rest are counted
+const MAX_LISTED_LOCATIONS: usize = 5;
+
+/// (kind, pattern), most specific first so e.g. Anthropic keys are not reported as OpenAI
+const PATTERNS: &[(&str, &str)] = &[
+    ("anthropic", r"sk-ant-[A-Za-z0-9_\-]{20,}"),
+    ("openrouter", r"sk-or-v1-[A-Fa-f0-9]{32,}"),
+    ("openai", r"sk-[A-Za-z0-9_\-]{20,}"),
+    ("google", r"AIza[0-9A-Za-z_\-]{35}"),
+    ("github", r"(?:gh[pousr]_[A-Za-z0-9]{36,}|github_pat_[A-Za-z0-9_]{22,})"),
+    ("aws", r"AKIA[0-9A-Z]{16}"),
+    ("slack", r"xox[abprs]-[A-Za-z0-9\-]{10,}"),
+    ("huggingface", r"hf_[A-Za-z0-9]{30,}"),
+];
+
+/// JSON field names whose long values are treated as keys even without a known prefix
+const SECRET_FIELD_PATTERN: &str = r"(?i)(api[_-]?key|auth[_-]?token|access[_-]?token|secret|password)";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecretFinding {
+    /// "source:$.json.path", "source:line:column" or "source:offset N" for binary files
+    pub location: String,
+    /// Pattern that matched, e.g. "anthropic", "openai", "generic"
+    pub kind: String,
+    /// Masked key, e.g. "sk-ant…9f2c"
+    pub preview: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanMode {
+    Off,
+    Warn,
+    Block,
+}
+
+impl ScanMode {
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "off" => Self::Off,
+            "block" => Self::Block,
+            _ => Self::Warn,
+        }
+    }
+}
+
+fn patterns() -> &'static [(&'static str, Regex)] {
+    static PATTERNS_RE: OnceLock<Vec<(&'static str, Regex)>> = OnceLock::new();
+    PATTERNS_RE.get_or_init(|| {
+        PATTERNS
+            .iter()
+            .map(|(kind, pattern)| (*kind, Regex::new(&format!(r"(?-u:\b){}", pattern)).unwrap()))
+            .collect()
+    })
+}
+
+fn secret_field() -> &'static regex::Regex {
+    static FIELD_RE: OnceLock<regex::Regex> = OnceLock::new();
+    FIELD_RE.get_or_init(|| regex::Regex::new(SECRET_FIELD_PATTERN).unwrap())
+}
+
+/// Whether a field name looks like it holds a key, token or password
+pub fn is_secret_field(field: &str) -> bool {
+    secret_field().is_match(field)
+}
+
+/// Keep the first 6 and last 4 characters
+pub fn mask(secret: &str) -> String {
+    let chars: Vec<char> = secret.chars().collect();
+    if chars.len() <= 12 {
+        return "…".to_string();
+    }
+    let head: String = chars[..6].iter().collect();
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("{}…{}", head, tail)
+}
+
+/// Non-overlapping pattern hits as (byte offset, kind, key), in order
+pub fn find_keys(data: &[u8]) -> Vec<(usize, &'static str, String)> {
+    let mut hits: Vec<(usize, usize, &'static str)> = Vec::new();
+    for (kind, re) in patterns() {
+        for m in re.find_iter(data) {
+            if hits.iter().any(|(start, end, _)| m.start() < *end && *start < m.end()) {
+                continue;
+            }
+            hits.push((m.start(), m.end(), *kind));
+        }
+    }
+    hits.sort_by_key(|(start, _, _)| *start);
+    hits.into_iter()
+        .map(|(start, end, kind)| (start, kind, String::from_utf8_lossy(&data[start..end]).into_owned()))
+        .collect()
+}
+
+/// Whether a value under a secret-looking field is a real key rather than a reference or placeholder
+fn looks_like_secret(value: &str) -> bool {
+    let value = value.trim();
+    value.len() >= 16
+        && !value.contains(char::is_whitespace)
+        && !value.starts_with("vault://")
+        && !value.starts_with("${")
+        && !value.starts_with("{env:")
+}
+
+fn finding(location: String, kind: &str, key: &str) -> SecretFinding {
+    SecretFinding {
+        location,
+        kind: kind.to_string(),
+        preview: mask(key),
+    }
+}
+
+/// Scan plain text; locations are "source:line:column"
+pub fn scan_text(source: &str, text: &str) -> Vec<SecretFinding> {
+    find_keys(text.as_bytes())
+        .into_iter()
+        .map(|(offset, kind, key)| {
+            let before = &text[..offset];
+            let line = before.matches('\n').count() + 1;
+            let line_start = before.rfind('\n').map(|pos| pos + 1).unwrap_or(0);
+            let column = before[line_start..].chars().count() + 1;
+            finding(format!("{}:{}:{}", source, line, column), kind, &key)
+        })
+        .collect()
+}
+
+/// Scan the string values of a JSON document; locations are "source:$.path"
+pub fn scan_value(source: &str, value: &Value) -> Vec<SecretFinding> {
+    let mut findings = Vec::new();
+    walk_value(source, "$", None, value, &mut findings);
+    findings
+}
+
+fn walk_value(source: &str, path: &str, field: Option<&str>, value: &Value, out: &mut Vec<SecretFinding>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                walk_value(source, &format!("{}.{}", path, key), Some(key.as_str()), child, out);
+            }
+        }
+        Value::Array(items) => {
+            for (index, child) in items.iter().enumerate() {
+                walk_value(source, &format!("{}[{}]", path, index), field, child, out);
+            }
+        }
+        Value::String(text) => {
+            let location = format!("{}:{}", source, path);
+            let hits = find_keys(text.as_bytes());
+            if hits.is_empty() {
+                if field.is_some_and(|f| secret_field().is_match(f)) && looks_like_secret(text) {
+                    out.push(finding(location, "generic", text.trim()));
+                }
+            } else {
+                out.extend(hits.into_iter().map(|(_, kind, key)| finding(location.clone(), kind, &key)));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// `${NAME}` placeholder for a key found under `field`
+fn placeholder(field: Option<&str>) -> String {
+    let name: String = field
+        .unwrap_or("API_KEY")
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect();
+    format!("${{{}}}", name)
+}
+
+/// Whether a string still carries a placeholder left by `redact_value`
+pub fn has_placeholder(text: &str) -> bool {
+    text.contains("${")
+}
+
+/// Replace the keys in the string values of a JSON document with `${FIELD}`
+/// placeholders, using the same rules as `scan_value`; returns how many were replaced
+pub fn redact_value(value: &mut Value) -> usize {
+    redact_walk(None, value)
+}
+
+fn redact_walk(field: Option<&str>, value: &mut Value) -> usize {
+    match value {
+        Value::Object(map) => map.iter_mut().map(|(key, child)| redact_walk(Some(key.as_str()), child)).sum(),
+        Value::Array(items) => items.iter_mut().map(|child| redact_walk(field, child)).sum(),
+        Value::String(text) => {
+            let hits = find_keys(text.as_bytes());
+            if hits.is_empty() {
+                if field.is_some_and(|f| secret_field().is_match(f)) && looks_like_secret(text) {
+                    *text = placeholder(field);
+                    return 1;
+                }
+                return 0;
+            }
+            for (_, _, key) in &hits {
+                *text = text.replace(key.as_str(), &placeholder(field));
+            }
+            hits.len()
+        }
+        _ => 0,
+    }
+}
+
+/// Scan one file: JSON by path, other text by line, binary data (the database) by byte offset
+pub fn scan_bytes(source: &str, data: &[u8]) -> Vec<SecretFinding> {
+    match std::str::from_utf8(data) {
+        Ok(text) => match serde_json::from_str::<Value>(text) {
+            Ok(value) if value.is_object() || value.is_array() => scan_value(source, &value),
+            _ => scan_text(source, text),
+        },
+        Err(_) => find_keys(data)
+            .into_iter()
+            .map(|(offset, kind, key)| finding(format!("{}:offset {}", source, offset), kind, &key))
+            .collect(),
+    }
+}
+
+/// Scan every file of a backup zip; locations start with the entry name
+pub fn scan_zip(bytes: &[u8]) -> AppResult<Vec<SecretFinding>> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).map_err(AppError::invalid)?;
+    let mut findings = Vec::new();
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index).map_err(AppError::invalid)?;
+        if entry.is_dir() || entry.size() > MAX_ENTRY_BYTES {
+            continue;
+        }
+        let name = entry.name().to_string();
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data)?;
+        findings.extend(scan_bytes(&name, &data));
+    }
+    Ok(findings)
+}
+
+/// The `secret_scan_mode` setting (default "warn")
+pub async fn scan_mode(state: &DbState) -> ScanMode {
+    let db = state.0.lock().await;
+    let records: Vec<Value> = match db.query("SELECT secret_scan_mode FROM settings:`app` LIMIT 1").await {
+        Ok(mut response) => response.take(0).unwrap_or_default(),
+        Err(_) => Vec::new(),
+    };
+    records
+        .first()
+        .and_then(|record| record.get("secret_scan_mode"))
+        .and_then(|mode| mode.as_str())
+        .map(ScanMode::parse)
+        .unwrap_or(ScanMode::Warn)
+}
+
+/// Short list of locations for messages, e.g. "a:$.api_key (openai); b:3:7 (anthropic) …"
+pub fn describe(findings: &[SecretFinding]) -> String {
+    let mut listed: Vec<String> = findings
+        .iter()
+        .take(MAX_LISTED_LOCATIONS)
+        .map(|f| format!("{} ({})", f.location, f.kind))
+        .collect();
+    if findings.len() > MAX_LISTED_LOCATIONS {
+        listed.push(format!("+{}", findings.len() - MAX_LISTED_LOCATIONS));
+    }
+    listed.join("; ")
+}
+
+/// Stop the operation when blocking is on, keys were found and the user has not confirmed
+pub fn enforce(mode: ScanMode, findings: &[SecretFinding], allow_secrets: bool) -> AppResult<()> {
+    if mode != ScanMode::Block || allow_secrets || findings.is_empty() {
+        return Ok(());
+    }
+    Err(AppError::conflict(
+        "SECRETS_DETECTED",
+        &[("count", &findings.len().to_string()), ("locations", &describe(findings))],
+    ))
+}
+
+// ============================================================================
+// Secret Scan Commands
+// ============================================================================
+
+/// Scan pasted text or JSON, e.g. before sharing a config by hand
+#[tauri::command]
+pub fn scan_for_secrets(text: String) -> Vec<SecretFinding> {
+    scan_bytes("input", text.as_bytes())
+}
+
+/// Scan what a backup would contain right now without writing it anywhere
+#[tauri::command]
+pub async fn preview_backup_secrets(app_handle: tauri::AppHandle) -> AppResult<Vec<SecretFinding>> {
+    use crate::settings::backup::utils::{create_backup_zip, get_db_path};
+
+    let db_path = get_db_path(&app_handle).map_err(AppError::io)?;
+    let zip_data = create_backup_zip(&app_handle, &db_path).map_err(AppError::io)?;
+    scan_zip(&zip_data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefers_specific_patterns_and_masks() {
+        let findings = scan_text("a.env", "X=1\nKEY=sk-ant-REDACTED\n");
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].kind, "anthropic");
+        assert_eq!(findings[0].location, "a.env:2:5");
+        assert_eq!(findings[0].preview, "sk-ant…wxyz");
+    }
+
+    #[test]
+    fn scan_value_reports_json_paths() {
+        let value = serde_json::json!({
+            "env": { "ANTHROPIC_AUTH_TOKEN": "relay-token-0123456789abcdef" },
+            "options": { "apiKey": "vault://relay" },
+            "headers": ["Bearer sk-proj-abcdefghijklmnopqrstuvwx"],
+            "description": "a task-list entry"
+        });
+        let findings = scan_value("share", &value);
+        let locations: Vec<&str> = findings.iter().map(|f| f.location.as_str()).collect();
+        assert_eq!(locations, vec!["share:$.env.ANTHROPIC_AUTH_TOKEN", "share:$.headers[0]"]);
+        assert_eq!(findings[0].kind, "generic");
+        assert_eq!(findings[1].kind, "openai");
+    }
+
+    #[test]
+    fn redact_value_replaces_keys_with_placeholders() {
+        let mut value = serde_json::json!({
+            "env": {
+                "ANTHROPIC_AUTH_TOKEN": "relay-token-0123456789abcdef",
+                "ANTHROPIC_BASE_URL": "https://relay.example.com"
+            },
+            "apiKeyHelper": "echo sk-proj-abcdefghijklmnopqrstuvwx",
+            "options": { "apiKey": "vault://relay" }
+        });
+        assert_eq!(redact_value(&mut value), 2);
+        assert_eq!(value["env"]["ANTHROPIC_AUTH_TOKEN"], "${ANTHROPIC_AUTH_TOKEN}");
+        assert_eq!(value["env"]["ANTHROPIC_BASE_URL"], "https://relay.example.com");
+        assert_eq!(value["apiKeyHelper"], "echo ${APIKEYHELPER}");
+        assert_eq!(value["options"]["apiKey"], "vault://relay");
+        assert!(scan_value("x", &value).is_empty());
+    }
+
+    #[test]
+    fn enforce_only_blocks_in_block_mode() {
+        let findings = scan_text("x", "sk-abcdefghijklmnopqrstuvwxyz");
+        assert!(enforce(ScanMode::Warn, &findings, false).is_ok());
+        assert!(enforce(ScanMode::Block, &findings, true).is_ok());
+        assert!(enforce(ScanMode::Block, &findings, false).is_err());
+        assert!(enforce(ScanMode::Block, &[], false).is_ok());
+    }
+}
@@ -0,0 +1,194 @@
+//! CLI Session Guard
+//!
+//! Scheduled work (backups triggered through the local API, provider applies
+//! from scripts, the post-restore resync) rewrites config files. Doing that
+//! while a `claude` or `opencode` session is running swaps the files under the
+//! live agent, so those jobs check here first and are deferred unless forced.
+//! Controlled by the `defer_jobs_during_sessions` setting (default: on).
+
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+use crate::db::DbState;
+use crate::error::{AppError, AppResult};
+
+/// Windows CREATE_NO_WINDOW flag to prevent console window from appearing
+#[cfg(target_os = "windows")]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// CLIs whose config files scheduled jobs touch (tool key = executable name)
+const SESSION_TOOLS: [&str; 2] = ["claude", "opencode"];
+/// Interpreters the npm-installed CLIs run under
+const SCRIPT_HOSTS: [&str; 2] = ["node", "bun"];
+
+/// How often a deferred job checks whether the sessions have ended
+pub const RETRY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// A running CLI process
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ActiveSession {
+    pub tool: String,
+    pub pid: u32,
+}
+
+/// (pid, command line) of every process; only the image name on Windows
+fn list_processes() -> Vec<(u32, String)> {
+    #[cfg(target_os = "windows")]
+    let output = Command::new("tasklist")
+        .args(["/FO", "CSV", "/NH"])
+        .stdin(Stdio::null())
+        .creation_flags(CREATE_NO_WINDOW)
+        .output();
+    #[cfg(not(target_os = "windows"))]
+    let output = Command::new("ps")
+        .args(["-A", "-o", "pid=,args="])
+        .stdin(Stdio::null())
+        .output();
+
+    match output {
+        Ok(output) => parse_process_list(&String::from_utf8_lossy(&output.stdout)),
+        Err(e) => {
+            log::warn!("Failed to list processes: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Parse `ps -o pid=,args=` lines or `tasklist /FO CSV` rows
+fn parse_process_list(text: &str) -> Vec<(u32, String)> {
+    text.lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if line.starts_with('"') {
+                let fields: Vec<&str> = line.split("\",\"").map(|f| f.trim_matches('"')).collect();
+                Some((fields.get(1)?.parse().ok()?, fields.first()?.to_string()))
+            } else {
+                let (pid, comm) = line.split_once(char::is_whitespace)?;
+                Some((pid.parse().ok()?, comm.trim().to_string()))
+            }
+        })
+        .collect()
+}
+
+fn base_name(path: &str) -> String {
+    let name = path.rsplit(['/', '\\']).next().unwrap_or(path).to_lowercase();
+    name.strip_suffix(".exe").map(String::from).unwrap_or(name)
+}
+
+/// Match a command line against the known CLIs: `claude ...`,
+/// `/usr/local/bin/opencode` or `node /usr/local/bin/claude ...`
+fn session_tool(command_line: &str) -> Option<&'static str> {
+    let mut tokens = command_line.split_whitespace();
+    let mut name = base_name(tokens.next()?);
+    if SCRIPT_HOSTS.contains(&name.as_str()) {
+        name = base_name(tokens.next()?);
+    }
+    SESSION_TOOLS.iter().find(|tool| name == **tool).copied()
+}
+
+/// Running claude / opencode processes
+pub fn active_sessions() -> Vec<ActiveSession> {
+    let own_pid = std::process::id();
+    list_processes()
+        .into_iter()
+        .filter(|(pid, _)| *pid != own_pid)
+        .filter_map(|(pid, name)| {
+            session_tool(&name).map(|tool| ActiveSession {
+                tool: tool.to_string(),
+                pid,
+            })
+        })
+        .collect()
+}
+
+async fn guard_enabled(state: &DbState) -> bool {
+    let db = state.0.lock().await;
+    let records: Vec<Value> = match db
+        .query("SELECT defer_jobs_during_sessions FROM settings:`app` LIMIT 1")
+        .await
+    {
+        Ok(mut response) => response.take(0).unwrap_or_default(),
+        Err(_) => Vec::new(),
+    };
+    records
+        .first()
+        .and_then(|record| record.get("defer_jobs_during_sessions"))
+        .and_then(|value| value.as_bool())
+        .unwrap_or(true)
+}
+
+/// Sessions that block `job` right now; empty when it may run
+pub async fn blocking_sessions(state: &DbState, force: bool) -> Vec<ActiveSession> {
+    if force || !guard_enabled(state).await {
+        return Vec::new();
+    }
+    tauri::async_runtime::spawn_blocking(active_sessions)
+        .await
+        .unwrap_or_default()
+}
+
+/// Fail with CLI_SESSION_ACTIVE while a session is live, unless `force` is set
+pub async fn ensure_idle(state: &DbState, job: &str, force: bool) -> AppResult<()> {
+    let sessions = blocking_sessions(state, force).await;
+    if sessions.is_empty() {
+        return Ok(());
+    }
+    let mut tools: Vec<&str> = sessions.iter().map(|s| s.tool.as_str()).collect();
+    tools.sort_unstable();
+    tools.dedup();
+    log::info!("Deferring {} while {} is running", job, tools.join(", "));
+    Err(AppError::conflict(
+        "CLI_SESSION_ACTIVE",
+        &[("job", job), ("tools", &tools.join(", "))],
+    ))
+}
+
+/// Wait until no session is live (for background jobs that must run eventually)
+pub async fn wait_until_idle(state: &DbState, job: &str) {
+    while ensure_idle(state, job, false).await.is_err() {
+        tokio::time::sleep(RETRY_INTERVAL).await;
+    }
+}
+
+// ============================================================================
+// Session Guard Commands
+// ============================================================================
+
+/// Running claude / opencode sessions
+#[tauri::command]
+pub async fn get_active_cli_sessions() -> AppResult<Vec<ActiveSession>> {
+    tauri::async_runtime::spawn_blocking(active_sessions)
+        .await
+        .map_err(AppError::io)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_ps_and_tasklist_output() {
+        let ps = "  1 /sbin/launchd\n 4321 claude --resume\n 999 /usr/local/bin/opencode\n 42 node /usr/lib/node_modules/.bin/claude\n";
+        let sessions: Vec<(u32, Option<&str>)> = parse_process_list(ps)
+            .iter()
+            .map(|(pid, name)| (*pid, session_tool(name)))
+            .collect();
+        assert_eq!(
+            sessions,
+            vec![(1, None), (4321, Some("claude")), (999, Some("opencode")), (42, Some("claude"))]
+        );
+
+        let tasklist = "\"opencode.exe\",\"5120\",\"Console\",\"1\",\"80,000 K\"\n\"claude-helper.exe\",\"7\",\"Console\",\"1\",\"1 K\"";
+        let sessions: Vec<(u32, Option<&str>)> = parse_process_list(tasklist)
+            .iter()
+            .map(|(pid, name)| (*pid, session_tool(name)))
+            .collect();
+        assert_eq!(sessions, vec![(5120, Some("opencode")), (7, None)]);
+    }
+}
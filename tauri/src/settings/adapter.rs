@@ -6,7 +6,10 @@
  */
 
 use serde_json::{json, Value};
-use super::types::{AppSettings, WebDAVConfig, S3Config};
+use super::types::{
+    AppSettings, HostOverride, HttpClientConfig, LocalApiConfig, NotificationSettings, S3Config, TrayMenuConfig,
+    TrayPinnedProvider, WebDAVConfig,
+};
 
 /// Convert database JSON Value to AppSettings with fault tolerance
 /// Missing fields will use default values, never panics
@@ -24,8 +27,23 @@ pub fn from_db_value(value: Value) -> AppSettings {
         last_backup_time: get_opt_str(&value, "last_backup_time"),
         launch_on_startup: get_bool(&value, "launch_on_startup", true),
         minimize_to_tray_on_close: get_bool(&value, "minimize_to_tray_on_close", true),
+        start_minimized: get_bool(&value, "start_minimized", false),
         proxy_url: get_str(&value, "proxy_url", ""),
         theme: get_str(&value, "theme", "system"),
+        host_overrides: get_host_overrides(&value),
+        http: get_http_config(&value),
+        action_notifications: get_bool(&value, "action_notifications", false),
+        notifications: get_notification_settings(&value),
+        tray_menu: get_tray_menu_config(&value),
+        launch_projects: get_str_list(&value, "launch_projects"),
+        default_terminal: get_str(&value, "default_terminal", ""),
+        local_api: get_local_api_config(&value),
+        log_level: get_str(&value, "log_level", "info"),
+        telemetry_enabled: get_bool(&value, "telemetry_enabled", false),
+        secret_scan_mode: get_str(&value, "secret_scan_mode", "warn"),
+        defer_jobs_during_sessions: get_bool(&value, "defer_jobs_during_sessions", true),
+        provider_template_url: get_str(&value, "provider_template_url", ""),
+        claude_config_dir: get_str(&value, "claude_config_dir", ""),
     }
 }
 
@@ -34,7 +52,7 @@ pub fn to_db_value(settings: &AppSettings) -> Value {
     // Use serde to serialize the entire structure
     // This ensures all types are properly converted
     serde_json::to_value(settings).unwrap_or_else(|e| {
-        eprintln!("Failed to serialize settings: {}", e);
+        log::warn!("Failed to serialize settings: {}", e);
         json!({})
     })
 }
@@ -56,6 +74,19 @@ fn get_opt_str(value: &Value, key: &str) -> Option<String> {
         .map(String::from)
 }
 
+fn get_str_list(value: &Value, key: &str) -> Vec<String> {
+    value
+        .get(key)
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 fn get_bool(value: &Value, key: &str, default: bool) -> bool {
     value
         .get(key)
@@ -63,6 +94,13 @@ fn get_bool(value: &Value, key: &str, default: bool) -> bool {
         .unwrap_or(default)
 }
 
+fn get_u64(value: &Value, key: &str, default: u64) -> u64 {
+    value
+        .get(key)
+        .and_then(|v| v.as_u64())
+        .unwrap_or(default)
+}
+
 fn get_webdav(value: &Value) -> WebDAVConfig {
     let webdav = value.get("webdav");
     
@@ -100,3 +138,118 @@ fn get_s3(value: &Value) -> S3Config {
     }
 }
 
+
+/// Parse HTTP client config, falling back to defaults for missing or zero values
+pub fn get_http_config(value: &Value) -> HttpClientConfig {
+    let defaults = HttpClientConfig::default();
+    let Some(http) = value.get("http") else {
+        return defaults;
+    };
+
+    let non_zero = |key: &str, default: u64| match get_u64(http, key, default) {
+        0 => default,
+        v => v,
+    };
+
+    HttpClientConfig {
+        connect_timeout_secs: non_zero("connect_timeout_secs", defaults.connect_timeout_secs),
+        request_timeout_secs: non_zero("request_timeout_secs", defaults.request_timeout_secs),
+        pool_idle_timeout_secs: get_u64(http, "pool_idle_timeout_secs", defaults.pool_idle_timeout_secs),
+        pool_max_idle_per_host: get_u64(http, "pool_max_idle_per_host", defaults.pool_max_idle_per_host as u64)
+            as usize,
+    }
+}
+
+/// Parse host overrides, skipping malformed entries
+pub fn get_host_overrides(value: &Value) -> Vec<HostOverride> {
+    value
+        .get("host_overrides")
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| {
+                    let host = get_str(item, "host", "").trim().to_lowercase();
+                    let target = get_str(item, "target", "").trim().to_string();
+                    if host.is_empty() || target.is_empty() {
+                        return None;
+                    }
+                    Some(HostOverride {
+                        host,
+                        target,
+                        enabled: get_bool(item, "enabled", true),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parse tray menu config, skipping malformed pins
+pub fn get_tray_menu_config(value: &Value) -> TrayMenuConfig {
+    let Some(tray) = value.get("tray_menu") else {
+        return TrayMenuConfig::default();
+    };
+
+    let hidden_modules = get_str_list(tray, "hidden_modules");
+
+    let pinned_providers = tray
+        .get("pinned_providers")
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| {
+                    let module = get_str(item, "module", "");
+                    let id = get_str(item, "id", "");
+                    if module.is_empty() || id.is_empty() {
+                        return None;
+                    }
+                    Some(TrayPinnedProvider { module, id })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    TrayMenuConfig {
+        hidden_modules,
+        pinned_providers,
+    }
+}
+
+/// Parse notification toggles; missing categories use their defaults
+pub fn get_notification_settings(value: &Value) -> NotificationSettings {
+    let defaults = NotificationSettings::default();
+    let Some(notifications) = value.get("notifications") else {
+        return defaults;
+    };
+
+    NotificationSettings {
+        backup: get_bool(notifications, "backup", defaults.backup),
+        update: get_bool(notifications, "update", defaults.update),
+        provider_health: get_bool(notifications, "provider_health", defaults.provider_health),
+        free_models: get_bool(notifications, "free_models", defaults.free_models),
+        renewal: get_bool(notifications, "renewal", defaults.renewal),
+        budget: get_bool(notifications, "budget", defaults.budget),
+    }
+}
+
+/// Parse local REST API settings, falling back to the default port
+pub fn get_local_api_config(value: &Value) -> LocalApiConfig {
+    let defaults = LocalApiConfig::default();
+    let Some(api) = value.get("local_api") else {
+        return defaults;
+    };
+
+    let port = match get_u64(api, "port", defaults.port as u64) {
+        port @ 1..=65535 => port as u16,
+        _ => defaults.port,
+    };
+
+    LocalApiConfig {
+        enabled: get_bool(api, "enabled", defaults.enabled),
+        port,
+        token: get_str(api, "token", ""),
+        lan_enabled: get_bool(api, "lan_enabled", defaults.lan_enabled),
+    }
+}
@@ -7,7 +7,10 @@ use walkdir::WalkDir;
 use zip::write::SimpleFileOptions;
 use zip::{ZipArchive, ZipWriter};
 
-use super::utils::{get_db_path, get_opencode_config_path, get_opencode_restore_dir, get_opencode_auth_path, get_codex_auth_path, get_codex_config_path, get_skills_dir};
+use super::utils::{check_backup_secrets, get_db_path, get_opencode_config_path, get_opencode_restore_dir, get_opencode_auth_path, get_codex_auth_path, get_codex_config_path, get_skills_dir};
+use crate::db::DbState;
+use crate::notifications::NotificationCategory;
+use crate::secret_scan::ScanMode;
 
 /// Get the home directory
 fn get_home_dir() -> Result<PathBuf, String> {
@@ -19,8 +22,7 @@ fn get_home_dir() -> Result<PathBuf, String> {
 
 /// Get Claude settings.json path if it exists
 fn get_claude_settings_path() -> Result<Option<PathBuf>, String> {
-    let home_dir = get_home_dir()?;
-    let settings_path = home_dir.join(".claude").join("settings.json");
+    let settings_path = crate::coding::claude_code::get_claude_config_dir()?.join("settings.json");
 
     if settings_path.exists() {
         Ok(Some(settings_path))
@@ -50,10 +52,28 @@ fn add_file_to_zip(
 }
 
 /// Backup database to a zip file
+/// The zip is scanned for API keys first; see `secret_scan` for `allow_secrets`.
 #[tauri::command]
 pub async fn backup_database(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, DbState>,
+    backup_path: String,
+    allow_secrets: Option<bool>,
+) -> Result<String, String> {
+    let scan_mode = crate::secret_scan::scan_mode(&state).await;
+    let allow_secrets = allow_secrets.unwrap_or(false);
+    let result = create_local_backup(app_handle.clone(), backup_path, scan_mode, allow_secrets).await;
+    if let Err(e) = &result {
+        crate::notifications::notify(&app_handle, NotificationCategory::Backup, "本地备份失败", e.clone());
+    }
+    result
+}
+
+async fn create_local_backup(
     app_handle: tauri::AppHandle,
     backup_path: String,
+    scan_mode: ScanMode,
+    allow_secrets: bool,
 ) -> Result<String, String> {
     let db_path = get_db_path(&app_handle)?;
 
@@ -225,6 +245,16 @@ pub async fn backup_database(
     zip.finish()
         .map_err(|e| format!("Failed to finish zip: {}", e))?;
 
+    // Don't leave a blocked backup behind
+    let zip_data = fs::read(&backup_file_path)
+        .map_err(|e| format!("Failed to read backup file: {}", e))?;
+    if let Err(e) = check_backup_secrets(&app_handle, scan_mode, &zip_data, allow_secrets) {
+        let _ = fs::remove_file(&backup_file_path);
+        return Err(e);
+    }
+
+    crate::activity_log::record(&app_handle, "backup", "本地备份已完成");
+
     Ok(backup_file_path.to_string_lossy().to_string())
 }
 
@@ -350,7 +380,7 @@ pub async fn restore_database(
                     continue;
                 }
 
-                let claude_dir = home_dir.join(".claude");
+                let claude_dir = crate::coding::claude_code::get_claude_config_dir()?;
                 if !claude_dir.exists() {
                     fs::create_dir_all(&claude_dir)
                         .map_err(|e| format!("Failed to create claude config directory: {}", e))?;
@@ -6,6 +6,7 @@ use zip::write::SimpleFileOptions;
 use zip::ZipWriter;
 
 use crate::coding::open_code::shell_env;
+use crate::secret_scan::{self, ScanMode};
 
 /// Get database directory path
 pub fn get_db_path(app_handle: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
@@ -96,8 +97,7 @@ pub fn get_opencode_restore_dir() -> Result<PathBuf, String> {
 
 /// Get Claude settings.json path if it exists
 pub fn get_claude_settings_path() -> Result<Option<PathBuf>, String> {
-    let home_dir = get_home_dir()?;
-    let settings_path = home_dir.join(".claude").join("settings.json");
+    let settings_path = crate::coding::claude_code::get_claude_config_dir()?.join("settings.json");
 
     if settings_path.exists() {
         Ok(Some(settings_path))
@@ -334,3 +334,32 @@ pub fn create_backup_zip(app_handle: &tauri::AppHandle, db_path: &Path) -> Resul
 
     Ok(buffer.into_inner())
 }
+
+/// Check a backup zip for API keys before it is kept or uploaded (backups are not encrypted).
+/// Errors in "block" scan mode unless `allow_secrets`; otherwise hits are logged and
+/// recorded in the activity log.
+pub fn check_backup_secrets(
+    app_handle: &tauri::AppHandle,
+    mode: ScanMode,
+    zip_data: &[u8],
+    allow_secrets: bool,
+) -> Result<(), String> {
+    if mode == ScanMode::Off {
+        return Ok(());
+    }
+    let findings = secret_scan::scan_zip(zip_data)?;
+    secret_scan::enforce(mode, &findings, allow_secrets)?;
+    if !findings.is_empty() {
+        log::warn!(
+            "Backup contains {} possible API keys: {}",
+            findings.len(),
+            secret_scan::describe(&findings)
+        );
+        crate::activity_log::record(
+            app_handle,
+            "backup",
+            format!("备份中发现 {} 处疑似密钥，建议将密钥移入保险库", findings.len()),
+        );
+    }
+    Ok(())
+}
@@ -6,9 +6,11 @@ use std::path::PathBuf;
 use tauri::Manager;
 use zip::ZipArchive;
 
-use super::utils::{create_backup_zip, get_db_path, get_opencode_restore_dir, get_skills_dir};
+use super::utils::{check_backup_secrets, create_backup_zip, get_db_path, get_opencode_restore_dir, get_skills_dir};
 use crate::db::DbState;
 use crate::http_client;
+use crate::notifications::NotificationCategory;
+use crate::secret_scan;
 
 /// Backup file info structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -170,6 +172,7 @@ pub async fn backup_to_webdav(
     username: String,
     password: String,
     remote_path: String,
+    allow_secrets: Option<bool>,
 ) -> Result<String, String> {
     info!("Starting WebDAV backup to: {}", url);
 
@@ -186,6 +189,8 @@ pub async fn backup_to_webdav(
 
     // Create backup zip in memory
     let zip_data = create_backup_zip(&app_handle, &db_path)?;
+    let scan_mode = secret_scan::scan_mode(&state).await;
+    check_backup_secrets(&app_handle, scan_mode, &zip_data, allow_secrets.unwrap_or(false))?;
 
     // Generate backup filename with timestamp
     let timestamp = Local::now().format("%Y%m%d-%H%M%S");
@@ -219,21 +224,33 @@ pub async fn backup_to_webdav(
         Ok(resp) => {
             if resp.status().is_success() {
                 info!("WebDAV backup successful: {}", full_url);
+                crate::activity_log::record(&app_handle, "backup", "WebDAV 备份已完成");
                 Ok(full_url)
             } else {
                 let error = analyze_http_error(resp.status(), &full_url);
                 error!("WebDAV backup failed: {:?}", error);
+                notify_backup_failed(&app_handle);
                 Err(error.to_json())
             }
         }
         Err(e) => {
             let error = analyze_reqwest_error(&e, &full_url);
             error!("WebDAV backup failed: {:?}", error);
+            notify_backup_failed(&app_handle);
             Err(error.to_json())
         }
     }
 }
 
+fn notify_backup_failed(app_handle: &tauri::AppHandle) {
+    crate::notifications::notify(
+        app_handle,
+        NotificationCategory::Backup,
+        "WebDAV 备份失败",
+        "请在设置中查看 WebDAV 配置",
+    );
+}
+
 /// List backup files from WebDAV server
 #[tauri::command]
 pub async fn list_webdav_backups(
@@ -571,7 +588,7 @@ pub async fn restore_from_webdav(
                     continue;
                 }
 
-                let claude_dir = home_dir.join(".claude");
+                let claude_dir = crate::coding::claude_code::get_claude_config_dir()?;
                 if !claude_dir.exists() {
                     fs::create_dir_all(&claude_dir)
                         .map_err(|e| format!("Failed to create claude config directory: {}", e))?;
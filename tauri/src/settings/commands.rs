@@ -1,7 +1,9 @@
+
 use crate::db::DbState;
 use crate::auto_launch;
 use super::adapter;
-use super::types::AppSettings;
+use super::types::{AppSettings, AutostartStatus};
+use crate::events::{emit_change, ChangeAction};
 
 /// Get settings from database using adapter layer for fault tolerance
 #[tauri::command]
@@ -31,6 +33,7 @@ pub async fn get_settings(state: tauri::State<'_, DbState>) -> Result<AppSetting
 #[tauri::command]
 pub async fn save_settings(
     state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
     settings: AppSettings,
 ) -> Result<(), String> {
     let db = state.0.lock().await;
@@ -43,6 +46,13 @@ pub async fn save_settings(
         .bind(("data", json))
         .await
         .map_err(|e| format!("Failed to save settings: {}", e))?;
+    crate::i18n::set_language(&settings.language);
+    crate::logging::set_level(&settings.log_level);
+    crate::coding::claude_code::set_config_dir_override(&settings.claude_config_dir);
+    crate::telemetry::set_enabled(&db, settings.telemetry_enabled).await;
+
+    // Refresh tray status (e.g. last backup time)
+    emit_change(&app, "settings", ChangeAction::Updated, None, "window");
 
     Ok(())
 }
@@ -66,6 +76,58 @@ pub fn get_auto_launch_status() -> Result<bool, String> {
         .map_err(|e| format!("Failed to check auto launch status: {}", e))
 }
 
+/// Get autostart on login status and the start-minimized option
+#[tauri::command]
+pub async fn get_autostart(state: tauri::State<'_, DbState>) -> Result<AutostartStatus, String> {
+    let settings = get_settings(state).await?;
+    let enabled = auto_launch::is_auto_launch_enabled()
+        .map_err(|e| format!("Failed to check auto launch status: {}", e))?;
+    Ok(AutostartStatus {
+        enabled,
+        start_minimized: settings.start_minimized,
+    })
+}
+
+/// Enable or disable autostart on login
+/// Registers the OS mechanism (registry run key, LaunchAgent/login item, XDG .desktop)
+/// and stores the choice in settings.
+#[tauri::command]
+pub async fn set_autostart(
+    state: tauri::State<'_, DbState>,
+    enabled: bool,
+    start_minimized: Option<bool>,
+) -> Result<AutostartStatus, String> {
+    set_auto_launch(enabled)?;
+
+    let db = state.0.lock().await;
+    let records: Vec<serde_json::Value> = db
+        .query("SELECT *, type::string(id) as id FROM settings:`app` LIMIT 1")
+        .await
+        .map_err(|e| format!("Failed to query settings: {}", e))?
+        .take(0)
+        .map_err(|e| format!("Failed to parse settings: {}", e))?;
+    let mut settings = records
+        .into_iter()
+        .next()
+        .map(adapter::from_db_value)
+        .unwrap_or_default();
+
+    settings.launch_on_startup = enabled;
+    if let Some(start_minimized) = start_minimized {
+        settings.start_minimized = start_minimized;
+    }
+
+    db.query("UPSERT settings:`app` CONTENT $data")
+        .bind(("data", adapter::to_db_value(&settings)))
+        .await
+        .map_err(|e| format!("Failed to save settings: {}", e))?;
+
+    Ok(AutostartStatus {
+        enabled,
+        start_minimized: settings.start_minimized,
+    })
+}
+
 /// Restart the application
 #[tauri::command]
 pub fn restart_app() -> Result<(), String> {
@@ -1,4 +1,4 @@
-mod adapter;
+pub(crate) mod adapter;
 pub mod backup;
 pub mod commands;
 pub mod types;
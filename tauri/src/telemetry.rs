@@ -0,0 +1,239 @@
+//! Opt-in Anonymous Telemetry
+//!
+//! Off by default (`telemetry_enabled`). When enabled, only coarse counters
+//! are collected: which kind of entity changed how (e.g. "claude_provider.applied",
+//! see `events::emit_change`), plus app version, OS and architecture. No ids,
+//! names, paths, URLs or keys are ever recorded.
+//!
+//! Counters are moved into a local queue (`telemetry_queue` table) by `flush`
+//! and sent to the collector configured at build time
+//! (`AI_TOOLBOX_TELEMETRY_URL`); builds without one never send anything.
+//! Turning telemetry off drops the counters and the queue.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::db::DbState;
+
+/// Collector endpoint, set at build time
+const TELEMETRY_ENDPOINT: Option<&str> = option_env!("AI_TOOLBOX_TELEMETRY_URL");
+
+/// Payload format version
+const SCHEMA_VERSION: u32 = 1;
+
+/// Oldest payloads are dropped beyond this many queued
+const MAX_QUEUED_PAYLOADS: usize = 30;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Counters since the last flush and when counting started
+struct Counters {
+    since: String,
+    values: BTreeMap<String, u64>,
+}
+
+static COUNTERS: OnceLock<Mutex<Counters>> = OnceLock::new();
+
+fn counters() -> &'static Mutex<Counters> {
+    COUNTERS.get_or_init(|| {
+        Mutex::new(Counters {
+            since: Local::now().to_rfc3339(),
+            values: BTreeMap::new(),
+        })
+    })
+}
+
+/// One report as sent to the collector
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelemetryPayload {
+    pub schema: u32,
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    /// Counting period (RFC 3339, local time)
+    pub period_start: String,
+    pub period_end: String,
+    pub counters: BTreeMap<String, u64>,
+}
+
+/// Returned by `preview_telemetry_payload`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelemetryPreview {
+    pub enabled: bool,
+    /// Whether this build has a collector; without one nothing is ever sent
+    pub has_endpoint: bool,
+    /// Queued payloads followed by the one being collected now
+    pub payloads: Vec<TelemetryPayload>,
+}
+
+fn build_payload(period_start: String, counters: BTreeMap<String, u64>) -> TelemetryPayload {
+    TelemetryPayload {
+        schema: SCHEMA_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        period_start,
+        period_end: Local::now().to_rfc3339(),
+        counters,
+    }
+}
+
+/// Count one use of a feature; no-op unless telemetry is enabled
+/// `feature` must be a fixed name, never user data.
+pub fn track(feature: &str) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    if let Ok(mut counters) = counters().lock() {
+        *counters.values.entry(feature.to_string()).or_insert(0) += 1;
+    }
+}
+
+/// Apply the `telemetry_enabled` setting; opting out drops everything collected
+pub async fn set_enabled(db: &surrealdb::Surreal<surrealdb::engine::local::Db>, enabled: bool) {
+    let was_enabled = ENABLED.swap(enabled, Ordering::Relaxed);
+    if enabled {
+        if !was_enabled {
+            if let Ok(mut counters) = counters().lock() {
+                counters.since = Local::now().to_rfc3339();
+            }
+        }
+        return;
+    }
+
+    if let Ok(mut counters) = counters().lock() {
+        counters.values.clear();
+    }
+    if let Err(e) = db.query("DELETE telemetry_queue").await {
+        log::warn!("Failed to clear telemetry queue: {}", e);
+    }
+}
+
+async fn load_queue(state: &DbState) -> Result<Vec<(String, TelemetryPayload)>, String> {
+    let db = state.0.lock().await;
+    let records: Vec<Value> = db
+        .query("SELECT *, type::string(id) as id FROM telemetry_queue ORDER BY created_at ASC")
+        .await
+        .map_err(|e| format!("Failed to query telemetry queue: {}", e))?
+        .take(0)
+        .map_err(|e| format!("Failed to parse telemetry queue: {}", e))?;
+    Ok(records
+        .into_iter()
+        .filter_map(|record| {
+            let id = crate::coding::db_extract_id(&record);
+            let payload = serde_json::from_value(record.get("payload")?.clone()).ok()?;
+            Some((id, payload))
+        })
+        .collect())
+}
+
+/// Move the counters into the queue and send queued payloads
+pub async fn flush(state: &DbState) -> Result<(), String> {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return Ok(());
+    }
+
+    let payload = {
+        let mut counters = counters().lock().map_err(|e| e.to_string())?;
+        if counters.values.is_empty() {
+            None
+        } else {
+            let values = std::mem::take(&mut counters.values);
+            let since = std::mem::replace(&mut counters.since, Local::now().to_rfc3339());
+            Some(build_payload(since, values))
+        }
+    };
+
+    if let Some(payload) = payload {
+        let db = state.0.lock().await;
+        db.query("CREATE telemetry_queue CONTENT $data")
+            .bind((
+                "data",
+                serde_json::json!({ "payload": payload, "created_at": Local::now().to_rfc3339() }),
+            ))
+            .await
+            .map_err(|e| format!("Failed to queue telemetry: {}", e))?;
+    }
+
+    let mut queue = load_queue(state).await?;
+    if queue.len() > MAX_QUEUED_PAYLOADS {
+        let dropped: Vec<(String, TelemetryPayload)> = queue.drain(..queue.len() - MAX_QUEUED_PAYLOADS).collect();
+        let db = state.0.lock().await;
+        for (id, _) in dropped {
+            let _ = db.query("DELETE type::thing('telemetry_queue', $id)").bind(("id", id)).await;
+        }
+    }
+
+    let Some(endpoint) = TELEMETRY_ENDPOINT else {
+        return Ok(());
+    };
+    let client = crate::http_client::client(state).await?;
+    for (id, payload) in queue {
+        let response = client
+            .post(endpoint)
+            .json(&payload)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to send telemetry: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("Failed to send telemetry: HTTP {}", response.status()));
+        }
+        let db = state.0.lock().await;
+        let _ = db.query("DELETE type::thing('telemetry_queue', $id)").bind(("id", id)).await;
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Telemetry Commands
+// ============================================================================
+
+/// Show exactly what would be sent: queued payloads and the current counters
+#[tauri::command]
+pub async fn preview_telemetry_payload(state: tauri::State<'_, DbState>) -> Result<TelemetryPreview, String> {
+    let mut payloads: Vec<TelemetryPayload> = load_queue(&state)
+        .await?
+        .into_iter()
+        .map(|(_, payload)| payload)
+        .collect();
+
+    let enabled = ENABLED.load(Ordering::Relaxed);
+    if enabled {
+        let counters = counters().lock().map_err(|e| e.to_string())?;
+        if !counters.values.is_empty() {
+            payloads.push(build_payload(counters.since.clone(), counters.values.clone()));
+        }
+    }
+
+    Ok(TelemetryPreview {
+        enabled,
+        has_endpoint: TELEMETRY_ENDPOINT.is_some(),
+        payloads,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn payload_has_no_identifying_fields() {
+        let payload = build_payload(
+            "2026-01-01T00:00:00+00:00".to_string(),
+            BTreeMap::from([("claude_provider.applied".to_string(), 3)]),
+        );
+        let value = serde_json::to_value(&payload).unwrap();
+        let mut keys: Vec<&String> = value.as_object().unwrap().keys().collect();
+        keys.sort();
+        assert_eq!(
+            keys,
+            vec!["app_version", "arch", "counters", "os", "period_end", "period_start", "schema"]
+        );
+        assert_eq!(value["counters"]["claude_provider.applied"], 3);
+    }
+}
@@ -0,0 +1,347 @@
+//! Terminal Module
+//!
+//! Detects the terminal emulators installed on this platform and opens one
+//! in a directory with a command running. The user can pick a default in
+//! settings (`default_terminal`); otherwise the first detected one is used.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::coding::cli_detect::find_binary;
+use crate::db::DbState;
+use crate::error::{AppError, AppResult};
+use crate::settings::adapter;
+
+/// Windows CREATE_NEW_CONSOLE flag to open the command in its own console window
+#[cfg(target_os = "windows")]
+const CREATE_NEW_CONSOLE: u32 = 0x00000010;
+
+/// How a terminal is asked to run a command (only this platform's styles exist)
+#[derive(Clone, Copy)]
+enum LaunchStyle {
+    /// `<terminal> <args...> sh -c <script>`; `{cwd}` in args is replaced
+    #[cfg(not(target_os = "windows"))]
+    Args(&'static [&'static str]),
+    /// Windows Terminal: `wt -w new -d <cwd> cmd /K <command>`
+    #[cfg(target_os = "windows")]
+    WindowsTerminal,
+    /// A new console window running `cmd /K <command>`
+    #[cfg(target_os = "windows")]
+    Cmd,
+    /// A new console window running `<powershell> -NoExit -Command <command>`
+    #[cfg(target_os = "windows")]
+    PowerShell,
+    /// macOS Terminal.app via AppleScript
+    #[cfg(target_os = "macos")]
+    TerminalApp,
+    /// iTerm2 via AppleScript
+    #[cfg(target_os = "macos")]
+    ITerm,
+}
+
+struct TerminalSpec {
+    id: &'static str,
+    name: &'static str,
+    /// Binary names looked up on PATH
+    binaries: &'static [&'static str],
+    /// Absolute paths checked when the binary is not on PATH (app bundles)
+    paths: &'static [&'static str],
+    style: LaunchStyle,
+}
+
+#[cfg(target_os = "windows")]
+const TERMINALS: &[TerminalSpec] = &[
+    TerminalSpec { id: "windows_terminal", name: "Windows Terminal", binaries: &["wt"], paths: &[], style: LaunchStyle::WindowsTerminal },
+    TerminalSpec { id: "pwsh", name: "PowerShell 7", binaries: &["pwsh"], paths: &[], style: LaunchStyle::PowerShell },
+    TerminalSpec { id: "powershell", name: "Windows PowerShell", binaries: &["powershell"], paths: &[], style: LaunchStyle::PowerShell },
+    TerminalSpec { id: "cmd", name: "Command Prompt", binaries: &["cmd"], paths: &[], style: LaunchStyle::Cmd },
+];
+
+#[cfg(target_os = "macos")]
+const TERMINALS: &[TerminalSpec] = &[
+    TerminalSpec { id: "terminal", name: "Terminal", binaries: &[], paths: &["/System/Applications/Utilities/Terminal.app", "/Applications/Utilities/Terminal.app"], style: LaunchStyle::TerminalApp },
+    TerminalSpec { id: "iterm2", name: "iTerm2", binaries: &[], paths: &["/Applications/iTerm.app"], style: LaunchStyle::ITerm },
+    TerminalSpec { id: "kitty", name: "Kitty", binaries: &["kitty"], paths: &["/Applications/kitty.app/Contents/MacOS/kitty"], style: LaunchStyle::Args(&[]) },
+    TerminalSpec { id: "wezterm", name: "WezTerm", binaries: &["wezterm"], paths: &["/Applications/WezTerm.app/Contents/MacOS/wezterm"], style: LaunchStyle::Args(&["start", "--cwd", "{cwd}", "--"]) },
+    TerminalSpec { id: "alacritty", name: "Alacritty", binaries: &["alacritty"], paths: &["/Applications/Alacritty.app/Contents/MacOS/alacritty"], style: LaunchStyle::Args(&["--working-directory", "{cwd}", "-e"]) },
+];
+
+#[cfg(target_os = "linux")]
+const TERMINALS: &[TerminalSpec] = &[
+    TerminalSpec { id: "gnome_terminal", name: "GNOME Terminal", binaries: &["gnome-terminal"], paths: &[], style: LaunchStyle::Args(&["--"]) },
+    TerminalSpec { id: "konsole", name: "Konsole", binaries: &["konsole"], paths: &[], style: LaunchStyle::Args(&["--workdir", "{cwd}", "-e"]) },
+    TerminalSpec { id: "xfce4_terminal", name: "Xfce Terminal", binaries: &["xfce4-terminal"], paths: &[], style: LaunchStyle::Args(&["-x"]) },
+    TerminalSpec { id: "kitty", name: "Kitty", binaries: &["kitty"], paths: &[], style: LaunchStyle::Args(&[]) },
+    TerminalSpec { id: "wezterm", name: "WezTerm", binaries: &["wezterm"], paths: &[], style: LaunchStyle::Args(&["start", "--cwd", "{cwd}", "--"]) },
+    TerminalSpec { id: "alacritty", name: "Alacritty", binaries: &["alacritty"], paths: &[], style: LaunchStyle::Args(&["--working-directory", "{cwd}", "-e"]) },
+    TerminalSpec { id: "xterm", name: "XTerm", binaries: &["xterm"], paths: &[], style: LaunchStyle::Args(&["-e"]) },
+    TerminalSpec { id: "x_terminal_emulator", name: "System default", binaries: &["x-terminal-emulator"], paths: &[], style: LaunchStyle::Args(&["-e"]) },
+];
+
+/// A terminal emulator found on this machine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TerminalInfo {
+    pub id: String,
+    pub name: String,
+    pub path: String,
+    /// Used by `open_terminal` (the user's choice, or the first detected one)
+    pub is_default: bool,
+}
+
+// ============================================================================
+// Detection
+// ============================================================================
+
+fn detect(spec: &TerminalSpec) -> Option<PathBuf> {
+    find_binary(spec.binaries).or_else(|| {
+        spec.paths
+            .iter()
+            .map(PathBuf::from)
+            .find(|path| path.exists())
+    })
+}
+
+/// Installed terminals in preference order
+fn detect_terminals() -> Vec<(&'static TerminalSpec, PathBuf)> {
+    TERMINALS
+        .iter()
+        .filter_map(|spec| detect(spec).map(|path| (spec, path)))
+        .collect()
+}
+
+async fn load_default_terminal(state: &DbState) -> String {
+    let db = state.0.lock().await;
+    let records: Vec<serde_json::Value> = match db
+        .query("SELECT *, type::string(id) as id FROM settings:`app` LIMIT 1")
+        .await
+        .and_then(|mut result| result.take(0))
+    {
+        Ok(records) => records,
+        Err(_) => return String::new(),
+    };
+    records
+        .into_iter()
+        .next()
+        .map(|record| adapter::from_db_value(record).default_terminal)
+        .unwrap_or_default()
+}
+
+/// The configured terminal if it is installed, otherwise the first detected one
+fn pick_terminal(
+    terminals: Vec<(&'static TerminalSpec, PathBuf)>,
+    preferred: &str,
+) -> Option<(&'static TerminalSpec, PathBuf)> {
+    let index = terminals
+        .iter()
+        .position(|(spec, _)| spec.id == preferred)
+        .unwrap_or(0);
+    terminals.into_iter().nth(index)
+}
+
+// ============================================================================
+// Command Line Helpers
+// ============================================================================
+
+/// Quote a value for POSIX shells
+#[cfg(not(target_os = "windows"))]
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Quote a value for PowerShell
+#[cfg(target_os = "windows")]
+fn powershell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// POSIX script: export env, cd, run the command, then keep an interactive shell
+#[cfg(not(target_os = "windows"))]
+fn posix_script(cwd: &Path, env: &[(String, String)], command: &[String], export_env: bool) -> String {
+    let mut script = format!("cd {}", shell_quote(&cwd.to_string_lossy()));
+    if export_env {
+        for (key, value) in env {
+            script.push_str(&format!(" && export {}={}", key, shell_quote(value)));
+        }
+    }
+    let command_line: Vec<String> = command.iter().map(|arg| shell_quote(arg)).collect();
+    script.push_str(&format!(" && {}; exec \"${{SHELL:-sh}}\"", command_line.join(" ")));
+    script
+}
+
+/// Run an AppleScript
+#[cfg(target_os = "macos")]
+fn run_osascript(lines: &[String]) -> AppResult<()> {
+    let mut cmd = Command::new("osascript");
+    for line in lines {
+        cmd.arg("-e").arg(line);
+    }
+    cmd.spawn()
+        .map_err(AppError::io)?;
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn applescript_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+// ============================================================================
+// Launching
+// ============================================================================
+
+fn spawn_terminal(
+    style: LaunchStyle,
+    path: &Path,
+    cwd: &Path,
+    env: &[(String, String)],
+    command: &[String],
+) -> AppResult<()> {
+    match style {
+        #[cfg(not(target_os = "windows"))]
+        LaunchStyle::Args(args) => {
+            let cwd_str = cwd.to_string_lossy();
+            Command::new(path)
+                .args(args.iter().map(|arg| arg.replace("{cwd}", &cwd_str)))
+                .args(["sh", "-c", &posix_script(cwd, env, command, false)])
+                .current_dir(cwd)
+                .envs(env.iter().map(|(k, v)| (k, v)))
+                .spawn()
+                .map_err(AppError::io)?;
+            Ok(())
+        }
+        #[cfg(target_os = "windows")]
+        LaunchStyle::WindowsTerminal => {
+            Command::new(path)
+                .args(["-w", "new", "-d"])
+                .arg(cwd)
+                .args(["cmd", "/K"])
+                .args(command)
+                .envs(env.iter().map(|(k, v)| (k, v)))
+                .spawn()
+                .map_err(AppError::io)?;
+            Ok(())
+        }
+        #[cfg(target_os = "windows")]
+        LaunchStyle::Cmd => {
+            Command::new(path)
+                .arg("/K")
+                .args(command)
+                .current_dir(cwd)
+                .envs(env.iter().map(|(k, v)| (k, v)))
+                .creation_flags(CREATE_NEW_CONSOLE)
+                .spawn()
+                .map_err(AppError::io)?;
+            Ok(())
+        }
+        #[cfg(target_os = "windows")]
+        LaunchStyle::PowerShell => {
+            let command_line: Vec<String> = command.iter().map(|arg| powershell_quote(arg)).collect();
+            Command::new(path)
+                .args(["-NoExit", "-Command"])
+                .arg(format!("& {}", command_line.join(" ")))
+                .current_dir(cwd)
+                .envs(env.iter().map(|(k, v)| (k, v)))
+                .creation_flags(CREATE_NEW_CONSOLE)
+                .spawn()
+                .map_err(AppError::io)?;
+            Ok(())
+        }
+        // Terminal.app and iTerm2 do not inherit our environment, so it is exported in the script
+        #[cfg(target_os = "macos")]
+        LaunchStyle::TerminalApp => {
+            let script = applescript_string(&posix_script(cwd, env, command, true));
+            run_osascript(&[
+                format!("tell application \"Terminal\" to do script {}", script),
+                "tell application \"Terminal\" to activate".to_string(),
+            ])
+        }
+        #[cfg(target_os = "macos")]
+        LaunchStyle::ITerm => {
+            let script = applescript_string(&posix_script(cwd, env, command, true));
+            run_osascript(&[
+                "tell application \"iTerm\"".to_string(),
+                "activate".to_string(),
+                "set newWindow to (create window with default profile)".to_string(),
+                format!("tell current session of newWindow to write text {}", script),
+                "end tell".to_string(),
+            ])
+        }
+    }
+}
+
+/// Open the default terminal in `cwd` running `command` (program and arguments)
+/// with `env` set; the terminal stays open after the command exits.
+pub async fn open_terminal(
+    state: &DbState,
+    cwd: &Path,
+    env: &[(String, String)],
+    command: &[String],
+) -> AppResult<()> {
+    if command.is_empty() {
+        return Err(AppError::invalid("No command to run"));
+    }
+    let preferred = load_default_terminal(state).await;
+    let (spec, path) = pick_terminal(detect_terminals(), &preferred)
+        .ok_or_else(|| AppError::not_found("NO_TERMINAL_FOUND", &[]))?;
+    spawn_terminal(spec.style, &path, cwd, env, command)
+}
+
+// ============================================================================
+// Terminal Commands
+// ============================================================================
+
+/// List installed terminal emulators
+#[tauri::command]
+pub async fn list_terminals(state: tauri::State<'_, DbState>) -> AppResult<Vec<TerminalInfo>> {
+    let preferred = load_default_terminal(&state).await;
+    let terminals = detect_terminals();
+    let default_id = terminals
+        .iter()
+        .find(|(spec, _)| spec.id == preferred)
+        .or_else(|| terminals.first())
+        .map(|(spec, _)| spec.id);
+
+    Ok(terminals
+        .iter()
+        .map(|(spec, path)| TerminalInfo {
+            id: spec.id.to_string(),
+            name: spec.name.to_string(),
+            path: path.to_string_lossy().to_string(),
+            is_default: Some(spec.id) == default_id,
+        })
+        .collect())
+}
+
+/// Set the terminal used to launch CLIs (empty id = first detected)
+#[tauri::command]
+pub async fn set_default_terminal(
+    state: tauri::State<'_, DbState>,
+    terminal_id: String,
+) -> AppResult<()> {
+    if !terminal_id.is_empty() && !TERMINALS.iter().any(|spec| spec.id == terminal_id) {
+        return Err(AppError::validation("UNKNOWN_TERMINAL", &[("terminal", terminal_id.as_str())]));
+    }
+
+    let db = state.0.lock().await;
+    let records: Vec<serde_json::Value> = db
+        .query("SELECT *, type::string(id) as id FROM settings:`app` LIMIT 1")
+        .await?
+        .take(0)?;
+
+    let mut settings = records
+        .into_iter()
+        .next()
+        .map(adapter::from_db_value)
+        .unwrap_or_default();
+    settings.default_terminal = terminal_id;
+
+    db.query("UPSERT settings:`app` CONTENT $data")
+        .bind(("data", adapter::to_db_value(&settings)))
+        .await?;
+    Ok(())
+}
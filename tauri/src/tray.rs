@@ -2,6 +2,7 @@
 //!
 //! Provides system tray icon and menu with flat structure:
 //! - Open Main Window
+//! - ─── 常用 ─── (pinned providers)
 //! - ─── OpenCode 模型 ────
 //! - 主模型 / 小模型 (with submenus for model selection)
 //! - ─── OpenCode 插件 ────
@@ -12,6 +13,8 @@
 //! - Provider options (with checkmarks for applied provider)
 //! - ─── MCP Servers ───
 //! - MCP server options (with submenus for tool selection)
+//! - 启动 CLI (launch opencode / claude in a project directory)
+//! - 最近操作 (recent actions submenu)
 //! - Quit
 
 use crate::coding::open_code::tray_support as opencode_tray;
@@ -19,8 +22,16 @@ use crate::coding::oh_my_opencode::tray_support as omo_tray;
 use crate::coding::oh_my_opencode_slim::tray_support as omo_slim_tray;
 use crate::coding::claude_code::tray_support as claude_tray;
 use crate::coding::codex::tray_support as codex_tray;
+use crate::coding::gemini_cli::tray_support as gemini_cli_tray;
+use crate::coding::qwen_code::tray_support as qwen_code_tray;
+use crate::coding::windsurf::tray_support as windsurf_tray;
+use crate::coding::goose::tray_support as goose_tray;
+use crate::coding::amp::tray_support as amp_tray;
+use crate::coding::droid::tray_support as droid_tray;
 use crate::coding::skills::tray_support as skills_tray;
 use crate::coding::mcp::tray_support as mcp_tray;
+use crate::settings::AppSettings;
+use tauri_plugin_dialog::DialogExt;
 use tauri::{
     menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu},
     tray::{TrayIconBuilder, TrayIconEvent},
@@ -49,6 +60,12 @@ pub async fn refresh_tray_menu<R: Runtime>(app: AppHandle<R>) -> Result<(), Stri
     refresh_tray_menus(&app).await
 }
 
+/// 命令：按设置重建托盘菜单（托盘菜单设置变更后调用）
+#[tauri::command]
+pub async fn rebuild_tray_menu<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    refresh_tray_menus(&app).await
+}
+
 /// Create system tray icon and menu
 pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> Result<(), Box<dyn std::error::Error>> {
     let quit_item = PredefinedMenuItem::quit(app, Some("退出"))?;
@@ -60,6 +77,11 @@ pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> Result<(), Box<dyn std::er
         .menu(&menu)
         .on_menu_event(move |app, event| {
             let event_id = event.id().as_ref().to_string();
+            // Pinned providers reuse their module's event ids
+            let event_id = match event_id.strip_prefix("pinned_") {
+                Some(id) => id.to_string(),
+                None => event_id,
+            };
 
             if event_id == "show" {
                 // macOS: Switch back to Regular mode to show in Dock
@@ -73,12 +95,43 @@ pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> Result<(), Box<dyn std::er
                     let _ = window.show();
                     let _ = window.set_focus();
                 }
+            } else if let Some(tool) = event_id.strip_prefix("launch_pick_") {
+                // Ask for a project directory, then launch
+                let tool = tool.to_string();
+                let app_handle = app.clone();
+                app.dialog().file().pick_folder(move |folder| {
+                    let Some(cwd) = folder.and_then(|f| f.into_path().ok()) else {
+                        return;
+                    };
+                    tauri::async_runtime::spawn(async move {
+                        let cwd = cwd.to_string_lossy().to_string();
+                        if let Err(e) = crate::launcher::launch_coding_cli_internal(&app_handle, &tool, &cwd).await {
+                            log::warn!("Failed to launch {}: {}", tool, e);
+                        }
+                    });
+                });
+            } else if event_id.starts_with("launch_opencode_") || event_id.starts_with("launch_claude_") {
+                let remaining = event_id.strip_prefix("launch_").unwrap();
+                let (tool, cwd) = remaining.split_once('_').unwrap();
+                let tool = tool.to_string();
+                let cwd = cwd.to_string();
+                let app_handle = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = crate::launcher::launch_coding_cli_internal(&app_handle, &tool, &cwd).await {
+                        log::warn!("Failed to launch {}: {}", tool, e);
+                    }
+                });
+            } else if event_id == "activity_log_clear" {
+                let app_handle = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    let _ = crate::activity_log::clear_activity_log(app_handle).await;
+                });
             } else if event_id.starts_with("omo_config_") {
                 let config_id = event_id.strip_prefix("omo_config_").unwrap().to_string();
                 let app_handle = app.clone();
                 tauri::async_runtime::spawn(async move {
                     if let Err(e) = omo_tray::apply_oh_my_opencode_config(&app_handle, &config_id).await {
-                        eprintln!("Failed to apply Oh My OpenCode config: {}", e);
+                        log::warn!("Failed to apply Oh My OpenCode config: {}", e);
                     }
                     // Refresh tray menu to update checkmarks
                     let _ = refresh_tray_menus(&app_handle).await;
@@ -88,7 +141,7 @@ pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> Result<(), Box<dyn std::er
                 let app_handle = app.clone();
                 tauri::async_runtime::spawn(async move {
                     if let Err(e) = omo_slim_tray::apply_oh_my_opencode_slim_config(&app_handle, &config_id).await {
-                        eprintln!("Failed to apply Oh My OpenCode Slim config: {}", e);
+                        log::warn!("Failed to apply Oh My OpenCode Slim config: {}", e);
                     }
                     // Refresh tray menu to update checkmarks
                     let _ = refresh_tray_menus(&app_handle).await;
@@ -101,7 +154,7 @@ pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> Result<(), Box<dyn std::er
                 let app_handle = app.clone();
                 tauri::async_runtime::spawn(async move {
                     if let Err(e) = claude_tray::apply_claude_code_provider(&app_handle, &provider_id).await {
-                        eprintln!("Failed to apply Claude provider: {}", e);
+                        log::warn!("Failed to apply Claude provider: {}", e);
                     }
                     // Refresh tray menu to update checkmarks
                     let _ = refresh_tray_menus(&app_handle).await;
@@ -115,7 +168,7 @@ pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> Result<(), Box<dyn std::er
                 let app_handle = app.clone();
                 tauri::async_runtime::spawn(async move {
                     if let Err(e) = opencode_tray::apply_opencode_model(&app_handle, &model_type, &item_id).await {
-                        eprintln!("Failed to apply OpenCode model: {}", e);
+                        log::warn!("Failed to apply OpenCode model: {}", e);
                     }
                     // Refresh tray menu to update checkmarks
                     let _ = refresh_tray_menus(&app_handle).await;
@@ -125,7 +178,7 @@ pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> Result<(), Box<dyn std::er
                 let app_handle = app.clone();
                 tauri::async_runtime::spawn(async move {
                     if let Err(e) = opencode_tray::apply_opencode_plugin(&app_handle, &plugin_name).await {
-                        eprintln!("Failed to apply OpenCode plugin: {}", e);
+                        log::warn!("Failed to apply OpenCode plugin: {}", e);
                     }
                     // Refresh tray menu to update checkmarks
                     let _ = refresh_tray_menus(&app_handle).await;
@@ -138,7 +191,79 @@ pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> Result<(), Box<dyn std::er
                 let app_handle = app.clone();
                 tauri::async_runtime::spawn(async move {
                     if let Err(e) = codex_tray::apply_codex_provider(&app_handle, &provider_id).await {
-                        eprintln!("Failed to apply Codex provider: {}", e);
+                        log::warn!("Failed to apply Codex provider: {}", e);
+                    }
+                    let _ = refresh_tray_menus(&app_handle).await;
+                });
+            } else if event_id.starts_with("gemini_cli_provider_") {
+                let provider_id = event_id
+                    .strip_prefix("gemini_cli_provider_")
+                    .unwrap()
+                    .to_string();
+                let app_handle = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = gemini_cli_tray::apply_gemini_cli_provider(&app_handle, &provider_id).await {
+                        log::warn!("Failed to apply Gemini CLI provider: {}", e);
+                    }
+                    let _ = refresh_tray_menus(&app_handle).await;
+                });
+            } else if event_id.starts_with("qwen_code_provider_") {
+                let provider_id = event_id
+                    .strip_prefix("qwen_code_provider_")
+                    .unwrap()
+                    .to_string();
+                let app_handle = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = qwen_code_tray::apply_qwen_code_provider(&app_handle, &provider_id).await {
+                        log::warn!("Failed to apply Qwen Code provider: {}", e);
+                    }
+                    let _ = refresh_tray_menus(&app_handle).await;
+                });
+            } else if event_id.starts_with("windsurf_provider_") {
+                let provider_id = event_id
+                    .strip_prefix("windsurf_provider_")
+                    .unwrap()
+                    .to_string();
+                let app_handle = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = windsurf_tray::apply_windsurf_provider(&app_handle, &provider_id).await {
+                        log::warn!("Failed to apply Windsurf provider: {}", e);
+                    }
+                    let _ = refresh_tray_menus(&app_handle).await;
+                });
+            } else if event_id.starts_with("goose_provider_") {
+                let provider_id = event_id
+                    .strip_prefix("goose_provider_")
+                    .unwrap()
+                    .to_string();
+                let app_handle = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = goose_tray::apply_goose_provider(&app_handle, &provider_id).await {
+                        log::warn!("Failed to apply Goose provider: {}", e);
+                    }
+                    let _ = refresh_tray_menus(&app_handle).await;
+                });
+            } else if event_id.starts_with("amp_provider_") {
+                let provider_id = event_id
+                    .strip_prefix("amp_provider_")
+                    .unwrap()
+                    .to_string();
+                let app_handle = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = amp_tray::apply_amp_provider(&app_handle, &provider_id).await {
+                        log::warn!("Failed to apply Amp provider: {}", e);
+                    }
+                    let _ = refresh_tray_menus(&app_handle).await;
+                });
+            } else if event_id.starts_with("droid_provider_") {
+                let provider_id = event_id
+                    .strip_prefix("droid_provider_")
+                    .unwrap()
+                    .to_string();
+                let app_handle = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = droid_tray::apply_droid_provider(&app_handle, &provider_id).await {
+                        log::warn!("Failed to apply Factory Droid provider: {}", e);
                     }
                     let _ = refresh_tray_menus(&app_handle).await;
                 });
@@ -151,7 +276,7 @@ pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> Result<(), Box<dyn std::er
                     let app_handle = app.clone();
                     tauri::async_runtime::spawn(async move {
                         if let Err(e) = skills_tray::apply_skills_tool_toggle(&app_handle, &skill_id, &tool_key).await {
-                            eprintln!("Failed to toggle skill tool: {}", e);
+                            log::warn!("Failed to toggle skill tool: {}", e);
                         }
                         let _ = refresh_tray_menus(&app_handle).await;
                     });
@@ -165,7 +290,7 @@ pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> Result<(), Box<dyn std::er
                     let app_handle = app.clone();
                     tauri::async_runtime::spawn(async move {
                         if let Err(e) = mcp_tray::apply_mcp_tool_toggle(&app_handle, &server_id, &tool_key).await {
-                            eprintln!("Failed to toggle MCP tool: {}", e);
+                            log::warn!("Failed to toggle MCP tool: {}", e);
                         }
                         let _ = refresh_tray_menus(&app_handle).await;
                     });
@@ -222,14 +347,45 @@ pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> Result<(), Box<dyn std::er
 
 /// Refresh tray menus with flat structure
 pub async fn refresh_tray_menus<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
+    // Tray menu contents (hidden modules, pinned providers) from settings
+    let app_settings = load_app_settings(app).await;
+    let tray_config = &app_settings.tray_menu;
+
     // Check if modules are enabled
-    let opencode_enabled = opencode_tray::is_enabled_for_tray(app).await;
-    let omo_enabled = omo_tray::is_enabled_for_tray(app).await;
-    let omo_slim_enabled = omo_slim_tray::is_enabled_for_tray(app).await;
-    let claude_enabled = claude_tray::is_enabled_for_tray(app).await;
-    let codex_enabled = codex_tray::is_enabled_for_tray(app).await;
-    let opencode_plugins_enabled = opencode_tray::is_plugins_enabled_for_tray(app).await;
-    let skills_enabled = skills_tray::is_skills_enabled_for_tray(app).await;
+    let opencode_enabled = opencode_tray::is_enabled_for_tray(app).await && tray_config.is_module_shown("opencode");
+    let omo_enabled = omo_tray::is_enabled_for_tray(app).await && tray_config.is_module_shown("omo");
+    let omo_slim_enabled = omo_slim_tray::is_enabled_for_tray(app).await && tray_config.is_module_shown("omo_slim");
+    let opencode_plugins_enabled = opencode_tray::is_plugins_enabled_for_tray(app).await
+        && tray_config.is_module_shown("opencode_plugins");
+    let skills_enabled = skills_tray::is_skills_enabled_for_tray(app).await && tray_config.is_module_shown("skills");
+    let recent_actions_enabled = tray_config.is_module_shown("recent_actions");
+    let launcher_enabled = tray_config.is_module_shown("launcher");
+
+    // Provider modules are also loaded when one of their providers is pinned
+    let claude_available = claude_tray::is_enabled_for_tray(app).await;
+    let claude_enabled = claude_available && tray_config.is_module_shown("claude");
+    let claude_pinned = claude_available && tray_config.has_pins("claude");
+    let codex_available = codex_tray::is_enabled_for_tray(app).await;
+    let codex_enabled = codex_available && tray_config.is_module_shown("codex");
+    let codex_pinned = codex_available && tray_config.has_pins("codex");
+    let gemini_cli_available = gemini_cli_tray::is_enabled_for_tray(app).await;
+    let gemini_cli_enabled = gemini_cli_available && tray_config.is_module_shown("gemini_cli");
+    let gemini_cli_pinned = gemini_cli_available && tray_config.has_pins("gemini_cli");
+    let qwen_code_available = qwen_code_tray::is_enabled_for_tray(app).await;
+    let qwen_code_enabled = qwen_code_available && tray_config.is_module_shown("qwen_code");
+    let qwen_code_pinned = qwen_code_available && tray_config.has_pins("qwen_code");
+    let windsurf_available = windsurf_tray::is_enabled_for_tray(app).await;
+    let windsurf_enabled = windsurf_available && tray_config.is_module_shown("windsurf");
+    let windsurf_pinned = windsurf_available && tray_config.has_pins("windsurf");
+    let goose_available = goose_tray::is_enabled_for_tray(app).await;
+    let goose_enabled = goose_available && tray_config.is_module_shown("goose");
+    let goose_pinned = goose_available && tray_config.has_pins("goose");
+    let amp_available = amp_tray::is_enabled_for_tray(app).await;
+    let amp_enabled = amp_available && tray_config.is_module_shown("amp");
+    let amp_pinned = amp_available && tray_config.has_pins("amp");
+    let droid_available = droid_tray::is_enabled_for_tray(app).await;
+    let droid_enabled = droid_available && tray_config.is_module_shown("droid");
+    let droid_pinned = droid_available && tray_config.has_pins("droid");
 
     // Get data from modules (only if enabled)
     let (main_model_data, small_model_data) = if opencode_enabled {
@@ -255,22 +411,52 @@ pub async fn refresh_tray_menus<R: Runtime>(app: &AppHandle<R>) -> Result<(), St
     } else {
         omo_slim_tray::TrayConfigData { title: "──── Oh My OpenCode Slim ────".to_string(), items: vec![] }
     };
-    let claude_data = if claude_enabled {
+    let claude_data = if claude_enabled || claude_pinned {
         claude_tray::get_claude_code_tray_data(app).await?
     } else {
         claude_tray::TrayProviderData { title: "──── Claude Code ────".to_string(), items: vec![] }
     };
-    let codex_data = if codex_enabled {
+    let codex_data = if codex_enabled || codex_pinned {
         codex_tray::get_codex_tray_data(app).await?
     } else {
         codex_tray::TrayProviderData { title: "──── Codex ────".to_string(), items: vec![] }
     };
+    let gemini_cli_data = if gemini_cli_enabled || gemini_cli_pinned {
+        gemini_cli_tray::get_gemini_cli_tray_data(app).await?
+    } else {
+        gemini_cli_tray::TrayProviderData { title: "──── Gemini CLI ────".to_string(), items: vec![] }
+    };
+    let qwen_code_data = if qwen_code_enabled || qwen_code_pinned {
+        qwen_code_tray::get_qwen_code_tray_data(app).await?
+    } else {
+        qwen_code_tray::TrayProviderData { title: "──── Qwen Code ────".to_string(), items: vec![] }
+    };
+    let windsurf_data = if windsurf_enabled || windsurf_pinned {
+        windsurf_tray::get_windsurf_tray_data(app).await?
+    } else {
+        windsurf_tray::TrayProviderData { title: "──── Windsurf ────".to_string(), items: vec![] }
+    };
+    let goose_data = if goose_enabled || goose_pinned {
+        goose_tray::get_goose_tray_data(app).await?
+    } else {
+        goose_tray::TrayProviderData { title: "──── Goose ────".to_string(), items: vec![] }
+    };
+    let amp_data = if amp_enabled || amp_pinned {
+        amp_tray::get_amp_tray_data(app).await?
+    } else {
+        amp_tray::TrayProviderData { title: "──── Amp ────".to_string(), items: vec![] }
+    };
+    let droid_data = if droid_enabled || droid_pinned {
+        droid_tray::get_droid_tray_data(app).await?
+    } else {
+        droid_tray::TrayProviderData { title: "──── Factory Droid ────".to_string(), items: vec![] }
+    };
     let skills_data = if skills_enabled {
         skills_tray::get_skills_tray_data(app).await?
     } else {
         skills_tray::TraySkillData { title: "──── Skills ────".to_string(), items: vec![] }
     };
-    let mcp_enabled = mcp_tray::is_mcp_enabled_for_tray(app).await;
+    let mcp_enabled = mcp_tray::is_mcp_enabled_for_tray(app).await && tray_config.is_module_shown("mcp");
     let mcp_data = if mcp_enabled {
         mcp_tray::get_mcp_tray_data(app).await?
     } else {
@@ -440,6 +626,60 @@ pub async fn refresh_tray_menus<R: Runtime>(app: &AppHandle<R>) -> Result<(), St
     // Check if modules have items (must be done before consuming items in for loops)
     let claude_has_items = claude_enabled && !claude_data.items.is_empty();
     let codex_has_items = codex_enabled && !codex_data.items.is_empty();
+    let gemini_cli_has_items = gemini_cli_enabled && !gemini_cli_data.items.is_empty();
+    let qwen_code_has_items = qwen_code_enabled && !qwen_code_data.items.is_empty();
+    let windsurf_has_items = windsurf_enabled && !windsurf_data.items.is_empty();
+    let goose_has_items = goose_enabled && !goose_data.items.is_empty();
+    let amp_has_items = amp_enabled && !amp_data.items.is_empty();
+    let droid_has_items = droid_enabled && !droid_data.items.is_empty();
+
+    // Pinned providers section (must be built before consuming items)
+    let mut pinned_items: Vec<Box<dyn tauri::menu::IsMenuItem<R>>> = Vec::new();
+    for pin in &tray_config.pinned_providers {
+        let found = match pin.module.as_str() {
+            "claude" => claude_data.items.iter().find(|i| i.id == pin.id)
+                .map(|i| (i.display_name.clone(), i.is_selected, i.is_disabled)),
+            "codex" => codex_data.items.iter().find(|i| i.id == pin.id)
+                .map(|i| (i.display_name.clone(), i.is_selected, i.is_disabled)),
+            "gemini_cli" => gemini_cli_data.items.iter().find(|i| i.id == pin.id)
+                .map(|i| (i.display_name.clone(), i.is_selected, i.is_disabled)),
+            "qwen_code" => qwen_code_data.items.iter().find(|i| i.id == pin.id)
+                .map(|i| (i.display_name.clone(), i.is_selected, i.is_disabled)),
+            "windsurf" => windsurf_data.items.iter().find(|i| i.id == pin.id)
+                .map(|i| (i.display_name.clone(), i.is_selected, i.is_disabled)),
+            "goose" => goose_data.items.iter().find(|i| i.id == pin.id)
+                .map(|i| (i.display_name.clone(), i.is_selected, i.is_disabled)),
+            "amp" => amp_data.items.iter().find(|i| i.id == pin.id)
+                .map(|i| (i.display_name.clone(), i.is_selected, i.is_disabled)),
+            "droid" => droid_data.items.iter().find(|i| i.id == pin.id)
+                .map(|i| (i.display_name.clone(), i.is_selected, i.is_disabled)),
+            _ => None,
+        };
+        let Some((display_name, is_selected, is_disabled)) = found else {
+            continue;
+        };
+        // "pinned_" prefix is stripped in the menu event handler
+        let item_id = format!("pinned_{}_provider_{}", pin.module, pin.id);
+        let label = format!("{} · {}", tray_module_label(&pin.module), display_name);
+        let menu_item: Box<dyn tauri::menu::IsMenuItem<R>> = Box::new(
+            CheckMenuItem::with_id(app, &item_id, &label, !is_disabled, is_selected, None::<&str>)
+                .map_err(|e| e.to_string())?,
+        );
+        pinned_items.push(menu_item);
+    }
+    let pinned_header = if pinned_items.is_empty() {
+        None
+    } else {
+        Some(MenuItem::with_id(app, "pinned_header", "──── 常用 ────", false, None::<&str>)
+            .map_err(|e| e.to_string())?)
+    };
+
+    // Status summary for the tooltip (must be read before consuming items)
+    let claude_current = claude_data
+        .items
+        .iter()
+        .find(|item| item.is_selected)
+        .map(|item| item.display_name.clone());
 
     // Claude Code section (only if enabled and has items)
     let claude_header = if claude_has_items {
@@ -496,11 +736,195 @@ pub async fn refresh_tray_menus<R: Runtime>(app: &AppHandle<R>) -> Result<(), St
         }
     }
 
+    let gemini_cli_header = if gemini_cli_has_items {
+        Some(MenuItem::with_id(app, "gemini_cli_header", &gemini_cli_data.title, false, None::<&str>)
+            .map_err(|e| e.to_string())?)
+    } else {
+        None
+    };
+
+    // Build Gemini CLI items (only if has items)
+    let mut gemini_cli_items: Vec<Box<dyn tauri::menu::IsMenuItem<R>>> = Vec::new();
+    if gemini_cli_has_items {
+        for item in gemini_cli_data.items {
+            let item_id = format!("gemini_cli_provider_{}", item.id);
+            let menu_item: Box<dyn tauri::menu::IsMenuItem<R>> = Box::new(
+                CheckMenuItem::with_id(
+                    app,
+                    &item_id,
+                    &item.display_name,
+                    !item.is_disabled,
+                    item.is_selected,
+                    None::<&str>
+                )
+                .map_err(|e| e.to_string())?,
+            );
+            gemini_cli_items.push(menu_item);
+        }
+    }
+
+    let qwen_code_header = if qwen_code_has_items {
+        Some(MenuItem::with_id(app, "qwen_code_header", &qwen_code_data.title, false, None::<&str>)
+            .map_err(|e| e.to_string())?)
+    } else {
+        None
+    };
+
+    // Build Qwen Code items (only if has items)
+    let mut qwen_code_items: Vec<Box<dyn tauri::menu::IsMenuItem<R>>> = Vec::new();
+    if qwen_code_has_items {
+        for item in qwen_code_data.items {
+            let item_id = format!("qwen_code_provider_{}", item.id);
+            let menu_item: Box<dyn tauri::menu::IsMenuItem<R>> = Box::new(
+                CheckMenuItem::with_id(
+                    app,
+                    &item_id,
+                    &item.display_name,
+                    !item.is_disabled,
+                    item.is_selected,
+                    None::<&str>
+                )
+                .map_err(|e| e.to_string())?,
+            );
+            qwen_code_items.push(menu_item);
+        }
+    }
+
+    let windsurf_header = if windsurf_has_items {
+        Some(MenuItem::with_id(app, "windsurf_header", &windsurf_data.title, false, None::<&str>)
+            .map_err(|e| e.to_string())?)
+    } else {
+        None
+    };
+
+    // Build Windsurf items (only if has items)
+    let mut windsurf_items: Vec<Box<dyn tauri::menu::IsMenuItem<R>>> = Vec::new();
+    if windsurf_has_items {
+        for item in windsurf_data.items {
+            let item_id = format!("windsurf_provider_{}", item.id);
+            let menu_item: Box<dyn tauri::menu::IsMenuItem<R>> = Box::new(
+                CheckMenuItem::with_id(
+                    app,
+                    &item_id,
+                    &item.display_name,
+                    !item.is_disabled,
+                    item.is_selected,
+                    None::<&str>
+                )
+                .map_err(|e| e.to_string())?,
+            );
+            windsurf_items.push(menu_item);
+        }
+    }
+
+    let goose_header = if goose_has_items {
+        Some(MenuItem::with_id(app, "goose_header", &goose_data.title, false, None::<&str>)
+            .map_err(|e| e.to_string())?)
+    } else {
+        None
+    };
+
+    // Build Goose items (only if has items)
+    let mut goose_items: Vec<Box<dyn tauri::menu::IsMenuItem<R>>> = Vec::new();
+    if goose_has_items {
+        for item in goose_data.items {
+            let item_id = format!("goose_provider_{}", item.id);
+            let menu_item: Box<dyn tauri::menu::IsMenuItem<R>> = Box::new(
+                CheckMenuItem::with_id(
+                    app,
+                    &item_id,
+                    &item.display_name,
+                    !item.is_disabled,
+                    item.is_selected,
+                    None::<&str>
+                )
+                .map_err(|e| e.to_string())?,
+            );
+            goose_items.push(menu_item);
+        }
+    }
+
+    let amp_header = if amp_has_items {
+        Some(MenuItem::with_id(app, "amp_header", &amp_data.title, false, None::<&str>)
+            .map_err(|e| e.to_string())?)
+    } else {
+        None
+    };
+
+    // Build Amp items (only if has items)
+    let mut amp_items: Vec<Box<dyn tauri::menu::IsMenuItem<R>>> = Vec::new();
+    if amp_has_items {
+        for item in amp_data.items {
+            let item_id = format!("amp_provider_{}", item.id);
+            let menu_item: Box<dyn tauri::menu::IsMenuItem<R>> = Box::new(
+                CheckMenuItem::with_id(
+                    app,
+                    &item_id,
+                    &item.display_name,
+                    !item.is_disabled,
+                    item.is_selected,
+                    None::<&str>
+                )
+                .map_err(|e| e.to_string())?,
+            );
+            amp_items.push(menu_item);
+        }
+    }
+
+    let droid_header = if droid_has_items {
+        Some(MenuItem::with_id(app, "droid_header", &droid_data.title, false, None::<&str>)
+            .map_err(|e| e.to_string())?)
+    } else {
+        None
+    };
+
+    // Build Factory Droid items (only if has items)
+    let mut droid_items: Vec<Box<dyn tauri::menu::IsMenuItem<R>>> = Vec::new();
+    if droid_has_items {
+        for item in droid_data.items {
+            let item_id = format!("droid_provider_{}", item.id);
+            let menu_item: Box<dyn tauri::menu::IsMenuItem<R>> = Box::new(
+                CheckMenuItem::with_id(
+                    app,
+                    &item_id,
+                    &item.display_name,
+                    !item.is_disabled,
+                    item.is_selected,
+                    None::<&str>
+                )
+                .map_err(|e| e.to_string())?,
+            );
+            droid_items.push(menu_item);
+        }
+    }
+
+    // CLI launcher submenu (only if enabled)
+    let launcher_submenu = if launcher_enabled {
+        Some(build_launcher_submenu(app, &app_settings.launch_projects)?)
+    } else {
+        None
+    };
+
+    // Recent actions submenu (only if enabled)
+    let activity_submenu = if recent_actions_enabled {
+        Some(build_activity_submenu(app)?)
+    } else {
+        None
+    };
+
     // Combine all items into a flat menu
     let mut all_items: Vec<&dyn tauri::menu::IsMenuItem<R>> = Vec::new();
     all_items.push(&show_item);
     all_items.push(&separator1);
 
+    // Add pinned providers at the top
+    if let Some(ref header) = pinned_header {
+        all_items.push(header);
+    }
+    for item in &pinned_items {
+        all_items.push(item.as_ref());
+    }
+
     // Add OpenCode section if enabled
     if let Some(ref header) = opencode_model_header {
         all_items.push(header);
@@ -559,8 +983,56 @@ pub async fn refresh_tray_menus<R: Runtime>(app: &AppHandle<R>) -> Result<(), St
     for item in &codex_items {
         all_items.push(item.as_ref());
     }
+    // Add Gemini CLI section if enabled
+    if let Some(ref header) = gemini_cli_header {
+        all_items.push(header);
+    }
+    for item in &gemini_cli_items {
+        all_items.push(item.as_ref());
+    }
+    // Add Qwen Code section if enabled
+    if let Some(ref header) = qwen_code_header {
+        all_items.push(header);
+    }
+    for item in &qwen_code_items {
+        all_items.push(item.as_ref());
+    }
+    // Add Windsurf section if enabled
+    if let Some(ref header) = windsurf_header {
+        all_items.push(header);
+    }
+    for item in &windsurf_items {
+        all_items.push(item.as_ref());
+    }
+    // Add Goose section if enabled
+    if let Some(ref header) = goose_header {
+        all_items.push(header);
+    }
+    for item in &goose_items {
+        all_items.push(item.as_ref());
+    }
+    // Add Amp section if enabled
+    if let Some(ref header) = amp_header {
+        all_items.push(header);
+    }
+    for item in &amp_items {
+        all_items.push(item.as_ref());
+    }
+    // Add Factory Droid section if enabled
+    if let Some(ref header) = droid_header {
+        all_items.push(header);
+    }
+    for item in &droid_items {
+        all_items.push(item.as_ref());
+    }
 
     all_items.push(&separator1);
+    if let Some(ref submenu) = launcher_submenu {
+        all_items.push(submenu);
+    }
+    if let Some(ref submenu) = activity_submenu {
+        all_items.push(submenu);
+    }
     all_items.push(&quit_item);
 
     let menu = Menu::with_items(app, &all_items).map_err(|e| e.to_string())?;
@@ -569,9 +1041,61 @@ pub async fn refresh_tray_menus<R: Runtime>(app: &AppHandle<R>) -> Result<(), St
     let tray = app.state::<tauri::tray::TrayIcon>();
     tray.set_menu(Some(menu)).map_err(|e| e.to_string())?;
 
+    // Update tooltip with current status
+    let mut status_lines = vec!["AI Toolbox".to_string()];
+    if claude_enabled {
+        status_lines.push(format!(
+            "Claude Code: {}",
+            claude_current.as_deref().unwrap_or("未应用")
+        ));
+    }
+    if opencode_enabled {
+        let current_model = if main_model_data.current_display.is_empty() {
+            "未设置"
+        } else {
+            main_model_data.current_display.as_str()
+        };
+        status_lines.push(format!("OpenCode: {}", current_model));
+    }
+    status_lines.push(format!(
+        "上次备份: {}",
+        format_backup_time(app_settings.last_backup_time).unwrap_or_else(|| "从未备份".to_string())
+    ));
+    tray.set_tooltip(Some(status_lines.join("\n")))
+        .map_err(|e| e.to_string())?;
+
     Ok(())
 }
 
+/// Read app settings (tray menu config, last backup time), falling back to defaults
+async fn load_app_settings<R: Runtime>(app: &AppHandle<R>) -> AppSettings {
+    let state = app.state::<crate::db::DbState>();
+    let db = state.0.lock().await;
+    let records: Vec<serde_json::Value> = match db
+        .query("SELECT *, type::string(id) as id FROM settings:`app` LIMIT 1")
+        .await
+        .and_then(|mut result| result.take(0))
+    {
+        Ok(records) => records,
+        Err(_) => return AppSettings::default(),
+    };
+    records
+        .into_iter()
+        .next()
+        .map(crate::settings::adapter::from_db_value)
+        .unwrap_or_default()
+}
+
+/// Format the last backup time for display
+fn format_backup_time(last_backup_time: Option<String>) -> Option<String> {
+    let last_backup_time = last_backup_time?;
+    Some(
+        chrono::DateTime::parse_from_rfc3339(&last_backup_time)
+            .map(|time| time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or(last_backup_time),
+    )
+}
+
 /// Build a model selection submenu from tray data
 async fn build_model_submenu<R: Runtime>(
     app: &AppHandle<R>,
@@ -604,6 +1128,106 @@ async fn build_model_submenu<R: Runtime>(
     Ok(submenu)
 }
 
+/// Short module name used for pinned provider labels
+fn tray_module_label(module: &str) -> &str {
+    match module {
+        "claude" => "Claude Code",
+        "codex" => "Codex",
+        "gemini_cli" => "Gemini CLI",
+        "qwen_code" => "Qwen Code",
+        "windsurf" => "Windsurf",
+        "goose" => "Goose",
+        "amp" => "Amp",
+        "droid" => "Factory Droid",
+        other => other,
+    }
+}
+
+/// Build the CLI launcher submenu: one submenu per recent project plus folder pickers
+fn build_launcher_submenu<R: Runtime>(
+    app: &AppHandle<R>,
+    projects: &[String],
+) -> Result<Submenu<R>, String> {
+    let submenu = Submenu::with_id(app, "launcher_submenu", "启动 CLI", true)
+        .map_err(|e| e.to_string())?;
+
+    for project in projects {
+        let project_name = std::path::Path::new(project)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| project.clone());
+        let project_submenu = Submenu::with_id(app, &format!("launch_project_{}", project), &project_name, true)
+            .map_err(|e| e.to_string())?;
+        for tool in ["opencode", "claude"] {
+            let item = MenuItem::with_id(
+                app,
+                &format!("launch_{}_{}", tool, project),
+                crate::launcher::launch_tool_name(tool),
+                true,
+                None::<&str>,
+            )
+            .map_err(|e| e.to_string())?;
+            project_submenu.append(&item).map_err(|e| e.to_string())?;
+        }
+        submenu.append(&project_submenu).map_err(|e| e.to_string())?;
+    }
+
+    if !projects.is_empty() {
+        let separator = PredefinedMenuItem::separator(app).map_err(|e| e.to_string())?;
+        submenu.append(&separator).map_err(|e| e.to_string())?;
+    }
+    for tool in ["opencode", "claude"] {
+        let item = MenuItem::with_id(
+            app,
+            &format!("launch_pick_{}", tool),
+            &format!("选择目录启动 {}...", crate::launcher::launch_tool_name(tool)),
+            true,
+            None::<&str>,
+        )
+        .map_err(|e| e.to_string())?;
+        submenu.append(&item).map_err(|e| e.to_string())?;
+    }
+
+    Ok(submenu)
+}
+
+/// Build the recent actions submenu from the in-memory activity log
+fn build_activity_submenu<R: Runtime>(app: &AppHandle<R>) -> Result<Submenu<R>, String> {
+    let submenu = Submenu::with_id(app, "activity_submenu", "最近操作", true)
+        .map_err(|e| e.to_string())?;
+
+    let entries = crate::activity_log::entries();
+    if entries.is_empty() {
+        let empty_item = MenuItem::with_id(app, "activity_empty", "  暂无记录", false, None::<&str>)
+            .map_err(|e| e.to_string())?;
+        submenu.append(&empty_item).map_err(|e| e.to_string())?;
+        return Ok(submenu);
+    }
+
+    for (index, entry) in entries.iter().enumerate() {
+        let time = chrono::DateTime::parse_from_rfc3339(&entry.timestamp)
+            .map(|t| t.format("%H:%M").to_string())
+            .unwrap_or_default();
+        let item = MenuItem::with_id(
+            app,
+            &format!("activity_entry_{}", index),
+            &format!("{}  {}", time, entry.message),
+            false,
+            None::<&str>,
+        )
+        .map_err(|e| e.to_string())?;
+        submenu.append(&item).map_err(|e| e.to_string())?;
+    }
+
+    let separator = PredefinedMenuItem::separator(app).map_err(|e| e.to_string())?;
+    let clear_item = MenuItem::with_id(app, "activity_log_clear", "清空记录", true, None::<&str>)
+        .map_err(|e| e.to_string())?;
+    submenu.append(&separator).map_err(|e| e.to_string())?;
+    submenu.append(&clear_item).map_err(|e| e.to_string())?;
+
+    Ok(submenu)
+}
+
 /// Build a skill submenu with tool checkmarks
 fn build_skill_submenu<R: Runtime>(
     app: &AppHandle<R>,
@@ -55,6 +55,7 @@ pub async fn check_for_updates(
 
     // Fetch latest.json using http_client with proxy support
     let client = http_client::client(&state).await?;
+    let latest_json_url = http_client::resolve_url(&state, &latest_json_url).await;
     let response = client
         .get(&latest_json_url)
         .send()
@@ -76,6 +77,9 @@ pub async fn check_for_updates(
     let latest_version = release.version.trim_start_matches('v').to_string();
 
     let has_update = compare_versions(&latest_version, &current_version) > 0;
+    if has_update {
+        crate::activity_log::record(&app_handle, "update", format!("发现新版本 v{}", latest_version));
+    }
 
     // Get signature and url for current platform
     let platform_info = release.platforms.get(&current_platform);
@@ -214,12 +218,12 @@ pub async fn install_update(
 
             match install_result {
                 Ok(_) => {
-                    println!("Update installed successfully");
+                    log::info!("Update installed successfully");
                     Ok(true)
                 }
                 Err(e) => {
                     let error_msg = format!("Failed to install update: {}", e);
-                    eprintln!("{}", error_msg);
+                    log::warn!("{}", error_msg);
                     Err(error_msg)
                 }
             }
@@ -1,10 +1,16 @@
 import React from 'react';
-import { Modal, Tabs, Form, Input, Select, Space, Button, Alert, message, AutoComplete, Radio } from 'antd';
+import { Modal, Tabs, Form, Input, InputNumber, Select, Space, Button, Alert, message, AutoComplete, Radio } from 'antd';
 import { EyeInvisibleOutlined, EyeOutlined, CloudDownloadOutlined } from '@ant-design/icons';
 import { useTranslation } from 'react-i18next';
 import { invoke } from '@tauri-apps/api/core';
 import { useAppStore } from '@/stores';
-import type { ClaudeCodeProvider, ClaudeProviderFormValues, ClaudeSettingsConfig } from '@/types/claudecode';
+import type {
+  ClaudeCodeProvider,
+  ClaudeProviderCategory,
+  ClaudeProviderFormValues,
+  ClaudeSettingsConfig,
+  ClaudeTokenLimits,
+} from '@/types/claudecode';
 import { listFavoriteProviders } from '@/services/opencodeApi';
 
 const { TextArea } = Input;
@@ -39,6 +45,37 @@ interface FetchModelsResponse {
   total: number;
 }
 
+const ENV_LINE_PATTERN = /^[A-Za-z_][A-Za-z0-9_]*=/;
+
+// `KEY=VALUE` 每行一个 <-> extraEnv
+const formatEnvLines = (env?: Record<string, string>): string =>
+  Object.entries(env ?? {})
+    .map(([key, value]) => `${key}=${value}`)
+    .join('\n');
+
+const parseEnvLines = (text?: string): Record<string, string> => {
+  const env: Record<string, string> = {};
+  for (const line of (text ?? '').split('\n')) {
+    const trimmed = line.trim();
+    if (!trimmed) continue;
+    const eq = trimmed.indexOf('=');
+    env[trimmed.slice(0, eq).trim()] = trimmed.slice(eq + 1).trim();
+  }
+  return env;
+};
+
+// 表单字段 -> settingsConfig.limits，留空的不写入
+const LIMIT_FIELDS: [keyof ClaudeTokenLimits, string][] = [
+  ['maxOutputTokens', 'claudecode.provider.maxOutputTokens'],
+  ['maxThinkingTokens', 'claudecode.provider.maxThinkingTokens'],
+  ['maxMcpOutputTokens', 'claudecode.provider.maxMcpOutputTokens'],
+];
+
+const pickLimits = (limits?: ClaudeTokenLimits): ClaudeTokenLimits =>
+  Object.fromEntries(
+    LIMIT_FIELDS.map(([key]) => [key, limits?.[key]]).filter(([, value]) => typeof value === 'number'),
+  );
+
 const ClaudeProviderFormModal: React.FC<ClaudeProviderFormModalProps> = ({
   open,
   provider,
@@ -71,6 +108,9 @@ const ClaudeProviderFormModal: React.FC<ClaudeProviderFormModalProps> = ({
   const [currentBaseUrl, setCurrentBaseUrl] = React.useState<string>('');
 
   const isEdit = !!provider && !isCopy;
+  // custom: Anthropic 兼容接口；bedrock / vertex: 云厂商，不需要 baseUrl 和 apiKey
+  const category: ClaudeProviderCategory = Form.useWatch('category', form) ?? 'custom';
+  const isCloud = category === 'bedrock' || category === 'vertex';
 
   // 当 Modal 打开时，根据 defaultTab 设置 activeTab
   React.useEffect(() => {
@@ -99,12 +139,22 @@ const ClaudeProviderFormModal: React.FC<ClaudeProviderFormModalProps> = ({
 
       form.setFieldsValue({
         name: provider.name,
+        category: settingsConfig.bedrock ? 'bedrock' : settingsConfig.vertex ? 'vertex' : 'custom',
+        bedrockRegion: settingsConfig.bedrock?.region,
+        bedrockProfile: settingsConfig.bedrock?.profile,
+        bedrockBearerToken: settingsConfig.bedrock?.bearerToken,
+        vertexRegion: settingsConfig.vertex?.region,
+        vertexProjectId: settingsConfig.vertex?.projectId,
         baseUrl,
         apiKey: settingsConfig.env?.ANTHROPIC_AUTH_TOKEN || settingsConfig.env?.ANTHROPIC_API_KEY,
         model: settingsConfig.model,
         haikuModel: settingsConfig.haikuModel,
         sonnetModel: settingsConfig.sonnetModel,
         opusModel: settingsConfig.opusModel,
+        extraEnv: formatEnvLines(settingsConfig.extraEnv),
+        proxy: settingsConfig.proxy,
+        noProxy: settingsConfig.noProxy,
+        limits: settingsConfig.limits,
         notes: provider.notes,
       });
     }
@@ -214,7 +264,18 @@ const ClaudeProviderFormModal: React.FC<ClaudeProviderFormModalProps> = ({
       // 只验证当前模式需要的字段
       const fieldsToValidate = activeTab === 'import'
         ? ['sourceProvider', 'name', 'baseUrl', 'apiKey', 'model', 'haikuModel', 'sonnetModel', 'opusModel', 'notes']
-        : ['name', 'baseUrl', 'apiKey', 'model', 'haikuModel', 'sonnetModel', 'opusModel', 'notes'];
+        : [
+            'name',
+            'category',
+            ...(category === 'bedrock'
+              ? ['bedrockRegion', 'bedrockProfile', 'bedrockBearerToken']
+              : category === 'vertex'
+                ? ['vertexRegion', 'vertexProjectId']
+                : ['baseUrl', 'apiKey']),
+            'model', 'haikuModel', 'sonnetModel', 'opusModel', 'proxy', 'noProxy',
+            ...LIMIT_FIELDS.map(([key]) => ['limits', key]),
+            'extraEnv', 'notes',
+          ];
       
       const values = await form.validateFields(fieldsToValidate);
       
@@ -222,13 +283,27 @@ const ClaudeProviderFormModal: React.FC<ClaudeProviderFormModalProps> = ({
       
       const formValues: ClaudeProviderFormValues = {
         name: values.name,
-        category: 'custom',
+        category: activeTab === 'import' ? 'custom' : category,
         baseUrl: values.baseUrl,
         apiKey: values.apiKey,
+        bedrock: activeTab !== 'import' && category === 'bedrock'
+          ? {
+              region: values.bedrockRegion.trim(),
+              profile: values.bedrockProfile?.trim() || undefined,
+              bearerToken: values.bedrockBearerToken?.trim() || undefined,
+            }
+          : undefined,
+        vertex: activeTab !== 'import' && category === 'vertex'
+          ? { region: values.vertexRegion.trim(), projectId: values.vertexProjectId.trim() }
+          : undefined,
         model: values.model,
         haikuModel: values.haikuModel,
         sonnetModel: values.sonnetModel,
         opusModel: values.opusModel,
+        extraEnv: parseEnvLines(values.extraEnv),
+        proxy: values.proxy?.trim() || undefined,
+        noProxy: values.noProxy?.trim() || undefined,
+        limits: pickLimits(values.limits),
         notes: values.notes,
         sourceProviderId: activeTab === 'import' ? selectedProvider?.id : undefined,
       };
@@ -326,64 +401,113 @@ const ClaudeProviderFormModal: React.FC<ClaudeProviderFormModalProps> = ({
         <Input placeholder={t('claudecode.provider.namePlaceholder')} />
       </Form.Item>
 
-      <Form.Item
-        name="baseUrl"
-        label={t('claudecode.provider.baseUrl')}
-        rules={[{ required: true, message: t('common.error') }]}
-      >
-        <Input
-          placeholder={t('claudecode.provider.baseUrlPlaceholder')}
-          onChange={(e) => setCurrentBaseUrl(e.target.value)}
-        />
+      <Form.Item name="category" label={t('claudecode.provider.category')} initialValue="custom">
+        <Radio.Group>
+          <Radio value="custom">{t('claudecode.provider.categoryCustom')}</Radio>
+          <Radio value="bedrock">AWS Bedrock</Radio>
+          <Radio value="vertex">Google Vertex AI</Radio>
+        </Radio.Group>
       </Form.Item>
 
-      <Form.Item
-        name="apiKey"
-        label={t('claudecode.provider.apiKey')}
-        rules={[{ required: true, message: t('common.error') }]}
-      >
-        <Input
-          type={showApiKey ? 'text' : 'password'}
-          placeholder={t('claudecode.provider.apiKeyPlaceholder')}
-          addonAfter={
-            <Button
-              type="text"
-              size="small"
-              icon={showApiKey ? <EyeInvisibleOutlined /> : <EyeOutlined />}
-              onClick={() => setShowApiKey(!showApiKey)}
-            >
-              {showApiKey ? t('claudecode.provider.hideApiKey') : t('claudecode.provider.showApiKey')}
-            </Button>
-          }
-        />
-      </Form.Item>
+      {category === 'bedrock' && (
+        <>
+          <Form.Item
+            name="bedrockRegion"
+            label={t('claudecode.provider.region')}
+            rules={[{ required: true, message: t('common.error') }]}
+          >
+            <Input placeholder="us-east-1" />
+          </Form.Item>
+          <Form.Item name="bedrockProfile" label={t('claudecode.provider.awsProfile')}>
+            <Input placeholder={t('claudecode.provider.awsProfilePlaceholder')} />
+          </Form.Item>
+          <Form.Item name="bedrockBearerToken" label={t('claudecode.provider.bedrockApiKey')}>
+            <Input.Password placeholder={t('claudecode.provider.bedrockApiKeyPlaceholder')} />
+          </Form.Item>
+        </>
+      )}
 
-      {/* 获取模型列表 */}
-      <Form.Item wrapperCol={{ offset: labelCol.span, span: wrapperCol.span }}>
-        <Space size="middle" style={{ width: '100%' }}>
-          <Radio.Group
-            value={fetchApiType}
-            onChange={(e) => setFetchApiType(e.target.value)}
-            size="small"
+      {category === 'vertex' && (
+        <>
+          <Form.Item
+            name="vertexRegion"
+            label={t('claudecode.provider.region')}
+            rules={[{ required: true, message: t('common.error') }]}
           >
-            <Radio value="openai_compat">{t('claudecode.fetchModels.openaiCompat')}</Radio>
-            <Radio value="native">{t('claudecode.fetchModels.native')}</Radio>
-          </Radio.Group>
-          <Button
-            type="default"
-            icon={<CloudDownloadOutlined />}
-            loading={loadingModels}
-            onClick={handleFetchModels}
+            <Input placeholder="us-east5" />
+          </Form.Item>
+          <Form.Item
+            name="vertexProjectId"
+            label={t('claudecode.provider.gcpProject')}
+            rules={[{ required: true, message: t('common.error') }]}
           >
-            {t('claudecode.fetchModels.button')}
-          </Button>
-          {fetchedModels.length > 0 && (
-            <span style={{ color: '#52c41a' }}>
-              {t('claudecode.fetchModels.loaded', { count: fetchedModels.length })}
-            </span>
-          )}
-        </Space>
-      </Form.Item>
+            <Input placeholder="my-gcp-project" />
+          </Form.Item>
+        </>
+      )}
+
+      {!isCloud && (
+        <>
+          <Form.Item
+            name="baseUrl"
+            label={t('claudecode.provider.baseUrl')}
+            rules={[{ required: true, message: t('common.error') }]}
+          >
+            <Input
+              placeholder={t('claudecode.provider.baseUrlPlaceholder')}
+              onChange={(e) => setCurrentBaseUrl(e.target.value)}
+            />
+          </Form.Item>
+
+          <Form.Item
+            name="apiKey"
+            label={t('claudecode.provider.apiKey')}
+            rules={[{ required: true, message: t('common.error') }]}
+          >
+            <Input
+              type={showApiKey ? 'text' : 'password'}
+              placeholder={t('claudecode.provider.apiKeyPlaceholder')}
+              addonAfter={
+                <Button
+                  type="text"
+                  size="small"
+                  icon={showApiKey ? <EyeInvisibleOutlined /> : <EyeOutlined />}
+                  onClick={() => setShowApiKey(!showApiKey)}
+                >
+                  {showApiKey ? t('claudecode.provider.hideApiKey') : t('claudecode.provider.showApiKey')}
+                </Button>
+              }
+            />
+          </Form.Item>
+
+          {/* 获取模型列表 */}
+          <Form.Item wrapperCol={{ offset: labelCol.span, span: wrapperCol.span }}>
+            <Space size="middle" style={{ width: '100%' }}>
+              <Radio.Group
+                value={fetchApiType}
+                onChange={(e) => setFetchApiType(e.target.value)}
+                size="small"
+              >
+                <Radio value="openai_compat">{t('claudecode.fetchModels.openaiCompat')}</Radio>
+                <Radio value="native">{t('claudecode.fetchModels.native')}</Radio>
+              </Radio.Group>
+              <Button
+                type="default"
+                icon={<CloudDownloadOutlined />}
+                loading={loadingModels}
+                onClick={handleFetchModels}
+              >
+                {t('claudecode.fetchModels.button')}
+              </Button>
+              {fetchedModels.length > 0 && (
+                <span style={{ color: '#52c41a' }}>
+                  {t('claudecode.fetchModels.loaded', { count: fetchedModels.length })}
+                </span>
+              )}
+            </Space>
+          </Form.Item>
+        </>
+      )}
 
       <Form.Item name="model" label={t('claudecode.model.defaultModel')}>
         <AutoComplete
@@ -433,6 +557,54 @@ const ClaudeProviderFormModal: React.FC<ClaudeProviderFormModalProps> = ({
         />
       </Form.Item>
 
+      <Form.Item
+        name="proxy"
+        label={t('claudecode.provider.proxy')}
+        tooltip={t('claudecode.provider.proxyTooltip')}
+        rules={[
+          {
+            pattern: /^((https?|socks5h?):\/\/|(?![a-z][a-z0-9+.-]*:\/\/))\S+$/i,
+            message: t('claudecode.provider.proxyInvalid'),
+          },
+        ]}
+      >
+        <Input placeholder="http://127.0.0.1:7890" />
+      </Form.Item>
+
+      <Form.Item name="noProxy" label={t('claudecode.provider.noProxy')}>
+        <Input placeholder="localhost,127.0.0.1" />
+      </Form.Item>
+
+      {LIMIT_FIELDS.map(([key, label]) => (
+        <Form.Item key={key} name={['limits', key]} label={t(label)}>
+          <InputNumber
+            min={1}
+            precision={0}
+            placeholder={t('claudecode.provider.limitPlaceholder')}
+            style={{ width: '100%' }}
+          />
+        </Form.Item>
+      ))}
+
+      <Form.Item
+        name="extraEnv"
+        label={t('claudecode.provider.extraEnv')}
+        rules={[
+          {
+            validator: (_, value?: string) =>
+              (value ?? '').split('\n').every((line) => !line.trim() || ENV_LINE_PATTERN.test(line.trim()))
+                ? Promise.resolve()
+                : Promise.reject(new Error(t('claudecode.provider.extraEnvInvalid'))),
+          },
+        ]}
+      >
+        <TextArea
+          rows={3}
+          placeholder={t('claudecode.provider.extraEnvPlaceholder')}
+          style={{ fontFamily: 'monospace' }}
+        />
+      </Form.Item>
+
       <Form.Item name="notes" label={t('claudecode.provider.notes')}>
         <TextArea
           rows={3}
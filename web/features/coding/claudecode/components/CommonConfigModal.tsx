@@ -3,6 +3,7 @@ import { Modal, Alert, message } from 'antd';
 import { useTranslation } from 'react-i18next';
 import { getClaudeCommonConfig, saveClaudeCommonConfig, saveClaudeLocalConfig } from '@/services/claudeCodeApi';
 import JsonEditor from '@/components/common/JsonEditor';
+import { getErrorMessage } from '@/utils/backendError';
 
 interface CommonConfigModalProps {
   open: boolean;
@@ -51,7 +52,7 @@ const CommonConfigModal: React.FC<CommonConfigModalProps> = ({
       }
     } catch (error) {
       console.error('Failed to load common config:', error);
-      const errorMsg = error instanceof Error ? error.message : String(error);
+      const errorMsg = getErrorMessage(error);
       message.error(errorMsg || t('common.error'));
     }
   };
@@ -75,7 +76,7 @@ const CommonConfigModal: React.FC<CommonConfigModalProps> = ({
       onCancel();
     } catch (error) {
       console.error('Failed to save common config:', error);
-      const errorMsg = error instanceof Error ? error.message : String(error);
+      const errorMsg = getErrorMessage(error);
       message.error(errorMsg || t('common.error'));
     } finally {
       setLoading(false);
@@ -34,13 +34,13 @@ import {
   updateClaudeProvider,
   saveClaudeLocalConfig,
   deleteClaudeProvider,
-  selectClaudeProvider,
-  applyClaudeConfig,
+  activateClaudeProvider,
   readClaudeSettings,
   toggleClaudeCodeProviderDisabled,
   reorderClaudeProviders,
 } from '@/services/claudeCodeApi';
 import { useRefreshStore } from '@/stores';
+import { getErrorMessage } from '@/utils/backendError';
 import { refreshTrayMenu } from '@/services/appApi';
 import ClaudeProviderCard from '../components/ClaudeProviderCard';
 import ClaudeProviderFormModal from '../components/ClaudeProviderFormModal';
@@ -132,13 +132,12 @@ const ClaudeCodePage: React.FC = () => {
 
   const handleSelectProvider = async (provider: ClaudeCodeProvider) => {
     try {
-      await selectClaudeProvider(provider.id);
-      await applyClaudeConfig(provider.id);
+      await activateClaudeProvider(provider.id);
       message.success(t('claudecode.apply.success'));
       await loadConfig();
     } catch (error) {
       console.error('Failed to select provider:', error);
-      message.error(t('common.error'));
+      message.error(getErrorMessage(error));
     }
   };
 
@@ -150,7 +149,7 @@ const ClaudeCodePage: React.FC = () => {
       await refreshTrayMenu();
     } catch (error) {
       console.error('Failed to toggle provider disabled status:', error);
-      message.error(t('common.error'));
+      message.error(getErrorMessage(error));
     }
   };
 
@@ -181,7 +180,7 @@ const ClaudeCodePage: React.FC = () => {
       // 失败回滚
       console.error('Failed to reorder providers:', error);
       setProviders(oldProviders);
-      message.error(t('common.error'));
+      message.error(getErrorMessage(error));
     }
   };
 
@@ -229,7 +228,7 @@ const ClaudeCodePage: React.FC = () => {
           await loadConfig();
         } catch (error) {
           console.error('Failed to delete provider:', error);
-          message.error(t('common.error'));
+          message.error(getErrorMessage(error));
         }
       },
     });
@@ -287,17 +286,25 @@ const ClaudeCodePage: React.FC = () => {
 
   const doSaveProvider = async (values: ClaudeProviderFormValues) => {
     try {
-      const settingsConfigObj: Record<string, unknown> = {
-        env: {
-          ANTHROPIC_BASE_URL: values.baseUrl,
-          ANTHROPIC_AUTH_TOKEN: values.apiKey,
-        },
-      };
+      const settingsConfigObj: Record<string, unknown> = values.bedrock
+        ? { bedrock: values.bedrock }
+        : values.vertex
+          ? { vertex: values.vertex }
+          : {
+              env: {
+                ANTHROPIC_BASE_URL: values.baseUrl,
+                ANTHROPIC_AUTH_TOKEN: values.apiKey,
+              },
+            };
 
       if (values.model) settingsConfigObj.model = values.model;
       if (values.haikuModel) settingsConfigObj.haikuModel = values.haikuModel;
       if (values.sonnetModel) settingsConfigObj.sonnetModel = values.sonnetModel;
       if (values.opusModel) settingsConfigObj.opusModel = values.opusModel;
+      if (values.extraEnv && Object.keys(values.extraEnv).length > 0) settingsConfigObj.extraEnv = values.extraEnv;
+      if (values.proxy) settingsConfigObj.proxy = values.proxy;
+      if (values.proxy && values.noProxy) settingsConfigObj.noProxy = values.noProxy;
+      if (values.limits && Object.keys(values.limits).length > 0) settingsConfigObj.limits = values.limits;
 
       // Check if this is a temporary provider from local file
       const isLocalTemp = editingProvider?.id === "__local__";
@@ -338,7 +345,7 @@ const ClaudeCodePage: React.FC = () => {
       await loadConfig();
     } catch (error) {
       console.error('Failed to save provider:', error);
-      message.error(t('common.error'));
+      message.error(getErrorMessage(error));
       throw error;
     }
   };
@@ -348,17 +355,25 @@ const ClaudeCodePage: React.FC = () => {
       const existingProvider = providers.find((p) => p.id === id);
       if (!existingProvider) return;
 
-      const settingsConfigObj: Record<string, unknown> = {
-        env: {
-          ANTHROPIC_BASE_URL: values.baseUrl,
-          ANTHROPIC_AUTH_TOKEN: values.apiKey,
-        },
-      };
+      const settingsConfigObj: Record<string, unknown> = values.bedrock
+        ? { bedrock: values.bedrock }
+        : values.vertex
+          ? { vertex: values.vertex }
+          : {
+              env: {
+                ANTHROPIC_BASE_URL: values.baseUrl,
+                ANTHROPIC_AUTH_TOKEN: values.apiKey,
+              },
+            };
 
       if (values.model) settingsConfigObj.model = values.model;
       if (values.haikuModel) settingsConfigObj.haikuModel = values.haikuModel;
       if (values.sonnetModel) settingsConfigObj.sonnetModel = values.sonnetModel;
       if (values.opusModel) settingsConfigObj.opusModel = values.opusModel;
+      if (values.extraEnv && Object.keys(values.extraEnv).length > 0) settingsConfigObj.extraEnv = values.extraEnv;
+      if (values.proxy) settingsConfigObj.proxy = values.proxy;
+      if (values.proxy && values.noProxy) settingsConfigObj.noProxy = values.noProxy;
+      if (values.limits && Object.keys(values.limits).length > 0) settingsConfigObj.limits = values.limits;
 
       const providerData: ClaudeCodeProvider = {
         ...existingProvider,
@@ -376,7 +391,7 @@ const ClaudeCodePage: React.FC = () => {
       await loadConfig();
     } catch (error) {
       console.error('Failed to update provider:', error);
-      message.error(t('common.error'));
+      message.error(getErrorMessage(error));
       throw error;
     }
   };
@@ -18,14 +18,15 @@ export interface BackupFileInfo {
 /**
  * Backup database to a local zip file
  * @param backupPath - The directory to save the backup file
+ * @param allowSecrets - Continue although the backup contains API keys (only matters in "block" scan mode)
  * @returns The full path of the created backup file
  */
-export const backupDatabase = async (backupPath: string): Promise<string> => {
+export const backupDatabase = async (backupPath: string, allowSecrets?: boolean): Promise<string> => {
   if (!backupPath) {
     throw new Error('Backup path is not configured');
   }
 
-  const result = await invoke<string>('backup_database', { backupPath });
+  const result = await invoke<string>('backup_database', { backupPath, allowSecrets });
   return result;
 };
 
@@ -71,13 +72,15 @@ export const backupToWebDAV = async (
   url: string,
   username: string,
   password: string,
-  remotePath: string
+  remotePath: string,
+  allowSecrets?: boolean
 ): Promise<string> => {
   const result = await invoke<string>('backup_to_webdav', {
     url,
     username,
     password,
     remotePath,
+    allowSecrets,
   });
   return result;
 };
@@ -5,12 +5,52 @@
  */
 
 import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import type { BalanceResult, ConfigDiff } from './settingsApi';
 import type {
   ClaudeCodeProvider,
+  ClaudeProviderQuery,
   ClaudeCommonConfig,
   ClaudeLocalConfigInput,
   ClaudeSettings,
   ClaudePluginStatus,
+  ClaudeProviderTestResult,
+  ClaudeSettingsBackup,
+  ClaudeHookSet,
+  ClaudeHookSetInput,
+  ClaudePermissionPreset,
+  ClaudePermissionPresetInput,
+  ClaudeSlashCommand,
+  ClaudeSlashCommandInput,
+  ClaudeCommandTemplate,
+  ClaudeCommandImportResult,
+  ClaudeAgent,
+  ClaudeAgentInput,
+  ClaudeAgentPreset,
+  ClaudeSessionSummary,
+  ClaudeSessionExportFormat,
+  ClaudeSessionExport,
+  ClaudeProviderBundle,
+  ClaudeProviderImportResult,
+  ClaudeBulkImportResult,
+  ClaudeProviderVersion,
+  ClaudeFailoverConfig,
+  ClaudeProviderHealth,
+  ClaudeProviderTemplate,
+  ClaudeProviderTemplateList,
+  ClaudeCliStatus,
+  ClaudeCliInstallMethod,
+  ClaudeCliInstallProgress,
+  ClaudeShell,
+  ClaudeShellProfileResult,
+  ClaudeConfigDrift,
+  ClaudeConfigDirInfo,
+  ClaudeLocalSettings,
+  ClaudeApplyTarget,
+  ClaudeProviderTestEntry,
+  ClaudeProviderTestProgress,
+  ClaudeTokenLimits,
+  ClaudeApplyHistoryEntry,
 } from '@/types/claudecode';
 
 /**
@@ -20,6 +60,14 @@ export const getClaudeConfigPath = async (): Promise<string> => {
   return await invoke<string>('get_claude_config_path');
 };
 
+/**
+ * Get the Claude config directory and whether it comes from the app settings,
+ * CLAUDE_CONFIG_DIR or the default ~/.claude
+ */
+export const getClaudeConfigDirInfo = async (): Promise<ClaudeConfigDirInfo> => {
+  return await invoke<ClaudeConfigDirInfo>('get_claude_config_dir_info');
+};
+
 /**
  * Reveal Claude Code configuration folder in file explorer
  */
@@ -28,14 +76,32 @@ export const revealClaudeConfigFolder = async (): Promise<void> => {
 };
 
 /**
- * List all Claude Code providers
+ * List Claude Code providers; archived ones only with `includeArchived`
+ * `query` filters by category / search text and pages the result when `limit` is set.
+ */
+export const listClaudeProviders = async (
+  includeArchived?: boolean,
+  query?: ClaudeProviderQuery
+): Promise<ClaudeCodeProvider[]> => {
+  return await invoke<ClaudeCodeProvider[]>('list_claude_providers', { includeArchived, query });
+};
+
+/**
+ * Number of providers listClaudeProviders would return without paging
  */
-export const listClaudeProviders = async (): Promise<ClaudeCodeProvider[]> => {
-  return await invoke<ClaudeCodeProvider[]>('list_claude_providers');
+export const countClaudeProviders = async (
+  includeArchived?: boolean,
+  query?: ClaudeProviderQuery
+): Promise<number> => {
+  return await invoke<number>('count_claude_providers', { includeArchived, query });
 };
 
 /**
  * Create a new Claude Code provider
+ * Rejects with an INVALID_PROVIDER_SETTINGS validation error whose params map
+ * each bad settingsConfig field (e.g. "env.ANTHROPIC_BASE_URL") to the rule it
+ * broke: json, object, string, env_name, url, model, proxy, required, integer
+ * or unknown.
  */
 export const createClaudeProvider = async (
   provider: Omit<ClaudeCodeProvider, 'id' | 'createdAt' | 'updatedAt'>
@@ -43,8 +109,17 @@ export const createClaudeProvider = async (
   return await invoke<ClaudeCodeProvider>('create_claude_provider', { provider });
 };
 
+/**
+ * Copy a provider as "<name> (copy)" at the end of the list
+ * @param newId - Record id of the copy (generated when omitted)
+ */
+export const cloneClaudeProvider = async (id: string, newId?: string): Promise<ClaudeCodeProvider> => {
+  return await invoke<ClaudeCodeProvider>('clone_claude_provider', { id, newId });
+};
+
 /**
  * Update an existing Claude Code provider
+ * settingsConfig is validated the same way as in createClaudeProvider.
  */
 export const updateClaudeProvider = async (
   provider: ClaudeCodeProvider
@@ -59,6 +134,224 @@ export const deleteClaudeProvider = async (id: string): Promise<void> => {
   await invoke('delete_claude_provider', { id });
 };
 
+/**
+ * Export providers (all when ids is omitted) as a shareable bundle
+ * @param redactSecrets - Replace API keys and tokens with placeholders
+ * @param allowSecrets - Export keys even when the secret scan is set to block
+ */
+export const exportClaudeProviders = async (
+  redactSecrets: boolean,
+  ids?: string[],
+  allowSecrets?: boolean
+): Promise<ClaudeProviderBundle> => {
+  return await invoke<ClaudeProviderBundle>('export_claude_providers', { ids, redactSecrets, allowSecrets });
+};
+
+/**
+ * Import a provider bundle; providers with an existing name are updated and keep their keys
+ */
+export const importClaudeProviders = async (
+  bundle: ClaudeProviderBundle
+): Promise<ClaudeProviderImportResult> => {
+  return await invoke<ClaudeProviderImportResult>('import_claude_providers', { bundle });
+};
+
+/**
+ * Create many providers at once, from provider objects or CSV text
+ * (name,base_url,api_key,model; a header row may reorder the columns)
+ */
+export const bulkImportClaudeProviders = async (
+  input:
+    | { providers: Omit<ClaudeCodeProvider, 'id' | 'isApplied' | 'isDisabled' | 'createdAt' | 'updatedAt'>[] }
+    | { csv: string }
+): Promise<ClaudeBulkImportResult> => {
+  return await invoke<ClaudeBulkImportResult>('bulk_import_claude_providers', input);
+};
+
+/**
+ * Stored versions of a provider, newest first
+ */
+export const listClaudeProviderHistory = async (id: string): Promise<ClaudeProviderVersion[]> => {
+  return await invoke<ClaudeProviderVersion[]>('list_claude_provider_history', { id });
+};
+
+/**
+ * Restore a provider to a stored version (re-applied when it is the current provider)
+ */
+export const restoreClaudeProviderVersion = async (
+  id: string,
+  version: number
+): Promise<ClaudeCodeProvider> => {
+  return await invoke<ClaudeCodeProvider>('restore_claude_provider_version', { id, version });
+};
+
+/**
+ * Get the health monitor / failover settings
+ */
+export const getClaudeFailoverConfig = async (): Promise<ClaudeFailoverConfig> => {
+  return await invoke<ClaudeFailoverConfig>('get_claude_failover_config');
+};
+
+/**
+ * Save the health monitor / failover settings; returns the normalized config
+ */
+export const saveClaudeFailoverConfig = async (
+  config: ClaudeFailoverConfig
+): Promise<ClaudeFailoverConfig> => {
+  return await invoke<ClaudeFailoverConfig>('save_claude_failover_config', { config });
+};
+
+/**
+ * Latest health check result of each checked provider
+ */
+export const listClaudeProviderHealth = async (): Promise<ClaudeProviderHealth[]> => {
+  return await invoke<ClaudeProviderHealth[]>('list_claude_provider_health');
+};
+
+/**
+ * Provider presets from the template gallery (bundled list when offline)
+ */
+export const fetchClaudeProviderTemplates = async (): Promise<ClaudeProviderTemplateList> => {
+  return await invoke<ClaudeProviderTemplateList>('fetch_claude_provider_templates');
+};
+
+/**
+ * Create a provider from a template with the user's API key
+ */
+export const createClaudeProviderFromTemplate = async (
+  template: ClaudeProviderTemplate,
+  apiKey: string
+): Promise<ClaudeCodeProvider> => {
+  const settingsConfig = {
+    env: {
+      ANTHROPIC_BASE_URL: template.baseUrl,
+      ANTHROPIC_AUTH_TOKEN: apiKey,
+    },
+    model: template.model,
+    haikuModel: template.haikuModel,
+    sonnetModel: template.sonnetModel,
+    opusModel: template.opusModel,
+  };
+  return await createClaudeProvider({
+    name: template.name,
+    category: template.category,
+    settingsConfig: JSON.stringify(settingsConfig),
+    websiteUrl: template.websiteUrl,
+  });
+};
+
+/**
+ * Whether the claude CLI is installed, how, and whether an update is available
+ */
+export const getClaudeCliStatus = async (): Promise<ClaudeCliStatus> => {
+  return await invoke<ClaudeCliStatus>('get_claude_cli_status');
+};
+
+/**
+ * Install or upgrade the claude CLI; resolves with the status afterwards
+ * Defaults to the current install method, else npm/bun, else the native installer.
+ */
+export const installClaudeCli = async (method?: ClaudeCliInstallMethod): Promise<ClaudeCliStatus> => {
+  return await invoke<ClaudeCliStatus>('install_claude_cli', { method });
+};
+
+/**
+ * Subscribe to installer output lines while installClaudeCli runs
+ */
+export const onClaudeCliInstallProgress = async (
+  handler: (progress: ClaudeCliInstallProgress) => void
+): Promise<UnlistenFn> => {
+  return await listen<ClaudeCliInstallProgress>('claude-cli-install-progress', (event) => handler(event.payload));
+};
+
+/**
+ * Write a provider's env vars into a managed block of the shell profiles
+ * Defaults to the shells in use on this system. settings.json env still wins
+ * over the shell for the same variable.
+ */
+export const applyClaudeProviderToShell = async (
+  providerId: string,
+  shells?: ClaudeShell[]
+): Promise<ClaudeShellProfileResult[]> => {
+  return await invoke<ClaudeShellProfileResult[]>('apply_claude_provider_to_shell', { providerId, shells });
+};
+
+/**
+ * Remove the managed env block from the shell profiles (all of them by default)
+ */
+export const removeClaudeShellEnv = async (shells?: ClaudeShell[]): Promise<ClaudeShellProfileResult[]> => {
+  return await invoke<ClaudeShellProfileResult[]>('remove_claude_shell_env', { shells });
+};
+
+/**
+ * Compare the live settings.json with what the applied provider and common config produce
+ */
+export const checkClaudeConfigDrift = async (): Promise<ClaudeConfigDrift> => {
+  return await invoke<ClaudeConfigDrift>('check_claude_config_drift');
+};
+
+/**
+ * Read settings.local.json
+ */
+export const getClaudeLocalSettings = async (): Promise<ClaudeLocalSettings> => {
+  return await invoke<ClaudeLocalSettings>('get_claude_local_settings');
+};
+
+/**
+ * Replace settings.local.json
+ */
+export const saveClaudeLocalSettings = async (settings: Record<string, unknown>): Promise<void> => {
+  await invoke('save_claude_local_settings', { settings });
+};
+
+/**
+ * Merge `patch` into settings.local.json (objects key by key) and return the result
+ */
+export const mergeClaudeLocalSettings = async (
+  patch: Record<string, unknown>
+): Promise<Record<string, unknown>> => {
+  return await invoke<Record<string, unknown>>('merge_claude_local_settings', { patch });
+};
+
+export const getClaudeApplyTarget = async (): Promise<ClaudeApplyTarget> => {
+  return await invoke<ClaudeApplyTarget>('get_claude_apply_target');
+};
+
+/**
+ * Choose the file later applies write to
+ */
+export const setClaudeApplyTarget = async (target: ClaudeApplyTarget): Promise<void> => {
+  await invoke('set_claude_apply_target', { target });
+};
+
+/**
+ * Provider applies, newest first (default 50)
+ */
+export const listClaudeApplyHistory = async (limit?: number): Promise<ClaudeApplyHistoryEntry[]> => {
+  return await invoke<ClaudeApplyHistoryEntry[]>('list_claude_apply_history', { limit });
+};
+
+/**
+ * Write the settings file of an earlier apply back and mark its provider applied
+ */
+export const revertClaudeApply = async (id: string): Promise<void> => {
+  await invoke('revert_claude_apply', { id });
+};
+
+/**
+ * Global token limits (providers can override them with settingsConfig.limits)
+ */
+export const getClaudeTokenLimits = async (): Promise<ClaudeTokenLimits> => {
+  return await invoke<ClaudeTokenLimits>('get_claude_token_limits');
+};
+
+/**
+ * Save the global token limits and re-apply the current provider
+ */
+export const saveClaudeTokenLimits = async (limits: ClaudeTokenLimits): Promise<void> => {
+  await invoke('save_claude_token_limits', { limits });
+};
+
 /**
  * Reorder Claude Code providers
  * Note: UI for drag-and-drop reordering is not yet implemented
@@ -75,6 +368,13 @@ export const selectClaudeProvider = async (id: string): Promise<void> => {
   await invoke('select_claude_provider', { id });
 };
 
+/**
+ * Select and apply a provider in one step; on failure the previous provider stays applied
+ */
+export const activateClaudeProvider = async (id: string): Promise<void> => {
+  await invoke('activate_claude_provider', { id });
+};
+
 /**
  * Apply Claude Code configuration (write to settings.json)
  */
@@ -82,6 +382,54 @@ export const applyClaudeConfig = async (providerId: string): Promise<void> => {
   await invoke('apply_claude_config', { providerId });
 };
 
+/**
+ * Changes applyClaudeConfig would make to settings.json, without writing them
+ */
+export const previewClaudeConfig = async (providerId: string): Promise<ConfigDiff[]> => {
+  return await invoke<ConfigDiff[]>('apply_claude_config', { providerId, dryRun: true });
+};
+
+/**
+ * Send a minimal request to a provider's base URL without applying it
+ */
+export const testClaudeProvider = async (
+  providerId: string,
+  model?: string
+): Promise<ClaudeProviderTestResult> => {
+  return await invoke<ClaudeProviderTestResult>('test_claude_provider', { providerId, model });
+};
+
+/**
+ * Test every non-archived provider concurrently; ranked fastest first, failures last
+ */
+export const testAllClaudeProviders = async (model?: string): Promise<ClaudeProviderTestEntry[]> => {
+  return await invoke<ClaudeProviderTestEntry[]>('test_all_claude_providers', { model });
+};
+
+/**
+ * Cached results of the last batch tests, ranked
+ */
+export const getClaudeProviderTestResults = async (): Promise<ClaudeProviderTestEntry[]> => {
+  return await invoke<ClaudeProviderTestEntry[]>('get_claude_provider_test_results');
+};
+
+/**
+ * Listen for batch test progress; returns the unlisten function
+ */
+export const onClaudeProviderTestProgress = async (
+  handler: (progress: ClaudeProviderTestProgress) => void
+): Promise<UnlistenFn> => {
+  return await listen<ClaudeProviderTestProgress>('claude-provider-test-progress', (event) => handler(event.payload));
+};
+
+/**
+ * Remaining quota of a provider from its balance checker (tool 'claude');
+ * a result from the last 5 minutes is reused unless `force`
+ */
+export const queryClaudeProviderBalance = async (providerId: string, force?: boolean): Promise<BalanceResult> => {
+  return await invoke<BalanceResult>('query_claude_provider_balance', { providerId, force });
+};
+
 /**
  * Read Claude Code settings.json
  */
@@ -89,6 +437,185 @@ export const readClaudeSettings = async (): Promise<ClaudeSettings> => {
   return await invoke<ClaudeSettings>('read_claude_settings');
 };
 
+/**
+ * List settings.json backups (taken before each provider apply), newest first
+ */
+export const listClaudeSettingsBackups = async (): Promise<ClaudeSettingsBackup[]> => {
+  return await invoke<ClaudeSettingsBackup[]>('list_claude_settings_backups');
+};
+
+/**
+ * Restore settings.json from a backup (the current file is backed up first)
+ */
+export const rollbackClaudeSettings = async (backupId: string): Promise<void> => {
+  await invoke('rollback_claude_settings', { backupId });
+};
+
+/**
+ * List hook sets (merged into settings.json on apply while enabled)
+ */
+export const listClaudeHookSets = async (): Promise<ClaudeHookSet[]> => {
+  return await invoke<ClaudeHookSet[]>('list_claude_hook_sets');
+};
+
+export const createClaudeHookSet = async (input: ClaudeHookSetInput): Promise<ClaudeHookSet> => {
+  return await invoke<ClaudeHookSet>('create_claude_hook_set', { input });
+};
+
+export const updateClaudeHookSet = async (id: string, input: ClaudeHookSetInput): Promise<ClaudeHookSet> => {
+  return await invoke<ClaudeHookSet>('update_claude_hook_set', { id, input });
+};
+
+export const deleteClaudeHookSet = async (id: string): Promise<void> => {
+  await invoke('delete_claude_hook_set', { id });
+};
+
+/**
+ * List permission presets (merged into settings.json on apply while enabled)
+ */
+export const listClaudePermissionPresets = async (): Promise<ClaudePermissionPreset[]> => {
+  return await invoke<ClaudePermissionPreset[]>('list_claude_permission_presets');
+};
+
+export const createClaudePermissionPreset = async (
+  input: ClaudePermissionPresetInput
+): Promise<ClaudePermissionPreset> => {
+  return await invoke<ClaudePermissionPreset>('create_claude_permission_preset', { input });
+};
+
+export const updateClaudePermissionPreset = async (
+  id: string,
+  input: ClaudePermissionPresetInput
+): Promise<ClaudePermissionPreset> => {
+  return await invoke<ClaudePermissionPreset>('update_claude_permission_preset', { id, input });
+};
+
+export const deleteClaudePermissionPreset = async (id: string): Promise<void> => {
+  await invoke('delete_claude_permission_preset', { id });
+};
+
+/**
+ * List custom slash commands (~/.claude/commands)
+ */
+export const listClaudeSlashCommands = async (): Promise<ClaudeSlashCommand[]> => {
+  return await invoke<ClaudeSlashCommand[]>('list_claude_slash_commands');
+};
+
+/**
+ * Create or edit a slash command; pass originalName to rename it
+ */
+export const saveClaudeSlashCommand = async (
+  input: ClaudeSlashCommandInput,
+  originalName?: string
+): Promise<ClaudeSlashCommand> => {
+  return await invoke<ClaudeSlashCommand>('save_claude_slash_command', { input, originalName });
+};
+
+export const deleteClaudeSlashCommand = async (name: string): Promise<void> => {
+  await invoke('delete_claude_slash_command', { name });
+};
+
+export const listClaudeCommandTemplates = async (): Promise<ClaudeCommandTemplate[]> => {
+  return await invoke<ClaudeCommandTemplate[]>('list_claude_command_templates');
+};
+
+/**
+ * Create (without id) or update a slash command template
+ */
+export const saveClaudeCommandTemplate = async (
+  command: ClaudeSlashCommandInput,
+  id?: string
+): Promise<ClaudeCommandTemplate> => {
+  return await invoke<ClaudeCommandTemplate>('save_claude_command_template', { id, command });
+};
+
+export const deleteClaudeCommandTemplate = async (id: string): Promise<void> => {
+  await invoke('delete_claude_command_template', { id });
+};
+
+/**
+ * Write a template to ~/.claude/commands, optionally under another name
+ */
+export const installClaudeCommandTemplate = async (id: string, name?: string): Promise<ClaudeSlashCommand> => {
+  return await invoke<ClaudeSlashCommand>('install_claude_command_template', { id, name });
+};
+
+/**
+ * Export slash commands (all, or the given names) and all templates to a JSON file
+ */
+export const exportClaudeSlashCommands = async (path: string, names?: string[]): Promise<void> => {
+  await invoke('export_claude_slash_commands', { path, names });
+};
+
+export const importClaudeSlashCommands = async (
+  path: string,
+  overwrite?: boolean
+): Promise<ClaudeCommandImportResult> => {
+  return await invoke<ClaudeCommandImportResult>('import_claude_slash_commands', { path, overwrite });
+};
+
+/**
+ * List sub-agent files (~/.claude/agents)
+ */
+export const listClaudeAgents = async (): Promise<ClaudeAgent[]> => {
+  return await invoke<ClaudeAgent[]>('list_claude_agents');
+};
+
+/**
+ * Create or edit a sub-agent file; pass originalFileName to rename it
+ */
+export const saveClaudeAgent = async (input: ClaudeAgentInput, originalFileName?: string): Promise<ClaudeAgent> => {
+  return await invoke<ClaudeAgent>('save_claude_agent', { input, originalFileName });
+};
+
+export const deleteClaudeAgent = async (fileName: string): Promise<void> => {
+  await invoke('delete_claude_agent', { fileName });
+};
+
+export const listClaudeAgentPresets = async (): Promise<ClaudeAgentPreset[]> => {
+  return await invoke<ClaudeAgentPreset[]>('list_claude_agent_presets');
+};
+
+/**
+ * Create (without id) or update an agent preset
+ */
+export const saveClaudeAgentPreset = async (
+  agent: ClaudeAgentInput,
+  enabled: boolean,
+  id?: string
+): Promise<ClaudeAgentPreset> => {
+  return await invoke<ClaudeAgentPreset>('save_claude_agent_preset', { id, agent, enabled });
+};
+
+export const deleteClaudeAgentPreset = async (id: string): Promise<void> => {
+  await invoke('delete_claude_agent_preset', { id });
+};
+
+/**
+ * Write enabled presets to ~/.claude/agents now; returns the changed agent names
+ */
+export const syncClaudeAgentPresets = async (): Promise<string[]> => {
+  return await invoke<string[]>('sync_claude_agent_presets');
+};
+
+/**
+ * List recent Claude Code sessions, newest first
+ */
+export const listClaudeSessions = async (limit?: number): Promise<ClaudeSessionSummary[]> => {
+  return await invoke<ClaudeSessionSummary[]>('list_claude_sessions', { limit });
+};
+
+/**
+ * Export a session transcript as Markdown or HTML to the given path
+ */
+export const exportClaudeSession = async (
+  sessionId: string,
+  format: ClaudeSessionExportFormat,
+  path: string
+): Promise<ClaudeSessionExport> => {
+  return await invoke<ClaudeSessionExport>('export_claude_session', { sessionId, format, path });
+};
+
 /**
  * Get common configuration
  */
@@ -141,6 +668,17 @@ export async function toggleClaudeCodeProviderDisabled(
   });
 }
 
+/**
+ * Archive a provider: kept, but hidden from the active list (not the applied one)
+ */
+export const archiveClaudeProvider = async (id: string): Promise<void> => {
+  await invoke('archive_claude_provider', { id });
+};
+
+export const unarchiveClaudeProvider = async (id: string): Promise<void> => {
+  await invoke('unarchive_claude_provider', { id });
+};
+
 /**
  * Get Claude onboarding status
  * @returns true if hasCompletedOnboarding is set
@@ -5,6 +5,7 @@
  */
 
 import { invoke } from '@tauri-apps/api/core';
+import type { ConfigDiff } from './settingsApi';
 import type {
   CodexProvider,
   CodexCommonConfig,
@@ -79,6 +80,13 @@ export const applyCodexConfig = async (providerId: string): Promise<void> => {
   await invoke('apply_codex_config', { providerId });
 };
 
+/**
+ * Changes applyCodexConfig would make to auth.json / config.toml, without writing them
+ */
+export const previewCodexConfig = async (providerId: string): Promise<ConfigDiff[]> => {
+  return await invoke<ConfigDiff[]>('apply_codex_config', { providerId, dryRun: true });
+};
+
 export async function toggleCodexProviderDisabled(
   providerId: string,
   isDisabled: boolean
@@ -5,6 +5,7 @@
  */
 
 import { invoke } from '@tauri-apps/api/core';
+import type { ConfigDiff } from './settingsApi';
 import type { OpenCodeConfig, OpenCodeProvider } from '@/types/opencode';
 
 /**
@@ -81,6 +82,55 @@ export const saveOpenCodeConfig = async (config: OpenCodeConfig): Promise<void>
   await invoke('save_opencode_config', { config });
 };
 
+/**
+ * Changes saveOpenCodeConfig would make to the config file, without writing them
+ */
+export const previewOpenCodeConfig = async (config: OpenCodeConfig): Promise<ConfigDiff[]> => {
+  return await invoke<ConfigDiff[]>('save_opencode_config', { config, dryRun: true });
+};
+
+/**
+ * A config value that violates the opencode JSON schema
+ */
+export interface OpenCodeSchemaError {
+  pointer: string;  // JSON pointer, "" for the root
+  keyword: string;  // Failed schema keyword, or "parse" when the file is not valid JSONC
+  message: string;
+}
+
+/**
+ * The schema a config was validated against
+ */
+export interface OpenCodeSchemaInfo {
+  url: string;
+  source: 'remote' | 'bundled';
+  updatedAt?: string;
+}
+
+export interface OpenCodeValidationResult {
+  /** No errors and every schema keyword was checked */
+  valid: boolean;
+  errors: OpenCodeSchemaError[];
+  /** Schema keywords that are not implemented, so parts of the config went unchecked */
+  uncheckedKeywords: string[];
+  schema: OpenCodeSchemaInfo;
+}
+
+/**
+ * Validate a config against the opencode JSON schema
+ * @param config Config to check; the current config file when omitted
+ */
+export const validateOpenCodeConfig = async (config?: OpenCodeConfig): Promise<OpenCodeValidationResult> => {
+  return await invoke<OpenCodeValidationResult>('validate_opencode_config', { config: config ?? null });
+};
+
+/**
+ * Download and cache the schema the config's $schema points to
+ */
+export const refreshOpenCodeSchema = async (): Promise<OpenCodeSchemaInfo> => {
+  return await invoke<OpenCodeSchemaInfo>('refresh_opencode_schema');
+};
+
 /**
  * Get OpenCode common config
  */
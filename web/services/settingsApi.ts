@@ -5,6 +5,7 @@
  */
 
 import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
 
 // Types matching Rust structures
 export interface WebDAVConfig {
@@ -25,6 +26,29 @@ export interface S3Config {
   public_domain: string;
 }
 
+export interface HostOverride {
+  host: string;
+  target: string;
+  enabled: boolean;
+}
+
+export interface HttpClientConfig {
+  connect_timeout_secs: number;
+  request_timeout_secs: number;
+  pool_idle_timeout_secs: number;
+  pool_max_idle_per_host: number;
+}
+
+export interface TrayPinnedProvider {
+  module: string;
+  id: string;
+}
+
+export interface TrayMenuConfig {
+  hidden_modules: string[];
+  pinned_providers: TrayPinnedProvider[];
+}
+
 export interface AppSettings {
   language: string;
   current_module: string;
@@ -36,8 +60,48 @@ export interface AppSettings {
   last_backup_time: string | null;
   launch_on_startup: boolean;
   minimize_to_tray_on_close: boolean;
+  start_minimized: boolean;
   proxy_url: string;
   theme: string;
+  host_overrides: HostOverride[];
+  http: HttpClientConfig;
+  action_notifications: boolean;
+  notifications: NotificationSettings;
+  tray_menu: TrayMenuConfig;
+  launch_projects: string[];
+  default_terminal: string;
+  local_api: LocalApiConfig;
+  log_level: LogLevel;
+  telemetry_enabled: boolean;
+  secret_scan_mode: SecretScanMode;
+  /** Defer scheduled backups and config rewrites while claude / opencode runs */
+  defer_jobs_during_sessions: boolean;
+  /** Where the Claude provider template gallery is downloaded from (empty = built-in URL) */
+  provider_template_url: string;
+  /** Claude config directory (empty = CLAUDE_CONFIG_DIR, else ~/.claude) */
+  claude_config_dir: string;
+}
+
+/** What a possible API key in a share string or backup does */
+export type SecretScanMode = 'warn' | 'block' | 'off';
+
+export type LogLevel = 'error' | 'warn' | 'info' | 'debug' | 'trace';
+
+export interface NotificationSettings {
+  backup: boolean;
+  update: boolean;
+  provider_health: boolean;
+  free_models: boolean;
+  renewal: boolean;
+  budget: boolean;
+}
+
+export interface LocalApiConfig {
+  enabled: boolean;
+  port: number;
+  token: string;
+  /** LAN remote control: listen on all interfaces over HTTPS */
+  lan_enabled: boolean;
 }
 
 // Default settings
@@ -66,8 +130,43 @@ export const defaultSettings: AppSettings = {
   last_backup_time: null,
   launch_on_startup: true,
   minimize_to_tray_on_close: true,
+  start_minimized: false,
   proxy_url: '',
   theme: 'system',
+  host_overrides: [],
+  http: {
+    connect_timeout_secs: 10,
+    request_timeout_secs: 30,
+    pool_idle_timeout_secs: 90,
+    pool_max_idle_per_host: 8,
+  },
+  action_notifications: false,
+  notifications: {
+    backup: true,
+    update: true,
+    provider_health: true,
+    free_models: false,
+    renewal: true,
+    budget: true,
+  },
+  tray_menu: {
+    hidden_modules: [],
+    pinned_providers: [],
+  },
+  launch_projects: [],
+  default_terminal: '',
+  local_api: {
+    enabled: false,
+    port: 17321,
+    token: '',
+    lan_enabled: false,
+  },
+  log_level: 'info',
+  telemetry_enabled: false,
+  secret_scan_mode: 'warn',
+  defer_jobs_during_sessions: true,
+  provider_template_url: '',
+  claude_config_dir: '',
 };
 
 /**
@@ -141,3 +240,937 @@ export const restartApp = async (): Promise<void> => {
 export const testProxyConnection = async (proxyUrl: string): Promise<void> => {
   await invoke('test_proxy_connection', { proxyUrl });
 };
+
+/**
+ * Save tray menu contents and rebuild the tray menu
+ */
+export const saveTrayMenuConfig = async (trayMenu: TrayMenuConfig): Promise<void> => {
+  await updateSettings({ tray_menu: trayMenu });
+  await invoke('rebuild_tray_menu');
+};
+
+/**
+ * Open a terminal in a project directory with a coding CLI started
+ */
+export const launchCodingCli = async (tool: 'opencode' | 'claude', cwd: string): Promise<void> => {
+  await invoke('launch_coding_cli', { tool, cwd });
+};
+
+export interface AutostartStatus {
+  enabled: boolean;
+  start_minimized: boolean;
+}
+
+/**
+ * Get autostart on login status
+ */
+export const getAutostart = async (): Promise<AutostartStatus> => {
+  return await invoke<AutostartStatus>('get_autostart');
+};
+
+/**
+ * Enable or disable autostart on login, optionally starting minimized to tray
+ */
+export const setAutostart = async (
+  enabled: boolean,
+  startMinimized?: boolean
+): Promise<AutostartStatus> => {
+  return await invoke<AutostartStatus>('set_autostart', { enabled, startMinimized });
+};
+
+export interface VaultKey {
+  id: string;
+  name: string;
+  hint: string;
+  /** Reference to put in provider configs: `vault://<id>` */
+  reference: string;
+  usages: string[];
+  created_at: string;
+  updated_at: string;
+}
+
+export interface VaultKeyInput {
+  id?: string;
+  name: string;
+  /** Required when creating; a new value rotates an existing key */
+  secret?: string;
+}
+
+/**
+ * List API keys stored in the vault (secrets are never returned)
+ */
+export const listVaultKeys = async (): Promise<VaultKey[]> => {
+  return await invoke<VaultKey[]>('list_vault_keys');
+};
+
+/**
+ * Create a vault key, or rename / rotate an existing one
+ */
+export const saveVaultKey = async (input: VaultKeyInput): Promise<VaultKey> => {
+  return await invoke<VaultKey>('save_vault_key', { input });
+};
+
+/**
+ * Delete a vault key that is no longer referenced
+ */
+export const deleteVaultKey = async (id: string): Promise<void> => {
+  await invoke('delete_vault_key', { id });
+};
+
+export interface ProjectToolProfile {
+  /** Claude: provider id; OpenCode: favorite provider_id */
+  provider_id: string;
+  /** Overrides the provider's default model */
+  model?: string;
+}
+
+export interface Project {
+  id: string;
+  name: string;
+  path: string;
+  claude?: ProjectToolProfile | null;
+  opencode?: ProjectToolProfile | null;
+  /** oh-my-opencode config id */
+  oh_my_opencode?: string | null;
+  last_applied_at?: string | null;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface ProjectInput {
+  id?: string;
+  /** Defaults to the directory name */
+  name?: string;
+  path: string;
+  claude?: ProjectToolProfile | null;
+  opencode?: ProjectToolProfile | null;
+  oh_my_opencode?: string | null;
+}
+
+export interface ProjectApplyResult {
+  project_id: string;
+  files: string[];
+  /** Diffs of the files on a dry run */
+  changes: ConfigDiff[];
+}
+
+/**
+ * List registered projects
+ */
+export const listProjects = async (): Promise<Project[]> => {
+  return await invoke<Project[]>('list_projects');
+};
+
+/**
+ * Register a project directory or edit an existing project
+ */
+export const saveProject = async (input: ProjectInput): Promise<Project> => {
+  return await invoke<Project>('save_project', { input });
+};
+
+/**
+ * Remove a project (its config files are left in place)
+ */
+export const deleteProject = async (id: string): Promise<void> => {
+  await invoke('delete_project', { id });
+};
+
+/**
+ * Write the project-level Claude / OpenCode / oh-my-opencode configs of the project at `path`
+ */
+export const applyProjectProfile = async (path: string, dryRun?: boolean): Promise<ProjectApplyResult> => {
+  return await invoke<ProjectApplyResult>('apply_project_profile', { path, dryRun });
+};
+
+export interface ProjectClaudeSettingsFile {
+  path: string;
+  exists: boolean;
+  /** File content; an empty object when missing */
+  settings: Record<string, unknown>;
+}
+
+export interface ProjectClaudeSettings {
+  project_id: string;
+  /** .claude/settings.json, shared with the team */
+  shared: ProjectClaudeSettingsFile;
+  /** .claude/settings.local.json, personal (provider env goes here) */
+  local: ProjectClaudeSettingsFile;
+}
+
+/**
+ * Read both Claude settings files of a registered project
+ */
+export const getProjectClaudeSettings = async (id: string): Promise<ProjectClaudeSettings> => {
+  return await invoke<ProjectClaudeSettings>('get_project_claude_settings', { id });
+};
+
+/**
+ * Replace a project's .claude/settings.json (or settings.local.json with `local`)
+ */
+export const saveProjectClaudeSettings = async (
+  id: string,
+  local: boolean,
+  settings: Record<string, unknown>
+): Promise<void> => {
+  await invoke('save_project_claude_settings', { id, local, settings });
+};
+
+/**
+ * Merge `patch` into a project's .claude/settings.json (or settings.local.json with `local`)
+ */
+export const mergeProjectClaudeSettings = async (
+  id: string,
+  local: boolean,
+  patch: Record<string, unknown>
+): Promise<Record<string, unknown>> => {
+  return await invoke<Record<string, unknown>>('merge_project_claude_settings', { id, local, patch });
+};
+
+export type BalanceAuthStyle = 'bearer' | 'header' | 'query' | 'none';
+
+export interface BalanceChecker {
+  provider_id: string;
+  tool: 'claude' | 'codex' | 'opencode';
+  /** URL template; {base_url}, {origin} and {api_key} are filled in */
+  endpoint: string;
+  method: 'GET' | 'POST';
+  auth_style: BalanceAuthStyle;
+  /** Header / query parameter name for 'header' / 'query' auth */
+  auth_name?: string;
+  /** Extra headers; values are templates like `endpoint` and may be vault:// references */
+  headers?: Record<string, string>;
+  /** JSON body template for POST */
+  body?: string;
+  /** e.g. 'data.total_available' or 'balance_infos[0].total_balance' */
+  balance_path: string;
+  divisor?: number;
+  unit?: string;
+  last_balance?: number;
+  last_checked_at?: string;
+}
+
+export type BalanceTemplate = Omit<BalanceChecker, 'provider_id' | 'tool' | 'body' | 'last_balance' | 'last_checked_at'> & {
+  id: string;
+  name: string;
+};
+
+export interface BalanceResult {
+  provider_id: string;
+  balance: number;
+  unit?: string;
+  checked_at: string;
+}
+
+/**
+ * Built-in balance checker templates for common relays and vendors
+ */
+export const listBalanceTemplates = async (): Promise<BalanceTemplate[]> => {
+  return await invoke<BalanceTemplate[]>('list_balance_templates');
+};
+
+export const listBalanceCheckers = async (): Promise<BalanceChecker[]> => {
+  return await invoke<BalanceChecker[]>('list_balance_checkers');
+};
+
+/**
+ * Create or replace the balance checker of a provider
+ */
+export const saveBalanceChecker = async (checker: BalanceChecker): Promise<BalanceChecker> => {
+  return await invoke<BalanceChecker>('save_balance_checker', { checker });
+};
+
+export const deleteBalanceChecker = async (providerId: string): Promise<void> => {
+  await invoke('delete_balance_checker', { providerId });
+};
+
+/**
+ * Query a provider's balance with its checker
+ */
+export const queryBalance = async (providerId: string): Promise<BalanceResult> => {
+  return await invoke<BalanceResult>('query_balance', { providerId });
+};
+
+export interface SpeedTestTarget {
+  tool: 'claude' | 'codex' | 'opencode';
+  provider_id: string;
+  /** Defaults to the provider's model */
+  model?: string;
+}
+
+export interface SpeedTestRun {
+  success: boolean;
+  /** Time to first token */
+  ttft_ms?: number;
+  total_ms?: number;
+  output_tokens?: number;
+  tokens_per_sec?: number;
+  error?: string;
+}
+
+export interface SpeedTestResult {
+  id: string;
+  tool: SpeedTestTarget['tool'];
+  provider_id: string;
+  provider_name: string;
+  model: string;
+  runs: SpeedTestRun[];
+  /** 0 - 1 */
+  failure_rate: number;
+  avg_ttft_ms?: number;
+  avg_tokens_per_sec?: number;
+  created_at: string;
+}
+
+/**
+ * Run the streaming speed test against each target `runs` times (default 3)
+ */
+export const runSpeedTest = async (targets: SpeedTestTarget[], runs?: number): Promise<SpeedTestResult[]> => {
+  return await invoke<SpeedTestResult[]>('run_speed_test', { targets, runs });
+};
+
+/**
+ * Stored speed test results, newest first
+ */
+export const listSpeedTestHistory = async (providerId?: string, limit?: number): Promise<SpeedTestResult[]> => {
+  return await invoke<SpeedTestResult[]>('list_speed_test_history', { providerId, limit });
+};
+
+export const clearSpeedTestHistory = async (): Promise<void> => {
+  await invoke('clear_speed_test_history');
+};
+
+export interface ModelRule {
+  id: string;
+  /** Requested model name; `*` matches any characters */
+  pattern: string;
+  /** Provider to route to; unset keeps the current provider */
+  target_provider?: string;
+  target_model: string;
+  enabled: boolean;
+  note?: string;
+  sort_index: number;
+}
+
+export interface ModelRuleInput {
+  id?: string;
+  pattern: string;
+  target_provider?: string;
+  target_model: string;
+  enabled: boolean;
+  note?: string;
+  sort_index?: number;
+}
+
+/**
+ * Model routing rules in match order (first match wins)
+ */
+export const listModelRules = async (): Promise<ModelRule[]> => {
+  return await invoke<ModelRule[]>('list_model_rules');
+};
+
+export const saveModelRule = async (input: ModelRuleInput): Promise<ModelRule> => {
+  return await invoke<ModelRule>('save_model_rule', { input });
+};
+
+export const deleteModelRule = async (id: string): Promise<void> => {
+  await invoke('delete_model_rule', { id });
+};
+
+export const reorderModelRules = async (ids: string[]): Promise<void> => {
+  await invoke('reorder_model_rules', { ids });
+};
+
+/**
+ * Rule that would apply to a requested model, or null
+ */
+export const resolveModelRoute = async (model: string): Promise<ModelRule | null> => {
+  return await invoke<ModelRule | null>('resolve_model_route', { model });
+};
+
+export type BillingCycle = 'monthly' | 'yearly' | 'none';
+
+export interface Subscription {
+  provider_id: string;
+  tool: 'claude' | 'codex' | 'opencode';
+  plan_name: string;
+  monthly_cost?: number;
+  currency?: string;
+  /** Next renewal, YYYY-MM-DD */
+  renewal_date?: string;
+  billing_cycle: BillingCycle;
+  quota?: string;
+  remind_days_before: number;
+  note?: string;
+  reminded_for?: string;
+}
+
+export interface UpcomingRenewal {
+  provider_id: string;
+  plan_name: string;
+  renewal_date: string;
+  days_left: number;
+  monthly_cost?: number;
+  currency?: string;
+}
+
+export interface SubscriptionSummary {
+  count: number;
+  monthly_totals: { currency: string; monthly_cost: number }[];
+  upcoming: UpcomingRenewal[];
+}
+
+export const listSubscriptions = async (): Promise<Subscription[]> => {
+  return await invoke<Subscription[]>('list_subscriptions');
+};
+
+/**
+ * Create or replace the subscription of a provider
+ */
+export const saveSubscription = async (subscription: Subscription): Promise<Subscription> => {
+  return await invoke<Subscription>('save_subscription', { subscription });
+};
+
+export const deleteSubscription = async (providerId: string): Promise<void> => {
+  await invoke('delete_subscription', { providerId });
+};
+
+/**
+ * Monthly spend per currency and renewals in the next `days` days (default 30)
+ */
+export const getSubscriptionSummary = async (days?: number): Promise<SubscriptionSummary> => {
+  return await invoke<SubscriptionSummary>('get_subscription_summary', { days });
+};
+
+export type HopStatus = 'ok' | 'failed' | 'skipped';
+
+export interface DiagnosticHop {
+  /** "dns", "tcp", "proxy_connect", "tls" or "http" */
+  name: string;
+  status: HopStatus;
+  latency_ms?: number;
+  detail?: string;
+}
+
+export interface RouteReport {
+  via: 'direct' | 'proxy';
+  reachable: boolean;
+  hops: DiagnosticHop[];
+}
+
+export interface EndpointReport {
+  name: string;
+  url: string;
+  routes: RouteReport[];
+}
+
+export interface NetworkDiagnosticsReport {
+  /** Configured proxy without credentials */
+  proxy?: string;
+  endpoints: EndpointReport[];
+  created_at: string;
+}
+
+/**
+ * Hop-by-hop reachability of AI endpoints, direct and through the proxy
+ * @param urls - Replaces the default endpoints and configured providers
+ */
+export const runNetworkDiagnostics = async (urls?: string[]): Promise<NetworkDiagnosticsReport> => {
+  return await invoke<NetworkDiagnosticsReport>('network_diagnostics', { urls });
+};
+
+export interface CliDetection {
+  tool: string;
+  name: string;
+  /** Module suggested for enabling, if the app has one */
+  module?: string;
+  installed: boolean;
+  path?: string;
+  version?: string;
+  first_seen?: string;
+  last_checked: string;
+}
+
+export interface CliScanResult {
+  detections: CliDetection[];
+  /** CLIs that were not installed at the previous scan */
+  newly_installed: CliDetection[];
+}
+
+/**
+ * Scan for installed coding CLIs now
+ */
+export const scanInstalledClis = async (): Promise<CliScanResult> => {
+  return await invoke<CliScanResult>('scan_installed_clis');
+};
+
+/**
+ * Results of the last CLI scan, for the doctor report
+ */
+export const listCliDetections = async (): Promise<CliDetection[]> => {
+  return await invoke<CliDetection[]>('list_cli_detections');
+};
+
+/**
+ * Subscribe to newly installed CLIs to suggest enabling their modules
+ */
+export const onCliDetected = async (
+  handler: (clis: CliDetection[]) => void
+): Promise<UnlistenFn> => {
+  return await listen<CliDetection[]>('cli-detected', (event) => handler(event.payload));
+};
+
+export interface ActiveSession {
+  tool: 'claude' | 'opencode';
+  pid: number;
+}
+
+/**
+ * Running claude / opencode sessions that defer scheduled jobs
+ */
+export const getActiveCliSessions = async (): Promise<ActiveSession[]> => {
+  return await invoke<ActiveSession[]>('get_active_cli_sessions');
+};
+
+export interface BudgetStatus {
+  /** Usage provider key; absent for the budget covering all providers */
+  provider?: string;
+  /** USD per calendar month */
+  monthly_limit: number;
+  spent: number;
+  percent: number;
+  /** Highest threshold reached: 50, 80 or 100 */
+  threshold?: number;
+  /** YYYY-MM */
+  month: string;
+}
+
+/**
+ * Set the monthly budget (USD) of a usage provider, or of all providers when omitted
+ */
+export const saveBudget = async (monthlyLimit: number, provider?: string): Promise<void> => {
+  await invoke('save_budget', { provider, monthlyLimit });
+};
+
+export const deleteBudget = async (provider?: string): Promise<void> => {
+  await invoke('delete_budget', { provider });
+};
+
+/**
+ * This month's spend against every budget
+ * @param refresh - Rescan the tool logs first
+ */
+export const getBudgetStatus = async (refresh?: boolean): Promise<BudgetStatus[]> => {
+  return await invoke<BudgetStatus[]>('get_budget_status', { refresh });
+};
+
+export interface OnboardingSource {
+  /** "claude", "codex", "opencode" or "env" */
+  id: string;
+  name: string;
+  /** Config file or directory (empty for environment variables) */
+  path: string;
+  found: boolean;
+  provider_count: number;
+}
+
+export interface OnboardingScan {
+  /** Onboarding not finished yet and no provider stored */
+  first_launch: boolean;
+  sources: OnboardingSource[];
+  /** Dry-run report of importing everything found */
+  report: MigrationReport;
+}
+
+/**
+ * Existing CLI configs and environment variables found on this machine
+ */
+export const getOnboardingScan = async (): Promise<OnboardingScan> => {
+  return await invoke<OnboardingScan>('get_onboarding_scan');
+};
+
+/**
+ * Import every provider found by the onboarding scan and finish onboarding
+ */
+export const runOnboardingImport = async (): Promise<MigrationReport> => {
+  return await invoke<MigrationReport>('run_onboarding_import');
+};
+
+export const skipOnboarding = async (): Promise<void> => {
+  await invoke('skip_onboarding');
+};
+
+export interface ProviderUsageStats {
+  provider_id: string;
+  provider_name: string;
+  /** Times it was switched to */
+  switches: number;
+  /** Total time it was the applied provider */
+  active_seconds: number;
+  /** Average length of one activation */
+  average_seconds: number;
+  last_applied_at: string;
+  current: boolean;
+}
+
+export interface ToolSwitchStats {
+  /** e.g. 'claude', 'codex', 'gemini_cli' */
+  tool: string;
+  switches: number;
+  /** Most used (longest active) first */
+  providers: ProviderUsageStats[];
+}
+
+export interface SwitchAnalytics {
+  days: number;
+  total_switches: number;
+  switches_per_week: number;
+  tools: ToolSwitchStats[];
+}
+
+/**
+ * Provider switch frequency and time spent on each provider over the last `days` (default 30)
+ */
+export const getSwitchAnalytics = async (days?: number): Promise<SwitchAnalytics> => {
+  return await invoke<SwitchAnalytics>('get_switch_analytics', { days });
+};
+
+export type UsageRange = '7d' | '30d' | '90d' | 'all';
+
+export interface UsageBucket {
+  key: string;
+  requests: number;
+  input_tokens: number;
+  output_tokens: number;
+  cache_read_tokens: number;
+  cache_write_tokens: number;
+  total_tokens: number;
+  cost: number;
+}
+
+export interface UsageDashboard {
+  range: UsageRange;
+  start_date?: string;
+  totals: UsageBucket;
+  by_day: UsageBucket[];
+  by_model: UsageBucket[];
+  by_provider: UsageBucket[];
+  by_tool: UsageBucket[];
+  scan_errors: string[];
+}
+
+/**
+ * Scan Claude Code / OpenCode usage logs and get aggregated statistics
+ */
+export const getUsageDashboard = async (range: UsageRange): Promise<UsageDashboard> => {
+  return await invoke<UsageDashboard>('get_usage_dashboard', { range });
+};
+
+export interface TerminalInfo {
+  id: string;
+  name: string;
+  path: string;
+  is_default: boolean;
+}
+
+/**
+ * List terminal emulators installed on this machine
+ */
+export const listTerminals = async (): Promise<TerminalInfo[]> => {
+  return await invoke<TerminalInfo[]>('list_terminals');
+};
+
+/**
+ * Set the terminal used to launch coding CLIs (empty = first detected)
+ */
+export const setDefaultTerminal = async (terminalId: string): Promise<void> => {
+  await invoke('set_default_terminal', { terminalId });
+};
+
+export interface LocalApiStatus extends LocalApiConfig {
+  running: boolean;
+  error?: string;
+  /** Address to open on the phone in LAN mode */
+  lan_url?: string;
+  /** SHA-256 fingerprint of the self-signed certificate */
+  cert_fingerprint?: string;
+}
+
+/**
+ * Get local REST API settings and whether the server is running
+ */
+export const getLocalApiStatus = async (): Promise<LocalApiStatus> => {
+  return await invoke<LocalApiStatus>('get_local_api_status');
+};
+
+/**
+ * Enable/disable the local REST API, change its port, switch LAN mode or regenerate its token
+ */
+export const setLocalApiConfig = async (
+  enabled: boolean,
+  port?: number,
+  regenerateToken?: boolean,
+  lanEnabled?: boolean
+): Promise<LocalApiStatus> => {
+  return await invoke<LocalApiStatus>('set_local_api_config', {
+    enabled,
+    port,
+    regenerateToken,
+    lanEnabled,
+  });
+};
+
+/**
+ * Get the last log lines (default 200), oldest first
+ */
+export const getRecentLogs = async (lines?: number): Promise<string[]> => {
+  return await invoke<string[]>('get_recent_logs', { lines });
+};
+
+/**
+ * Open the logs folder in the system file manager
+ */
+export const openLogFolder = async (): Promise<void> => {
+  await invoke('open_log_folder');
+};
+
+export interface TelemetryPayload {
+  schema: number;
+  app_version: string;
+  os: string;
+  arch: string;
+  period_start: string;
+  period_end: string;
+  counters: Record<string, number>;
+}
+
+export interface TelemetryPreview {
+  enabled: boolean;
+  has_endpoint: boolean;
+  payloads: TelemetryPayload[];
+}
+
+/**
+ * Show exactly what telemetry would send (queued payloads and current counters)
+ */
+export const previewTelemetryPayload = async (): Promise<TelemetryPreview> => {
+  return await invoke<TelemetryPreview>('preview_telemetry_payload');
+};
+
+export interface ChangeSummary {
+  group_id: string;
+  /** Module that wrote the files, e.g. 'claude_code', 'mcp' */
+  scope: string;
+  files: string[];
+  timestamp: string;
+  undone: boolean;
+}
+
+export interface UndoResult {
+  scope: string;
+  files: string[];
+  timestamp: string;
+}
+
+/**
+ * Undo the most recent config file change (of one module, or of any module)
+ * Fails with a `conflict` error when a file was modified since
+ */
+export const undoLastChange = async (scope?: string): Promise<UndoResult> => {
+  return await invoke<UndoResult>('undo_last_change', { scope });
+};
+
+/**
+ * List recorded config file changes, newest first
+ */
+export const listChangeHistory = async (scope?: string, limit?: number): Promise<ChangeSummary[]> => {
+  return await invoke<ChangeSummary[]>('list_change_history', { scope, limit });
+};
+
+/** Change an apply command would make to one config file (returned with `dryRun`) */
+export interface ConfigDiff {
+  scope: string;
+  path: string;
+  /** File does not exist yet */
+  created: boolean;
+  before?: string | null;
+  after: string;
+  /** Unified diff of before -> after */
+  diff: string;
+}
+
+/**
+ * Config changes `mcpSyncAll` / `mcpSyncToTool` would make, without writing them
+ */
+export const mcpPreviewSync = async (toolKey?: string): Promise<ConfigDiff[]> => {
+  return await invoke<ConfigDiff[]>('mcp_preview_sync', { toolKey });
+};
+
+/**
+ * Show a test desktop notification (ignores the category toggles)
+ */
+export const sendTestNotification = async (): Promise<void> => {
+  await invoke('send_test_notification');
+};
+
+export interface ProviderImport {
+  tool: 'claude' | 'codex' | 'opencode';
+  name: string;
+  base_url: string;
+  api_key: string;
+  model?: string;
+  npm?: string;
+  website_url?: string;
+}
+
+export interface PendingProviderImport {
+  id: string;
+  source: string;
+  /** api_key is always empty here; see masked_api_key */
+  provider: ProviderImport;
+  masked_api_key: string;
+}
+
+/**
+ * Imports staged by aitoolbox:// links, waiting for confirmation
+ * New ones are also announced with the `provider-import-pending` event.
+ */
+export const listPendingProviderImports = async (): Promise<PendingProviderImport[]> => {
+  return await invoke<PendingProviderImport[]>('list_pending_provider_imports');
+};
+
+/**
+ * Confirm a staged import; returns the id of the created provider
+ */
+export const confirmProviderImport = async (id: string): Promise<string> => {
+  return await invoke<string>('confirm_provider_import', { id });
+};
+
+/**
+ * Discard a staged import
+ */
+export const discardProviderImport = async (id: string): Promise<void> => {
+  await invoke('discard_provider_import', { id });
+};
+
+export interface SecretFinding {
+  /** "source:$.json.path", "source:line:column" or "source:offset N" */
+  location: string;
+  kind: string;
+  /** Masked key */
+  preview: string;
+}
+
+export interface ProviderShare {
+  share_string: string;
+  qr_payload: string;
+  includes_api_key: boolean;
+  secret_findings: SecretFinding[];
+}
+
+/**
+ * Encode a provider into a share string / QR payload, optionally without its API key
+ * In "block" scan mode, keys found elsewhere in the payload fail with SECRETS_DETECTED
+ * unless `allowSecrets` is set.
+ */
+export const encodeProviderShare = async (
+  tool: ProviderImport['tool'],
+  providerId: string,
+  includeApiKey: boolean,
+  allowSecrets?: boolean
+): Promise<ProviderShare> => {
+  return await invoke<ProviderShare>('encode_provider_share', { tool, providerId, includeApiKey, allowSecrets });
+};
+
+/**
+ * Scan pasted text or JSON for API keys
+ */
+export const scanForSecrets = async (text: string): Promise<SecretFinding[]> => {
+  return await invoke<SecretFinding[]>('scan_for_secrets', { text });
+};
+
+/**
+ * Scan what a backup would contain right now
+ */
+export const previewBackupSecrets = async (): Promise<SecretFinding[]> => {
+  return await invoke<SecretFinding[]>('preview_backup_secrets');
+};
+
+/**
+ * Decode a share string and stage it for confirmation (see confirmProviderImport)
+ */
+export const importProviderShare = async (share: string): Promise<PendingProviderImport> => {
+  return await invoke<PendingProviderImport>('import_provider_share', { share });
+};
+
+/**
+ * Pull a base URL, API key and model out of pasted text and stage them for confirmation
+ * @param tool - Overrides the guessed tool ("claude" for Anthropic-style details, else "opencode")
+ */
+export const parseProviderFromText = async (
+  text: string,
+  tool?: ProviderImport['tool']
+): Promise<PendingProviderImport> => {
+  return await invoke<PendingProviderImport>('parse_provider_from_text', { text, tool });
+};
+
+export interface MigrationSource {
+  id: 'cc_switch' | 'claude_code_router';
+  name: string;
+  path: string;
+  found: boolean;
+}
+
+export interface MigrationItem {
+  /** Mapped provider (api_key is always empty) */
+  provider: Omit<ProviderImport, 'tool'> & { tool: string };
+  masked_api_key: string;
+  action: 'create' | 'update' | 'skip' | 'failed';
+  reason?: string;
+}
+
+export interface MigrationReport {
+  source: string;
+  path: string;
+  dry_run: boolean;
+  items: MigrationItem[];
+  warnings: string[];
+  imported: number;
+}
+
+/**
+ * Provider switcher apps (cc-switch, claude-code-router) that can be imported from
+ */
+export const detectMigrationSources = async (): Promise<MigrationSource[]> => {
+  return await invoke<MigrationSource[]>('detect_migration_sources');
+};
+
+/**
+ * Import providers from a switcher app; run with dryRun first to show the report
+ * @param path - Overrides the source's default data file
+ */
+export const importFromSwitcher = async (
+  source: MigrationSource['id'],
+  dryRun: boolean,
+  path?: string
+): Promise<MigrationReport> => {
+  return await invoke<MigrationReport>('import_from_switcher', { source, path, dryRun });
+};
+
+export type ChangeAction = 'created' | 'updated' | 'deleted' | 'applied' | 'imported' | 'synced';
+
+export interface ChangeEvent {
+  /** Changed entity, named after its table, e.g. "claude_provider", "settings" */
+  entity: string;
+  action: ChangeAction;
+  id?: string;
+  source: 'window' | 'tray';
+}
+
+/**
+ * Subscribe to typed change events emitted by every mutating command
+ */
+export const onEntityChanged = async (
+  handler: (event: ChangeEvent) => void
+): Promise<UnlistenFn> => {
+  return await listen<ChangeEvent>('entity-changed', (event) => handler(event.payload));
+};
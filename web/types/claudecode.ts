@@ -4,7 +4,26 @@
  * Type definitions for Claude Code configuration management.
  */
 
-export type ClaudeProviderCategory = 'official' | 'third_party' | 'custom';
+export type ClaudeProviderCategory = 'official' | 'third_party' | 'custom' | 'bedrock' | 'vertex';
+
+/**
+ * AWS Bedrock provider fields (category 'bedrock')
+ * Credentials come from the AWS CLI profile unless bearerToken is set
+ */
+export interface ClaudeBedrockConfig {
+  region: string; // AWS_REGION
+  profile?: string; // AWS_PROFILE
+  bearerToken?: string; // AWS_BEARER_TOKEN_BEDROCK (Bedrock API key)
+  smallFastModelRegion?: string; // ANTHROPIC_SMALL_FAST_MODEL_AWS_REGION
+}
+
+/**
+ * Google Vertex AI provider fields (category 'vertex')
+ */
+export interface ClaudeVertexConfig {
+  region: string; // CLOUD_ML_REGION, e.g. us-east5 or global
+  projectId: string; // ANTHROPIC_VERTEX_PROJECT_ID
+}
 
 /**
  * Claude Code Provider settings configuration
@@ -21,6 +40,18 @@ export interface ClaudeSettingsConfig {
   haikuModel?: string;
   sonnetModel?: string;
   opusModel?: string;
+  // Extra env vars merged into settings.json env on apply (e.g. API_TIMEOUT_MS);
+  // the fields above take precedence
+  extraEnv?: Record<string, string>;
+  // Proxy for this provider only, written as HTTPS_PROXY / HTTP_PROXY (+ NO_PROXY)
+  proxy?: string; // http://, https://, socks5:// or socks5h://; http:// when omitted
+  noProxy?: string; // Comma-separated hosts
+  // Overrides the global token limits for this provider
+  limits?: ClaudeTokenLimits;
+  // Cloud providers: written as CLAUDE_CODE_USE_BEDROCK / CLAUDE_CODE_USE_VERTEX
+  // env instead of ANTHROPIC_BASE_URL / ANTHROPIC_AUTH_TOKEN
+  bedrock?: ClaudeBedrockConfig;
+  vertex?: ClaudeVertexConfig;
 }
 
 /**
@@ -41,19 +72,194 @@ export interface ClaudeCodeProvider {
   sortIndex?: number;
   isApplied?: boolean;
   isDisabled?: boolean;
+  isArchived?: boolean; // Kept but hidden from the active list
   createdAt: string;
   updatedAt: string;
 }
 
+/**
+ * Filters and paging of listClaudeProviders
+ */
+export interface ClaudeProviderQuery {
+  category?: ClaudeProviderCategory;
+  search?: string; // Case-insensitive match on name or notes
+  archived?: boolean; // Only archived (true) or only active (false); overrides includeArchived
+  page?: number; // 1-based, used with limit
+  limit?: number;
+}
+
+/**
+ * Providers exported for sharing
+ * With `redacted`, keys in settingsConfig/notes are `${FIELD}` placeholders
+ */
+export interface ClaudeProviderBundle {
+  version: number;
+  exportedAt: string;
+  redacted: boolean;
+  providers: Omit<ClaudeCodeProvider, 'id' | 'isApplied' | 'isDisabled' | 'isArchived' | 'createdAt' | 'updatedAt'>[];
+}
+
+export interface ClaudeProviderImportResult {
+  created: number;
+  updated: number; // Providers matched by name
+  needsSecrets: string[]; // Names whose keys are still placeholders
+}
+
+export interface ClaudeBulkImportRow {
+  row: number; // 1-based position in the input (CSV line number for CSV)
+  name: string;
+  success: boolean;
+  id?: string;
+  error?: string;
+}
+
+export interface ClaudeBulkImportResult {
+  created: number;
+  failed: number;
+  rows: ClaudeBulkImportRow[];
+}
+
+/**
+ * Snapshot of a provider, taken when it is updated or applied
+ */
+export interface ClaudeProviderVersion {
+  providerId: string;
+  version: number;
+  name: string;
+  category: ClaudeProviderCategory;
+  settingsConfig: string;
+  websiteUrl?: string;
+  notes?: string;
+  icon?: string;
+  iconColor?: string;
+  reason: 'update' | 'apply' | 'restore';
+  createdAt: string;
+}
+
+/**
+ * Provider preset from the template gallery
+ */
+export interface ClaudeProviderTemplate {
+  id: string;
+  name: string;
+  category: ClaudeProviderCategory;
+  description?: string;
+  websiteUrl?: string;
+  baseUrl: string;
+  model?: string;
+  haikuModel?: string;
+  sonnetModel?: string;
+  opusModel?: string;
+}
+
+export interface ClaudeProviderTemplateList {
+  templates: ClaudeProviderTemplate[];
+  source: 'remote' | 'bundled';
+  error?: string; // Why the remote list could not be used
+}
+
+/**
+ * Health monitoring of the applied provider and the failover order
+ */
+export interface ClaudeFailoverConfig {
+  enabled: boolean;
+  intervalSecs: number; // Seconds between checks (at least 60)
+  failureThreshold: number; // Consecutive failed checks before switching
+  chain: string[]; // Provider ids in failover order, wraps around
+}
+
+export interface ClaudeProviderHealth {
+  providerId: string;
+  consecutiveFailures: number;
+  lastCheckedAt: string;
+  lastOkAt?: string;
+  lastError?: string;
+  latencyMs?: number;
+}
+
+/**
+ * Payload of the `claude-provider-failover` event
+ */
+export interface ClaudeFailoverEvent {
+  fromId: string;
+  fromName: string;
+  toId: string;
+  toName: string;
+  failures: number;
+  error: string;
+}
+
+/**
+ * Installation state of the `claude` binary
+ */
+export interface ClaudeCliStatus {
+  installed: boolean;
+  path?: string;
+  version?: string;
+  installMethod?: ClaudeCliInstallMethod | 'unknown';
+  latestVersion?: string; // Latest npm release; missing when the registry is unreachable
+  updateAvailable: boolean;
+  packageManagers: ClaudeCliInstallMethod[]; // Package managers found ("npm", "bun")
+}
+
+export type ClaudeCliInstallMethod = 'npm' | 'bun' | 'native';
+
+/**
+ * Payload of the `claude-cli-install-progress` event
+ */
+export interface ClaudeCliInstallProgress {
+  stream: 'stdout' | 'stderr';
+  line: string;
+}
+
+export type ClaudeShell = 'zsh' | 'bash' | 'powershell';
+
+/**
+ * Outcome of writing / removing the managed env block in one shell profile
+ */
+export interface ClaudeShellProfileResult {
+  shell: ClaudeShell;
+  path: string;
+  changed: boolean; // False when the profile already had this content
+}
+
+/**
+ * One settings.json key that differs from what the applied provider produces
+ */
+export interface ClaudeConfigDriftEntry {
+  key: string; // Dot path, e.g. `env.ANTHROPIC_BASE_URL`
+  kind: 'changed' | 'missing' | 'unexpected';
+  expected?: unknown; // Secret-looking values are masked
+  actual?: unknown;
+}
+
+export interface ClaudeConfigDrift {
+  providerId?: string; // Applied provider; absent when none is applied
+  providerName?: string;
+  fileExists: boolean;
+  inSync: boolean;
+  entries: ClaudeConfigDriftEntry[];
+}
+
 /**
  * Common configuration that applies to all providers
- * Stored as a single record in database
+ * Stored as a single record in database. A `$merge` object in `config` maps
+ * dot-separated key paths to how the provider's value is merged in, e.g.
+ * `{ "$merge": { "permissions.allow": "append" } }`.
  */
 export interface ClaudeCommonConfig {
   config: string; // JSON string like '{ "statusLine": {...}, "skipWebFetchPreflight": true }'
   updatedAt?: string;
 }
 
+/**
+ * deep: merge objects key by key (default for objects)
+ * replace: provider value wins (default for arrays and scalars)
+ * append: concatenate arrays without duplicates
+ * keep: common config value wins
+ */
+export type ClaudeMergeStrategy = 'deep' | 'replace' | 'append' | 'keep';
+
 /**
  * Claude Code settings.json file structure
  * Note: Due to #[serde(flatten)] in Rust, other fields are flattened at the top level
@@ -84,6 +290,12 @@ export interface ClaudeProviderFormValues {
   haikuModel?: string;
   sonnetModel?: string;
   opusModel?: string;
+  extraEnv?: Record<string, string>;
+  proxy?: string;
+  noProxy?: string;
+  limits?: ClaudeTokenLimits;
+  bedrock?: ClaudeBedrockConfig;
+  vertex?: ClaudeVertexConfig;
   notes?: string;
   isDisabled?: boolean;
   // For import from settings
@@ -127,6 +339,52 @@ export interface ImportConflictInfo {
   sourceProviderId: string;
 }
 
+/**
+ * settings.local.json in the Claude config directory
+ */
+export interface ClaudeLocalSettings {
+  path: string;
+  exists: boolean;
+  settings: Record<string, unknown>; // Empty object when the file is missing
+}
+
+/**
+ * Where applying a provider writes: settings.json, or merged into settings.local.json
+ */
+export type ClaudeApplyTarget = 'settings' | 'local';
+
+/**
+ * One provider apply and the settings file it wrote
+ */
+export interface ClaudeApplyHistoryEntry {
+  id: string;
+  providerId: string;
+  providerName: string;
+  target: ClaudeApplyTarget; // File written at the time
+  path: string;
+  settingsHash: string; // SHA-256 of the written file
+  source: 'window' | 'tray' | 'revert';
+  createdAt: string;
+}
+
+/**
+ * Token limits written to the settings.json env block; unset ones are left alone
+ * Global via saveClaudeTokenLimits, per provider via settingsConfig.limits
+ */
+export interface ClaudeTokenLimits {
+  maxOutputTokens?: number; // CLAUDE_CODE_MAX_OUTPUT_TOKENS
+  maxThinkingTokens?: number; // MAX_THINKING_TOKENS
+  maxMcpOutputTokens?: number; // MAX_MCP_OUTPUT_TOKENS
+}
+
+/**
+ * Resolved Claude config directory
+ */
+export interface ClaudeConfigDirInfo {
+  path: string;
+  source: 'custom' | 'env' | 'default'; // App settings, CLAUDE_CONFIG_DIR or ~/.claude
+}
+
 /**
  * Claude Plugin integration status
  */
@@ -134,3 +392,218 @@ export interface ClaudePluginStatus {
   enabled: boolean;       // Whether primaryApiKey = "any" is set
   hasConfigFile: boolean; // Whether ~/.claude/config.json exists
 }
+
+/**
+ * Result of a Claude provider connectivity test
+ */
+export interface ClaudeProviderTestResult {
+  success: boolean;
+  status?: number | null;   // HTTP status, null when no response was received
+  latencyMs: number;
+  model?: string | null;    // Model that answered
+  error?: string | null;
+}
+
+/**
+ * Latest batch test result of one provider, cached in the database
+ */
+export interface ClaudeProviderTestEntry extends ClaudeProviderTestResult {
+  providerId: string;
+  providerName: string;
+  testedAt: string;
+}
+
+/**
+ * Progress event of testAllClaudeProviders, one per finished provider
+ */
+export interface ClaudeProviderTestProgress {
+  done: number;
+  total: number;
+  entry: ClaudeProviderTestEntry;
+}
+
+/**
+ * Copy of ~/.claude/settings.json taken before a provider apply
+ */
+export interface ClaudeSettingsBackup {
+  id: string;        // e.g. "settings-20260301-101500-123"
+  path: string;
+  createdAt: string;
+  size: number;
+}
+
+/**
+ * One hook command (settings.json hooks.<event>[].hooks[])
+ */
+export interface ClaudeHookCommand {
+  type: 'command';
+  command: string;
+  timeout?: number;  // Seconds
+}
+
+/**
+ * Commands run for tools matching `matcher` (omitted for events without tools)
+ */
+export interface ClaudeHookMatcher {
+  matcher?: string;
+  hooks: ClaudeHookCommand[];
+}
+
+export type ClaudeHookEvent =
+  | 'PreToolUse'
+  | 'PostToolUse'
+  | 'Notification'
+  | 'UserPromptSubmit'
+  | 'Stop'
+  | 'SubagentStop'
+  | 'PreCompact'
+  | 'SessionStart'
+  | 'SessionEnd';
+
+/**
+ * Reusable hook set, merged into settings.json on apply while enabled
+ */
+export interface ClaudeHookSet {
+  id: string;
+  name: string;
+  description?: string;
+  enabled: boolean;
+  hooks: Partial<Record<ClaudeHookEvent, ClaudeHookMatcher[]>>;
+  sortIndex?: number;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface ClaudeHookSetInput {
+  name: string;
+  description?: string;
+  enabled: boolean;
+  hooks: Partial<Record<ClaudeHookEvent, ClaudeHookMatcher[]>>;
+  sortIndex?: number;
+}
+
+export type ClaudePermissionMode = 'default' | 'acceptEdits' | 'plan' | 'bypassPermissions';
+
+/**
+ * The `permissions` block of settings.json
+ */
+export interface ClaudePermissions {
+  allow?: string[]; // Tool rules like "Bash(npm run test:*)", "Read(~/.zshrc)", "WebFetch"
+  deny?: string[];
+  ask?: string[];
+  defaultMode?: ClaudePermissionMode;
+  additionalDirectories?: string[];
+}
+
+/**
+ * Reusable permission preset, merged into settings.json on apply while enabled
+ */
+export interface ClaudePermissionPreset {
+  id: string;
+  name: string;
+  description?: string;
+  enabled: boolean;
+  permissions: ClaudePermissions;
+  sortIndex?: number;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface ClaudePermissionPresetInput {
+  name: string;
+  description?: string;
+  enabled: boolean;
+  permissions: ClaudePermissions;
+  sortIndex?: number;
+}
+
+/**
+ * Command file under ~/.claude/commands
+ */
+export interface ClaudeSlashCommand {
+  name: string;            // Path without ".md", e.g. "git/review"
+  description?: string | null;
+  allowedTools?: string | null;
+  argumentHint?: string | null;
+  model?: string | null;
+  content: string;         // Prompt after the front matter
+  path: string;
+  updatedAt: string;
+}
+
+export interface ClaudeSlashCommandInput {
+  name: string;
+  description?: string | null;
+  allowedTools?: string | null;
+  argumentHint?: string | null;
+  model?: string | null;
+  content: string;
+}
+
+/**
+ * Reusable slash command stored in the database
+ */
+export interface ClaudeCommandTemplate {
+  id: string;
+  command: ClaudeSlashCommandInput;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface ClaudeCommandImportResult {
+  commands: number;
+  templates: number;
+  skipped: string[];      // Existing (without overwrite) or invalid names
+}
+
+/**
+ * Sub-agent definition file under ~/.claude/agents
+ */
+export interface ClaudeAgent {
+  name: string;
+  fileName: string;        // File name without ".md"
+  description: string;
+  tools: string[];         // Empty = all tools
+  model?: string | null;
+  prompt: string;          // System prompt after the front matter
+  path: string;
+  updatedAt: string;
+}
+
+export interface ClaudeAgentInput {
+  name: string;
+  description: string;
+  tools: string[];
+  model?: string | null;   // e.g. "sonnet", "opus", "inherit"
+  prompt: string;
+}
+
+/**
+ * Reusable agent; enabled presets are written to ~/.claude/agents on apply
+ */
+export interface ClaudeAgentPreset {
+  id: string;
+  agent: ClaudeAgentInput;
+  enabled: boolean;
+  createdAt: string;
+  updatedAt: string;
+}
+
+/**
+ * Session transcript under ~/.claude/projects
+ */
+export interface ClaudeSessionSummary {
+  sessionId: string;
+  title: string; // First user message, shortened
+  project?: string; // Working directory of the session
+  startedAt?: string;
+  updatedAt: string;
+  messageCount: number;
+}
+
+export type ClaudeSessionExportFormat = 'markdown' | 'html';
+
+export interface ClaudeSessionExport {
+  path: string;
+  messageCount: number;
+}
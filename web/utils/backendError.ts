@@ -0,0 +1,63 @@
+/**
+ * 后端错误解析
+ * 结构化错误（AppError）以对象 `{ kind, code, params, message }` 返回；
+ * 经过旧的 `Result<_, String>` 路径时为同结构的 JSON 字符串；其余错误仍是普通文本
+ */
+
+/** 错误类别，可据此分支处理（如 not_found 时提示新建） */
+export type AppErrorKind = 'not_found' | 'conflict' | 'io' | 'db' | 'http' | 'validation';
+
+export interface BackendError {
+  /** 错误类别，普通文本错误为 undefined */
+  kind?: AppErrorKind;
+  /** 错误码（如 UNSUPPORTED_TOOL），普通文本错误为 undefined */
+  code?: string;
+  params: Record<string, string>;
+  /** 已按当前语言渲染的消息 */
+  message: string;
+}
+
+function fromObject(value: unknown): BackendError | null {
+  if (!value || typeof value !== 'object') return null;
+  const obj = value as Record<string, unknown>;
+  if (typeof obj.code !== 'string' || typeof obj.message !== 'string') return null;
+  return {
+    kind: typeof obj.kind === 'string' ? (obj.kind as AppErrorKind) : undefined,
+    code: obj.code,
+    params: (obj.params as Record<string, string>) ?? {},
+    message: obj.message,
+  };
+}
+
+/**
+ * 解析 invoke 抛出的错误
+ * @param error - catch 到的错误
+ * @returns 结构化错误
+ */
+export function parseBackendError(error: unknown): BackendError {
+  const structured = fromObject(error);
+  if (structured) return structured;
+
+  const text = error instanceof Error ? error.message : String(error);
+  try {
+    const parsed = fromObject(JSON.parse(text));
+    if (parsed) return parsed;
+  } catch {
+    // 非 JSON：普通文本错误
+  }
+  return { params: {}, message: text };
+}
+
+/**
+ * 获取可直接展示的错误消息
+ */
+export function getErrorMessage(error: unknown): string {
+  return parseBackendError(error).message;
+}
+
+/**
+ * 是否为指定类别的错误
+ */
+export function isErrorKind(error: unknown, kind: AppErrorKind): boolean {
+  return parseBackendError(error).kind === kind;
+}